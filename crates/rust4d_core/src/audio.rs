@@ -0,0 +1,74 @@
+//! Spatial audio emitter data
+//!
+//! There is no audio backend in the engine; an [`AudioEmitter`] only records
+//! where on an entity a sound originates and how far it carries, so an audio
+//! system can query [`World::audio_emitters_near`](crate::World::audio_emitters_near).
+
+use rust4d_math::Vec4;
+use crate::EntityKey;
+
+/// A sound source attached to an entity
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AudioEmitter {
+    /// Emitter position relative to the entity (entity local space)
+    pub offset: Vec4,
+    /// 4D distance at which the sound fades to silence
+    pub max_distance: f32,
+}
+
+impl AudioEmitter {
+    /// Create an emitter at the entity origin
+    pub fn new(max_distance: f32) -> Self {
+        Self {
+            offset: Vec4::ZERO,
+            max_distance,
+        }
+    }
+
+    /// Set the local-space offset of the emitter
+    pub fn with_offset(mut self, offset: Vec4) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Attenuation factor for a listener at `distance`
+    ///
+    /// Falls off linearly from 1.0 at the emitter to 0.0 at `max_distance`.
+    pub fn attenuation(&self, distance: f32) -> f32 {
+        if self.max_distance <= 0.0 || distance >= self.max_distance {
+            return 0.0;
+        }
+        1.0 - distance / self.max_distance
+    }
+}
+
+/// An emitter found by a listener query
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EmitterSample {
+    /// The entity the emitter is attached to
+    pub entity: EntityKey,
+    /// Emitter position in world space
+    pub position: Vec4,
+    /// Attenuation factor in [0, 1] from the 4D listener distance
+    pub attenuation: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attenuation_falloff() {
+        let emitter = AudioEmitter::new(10.0);
+        assert_eq!(emitter.attenuation(0.0), 1.0);
+        assert!((emitter.attenuation(5.0) - 0.5).abs() < 1e-6);
+        assert_eq!(emitter.attenuation(10.0), 0.0);
+        assert_eq!(emitter.attenuation(20.0), 0.0);
+    }
+
+    #[test]
+    fn test_zero_range_is_silent() {
+        let emitter = AudioEmitter::new(0.0);
+        assert_eq!(emitter.attenuation(0.0), 0.0);
+    }
+}
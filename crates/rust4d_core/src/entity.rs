@@ -2,14 +2,18 @@
 //!
 //! An Entity represents an object in the 4D world with a transform, shape, and material.
 
-use std::collections::HashSet;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
 use std::sync::Arc;
 use bitflags::bitflags;
 use rust4d_math::ConvexShape4D;
-use rust4d_physics::BodyKey;
+use rust4d_physics::{BodyKey, PhysicsMaterial};
 use serde::{Serialize, Deserialize};
 use crate::Transform4D;
 use crate::shapes::ShapeTemplate;
+use crate::tag::TagId;
+use crate::audio::AudioEmitter;
+use crate::palette::lerp_color;
 
 bitflags! {
     /// Flags indicating which parts of an entity have changed and need updating
@@ -24,6 +28,11 @@ bitflags! {
         const TRANSFORM = 1 << 0;
         /// Mesh/shape has changed
         const MESH = 1 << 1;
+        /// Shape was replaced or edited; its tetrahedra must be regenerated
+        ///
+        /// Alias of [`MESH`](Self::MESH). Unlike `TRANSFORM`, which only moves
+        /// existing vertices, this forces the geometry build to re-tetrahedralize.
+        const SHAPE = Self::MESH.bits();
         /// Material has changed
         const MATERIAL = 1 << 2;
         /// All flags set - entity needs full rebuild
@@ -31,28 +40,54 @@ bitflags! {
     }
 }
 
-/// A simple material with just a base color
+/// A simple material with a base color, a shading id and a lighting response
 ///
 /// This is minimal for now - can be extended with PBR properties later.
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct Material {
     /// Base color as RGBA (each component 0.0-1.0)
+    #[serde(serialize_with = "rust4d_math::float_format::array4::serialize")]
     pub base_color: [f32; 4],
+    /// Shading pipeline id (see [`Material::LIT_ID`] and [`Material::UNLIT_ID`])
+    #[serde(default)]
+    pub material_id: u16,
+    /// Multiplier on the renderer's ambient light strength
+    #[serde(default = "default_light_scale")]
+    pub ambient: f32,
+    /// Multiplier on the renderer's diffuse light strength
+    #[serde(default = "default_light_scale")]
+    pub diffuse: f32,
+}
+
+fn default_light_scale() -> f32 {
+    1.0
 }
 
 impl Default for Material {
     fn default() -> Self {
         Self {
             base_color: [1.0, 1.0, 1.0, 1.0], // White
+            material_id: Self::LIT_ID,
+            ambient: 1.0,
+            diffuse: 1.0,
         }
     }
 }
 
 impl Material {
+    /// Built-in material id: diffuse-lit shading (the default)
+    pub const LIT_ID: u16 = 0;
+
+    /// Built-in material id: unlit shading (flat color, no lighting)
+    pub const UNLIT_ID: u16 = 1;
+
     /// Create a new material with the given RGBA color
     pub fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
         Self {
             base_color: [r, g, b, a],
+            material_id: Self::LIT_ID,
+            ambient: 1.0,
+            diffuse: 1.0,
         }
     }
 
@@ -61,20 +96,55 @@ impl Material {
         Self::new(r, g, b, 1.0)
     }
 
+    /// Interpolate towards another material
+    ///
+    /// The base color and light multipliers blend linearly; the shading id is
+    /// discrete and switches to `other`'s at the halfway point.
+    pub fn lerp(&self, other: &Material, t: f32) -> Material {
+        Material {
+            base_color: lerp_color(self.base_color, other.base_color, t),
+            material_id: if t < 0.5 { self.material_id } else { other.material_id },
+            ambient: self.ambient + (other.ambient - self.ambient) * t,
+            diffuse: self.diffuse + (other.diffuse - self.diffuse) * t,
+        }
+    }
+
+    /// Check if this material is see-through (alpha below 1)
+    ///
+    /// Transparent materials are drawn after opaque ones, blended back to front.
+    pub fn is_transparent(&self) -> bool {
+        self.base_color[3] < 1.0
+    }
+
+    /// Set the shading pipeline id for this material
+    pub fn with_material_id(mut self, material_id: u16) -> Self {
+        self.material_id = material_id;
+        self
+    }
+
+    /// Set how strongly this material responds to ambient and diffuse light
+    ///
+    /// Both are multipliers on the renderer's global light strengths.
+    pub fn with_lighting(mut self, ambient: f32, diffuse: f32) -> Self {
+        self.ambient = ambient;
+        self.diffuse = diffuse;
+        self
+    }
+
     /// White material
-    pub const WHITE: Self = Self { base_color: [1.0, 1.0, 1.0, 1.0] };
+    pub const WHITE: Self = Self { base_color: [1.0, 1.0, 1.0, 1.0], material_id: Self::LIT_ID, ambient: 1.0, diffuse: 1.0 };
 
     /// Gray material
-    pub const GRAY: Self = Self { base_color: [0.5, 0.5, 0.5, 1.0] };
+    pub const GRAY: Self = Self { base_color: [0.5, 0.5, 0.5, 1.0], material_id: Self::LIT_ID, ambient: 1.0, diffuse: 1.0 };
 
     /// Red material
-    pub const RED: Self = Self { base_color: [1.0, 0.0, 0.0, 1.0] };
+    pub const RED: Self = Self { base_color: [1.0, 0.0, 0.0, 1.0], material_id: Self::LIT_ID, ambient: 1.0, diffuse: 1.0 };
 
     /// Green material
-    pub const GREEN: Self = Self { base_color: [0.0, 1.0, 0.0, 1.0] };
+    pub const GREEN: Self = Self { base_color: [0.0, 1.0, 0.0, 1.0], material_id: Self::LIT_ID, ambient: 1.0, diffuse: 1.0 };
 
     /// Blue material
-    pub const BLUE: Self = Self { base_color: [0.0, 0.0, 1.0, 1.0] };
+    pub const BLUE: Self = Self { base_color: [0.0, 0.0, 1.0, 1.0], material_id: Self::LIT_ID, ambient: 1.0, diffuse: 1.0 };
 }
 
 /// Reference to a shape - either shared (Arc) or owned (Box)
@@ -116,23 +186,63 @@ impl ShapeRef {
 /// - A transform (position, rotation, scale)
 /// - A shape (the geometry)
 /// - A material (visual properties)
+/// - An optional palette color name (recolored by palettes)
 /// - An optional physics body key (links to PhysicsWorld)
+/// - An optional physics material override for its body
+/// - An optional W coordinate it is pinned to (see [`Entity::w_lock`])
 /// - Dirty flags (for change tracking)
+/// - The world frame it was last modified on
+/// - Any number of user components, one per type (see [`Entity::insert_component`])
 pub struct Entity {
     /// Optional name for this entity (for lookup)
+    ///
+    /// Once the entity is in a [`World`](crate::World), rename it with
+    /// [`World::rename_entity`](crate::World::rename_entity); assigning this
+    /// field directly leaves the world's name index stale.
     pub name: Option<String>,
-    /// Tags for categorization (e.g., "dynamic", "static", "enemy")
-    pub tags: HashSet<String>,
+    /// Interned tags for categorization (e.g., "dynamic", "static", "enemy")
+    ///
+    /// Read them with [`has_tag`](Self::has_tag) and
+    /// [`tag_names`](Self::tag_names), and change them with
+    /// [`add_tag`](Self::add_tag) and [`remove_tag`](Self::remove_tag).
+    pub(crate) tags: Vec<TagId>,
     /// The entity's transform in world space
     pub transform: Transform4D,
     /// The entity's shape
     pub shape: ShapeRef,
+    /// Template the shape was created from, needed to save the entity to a scene
+    ///
+    /// Set by [`EntityTemplate::to_entity`] and cleared by [`set_shape`](Self::set_shape).
+    pub shape_template: Option<ShapeTemplate>,
     /// The entity's material
     pub material: Material,
+    /// Palette color this entity takes its base color from
+    pub palette_color: Option<String>,
     /// Optional physics body key (links to PhysicsWorld)
     pub physics_body: Option<BodyKey>,
+    /// Physics material for this entity's body, overriding the collider default
+    pub physics_material: Option<PhysicsMaterial>,
+    /// W coordinate this entity is pinned to, if any (a "dimensional lock")
+    ///
+    /// [`World::update`](crate::World::update) copies it to the entity's
+    /// physics body whenever it changes, so the body keeps its X, Y and Z
+    /// motion but never leaves this W (see `RigidBody4D::w_lock`); an entity
+    /// without a body is moved back to it. Set it to `None` to restore full
+    /// 4D motion. While it stays `None`, a lock set on the body directly is
+    /// left alone.
+    pub w_lock: Option<f32>,
+    /// The `w_lock` last copied to the physics body
+    pub(crate) applied_w_lock: Option<f32>,
+    /// Optional spatial sound source
+    pub audio_emitter: Option<AudioEmitter>,
+    /// Remaining lifetime in seconds; the world despawns the entity at zero
+    pub lifetime: Option<f32>,
     /// Dirty flags for change tracking (what needs rebuilding)
     dirty: DirtyFlags,
+    /// World frame on which this entity was last seen dirty
+    modified_frame: u64,
+    /// User components by type, allocated on first insert
+    components: Option<HashMap<TypeId, Box<dyn Any + Send + Sync>>>,
 }
 
 impl Entity {
@@ -140,12 +250,21 @@ impl Entity {
     pub fn new(shape: ShapeRef) -> Self {
         Self {
             name: None,
-            tags: HashSet::new(),
+            tags: Vec::new(),
             transform: Transform4D::identity(),
             shape,
+            shape_template: None,
             material: Material::default(),
+            palette_color: None,
             physics_body: None,
+            physics_material: None,
+            w_lock: None,
+            applied_w_lock: None,
+            audio_emitter: None,
+            lifetime: None,
             dirty: DirtyFlags::ALL, // New entities are dirty
+            modified_frame: 0,
+            components: None,
         }
     }
 
@@ -153,12 +272,21 @@ impl Entity {
     pub fn with_material(shape: ShapeRef, material: Material) -> Self {
         Self {
             name: None,
-            tags: HashSet::new(),
+            tags: Vec::new(),
             transform: Transform4D::identity(),
             shape,
+            shape_template: None,
             material,
+            palette_color: None,
             physics_body: None,
+            physics_material: None,
+            w_lock: None,
+            applied_w_lock: None,
+            audio_emitter: None,
+            lifetime: None,
             dirty: DirtyFlags::ALL, // New entities are dirty
+            modified_frame: 0,
+            components: None,
         }
     }
 
@@ -166,12 +294,21 @@ impl Entity {
     pub fn with_transform(shape: ShapeRef, transform: Transform4D, material: Material) -> Self {
         Self {
             name: None,
-            tags: HashSet::new(),
+            tags: Vec::new(),
             transform,
             shape,
+            shape_template: None,
             material,
+            palette_color: None,
             physics_body: None,
+            physics_material: None,
+            w_lock: None,
+            applied_w_lock: None,
+            audio_emitter: None,
+            lifetime: None,
             dirty: DirtyFlags::ALL, // New entities are dirty
+            modified_frame: 0,
+            components: None,
         }
     }
 
@@ -182,22 +319,73 @@ impl Entity {
     }
 
     /// Add a tag to this entity
-    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
-        self.tags.insert(tag.into());
-        self
+    pub fn with_tag(self, tag: impl Into<String>) -> Self {
+        self.with_tag_id(TagId::intern(&tag.into()))
     }
 
     /// Add multiple tags to this entity
     pub fn with_tags(mut self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
         for tag in tags {
-            self.tags.insert(tag.into());
+            self = self.with_tag(tag);
         }
         self
     }
 
+    /// Add an already-interned tag to this entity
+    pub(crate) fn with_tag_id(mut self, id: TagId) -> Self {
+        if !self.tags.contains(&id) {
+            self.tags.push(id);
+        }
+        self
+    }
+
+    /// Add a tag to this entity in place
+    ///
+    /// Returns false if the entity already had it.
+    pub fn add_tag(&mut self, tag: &str) -> bool {
+        let id = TagId::intern(tag);
+        if self.tags.contains(&id) {
+            return false;
+        }
+        self.tags.push(id);
+        true
+    }
+
+    /// Remove a tag from this entity
+    ///
+    /// Returns false if the entity didn't have it.
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let Some(index) = TagId::lookup(tag).and_then(|id| self.tags.iter().position(|&t| t == id)) else {
+            return false;
+        };
+        self.tags.remove(index);
+        true
+    }
+
     /// Check if this entity has a specific tag
+    ///
+    /// Looks the tag up in the shared registry on every call; when testing
+    /// many entities, resolve it once with [`TagId::lookup`] and use
+    /// [`has_tag_id`](Self::has_tag_id).
     pub fn has_tag(&self, tag: &str) -> bool {
-        self.tags.contains(tag)
+        TagId::lookup(tag).is_some_and(|id| self.has_tag_id(id))
+    }
+
+    /// Check if this entity has a specific interned tag, without locking
+    #[inline]
+    pub fn has_tag_id(&self, id: TagId) -> bool {
+        self.tags.contains(&id)
+    }
+
+    /// Get the tag strings of this entity
+    pub fn tag_names(&self) -> Vec<String> {
+        self.tags.iter().filter_map(|id| id.name()).collect()
+    }
+
+    /// Take the base color from a named palette color
+    pub fn with_palette_color(mut self, name: impl Into<String>) -> Self {
+        self.palette_color = Some(name.into());
+        self
     }
 
     /// Attach a physics body to this entity
@@ -206,11 +394,80 @@ impl Entity {
         self
     }
 
+    /// Override the physics material used for this entity's body
+    pub fn with_physics_material(mut self, material: PhysicsMaterial) -> Self {
+        self.physics_material = Some(material);
+        self
+    }
+
+    /// Pin this entity to a W coordinate (see [`w_lock`](Self::w_lock))
+    pub fn with_w_lock(mut self, w: f32) -> Self {
+        self.w_lock = Some(w);
+        self
+    }
+
+    /// Attach an audio emitter to this entity
+    pub fn with_audio_emitter(mut self, emitter: AudioEmitter) -> Self {
+        self.audio_emitter = Some(emitter);
+        self
+    }
+
+    /// Despawn this entity (and its children) after `seconds` of world updates
+    pub fn with_lifetime(mut self, seconds: f32) -> Self {
+        self.lifetime = Some(seconds);
+        self
+    }
+
+    /// Attach a component to this entity (see [`insert_component`](Self::insert_component))
+    pub fn with_component<T: Any + Send + Sync>(mut self, component: T) -> Self {
+        self.insert_component(component);
+        self
+    }
+
     /// Get the shape of this entity
     pub fn shape(&self) -> &dyn ConvexShape4D {
         self.shape.as_shape()
     }
 
+    // --- Component methods ---
+
+    /// Attach a component, returning the one of the same type it replaces
+    ///
+    /// Components are arbitrary user data (health, AI state, ...) keyed by
+    /// type, so an entity holds at most one of each type.
+    pub fn insert_component<T: Any + Send + Sync>(&mut self, component: T) -> Option<T> {
+        self.components
+            .get_or_insert_with(HashMap::new)
+            .insert(TypeId::of::<T>(), Box::new(component))
+            .and_then(|old| old.downcast().ok())
+            .map(|old| *old)
+    }
+
+    /// Get a component by type
+    pub fn get_component<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.components.as_ref()?.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    /// Get a mutable component by type
+    pub fn get_component_mut<T: Any + Send + Sync>(&mut self) -> Option<&mut T> {
+        self.components.as_mut()?.get_mut(&TypeId::of::<T>())?.downcast_mut()
+    }
+
+    /// Check if this entity has a component of type `T`
+    pub fn has_component<T: Any + Send + Sync>(&self) -> bool {
+        self.components.as_ref().is_some_and(|c| c.contains_key(&TypeId::of::<T>()))
+    }
+
+    /// Detach and return a component by type
+    pub fn remove_component<T: Any + Send + Sync>(&mut self) -> Option<T> {
+        let components = self.components.as_mut()?;
+        let component = components.remove(&TypeId::of::<T>())?;
+        if components.is_empty() {
+            self.components = None;
+        }
+        component.downcast().ok().map(|c| *c)
+    }
+
     // --- Dirty tracking methods ---
 
     /// Check if this entity has any dirty flags set
@@ -237,6 +494,21 @@ impl Entity {
         self.dirty = DirtyFlags::NONE;
     }
 
+    /// Get the world frame on which this entity was last modified
+    ///
+    /// Stamped by [`World`](crate::World) when the entity is added and on each
+    /// `update` where the entity is dirty.
+    #[inline]
+    pub fn modified_frame(&self) -> u64 {
+        self.modified_frame
+    }
+
+    /// Record the frame on which this entity was modified
+    #[inline]
+    pub(crate) fn set_modified_frame(&mut self, frame: u64) {
+        self.modified_frame = frame;
+    }
+
     /// Set the position and mark the transform as dirty
     pub fn set_position(&mut self, position: rust4d_math::Vec4) {
         self.transform.position = position;
@@ -254,6 +526,17 @@ impl Entity {
         self.material = material;
         self.mark_dirty(DirtyFlags::MATERIAL);
     }
+
+    /// Replace the shape and mark it as dirty
+    ///
+    /// Sets [`DirtyFlags::SHAPE`], so the entity's geometry is rebuilt from
+    /// the new shape's tetrahedra rather than just re-transformed. The shape
+    /// template is cleared; set it again if the new shape has one.
+    pub fn set_shape(&mut self, shape: ShapeRef) {
+        self.shape = shape;
+        self.shape_template = None;
+        self.mark_dirty(DirtyFlags::SHAPE);
+    }
 }
 
 /// A serializable entity template
@@ -267,12 +550,31 @@ pub struct EntityTemplate {
     /// Tags for categorization (e.g., "dynamic", "static")
     #[serde(default)]
     pub tags: Vec<String>,
-    /// The entity's transform in world space
+    /// Name of the entity this one is a child of, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// The entity's transform (relative to its parent, if it has one)
     pub transform: Transform4D,
     /// The entity's shape template (serializable)
     pub shape: ShapeTemplate,
     /// The entity's material
     pub material: Material,
+    /// Palette color this entity takes its base color from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub palette_color: Option<String>,
+    /// Velocity [x, y, z, w] given to the entity's physics body when instantiated
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "rust4d_math::float_format::option_array4::serialize"
+    )]
+    pub initial_velocity: Option<[f32; 4]>,
+    /// Physics material for the entity's body, overriding the collider default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub physics_material: Option<PhysicsMaterial>,
+    /// W coordinate the entity is pinned to (see [`Entity::w_lock`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub w_lock: Option<f32>,
 }
 
 impl EntityTemplate {
@@ -281,9 +583,14 @@ impl EntityTemplate {
         Self {
             name: None,
             tags: Vec::new(),
+            parent: None,
             transform,
             shape,
             material,
+            palette_color: None,
+            initial_velocity: None,
+            physics_material: None,
+            w_lock: None,
         }
     }
 
@@ -299,6 +606,36 @@ impl EntityTemplate {
         self
     }
 
+    /// Make this entity a child of the named entity when instantiated
+    pub fn with_parent(mut self, name: impl Into<String>) -> Self {
+        self.parent = Some(name.into());
+        self
+    }
+
+    /// Take the base color from a named palette color
+    pub fn with_palette_color(mut self, name: impl Into<String>) -> Self {
+        self.palette_color = Some(name.into());
+        self
+    }
+
+    /// Give the entity's physics body an initial velocity
+    pub fn with_initial_velocity(mut self, x: f32, y: f32, z: f32, w: f32) -> Self {
+        self.initial_velocity = Some([x, y, z, w]);
+        self
+    }
+
+    /// Override the physics material used for the entity's body
+    pub fn with_physics_material(mut self, material: PhysicsMaterial) -> Self {
+        self.physics_material = Some(material);
+        self
+    }
+
+    /// Pin the entity to a W coordinate (see [`Entity::w_lock`])
+    pub fn with_w_lock(mut self, w: f32) -> Self {
+        self.w_lock = Some(w);
+        self
+    }
+
     /// Convert this template to an Entity
     pub fn to_entity(&self) -> Entity {
         let shape = self.shape.create_shape();
@@ -313,6 +650,10 @@ impl EntityTemplate {
         for tag in &self.tags {
             entity = entity.with_tag(tag.clone());
         }
+        entity.shape_template = Some(self.shape.clone());
+        entity.palette_color = self.palette_color.clone();
+        entity.physics_material = self.physics_material;
+        entity.w_lock = self.w_lock;
         entity
     }
 }
@@ -334,12 +675,50 @@ mod tests {
         assert_eq!(m.base_color, [0.5, 0.6, 0.7, 0.8]);
     }
 
+    #[test]
+    fn test_material_is_transparent() {
+        assert!(!Material::WHITE.is_transparent());
+        assert!(Material::new(1.0, 1.0, 1.0, 0.5).is_transparent());
+    }
+
+    #[test]
+    fn test_material_lerp_halfway_averages() {
+        let a = Material::new(0.0, 0.25, 1.0, 1.0);
+        let b = Material::new(1.0, 0.75, 0.0, 0.5).with_material_id(Material::UNLIT_ID);
+
+        let mid = a.lerp(&b, 0.5);
+        assert_eq!(mid.base_color, [0.5, 0.5, 0.5, 0.75]);
+        assert_eq!(mid.material_id, Material::UNLIT_ID);
+        assert_eq!(a.lerp(&b, 0.0).base_color, a.base_color);
+        assert_eq!(a.lerp(&b, 0.25).material_id, Material::LIT_ID);
+    }
+
     #[test]
     fn test_material_from_rgb() {
         let m = Material::from_rgb(0.5, 0.6, 0.7);
         assert_eq!(m.base_color, [0.5, 0.6, 0.7, 1.0]);
     }
 
+    #[test]
+    fn test_material_id_defaults_to_lit() {
+        assert_eq!(Material::default().material_id, Material::LIT_ID);
+        assert_eq!(Material::from_rgb(0.1, 0.2, 0.3).material_id, Material::LIT_ID);
+        assert_eq!(Material::RED.material_id, Material::LIT_ID);
+    }
+
+    #[test]
+    fn test_material_with_material_id() {
+        let m = Material::GREEN.with_material_id(Material::UNLIT_ID);
+        assert_eq!(m.material_id, Material::UNLIT_ID);
+        assert_eq!(m.base_color, [0.0, 1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_material_id_missing_in_ron_defaults_to_lit() {
+        let m: Material = ron::from_str("(base_color: (1.0, 0.0, 0.0, 1.0))").unwrap();
+        assert_eq!(m.material_id, Material::LIT_ID);
+    }
+
     #[test]
     fn test_shape_ref_shared() {
         let tesseract = Tesseract4D::new(2.0);
@@ -400,6 +779,46 @@ mod tests {
         assert_eq!(entity.material.base_color, [0.0, 0.0, 1.0, 1.0]);
     }
 
+    // --- Tag tests ---
+
+    #[test]
+    fn test_entity_has_tag_via_id() {
+        let entity = Entity::new(ShapeRef::shared(Tesseract4D::new(2.0)))
+            .with_tag("enemy")
+            .with_tags(["flying", "enemy"]);
+
+        // Duplicate tags are stored once
+        assert_eq!(entity.tags.len(), 2);
+        assert!(entity.has_tag("enemy"));
+        assert!(entity.has_tag_id(TagId::intern("flying")));
+        assert!(!entity.has_tag("friendly"));
+
+        let mut names = entity.tag_names();
+        names.sort();
+        assert_eq!(names, vec!["enemy".to_string(), "flying".to_string()]);
+    }
+
+    #[test]
+    fn test_add_and_remove_tag() {
+        let mut entity = Entity::new(ShapeRef::shared(Tesseract4D::new(2.0))).with_tag("enemy");
+
+        assert!(entity.add_tag("boss"));
+        assert!(!entity.add_tag("boss"));
+        assert!(entity.has_tag("boss"));
+
+        assert!(entity.remove_tag("enemy"));
+        assert!(!entity.remove_tag("enemy"));
+        assert!(!entity.remove_tag("tag_test_never_added"));
+        assert_eq!(entity.tag_names(), vec!["boss".to_string()]);
+    }
+
+    #[test]
+    fn test_entity_with_tag_id_matches_string_tag() {
+        let id = TagId::intern("pickup");
+        let entity = Entity::new(ShapeRef::shared(Tesseract4D::new(2.0))).with_tag_id(id);
+        assert!(entity.has_tag("pickup"));
+    }
+
     // --- Dirty tracking tests ---
 
     #[test]
@@ -500,6 +919,28 @@ mod tests {
         assert_eq!(entity.material.base_color, [1.0, 0.0, 0.0, 1.0]);
     }
 
+    #[test]
+    fn test_set_shape_marks_shape_dirty() {
+        let mut entity = Entity::new(ShapeRef::owned(Tesseract4D::new(2.0)));
+        entity.clear_dirty();
+
+        entity.set_shape(ShapeRef::owned(Tesseract4D::new(4.0)));
+
+        assert!(entity.dirty_flags().contains(DirtyFlags::SHAPE));
+        assert!(!entity.dirty_flags().contains(DirtyFlags::TRANSFORM));
+        assert_eq!(entity.shape().vertices()[0].x.abs(), 2.0);
+    }
+
+    #[test]
+    fn test_transform_change_does_not_mark_shape() {
+        let mut entity = Entity::new(ShapeRef::owned(Tesseract4D::new(2.0)));
+        entity.clear_dirty();
+
+        entity.set_position(Vec4::new(1.0, 0.0, 0.0, 0.0));
+
+        assert!(!entity.dirty_flags().contains(DirtyFlags::SHAPE));
+    }
+
     #[test]
     fn test_mark_dirty_combines_flags() {
         let tesseract = Tesseract4D::new(2.0);
@@ -515,4 +956,25 @@ mod tests {
         assert!(flags.contains(DirtyFlags::MATERIAL));
         assert!(!flags.contains(DirtyFlags::MESH));
     }
+
+    #[derive(Debug, PartialEq)]
+    struct Health(u32);
+
+    #[test]
+    fn test_components_insert_get_remove() {
+        let mut entity = Entity::new(ShapeRef::owned(Tesseract4D::new(2.0)));
+        assert!(entity.get_component::<Health>().is_none());
+
+        assert_eq!(entity.insert_component(Health(100)), None);
+        assert_eq!(entity.get_component::<Health>(), Some(&Health(100)));
+        entity.get_component_mut::<Health>().unwrap().0 -= 30;
+        assert_eq!(entity.insert_component(Health(50)), Some(Health(70)));
+
+        // Other types are stored separately
+        let mut entity = entity.with_component("idle");
+        assert_eq!(entity.get_component::<&str>(), Some(&"idle"));
+        assert_eq!(entity.remove_component::<Health>(), Some(Health(50)));
+        assert!(!entity.has_component::<Health>());
+        assert!(entity.has_component::<&str>());
+    }
 }
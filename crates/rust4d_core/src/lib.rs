@@ -4,15 +4,16 @@
 //!
 //! - [`Transform4D`] - Position, rotation, and scale in 4D space
 //! - [`Material`] - Visual properties of an entity
+//! - [`Palette`] - Named colors for theming entity materials
 //! - [`Entity`] - An object in the world with transform, shape, and material
 //! - [`ShapeRef`] - Reference to a shape (shared or owned)
 //! - [`World`] - Container for all entities
 //! - [`EntityKey`] - Generational key to an entity in the world
+//! - [`TagId`] - Interned entity tag, for lock-free tag tests
 //! - [`ShapeTemplate`] - Serializable shape template
 //! - [`EntityTemplate`] - Serializable entity template
 //! - [`Scene`] - Loadable/saveable scene containing entities
 
-mod transform;
 mod entity;
 mod world;
 mod shapes;
@@ -23,22 +24,29 @@ mod asset_cache;
 mod scene_transition;
 mod scene_loader;
 mod scene_validator;
+mod tag;
+mod audio;
+mod palette;
+mod state_hash;
 
-pub use transform::Transform4D;
 pub use entity::{Material, Entity, ShapeRef, DirtyFlags, EntityTemplate};
-pub use world::{World, EntityKey, HierarchyError};
+pub use world::{World, EntityKey, HierarchyError, DespawnEvent, DirtyBatch, InspectQuery};
 pub use shapes::ShapeTemplate;
-pub use scene::{Scene, SceneLoadError, SceneSaveError, SceneError, ActiveScene};
+pub use scene::{Scene, SceneLoadError, SceneSaveError, SceneError, ActiveScene, MergePolicy, MergeReport};
 pub use scene_manager::SceneManager;
 pub use asset_error::AssetError;
 pub use asset_cache::{AssetId, AssetHandle, Asset, AssetCache};
 pub use scene_transition::{SceneTransition, TransitionEffect, SlideDirection};
 pub use scene_loader::{SceneLoader, LoadResult};
 pub use scene_validator::{SceneValidator, ValidationError};
+pub use audio::{AudioEmitter, EmitterSample};
+pub use palette::Palette;
+pub use tag::TagId;
+pub use rust4d_math::float_format::{canonical_f32, SCENE_FLOAT_DECIMALS};
 
 // Re-export commonly used types from rust4d_math for convenience
-pub use rust4d_math::{Vec4, Rotor4, RotationPlane, ConvexShape4D, Tetrahedron};
-pub use rust4d_math::{Tesseract4D, Hyperplane4D};
+pub use rust4d_math::{Vec4, Rotor4, RotationPlane, ConvexShape4D, Tetrahedron, Transform4D};
+pub use rust4d_math::{Tesseract4D, Hyperplane4D, SixteenCell4D, Hypersphere4D};
 
 // Re-export physics types for convenient access through rust4d_core
 pub use rust4d_physics::{BodyKey, PhysicsConfig, PhysicsWorld, RigidBody4D, StaticCollider};
@@ -0,0 +1,97 @@
+//! Named color palettes for theme-able scenes
+//!
+//! A [`Palette`] maps semantic names ("floor", "team_red", "accent") to
+//! colors. Entities opt in by naming a palette color, and
+//! [`World::apply_palette`](crate::World::apply_palette) recolors them all at
+//! once. Blending two palettes with [`Palette::lerp`] and applying the result
+//! each frame gives smooth day/night or team-color transitions.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// A set of named RGBA colors
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Palette {
+    /// Colors by semantic name (each component 0.0-1.0)
+    pub colors: BTreeMap<String, [f32; 4]>,
+}
+
+impl Palette {
+    /// Create an empty palette
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace a named color
+    pub fn with_color(mut self, name: impl Into<String>, color: [f32; 4]) -> Self {
+        self.set(name, color);
+        self
+    }
+
+    /// Add or replace a named color
+    pub fn set(&mut self, name: impl Into<String>, color: [f32; 4]) {
+        self.colors.insert(name.into(), color);
+    }
+
+    /// Look up a named color
+    pub fn get(&self, name: &str) -> Option<[f32; 4]> {
+        self.colors.get(name).copied()
+    }
+
+    /// Blend towards another palette
+    ///
+    /// Colors named in both palettes are interpolated; colors named in only
+    /// one are kept unchanged so entities never lose their color mid-blend.
+    pub fn lerp(&self, other: &Palette, t: f32) -> Palette {
+        let mut colors = self.colors.clone();
+        for (name, &to) in &other.colors {
+            let blended = match self.colors.get(name) {
+                Some(&from) => lerp_color(from, to, t),
+                None => to,
+            };
+            colors.insert(name.clone(), blended);
+        }
+        Palette { colors }
+    }
+}
+
+/// Component-wise linear interpolation between two RGBA colors
+pub(crate) fn lerp_color(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+    std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette_lookup() {
+        let palette = Palette::new().with_color("floor", [0.1, 0.2, 0.3, 1.0]);
+        assert_eq!(palette.get("floor"), Some([0.1, 0.2, 0.3, 1.0]));
+        assert_eq!(palette.get("wall"), None);
+    }
+
+    #[test]
+    fn test_palette_lerp_blends_shared_names() {
+        let day = Palette::new()
+            .with_color("sky", [1.0, 1.0, 1.0, 1.0])
+            .with_color("sun", [1.0, 1.0, 0.0, 1.0]);
+        let night = Palette::new()
+            .with_color("sky", [0.0, 0.0, 0.2, 1.0])
+            .with_color("moon", [0.8, 0.8, 0.8, 1.0]);
+
+        let dusk = day.lerp(&night, 0.5);
+        assert_eq!(dusk.get("sky"), Some([0.5, 0.5, 0.6, 1.0]));
+        assert_eq!(dusk.get("sun"), Some([1.0, 1.0, 0.0, 1.0]));
+        assert_eq!(dusk.get("moon"), Some([0.8, 0.8, 0.8, 1.0]));
+    }
+
+    #[test]
+    fn test_palette_serialization() {
+        let palette = Palette::new().with_color("accent", [0.5, 0.25, 1.0, 1.0]);
+        let text = ron::to_string(&palette).unwrap();
+        let back: Palette = ron::from_str(&text).unwrap();
+        assert_eq!(back, palette);
+    }
+}
@@ -4,15 +4,17 @@
 //! Scenes contain entity templates, physics settings, and player spawn info.
 
 use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
 use std::path::Path;
 use std::fs;
 use std::io;
 
-use crate::entity::EntityTemplate;
+use crate::entity::{Entity, EntityTemplate};
 use crate::shapes::ShapeTemplate;
-use crate::World;
-use rust4d_math::Vec4;
-use rust4d_physics::{PhysicsConfig, RigidBody4D, StaticCollider, BodyType, PhysicsMaterial};
+use crate::{EntityKey, World};
+use rust4d_math::float_format;
+use rust4d_math::{Bivector4, Vec4};
+use rust4d_physics::{PhysicsConfig, RigidBody4D, StaticCollider, BodyType, PhysicsMaterial, Collider};
 
 /// A serializable scene containing entity templates
 ///
@@ -25,10 +27,19 @@ pub struct Scene {
     /// Entity templates in this scene
     pub entities: Vec<EntityTemplate>,
     /// Gravity for physics (negative = downward)
-    #[serde(default)]
+    #[serde(default, serialize_with = "float_format::option_scalar::serialize")]
     pub gravity: Option<f32>,
+    /// Gravity as a 4D acceleration [x, y, z, w], overriding `gravity`
+    ///
+    /// Only needed for gravity that doesn't point along Y.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "float_format::option_array4::serialize"
+    )]
+    pub gravity_vector: Option<[f32; 4]>,
     /// Player spawn position [x, y, z, w]
-    #[serde(default)]
+    #[serde(default, serialize_with = "float_format::option_array4::serialize")]
     pub player_spawn: Option<[f32; 4]>,
 }
 
@@ -39,6 +50,7 @@ impl Scene {
             name: name.into(),
             entities: Vec::new(),
             gravity: None,
+            gravity_vector: None,
             player_spawn: None,
         }
     }
@@ -54,12 +66,20 @@ impl Scene {
 
     /// Save a scene to a RON file
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), SceneSaveError> {
+        let contents = self.to_ron_string()?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Serialize this scene to RON text
+    ///
+    /// Floats are written in canonical form (see [`canonical_f32`](crate::canonical_f32)),
+    /// so the same scene produces identical text on every platform.
+    pub fn to_ron_string(&self) -> Result<String, ron::Error> {
         let pretty = ron::ser::PrettyConfig::new()
             .struct_names(true)
             .enumerate_arrays(false);
-        let contents = ron::ser::to_string_pretty(self, pretty)?;
-        fs::write(path, contents)?;
-        Ok(())
+        ron::ser::to_string_pretty(self, pretty)
     }
 
     /// Add an entity template to this scene
@@ -73,11 +93,139 @@ impl Scene {
         self
     }
 
+    /// Set the gravity for this scene as a 4D acceleration
+    pub fn with_gravity_vector(mut self, gravity: Vec4) -> Self {
+        self.gravity_vector = Some([gravity.x, gravity.y, gravity.z, gravity.w]);
+        self
+    }
+
+    /// The scene's gravity as a 4D acceleration, if it sets one
+    pub fn gravity_acceleration(&self) -> Option<Vec4> {
+        match (self.gravity_vector, self.gravity) {
+            (Some([x, y, z, w]), _) => Some(Vec4::new(x, y, z, w)),
+            (None, Some(y)) => Some(Vec4::new(0.0, y, 0.0, 0.0)),
+            (None, None) => None,
+        }
+    }
+
     /// Set the player spawn position
     pub fn with_player_spawn(mut self, x: f32, y: f32, z: f32, w: f32) -> Self {
         self.player_spawn = Some([x, y, z, w]);
         self
     }
+
+    /// Snapshot a live world as a scene template
+    ///
+    /// Each entity becomes an [`EntityTemplate`] with its name, tags, local
+    /// transform, material, palette color and physics material; its parent is
+    /// referenced by name, and a physics body's current velocity is kept as
+    /// the initial velocity. The world's gravity is saved as `gravity` when it
+    /// points along Y, and as `gravity_vector` otherwise.
+    ///
+    /// Entities whose shape wasn't created from a [`ShapeTemplate`] can't be
+    /// saved and are skipped. A child of an unnamed or skipped parent is saved
+    /// as a root at its world transform. The player body is not an entity, so
+    /// the player spawn is left unset.
+    pub fn from_world(world: &World, name: &str) -> Scene {
+        let mut scene = Scene::new(name);
+        if let Some(physics) = world.physics() {
+            let gravity = physics.config.gravity_vector;
+            if gravity.x == 0.0 && gravity.z == 0.0 && gravity.w == 0.0 {
+                scene.gravity = Some(gravity.y);
+            } else {
+                scene = scene.with_gravity_vector(gravity);
+            }
+        }
+
+        for (key, entity) in world.iter_with_keys() {
+            let Some(shape) = entity.shape_template.clone() else {
+                log::warn!("Scene '{}': skipping entity {:?} without a shape template", name, entity.name);
+                continue;
+            };
+
+            let parent = world
+                .parent_of(key)
+                .and_then(|parent| world.get_entity(parent))
+                .filter(|parent| parent.shape_template.is_some())
+                .and_then(|parent| parent.name.clone());
+            let transform = if parent.is_none() && world.has_parent(key) {
+                world.world_transform(key).unwrap_or(entity.transform)
+            } else {
+                entity.transform
+            };
+
+            let velocity = entity
+                .physics_body
+                .and_then(|body| world.physics()?.get_body(body))
+                .map(|body| body.velocity)
+                .filter(|v| *v != Vec4::ZERO);
+
+            scene.add_entity(EntityTemplate {
+                name: entity.name.clone(),
+                tags: entity.tag_names(),
+                parent,
+                transform,
+                shape,
+                material: entity.material,
+                palette_color: entity.palette_color.clone(),
+                initial_velocity: velocity.map(|v| [v.x, v.y, v.z, v.w]),
+                physics_material: entity.physics_material,
+                w_lock: entity.w_lock,
+            });
+        }
+        scene
+    }
+
+    /// Indices of the entity templates in the order they should be created
+    ///
+    /// Parents come before their children; otherwise template order is kept,
+    /// so the same template always creates (and keys) entities in the same
+    /// order. Fails if a parent name matches no entity or parents form a cycle.
+    pub fn spawn_order(&self) -> Result<Vec<usize>, SceneError> {
+        let parents = self.parent_indices()?;
+        let mut placed = vec![false; self.entities.len()];
+        let mut order = Vec::with_capacity(self.entities.len());
+        for start in 0..self.entities.len() {
+            // Walk up to the first placed ancestor, then place the chain root first
+            let mut chain = Vec::new();
+            let mut current = Some(start);
+            while let Some(index) = current.filter(|&i| !placed[i]) {
+                if chain.contains(&index) {
+                    let name = self.entities[index].name.clone().unwrap_or_default();
+                    return Err(SceneError::ParentCycle(name));
+                }
+                chain.push(index);
+                current = parents[index];
+            }
+            for &index in chain.iter().rev() {
+                placed[index] = true;
+                order.push(index);
+            }
+        }
+        Ok(order)
+    }
+
+    /// Index of each entity template's parent, resolved by name
+    ///
+    /// Names are matched against the first template using them.
+    fn parent_indices(&self) -> Result<Vec<Option<usize>>, SceneError> {
+        let mut by_name = HashMap::new();
+        for (index, entity) in self.entities.iter().enumerate() {
+            if let Some(name) = &entity.name {
+                by_name.entry(name.as_str()).or_insert(index);
+            }
+        }
+        self.entities
+            .iter()
+            .map(|entity| match &entity.parent {
+                Some(parent) => by_name
+                    .get(parent.as_str())
+                    .map(|&index| Some(index))
+                    .ok_or_else(|| SceneError::UnknownParent(parent.clone())),
+                None => Ok(None),
+            })
+            .collect()
+    }
 }
 
 /// Error loading a scene
@@ -160,6 +308,10 @@ pub enum SceneError {
     NotLoaded(String),
     /// No active scene on the stack
     NoActiveScene,
+    /// An entity's parent name matches no entity in the template
+    UnknownParent(String),
+    /// Parent links form a cycle (names one entity in it)
+    ParentCycle(String),
 }
 
 impl From<io::Error> for SceneError {
@@ -197,12 +349,113 @@ impl std::fmt::Display for SceneError {
             SceneError::Serialize(e) => write!(f, "Serialize error: {}", e),
             SceneError::NotLoaded(name) => write!(f, "Scene not loaded: {}", name),
             SceneError::NoActiveScene => write!(f, "No active scene"),
+            SceneError::UnknownParent(name) => write!(f, "Unknown parent entity: {}", name),
+            SceneError::ParentCycle(name) => write!(f, "Parent cycle through entity: {}", name),
         }
     }
 }
 
 impl std::error::Error for SceneError {}
 
+/// How additive loading handles an incoming entity whose name is already taken
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum MergePolicy {
+    /// Add the incoming entity under a free name (`name_2`, `name_3`, ...)
+    #[default]
+    Rename,
+    /// Drop the incoming entity, keeping the existing one
+    Skip,
+    /// Remove the existing entity and add the incoming one in its place
+    Overwrite,
+}
+
+/// What an additive load did with the incoming entities
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MergeReport {
+    /// Keys of the entities that were added, in template order
+    pub added: Vec<EntityKey>,
+    /// Name collisions resolved by renaming, as `(original, new)`
+    pub renamed: Vec<(String, String)>,
+    /// Names of incoming entities that were dropped
+    pub skipped: Vec<String>,
+    /// Names of existing entities that were replaced
+    pub overwritten: Vec<String>,
+}
+
+impl MergeReport {
+    /// Check if any names collided
+    pub fn has_conflicts(&self) -> bool {
+        !self.renamed.is_empty() || !self.skipped.is_empty() || !self.overwritten.is_empty()
+    }
+}
+
+/// Build an entity from its template, adding its physics body or collider to the world
+///
+/// Static-tagged hyperplanes get a bounded floor collider and dynamic-tagged
+/// entities a rigid body. The entity itself is returned for the caller to add.
+fn instantiate_entity(world: &mut World, entity_template: &EntityTemplate) -> Entity {
+    let mut entity = entity_template.to_entity();
+    let is_static = entity_template.tags.contains(&"static".to_string());
+    let is_dynamic = entity_template.tags.contains(&"dynamic".to_string());
+
+    if let Some(physics) = world.physics_mut() {
+        if is_static {
+            // Create bounded static collider for floor/walls (objects can fall off edges)
+            if let ShapeTemplate::Hyperplane { y, size, cell_size, thickness, .. } = &entity_template.shape {
+                log::debug!("Adding bounded floor collider: y={}, size={}, cell_size={}, thickness={}",
+                    y, size, cell_size, thickness);
+                physics.add_static_collider(StaticCollider::floor_bounded(
+                    *y,
+                    *size,      // X/Z extent from hyperplane
+                    *cell_size, // W extent
+                    *thickness, // Y thickness
+                    entity.physics_material.unwrap_or(PhysicsMaterial::CONCRETE),
+                ));
+            }
+        } else if is_dynamic {
+            // Create dynamic rigid body for movable objects
+            let position = Vec4::new(
+                entity_template.transform.position.x,
+                entity_template.transform.position.y,
+                entity_template.transform.position.z,
+                entity_template.transform.position.w,
+            );
+
+            // Get half-extent from shape
+            let half_extent = match &entity_template.shape {
+                ShapeTemplate::Tesseract { size } => size / 2.0,
+                ShapeTemplate::Hyperplane { .. } => 1.0, // shouldn't be dynamic, but fallback
+            };
+
+            let body = RigidBody4D::new_aabb(
+                position,
+                Vec4::new(half_extent, half_extent, half_extent, half_extent),
+            )
+            .with_orientation(entity_template.transform.rotation)
+            .with_body_type(BodyType::Dynamic)
+            .with_mass(10.0)
+            .with_material(entity.physics_material.unwrap_or(PhysicsMaterial::WOOD));
+
+            let body_key = physics.add_body(body);
+            entity = entity.with_physics_body(body_key);
+        }
+    }
+
+    if let Some(v) = entity_template.initial_velocity {
+        let body = entity.physics_body
+            .and_then(|key| world.physics_mut().and_then(|p| p.get_body_mut(key)));
+        match body {
+            Some(body) => body.velocity = Vec4::new(v[0], v[1], v[2], v[3]),
+            None => log::warn!(
+                "Entity {:?} has an initial velocity but no physics body; ignoring it",
+                entity_template.name
+            ),
+        }
+    }
+
+    entity
+}
+
 /// A runtime scene containing an instantiated World
 ///
 /// ActiveScene wraps a World instance that has been instantiated from a Scene template
@@ -215,85 +468,80 @@ pub struct ActiveScene {
     pub player_spawn: Option<[f32; 4]>,
     /// The live world with entities and physics
     pub world: World,
+    /// Keys of the entities created from the template, in template order
+    pub template_entities: Vec<EntityKey>,
 }
 
 impl ActiveScene {
     /// Create an active scene from a Scene template
     ///
     /// This instantiates all entities from the template into a new World,
-    /// optionally enabling physics with the provided config.
+    /// optionally enabling physics with the provided config. Entities are
+    /// created in [`Scene::spawn_order`] and linked to their parents; if the
+    /// parent links are invalid, a warning is logged and they are ignored.
     ///
     /// The `player_radius` parameter sets the collision radius for the player body.
     pub fn from_template(template: &Scene, physics_config: Option<PhysicsConfig>, player_radius: f32) -> Self {
+        Self::from_template_with_capsule(template, physics_config, player_radius, 0.0)
+    }
+
+    /// Create an active scene whose player is an upright capsule
+    ///
+    /// Like [`from_template`](Self::from_template), but the player body is a
+    /// capsule whose segment reaches `player_half_height` above and below the
+    /// spawn, which walks over floor seams and small ledges more smoothly than
+    /// a sphere. A half-height of 0.0 gives the usual sphere.
+    pub fn from_template_with_capsule(
+        template: &Scene,
+        physics_config: Option<PhysicsConfig>,
+        player_radius: f32,
+        player_half_height: f32,
+    ) -> Self {
         log::debug!("from_template: physics_config={:?}, template.gravity={:?}", physics_config, template.gravity);
 
         // Create world with physics
         let mut world = if let Some(config) = physics_config {
-            log::debug!("Using provided physics_config with gravity={}", config.gravity);
+            log::debug!("Using provided physics_config with gravity={:?}", config.gravity_vector);
             World::new().with_physics(config)
-        } else if let Some(gravity) = template.gravity {
-            log::debug!("Using template gravity={}", gravity);
-            World::new().with_physics(PhysicsConfig::new(gravity))
+        } else if let Some(gravity) = template.gravity_acceleration() {
+            log::debug!("Using template gravity={:?}", gravity);
+            World::new().with_physics(PhysicsConfig::new(0.0).with_gravity_vector(gravity))
         } else {
             log::debug!("No physics configured");
             World::new()
         };
 
-        // Instantiate all entities from the template, setting up physics based on tags
-        for entity_template in &template.entities {
-            let mut entity = entity_template.to_entity();
-            let is_static = entity_template.tags.contains(&"static".to_string());
-            let is_dynamic = entity_template.tags.contains(&"dynamic".to_string());
-
-            if let Some(physics) = world.physics_mut() {
-                if is_static {
-                    // Create bounded static collider for floor/walls (objects can fall off edges)
-                    if let ShapeTemplate::Hyperplane { y, size, cell_size, thickness, .. } = &entity_template.shape {
-                        log::debug!("Adding bounded floor collider: y={}, size={}, cell_size={}, thickness={}",
-                            y, size, cell_size, thickness);
-                        physics.add_static_collider(StaticCollider::floor_bounded(
-                            *y,
-                            *size,      // X/Z extent from hyperplane
-                            *cell_size, // W extent
-                            *thickness, // Y thickness
-                            PhysicsMaterial::CONCRETE,
-                        ));
-                    }
-                } else if is_dynamic {
-                    // Create dynamic rigid body for movable objects
-                    let position = Vec4::new(
-                        entity_template.transform.position.x,
-                        entity_template.transform.position.y,
-                        entity_template.transform.position.z,
-                        entity_template.transform.position.w,
-                    );
-
-                    // Get half-extent from shape
-                    let half_extent = match &entity_template.shape {
-                        ShapeTemplate::Tesseract { size } => size / 2.0,
-                        ShapeTemplate::Hyperplane { .. } => 1.0, // shouldn't be dynamic, but fallback
-                    };
-
-                    let body = RigidBody4D::new_aabb(
-                        position,
-                        Vec4::new(half_extent, half_extent, half_extent, half_extent),
-                    )
-                    .with_body_type(BodyType::Dynamic)
-                    .with_mass(10.0)
-                    .with_material(PhysicsMaterial::WOOD);
-
-                    let body_key = physics.add_body(body);
-                    entity = entity.with_physics_body(body_key);
-                }
+        // Instantiate all entities from the template, parents first, setting
+        // up physics based on tags
+        let count = template.entities.len();
+        let (order, parents) = match template.spawn_order() {
+            Ok(order) => (order, template.parent_indices().expect("checked by spawn_order")),
+            Err(e) => {
+                log::warn!("Scene '{}': {}; ignoring parent links", template.name, e);
+                ((0..count).collect(), vec![None; count])
             }
-
-            world.add_entity(entity);
+        };
+        let mut keys = vec![None; count];
+        for index in order {
+            let entity = instantiate_entity(&mut world, &template.entities[index]);
+            let key = world.add_entity(entity);
+            if let Some(parent) = parents[index].and_then(|p| keys[p]) {
+                world.add_child(parent, key).expect("new entities can't form a cycle");
+                world.place_body_at_entity(key);
+            }
+            keys[index] = Some(key);
         }
+        let template_entities: Vec<EntityKey> = keys.into_iter().flatten().collect();
 
         // Create player body from player_spawn
         if let (Some(spawn), Some(physics)) = (template.player_spawn, world.physics_mut()) {
             let position = Vec4::new(spawn[0], spawn[1], spawn[2], spawn[3]);
-            let player_body = RigidBody4D::new_sphere(position, player_radius)
+            let player_body = if player_half_height > 0.0 {
+                RigidBody4D::new_capsule(position, player_half_height, player_radius)
+            } else {
+                RigidBody4D::new_sphere(position, player_radius)
+            };
+            let player_body = player_body
                 .with_body_type(BodyType::Kinematic)
                 .with_mass(1.0)
                 .with_material(PhysicsMaterial::WOOD);
@@ -306,6 +554,7 @@ impl ActiveScene {
             name: template.name.clone(),
             player_spawn: template.player_spawn,
             world,
+            template_entities,
         }
     }
 
@@ -315,6 +564,7 @@ impl ActiveScene {
             name: name.into(),
             player_spawn: None,
             world: World::new(),
+            template_entities: Vec::new(),
         }
     }
 
@@ -334,6 +584,140 @@ impl ActiveScene {
     pub fn update(&mut self, dt: f32) {
         self.world.update(dt);
     }
+
+    /// Add a template's entities to this scene without replacing what is there
+    ///
+    /// Entities whose name is already used in the world are handled by
+    /// `policy`; unnamed entities never collide. The template's gravity and
+    /// player spawn are ignored, and merged entities are not part of
+    /// [`template_entities`](Self::template_entities), so
+    /// [`reset_to_template`](Self::reset_to_template) leaves them alone.
+    ///
+    /// Incoming entities are created in [`Scene::spawn_order`] and linked to
+    /// their parents within the template, even if a parent was renamed; the
+    /// children of a skipped entity become roots. If the parent links are
+    /// invalid, a warning is logged and they are ignored.
+    ///
+    /// Overwriting removes the existing entity and its physics body; a static
+    /// collider created for it stays in the physics world.
+    pub fn merge_template(&mut self, template: &Scene, policy: MergePolicy) -> MergeReport {
+        let mut report = MergeReport::default();
+        let count = template.entities.len();
+        let (order, parents) = match template.spawn_order() {
+            Ok(order) => (order, template.parent_indices().expect("checked by spawn_order")),
+            Err(e) => {
+                log::warn!("Scene '{}': {}; ignoring parent links", template.name, e);
+                ((0..count).collect(), vec![None; count])
+            }
+        };
+        let mut keys = vec![None; count];
+        for index in order {
+            let entity_template = &template.entities[index];
+            let mut name = entity_template.name.clone();
+            if let Some(taken) = name.as_deref().filter(|n| self.world.get_by_name(n).is_some()) {
+                let taken = taken.to_string();
+                match policy {
+                    MergePolicy::Rename => {
+                        let renamed = (2..)
+                            .map(|i| format!("{}_{}", taken, i))
+                            .find(|candidate| self.world.get_by_name(candidate).is_none())
+                            .expect("unbounded suffix search");
+                        report.renamed.push((taken, renamed.clone()));
+                        name = Some(renamed);
+                    }
+                    MergePolicy::Skip => {
+                        report.skipped.push(taken);
+                        continue;
+                    }
+                    MergePolicy::Overwrite => {
+                        if let Some((existing, _)) = self.world.get_by_name(&taken) {
+                            self.world.remove_entity(existing);
+                        }
+                        report.overwritten.push(taken);
+                    }
+                }
+            }
+
+            let mut entity = instantiate_entity(&mut self.world, entity_template);
+            entity.name = name;
+            let key = self.world.add_entity(entity);
+            if let Some(parent) = parents[index].and_then(|p| keys[p]) {
+                self.world.add_child(parent, key).expect("new entities can't form a cycle");
+                self.world.place_body_at_entity(key);
+            }
+            keys[index] = Some(key);
+        }
+        report.added = keys.into_iter().flatten().collect();
+        report
+    }
+
+    /// Move a player spawned inside the floor up to rest on it
+    ///
+    /// Lifts the player body (and `player_spawn`) with
+    /// [`PhysicsWorld::lift_above_floor`](rust4d_physics::PhysicsWorld::lift_above_floor),
+    /// leaving `clearance` between the player and the floor. Returns true if
+    /// the player was moved; a spawn already above the floor is left as is.
+    pub fn snap_player_spawn(&mut self, clearance: f32) -> bool {
+        let Some(physics) = self.world.physics_mut() else {
+            return false;
+        };
+        let Some(player) = physics.player() else {
+            return false;
+        };
+        let radius = match player.collider {
+            Collider::Sphere(sphere) => sphere.radius,
+            Collider::Capsule(capsule) => capsule.half_height + capsule.radius,
+            _ => 0.0,
+        };
+        let position = player.position;
+        let lifted = physics.lift_above_floor(position, radius, clearance);
+        if lifted == position {
+            return false;
+        }
+
+        if let Some(player) = physics.player_mut() {
+            player.set_position(lifted);
+        }
+        self.player_spawn = Some([lifted.x, lifted.y, lifted.z, lifted.w]);
+        true
+    }
+
+    /// Put template entities back to their initial state
+    ///
+    /// Re-applies each template transform to its live entity and moves the
+    /// entity's physics body to the entity's world transform with no spin
+    /// and its initial velocity, waking it if it had fallen asleep. Entities
+    /// and bodies are reused, so keys and GPU geometry stay valid. Entities
+    /// that have since been removed are skipped, and the player body is left
+    /// alone.
+    /// `template` must be the scene this one was instantiated from.
+    pub fn reset_to_template(&mut self, template: &Scene) {
+        let live: Vec<(EntityKey, &EntityTemplate)> = self
+            .template_entities
+            .iter()
+            .zip(&template.entities)
+            .filter_map(|(&key, entity_template)| {
+                let entity = self.world.get_entity_mut(key)?;
+                entity.set_transform(entity_template.transform);
+                Some((key, entity_template))
+            })
+            .collect();
+
+        // With every transform back, children's bodies go to their world transforms
+        for (key, entity_template) in live {
+            self.world.place_body_at_entity(key);
+            let body_key = self.world.get_entity(key).and_then(|entity| entity.physics_body);
+            if let (Some(body_key), Some(physics)) = (body_key, self.world.physics_mut()) {
+                if let Some(body) = physics.get_body_mut(body_key) {
+                    body.angular_velocity = Bivector4::ZERO;
+                    body.velocity = entity_template.initial_velocity
+                        .map_or(Vec4::ZERO, |v| Vec4::new(v[0], v[1], v[2], v[3]));
+                    body.grounded = false;
+                    body.wake();
+                }
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -407,6 +791,69 @@ mod tests {
         assert_eq!(deserialized.entities[0].name, Some("test_cube".to_string()));
     }
 
+    #[test]
+    fn test_scene_serialization_is_deterministic() {
+        let scene = make_float_heavy_scene();
+
+        let first = scene.to_ron_string().unwrap();
+        let second = scene.to_ron_string().unwrap();
+        assert_eq!(first, second);
+
+        // A clone (independent values) serializes identically too
+        assert_eq!(scene.clone().to_ron_string().unwrap(), first);
+    }
+
+    #[test]
+    fn test_scene_serialization_canonical_floats() {
+        // Values that differ only below the kept precision produce the same text
+        let a = Scene::new("Floats").with_gravity(-9.81);
+        let b = Scene::new("Floats").with_gravity(-9.81 - 4e-7);
+        assert_eq!(a.to_ron_string().unwrap(), b.to_ron_string().unwrap());
+
+        // Negative zero is written as zero
+        let neg = Scene::new("Zero").with_player_spawn(-0.0, 1.0, -0.0, 0.0);
+        let pos = Scene::new("Zero").with_player_spawn(0.0, 1.0, 0.0, 0.0);
+        assert_eq!(neg.to_ron_string().unwrap(), pos.to_ron_string().unwrap());
+    }
+
+    #[test]
+    fn test_scene_serialization_round_trip_precision() {
+        let scene = make_float_heavy_scene();
+        let text = scene.to_ron_string().unwrap();
+        let loaded: Scene = ron::from_str(&text).unwrap();
+
+        let tolerance = 10f32.powi(-crate::SCENE_FLOAT_DECIMALS);
+        assert!((loaded.gravity.unwrap() - scene.gravity.unwrap()).abs() <= tolerance);
+        for (a, b) in loaded.player_spawn.unwrap().iter().zip(scene.player_spawn.unwrap().iter()) {
+            assert!((a - b).abs() <= tolerance);
+        }
+
+        let original = &scene.entities[0];
+        let restored = &loaded.entities[0];
+        assert!((restored.transform.position - original.transform.position).length() <= 2.0 * tolerance);
+        assert!((restored.transform.scale - original.transform.scale).abs() <= tolerance);
+        for (a, b) in restored.material.base_color.iter().zip(original.material.base_color.iter()) {
+            assert!((a - b).abs() <= tolerance);
+        }
+
+        // Serializing the loaded scene reproduces the same text
+        assert_eq!(loaded.to_ron_string().unwrap(), text);
+    }
+
+    fn make_float_heavy_scene() -> Scene {
+        let mut scene = Scene::new("Floats")
+            .with_gravity(-9.806_65)
+            .with_player_spawn(0.1, 1.0 / 3.0, -std::f32::consts::E, 1e-7);
+        let mut transform = Transform4D::from_position(Vec4::new(0.1 + 0.2, -1.0 / 7.0, std::f32::consts::PI, -0.0));
+        transform.scale = 2.0 / 3.0;
+        scene.add_entity(EntityTemplate::new(
+            ShapeTemplate::tesseract(1.0 / 3.0),
+            transform,
+            Material::new(0.1, 0.2, 1.0 / 3.0, 1.0),
+        ));
+        scene
+    }
+
     #[test]
     fn test_parse_scene_file_format() {
         // Test parsing a scene matching the actual serialization format
@@ -546,7 +993,7 @@ Scene(
         let scene = ActiveScene::new("Physics Scene")
             .with_physics(PhysicsConfig::new(-20.0));
         assert!(scene.world.physics().is_some());
-        assert_eq!(scene.world.physics().unwrap().config.gravity, -20.0);
+        assert_eq!(scene.world.physics().unwrap().config.gravity_vector.y, -20.0);
     }
 
     #[test]
@@ -578,7 +1025,7 @@ Scene(
 
         // Check physics was set from template gravity
         assert!(active.world.physics().is_some());
-        assert_eq!(active.world.physics().unwrap().config.gravity, -15.0);
+        assert_eq!(active.world.physics().unwrap().config.gravity_vector.y, -15.0);
 
         // Check entity was instantiated
         let (_, entity) = active.world.get_by_name("cube").unwrap();
@@ -597,7 +1044,347 @@ Scene(
         );
 
         // Should use overridden config, not template gravity
-        assert_eq!(active.world.physics().unwrap().config.gravity, -30.0);
+        assert_eq!(active.world.physics().unwrap().config.gravity_vector.y, -30.0);
+    }
+
+    fn rolling_ball_scene(initial_velocity: Option<[f32; 4]>) -> Scene {
+        let mut template = Scene::new("Rolling");
+        let mut ball = EntityTemplate::new(
+            ShapeTemplate::tesseract(1.0),
+            Transform4D::identity(),
+            Material::RED,
+        )
+        .with_name("ball")
+        .with_tag("dynamic");
+        ball.initial_velocity = initial_velocity;
+        template.add_entity(ball);
+        template
+    }
+
+    fn ball_velocity(active: &ActiveScene) -> Vec4 {
+        let (_, ball) = active.world.get_by_name("ball").unwrap();
+        let physics = active.world.physics().unwrap();
+        physics.get_body(ball.physics_body.unwrap()).unwrap().velocity
+    }
+
+    #[test]
+    fn test_initial_velocity_applied_to_body() {
+        let template = rolling_ball_scene(Some([2.0, 0.0, 0.0, -1.0]));
+        let mut active = ActiveScene::from_template(&template, Some(PhysicsConfig::new(0.0)), 0.5);
+        active.update(0.1);
+
+        assert_eq!(ball_velocity(&active), Vec4::new(2.0, 0.0, 0.0, -1.0));
+        let (_, ball) = active.world.get_by_name("ball").unwrap();
+        assert!((ball.transform.position.x - 0.2).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_missing_initial_velocity_is_zero() {
+        let template = rolling_ball_scene(None);
+        let mut active = ActiveScene::from_template(&template, Some(PhysicsConfig::new(0.0)), 0.5);
+        active.update(0.1);
+
+        assert_eq!(ball_velocity(&active), Vec4::ZERO);
+    }
+
+    #[test]
+    fn test_reset_restores_initial_velocity() {
+        let template = rolling_ball_scene(Some([2.0, 0.0, 0.0, 0.0]));
+        let mut active = ActiveScene::from_template(&template, Some(PhysicsConfig::new(-20.0)), 0.5);
+        active.update(0.5);
+        assert_ne!(ball_velocity(&active), Vec4::new(2.0, 0.0, 0.0, 0.0));
+
+        active.reset_to_template(&template);
+        assert_eq!(ball_velocity(&active), Vec4::new(2.0, 0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_reset_wakes_bodies_that_fell_asleep() {
+        let template = bounce_scene();
+        let mut active = ActiveScene::from_template(&template, Some(PhysicsConfig::new(-20.0)), 0.5);
+        let body_key = active.world.get_by_name("dead").unwrap().1.physics_body.unwrap();
+        for _ in 0..300 {
+            active.update(1.0 / 60.0);
+        }
+        assert!(active.world.physics().unwrap().get_body(body_key).unwrap().is_sleeping());
+
+        // Back at the top, it falls again rather than hanging there asleep
+        active.reset_to_template(&template);
+        active.update(1.0 / 60.0);
+        let body = active.world.physics().unwrap().get_body(body_key).unwrap();
+        assert!(!body.is_sleeping());
+        assert!(body.position.y < 3.0);
+    }
+
+    #[test]
+    fn test_reset_stops_spinning_bodies() {
+        let template = rolling_ball_scene(None);
+        let mut active = ActiveScene::from_template(&template, Some(PhysicsConfig::new(0.0)), 0.5);
+        let (key, ball) = active.world.get_by_name("ball").unwrap();
+        let body_key = ball.physics_body.unwrap();
+        active.world.physics_mut().unwrap().get_body_mut(body_key).unwrap().angular_velocity =
+            Bivector4 { zw: 2.0, ..Bivector4::ZERO };
+        active.update(0.25);
+        assert!(active.world.get_entity(key).unwrap().transform.rotation.b_zw.abs() > 0.1);
+
+        // The reset sticks: the next update doesn't spin it back
+        active.reset_to_template(&template);
+        active.update(0.25);
+        let body = active.world.physics().unwrap().get_body(body_key).unwrap();
+        assert_eq!(body.angular_velocity, Bivector4::ZERO);
+        assert_eq!(body.orientation.b_zw, 0.0);
+        assert_eq!(active.world.get_entity(key).unwrap().transform.rotation.b_zw, 0.0);
+    }
+
+    #[test]
+    fn test_child_bodies_live_at_their_world_position() {
+        let mut template = Scene::new("Nested");
+        template.add_entity(
+            EntityTemplate::new(ShapeTemplate::tesseract(1.0), Transform4D::from_position(Vec4::new(0.0, 5.0, 0.0, 0.0)), Material::GRAY)
+                .with_name("shelf"),
+        );
+        template.add_entity(
+            EntityTemplate::new(ShapeTemplate::tesseract(1.0), Transform4D::from_position(Vec4::new(1.0, 0.0, 0.0, 0.0)), Material::RED)
+                .with_name("box")
+                .with_tag("dynamic")
+                .with_parent("shelf"),
+        );
+        let mut active = ActiveScene::from_template(&template, Some(PhysicsConfig::new(0.0)), 0.5);
+        let (key, entity) = active.world.get_by_name("box").unwrap();
+        let body_key = entity.physics_body.unwrap();
+        let body_position = |active: &ActiveScene| active.world.physics().unwrap().get_body(body_key).unwrap().position;
+        assert_eq!(body_position(&active), Vec4::new(1.0, 5.0, 0.0, 0.0));
+
+        // Syncing keeps the child's transform relative to its parent
+        active.update(1.0 / 60.0);
+        assert_eq!(active.world.get_entity(key).unwrap().transform.position, Vec4::new(1.0, 0.0, 0.0, 0.0));
+
+        // And a reset puts the body back at the child's world position
+        active.world.physics_mut().unwrap().get_body_mut(body_key).unwrap().set_position(Vec4::new(8.0, 0.0, 0.0, 0.0));
+        active.update(1.0 / 60.0);
+        assert_eq!(active.world.get_entity(key).unwrap().transform.position, Vec4::new(8.0, -5.0, 0.0, 0.0));
+        active.reset_to_template(&template);
+        assert_eq!(body_position(&active), Vec4::new(1.0, 5.0, 0.0, 0.0));
+        assert_eq!(active.world.world_transform(key).unwrap().position, Vec4::new(1.0, 5.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_initial_velocity_without_body_is_ignored() {
+        let mut template = Scene::new("Static");
+        template.add_entity(
+            EntityTemplate::new(ShapeTemplate::tesseract(1.0), Transform4D::identity(), Material::RED)
+                .with_name("prop")
+                .with_initial_velocity(1.0, 0.0, 0.0, 0.0),
+        );
+        let active = ActiveScene::from_template(&template, Some(PhysicsConfig::new(0.0)), 0.5);
+
+        let (_, prop) = active.world.get_by_name("prop").unwrap();
+        assert!(prop.physics_body.is_none());
+    }
+
+    #[test]
+    fn test_initial_velocity_parses_from_ron() {
+        let ron = r#"EntityTemplate(
+            name: Some("ball"),
+            tags: ["dynamic"],
+            transform: Transform4D(
+                position: Vec4(x: 0.0, y: 0.0, z: 0.0, w: 0.0),
+                rotation: (1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+                scale: 1.0,
+            ),
+            shape: ShapeTemplate(type: "Tesseract", size: 1.0),
+            material: Material(base_color: (1.0, 1.0, 1.0, 1.0)),
+            initial_velocity: Some((3.0, 0.0, 0.5, 0.0)),
+        )"#;
+        let template: EntityTemplate = ron::from_str(ron).unwrap();
+        assert_eq!(template.initial_velocity, Some([3.0, 0.0, 0.5, 0.0]));
+    }
+
+    fn bounce_scene() -> Scene {
+        let mut template = Scene::new("Bounce");
+        template.add_entity(
+            EntityTemplate::new(ShapeTemplate::hyperplane(0.0, 20.0, 4, 5.0, 0.1), Transform4D::identity(), Material::GRAY)
+                .with_tag("static")
+                .with_physics_material(PhysicsMaterial::new(0.5, 0.0)),
+        );
+        for (name, x, restitution) in [("bouncy", -3.0, 1.0), ("dead", 3.0, 0.0)] {
+            template.add_entity(
+                EntityTemplate::new(
+                    ShapeTemplate::tesseract(1.0),
+                    Transform4D::from_position(Vec4::new(x, 3.0, 0.0, 0.0)),
+                    Material::RED,
+                )
+                .with_name(name)
+                .with_tag("dynamic")
+                .with_physics_material(PhysicsMaterial::new(0.5, restitution)),
+            );
+        }
+        template
+    }
+
+    #[test]
+    fn test_physics_material_override_applied_to_body() {
+        let active = ActiveScene::from_template(&bounce_scene(), Some(PhysicsConfig::new(-20.0)), 0.5);
+        let (_, bouncy) = active.world.get_by_name("bouncy").unwrap();
+        assert_eq!(bouncy.physics_material, Some(PhysicsMaterial::new(0.5, 1.0)));
+        let body = active.world.physics().unwrap().get_body(bouncy.physics_body.unwrap()).unwrap();
+        assert_eq!(body.material.restitution, 1.0);
+    }
+
+    #[test]
+    fn test_physics_material_override_changes_bounce() {
+        let mut active = ActiveScene::from_template(&bounce_scene(), Some(PhysicsConfig::new(-20.0)), 0.5);
+
+        // Same shape, same fall; only the material differs
+        let mut max_rebound = [0.0f32; 2];
+        for _ in 0..90 {
+            active.update(1.0 / 60.0);
+            for (i, name) in ["bouncy", "dead"].iter().enumerate() {
+                let (_, entity) = active.world.get_by_name(name).unwrap();
+                let physics = active.world.physics().unwrap();
+                let velocity = physics.get_body(entity.physics_body.unwrap()).unwrap().velocity;
+                max_rebound[i] = max_rebound[i].max(velocity.y);
+            }
+        }
+
+        assert!(max_rebound[0] > 5.0, "bouncy rebound {}", max_rebound[0]);
+        assert!(max_rebound[1] < 0.5, "dead rebound {}", max_rebound[1]);
+    }
+
+    #[test]
+    fn test_physics_material_serialization() {
+        let template = EntityTemplate::new(ShapeTemplate::tesseract(1.0), Transform4D::identity(), Material::RED)
+            .with_physics_material(PhysicsMaterial::RUBBER);
+        let text = ron::to_string(&template).unwrap();
+        let back: EntityTemplate = ron::from_str(&text).unwrap();
+        assert_eq!(back.physics_material, Some(PhysicsMaterial::RUBBER));
+
+        // Omitted when unset
+        let plain = EntityTemplate::new(ShapeTemplate::tesseract(1.0), Transform4D::identity(), Material::RED);
+        assert!(!ron::to_string(&plain).unwrap().contains("physics_material"));
+    }
+
+    #[test]
+    fn test_w_lock_serialization() {
+        let template = EntityTemplate::new(ShapeTemplate::tesseract(1.0), Transform4D::identity(), Material::RED)
+            .with_w_lock(2.5);
+        let text = ron::to_string(&template).unwrap();
+        let back: EntityTemplate = ron::from_str(&text).unwrap();
+        assert_eq!(back.w_lock, Some(2.5));
+        assert_eq!(back.to_entity().w_lock, Some(2.5));
+
+        // Omitted when unset
+        let plain = EntityTemplate::new(ShapeTemplate::tesseract(1.0), Transform4D::identity(), Material::RED);
+        assert!(!ron::to_string(&plain).unwrap().contains("w_lock"));
+    }
+
+    // --- Additive merge tests ---
+
+    fn named_cube(name: &str, x: f32) -> EntityTemplate {
+        EntityTemplate::new(
+            ShapeTemplate::tesseract(1.0),
+            Transform4D::from_position(Vec4::new(x, 0.0, 0.0, 0.0)),
+            Material::RED,
+        )
+        .with_name(name)
+    }
+
+    fn merge_scenes(policy: MergePolicy) -> (ActiveScene, MergeReport) {
+        let mut base = Scene::new("Base");
+        base.add_entity(named_cube("crate", 1.0));
+        base.add_entity(named_cube("door", 2.0));
+        let mut extra = Scene::new("Extra");
+        extra.add_entity(named_cube("crate", 10.0));
+        extra.add_entity(named_cube("lamp", 11.0));
+
+        let mut active = ActiveScene::from_template(&base, None, 0.5);
+        let report = active.merge_template(&extra, policy);
+        (active, report)
+    }
+
+    /// Every named entity is found under its own name, and nothing else is indexed
+    fn assert_name_index_consistent(world: &World) {
+        for (key, entity) in world.iter_with_keys() {
+            if let Some(name) = &entity.name {
+                assert_eq!(world.get_by_name(name).map(|(k, _)| k), Some(key), "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_rename_keeps_both() {
+        let (active, report) = merge_scenes(MergePolicy::Rename);
+
+        assert_eq!(active.world.entity_count(), 4);
+        assert_eq!(report.added.len(), 2);
+        assert_eq!(report.renamed, vec![("crate".to_string(), "crate_2".to_string())]);
+        let (_, original) = active.world.get_by_name("crate").unwrap();
+        let (_, incoming) = active.world.get_by_name("crate_2").unwrap();
+        assert_eq!(original.transform.position.x, 1.0);
+        assert_eq!(incoming.transform.position.x, 10.0);
+        assert_name_index_consistent(&active.world);
+    }
+
+    #[test]
+    fn test_merge_skip_keeps_original() {
+        let (active, report) = merge_scenes(MergePolicy::Skip);
+
+        assert_eq!(active.world.entity_count(), 3);
+        assert_eq!(report.skipped, vec!["crate".to_string()]);
+        assert_eq!(report.added.len(), 1);
+        let (_, original) = active.world.get_by_name("crate").unwrap();
+        assert_eq!(original.transform.position.x, 1.0);
+        assert!(active.world.get_by_name("lamp").is_some());
+        assert_name_index_consistent(&active.world);
+    }
+
+    #[test]
+    fn test_merge_overwrite_replaces() {
+        let (active, report) = merge_scenes(MergePolicy::Overwrite);
+
+        assert_eq!(active.world.entity_count(), 3);
+        assert_eq!(report.overwritten, vec!["crate".to_string()]);
+        let (key, replaced) = active.world.get_by_name("crate").unwrap();
+        assert_eq!(replaced.transform.position.x, 10.0);
+        assert_eq!(key, report.added[0]);
+        assert_name_index_consistent(&active.world);
+    }
+
+    #[test]
+    fn test_merge_without_conflicts() {
+        let mut extra = Scene::new("Extra");
+        extra.add_entity(named_cube("lamp", 0.0));
+        let mut active = ActiveScene::new("Empty");
+
+        let report = active.merge_template(&extra, MergePolicy::Skip);
+        assert!(!report.has_conflicts());
+        assert_eq!(report.added.len(), 1);
+        assert!(active.template_entities.is_empty());
+    }
+
+    #[test]
+    fn test_merge_links_parents() {
+        let mut extra = Scene::new("Extra");
+        extra.add_entity(named_cube("lid", 10.0).with_parent("crate"));
+        extra.add_entity(named_cube("crate", 10.0));
+        let mut base = Scene::new("Base");
+        base.add_entity(named_cube("crate", 1.0));
+        let mut active = ActiveScene::from_template(&base, None, 0.5);
+
+        let report = active.merge_template(&extra, MergePolicy::Rename);
+        let (lid, _) = active.world.get_by_name("lid").unwrap();
+        let (original, _) = active.world.get_by_name("crate").unwrap();
+        let (renamed, _) = active.world.get_by_name("crate_2").unwrap();
+        assert_eq!(active.world.parent_of(lid), Some(renamed));
+        assert!(active.world.children_of(original).is_empty());
+        assert_eq!(report.added, vec![lid, renamed]);
+
+        // A skipped parent leaves its merged children as roots
+        let mut active = ActiveScene::from_template(&base, None, 0.5);
+        let report = active.merge_template(&extra, MergePolicy::Skip);
+        let (lid, _) = active.world.get_by_name("lid").unwrap();
+        assert_eq!(report.added, vec![lid]);
+        assert_eq!(active.world.parent_of(lid), None);
     }
 
     #[test]
@@ -608,4 +1395,104 @@ Scene(
         // Just verify update doesn't panic
         scene.update(0.016);
     }
+
+    #[test]
+    fn test_from_world_keeps_transforms_and_hierarchy() {
+        let mut world = World::new();
+        let base = EntityTemplate::new(
+            ShapeTemplate::tesseract(2.0),
+            Transform4D::from_position(Vec4::new(1.0, 0.0, 0.0, 3.0)),
+            Material::RED,
+        )
+        .with_name("base")
+        .with_tag("static");
+        let arm = EntityTemplate::new(
+            ShapeTemplate::tesseract(0.5),
+            Transform4D::from_position(Vec4::new(0.0, 2.0, 0.0, 0.0)),
+            Material::BLUE,
+        )
+        .with_name("arm");
+        let base = world.add_entity(base.to_entity());
+        let arm = world.add_entity(arm.to_entity());
+        world.add_child(base, arm).unwrap();
+
+        let scene = Scene::from_world(&world, "Snapshot");
+        assert_eq!(scene.name, "Snapshot");
+        assert_eq!(scene.entities.len(), 2);
+
+        let find = |name: &str| scene.entities.iter().find(|e| e.name.as_deref() == Some(name)).unwrap();
+        let base = find("base");
+        assert_eq!(base.parent, None);
+        assert_eq!(base.tags, vec!["static".to_string()]);
+        assert_eq!(base.transform.position, Vec4::new(1.0, 0.0, 0.0, 3.0));
+        assert!(matches!(base.shape, ShapeTemplate::Tesseract { size } if size == 2.0));
+
+        // The child keeps its local transform and names its parent
+        let arm = find("arm");
+        assert_eq!(arm.parent.as_deref(), Some("base"));
+        assert_eq!(arm.transform.position, Vec4::new(0.0, 2.0, 0.0, 0.0));
+        assert_eq!(arm.material.base_color, Material::BLUE.base_color);
+    }
+
+    #[test]
+    fn test_from_world_skips_shapes_without_template() {
+        use crate::{Entity, ShapeRef};
+        use rust4d_math::Tesseract4D;
+
+        let mut world = World::new();
+        let parent = world.add_entity(Entity::new(ShapeRef::shared(Tesseract4D::new(1.0))).with_name("raw"));
+        let child = EntityTemplate::new(
+            ShapeTemplate::tesseract(1.0),
+            Transform4D::from_position(Vec4::new(0.0, 1.0, 0.0, 0.0)),
+            Material::GRAY,
+        );
+        let child = world.add_entity(child.to_entity());
+        world.set_entity_position(parent, Vec4::new(5.0, 0.0, 0.0, 0.0));
+        world.add_child(parent, child).unwrap();
+
+        // The parent can't be saved, so the child becomes a root where it is
+        let scene = Scene::from_world(&world, "Partial");
+        assert_eq!(scene.entities.len(), 1);
+        assert_eq!(scene.entities[0].parent, None);
+        assert_eq!(scene.entities[0].transform.position, Vec4::new(5.0, 1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_from_world_round_trips_instantiated_scene() {
+        let mut template = Scene::new("Round Trip").with_gravity(-9.8);
+        template.add_entity(
+            EntityTemplate::new(ShapeTemplate::hyperplane(-2.0, 10.0, 4, 5.0, 0.001), Transform4D::identity(), Material::GRAY)
+                .with_name("floor")
+                .with_tag("static"),
+        );
+        template.add_entity(
+            EntityTemplate::new(
+                ShapeTemplate::tesseract(1.0),
+                Transform4D::from_position(Vec4::new(0.0, 1.0, 0.0, 0.0)),
+                Material::RED,
+            )
+            .with_name("lamp")
+            .with_parent("floor"),
+        );
+        let ron = template.to_ron_string().unwrap();
+
+        let loaded: Scene = ron::from_str(&ron).unwrap();
+        let active = ActiveScene::from_template(&loaded, None, 0.5);
+        let saved = Scene::from_world(&active.world, "Round Trip");
+
+        assert_eq!(saved.gravity, Some(-9.8));
+        assert_eq!(saved.to_ron_string().unwrap(), ron);
+    }
+
+    #[test]
+    fn test_from_world_keeps_sideways_gravity() {
+        let gravity = Vec4::new(3.0, -9.0, 0.0, -4.0);
+        let world = World::new().with_physics(PhysicsConfig::new(0.0).with_gravity_vector(gravity));
+
+        let scene = Scene::from_world(&world, "Tilted");
+        assert_eq!(scene.gravity, None);
+        let loaded: Scene = ron::from_str(&scene.to_ron_string().unwrap()).unwrap();
+        let active = ActiveScene::from_template(&loaded, None, 0.5);
+        assert_eq!(active.world.physics().unwrap().config.gravity_vector, gravity);
+    }
 }
@@ -23,7 +23,7 @@
 
 use std::collections::HashMap;
 use crate::{Scene, World};
-use crate::scene::{SceneError, ActiveScene};
+use crate::scene::{SceneError, ActiveScene, MergePolicy, MergeReport};
 use crate::scene_transition::{SceneTransition, TransitionEffect};
 use crate::scene_loader::SceneLoader;
 use rust4d_physics::PhysicsConfig;
@@ -46,10 +46,16 @@ pub struct SceneManager {
     default_physics: Option<PhysicsConfig>,
     /// Player collision radius for scene instantiation
     player_radius: f32,
+    /// Player capsule half-height for scene instantiation (0.0 = sphere)
+    player_half_height: f32,
+    /// Gap to leave when lifting a player spawned inside the floor (None = don't lift)
+    spawn_clearance: Option<f32>,
     /// Active transition between scenes
     transition: Option<SceneTransition>,
     /// Overlay scene names (rendered on top of active scene)
     overlay_stack: Vec<String>,
+    /// Whether each overlay (parallel to `overlay_stack`) blocks gameplay input
+    overlay_modal: Vec<bool>,
     /// Background scene loader
     loader: SceneLoader,
 }
@@ -69,8 +75,11 @@ impl SceneManager {
             active_stack: Vec::new(),
             default_physics: None,
             player_radius: 0.5,
+            player_half_height: 0.0,
+            spawn_clearance: None,
             transition: None,
             overlay_stack: Vec::new(),
+            overlay_modal: Vec::new(),
             loader: SceneLoader::new(),
         }
     }
@@ -87,6 +96,23 @@ impl SceneManager {
         self
     }
 
+    /// Make the player an upright capsule instead of a sphere
+    ///
+    /// See [`ActiveScene::from_template_with_capsule`].
+    pub fn with_player_half_height(mut self, half_height: f32) -> Self {
+        self.player_half_height = half_height;
+        self
+    }
+
+    /// Lift players spawned inside the floor on instantiation
+    ///
+    /// The player is raised to rest `clearance` above the floor under its
+    /// spawn (see [`ActiveScene::snap_player_spawn`]).
+    pub fn with_spawn_snap(mut self, clearance: f32) -> Self {
+        self.spawn_clearance = Some(clearance);
+        self
+    }
+
     // --- Template management ---
 
     /// Load a scene template from a RON file
@@ -121,16 +147,41 @@ impl SceneManager {
     /// Instantiate a runtime scene from a loaded template
     ///
     /// The instantiated scene is stored but not automatically made active.
-    /// Use `push_scene` to make it the current scene.
+    /// Use `push_scene` to make it the current scene. Entities are created
+    /// parents first (see [`Scene::spawn_order`]); fails if an entity's
+    /// parent doesn't exist or parent links form a cycle.
     pub fn instantiate(&mut self, template_name: &str) -> Result<(), SceneError> {
         let template = self.templates.get(template_name)
             .ok_or_else(|| SceneError::NotLoaded(template_name.to_string()))?;
+        template.spawn_order()?;
 
-        let active = ActiveScene::from_template(template, self.default_physics.clone(), self.player_radius);
+        let mut active = ActiveScene::from_template_with_capsule(
+            template,
+            self.default_physics.clone(),
+            self.player_radius,
+            self.player_half_height,
+        );
+        if let Some(clearance) = self.spawn_clearance {
+            if active.snap_player_spawn(clearance) {
+                log::info!("Lifted player spawn in '{}' onto the floor: {:?}", template_name, active.player_spawn);
+            }
+        }
         self.scenes.insert(template_name.to_string(), active);
         Ok(())
     }
 
+    /// Add a loaded template's entities to the active scene
+    ///
+    /// Name collisions with entities already in the scene are resolved by
+    /// `policy` (see [`ActiveScene::merge_template`]).
+    pub fn load_additive(&mut self, template_name: &str, policy: MergePolicy) -> Result<MergeReport, SceneError> {
+        let template = self.templates.get(template_name)
+            .ok_or_else(|| SceneError::NotLoaded(template_name.to_string()))?;
+        let name = self.active_stack.last().ok_or(SceneError::NoActiveScene)?;
+        let active = self.scenes.get_mut(name).ok_or(SceneError::NoActiveScene)?;
+        Ok(active.merge_template(template, policy))
+    }
+
     // --- Scene stack ---
 
     /// Push a scene onto the stack, making it the active scene
@@ -229,6 +280,21 @@ impl SceneManager {
         }
     }
 
+    /// Reset the active scene's entities to their template state
+    ///
+    /// Re-applies the template transforms and zeroes physics body velocities
+    /// in place (see [`ActiveScene::reset_to_template`]). Nothing is re-read
+    /// from disk and the world, entity keys and geometry are kept.
+    pub fn reset_physics(&mut self) -> Result<(), SceneError> {
+        let name = self.active_stack.last().ok_or(SceneError::NoActiveScene)?;
+        let template = self.templates.get(name)
+            .ok_or_else(|| SceneError::NotLoaded(name.clone()))?;
+        let scene = self.scenes.get_mut(name)
+            .ok_or_else(|| SceneError::NotLoaded(name.clone()))?;
+        scene.reset_to_template(template);
+        Ok(())
+    }
+
     // --- Transitions ---
 
     /// Switch to a scene with a transition effect
@@ -294,10 +360,24 @@ impl SceneManager {
     /// rendered on top of the active scene. The scene must already be
     /// instantiated or registered.
     pub fn push_overlay(&mut self, name: &str) -> Result<(), SceneError> {
+        self.push_overlay_inner(name, false)
+    }
+
+    /// Push a modal overlay scene (pause menu, dialog)
+    ///
+    /// Like [`push_overlay`](Self::push_overlay), but while it is on the stack
+    /// [`has_modal_overlay`](Self::has_modal_overlay) returns true so gameplay
+    /// input (mouse look) can be gated off.
+    pub fn push_modal_overlay(&mut self, name: &str) -> Result<(), SceneError> {
+        self.push_overlay_inner(name, true)
+    }
+
+    fn push_overlay_inner(&mut self, name: &str, modal: bool) -> Result<(), SceneError> {
         if !self.scenes.contains_key(name) {
             return Err(SceneError::NotLoaded(name.to_string()));
         }
         self.overlay_stack.push(name.to_string());
+        self.overlay_modal.push(modal);
         Ok(())
     }
 
@@ -305,9 +385,15 @@ impl SceneManager {
     ///
     /// Returns the name of the popped overlay, or None if the overlay stack is empty.
     pub fn pop_overlay(&mut self) -> Option<String> {
+        self.overlay_modal.pop();
         self.overlay_stack.pop()
     }
 
+    /// Check if any modal overlay is on the overlay stack
+    pub fn has_modal_overlay(&self) -> bool {
+        self.overlay_modal.iter().any(|&modal| modal)
+    }
+
     /// Get the overlay stack
     pub fn overlays(&self) -> &[String] {
         &self.overlay_stack
@@ -318,6 +404,32 @@ impl SceneManager {
         self.overlay_stack.iter().any(|n| n == name)
     }
 
+    // --- Introspection ---
+
+    /// Names of all loaded templates, sorted
+    pub fn loaded_templates(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.templates.keys().map(|s| s.as_str()).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Names of all instantiated (runtime) scenes, sorted
+    pub fn instantiated_scenes(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.scenes.keys().map(|s| s.as_str()).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// The scene stack, bottom first (the active scene is last)
+    pub fn active_stack(&self) -> &[String] {
+        &self.active_stack
+    }
+
+    /// The overlay stack, bottom first (the topmost overlay is last)
+    pub fn overlay_stack(&self) -> &[String] {
+        &self.overlay_stack
+    }
+
     // --- Async Loading ---
 
     /// Start loading a scene in the background
@@ -356,7 +468,7 @@ impl SceneManager {
 mod tests {
     use super::*;
     use crate::{Entity, ShapeRef};
-    use rust4d_math::Tesseract4D;
+    use rust4d_math::{Tesseract4D, Vec4};
 
     fn make_test_entity() -> Entity {
         let tesseract = Tesseract4D::new(2.0);
@@ -375,7 +487,7 @@ mod tests {
         let manager = SceneManager::new()
             .with_physics(PhysicsConfig::new(-20.0));
         assert!(manager.default_physics.is_some());
-        assert_eq!(manager.default_physics.unwrap().gravity, -20.0);
+        assert_eq!(manager.default_physics.unwrap().gravity_vector.y, -20.0);
     }
 
     #[test]
@@ -597,6 +709,217 @@ mod tests {
         assert_eq!(scene.unwrap().player_spawn, Some([0.0, 1.0, 5.0, 0.0]));
     }
 
+    fn floor_template(spawn_y: f32) -> Scene {
+        use crate::{EntityTemplate, Material, ShapeTemplate, Transform4D};
+
+        let mut template = Scene::new("Floor")
+            .with_gravity(-10.0)
+            .with_player_spawn(1.0, spawn_y, 2.0, 0.0);
+        template.add_entity(
+            EntityTemplate::new(
+                ShapeTemplate::hyperplane(0.0, 10.0, 10, 5.0, 0.001),
+                Transform4D::identity(),
+                Material::GRAY,
+            )
+            .with_tag("static"),
+        );
+        template
+    }
+
+    #[test]
+    fn test_spawn_below_floor_is_lifted_onto_it() {
+        let mut manager = SceneManager::new().with_player_radius(0.5).with_spawn_snap(0.1);
+        manager.register_template(floor_template(-1.0));
+        manager.instantiate("Floor").unwrap();
+
+        let scene = manager.get_scene("Floor").unwrap();
+        let expected = Vec4::new(1.0, 0.6, 2.0, 0.0);
+        assert_eq!(scene.player_spawn, Some([1.0, 0.6, 2.0, 0.0]));
+        assert_eq!(scene.world.physics().unwrap().player_position(), Some(expected));
+    }
+
+    #[test]
+    fn test_spawn_above_floor_is_unchanged() {
+        let mut manager = SceneManager::new().with_spawn_snap(0.1);
+        manager.register_template(floor_template(3.0));
+        manager.instantiate("Floor").unwrap();
+
+        let scene = manager.get_scene("Floor").unwrap();
+        assert_eq!(scene.player_spawn, Some([1.0, 3.0, 2.0, 0.0]));
+        assert_eq!(scene.world.physics().unwrap().player_position(), Some(Vec4::new(1.0, 3.0, 2.0, 0.0)));
+    }
+
+    #[test]
+    fn test_spawn_snap_is_off_by_default() {
+        let mut manager = SceneManager::new();
+        manager.register_template(floor_template(-1.0));
+        manager.instantiate("Floor").unwrap();
+        assert_eq!(manager.get_scene("Floor").unwrap().player_spawn, Some([1.0, -1.0, 2.0, 0.0]));
+    }
+
+    /// A template of cubes, each `(name, parent)`, listed in the given order
+    fn hierarchy_template(entities: &[(&str, Option<&str>)]) -> Scene {
+        use crate::{EntityTemplate, Material, ShapeTemplate, Transform4D};
+
+        let mut template = Scene::new("Hierarchy");
+        for (name, parent) in entities {
+            let mut entity = EntityTemplate::new(ShapeTemplate::tesseract(1.0), Transform4D::identity(), Material::GRAY)
+                .with_name(*name);
+            if let Some(parent) = parent {
+                entity = entity.with_parent(*parent);
+            }
+            template.add_entity(entity);
+        }
+        template
+    }
+
+    #[test]
+    fn test_child_listed_before_parent_is_linked() {
+        let mut manager = SceneManager::new();
+        manager.register_template(hierarchy_template(&[
+            ("wheel", Some("car")),
+            ("car", None),
+            ("hubcap", Some("wheel")),
+        ]));
+        manager.instantiate("Hierarchy").unwrap();
+
+        let scene = manager.get_scene("Hierarchy").unwrap();
+        let world = &scene.world;
+        let (car, _) = world.get_by_name("car").unwrap();
+        let (wheel, _) = world.get_by_name("wheel").unwrap();
+        let (hubcap, _) = world.get_by_name("hubcap").unwrap();
+        assert_eq!(world.parent_of(wheel), Some(car));
+        assert_eq!(world.parent_of(hubcap), Some(wheel));
+        assert_eq!(world.parent_of(car), None);
+
+        // Template entities stay in template order; keys follow spawn order
+        assert_eq!(scene.template_entities, vec![wheel, car, hubcap]);
+        assert!(car < wheel && wheel < hubcap);
+    }
+
+    #[test]
+    fn test_parent_cycle_fails_to_instantiate() {
+        let mut manager = SceneManager::new();
+        manager.register_template(hierarchy_template(&[
+            ("root", None),
+            ("a", Some("b")),
+            ("b", Some("a")),
+        ]));
+        assert!(matches!(manager.instantiate("Hierarchy"), Err(SceneError::ParentCycle(_))));
+        assert!(manager.get_scene("Hierarchy").is_none());
+
+        manager.register_template(hierarchy_template(&[("a", Some("missing"))]));
+        assert!(matches!(manager.instantiate("Hierarchy"), Err(SceneError::UnknownParent(name)) if name == "missing"));
+    }
+
+    fn reset_test_manager() -> SceneManager {
+        use crate::{EntityTemplate, Material, ShapeTemplate, Transform4D};
+
+        let mut template = Scene::new("Reset").with_gravity(-10.0);
+        template.add_entity(
+            EntityTemplate::new(
+                ShapeTemplate::tesseract(1.0),
+                Transform4D::from_position(Vec4::new(0.0, 5.0, 0.0, 0.0)),
+                Material::WHITE,
+            )
+            .with_name("crate")
+            .with_tag("dynamic"),
+        );
+        template.add_entity(
+            EntityTemplate::new(
+                ShapeTemplate::tesseract(1.0),
+                Transform4D::from_position(Vec4::new(3.0, 1.0, 0.0, 2.0)),
+                Material::WHITE,
+            )
+            .with_name("decor"),
+        );
+
+        let mut manager = SceneManager::new();
+        manager.register_template(template);
+        manager.instantiate("Reset").unwrap();
+        manager.push_scene("Reset").unwrap();
+        manager
+    }
+
+    #[test]
+    fn test_reset_physics_restores_template_state() {
+        let mut manager = reset_test_manager();
+        let keys = manager.active_scene().unwrap().template_entities.clone();
+        assert_eq!(keys.len(), 2);
+
+        // Let the crate fall and move the decor by hand
+        for _ in 0..30 {
+            manager.update(1.0 / 60.0);
+        }
+        let world = manager.active_world_mut().unwrap();
+        world.get_entity_mut(keys[1]).unwrap().set_position(Vec4::new(-4.0, 0.0, 0.0, 0.0));
+        let crate_body = world.get_entity(keys[0]).unwrap().physics_body.unwrap();
+        assert!(world.get_entity(keys[0]).unwrap().transform.position.y < 5.0);
+        let world_ptr = world as *const World;
+
+        manager.reset_physics().unwrap();
+
+        let world = manager.active_world().unwrap();
+        assert!(std::ptr::eq(world, world_ptr));
+        assert_eq!(world.get_entity(keys[0]).unwrap().transform.position, Vec4::new(0.0, 5.0, 0.0, 0.0));
+        assert_eq!(world.get_entity(keys[1]).unwrap().transform.position, Vec4::new(3.0, 1.0, 0.0, 2.0));
+
+        let body = world.physics().unwrap().get_body(crate_body).unwrap();
+        assert_eq!(body.position, Vec4::new(0.0, 5.0, 0.0, 0.0));
+        assert_eq!(body.velocity, Vec4::ZERO);
+        assert_eq!(world.get_by_name("crate").map(|(key, _)| key), Some(keys[0]));
+    }
+
+    #[test]
+    fn test_reset_physics_requires_template() {
+        let mut manager = SceneManager::new();
+        assert!(matches!(manager.reset_physics(), Err(SceneError::NoActiveScene)));
+
+        manager.register_active_scene("adhoc", ActiveScene::new("Adhoc"));
+        manager.push_scene("adhoc").unwrap();
+        assert!(matches!(manager.reset_physics(), Err(SceneError::NotLoaded(_))));
+    }
+
+    #[test]
+    fn test_load_additive_overwrite_replaces_body() {
+        use crate::{EntityTemplate, Material, ShapeTemplate, Transform4D};
+
+        let mut manager = reset_test_manager();
+        let mut props = Scene::new("Props");
+        props.add_entity(
+            EntityTemplate::new(
+                ShapeTemplate::tesseract(1.0),
+                Transform4D::from_position(Vec4::new(8.0, 5.0, 0.0, 0.0)),
+                Material::WHITE,
+            )
+            .with_name("crate")
+            .with_tag("dynamic"),
+        );
+        manager.register_template(props);
+        let old_body = manager.active_world().unwrap().get_by_name("crate").unwrap().1.physics_body.unwrap();
+
+        let report = manager.load_additive("Props", MergePolicy::Overwrite).unwrap();
+
+        assert_eq!(report.overwritten, vec!["crate".to_string()]);
+        let world = manager.active_world().unwrap();
+        assert_eq!(world.entity_count(), 2);
+        let (key, entity) = world.get_by_name("crate").unwrap();
+        assert_eq!(key, report.added[0]);
+        assert_eq!(entity.transform.position.x, 8.0);
+        let physics = world.physics().unwrap();
+        assert!(physics.get_body(old_body).is_none());
+        assert!(physics.get_body(entity.physics_body.unwrap()).is_some());
+    }
+
+    #[test]
+    fn test_load_additive_errors() {
+        let mut manager = SceneManager::new();
+        assert!(matches!(manager.load_additive("Missing", MergePolicy::Rename), Err(SceneError::NotLoaded(_))));
+
+        manager.register_template(Scene::new("Props"));
+        assert!(matches!(manager.load_additive("Props", MergePolicy::Rename), Err(SceneError::NoActiveScene)));
+    }
+
     #[test]
     fn test_default() {
         let manager = SceneManager::default();
@@ -741,6 +1064,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_modal_overlay_tracking() {
+        let mut manager = SceneManager::new();
+        manager.register_active_scene("hud", ActiveScene::new("HUD"));
+        manager.register_active_scene("pause", ActiveScene::new("Pause"));
+
+        manager.push_overlay("hud").unwrap();
+        assert!(!manager.has_modal_overlay());
+
+        manager.push_modal_overlay("pause").unwrap();
+        assert!(manager.has_modal_overlay());
+        assert_eq!(manager.overlays().len(), 2);
+
+        manager.pop_overlay();
+        assert!(!manager.has_modal_overlay());
+        assert_eq!(manager.overlays(), ["hud".to_string()]);
+    }
+
     #[test]
     fn test_overlays_accessor() {
         let mut manager = SceneManager::new();
@@ -775,6 +1116,45 @@ mod tests {
         assert!(!manager.is_overlay("nonexistent"));
     }
 
+    // --- Introspection tests ---
+
+    #[test]
+    fn test_introspection_reports_state() {
+        let mut manager = SceneManager::new();
+        manager.register_template(Scene::new("level"));
+        manager.register_template(Scene::new("hud"));
+        manager.instantiate("level").unwrap();
+        manager.instantiate("hud").unwrap();
+        manager.register_active_scene("menu", ActiveScene::new("Menu"));
+        manager.push_scene("level").unwrap();
+        manager.push_overlay("hud").unwrap();
+
+        assert_eq!(manager.loaded_templates(), vec!["hud", "level"]);
+        assert_eq!(manager.instantiated_scenes(), vec!["hud", "level", "menu"]);
+        assert_eq!(manager.active_stack(), ["level".to_string()]);
+        assert_eq!(manager.overlay_stack(), ["hud".to_string()]);
+    }
+
+    #[test]
+    fn test_introspection_empty_manager() {
+        let manager = SceneManager::new();
+        assert!(manager.loaded_templates().is_empty());
+        assert!(manager.instantiated_scenes().is_empty());
+        assert!(manager.active_stack().is_empty());
+        assert!(manager.overlay_stack().is_empty());
+    }
+
+    #[test]
+    fn test_active_stack_order() {
+        let mut manager = SceneManager::new();
+        manager.register_active_scene("game", ActiveScene::new("Game"));
+        manager.register_active_scene("pause", ActiveScene::new("Pause"));
+        manager.push_scene("game").unwrap();
+        manager.push_scene("pause").unwrap();
+
+        assert_eq!(manager.active_stack(), ["game".to_string(), "pause".to_string()]);
+    }
+
     // --- Async loading tests ---
 
     #[test]
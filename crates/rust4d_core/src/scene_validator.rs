@@ -1,12 +1,15 @@
 //! Scene validation
 //!
 //! Validates scenes for common errors before runtime. The [`SceneValidator`]
-//! checks for issues like empty scenes, duplicate entity names, unreasonable
-//! physics values, and extreme spawn positions.
+//! checks for issues like empty scenes, duplicate entity names, shapes that
+//! cannot be built, broken parent links, unreasonable physics values, and bad
+//! spawn positions.
 
 use std::collections::HashSet;
 
-use crate::scene::Scene;
+use crate::entity::EntityTemplate;
+use crate::scene::{Scene, SceneError};
+use crate::shapes::ShapeTemplate;
 
 /// Validation error found in a scene
 #[derive(Debug, Clone, PartialEq)]
@@ -17,10 +20,28 @@ pub enum ValidationError {
     DuplicateName(String),
     /// Entity has no shape (defensive check - shouldn't happen with current types)
     MissingShape(String),
-    /// Gravity value seems unreasonable (absolute value > 1000)
+    /// Gravity value seems unreasonable (absolute value, or vector length, > 1000)
     UnreasonableGravity(f32),
     /// Player spawn is at extreme coordinates (any component absolute value > 10000)
     ExtremeSpawnPosition([f32; 4]),
+    /// Player spawn has a NaN or infinite component
+    NonFiniteSpawn([f32; 4]),
+    /// Entity's shape template cannot produce a valid shape
+    InvalidShape {
+        /// Entity name, or `#index` for unnamed entities
+        entity: String,
+        /// What is wrong with the shape parameters
+        reason: String,
+    },
+    /// Entity's parent name matches no entity in the scene
+    UnknownParent {
+        /// Entity name, or `#index` for unnamed entities
+        entity: String,
+        /// The parent name that was not found
+        parent: String,
+    },
+    /// Entity is its own ancestor through its parent links
+    ParentCycle(String),
 }
 
 impl std::fmt::Display for ValidationError {
@@ -43,6 +64,22 @@ impl std::fmt::Display for ValidationError {
                     pos[0], pos[1], pos[2], pos[3]
                 )
             }
+            ValidationError::NonFiniteSpawn(pos) => {
+                write!(
+                    f,
+                    "Non-finite spawn position: [{}, {}, {}, {}]",
+                    pos[0], pos[1], pos[2], pos[3]
+                )
+            }
+            ValidationError::InvalidShape { entity, reason } => {
+                write!(f, "Entity '{}' has an invalid shape: {}", entity, reason)
+            }
+            ValidationError::UnknownParent { entity, parent } => {
+                write!(f, "Entity '{}' has unknown parent '{}'", entity, parent)
+            }
+            ValidationError::ParentCycle(name) => {
+                write!(f, "Parent cycle through entity '{}'", name)
+            }
         }
     }
 }
@@ -90,15 +127,48 @@ impl SceneValidator {
             }
         }
 
+        // Check that every shape template can be built
+        for (index, entity) in scene.entities.iter().enumerate() {
+            if let Some(reason) = shape_problem(&entity.shape) {
+                errors.push(ValidationError::InvalidShape {
+                    entity: entity_label(index, entity),
+                    reason,
+                });
+            }
+        }
+
+        // Check parent links the same way instantiation orders entities
+        match scene.spawn_order() {
+            Err(SceneError::UnknownParent(parent)) => {
+                for (index, entity) in scene.entities.iter().enumerate() {
+                    if entity.parent.as_deref() == Some(parent.as_str()) {
+                        errors.push(ValidationError::UnknownParent {
+                            entity: entity_label(index, entity),
+                            parent: parent.clone(),
+                        });
+                    }
+                }
+            }
+            Err(SceneError::ParentCycle(name)) => errors.push(ValidationError::ParentCycle(name)),
+            _ => {}
+        }
+
         // Check for unreasonable gravity
-        if let Some(gravity) = scene.gravity {
+        let gravity = match scene.gravity_acceleration() {
+            Some(vector) if scene.gravity_vector.is_some() => Some(vector.length()),
+            _ => scene.gravity,
+        };
+        if let Some(gravity) = gravity {
             if gravity.abs() > 1000.0 {
                 errors.push(ValidationError::UnreasonableGravity(gravity));
             }
         }
 
-        // Check for extreme spawn position
+        // Check for non-finite or extreme spawn position
         if let Some(spawn) = scene.player_spawn {
+            if spawn.iter().any(|c| !c.is_finite()) {
+                errors.push(ValidationError::NonFiniteSpawn(spawn));
+            }
             let is_extreme = spawn.iter().any(|c| c.abs() > 10000.0);
             if is_extreme {
                 errors.push(ValidationError::ExtremeSpawnPosition(spawn));
@@ -122,12 +192,39 @@ impl SceneValidator {
     }
 }
 
+/// Name used to identify an entity in validation errors
+fn entity_label(index: usize, entity: &EntityTemplate) -> String {
+    entity.name.clone().unwrap_or_else(|| format!("#{}", index))
+}
+
+/// Describe why a shape template cannot produce a usable shape, if it can't
+fn shape_problem(shape: &ShapeTemplate) -> Option<String> {
+    let positive = |field: &str, value: f32| {
+        (!value.is_finite() || value <= 0.0)
+            .then(|| format!("{} must be positive and finite (got {})", field, value))
+    };
+
+    match *shape {
+        ShapeTemplate::Tesseract { size } => positive("size", size),
+        ShapeTemplate::Hyperplane { y, size, subdivisions, cell_size, thickness } => {
+            if !y.is_finite() {
+                return Some(format!("y must be finite (got {})", y));
+            }
+            if subdivisions == 0 {
+                return Some("subdivisions must be at least 1".to_string());
+            }
+            positive("size", size)
+                .or_else(|| positive("cell_size", cell_size))
+                .or_else(|| positive("thickness", thickness))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::entity::EntityTemplate;
-    use crate::shapes::ShapeTemplate;
     use crate::{Transform4D, Material};
+    use rust4d_math::Vec4;
 
     fn make_valid_scene() -> Scene {
         let mut scene = Scene::new("Valid Scene")
@@ -209,6 +306,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_unreasonable_gravity_vector_detected() {
+        let scene = Scene::new("Sideways").with_gravity_vector(Vec4::new(3000.0, 0.0, 0.0, -4000.0));
+
+        let errors = SceneValidator::validate(&scene);
+        assert!(
+            errors.contains(&ValidationError::UnreasonableGravity(5000.0)),
+            "Expected UnreasonableGravity, got: {:?}",
+            errors
+        );
+    }
+
     #[test]
     fn test_reasonable_gravity_no_error() {
         let mut scene = Scene::new("Normal").with_gravity(-20.0);
@@ -321,4 +430,128 @@ mod tests {
                 .contains("1, 2, 3, 4")
         );
     }
+
+    #[test]
+    fn test_invalid_shape_names_entity() {
+        let mut scene = make_valid_scene();
+        scene.add_entity(
+            EntityTemplate::new(
+                ShapeTemplate::tesseract(0.0),
+                Transform4D::identity(),
+                Material::WHITE,
+            )
+            .with_name("flat"),
+        );
+        scene.add_entity(EntityTemplate::new(
+            ShapeTemplate::hyperplane(-2.0, 10.0, 0, 5.0, 0.001),
+            Transform4D::identity(),
+            Material::GRAY,
+        ));
+
+        let errors = SceneValidator::validate(&scene);
+        assert_eq!(errors.len(), 2, "got: {:?}", errors);
+        let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages[0],
+            "Entity 'flat' has an invalid shape: size must be positive and finite (got 0)"
+        );
+        assert_eq!(
+            messages[1],
+            "Entity '#2' has an invalid shape: subdivisions must be at least 1"
+        );
+    }
+
+    #[test]
+    fn test_unknown_parent_names_entity() {
+        let mut scene = make_valid_scene();
+        scene.add_entity(
+            EntityTemplate::new(ShapeTemplate::tesseract(1.0), Transform4D::identity(), Material::WHITE)
+                .with_name("lid")
+                .with_parent("crate"),
+        );
+
+        let errors = SceneValidator::validate(&scene);
+        assert_eq!(
+            errors,
+            vec![ValidationError::UnknownParent { entity: "lid".to_string(), parent: "crate".to_string() }]
+        );
+        assert_eq!(errors[0].to_string(), "Entity 'lid' has unknown parent 'crate'");
+    }
+
+    #[test]
+    fn test_parent_cycle_detected() {
+        let mut scene = make_valid_scene();
+        for (name, parent) in [("a", "b"), ("b", "a")] {
+            scene.add_entity(
+                EntityTemplate::new(ShapeTemplate::tesseract(1.0), Transform4D::identity(), Material::WHITE)
+                    .with_name(name)
+                    .with_parent(parent),
+            );
+        }
+
+        let errors = SceneValidator::validate(&scene);
+        assert!(matches!(&errors[..], [ValidationError::ParentCycle(_)]), "got: {:?}", errors);
+    }
+
+    #[test]
+    fn test_resolved_parents_pass() {
+        let mut scene = make_valid_scene();
+        scene.add_entity(
+            EntityTemplate::new(ShapeTemplate::tesseract(1.0), Transform4D::identity(), Material::WHITE)
+                .with_name("crate"),
+        );
+        scene.add_entity(
+            EntityTemplate::new(ShapeTemplate::tesseract(1.0), Transform4D::identity(), Material::WHITE)
+                .with_parent("crate"),
+        );
+        assert!(SceneValidator::validate(&scene).is_empty());
+    }
+
+    #[test]
+    fn test_non_finite_shape_parameter_detected() {
+        let mut scene = Scene::new("NaN Floor");
+        scene.add_entity(
+            EntityTemplate::new(
+                ShapeTemplate::hyperplane(-2.0, 10.0, 4, f32::NAN, 0.001),
+                Transform4D::identity(),
+                Material::GRAY,
+            )
+            .with_name("floor"),
+        );
+
+        let errors = SceneValidator::validate(&scene);
+        assert!(
+            matches!(
+                &errors[..],
+                [ValidationError::InvalidShape { entity, reason }]
+                    if entity == "floor" && reason.starts_with("cell_size")
+            ),
+            "got: {:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn test_valid_hyperplane_passes() {
+        let mut scene = make_valid_scene();
+        scene.add_entity(EntityTemplate::new(
+            ShapeTemplate::hyperplane(-2.0, 10.0, 10, 5.0, 0.001),
+            Transform4D::identity(),
+            Material::GRAY,
+        ));
+        assert!(SceneValidator::validate_or_error(&scene).is_ok());
+    }
+
+    #[test]
+    fn test_non_finite_spawn_detected() {
+        let mut scene = make_valid_scene();
+        scene.player_spawn = Some([0.0, f32::NAN, 0.0, 0.0]);
+
+        let errors = SceneValidator::validate(&scene);
+        assert!(
+            matches!(&errors[..], [ValidationError::NonFiniteSpawn(_)]),
+            "got: {:?}",
+            errors
+        );
+    }
 }
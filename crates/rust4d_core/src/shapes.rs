@@ -25,6 +25,7 @@ pub enum ShapeTemplate {
     /// Created centered at origin with vertices at ±(size/2) on each axis.
     Tesseract {
         /// Full side length of the tesseract
+        #[serde(serialize_with = "rust4d_math::float_format::scalar::serialize")]
         size: f32,
     },
     /// A floor/ground plane in 4D
@@ -34,14 +35,18 @@ pub enum ShapeTemplate {
     /// Use the entity transform to position the visual mesh.
     Hyperplane {
         /// Y-level for the physics collider (visual mesh uses entity transform)
+        #[serde(serialize_with = "rust4d_math::float_format::scalar::serialize")]
         y: f32,
         /// Half-extent in X and Z (total size is 2*size)
+        #[serde(serialize_with = "rust4d_math::float_format::scalar::serialize")]
         size: f32,
         /// Number of cells along each axis
         subdivisions: u32,
         /// Half-extent in W dimension (for slicing visibility)
+        #[serde(serialize_with = "rust4d_math::float_format::scalar::serialize")]
         cell_size: f32,
         /// Y thickness (bottom at y=0 in local space)
+        #[serde(serialize_with = "rust4d_math::float_format::scalar::serialize")]
         thickness: f32,
     },
 }
@@ -0,0 +1,114 @@
+//! Deterministic hashing of world state
+//!
+//! [`World::state_hash`](crate::World::state_hash) folds entity and body state
+//! through [`StateHasher`], a 64-bit FNV-1a hasher. Unlike the std hashers its
+//! output is fixed across processes, platforms and Rust versions, so hashes
+//! can be compared between peers or stored as regression fixtures.
+
+use rust4d_math::{Bivector4, Rotor4, Vec4};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 64-bit FNV-1a hasher over little-endian values
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct StateHasher(u64);
+
+impl StateHasher {
+    pub fn new() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+
+    pub fn finish(self) -> u64 {
+        self.0
+    }
+
+    pub fn bytes(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 ^ b as u64).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    pub fn u8(&mut self, value: u8) {
+        self.bytes(&[value]);
+    }
+
+    pub fn u32(&mut self, value: u32) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    pub fn u64(&mut self, value: u64) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    pub fn bool(&mut self, value: bool) {
+        self.u8(value as u8);
+    }
+
+    /// Hash the exact bits, with `-0.0` folded into `0.0` and every NaN alike
+    pub fn f32(&mut self, value: f32) {
+        let value = if value == 0.0 {
+            0.0
+        } else if value.is_nan() {
+            f32::NAN
+        } else {
+            value
+        };
+        self.u32(value.to_bits());
+    }
+
+    /// Length-prefixed, so adjacent strings can't run together
+    pub fn str(&mut self, value: &str) {
+        self.u64(value.len() as u64);
+        self.bytes(value.as_bytes());
+    }
+
+    pub fn option<T>(&mut self, value: Option<T>, hash: impl FnOnce(&mut Self, T)) {
+        self.bool(value.is_some());
+        if let Some(value) = value {
+            hash(self, value);
+        }
+    }
+
+    pub fn vec4(&mut self, v: Vec4) {
+        for c in [v.x, v.y, v.z, v.w] {
+            self.f32(c);
+        }
+    }
+
+    pub fn rotor4(&mut self, r: Rotor4) {
+        for c in [r.s, r.b_xy, r.b_xz, r.b_xw, r.b_yz, r.b_yw, r.b_zw, r.p] {
+            self.f32(c);
+        }
+    }
+
+    pub fn bivector4(&mut self, b: Bivector4) {
+        for c in [b.xy, b.xz, b.xw, b.yz, b.yw, b.zw] {
+            self.f32(c);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a_reference_values() {
+        assert_eq!(StateHasher::new().finish(), 0xcbf29ce484222325);
+        let mut h = StateHasher::new();
+        h.bytes(b"a");
+        assert_eq!(h.finish(), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn test_signed_zero_hashes_alike() {
+        let hash = |v: f32| {
+            let mut h = StateHasher::new();
+            h.f32(v);
+            h.finish()
+        };
+        assert_eq!(hash(0.0), hash(-0.0));
+        assert_ne!(hash(0.0), hash(1e-30));
+    }
+}
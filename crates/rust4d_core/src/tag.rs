@@ -0,0 +1,145 @@
+//! Interned entity tags
+//!
+//! Tags are interned into a [`TagRegistry`] so entities store small
+//! [`TagId`]s and tag queries compare integers instead of strings.
+//! A process-wide registry backs the string-based tag API on
+//! [`Entity`](crate::Entity) and [`World`](crate::World), so entities built
+//! outside a world still agree on ids.
+//!
+//! The registry is shared by every world in the process, behind a lock.
+//! String lookups such as [`Entity::has_tag`](crate::Entity::has_tag) take
+//! that lock and hash the string on every call, so hot loops should resolve
+//! the tag once with [`TagId::lookup`] and test entities with the lock-free
+//! [`Entity::has_tag_id`](crate::Entity::has_tag_id). Interned strings are
+//! never freed: every distinct tag ever interned stays for the life of the
+//! process, so tags generated without bound (one per spawned object, say)
+//! leak. Use components for per-object data instead.
+
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+/// Interned id of a tag string
+///
+/// Ids are only meaningful within one process; save tags as strings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TagId(u32);
+
+impl TagId {
+    /// Intern a tag in the global registry, returning its id
+    ///
+    /// The string is kept for the life of the process.
+    pub fn intern(tag: &str) -> Self {
+        if let Some(id) = Self::lookup(tag) {
+            return id;
+        }
+        TagRegistry::global()
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .intern(tag)
+    }
+
+    /// Look up a tag in the global registry without interning it
+    ///
+    /// A tag nothing was ever given has no id, and no entity has it.
+    pub fn lookup(tag: &str) -> Option<Self> {
+        TagRegistry::global()
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(tag)
+    }
+
+    /// Get the tag string for this id from the global registry
+    pub fn name(self) -> Option<String> {
+        TagRegistry::global()
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .name(self)
+            .map(str::to_owned)
+    }
+}
+
+/// Bidirectional mapping between tag strings and [`TagId`]s
+#[derive(Debug, Default)]
+pub(crate) struct TagRegistry {
+    /// Tag string to id
+    ids: HashMap<String, TagId>,
+    /// Tag strings indexed by id
+    names: Vec<String>,
+}
+
+impl TagRegistry {
+    /// Create an empty registry
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process-wide registry used by the string-based tag API
+    pub(crate) fn global() -> &'static RwLock<TagRegistry> {
+        static GLOBAL: OnceLock<RwLock<TagRegistry>> = OnceLock::new();
+        GLOBAL.get_or_init(|| RwLock::new(TagRegistry::new()))
+    }
+
+    /// Intern a tag, returning the existing id if it was already interned
+    pub(crate) fn intern(&mut self, tag: &str) -> TagId {
+        if let Some(&id) = self.ids.get(tag) {
+            return id;
+        }
+        let id = TagId(self.names.len() as u32);
+        self.names.push(tag.to_owned());
+        self.ids.insert(tag.to_owned(), id);
+        id
+    }
+
+    /// Get the id of a tag if it has been interned
+    pub(crate) fn get(&self, tag: &str) -> Option<TagId> {
+        self.ids.get(tag).copied()
+    }
+
+    /// Get the tag string for an id
+    pub(crate) fn name(&self, id: TagId) -> Option<&str> {
+        self.names.get(id.0 as usize).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern_same_tag_same_id() {
+        let mut registry = TagRegistry::new();
+        let a = registry.intern("enemy");
+        let b = registry.intern("enemy");
+        assert_eq!(a, b);
+        assert_eq!(registry.names.len(), 1);
+    }
+
+    #[test]
+    fn test_intern_different_tags_different_ids() {
+        let mut registry = TagRegistry::new();
+        let a = registry.intern("enemy");
+        let b = registry.intern("pickup");
+        assert_ne!(a, b);
+        assert_eq!(registry.name(a), Some("enemy"));
+        assert_eq!(registry.name(b), Some("pickup"));
+    }
+
+    #[test]
+    fn test_get_does_not_intern() {
+        let mut registry = TagRegistry::new();
+        assert!(registry.get("missing").is_none());
+        assert!(registry.names.is_empty());
+
+        let id = registry.intern("present");
+        assert_eq!(registry.get("present"), Some(id));
+    }
+
+    #[test]
+    fn test_global_intern_round_trip() {
+        let id = TagId::intern("tag_test_global");
+        assert_eq!(TagId::intern("tag_test_global"), id);
+        assert_eq!(TagId::lookup("tag_test_global"), Some(id));
+        assert_eq!(id.name().as_deref(), Some("tag_test_global"));
+        assert!(TagId::lookup("tag_test_never_interned").is_none());
+    }
+}
@@ -3,10 +3,15 @@
 //! The World manages all entities in the simulation, including parent-child
 //! entity hierarchy with cycle detection and recursive operations.
 
-use std::collections::{HashMap, VecDeque};
-use std::fmt;
-use crate::{Entity, DirtyFlags, Transform4D};
-use rust4d_physics::{PhysicsConfig, PhysicsWorld};
+use std::any::Any;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{self, Write as _};
+use std::sync::Arc;
+use crate::state_hash::StateHasher;
+use crate::{Entity, DirtyFlags, EmitterSample, Material, Palette, ShapeRef, Transform4D};
+use crate::tag::TagId;
+use rust4d_math::Vec4;
+use rust4d_physics::{ray_vs_aabb, BodyKey, PhysicsConfig, PhysicsWorld, RigidBody4D, AABB4D};
 use slotmap::{new_key_type, SlotMap};
 
 // Define generational key type for entities
@@ -42,6 +47,39 @@ impl fmt::Display for HierarchyError {
 
 impl std::error::Error for HierarchyError {}
 
+/// Emitted when an entity's lifetime runs out and it is despawned
+#[derive(Debug, Clone, PartialEq)]
+pub struct DespawnEvent {
+    /// The entity whose lifetime expired
+    pub entity: EntityKey,
+    /// Its name, if it had one
+    pub name: Option<String>,
+    /// Every entity removed with it (the entity first, then its descendants)
+    pub removed: Vec<EntityKey>,
+}
+
+/// Entities moved through [`World::set_entity_position`] together
+///
+/// Each call outside a [`World::batch_update`] produces a batch of one; a
+/// whole `batch_update` produces a single batch, so a consumer can size its
+/// buffers and react once per batch instead of once per entity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirtyBatch {
+    /// The moved entities, each listed once, in the order first moved
+    pub entities: Vec<EntityKey>,
+}
+
+/// Which entities [`World::inspect`] reports on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InspectQuery<'a> {
+    /// A single entity by key
+    Key(EntityKey),
+    /// A single entity by name
+    Name(&'a str),
+    /// Every entity with this tag
+    Tag(&'a str),
+}
+
 /// The 4D world containing all entities
 ///
 /// The World is the central container for all game objects.
@@ -58,6 +96,22 @@ pub struct World {
     parents: HashMap<EntityKey, EntityKey>,
     /// Children mapping: parent entity key -> list of child entity keys
     children_map: HashMap<EntityKey, Vec<EntityKey>>,
+    /// Monotonic frame counter, incremented on each `update`
+    frame: u64,
+    /// Body poses before and after the last physics step, per entity
+    step_poses: HashMap<EntityKey, (Transform4D, Transform4D)>,
+    /// Interpolated transforms for rendering (see `lerp_render_transforms`)
+    render_transforms: HashMap<EntityKey, Transform4D>,
+    /// Lifetime despawns since the last `drain_despawn_events`
+    despawn_events: Vec<DespawnEvent>,
+    /// Dirty batches since the last `drain_dirty_batches`
+    dirty_batches: Vec<DirtyBatch>,
+    /// Entities moved inside the running `batch_update`, if any
+    pending_batch: Option<Vec<EntityKey>>,
+    /// The entities in `pending_batch`, kept between batches to reuse its capacity
+    pending_members: HashSet<EntityKey>,
+    /// Size of the last batch, reserved up front for the next one
+    last_batch_len: usize,
 }
 
 impl Default for World {
@@ -75,6 +129,14 @@ impl World {
             physics_world: None,
             parents: HashMap::new(),
             children_map: HashMap::new(),
+            frame: 0,
+            step_poses: HashMap::new(),
+            render_transforms: HashMap::new(),
+            despawn_events: Vec::new(),
+            dirty_batches: Vec::new(),
+            pending_batch: None,
+            pending_members: HashSet::new(),
+            last_batch_len: 0,
         }
     }
 
@@ -86,6 +148,14 @@ impl World {
             physics_world: None,
             parents: HashMap::new(),
             children_map: HashMap::new(),
+            frame: 0,
+            step_poses: HashMap::new(),
+            render_transforms: HashMap::new(),
+            despawn_events: Vec::new(),
+            dirty_batches: Vec::new(),
+            pending_batch: None,
+            pending_members: HashSet::new(),
+            last_batch_len: 0,
         }
     }
 
@@ -106,7 +176,9 @@ impl World {
     }
 
     /// Add an entity to the world, returning its key
-    pub fn add_entity(&mut self, entity: Entity) -> EntityKey {
+    pub fn add_entity(&mut self, mut entity: Entity) -> EntityKey {
+        entity.set_modified_frame(self.frame);
+
         // Get the name before moving the entity
         let name = entity.name.clone();
         let key = self.entities.insert(entity);
@@ -131,7 +203,7 @@ impl World {
         if let Some(entity) = self.entities.remove(key) {
             // Clean up name index if the entity had a name
             if let Some(ref name) = entity.name {
-                self.name_index.remove(name);
+                self.unindex_name(name, key);
             }
 
             // Clean up physics body if present
@@ -170,10 +242,158 @@ impl World {
     }
 
     /// Get a mutable reference to an entity by key
+    ///
+    /// Don't change `name` through this reference; use [`rename_entity`](Self::rename_entity).
     pub fn get_entity_mut(&mut self, key: EntityKey) -> Option<&mut Entity> {
         self.entities.get_mut(key)
     }
 
+    /// Move an entity and report it in a [`DirtyBatch`]
+    ///
+    /// Marks the transform dirty like [`Entity::set_position`], and records
+    /// the move: as a batch of its own, or as part of the running
+    /// [`batch_update`](Self::batch_update). Returns false if the key is stale.
+    pub fn set_entity_position(&mut self, key: EntityKey, position: Vec4) -> bool {
+        let Some(entity) = self.entities.get_mut(key) else {
+            return false;
+        };
+        entity.set_position(position);
+        match &mut self.pending_batch {
+            Some(pending) => {
+                if self.pending_members.insert(key) {
+                    pending.push(key);
+                }
+            }
+            None => self.dirty_batches.push(DirtyBatch { entities: vec![key] }),
+        }
+        true
+    }
+
+    /// Round an entity's position to a grid (see [`Transform4D::snap_to_grid`])
+    ///
+    /// The move is reported like [`set_entity_position`](Self::set_entity_position),
+    /// and the entity's physics body, if any, is moved with it. Returns false
+    /// if the key is stale.
+    pub fn snap_entity_to_grid(&mut self, key: EntityKey, cell: Vec4) -> bool {
+        let Some(entity) = self.entities.get(key) else {
+            return false;
+        };
+        let position = entity.transform.snap_to_grid(cell).position;
+        let body_key = entity.physics_body;
+        self.set_entity_position(key, position);
+        self.place_body_at_entity(key);
+        if let Some(body) = body_key.and_then(|body_key| self.physics_world.as_mut()?.get_body_mut(body_key)) {
+            body.wake();
+        }
+        true
+    }
+
+    /// Spawn a mirror image of an entity across the hyperplane through the origin with normal `plane_normal`
+    ///
+    /// The copy is a root entity placed at the reflection of the original's
+    /// world transform (see [`Transform4D::reflected`]), with the same shape,
+    /// material and tags. It gets no name, physics body or components.
+    /// Returns `None` if the key is stale, or if the entity owns a shape
+    /// that was not built from a template and so cannot be copied.
+    pub fn mirror_entity(&mut self, key: EntityKey, plane_normal: Vec4) -> Option<EntityKey> {
+        let transform = self.world_transform(key)?.reflected(plane_normal);
+        let entity = self.entities.get(key)?;
+        let shape = match (&entity.shape, &entity.shape_template) {
+            (ShapeRef::Shared(shape), _) => ShapeRef::Shared(Arc::clone(shape)),
+            (ShapeRef::Owned(_), Some(template)) => ShapeRef::Owned(template.create_shape()),
+            (ShapeRef::Owned(_), None) => return None,
+        };
+
+        let mut mirrored = Entity::with_transform(shape, transform, entity.material);
+        mirrored.tags = entity.tags.clone();
+        mirrored.shape_template = entity.shape_template.clone();
+        mirrored.palette_color = entity.palette_color.clone();
+        Some(self.add_entity(mirrored))
+    }
+
+    /// Run a bulk update, reporting every move in it as one [`DirtyBatch`]
+    ///
+    /// Moves made with [`set_entity_position`](Self::set_entity_position)
+    /// inside `f` are collected and recorded together when it returns (if
+    /// there were any). Nested calls join the outer batch. Room for as many
+    /// entities as the last batch moved is reserved up front, so moving the
+    /// same group every frame allocates once.
+    pub fn batch_update<F: FnOnce(&mut World)>(&mut self, f: F) {
+        if self.pending_batch.is_some() {
+            f(self);
+            return;
+        }
+
+        self.pending_batch = Some(Vec::with_capacity(self.last_batch_len));
+        self.pending_members.reserve(self.last_batch_len);
+        f(self);
+        self.pending_members.clear();
+        if let Some(entities) = self.pending_batch.take().filter(|e| !e.is_empty()) {
+            self.last_batch_len = entities.len();
+            self.dirty_batches.push(DirtyBatch { entities });
+        }
+    }
+
+    /// Get the dirty batches recorded since the last drain
+    ///
+    /// Batches pile up until drained, so whoever moves entities with
+    /// [`set_entity_position`](Self::set_entity_position) should drain them
+    /// every frame.
+    pub fn dirty_batches(&self) -> &[DirtyBatch] {
+        &self.dirty_batches
+    }
+
+    /// Take the recorded dirty batches, clearing the list
+    pub fn drain_dirty_batches(&mut self) -> Vec<DirtyBatch> {
+        std::mem::take(&mut self.dirty_batches)
+    }
+
+    /// Rename an entity, keeping the name index in sync
+    ///
+    /// `None` removes the name. Like [`add_entity`](Self::add_entity), the
+    /// most recently named entity wins when names collide; when it gives the
+    /// name up, lookups fall back to another entity that still has it.
+    /// Returns false if the key is stale.
+    pub fn rename_entity(&mut self, key: EntityKey, new_name: Option<String>) -> bool {
+        let Some(entity) = self.entities.get_mut(key) else {
+            return false;
+        };
+        let old_name = std::mem::replace(&mut entity.name, new_name.clone());
+        entity.set_modified_frame(self.frame);
+
+        if let Some(old_name) = old_name {
+            self.unindex_name(&old_name, key);
+        }
+        if let Some(new_name) = new_name {
+            self.name_index.insert(new_name, key);
+        }
+        true
+    }
+
+    /// Drop `key` from the name index under `name`
+    ///
+    /// If another entity still has that name, the index points at it instead
+    /// (the one with the smallest key, so the choice is repeatable).
+    fn unindex_name(&mut self, name: &str, key: EntityKey) {
+        if self.name_index.get(name) != Some(&key) {
+            return;
+        }
+        let fallback = self
+            .entities
+            .iter()
+            .filter(|(k, e)| *k != key && e.name.as_deref() == Some(name))
+            .map(|(k, _)| k)
+            .min();
+        match fallback {
+            Some(other) => {
+                self.name_index.insert(name.to_string(), other);
+            }
+            None => {
+                self.name_index.remove(name);
+            }
+        }
+    }
+
     /// Get an entity by name
     pub fn get_by_name(&self, name: &str) -> Option<(EntityKey, &Entity)> {
         let key = *self.name_index.get(name)?;
@@ -189,8 +409,106 @@ impl World {
     }
 
     /// Get all entities with a specific tag
-    pub fn get_by_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = (EntityKey, &'a Entity)> {
-        self.entities.iter().filter(move |(_, entity)| entity.has_tag(tag))
+    ///
+    /// The tag is resolved to its interned id once; entities are then matched by id.
+    pub fn get_by_tag<'a>(&'a self, tag: &str) -> impl Iterator<Item = (EntityKey, &'a Entity)> {
+        let id = TagId::lookup(tag);
+        self.entities
+            .iter()
+            .filter(move |(_, entity)| id.is_some_and(|id| entity.has_tag_id(id)))
+    }
+
+    /// Get all entities that have every one of `tags`
+    ///
+    /// The tags are resolved to their interned ids once, like
+    /// [`get_by_tag`](Self::get_by_tag); a tag no entity was ever given
+    /// matches nothing. An empty `tags` slice matches every entity.
+    pub fn get_by_all_tags<'a>(&'a self, tags: &[&str]) -> impl Iterator<Item = (EntityKey, &'a Entity)> {
+        let ids: Option<Vec<TagId>> = tags.iter().map(|tag| TagId::lookup(tag)).collect();
+        self.entities
+            .iter()
+            .filter(move |(_, entity)| ids.as_ref().is_some_and(|ids| ids.iter().all(|&id| entity.has_tag_id(id))))
+    }
+
+    /// Get all entities that have at least one of `tags`
+    ///
+    /// The tags are resolved to their interned ids once; tags no entity was
+    /// ever given are skipped. An empty `tags` slice matches no entities.
+    pub fn get_by_any_tag<'a>(&'a self, tags: &[&str]) -> impl Iterator<Item = (EntityKey, &'a Entity)> {
+        let ids: Vec<TagId> = tags.iter().filter_map(|tag| TagId::lookup(tag)).collect();
+        self.entities
+            .iter()
+            .filter(move |(_, entity)| ids.iter().any(|&id| entity.has_tag_id(id)))
+    }
+
+    /// Iterate over the entities that have a component of type `T`
+    pub fn query_component<T: Any + Send + Sync>(&self) -> impl Iterator<Item = (EntityKey, &T)> {
+        self.entities
+            .iter()
+            .filter_map(|(key, entity)| entity.get_component::<T>().map(|c| (key, c)))
+    }
+
+    /// Get the audio emitters within `radius` of a listener
+    ///
+    /// Emitter positions include the entity's world transform (hierarchy
+    /// included). Attenuation comes from the 4D listener distance and is 0.0
+    /// for emitters beyond their `max_distance`.
+    pub fn audio_emitters_near(&self, listener: Vec4, radius: f32) -> Vec<EmitterSample> {
+        self.entities
+            .iter()
+            .filter_map(|(key, entity)| {
+                let emitter = entity.audio_emitter?;
+                let transform = self.world_transform(key)?;
+                let position = transform.transform_point(emitter.offset);
+                let distance = (position - listener).length();
+                (distance <= radius).then(|| EmitterSample {
+                    entity: key,
+                    position,
+                    attenuation: emitter.attenuation(distance),
+                })
+            })
+            .collect()
+    }
+
+    /// Find the nearest entity whose shape is hit by a ray
+    ///
+    /// Returns the entity and the distance along the ray. Each entity's
+    /// world-space bounding box is tested first; only if the ray passes
+    /// through it is the ray moved into the entity's local space and
+    /// intersected with the shape itself, so rays grazing the corners of a
+    /// rotated shape's box don't count.
+    pub fn raycast(&self, origin: Vec4, dir: Vec4, max_dist: f32) -> Option<(EntityKey, f32)> {
+        let dir = dir.normalized();
+        if dir == Vec4::ZERO {
+            return None;
+        }
+
+        let mut nearest: Option<(EntityKey, f32)> = None;
+        for (key, entity) in &self.entities {
+            let Some(transform) = self.world_transform(key) else {
+                continue;
+            };
+            let mut corners = entity.shape().vertices().iter().map(|&v| transform.transform_point(v));
+            let Some(first) = corners.next() else {
+                continue;
+            };
+            let (min, max) = corners.fold((first, first), |(lo, hi), v| (lo.min_components(v), hi.max_components(v)));
+            let closest = nearest.map_or(max_dist, |(_, d)| d);
+            if ray_vs_aabb(origin, dir, &AABB4D::new(min, max)).is_none_or(|(t, _)| t > closest) {
+                continue;
+            }
+
+            // The local ray has the same parameter as the world ray
+            let inverse = transform.inverse();
+            let local_origin = inverse.transform_point(origin);
+            let local_dir = inverse.transform_direction(dir);
+            if let Some(t) = entity.shape().ray_intersect(local_origin, local_dir) {
+                if t <= closest {
+                    nearest = Some((key, t));
+                }
+            }
+        }
+        nearest
     }
 
     /// Get the number of entities
@@ -210,32 +528,302 @@ impl World {
         self.entities.keys()
     }
 
+    /// Hash the world's simulation state, for sync checks and regression tests
+    ///
+    /// Folds in each entity's name, tags, parent name, transform, material,
+    /// lifetime, W lock and physics body state (position, velocity, orientation,
+    /// angular velocity, grounded and sleeping), plus bodies no entity owns,
+    /// such as the player. Entities have no persistent ids, so each is hashed
+    /// on its own and the per-entity hashes are sorted before being combined:
+    /// worlds in the same logical state hash equal regardless of insertion
+    /// order or slot keys. Floats are hashed bit-exactly (only `-0.0` is
+    /// folded into `0.0`), and the hash is stable across runs and platforms.
+    pub fn state_hash(&self) -> u64 {
+        let mut owned_bodies = HashSet::new();
+        let mut items: Vec<u64> = self
+            .entities
+            .iter()
+            .map(|(key, entity)| {
+                let mut h = StateHasher::new();
+                h.u8(0);
+                h.option(entity.name.as_deref(), StateHasher::str);
+                let mut tags: Vec<String> = entity.tags.iter().filter_map(|t| t.name()).collect();
+                tags.sort_unstable();
+                h.u64(tags.len() as u64);
+                for tag in &tags {
+                    h.str(tag);
+                }
+                let parent = self.parents.get(&key).map(|p| self.entities[*p].name.as_deref());
+                h.option(parent, |h, name| h.option(name, StateHasher::str));
+                h.vec4(entity.transform.position);
+                h.rotor4(entity.transform.rotation);
+                h.f32(entity.transform.scale);
+                h.option(entity.transform.mirror, StateHasher::vec4);
+                let material = &entity.material;
+                for c in material.base_color {
+                    h.f32(c);
+                }
+                h.u32(material.material_id as u32);
+                h.f32(material.ambient);
+                h.f32(material.diffuse);
+                h.option(entity.lifetime, StateHasher::f32);
+                h.option(entity.w_lock, StateHasher::f32);
+                let body = entity.physics_body.and_then(|b| self.physics_body(b));
+                h.option(body, hash_body);
+                owned_bodies.extend(entity.physics_body);
+                h.finish()
+            })
+            .collect();
+
+        if let Some(physics) = &self.physics_world {
+            for key in physics.body_keys().filter(|k| !owned_bodies.contains(k)) {
+                let mut h = StateHasher::new();
+                h.u8(1);
+                h.bool(physics.player_key() == Some(key));
+                h.option(physics.get_body(key), hash_body);
+                items.push(h.finish());
+            }
+        }
+
+        items.sort_unstable();
+        let mut h = StateHasher::new();
+        h.u64(items.len() as u64);
+        for item in items {
+            h.u64(item);
+        }
+        h.finish()
+    }
+
+    fn physics_body(&self, key: BodyKey) -> Option<&RigidBody4D> {
+        self.physics_world.as_ref().and_then(|p| p.get_body(key))
+    }
+
+    /// Number of ancestors above an entity
+    fn depth(&self, key: EntityKey) -> usize {
+        std::iter::successors(self.parents.get(&key), |parent| self.parents.get(parent)).count()
+    }
+
+    /// A body's pose relative to the parent of the entity it drives
+    ///
+    /// Bodies live in world space, so a child's body is brought into its
+    /// parent's space; a root entity's is used as is.
+    fn body_local_transform(&self, key: EntityKey, body_key: BodyKey) -> Option<Transform4D> {
+        let body = self.physics_body(body_key)?;
+        let pose = Transform4D::from_position_rotation(body.position, body.orientation);
+        Some(match self.parents.get(&key).and_then(|&parent| self.world_transform(parent)) {
+            Some(parent) => parent.inverse().compose(&pose),
+            None => pose,
+        })
+    }
+
+    /// Move an entity's physics body to the entity's world transform
+    ///
+    /// The body takes the entity's world position and orientation, so a
+    /// child's body ends up where the child is drawn. Velocities are left
+    /// alone. Does nothing if the entity has no body.
+    pub fn place_body_at_entity(&mut self, key: EntityKey) {
+        let Some(transform) = self.world_transform(key) else {
+            return;
+        };
+        let body = self.entities[key]
+            .physics_body
+            .and_then(|body_key| self.physics_world.as_mut()?.get_body_mut(body_key));
+        if let Some(body) = body {
+            body.set_position(transform.position);
+            body.orientation = transform.rotation;
+        }
+    }
+
     /// Update the world by stepping physics and syncing entity transforms
     ///
     /// This method:
-    /// 1. Steps the physics simulation (if enabled)
-    /// 2. Syncs entity transforms from their associated physics bodies
-    /// 3. Marks entities as dirty when their transforms change
+    /// 1. Applies entity W locks to their physics bodies (see [`Entity::w_lock`])
+    ///    and moves W-locked entities without a body back to their W
+    /// 2. Steps the physics simulation (if enabled)
+    /// 3. Syncs entity positions (and orientations, for spinning bodies) from
+    ///    their associated physics bodies, which are in world space, so a
+    ///    child's transform stays relative to its parent
+    /// 4. Marks entities as dirty when their transforms change
+    /// 5. Despawns entities whose lifetime ran out (see [`DespawnEvent`])
+    /// 6. Stamps every dirty entity with the new frame number
     pub fn update(&mut self, dt: f32) {
+        self.frame += 1;
+        self.step_poses.clear();
+        self.render_transforms.clear();
+
+        // Entities with bodies, parents before children so each child is
+        // placed relative to its parent's synced transform
+        let mut synced: Vec<(EntityKey, BodyKey)> = self
+            .entities
+            .iter()
+            .filter_map(|(key, entity)| Some((key, entity.physics_body?)))
+            .filter(|&(_, body_key)| self.physics_body(body_key).is_some())
+            .collect();
+        if !self.parents.is_empty() {
+            synced.sort_by_cached_key(|&(key, _)| self.depth(key));
+        }
+
+        // Record pre-step body poses for render interpolation
+        for &(key, body_key) in &synced {
+            if let Some(local) = self.body_local_transform(key, body_key) {
+                let pre = Transform4D { position: local.position, ..self.entities[key].transform };
+                self.step_poses.insert(key, (pre, pre));
+            }
+        }
+
+        self.apply_w_locks();
+
         // Step the physics simulation
         if let Some(ref mut physics) = self.physics_world {
             physics.step(dt);
         }
 
         // Sync entity transforms from their physics bodies
-        if let Some(ref physics) = self.physics_world {
-            for (_key, entity) in &mut self.entities {
-                if let Some(body_key) = entity.physics_body {
-                    if let Some(body) = physics.get_body(body_key) {
-                        // Only update and mark dirty if position actually changed
-                        if entity.transform.position != body.position {
-                            entity.transform.position = body.position;
-                            entity.mark_dirty(DirtyFlags::TRANSFORM);
-                        }
+        for (key, body_key) in synced {
+            let Some(local) = self.body_local_transform(key, body_key) else {
+                continue;
+            };
+            let spinning = self.physics_body(body_key).is_some_and(|body| !body.angular_velocity.is_zero());
+            let entity = &mut self.entities[key];
+
+            // Only update and mark dirty if position actually changed
+            if entity.transform.position != local.position {
+                entity.transform.position = local.position;
+                entity.mark_dirty(DirtyFlags::TRANSFORM);
+            }
+
+            // Orientation only changes while the body is spinning
+            if spinning {
+                entity.transform.rotation = local.rotation;
+                entity.mark_dirty(DirtyFlags::TRANSFORM);
+            }
+
+            if let Some((_, post)) = self.step_poses.get_mut(&key) {
+                *post = entity.transform;
+            }
+        }
+
+        self.expire_lifetimes(dt);
+
+        // Record the frame for anything changed since the last update
+        let frame = self.frame;
+        for entity in self.entities.values_mut() {
+            if entity.is_dirty() {
+                entity.set_modified_frame(frame);
+            }
+        }
+    }
+
+    /// Copy entity W locks to their bodies and pin bodyless entities' W
+    ///
+    /// Only changes are copied, so a lock set on a body directly (or with
+    /// [`PhysicsWorld::set_w_locked`]) survives an entity that has none.
+    fn apply_w_locks(&mut self) {
+        for entity in self.entities.values_mut() {
+            let body = entity
+                .physics_body
+                .and_then(|key| self.physics_world.as_mut()?.get_body_mut(key));
+            match (body, entity.w_lock) {
+                (Some(body), w_lock) => {
+                    if w_lock != entity.applied_w_lock {
+                        body.w_lock = w_lock;
+                        entity.applied_w_lock = w_lock;
                     }
                 }
+                (None, Some(w)) if entity.transform.position.w != w => {
+                    entity.transform.position.w = w;
+                    entity.mark_dirty(DirtyFlags::TRANSFORM);
+                }
+                (None, _) => {}
+            }
+        }
+    }
+
+    /// Count down entity lifetimes and despawn the expired ones
+    ///
+    /// Expired keys are collected first and removed afterwards, so removal
+    /// never happens while iterating. An entity already removed as the
+    /// descendant of another expired entity is skipped.
+    fn expire_lifetimes(&mut self, dt: f32) {
+        let mut expired = Vec::new();
+        for (key, entity) in &mut self.entities {
+            if let Some(lifetime) = entity.lifetime.as_mut() {
+                *lifetime -= dt;
+                if *lifetime <= 0.0 {
+                    expired.push(key);
+                }
             }
         }
+
+        for key in expired {
+            if !self.entities.contains_key(key) {
+                continue;
+            }
+            let name = self.entities[key].name.clone();
+            let mut removed = vec![key];
+            removed.extend(self.descendants(key));
+            self.delete_recursive(key);
+            self.despawn_events.push(DespawnEvent { entity: key, name, removed });
+        }
+    }
+
+    /// Get the lifetime despawns recorded since the last drain
+    pub fn despawn_events(&self) -> &[DespawnEvent] {
+        &self.despawn_events
+    }
+
+    /// Take the recorded lifetime despawns, clearing the list
+    pub fn drain_despawn_events(&mut self) -> Vec<DespawnEvent> {
+        std::mem::take(&mut self.despawn_events)
+    }
+
+    // --- Render interpolation ---
+
+    /// Write interpolated transforms for rendering
+    ///
+    /// For every entity synced from a physics body, blends the body pose from
+    /// before the last `update` (`alpha = 0.0`) to after it (`alpha = 1.0`):
+    /// the position is lerped and the rotation slerped.
+    /// Results are read back with [`render_transform`](Self::render_transform);
+    /// entity transforms and physics bodies are left untouched.
+    pub fn lerp_render_transforms(&mut self, alpha: f32) {
+        self.render_transforms.clear();
+        for (&key, &(pre, post)) in &self.step_poses {
+            if let Some(entity) = self.entities.get(key) {
+                let mut transform = entity.transform;
+                transform.position = pre.position.lerp(post.position, alpha);
+                transform.rotation = pre.rotation.slerp(&post.rotation, alpha);
+                self.render_transforms.insert(key, transform);
+            }
+        }
+    }
+
+    /// Get the transform to render an entity with
+    ///
+    /// Returns the interpolated transform written by
+    /// [`lerp_render_transforms`](Self::lerp_render_transforms) if there is one,
+    /// otherwise the entity's own transform. Returns `None` if the entity does not exist.
+    pub fn render_transform(&self, key: EntityKey) -> Option<Transform4D> {
+        let entity = self.entities.get(key)?;
+        Some(self.render_transforms.get(&key).copied().unwrap_or(entity.transform))
+    }
+
+    /// Get the current frame number (the number of `update` calls so far)
+    #[inline]
+    pub fn current_frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Iterate over entities modified after the given frame
+    ///
+    /// An entity counts as modified on a frame if it was added during it or
+    /// was dirty when that frame's `update` ran. Unlike the dirty flags, this
+    /// lets systems running at different cadences each track their own
+    /// last-seen frame.
+    pub fn entities_modified_since(&self, frame: u64) -> impl Iterator<Item = (EntityKey, &Entity)> {
+        self.entities
+            .iter()
+            .filter(move |(_, entity)| entity.modified_frame() > frame)
     }
 
     // --- Dirty tracking methods ---
@@ -245,6 +833,14 @@ impl World {
         self.entities.values().any(|entity| entity.is_dirty())
     }
 
+    /// Check if any entity's shape changed and needs re-tetrahedralizing
+    ///
+    /// When this is false, dirty entities only moved or changed material,
+    /// and their existing tetrahedra can be reused.
+    pub fn has_shape_changes(&self) -> bool {
+        self.entities.values().any(|entity| entity.dirty_flags().contains(DirtyFlags::SHAPE))
+    }
+
     /// Iterate over all dirty entities (entities with any dirty flags set)
     pub fn dirty_entities(&self) -> impl Iterator<Item = (EntityKey, &Entity)> {
         self.entities.iter().filter(|(_, entity)| entity.is_dirty())
@@ -268,6 +864,10 @@ impl World {
         self.name_index.clear();
         self.parents.clear();
         self.children_map.clear();
+        self.step_poses.clear();
+        self.render_transforms.clear();
+        self.despawn_events.clear();
+        self.dirty_batches.clear();
     }
 
     /// Iterate over all entities
@@ -285,6 +885,26 @@ impl World {
         self.entities.iter()
     }
 
+    // --- Palette methods ---
+
+    /// Recolor every entity that names a color in `palette`
+    ///
+    /// Only the base color changes; entities whose color is already up to date
+    /// are left clean. Returns the number of entities recolored.
+    pub fn apply_palette(&mut self, palette: &Palette) -> usize {
+        let mut recolored = 0;
+        for entity in self.entities.values_mut() {
+            let Some(color) = entity.palette_color.as_deref().and_then(|name| palette.get(name)) else {
+                continue;
+            };
+            if entity.material.base_color != color {
+                entity.set_material(Material { base_color: color, ..entity.material });
+                recolored += 1;
+            }
+        }
+        recolored
+    }
+
     // --- Hierarchy methods ---
 
     /// Get an entity's parent key
@@ -451,7 +1071,7 @@ impl World {
             // Remove the entity itself (with name/physics cleanup)
             if let Some(ent) = self.entities.remove(key) {
                 if let Some(ref name) = ent.name {
-                    self.name_index.remove(name);
+                    self.unindex_name(name, key);
                 }
                 if let Some(body_key) = ent.physics_body {
                     if let Some(ref mut physics) = self.physics_world {
@@ -513,6 +1133,150 @@ impl World {
         }
         false
     }
+
+    /// Render the entity hierarchy as an indented tree, for debugging
+    ///
+    /// Each line is an entity's name (or key, if unnamed) and its world
+    /// position, indented two spaces per level below its root. Entities
+    /// whose parent no longer exists are listed as roots.
+    pub fn hierarchy_report(&self) -> String {
+        let mut report = String::new();
+        let roots = self
+            .entities
+            .keys()
+            .filter(|key| self.parents.get(key).is_none_or(|parent| !self.entities.contains_key(*parent)));
+        for root in roots {
+            self.write_hierarchy(&mut report, root, 0);
+        }
+        report
+    }
+
+    /// Describe the full state of the entities matching `query`, for debugging
+    ///
+    /// Lists each entity's transform, material, tags, shape size, physics body
+    /// state and place in the hierarchy, separated by blank lines. Returns a
+    /// "not found" line if nothing matches.
+    pub fn inspect(&self, query: InspectQuery) -> String {
+        let keys: Vec<EntityKey> = match query {
+            InspectQuery::Key(key) => self.entities.contains_key(key).then_some(key).into_iter().collect(),
+            InspectQuery::Name(name) => self.get_by_name(name).map(|(key, _)| key).into_iter().collect(),
+            InspectQuery::Tag(tag) => self.get_by_tag(tag).map(|(key, _)| key).collect(),
+        };
+        if keys.is_empty() {
+            return match query {
+                InspectQuery::Key(key) => format!("No entity found with key {:?}\n", key),
+                InspectQuery::Name(name) => format!("No entity found named \"{}\"\n", name),
+                InspectQuery::Tag(tag) => format!("No entities found tagged \"{}\"\n", tag),
+            };
+        }
+
+        let mut report = String::new();
+        for (i, key) in keys.into_iter().enumerate() {
+            if i > 0 {
+                report.push('\n');
+            }
+            self.write_inspection(&mut report, key);
+        }
+        report
+    }
+
+    fn write_inspection(&self, report: &mut String, key: EntityKey) {
+        let Some(entity) = self.entities.get(key) else { return };
+        let label = |key: EntityKey| {
+            self.entities.get(key).and_then(|e| e.name.clone()).unwrap_or_else(|| format!("{:?}", key))
+        };
+        let vec = |v: Vec4| format!("({:.2}, {:.2}, {:.2}, {:.2})", v.x, v.y, v.z, v.w);
+
+        let _ = writeln!(report, "{} [{:?}]", label(key), key);
+
+        let t = &entity.transform;
+        let r = t.rotation;
+        let _ = writeln!(report, "  position: {}", vec(t.position));
+        let _ = writeln!(
+            report,
+            "  rotation: s {:.3}, xy {:.3}, xz {:.3}, xw {:.3}, yz {:.3}, yw {:.3}, zw {:.3}, p {:.3}",
+            r.s, r.b_xy, r.b_xz, r.b_xw, r.b_yz, r.b_yw, r.b_zw, r.p
+        );
+        let _ = writeln!(report, "  scale: {:.3}", t.scale);
+        if self.has_parent(key) {
+            let world = self.world_transform(key).map_or(Vec4::ZERO, |t| t.position);
+            let _ = writeln!(report, "  world position: {}", vec(world));
+        }
+
+        let m = &entity.material;
+        let c = m.base_color;
+        let palette = entity.palette_color.as_ref().map(|name| format!(", palette \"{}\"", name)).unwrap_or_default();
+        let _ = writeln!(
+            report,
+            "  material: color ({:.2}, {:.2}, {:.2}, {:.2}), id {}, ambient {:.2}, diffuse {:.2}{}",
+            c[0], c[1], c[2], c[3], m.material_id, m.ambient, m.diffuse, palette
+        );
+
+        let tags = entity.tag_names();
+        let _ = writeln!(report, "  tags: {}", if tags.is_empty() { "(none)".to_string() } else { tags.join(", ") });
+
+        let shape = entity.shape();
+        let _ = writeln!(report, "  shape: {} vertices, {} tetrahedra", shape.vertex_count(), shape.tetrahedron_count());
+
+        let body = entity
+            .physics_body
+            .map(|body_key| (body_key, self.physics_world.as_ref().and_then(|p| p.get_body(body_key))));
+        let physics = match body {
+            None => "none".to_string(),
+            Some((body_key, None)) => format!("{:?} (missing)", body_key),
+            Some((body_key, Some(body))) => {
+                let mut state = format!("{:?}", body.body_type);
+                for (flag, name) in [
+                    (body.grounded, "grounded"),
+                    (body.is_sleeping(), "sleeping"),
+                    (body.is_frozen(), "frozen"),
+                ] {
+                    if flag {
+                        state.push_str(", ");
+                        state.push_str(name);
+                    }
+                }
+                format!(
+                    "{:?} {}, position {}, velocity {}, mass {:.2}",
+                    body_key, state, vec(body.position), vec(body.velocity), body.mass
+                )
+            }
+        };
+        let _ = writeln!(report, "  physics: {}", physics);
+
+        let parent = self.parent_of(key).map_or_else(|| "(root)".to_string(), label);
+        let _ = writeln!(report, "  parent: {}", parent);
+        let children = self.children_of(key);
+        if !children.is_empty() {
+            let names: Vec<String> = children.iter().map(|&child| label(child)).collect();
+            let _ = writeln!(report, "  children: {}", names.join(", "));
+        }
+    }
+
+    fn write_hierarchy(&self, report: &mut String, key: EntityKey, depth: usize) {
+        let Some(entity) = self.entities.get(key) else { return };
+        let label = entity.name.clone().unwrap_or_else(|| format!("{:?}", key));
+        let p = self.world_transform(key).map_or(Vec4::ZERO, |t| t.position);
+        let _ = writeln!(
+            report,
+            "{:indent$}{} ({:.2}, {:.2}, {:.2}, {:.2})",
+            "", label, p.x, p.y, p.z, p.w,
+            indent = depth * 2
+        );
+        for &child in self.children_of(key) {
+            self.write_hierarchy(report, child, depth + 1);
+        }
+    }
+}
+
+/// Fold the simulated state of a physics body into a state hash
+fn hash_body(h: &mut StateHasher, body: &RigidBody4D) {
+    h.vec4(body.position);
+    h.vec4(body.velocity);
+    h.rotor4(body.orientation);
+    h.bivector4(body.angular_velocity);
+    h.bool(body.grounded);
+    h.bool(body.is_sleeping());
 }
 
 #[cfg(test)]
@@ -718,6 +1482,79 @@ mod tests {
         assert!(entity.transform.position.y < 10.0);
     }
 
+    /// World with gravity along -Y and +W, and an entity whose body is pushed in all four axes
+    fn w_locked_world() -> (World, EntityKey, BodyKey) {
+        use rust4d_physics::RigidBody4D;
+        use rust4d_math::Vec4;
+
+        let config = PhysicsConfig::new(0.0).with_gravity_vector(Vec4::new(0.0, -10.0, 0.0, 10.0));
+        let mut world = World::new().with_physics(config);
+        let body = RigidBody4D::new_sphere(Vec4::new(0.0, 0.0, 0.0, 1.0), 0.5)
+            .with_velocity(Vec4::new(1.0, 0.0, 0.0, 2.0));
+        let body = world.physics_mut().unwrap().add_body(body);
+        let key = world.add_entity(make_test_entity().with_physics_body(body).with_w_lock(1.0));
+        (world, key, body)
+    }
+
+    #[test]
+    fn test_w_locked_entity_keeps_w() {
+        use rust4d_math::Vec4;
+
+        let (mut world, key, body) = w_locked_world();
+        for _ in 0..10 {
+            world.physics_mut().unwrap().apply_force(body, Vec4::new(0.0, 0.0, 3.0, 5.0));
+            world.update(0.1);
+        }
+
+        let position = world.get_entity(key).unwrap().transform.position;
+        assert_eq!(position.w, 1.0);
+        assert!(position.x > 0.9);
+        assert!(position.y < -1.0);
+        assert!(position.z > 1.0);
+        assert_eq!(world.physics().unwrap().get_body(body).unwrap().velocity.w, 0.0);
+    }
+
+    #[test]
+    fn test_removing_w_lock_restores_4d_motion() {
+        let (mut world, key, body) = w_locked_world();
+        world.update(0.1);
+        assert_eq!(world.get_entity(key).unwrap().transform.position.w, 1.0);
+
+        world.get_entity_mut(key).unwrap().w_lock = None;
+        world.update(0.1);
+        assert_eq!(world.physics().unwrap().get_body(body).unwrap().w_lock, None);
+        // One step of W gravity from rest along W
+        let w = world.get_entity(key).unwrap().transform.position.w;
+        assert!((w - 1.1).abs() < 1e-4, "w = {w}");
+    }
+
+    #[test]
+    fn test_body_w_lock_survives_entity_without_lock() {
+        use rust4d_physics::RigidBody4D;
+        use rust4d_math::Vec4;
+
+        let config = PhysicsConfig::new(0.0).with_gravity_vector(Vec4::new(0.0, 0.0, 0.0, 10.0));
+        let mut world = World::new().with_physics(config);
+        let body = RigidBody4D::new_sphere(Vec4::new(0.0, 0.0, 0.0, 1.0), 0.5).with_w_lock(1.0);
+        let body = world.physics_mut().unwrap().add_body(body);
+        let key = world.add_entity(make_test_entity().with_physics_body(body));
+
+        world.update(0.1);
+        assert_eq!(world.physics().unwrap().get_body(body).unwrap().w_lock, Some(1.0));
+        assert_eq!(world.get_entity(key).unwrap().transform.position.w, 1.0);
+    }
+
+    #[test]
+    fn test_w_lock_pins_entity_without_body() {
+        let mut world = World::new();
+        let mut entity = make_test_entity().with_w_lock(-2.0);
+        entity.transform.position.w = 3.0;
+        let key = world.add_entity(entity);
+
+        world.update(0.1);
+        assert_eq!(world.get_entity(key).unwrap().transform.position.w, -2.0);
+    }
+
     #[test]
     fn test_entity_without_physics_body() {
         // Create a world with physics
@@ -804,6 +1641,55 @@ mod tests {
         assert!(none_entities.is_empty());
     }
 
+    #[test]
+    fn test_get_by_tag_follows_tag_changes() {
+        let mut world = World::new();
+        let enemy1 = world.add_entity(make_test_entity().with_tag("enemy"));
+        let friend = world.add_entity(make_test_entity().with_tag("friend"));
+        let enemy2 = world.add_entity(make_test_entity().with_tags(["enemy", "boss"]));
+
+        let mut by_tag: Vec<_> = world.get_by_tag("enemy").map(|(k, _)| k).collect();
+        by_tag.sort();
+        let mut expected = vec![enemy1, enemy2];
+        expected.sort();
+        assert_eq!(by_tag, expected);
+
+        world.get_entity_mut(friend).unwrap().add_tag("enemy");
+        world.get_entity_mut(enemy1).unwrap().remove_tag("enemy");
+        let mut by_tag: Vec<_> = world.get_by_tag("enemy").map(|(k, _)| k).collect();
+        by_tag.sort();
+        let mut expected = vec![friend, enemy2];
+        expected.sort();
+        assert_eq!(by_tag, expected);
+    }
+
+    #[test]
+    fn test_get_by_all_and_any_tags() {
+        let mut world = World::new();
+        let grunt = world.add_entity(make_test_entity().with_tag("enemy"));
+        let boss = world.add_entity(make_test_entity().with_tags(["enemy", "boss"]));
+        let coin = world.add_entity(make_test_entity().with_tag("pickup"));
+        let lava = world.add_entity(make_test_entity().with_tag("hazard"));
+        let plain = world.add_entity(make_test_entity());
+
+        let sorted = |mut keys: Vec<EntityKey>| {
+            keys.sort();
+            keys
+        };
+        let all = |tags: &[&str]| sorted(world.get_by_all_tags(tags).map(|(k, _)| k).collect());
+        let any = |tags: &[&str]| sorted(world.get_by_any_tag(tags).map(|(k, _)| k).collect());
+
+        assert_eq!(all(&["enemy", "boss"]), vec![boss]);
+        assert_eq!(all(&["enemy"]), sorted(vec![grunt, boss]));
+        assert!(all(&["enemy", "nonexistent"]).is_empty());
+        assert_eq!(any(&["pickup", "hazard"]), sorted(vec![coin, lava]));
+        assert_eq!(any(&["boss", "nonexistent"]), vec![boss]);
+
+        // AND over nothing is everything, ANY over nothing is nothing
+        assert_eq!(all(&[]), sorted(vec![grunt, boss, coin, lava, plain]));
+        assert!(any(&[]).is_empty());
+    }
+
     #[test]
     fn test_name_index_cleanup_on_remove() {
         let mut world = World::new();
@@ -843,36 +1729,97 @@ mod tests {
     }
 
     #[test]
-    fn test_entity_without_name() {
+    fn test_rename_entity_updates_index() {
         let mut world = World::new();
+        let key = world.add_entity(make_test_entity().with_name("old"));
 
-        // Add an unnamed entity
-        let entity = make_test_entity();
-        let key = world.add_entity(entity);
+        assert!(world.rename_entity(key, Some("new".to_string())));
 
-        // Entity should exist but not be findable by any name
-        assert!(world.get_entity(key).is_some());
-        assert!(world.get_by_name("").is_none());
+        assert!(world.get_by_name("old").is_none());
+        let (found, entity) = world.get_by_name("new").unwrap();
+        assert_eq!(found, key);
+        assert_eq!(entity.name.as_deref(), Some("new"));
     }
 
-    // --- Dirty tracking tests ---
-
     #[test]
-    fn test_new_entities_are_dirty() {
+    fn test_rename_entity_to_none_clears_index() {
         let mut world = World::new();
-        let key = world.add_entity(make_test_entity());
+        let key = world.add_entity(make_test_entity().with_name("named"));
 
-        // New entities should be dirty (DirtyFlags::ALL)
-        let entity = world.get_entity(key).unwrap();
-        assert!(entity.is_dirty());
-        assert!(world.has_dirty_entities());
+        assert!(world.rename_entity(key, None));
+
+        assert!(world.get_by_name("named").is_none());
+        assert!(world.get_entity(key).unwrap().name.is_none());
     }
 
     #[test]
-    fn test_clear_all_dirty() {
+    fn test_rename_entity_stale_key() {
+        let mut world = World::new();
+        let key = world.add_entity(make_test_entity());
+        world.remove_entity(key);
+
+        assert!(!world.rename_entity(key, Some("ghost".to_string())));
+        assert!(world.get_by_name("ghost").is_none());
+    }
+
+    #[test]
+    fn test_rename_entity_duplicate_names() {
+        let mut world = World::new();
+        let a = world.add_entity(make_test_entity().with_name("a"));
+        let b = world.add_entity(make_test_entity().with_name("b"));
+
+        // Most recent name wins
+        world.rename_entity(b, Some("a".to_string()));
+        assert_eq!(world.get_by_name("a").unwrap().0, b);
+        assert!(world.get_by_name("b").is_none());
+
+        // Giving the name up falls back to the other holder
+        world.rename_entity(b, None);
+        assert_eq!(world.get_by_name("a").unwrap().0, a);
+
+        // Renaming the shadowed holder doesn't disturb the current one
+        world.rename_entity(b, Some("a".to_string()));
+        world.rename_entity(a, Some("c".to_string()));
+        assert_eq!(world.get_by_name("a").unwrap().0, b);
+        assert_eq!(world.get_by_name("c").unwrap().0, a);
+
+        // Removing a shadowed holder keeps the index intact
+        let d = world.add_entity(make_test_entity().with_name("c"));
+        world.remove_entity(a);
+        assert_eq!(world.get_by_name("c").unwrap().0, d);
+    }
+
+    #[test]
+    fn test_entity_without_name() {
+        let mut world = World::new();
+
+        // Add an unnamed entity
+        let entity = make_test_entity();
+        let key = world.add_entity(entity);
+
+        // Entity should exist but not be findable by any name
+        assert!(world.get_entity(key).is_some());
+        assert!(world.get_by_name("").is_none());
+    }
+
+    // --- Dirty tracking tests ---
+
+    #[test]
+    fn test_new_entities_are_dirty() {
+        let mut world = World::new();
+        let key = world.add_entity(make_test_entity());
+
+        // New entities should be dirty (DirtyFlags::ALL)
+        let entity = world.get_entity(key).unwrap();
+        assert!(entity.is_dirty());
+        assert!(world.has_dirty_entities());
+    }
+
+    #[test]
+    fn test_clear_all_dirty() {
         let mut world = World::new();
         world.add_entity(make_test_entity());
-        world.add_entity(make_test_entity());
+        world.add_entity(make_test_entity());
 
         // Both should be dirty initially
         assert!(world.has_dirty_entities());
@@ -886,6 +1833,20 @@ mod tests {
         assert_eq!(world.dirty_entities().count(), 0);
     }
 
+    #[test]
+    fn test_has_shape_changes() {
+        let mut world = World::new();
+        let key = world.add_entity(make_test_entity());
+        world.clear_all_dirty();
+
+        world.get_entity_mut(key).unwrap().set_position(Vec4::new(1.0, 0.0, 0.0, 0.0));
+        assert!(world.has_dirty_entities());
+        assert!(!world.has_shape_changes());
+
+        world.get_entity_mut(key).unwrap().set_shape(ShapeRef::owned(Tesseract4D::new(3.0)));
+        assert!(world.has_shape_changes());
+    }
+
     #[test]
     fn test_dirty_entities_iterator() {
         let mut world = World::new();
@@ -941,6 +1902,29 @@ mod tests {
         assert!(entity.dirty_flags().contains(DirtyFlags::TRANSFORM));
     }
 
+    #[test]
+    fn test_physics_sync_angular_velocity() {
+        use rust4d_physics::RigidBody4D;
+        use rust4d_math::{Bivector4, RotationPlane, Rotor4, Vec4};
+
+        let mut world = World::new().with_physics(PhysicsConfig::new(0.0));
+        let body = RigidBody4D::new_sphere(Vec4::ZERO, 0.5)
+            .with_angular_velocity(Bivector4::from_plane(RotationPlane::ZW, 0.5));
+        let body_handle = world.physics_mut().unwrap().add_body(body);
+        let entity_handle = world.add_entity(make_test_entity().with_physics_body(body_handle));
+        world.clear_all_dirty();
+
+        world.update(1.0);
+
+        // Spinning in place still dirties the transform
+        let entity = world.get_entity(entity_handle).unwrap();
+        assert!(entity.dirty_flags().contains(DirtyFlags::TRANSFORM));
+        assert_eq!(entity.transform.position, Vec4::ZERO);
+        let expected = Rotor4::from_plane_angle(RotationPlane::ZW, 0.5);
+        let v = Vec4::Z;
+        assert!((entity.transform.rotation.rotate(v) - expected.rotate(v)).length() < 1e-5);
+    }
+
     #[test]
     fn test_physics_sync_no_change_not_dirty() {
         use rust4d_physics::RigidBody4D;
@@ -993,6 +1977,361 @@ mod tests {
         assert!(!world.has_dirty_entities());
     }
 
+    // --- Modification frame tests ---
+
+    #[test]
+    fn test_frame_counter_increments_on_update() {
+        let mut world = World::new();
+        assert_eq!(world.current_frame(), 0);
+
+        world.update(0.016);
+        world.update(0.016);
+        assert_eq!(world.current_frame(), 2);
+    }
+
+    #[test]
+    fn test_entities_modified_since() {
+        use rust4d_math::Vec4;
+
+        let mut world = World::new();
+        let changed = world.add_entity(make_test_entity());
+        let untouched = world.add_entity(make_test_entity());
+
+        // Frame 1 picks up the newly added entities
+        world.update(0.016);
+        world.clear_all_dirty();
+
+        // Frames 2-4 with no changes
+        for _ in 0..3 {
+            world.update(0.016);
+        }
+        assert_eq!(world.current_frame(), 4);
+
+        // Modify an entity before frame 5
+        world.get_entity_mut(changed).unwrap().set_position(Vec4::new(1.0, 0.0, 0.0, 0.0));
+        world.update(0.016);
+        world.clear_all_dirty();
+        assert_eq!(world.get_entity(changed).unwrap().modified_frame(), 5);
+
+        let since_4: Vec<_> = world.entities_modified_since(4).map(|(k, _)| k).collect();
+        assert_eq!(since_4, vec![changed]);
+
+        assert_eq!(world.entities_modified_since(5).count(), 0);
+
+        // Both entities were modified on frame 1
+        let since_0: Vec<_> = world.entities_modified_since(0).map(|(k, _)| k).collect();
+        assert!(since_0.contains(&changed));
+        assert!(since_0.contains(&untouched));
+    }
+
+    #[test]
+    fn test_add_entity_stamps_current_frame() {
+        let mut world = World::new();
+        world.update(0.016);
+        world.update(0.016);
+
+        let key = world.add_entity(make_test_entity());
+        assert_eq!(world.get_entity(key).unwrap().modified_frame(), 2);
+    }
+
+    #[test]
+    fn test_physics_sync_stamps_modified_frame() {
+        use rust4d_physics::RigidBody4D;
+        use rust4d_math::Vec4;
+
+        let config = PhysicsConfig::new(-10.0);
+        let mut world = World::new().with_physics(config);
+
+        let body = RigidBody4D::new_sphere(Vec4::new(0.0, 10.0, 0.0, 0.0), 0.5);
+        let body_key = world.physics_mut().unwrap().add_body(body);
+        let key = world.add_entity(make_test_entity().with_physics_body(body_key));
+        world.clear_all_dirty();
+
+        world.update(0.1);
+        world.clear_all_dirty();
+        world.update(0.1);
+
+        assert_eq!(world.get_entity(key).unwrap().modified_frame(), 2);
+        assert_eq!(world.entities_modified_since(1).count(), 1);
+    }
+
+    // --- Audio emitter tests ---
+
+    fn make_emitter_entity(position: Vec4, max_distance: f32) -> Entity {
+        let mut entity = make_test_entity().with_audio_emitter(crate::AudioEmitter::new(max_distance));
+        entity.transform.position = position;
+        entity
+    }
+
+    #[test]
+    fn test_audio_emitter_in_range_attenuates() {
+        let mut world = World::new();
+        let key = world.add_entity(make_emitter_entity(Vec4::new(3.0, 0.0, 0.0, 4.0), 10.0));
+        world.add_entity(make_test_entity()); // No emitter
+
+        let samples = world.audio_emitters_near(Vec4::ZERO, 20.0);
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].entity, key);
+        assert_eq!(samples[0].position, Vec4::new(3.0, 0.0, 0.0, 4.0));
+        // 4D distance is 5 of a 10 unit range
+        assert!(samples[0].attenuation > 0.0 && samples[0].attenuation <= 1.0);
+        assert!((samples[0].attenuation - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_audio_emitter_beyond_max_distance_is_silent() {
+        let mut world = World::new();
+        world.add_entity(make_emitter_entity(Vec4::new(0.0, 0.0, 0.0, 8.0), 5.0));
+
+        let samples = world.audio_emitters_near(Vec4::ZERO, 20.0);
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].attenuation, 0.0);
+    }
+
+    #[test]
+    fn test_audio_emitters_outside_query_radius_skipped() {
+        let mut world = World::new();
+        world.add_entity(make_emitter_entity(Vec4::new(50.0, 0.0, 0.0, 0.0), 100.0));
+
+        assert!(world.audio_emitters_near(Vec4::ZERO, 10.0).is_empty());
+    }
+
+    #[test]
+    fn test_audio_emitter_offset_follows_transform() {
+        let mut world = World::new();
+        let mut entity = make_test_entity().with_audio_emitter(
+            crate::AudioEmitter::new(10.0).with_offset(Vec4::new(0.0, 1.0, 0.0, 0.0)),
+        );
+        entity.transform.position = Vec4::new(2.0, 0.0, 0.0, 0.0);
+        world.add_entity(entity);
+
+        let samples = world.audio_emitters_near(Vec4::ZERO, 10.0);
+        assert_eq!(samples[0].position, Vec4::new(2.0, 1.0, 0.0, 0.0));
+    }
+
+    // --- Lifetime tests ---
+
+    #[test]
+    fn test_lifetime_despawns_after_duration() {
+        let mut world = World::new();
+        let key = world.add_entity(make_test_entity().with_name("bullet").with_lifetime(1.0));
+        let survivor = world.add_entity(make_test_entity());
+
+        for _ in 0..9 {
+            world.update(0.1);
+        }
+        assert!(world.get_entity(key).is_some());
+
+        world.update(0.2);
+        assert!(world.get_entity(key).is_none());
+        assert!(world.get_by_name("bullet").is_none());
+        assert!(world.get_entity(survivor).is_some());
+    }
+
+    #[test]
+    fn test_lifetime_despawns_children() {
+        let mut world = World::new();
+        let parent = world.add_entity(make_test_entity().with_lifetime(1.0));
+        let child = world.add_entity(make_test_entity());
+        let grandchild = world.add_entity(make_test_entity());
+        world.add_child(parent, child).unwrap();
+        world.add_child(child, grandchild).unwrap();
+
+        world.update(1.5);
+
+        assert!(world.get_entity(parent).is_none());
+        assert!(world.get_entity(child).is_none());
+        assert!(world.get_entity(grandchild).is_none());
+        assert!(world.is_empty());
+    }
+
+    #[test]
+    fn test_lifetime_emits_despawn_event() {
+        let mut world = World::new();
+        let parent = world.add_entity(make_test_entity().with_name("flare").with_lifetime(0.5));
+        let child = world.add_entity(make_test_entity());
+        world.add_child(parent, child).unwrap();
+
+        world.update(0.25);
+        assert!(world.despawn_events().is_empty());
+
+        world.update(0.25);
+        let events = world.drain_despawn_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].entity, parent);
+        assert_eq!(events[0].name.as_deref(), Some("flare"));
+        assert_eq!(events[0].removed, vec![parent, child]);
+
+        // Draining clears the list
+        assert!(world.despawn_events().is_empty());
+    }
+
+    #[test]
+    fn test_lifetime_parent_and_child_expire_together() {
+        // The child is removed with its parent; it must not be despawned twice
+        let mut world = World::new();
+        let parent = world.add_entity(make_test_entity().with_lifetime(0.1));
+        let child = world.add_entity(make_test_entity().with_lifetime(0.1));
+        world.add_child(parent, child).unwrap();
+
+        world.update(0.2);
+
+        assert!(world.is_empty());
+        assert_eq!(world.despawn_events().len(), 1);
+    }
+
+    #[test]
+    fn test_lifetime_removes_physics_body() {
+        use rust4d_physics::RigidBody4D;
+
+        let mut world = World::new().with_physics(PhysicsConfig::new(0.0));
+        let body = world
+            .physics_mut()
+            .unwrap()
+            .add_body(RigidBody4D::new_sphere(Vec4::ZERO, 0.5));
+        world.add_entity(make_test_entity().with_physics_body(body).with_lifetime(0.1));
+
+        world.update(0.2);
+
+        assert!(world.physics().unwrap().get_body(body).is_none());
+    }
+
+    // --- Render interpolation tests ---
+
+    fn make_falling_world() -> (World, EntityKey) {
+        use rust4d_physics::RigidBody4D;
+
+        let mut world = World::new().with_physics(PhysicsConfig::new(-10.0));
+        let body = RigidBody4D::new_sphere(Vec4::new(0.0, 10.0, 0.0, 0.0), 0.5)
+            .with_velocity(Vec4::new(1.0, 0.0, 0.0, 0.0));
+        let body_key = world.physics_mut().unwrap().add_body(body);
+        let key = world.add_entity(make_test_entity().with_physics_body(body_key));
+        (world, key)
+    }
+
+    #[test]
+    fn test_lerp_render_transforms_alpha_zero_is_pre_step() {
+        let (mut world, key) = make_falling_world();
+        world.update(0.1);
+        let pre = world.get_entity(key).unwrap().transform.position;
+
+        world.update(0.1);
+        world.lerp_render_transforms(0.0);
+
+        assert_eq!(world.render_transform(key).unwrap().position, pre);
+    }
+
+    #[test]
+    fn test_lerp_render_transforms_alpha_one_is_post_step() {
+        let (mut world, key) = make_falling_world();
+        world.update(0.1);
+        world.update(0.1);
+        world.lerp_render_transforms(1.0);
+
+        let post = world.get_entity(key).unwrap().transform.position;
+        assert_eq!(world.render_transform(key).unwrap().position, post);
+    }
+
+    #[test]
+    fn test_lerp_render_transforms_midpoint() {
+        let (mut world, key) = make_falling_world();
+        world.update(0.1);
+        let pre = world.get_entity(key).unwrap().transform.position;
+        world.update(0.1);
+        let post = world.get_entity(key).unwrap().transform.position;
+
+        world.lerp_render_transforms(0.5);
+        let mid = world.render_transform(key).unwrap().position;
+        assert!((mid - pre.lerp(post, 0.5)).length() < 1e-6);
+    }
+
+    #[test]
+    fn test_lerp_render_transforms_slerps_rotation() {
+        use rust4d_physics::RigidBody4D;
+        use rust4d_math::{Bivector4, RotationPlane, Rotor4};
+
+        let mut world = World::new().with_physics(PhysicsConfig::new(0.0));
+        let body = RigidBody4D::new_sphere(Vec4::ZERO, 0.5)
+            .with_angular_velocity(Bivector4::from_plane(RotationPlane::ZW, 1.0));
+        let body_key = world.physics_mut().unwrap().add_body(body);
+        let key = world.add_entity(make_test_entity().with_physics_body(body_key));
+
+        world.update(0.5);
+        world.lerp_render_transforms(0.5);
+
+        // Halfway through a 0.5 rad turn from the identity
+        let expected = Rotor4::from_plane_angle(RotationPlane::ZW, 0.25);
+        let rotation = world.render_transform(key).unwrap().rotation;
+        let v = Vec4::Z;
+        assert!((rotation.rotate(v) - expected.rotate(v)).length() < 1e-5);
+    }
+
+    #[test]
+    fn test_lerp_render_transforms_leaves_physics_untouched() {
+        let (mut world, key) = make_falling_world();
+        world.update(0.1);
+        let body_key = world.get_entity(key).unwrap().physics_body.unwrap();
+        let body_pos = world.physics().unwrap().get_body(body_key).unwrap().position;
+        let entity_pos = world.get_entity(key).unwrap().transform.position;
+        world.clear_all_dirty();
+
+        world.lerp_render_transforms(0.0);
+
+        assert_eq!(world.physics().unwrap().get_body(body_key).unwrap().position, body_pos);
+        assert_eq!(world.get_entity(key).unwrap().transform.position, entity_pos);
+        assert!(!world.has_dirty_entities());
+    }
+
+    #[test]
+    fn test_render_transform_without_physics_is_entity_transform() {
+        let mut world = World::new();
+        let key = world.add_entity(make_test_entity());
+        world.get_entity_mut(key).unwrap().set_position(Vec4::new(1.0, 2.0, 3.0, 4.0));
+        world.update(0.1);
+        world.lerp_render_transforms(0.25);
+
+        assert_eq!(world.render_transform(key).unwrap().position, Vec4::new(1.0, 2.0, 3.0, 4.0));
+    }
+
+    // --- Palette tests ---
+
+    #[test]
+    fn test_apply_palette_recolors_referencing_entities() {
+        let mut world = World::new();
+        let floor = world.add_entity(make_test_entity().with_palette_color("floor"));
+        let team = world.add_entity(make_test_entity().with_palette_color("team"));
+        let plain = world.add_entity(make_test_entity());
+        world.clear_all_dirty();
+
+        let palette = Palette::new()
+            .with_color("floor", [0.2, 0.2, 0.25, 1.0])
+            .with_color("team", [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(world.apply_palette(&palette), 2);
+
+        assert_eq!(world.get_entity(floor).unwrap().material.base_color, [0.2, 0.2, 0.25, 1.0]);
+        assert_eq!(world.get_entity(team).unwrap().material.base_color, [1.0, 0.0, 0.0, 1.0]);
+        assert!(world.get_entity(team).unwrap().dirty_flags().contains(DirtyFlags::MATERIAL));
+        assert_eq!(world.get_entity(plain).unwrap().material.base_color, Material::WHITE.base_color);
+        assert!(!world.get_entity(plain).unwrap().is_dirty());
+
+        // Re-applying the same palette changes nothing
+        world.clear_all_dirty();
+        assert_eq!(world.apply_palette(&palette), 0);
+        assert!(!world.has_dirty_entities());
+    }
+
+    #[test]
+    fn test_apply_blended_palette() {
+        let mut world = World::new();
+        let key = world.add_entity(make_test_entity().with_palette_color("sky"));
+
+        let day = Palette::new().with_color("sky", [1.0, 1.0, 1.0, 1.0]);
+        let night = Palette::new().with_color("sky", [0.0, 0.0, 0.0, 1.0]);
+        world.apply_palette(&day.lerp(&night, 0.5));
+
+        assert_eq!(world.get_entity(key).unwrap().material.base_color, [0.5, 0.5, 0.5, 1.0]);
+    }
+
     // --- Hierarchy tests ---
 
     fn make_positioned_entity(x: f32, y: f32, z: f32, w: f32) -> Entity {
@@ -1388,4 +2727,361 @@ mod tests {
         // Non-existent entity returns None
         assert!(world.world_transform(key).is_none());
     }
+
+    #[test]
+    fn test_hierarchy_report_indents_levels() {
+        let mut world = World::new();
+        let root = world.add_entity(make_positioned_entity(10.0, 0.0, 0.0, 0.0).with_name("root"));
+        let arm = world.add_entity(make_positioned_entity(5.0, 0.0, 0.0, 0.0).with_name("arm"));
+        let hand = world.add_entity(make_positioned_entity(1.0, 2.0, 0.0, 0.0).with_name("hand"));
+        let leg = world.add_entity(make_positioned_entity(0.0, -1.0, 0.0, 0.0).with_name("leg"));
+        world.add_child(root, arm).unwrap();
+        world.add_child(arm, hand).unwrap();
+        world.add_child(root, leg).unwrap();
+
+        let expected = [
+            "root (10.00, 0.00, 0.00, 0.00)",
+            "  arm (15.00, 0.00, 0.00, 0.00)",
+            "    hand (16.00, 2.00, 0.00, 0.00)",
+            "  leg (10.00, -1.00, 0.00, 0.00)",
+        ];
+        assert_eq!(world.hierarchy_report().lines().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_hierarchy_report_orphans_are_roots() {
+        let mut world = World::new();
+        let parent = world.add_entity(make_test_entity().with_name("parent"));
+        let child = world.add_entity(make_positioned_entity(1.0, 0.0, 0.0, 0.0).with_name("child"));
+        let unnamed = world.add_entity(make_test_entity());
+        world.add_child(parent, child).unwrap();
+        world.remove_entity(parent);
+
+        let report = world.hierarchy_report();
+        assert!(report.contains(&format!("{:?} (0.00, 0.00, 0.00, 0.00)\n", unnamed)));
+        assert!(report.lines().any(|line| line == "child (1.00, 0.00, 0.00, 0.00)"));
+        assert!(!report.contains("parent"));
+    }
+
+    // ==================== Inspect Tests ====================
+
+    #[test]
+    fn test_inspect_by_name_reports_position_and_tags() {
+        let mut world = World::new();
+        let parent = world.add_entity(make_positioned_entity(1.0, 0.0, 0.0, 0.0).with_name("base"));
+        let child = world.add_entity(
+            make_positioned_entity(2.5, 1.0, 0.0, -3.0).with_name("turret").with_tags(["enemy", "armed"]),
+        );
+        world.add_child(parent, child).unwrap();
+
+        let report = world.inspect(InspectQuery::Name("turret"));
+        assert!(report.starts_with("turret ["));
+        assert!(report.contains("position: (2.50, 1.00, 0.00, -3.00)"));
+        assert!(report.contains("world position: (3.50, 1.00, 0.00, -3.00)"));
+        assert!(report.contains("tags: enemy, armed"));
+        assert!(report.contains("shape: 16 vertices"));
+        assert!(report.contains("physics: none"));
+        assert!(report.contains("parent: base"));
+
+        // Same report by key
+        assert_eq!(world.inspect(InspectQuery::Key(child)), report);
+        assert!(world.inspect(InspectQuery::Name("base")).contains("children: turret"));
+    }
+
+    #[test]
+    fn test_inspect_by_tag_lists_every_match() {
+        let mut world = World::new().with_physics(PhysicsConfig::default());
+        let body = world
+            .physics_mut()
+            .unwrap()
+            .add_body(rust4d_physics::RigidBody4D::new_sphere(Vec4::new(0.0, 4.0, 0.0, 0.0), 0.5));
+        world.add_entity(make_test_entity().with_name("a").with_tag("crate").with_physics_body(body));
+        world.add_entity(make_test_entity().with_name("b").with_tag("crate"));
+        world.add_entity(make_test_entity().with_name("c"));
+
+        let report = world.inspect(InspectQuery::Tag("crate"));
+        assert_eq!(report.split("\n\n").count(), 2);
+        assert!(report.contains("physics: BodyKey"));
+        assert!(report.contains("Dynamic, position (0.00, 4.00, 0.00, 0.00)"));
+        assert!(!report.contains("\nc ["));
+    }
+
+    #[test]
+    fn test_inspect_unknown_is_not_found() {
+        let mut world = World::new();
+        let key = world.add_entity(make_test_entity().with_name("ghost"));
+        world.remove_entity(key);
+
+        assert_eq!(world.inspect(InspectQuery::Name("nobody")), "No entity found named \"nobody\"\n");
+        assert_eq!(world.inspect(InspectQuery::Tag("missing")), "No entities found tagged \"missing\"\n");
+        assert!(world.inspect(InspectQuery::Key(key)).starts_with("No entity found with key"));
+    }
+
+    // ==================== Raycast Tests ====================
+
+    #[test]
+    fn test_raycast_misses_corner_of_rotated_tesseract_bounds() {
+        use rust4d_math::{RotationPlane, Rotor4, Vec4};
+
+        // Rotated 45 degrees in XY: a diamond |x| + |y| <= sqrt(2) inside a
+        // bounding box reaching sqrt(2) along both axes
+        let mut world = World::new();
+        let rotation = Rotor4::from_plane_angle(RotationPlane::XY, std::f32::consts::FRAC_PI_4);
+        let tesseract = world.add_entity(Entity::with_transform(
+            ShapeRef::shared(Tesseract4D::new(2.0)),
+            Transform4D::from_position_rotation(Vec4::ZERO, rotation),
+            Material::WHITE,
+        ));
+
+        // Through the box corner but outside the diamond
+        assert!(world.raycast(Vec4::new(1.0, 1.0, 10.0, 0.0), -Vec4::Z, 100.0).is_none());
+
+        // Through the center, entering the z = 1 face
+        let (hit, dist) = world.raycast(Vec4::new(0.0, 0.0, 10.0, 0.0), -Vec4::Z, 100.0).unwrap();
+        assert_eq!(hit, tesseract);
+        assert!((dist - 9.0).abs() < 1e-4);
+
+        // Out of range
+        assert!(world.raycast(Vec4::new(0.0, 0.0, 10.0, 0.0), -Vec4::Z, 5.0).is_none());
+    }
+
+    #[test]
+    fn test_raycast_returns_nearest_scaled_entity() {
+        use rust4d_math::Vec4;
+
+        let mut world = World::new();
+        let mut far = make_test_entity();
+        far.transform = Transform4D::from_position(Vec4::new(10.0, 0.0, 0.0, 0.0));
+        world.add_entity(far);
+        let mut near = make_test_entity();
+        near.transform = Transform4D::from_position(Vec4::new(5.0, 0.0, 0.0, 0.0));
+        near.transform.set_scale(2.0);
+        let near = world.add_entity(near);
+
+        // The scaled tesseract spans x in [3, 7]
+        let (hit, dist) = world.raycast(Vec4::ZERO, Vec4::X * 3.0, 100.0).unwrap();
+        assert_eq!(hit, near);
+        assert!((dist - 3.0).abs() < 1e-4);
+    }
+
+    // ==================== Batch Update Tests ====================
+
+    fn world_with_entities(count: usize) -> (World, Vec<EntityKey>) {
+        let mut world = World::new();
+        let keys = (0..count).map(|_| world.add_entity(make_test_entity())).collect();
+        world.clear_all_dirty();
+        (world, keys)
+    }
+
+    #[test]
+    fn test_batch_update_coalesces_moves_into_one_batch() {
+        use rust4d_math::Vec4;
+
+        let (mut world, keys) = world_with_entities(50);
+        world.batch_update(|world| {
+            for (i, &key) in keys.iter().enumerate() {
+                world.set_entity_position(key, Vec4::new(i as f32, 0.0, 0.0, 0.0));
+            }
+        });
+
+        let batches = world.drain_dirty_batches();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].entities, keys);
+        assert_eq!(world.dirty_entities().count(), 50);
+        assert!(world.dirty_batches().is_empty());
+    }
+
+    #[test]
+    fn test_moves_outside_batch_are_reported_separately() {
+        use rust4d_math::Vec4;
+
+        let (mut world, keys) = world_with_entities(50);
+        for &key in &keys {
+            world.set_entity_position(key, Vec4::X);
+        }
+
+        let batches = world.drain_dirty_batches();
+        assert_eq!(batches.len(), 50);
+        assert!(batches.iter().zip(&keys).all(|(batch, &key)| batch.entities == vec![key]));
+    }
+
+    #[test]
+    fn test_nested_batch_update_joins_outer_batch() {
+        use rust4d_math::Vec4;
+
+        let (mut world, keys) = world_with_entities(3);
+        world.batch_update(|world| {
+            world.set_entity_position(keys[0], Vec4::X);
+            world.batch_update(|world| {
+                world.set_entity_position(keys[1], Vec4::Y);
+                world.set_entity_position(keys[0], Vec4::Z);
+            });
+        });
+        world.batch_update(|_| {});
+
+        // Each entity listed once; the empty batch records nothing
+        let batches = world.drain_dirty_batches();
+        assert_eq!(batches, vec![DirtyBatch { entities: vec![keys[0], keys[1]] }]);
+        assert_eq!(world.get_entity(keys[0]).unwrap().transform.position, Vec4::Z);
+    }
+
+    #[test]
+    fn test_batch_update_reserves_last_batch_size() {
+        use rust4d_math::Vec4;
+
+        let (mut world, keys) = world_with_entities(50);
+        let move_all = |world: &mut World| {
+            for &key in &keys {
+                world.set_entity_position(key, Vec4::X);
+            }
+        };
+        world.batch_update(move_all);
+        world.batch_update(|world| {
+            assert!(world.pending_batch.as_ref().unwrap().capacity() >= 50);
+            move_all(world);
+        });
+
+        let batches = world.drain_dirty_batches();
+        assert_eq!(batches.len(), 2);
+        assert!(batches.iter().all(|batch| batch.entities == keys));
+    }
+
+    #[test]
+    fn test_snap_entity_to_grid_moves_physics_body() {
+        use rust4d_physics::RigidBody4D;
+        use rust4d_math::Vec4;
+
+        let mut world = World::new().with_physics(PhysicsConfig::new(0.0));
+        let start = Vec4::new(1.3, 2.7, 0.0, 0.4);
+        let body = world.physics_mut().unwrap().add_body(RigidBody4D::new_sphere(start, 0.5));
+        let mut entity = make_test_entity().with_physics_body(body);
+        entity.transform.position = start;
+        let key = world.add_entity(entity);
+        world.clear_all_dirty();
+
+        assert!(world.snap_entity_to_grid(key, Vec4::new(1.0, 1.0, 1.0, 0.0)));
+        let snapped = Vec4::new(1.0, 3.0, 0.0, 0.4);
+        let entity = world.get_entity(key).unwrap();
+        assert_eq!(entity.transform.position, snapped);
+        assert!(entity.is_dirty());
+        assert_eq!(world.drain_dirty_batches(), vec![DirtyBatch { entities: vec![key] }]);
+
+        // The body moved too, so the next sync keeps the snapped position
+        world.update(0.1);
+        assert_eq!(world.get_entity(key).unwrap().transform.position, snapped);
+    }
+
+    /// A world with a falling ball entity and a tagged, named crate entity,
+    /// added in the given order
+    fn make_hash_world(ball_first: bool) -> World {
+        let mut world = World::new().with_physics(PhysicsConfig::default());
+        let body = RigidBody4D::new_sphere(Vec4::new(0.0, 10.0, 0.0, 0.0), 0.5);
+        let body = world.physics_mut().unwrap().add_body(body);
+        let ball = make_test_entity().with_physics_body(body);
+        let mut crate_entity = make_test_entity().with_name("crate").with_tag("dynamic");
+        crate_entity.transform.position = Vec4::new(3.0, 0.0, 0.0, 1.0);
+        crate_entity.material = Material::RED;
+
+        if ball_first {
+            world.add_entity(ball);
+            world.add_entity(crate_entity);
+        } else {
+            world.add_entity(crate_entity);
+            world.add_entity(ball);
+        }
+        world
+    }
+
+    #[test]
+    fn test_identical_worlds_hash_equal() {
+        let mut a = make_hash_world(true);
+        let mut b = make_hash_world(true);
+        assert_eq!(a.state_hash(), b.state_hash());
+
+        // Stepping both the same way keeps them in sync
+        a.update(0.1);
+        b.update(0.1);
+        assert_eq!(a.state_hash(), b.state_hash());
+        assert_ne!(a.state_hash(), make_hash_world(true).state_hash());
+    }
+
+    #[test]
+    fn test_moving_an_entity_changes_hash() {
+        let mut world = make_hash_world(true);
+        let before = world.state_hash();
+
+        let (key, _) = world.get_by_name("crate").unwrap();
+        world.set_entity_position(key, Vec4::new(3.0, 0.0, 0.0, 1.001));
+        assert_ne!(world.state_hash(), before);
+
+        // Back where it was, the hash returns too
+        world.set_entity_position(key, Vec4::new(3.0, 0.0, 0.0, 1.0));
+        assert_eq!(world.state_hash(), before);
+    }
+
+    #[test]
+    fn test_hash_ignores_insertion_order() {
+        let a = make_hash_world(true);
+        let b = make_hash_world(false);
+        assert_eq!(a.state_hash(), b.state_hash());
+
+        // A different material is a different state, though
+        let mut c = make_hash_world(false);
+        let (key, _) = c.get_by_name("crate").unwrap();
+        c.get_entity_mut(key).unwrap().material = Material::BLUE;
+        assert_ne!(a.state_hash(), c.state_hash());
+    }
+
+    #[test]
+    fn test_hash_covers_unowned_bodies() {
+        let mut world = make_hash_world(true);
+        let before = world.state_hash();
+
+        let loose = world.physics_mut().unwrap().add_body(RigidBody4D::new_sphere(Vec4::ZERO, 0.5));
+        let with_loose = world.state_hash();
+        assert_ne!(with_loose, before);
+
+        world.physics_mut().unwrap().get_body_mut(loose).unwrap().velocity = Vec4::X;
+        assert_ne!(world.state_hash(), with_loose);
+    }
+
+    #[test]
+    fn test_mirror_entity_spawns_reflected_copy() {
+        use rust4d_math::Vec4;
+
+        let mut world = World::new();
+        let mut entity = make_test_entity().with_name("left").with_tag("wall");
+        entity.transform.position = Vec4::new(-3.0, 1.0, 0.0, 2.0);
+        let key = world.add_entity(entity);
+
+        let copy = world.mirror_entity(key, Vec4::X).unwrap();
+        assert_eq!(world.entity_count(), 2);
+        let mirrored = world.get_entity(copy).unwrap();
+        assert_eq!(mirrored.transform.position, Vec4::new(3.0, 1.0, 0.0, 2.0));
+        assert!(mirrored.transform.is_mirrored());
+        assert!(mirrored.has_tag("wall"));
+        assert_eq!(mirrored.name, None);
+
+        // Owned shapes without a template cannot be copied
+        let owned = world.add_entity(Entity::new(ShapeRef::owned(Tesseract4D::new(1.0))));
+        assert_eq!(world.mirror_entity(owned, Vec4::X), None);
+        world.remove_entity(key);
+        assert_eq!(world.mirror_entity(key, Vec4::X), None);
+    }
+
+    #[test]
+    fn test_query_component_only_yields_entities_with_it() {
+        struct Health(u32);
+
+        let mut world = World::new();
+        let a = world.add_entity(make_test_entity().with_component(Health(10)));
+        world.add_entity(make_test_entity());
+        let c = world.add_entity(make_test_entity().with_component(Health(30)));
+        world.add_entity(make_test_entity().with_component(7u8));
+
+        let mut found: Vec<(EntityKey, u32)> = world.query_component::<Health>().map(|(k, h)| (k, h.0)).collect();
+        found.sort_by_key(|&(_, hp)| hp);
+        assert_eq!(found, vec![(a, 10), (c, 30)]);
+        assert_eq!(world.query_component::<u16>().count(), 0);
+    }
 }
@@ -250,6 +250,42 @@ fn test_entity_transform_syncs_from_physics() {
     );
 }
 
+/// Test that a body at rest falls asleep and stops dirtying its entity
+#[test]
+fn test_sleeping_body_stops_marking_entity_dirty() {
+    let mut world = World::new().with_physics(PhysicsConfig::new(-20.0));
+    let physics = world.physics_mut().unwrap();
+    physics.add_static_collider(StaticCollider::floor(0.0, PhysicsMaterial::CONCRETE));
+    let body_key = physics.add_body(
+        RigidBody4D::new_sphere(Vec4::new(0.0, 1.0, 0.0, 0.0), 0.5)
+            .with_material(PhysicsMaterial::CONCRETE),
+    );
+
+    let tesseract = Tesseract4D::new(1.0);
+    let entity = rust4d_core::Entity::new(ShapeRef::shared(tesseract))
+        .with_name("ball")
+        .with_physics_body(body_key);
+    world.add_entity(entity);
+
+    // Settle until asleep
+    for _ in 0..180 {
+        world.update(1.0 / 60.0);
+    }
+    assert!(world.physics().unwrap().get_body(body_key).unwrap().is_sleeping());
+
+    world.clear_all_dirty();
+    world.update(1.0 / 60.0);
+    let (_, entity) = world.get_by_name("ball").unwrap();
+    assert!(!entity.is_dirty(), "Sleeping body should not dirty its entity");
+
+    // An impulse wakes it and it moves again
+    world.physics_mut().unwrap().apply_impulse(body_key, Vec4::new(5.0, 0.0, 0.0, 0.0));
+    world.update(1.0 / 60.0);
+    let (_, entity) = world.get_by_name("ball").unwrap();
+    assert!(entity.is_dirty(), "Woken body should dirty its entity");
+    assert!(entity.transform.position.x > 0.0);
+}
+
 // ==================== Full Pipeline Test ====================
 
 /// The critical test: full scene loading to physics settling
@@ -438,7 +474,7 @@ fn test_physics_step_trace() {
     let key = physics.add_body(body);
 
     println!("=== Physics Step Trace ===");
-    println!("Gravity: {}", physics.config.gravity);
+    println!("Gravity: {:?}", physics.config.gravity_vector);
     println!("Static colliders: {}", physics.static_colliders().len());
 
     for frame in 0..10 {
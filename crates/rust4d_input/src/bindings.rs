@@ -0,0 +1,165 @@
+//! Rebindable controller keys
+//!
+//! [`KeyBindings`] maps each movement [`InputAction`] to the keys that
+//! trigger it. The default matches the classic layout (WASD, Q/E, Space,
+//! Shift, B); [`CameraController`](crate::CameraController) consults the
+//! bindings in `process_keyboard`, so players can remap movement at runtime
+//! or from config.
+
+use serde::{Deserialize, Serialize};
+use winit::keyboard::KeyCode;
+
+/// A controller action driven by a key
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputAction {
+    Forward,
+    Back,
+    Left,
+    Right,
+    /// Move toward +W
+    Ana,
+    /// Move toward -W
+    Kata,
+    /// Free-fly ascend
+    Up,
+    /// Free-fly descend
+    Down,
+    /// Jump in physics mode
+    Jump,
+    /// Toggle double-rotation mouse look (on press)
+    ToggleDoubleRotation,
+}
+
+impl InputAction {
+    /// Every action, in declaration order
+    pub const ALL: [InputAction; 10] = [
+        InputAction::Forward,
+        InputAction::Back,
+        InputAction::Left,
+        InputAction::Right,
+        InputAction::Ana,
+        InputAction::Kata,
+        InputAction::Up,
+        InputAction::Down,
+        InputAction::Jump,
+        InputAction::ToggleDoubleRotation,
+    ];
+}
+
+/// Keys bound to each controller action
+///
+/// An action can have several keys (both Shift keys descend by default), and
+/// a key can drive several actions (Space both ascends and jumps). Missing
+/// entries in config keep their default keys.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub forward: Vec<KeyCode>,
+    pub back: Vec<KeyCode>,
+    pub left: Vec<KeyCode>,
+    pub right: Vec<KeyCode>,
+    pub ana: Vec<KeyCode>,
+    pub kata: Vec<KeyCode>,
+    pub up: Vec<KeyCode>,
+    pub down: Vec<KeyCode>,
+    pub jump: Vec<KeyCode>,
+    pub toggle_double_rotation: Vec<KeyCode>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            forward: vec![KeyCode::KeyW],
+            back: vec![KeyCode::KeyS],
+            left: vec![KeyCode::KeyA],
+            right: vec![KeyCode::KeyD],
+            ana: vec![KeyCode::KeyQ],
+            kata: vec![KeyCode::KeyE],
+            up: vec![KeyCode::Space],
+            down: vec![KeyCode::ShiftLeft, KeyCode::ShiftRight],
+            jump: vec![KeyCode::Space],
+            toggle_double_rotation: vec![KeyCode::KeyB],
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Get the keys bound to an action
+    pub fn keys(&self, action: InputAction) -> &[KeyCode] {
+        match action {
+            InputAction::Forward => &self.forward,
+            InputAction::Back => &self.back,
+            InputAction::Left => &self.left,
+            InputAction::Right => &self.right,
+            InputAction::Ana => &self.ana,
+            InputAction::Kata => &self.kata,
+            InputAction::Up => &self.up,
+            InputAction::Down => &self.down,
+            InputAction::Jump => &self.jump,
+            InputAction::ToggleDoubleRotation => &self.toggle_double_rotation,
+        }
+    }
+
+    fn keys_mut(&mut self, action: InputAction) -> &mut Vec<KeyCode> {
+        match action {
+            InputAction::Forward => &mut self.forward,
+            InputAction::Back => &mut self.back,
+            InputAction::Left => &mut self.left,
+            InputAction::Right => &mut self.right,
+            InputAction::Ana => &mut self.ana,
+            InputAction::Kata => &mut self.kata,
+            InputAction::Up => &mut self.up,
+            InputAction::Down => &mut self.down,
+            InputAction::Jump => &mut self.jump,
+            InputAction::ToggleDoubleRotation => &mut self.toggle_double_rotation,
+        }
+    }
+
+    /// Bind an action to a single key, replacing its previous keys
+    pub fn bind(&mut self, action: InputAction, key: KeyCode) {
+        *self.keys_mut(action) = vec![key];
+    }
+
+    /// Builder: bind an action to a single key (see [`bind`](Self::bind))
+    pub fn with(mut self, action: InputAction, key: KeyCode) -> Self {
+        self.bind(action, key);
+        self
+    }
+
+    /// Actions a key is bound to
+    pub fn actions(&self, key: KeyCode) -> impl Iterator<Item = InputAction> + '_ {
+        InputAction::ALL.into_iter().filter(move |&action| self.keys(action).contains(&key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_layout() {
+        let bindings = KeyBindings::default();
+        assert_eq!(bindings.keys(InputAction::Forward), &[KeyCode::KeyW]);
+        assert_eq!(bindings.actions(KeyCode::ShiftRight).collect::<Vec<_>>(), vec![InputAction::Down]);
+        assert_eq!(
+            bindings.actions(KeyCode::Space).collect::<Vec<_>>(),
+            vec![InputAction::Up, InputAction::Jump]
+        );
+        assert_eq!(bindings.actions(KeyCode::KeyR).count(), 0);
+    }
+
+    #[test]
+    fn test_bind_replaces_keys() {
+        let bindings = KeyBindings::default().with(InputAction::Down, KeyCode::KeyC);
+        assert_eq!(bindings.keys(InputAction::Down), &[KeyCode::KeyC]);
+        assert_eq!(bindings.actions(KeyCode::ShiftLeft).count(), 0);
+    }
+
+    #[test]
+    fn test_partial_config_keeps_defaults() {
+        let bindings: KeyBindings = ron::from_str("(forward: [ArrowUp], jump: [KeyJ, Space])").unwrap();
+        assert_eq!(bindings.keys(InputAction::Forward), &[KeyCode::ArrowUp]);
+        assert_eq!(bindings.keys(InputAction::Jump), &[KeyCode::KeyJ, KeyCode::Space]);
+        assert_eq!(bindings.keys(InputAction::Back), &[KeyCode::KeyS]);
+    }
+}
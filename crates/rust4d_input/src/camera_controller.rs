@@ -1,17 +1,49 @@
 //! Camera controller for 4D Golf-style input handling
 //!
-//! Controls:
+//! Default controls (keys can be remapped with [`KeyBindings`]):
 //! - W/S: Forward/backward (Z)
 //! - A/D: Left/right strafe (X)
 //! - Q/E: Ana/kata movement (W)
 //! - Space/Shift: Up/down (Y)
 //! - Mouse drag: 3D camera rotation
 //! - Right-click + drag: W-axis rotation
-
-use rust4d_math::Vec4;
+//! - B: Toggle double-rotation look (mouse X: XY+ZW, mouse Y: XZ+YW)
+//!
+//! Gamepad:
+//! - Left stick: Forward/backward and strafe
+//! - Right stick: 3D camera rotation
+//! - Right/left trigger: Ana/kata movement (W)
+//! - South button (A/Cross): Up/jump
+//! - East button (B/Circle): Down
+
+use rust4d_math::{RotationPlane, Vec4};
 use winit::event::{ElementState, MouseButton};
 use winit::keyboard::KeyCode;
 
+use crate::bindings::{InputAction, KeyBindings};
+
+/// An analog gamepad axis
+///
+/// Sticks range from -1 to 1, with +Y pushed up; triggers range from 0 to 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+/// A digital gamepad button, named by its position on the face
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+}
+
 /// Camera controller for handling input
 pub struct CameraController {
     // Movement state
@@ -26,10 +58,23 @@ pub struct CameraController {
 
     // Jump state (for physics-based movement)
     jump_pressed: bool,
+    jump_held: bool,
+
+    // Keys driving the movement actions
+    bindings: KeyBindings,
+
+    // Gamepad state (raw axis values, dead zone applied on read)
+    left_stick: (f32, f32),
+    right_stick: (f32, f32),
+    left_trigger: f32,
+    right_trigger: f32,
+    pad_up: bool,    // South button
+    pad_down: bool,  // East button
 
     // Mouse state
     mouse_pressed: bool,
     w_rotation_mode: bool,  // Right-click held
+    double_rotation_mode: bool,  // Mouse look drives isoclinic double rotations
     pending_yaw: f32,
     pending_pitch: f32,
 
@@ -37,6 +82,12 @@ pub struct CameraController {
     smooth_yaw: f32,
     smooth_pitch: f32,
 
+    // Look gating (closed while a modal overlay is up)
+    look_enabled: bool,
+
+    // Set on cursor capture; the next motion event is discarded
+    capture_motion_pending: bool,
+
     // Configuration
     pub move_speed: f32,
     pub w_move_speed: f32,
@@ -44,6 +95,11 @@ pub struct CameraController {
     pub w_rotation_sensitivity: f32,
     pub smoothing_half_life: f32,  // Exponential smoothing half-life in seconds
     pub smoothing_enabled: bool,
+    pub mouse_dead_zone: f32,  // Mouse deltas shorter than this (in pixels) are ignored
+    pub auto_bhop: bool,  // Holding jump re-jumps on every landing
+    pub skip_first_capture_motion: bool,  // Drop the first mouse delta after the cursor is captured
+    pub gamepad_dead_zone: f32,  // Stick and trigger values with a smaller magnitude read as zero
+    pub gamepad_look_speed: f32,  // Right stick turn rate at full deflection (radians per second)
 }
 
 impl Default for CameraController {
@@ -65,46 +121,98 @@ impl CameraController {
             kata: false,
 
             jump_pressed: false,
+            jump_held: false,
+
+            bindings: KeyBindings::default(),
+
+            left_stick: (0.0, 0.0),
+            right_stick: (0.0, 0.0),
+            left_trigger: 0.0,
+            right_trigger: 0.0,
+            pad_up: false,
+            pad_down: false,
 
             mouse_pressed: false,
             w_rotation_mode: false,
+            double_rotation_mode: false,
             pending_yaw: 0.0,
             pending_pitch: 0.0,
 
             smooth_yaw: 0.0,
             smooth_pitch: 0.0,
 
+            look_enabled: true,
+
+            capture_motion_pending: false,
+
             move_speed: 3.0,
             w_move_speed: 2.0,
             mouse_sensitivity: 0.002,  // Standard FPS sensitivity
             w_rotation_sensitivity: 0.005,
             smoothing_half_life: 0.05,  // 50ms half-life when enabled
             smoothing_enabled: false,   // Disabled by default for responsive FPS feel
+            mouse_dead_zone: 0.0,
+            auto_bhop: false,
+            skip_first_capture_motion: true,
+            gamepad_dead_zone: 0.15,
+            gamepad_look_speed: 2.5,
         }
     }
 
     /// Process keyboard input
+    ///
+    /// Returns true if the key is bound to any movement action.
     pub fn process_keyboard(&mut self, key: KeyCode, state: ElementState) -> bool {
         let pressed = state == ElementState::Pressed;
 
-        match key {
-            KeyCode::KeyW => { self.forward = pressed; true }
-            KeyCode::KeyS => { self.backward = pressed; true }
-            KeyCode::KeyA => { self.left = pressed; true }
-            KeyCode::KeyD => { self.right = pressed; true }
-            KeyCode::KeyQ => { self.ana = pressed; true }
-            KeyCode::KeyE => { self.kata = pressed; true }
-            KeyCode::Space => {
-                self.up = pressed;
-                // Also track jump for physics mode
-                if pressed {
-                    self.jump_pressed = true;
+        let mut handled = false;
+        for action in self.bindings.actions(key) {
+            handled = true;
+            match action {
+                InputAction::Forward => self.forward = pressed,
+                InputAction::Back => self.backward = pressed,
+                InputAction::Left => self.left = pressed,
+                InputAction::Right => self.right = pressed,
+                InputAction::Ana => self.ana = pressed,
+                InputAction::Kata => self.kata = pressed,
+                InputAction::Up => self.up = pressed,
+                InputAction::Down => self.down = pressed,
+                InputAction::Jump => {
+                    self.jump_held = pressed;
+                    if pressed {
+                        self.jump_pressed = true;
+                    }
+                }
+                InputAction::ToggleDoubleRotation => {
+                    if pressed {
+                        self.double_rotation_mode = !self.double_rotation_mode;
+                    }
                 }
-                true
             }
-            KeyCode::ShiftLeft | KeyCode::ShiftRight => { self.down = pressed; true }
-            _ => false,
         }
+        handled
+    }
+
+    /// Get the current key bindings
+    pub fn bindings(&self) -> &KeyBindings {
+        &self.bindings
+    }
+
+    /// Replace the key bindings
+    ///
+    /// Held movement keys are released, since the keys holding them may no
+    /// longer be bound (and their release would never be seen).
+    pub fn set_bindings(&mut self, bindings: KeyBindings) {
+        self.bindings = bindings;
+        self.forward = false;
+        self.backward = false;
+        self.left = false;
+        self.right = false;
+        self.up = false;
+        self.down = false;
+        self.ana = false;
+        self.kata = false;
+        self.jump_held = false;
     }
 
     /// Process mouse button input
@@ -123,27 +231,100 @@ impl CameraController {
     }
 
     /// Process mouse movement
+    ///
+    /// Ignored while look input is gated off, when the delta is inside the dead zone,
+    /// or when it is the first delta after [`notify_cursor_captured`](Self::notify_cursor_captured).
     pub fn process_mouse_motion(&mut self, delta_x: f64, delta_y: f64) {
+        if !self.look_enabled {
+            return;
+        }
+        if self.capture_motion_pending {
+            self.capture_motion_pending = false;
+            return;
+        }
+        if ((delta_x * delta_x + delta_y * delta_y) as f32).sqrt() < self.mouse_dead_zone {
+            return;
+        }
         self.pending_yaw += delta_x as f32;
         self.pending_pitch += delta_y as f32;
     }
 
+    /// Process a gamepad axis moving to `value`
+    ///
+    /// Values are stored as given and clamped to the axis range; the dead zone
+    /// is applied when input is read, so changing it takes effect immediately.
+    pub fn process_gamepad_axis(&mut self, axis: GamepadAxis, value: f32) {
+        let stick = value.clamp(-1.0, 1.0);
+        let trigger = value.clamp(0.0, 1.0);
+        match axis {
+            GamepadAxis::LeftStickX => self.left_stick.0 = stick,
+            GamepadAxis::LeftStickY => self.left_stick.1 = stick,
+            GamepadAxis::RightStickX => self.right_stick.0 = stick,
+            GamepadAxis::RightStickY => self.right_stick.1 = stick,
+            GamepadAxis::LeftTrigger => self.left_trigger = trigger,
+            GamepadAxis::RightTrigger => self.right_trigger = trigger,
+        }
+    }
+
+    /// Process gamepad button input
+    ///
+    /// Returns true if the button is mapped.
+    pub fn process_gamepad_button(&mut self, button: GamepadButton, pressed: bool) -> bool {
+        match button {
+            GamepadButton::South => {
+                self.pad_up = pressed;
+                if pressed {
+                    self.jump_pressed = true;
+                }
+                true
+            }
+            GamepadButton::East => { self.pad_down = pressed; true }
+            _ => false,
+        }
+    }
+
+    /// A stick's deflection, or zero inside the dead zone
+    ///
+    /// The dead zone is radial, so a slightly off-center stick doesn't drift
+    /// along either axis.
+    fn stick(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        if x * x + y * y < self.gamepad_dead_zone * self.gamepad_dead_zone {
+            (0.0, 0.0)
+        } else {
+            (x, y)
+        }
+    }
+
+    /// A trigger's value, or zero inside the dead zone
+    fn trigger(&self, value: f32) -> f32 {
+        if value < self.gamepad_dead_zone { 0.0 } else { value }
+    }
+
+    /// Get up/down movement input in range -1.0 to 1.0
+    fn get_vertical_input(&self) -> f32 {
+        ((self.up || self.pad_up) as i32 - (self.down || self.pad_down) as i32) as f32
+    }
+
     /// Update the camera based on accumulated input
     ///
     /// When `cursor_captured` is true, free look is enabled (no click required).
     /// Returns the camera position for debug display.
     pub fn update<C: CameraControl>(&mut self, camera: &mut C, dt: f32, cursor_captured: bool) -> Vec4 {
         // Calculate movement deltas
-        let fwd = (self.forward as i32 - self.backward as i32) as f32;
-        let rgt = (self.right as i32 - self.left as i32) as f32;
-        let up_down = (self.up as i32 - self.down as i32) as f32;
-        let w = (self.ana as i32 - self.kata as i32) as f32;
+        let (fwd, rgt) = self.get_movement_input();
+        let up_down = self.get_vertical_input();
+        let w = self.get_w_input();
 
         // Apply movement
         camera.move_local_xz(fwd * self.move_speed * dt, rgt * self.move_speed * dt);
         camera.move_y(up_down * self.move_speed * dt);
         camera.move_w(w * self.w_move_speed * dt);
 
+        if !self.look_enabled {
+            self.reset_look_state();
+            return camera.position();
+        }
+
         // Apply exponential smoothing to mouse input (engine4d-style)
         let (yaw_input, pitch_input) = if self.smoothing_enabled && dt > 0.0 {
             // Exponential smoothing: new = old * factor + input * (1 - factor)
@@ -167,7 +348,14 @@ impl CameraController {
                 // Vertical mouse: XW rotation (roll_xw)
                 camera.rotate_w(yaw_input * self.w_rotation_sensitivity);
                 camera.rotate_xw(pitch_input * self.w_rotation_sensitivity);
-            } else if can_look {
+            } else if self.double_rotation_mode {
+                // Double rotation mode: each mouse axis turns two orthogonal
+                // planes by the same angle (isoclinic), a genuinely 4D rotation
+                let x_angle = yaw_input * self.w_rotation_sensitivity;
+                let y_angle = -pitch_input * self.w_rotation_sensitivity;
+                camera.rotate_double(RotationPlane::XY, x_angle, RotationPlane::ZW, x_angle);
+                camera.rotate_double(RotationPlane::XZ, y_angle, RotationPlane::YW, y_angle);
+            } else {
                 // Free look: Standard 3D FPS rotation
                 // Mouse right (positive delta_x) should turn camera right (positive yaw)
                 // Mouse down (positive delta_y) should look down (negative pitch)
@@ -178,6 +366,13 @@ impl CameraController {
             }
         }
 
+        // Right stick turns at a steady rate, whether or not the cursor is captured
+        let (look_x, look_y) = self.stick(self.right_stick);
+        if look_x != 0.0 || look_y != 0.0 {
+            let turn = self.gamepad_look_speed * dt;
+            camera.rotate_3d(look_x * turn, look_y * turn);
+        }
+
         // Reset pending mouse movement
         self.pending_yaw = 0.0;
         self.pending_pitch = 0.0;
@@ -185,10 +380,13 @@ impl CameraController {
         camera.position()
     }
 
-    /// Check if any movement keys are pressed
+    /// Check if any movement keys are pressed or the gamepad is moving the camera
     pub fn is_moving(&self) -> bool {
         self.forward || self.backward || self.left || self.right
             || self.up || self.down || self.ana || self.kata
+            || self.pad_up || self.pad_down
+            || self.get_movement_input() != (0.0, 0.0)
+            || self.get_w_input() != 0.0
     }
 
     /// Toggle input smoothing on/off
@@ -205,6 +403,56 @@ impl CameraController {
         self.smoothing_enabled
     }
 
+    /// Switch mouse look between FPS rotation and double rotation
+    ///
+    /// In double-rotation mode, horizontal mouse motion rotates the XY and ZW
+    /// planes together and vertical motion rotates XZ and YW together, using
+    /// `w_rotation_sensitivity`. Right-click W rotation still takes priority.
+    pub fn set_double_rotation_mode(&mut self, enabled: bool) {
+        self.double_rotation_mode = enabled;
+    }
+
+    /// Check if mouse look drives double rotations
+    pub fn is_double_rotation_mode(&self) -> bool {
+        self.double_rotation_mode
+    }
+
+    /// Open or close the look input gate
+    ///
+    /// While closed (e.g. a pause menu is up), mouse motion is discarded and
+    /// `update` applies no rotation. Changing the gate clears any pending and
+    /// smoothed look input, so the view doesn't jump when gameplay resumes.
+    pub fn set_look_enabled(&mut self, enabled: bool) {
+        if self.look_enabled != enabled {
+            self.look_enabled = enabled;
+            self.reset_look_state();
+        }
+    }
+
+    /// Check if look input is currently accepted
+    pub fn is_look_enabled(&self) -> bool {
+        self.look_enabled
+    }
+
+    /// Tell the controller the cursor was just captured
+    ///
+    /// The first motion event after capture often carries the whole distance
+    /// the cursor travelled while free, which snaps the view. With
+    /// `skip_first_capture_motion` set, that event is discarded and look input
+    /// resumes from the one after it.
+    pub fn notify_cursor_captured(&mut self) {
+        self.reset_look_state();
+        self.capture_motion_pending = self.skip_first_capture_motion;
+    }
+
+    /// Clear pending and smoothed mouse input
+    fn reset_look_state(&mut self) {
+        self.pending_yaw = 0.0;
+        self.pending_pitch = 0.0;
+        self.smooth_yaw = 0.0;
+        self.smooth_pitch = 0.0;
+    }
+
     /// Consume the jump input flag
     ///
     /// Returns true if jump was pressed since last consume, then clears the flag.
@@ -215,23 +463,43 @@ impl CameraController {
         was_pressed
     }
 
+    /// Check if the jump key (or gamepad jump button) is currently held down
+    pub fn is_jump_held(&self) -> bool {
+        self.jump_held || self.pad_up
+    }
+
+    /// Consume the jump request for this physics step
+    ///
+    /// Like [`consume_jump`](Self::consume_jump), but with `auto_bhop` enabled
+    /// a held jump key keeps requesting a jump every step, so the player
+    /// bounces again the moment they land.
+    pub fn wants_jump(&mut self) -> bool {
+        let pressed = self.consume_jump();
+        pressed || (self.auto_bhop && self.is_jump_held())
+    }
+
     /// Get raw movement input for physics-based movement
     ///
     /// Returns (forward, right) input values in range -1.0 to 1.0.
     /// Forward is positive when W is pressed, negative when S is pressed.
     /// Right is positive when D is pressed, negative when A is pressed.
+    /// The left stick adds its deflection to the keys, clamped to that range.
     pub fn get_movement_input(&self) -> (f32, f32) {
-        let forward = (self.forward as i32 - self.backward as i32) as f32;
-        let right = (self.right as i32 - self.left as i32) as f32;
-        (forward, right)
+        let (stick_right, stick_forward) = self.stick(self.left_stick);
+        let forward = (self.forward as i32 - self.backward as i32) as f32 + stick_forward;
+        let right = (self.right as i32 - self.left as i32) as f32 + stick_right;
+        (forward.clamp(-1.0, 1.0), right.clamp(-1.0, 1.0))
     }
 
     /// Get W-axis (ana/kata) movement input
     ///
     /// Returns input value in range -1.0 to 1.0.
     /// Positive when Q is pressed (ana), negative when E is pressed (kata).
+    /// The right trigger adds to it and the left trigger subtracts.
     pub fn get_w_input(&self) -> f32 {
-        (self.ana as i32 - self.kata as i32) as f32
+        let keys = (self.ana as i32 - self.kata as i32) as f32;
+        let triggers = self.trigger(self.right_trigger) - self.trigger(self.left_trigger);
+        (keys + triggers).clamp(-1.0, 1.0)
     }
 
     /// Builder: set movement speed
@@ -269,6 +537,42 @@ impl CameraController {
         self.smoothing_enabled = enabled;
         self
     }
+
+    /// Builder: set the mouse dead zone (in pixels)
+    pub fn with_mouse_dead_zone(mut self, dead_zone: f32) -> Self {
+        self.mouse_dead_zone = dead_zone;
+        self
+    }
+
+    /// Builder: enable or disable auto bunny-hop (held jump re-jumps on landing)
+    pub fn with_auto_bhop(mut self, enabled: bool) -> Self {
+        self.auto_bhop = enabled;
+        self
+    }
+
+    /// Builder: discard the first mouse delta after the cursor is captured
+    pub fn with_skip_first_capture_motion(mut self, enabled: bool) -> Self {
+        self.skip_first_capture_motion = enabled;
+        self
+    }
+
+    /// Builder: set the key bindings
+    pub fn with_bindings(mut self, bindings: KeyBindings) -> Self {
+        self.bindings = bindings;
+        self
+    }
+
+    /// Builder: set the gamepad stick and trigger dead zone (0 to 1)
+    pub fn with_gamepad_dead_zone(mut self, dead_zone: f32) -> Self {
+        self.gamepad_dead_zone = dead_zone;
+        self
+    }
+
+    /// Builder: set the right stick turn rate (radians per second)
+    pub fn with_gamepad_look_speed(mut self, speed: f32) -> Self {
+        self.gamepad_look_speed = speed;
+        self
+    }
 }
 
 /// Trait for camera control
@@ -280,6 +584,8 @@ pub trait CameraControl {
     fn rotate_3d(&mut self, delta_yaw: f32, delta_pitch: f32);
     fn rotate_w(&mut self, delta: f32);
     fn rotate_xw(&mut self, delta: f32);
+    /// Rotate `plane_a` by `angle_a` and `plane_b` by `angle_b` at once
+    fn rotate_double(&mut self, plane_a: RotationPlane, angle_a: f32, plane_b: RotationPlane, angle_b: f32);
     fn position(&self) -> Vec4;
 }
 
@@ -628,6 +934,28 @@ mod tests {
         assert!(!controller.consume_jump());
     }
 
+    #[test]
+    fn test_wants_jump_once_per_press_without_auto_bhop() {
+        let mut controller = CameraController::new();
+
+        controller.process_keyboard(KeyCode::Space, ElementState::Pressed);
+        assert!(controller.is_jump_held());
+        assert!(controller.wants_jump());
+        assert!(!controller.wants_jump());
+    }
+
+    #[test]
+    fn test_wants_jump_while_held_with_auto_bhop() {
+        let mut controller = CameraController::new().with_auto_bhop(true);
+
+        controller.process_keyboard(KeyCode::Space, ElementState::Pressed);
+        assert!(controller.wants_jump());
+        assert!(controller.wants_jump());
+
+        controller.process_keyboard(KeyCode::Space, ElementState::Released);
+        assert!(!controller.wants_jump());
+    }
+
     // ==================== Mouse Input Tests ====================
 
     #[test]
@@ -718,6 +1046,7 @@ mod tests {
         pub pitch_rotated: f32,
         pub w_rotated: f32,
         pub xw_rotated: f32,
+        pub double_rotations: Vec<(RotationPlane, f32, RotationPlane, f32)>,
     }
 
     impl MockCamera {
@@ -732,6 +1061,7 @@ mod tests {
                 pitch_rotated: 0.0,
                 w_rotated: 0.0,
                 xw_rotated: 0.0,
+                double_rotations: Vec::new(),
             }
         }
     }
@@ -763,6 +1093,10 @@ mod tests {
             self.xw_rotated += delta;
         }
 
+        fn rotate_double(&mut self, plane_a: RotationPlane, angle_a: f32, plane_b: RotationPlane, angle_b: f32) {
+            self.double_rotations.push((plane_a, angle_a, plane_b, angle_b));
+        }
+
         fn position(&self) -> Vec4 {
             self.position
         }
@@ -921,6 +1255,311 @@ mod tests {
         assert!((camera.yaw_rotated - 1.0).abs() < 0.001);
     }
 
+    // ==================== Look Gate Tests ====================
+
+    #[test]
+    fn test_look_gate_closed_blocks_rotation() {
+        let mut controller = CameraController::new().with_mouse_sensitivity(0.01);
+        let mut camera = MockCamera::new();
+
+        controller.set_look_enabled(false);
+        assert!(!controller.is_look_enabled());
+        controller.process_mouse_motion(100.0, 50.0);
+        controller.update(&mut camera, 0.1, true);
+
+        assert_eq!(camera.yaw_rotated, 0.0);
+        assert_eq!(camera.pitch_rotated, 0.0);
+    }
+
+    #[test]
+    fn test_look_gate_closed_still_moves() {
+        let mut controller = CameraController::new().with_move_speed(10.0);
+        let mut camera = MockCamera::new();
+
+        controller.set_look_enabled(false);
+        controller.process_keyboard(KeyCode::KeyW, ElementState::Pressed);
+        controller.update(&mut camera, 0.1, true);
+
+        assert!((camera.forward_moved - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_look_gate_reopen_restores_rotation() {
+        let mut controller = CameraController::new().with_mouse_sensitivity(0.01);
+        let mut camera = MockCamera::new();
+
+        controller.set_look_enabled(false);
+        controller.process_mouse_motion(500.0, 500.0);
+        controller.update(&mut camera, 0.1, true);
+
+        controller.set_look_enabled(true);
+        controller.process_mouse_motion(100.0, 50.0);
+        controller.update(&mut camera, 0.1, true);
+
+        // Only the motion after reopening is applied
+        assert!((camera.yaw_rotated - 1.0).abs() < 0.001);
+        assert!((camera.pitch_rotated - (-0.5)).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_closing_look_gate_clears_pending_input() {
+        let mut controller = CameraController::new().with_smoothing(true);
+        let mut camera = MockCamera::new();
+
+        controller.process_mouse_motion(100.0, 50.0);
+        controller.update(&mut camera, 0.016, true);
+        controller.process_mouse_motion(100.0, 50.0);
+        controller.set_look_enabled(false);
+
+        assert_eq!(controller.pending_yaw, 0.0);
+        assert_eq!(controller.pending_pitch, 0.0);
+        assert_eq!(controller.smooth_yaw, 0.0);
+        assert_eq!(controller.smooth_pitch, 0.0);
+    }
+
+    #[test]
+    fn test_mouse_dead_zone() {
+        let mut controller = CameraController::new().with_mouse_dead_zone(2.0);
+
+        controller.process_mouse_motion(1.0, 1.0);
+        assert_eq!(controller.pending_yaw, 0.0);
+
+        controller.process_mouse_motion(3.0, 0.0);
+        assert_eq!(controller.pending_yaw, 3.0);
+    }
+
+    #[test]
+    fn test_first_motion_after_capture_is_discarded() {
+        let mut controller = CameraController::new();
+        let mut camera = MockCamera::new();
+
+        controller.notify_cursor_captured();
+        controller.process_mouse_motion(500.0, -300.0);
+        controller.update(&mut camera, 0.016, true);
+        assert_eq!(camera.yaw_rotated, 0.0);
+        assert_eq!(camera.pitch_rotated, 0.0);
+
+        // Later deltas are applied normally
+        controller.process_mouse_motion(10.0, 0.0);
+        controller.update(&mut camera, 0.016, true);
+        assert!((camera.yaw_rotated - 10.0 * controller.mouse_sensitivity).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_capture_motion_kept_when_skip_disabled() {
+        let mut controller = CameraController::new().with_skip_first_capture_motion(false);
+        let mut camera = MockCamera::new();
+
+        controller.notify_cursor_captured();
+        controller.process_mouse_motion(10.0, 0.0);
+        controller.update(&mut camera, 0.016, true);
+        assert!((camera.yaw_rotated - 10.0 * controller.mouse_sensitivity).abs() < 1e-6);
+    }
+
+    // ==================== Double Rotation Tests ====================
+
+    #[test]
+    fn test_double_rotation_toggle_key() {
+        let mut controller = CameraController::new();
+        assert!(!controller.is_double_rotation_mode());
+
+        assert!(controller.process_keyboard(KeyCode::KeyB, ElementState::Pressed));
+        assert!(controller.is_double_rotation_mode());
+        // Releasing doesn't toggle back
+        controller.process_keyboard(KeyCode::KeyB, ElementState::Released);
+        assert!(controller.is_double_rotation_mode());
+
+        controller.process_keyboard(KeyCode::KeyB, ElementState::Pressed);
+        assert!(!controller.is_double_rotation_mode());
+    }
+
+    #[test]
+    fn test_double_rotation_mode_routes_mouse() {
+        let mut controller = CameraController::new();
+        let mut camera = MockCamera::new();
+        controller.set_double_rotation_mode(true);
+
+        controller.process_mouse_motion(100.0, 50.0);
+        controller.update(&mut camera, 0.016, true);
+
+        let x = 100.0 * controller.w_rotation_sensitivity;
+        let y = -50.0 * controller.w_rotation_sensitivity;
+        assert_eq!(
+            camera.double_rotations,
+            vec![
+                (RotationPlane::XY, x, RotationPlane::ZW, x),
+                (RotationPlane::XZ, y, RotationPlane::YW, y),
+            ]
+        );
+        // No FPS or W rotation alongside it
+        assert_eq!(camera.yaw_rotated, 0.0);
+        assert_eq!(camera.pitch_rotated, 0.0);
+        assert_eq!(camera.w_rotated, 0.0);
+    }
+
+    #[test]
+    fn test_fps_mode_makes_no_double_rotations() {
+        let mut controller = CameraController::new();
+        let mut camera = MockCamera::new();
+
+        controller.process_mouse_motion(100.0, 50.0);
+        controller.update(&mut camera, 0.016, true);
+        assert!(camera.double_rotations.is_empty());
+        assert!((camera.yaw_rotated - 100.0 * controller.mouse_sensitivity).abs() < 1e-6);
+        assert!((camera.pitch_rotated + 50.0 * controller.mouse_sensitivity).abs() < 1e-6);
+
+        // Toggled on and off again, mouse look is plain FPS rotation
+        controller.process_keyboard(KeyCode::KeyB, ElementState::Pressed);
+        controller.process_keyboard(KeyCode::KeyB, ElementState::Pressed);
+        controller.process_mouse_motion(10.0, 0.0);
+        controller.update(&mut camera, 0.016, true);
+        assert!(camera.double_rotations.is_empty());
+    }
+
+    #[test]
+    fn test_double_rotation_needs_look() {
+        let mut controller = CameraController::new();
+        let mut camera = MockCamera::new();
+        controller.set_double_rotation_mode(true);
+
+        // Cursor not captured and no button held
+        controller.process_mouse_motion(100.0, 50.0);
+        controller.update(&mut camera, 0.016, false);
+        assert!(camera.double_rotations.is_empty());
+    }
+
+    // ==================== Key Binding Tests ====================
+
+    #[test]
+    fn test_rebound_forward_key() {
+        let bindings = KeyBindings::default().with(InputAction::Forward, KeyCode::ArrowUp);
+        let mut controller = CameraController::new().with_bindings(bindings);
+
+        assert!(controller.process_keyboard(KeyCode::ArrowUp, ElementState::Pressed));
+        assert_eq!(controller.get_movement_input(), (1.0, 0.0));
+        controller.process_keyboard(KeyCode::ArrowUp, ElementState::Released);
+
+        // The old key no longer moves
+        assert!(!controller.process_keyboard(KeyCode::KeyW, ElementState::Pressed));
+        assert_eq!(controller.get_movement_input(), (0.0, 0.0));
+        assert!(!controller.is_moving());
+    }
+
+    #[test]
+    fn test_jump_and_up_bound_separately() {
+        let bindings = KeyBindings::default().with(InputAction::Jump, KeyCode::KeyJ);
+        let mut controller = CameraController::new().with_bindings(bindings);
+
+        // Space still ascends but no longer jumps
+        controller.process_keyboard(KeyCode::Space, ElementState::Pressed);
+        assert!(controller.is_moving());
+        assert!(!controller.consume_jump());
+        assert!(!controller.is_jump_held());
+
+        controller.process_keyboard(KeyCode::KeyJ, ElementState::Pressed);
+        assert!(controller.is_jump_held());
+        assert!(controller.consume_jump());
+    }
+
+    #[test]
+    fn test_set_bindings_releases_held_keys() {
+        let mut controller = CameraController::new();
+        controller.process_keyboard(KeyCode::KeyW, ElementState::Pressed);
+
+        controller.set_bindings(KeyBindings::default().with(InputAction::Forward, KeyCode::ArrowUp));
+        assert!(!controller.is_moving());
+        assert_eq!(controller.bindings().keys(InputAction::Forward), &[KeyCode::ArrowUp]);
+    }
+
+    // ==================== Gamepad Tests ====================
+
+    #[test]
+    fn test_gamepad_stick_gives_analog_movement() {
+        let mut controller = CameraController::new();
+
+        controller.process_gamepad_axis(GamepadAxis::LeftStickY, 0.5);
+        assert_eq!(controller.get_movement_input(), (0.5, 0.0));
+        assert!(controller.is_moving());
+
+        controller.process_gamepad_axis(GamepadAxis::LeftStickX, -0.25);
+        assert_eq!(controller.get_movement_input(), (0.5, -0.25));
+    }
+
+    #[test]
+    fn test_gamepad_dead_zone() {
+        let mut controller = CameraController::new().with_gamepad_dead_zone(0.2);
+
+        controller.process_gamepad_axis(GamepadAxis::LeftStickY, 0.1);
+        controller.process_gamepad_axis(GamepadAxis::LeftStickX, 0.1);
+        controller.process_gamepad_axis(GamepadAxis::RightTrigger, 0.15);
+        assert_eq!(controller.get_movement_input(), (0.0, 0.0));
+        assert_eq!(controller.get_w_input(), 0.0);
+        assert!(!controller.is_moving());
+
+        // Outside the dead zone the value passes through unchanged
+        controller.process_gamepad_axis(GamepadAxis::RightTrigger, 0.6);
+        assert_eq!(controller.get_w_input(), 0.6);
+    }
+
+    #[test]
+    fn test_gamepad_combines_with_keyboard() {
+        let mut controller = CameraController::new();
+
+        // Opposing key and stick partly cancel
+        controller.process_keyboard(KeyCode::KeyW, ElementState::Pressed);
+        controller.process_gamepad_axis(GamepadAxis::LeftStickY, -0.5);
+        assert_eq!(controller.get_movement_input(), (0.5, 0.0));
+
+        // Agreeing key and stick don't exceed full speed
+        controller.process_gamepad_axis(GamepadAxis::LeftStickY, 0.5);
+        assert_eq!(controller.get_movement_input(), (1.0, 0.0));
+
+        // Same for the W axis: kata key against the ana trigger
+        controller.process_keyboard(KeyCode::KeyE, ElementState::Pressed);
+        controller.process_gamepad_axis(GamepadAxis::RightTrigger, 0.75);
+        assert_eq!(controller.get_w_input(), -0.25);
+    }
+
+    #[test]
+    fn test_gamepad_triggers_move_along_w() {
+        let mut controller = CameraController::new();
+        let mut camera = MockCamera::new();
+
+        controller.process_gamepad_axis(GamepadAxis::LeftTrigger, 0.5);
+        assert_eq!(controller.get_w_input(), -0.5);
+
+        controller.update(&mut camera, 0.1, false);
+        assert!((camera.w_moved - (-0.5 * controller.w_move_speed * 0.1)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_gamepad_right_stick_looks() {
+        let mut controller = CameraController::new();
+        let mut camera = MockCamera::new();
+
+        // Stick look works without capturing the cursor
+        controller.process_gamepad_axis(GamepadAxis::RightStickX, 1.0);
+        controller.process_gamepad_axis(GamepadAxis::RightStickY, 0.5);
+        controller.update(&mut camera, 0.1, false);
+        assert!((camera.yaw_rotated - controller.gamepad_look_speed * 0.1).abs() < 1e-6);
+        assert!((camera.pitch_rotated - 0.5 * controller.gamepad_look_speed * 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_gamepad_buttons() {
+        let mut controller = CameraController::new();
+
+        assert!(controller.process_gamepad_button(GamepadButton::South, true));
+        assert!(controller.is_jump_held());
+        assert!(controller.consume_jump());
+
+        controller.process_gamepad_button(GamepadButton::South, false);
+        assert!(!controller.is_jump_held());
+
+        assert!(!controller.process_gamepad_button(GamepadButton::North, true));
+        assert!(!controller.is_moving());
+    }
+
     #[test]
     fn test_update_returns_camera_position() {
         let mut controller = CameraController::new();
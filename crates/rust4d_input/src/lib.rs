@@ -3,6 +3,12 @@
 //! This crate provides input handling for 4D camera control,
 //! replicating 4D Golf-style controls.
 
+mod bindings;
 mod camera_controller;
+mod recording;
 
-pub use camera_controller::{CameraController, CameraControl};
+pub use bindings::{InputAction, KeyBindings};
+pub use camera_controller::{CameraController, CameraControl, GamepadAxis, GamepadButton};
+pub use recording::{
+    InputRecorder, InputRecording, InputReplayer, RecordedInput, RecordingError, TimedInput,
+};
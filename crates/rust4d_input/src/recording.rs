@@ -0,0 +1,402 @@
+//! Input recording and replay
+//!
+//! [`InputRecorder`] captures raw keyboard, mouse button and mouse motion
+//! events, stamped with the frame they arrived in, and saves them to a RON
+//! file. [`InputReplayer`] feeds a recording back frame by frame, so a bug
+//! report can ship the exact input stream that triggered it. Special actions
+//! (reset, toggles) are derived from key events, so recording keys is enough
+//! to reproduce them.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use winit::event::{ElementState, MouseButton};
+use winit::keyboard::KeyCode;
+
+use crate::CameraController;
+
+/// A single raw input event
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RecordedInput {
+    /// Keyboard key press or release
+    Key { key: KeyCode, state: ElementState },
+    /// Mouse button press or release
+    MouseButton { button: MouseButton, state: ElementState },
+    /// Raw mouse motion delta
+    MouseMotion { dx: f64, dy: f64 },
+}
+
+impl RecordedInput {
+    /// Feed this event into a camera controller
+    pub fn apply(&self, controller: &mut CameraController) {
+        match *self {
+            RecordedInput::Key { key, state } => {
+                controller.process_keyboard(key, state);
+            }
+            RecordedInput::MouseButton { button, state } => {
+                controller.process_mouse_button(button, state);
+            }
+            RecordedInput::MouseMotion { dx, dy } => {
+                controller.process_mouse_motion(dx, dy);
+            }
+        }
+    }
+}
+
+/// An input event with the frame and time it was recorded at
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimedInput {
+    /// Frame index the event arrived in (events apply before that frame's update)
+    pub frame: u64,
+    /// Seconds since recording started (informational; replay is frame based)
+    pub time: f64,
+    /// The event
+    pub input: RecordedInput,
+}
+
+/// A recorded input stream
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct InputRecording {
+    /// Events in arrival order
+    pub events: Vec<TimedInput>,
+    /// Number of frames covered by the recording
+    pub frame_count: u64,
+}
+
+impl InputRecording {
+    /// Load a recording from a RON file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, RecordingError> {
+        let contents = fs::read_to_string(path)?;
+        Ok(Self::from_ron_str(&contents)?)
+    }
+
+    /// Save the recording to a RON file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), RecordingError> {
+        let contents = self.to_ron_string()?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Serialize the recording to RON text
+    pub fn to_ron_string(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::new())
+    }
+
+    /// Parse a recording from RON text
+    pub fn from_ron_str(s: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(s)
+    }
+}
+
+/// Error loading or saving an input recording
+#[derive(Debug)]
+pub enum RecordingError {
+    /// IO error (file not found, permission denied, etc.)
+    Io(io::Error),
+    /// Parse error (invalid RON syntax)
+    Parse(ron::error::SpannedError),
+    /// Serialization error
+    Serialize(ron::Error),
+}
+
+impl From<io::Error> for RecordingError {
+    fn from(e: io::Error) -> Self {
+        RecordingError::Io(e)
+    }
+}
+
+impl From<ron::error::SpannedError> for RecordingError {
+    fn from(e: ron::error::SpannedError) -> Self {
+        RecordingError::Parse(e)
+    }
+}
+
+impl From<ron::Error> for RecordingError {
+    fn from(e: ron::Error) -> Self {
+        RecordingError::Serialize(e)
+    }
+}
+
+impl std::fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordingError::Io(e) => write!(f, "IO error: {}", e),
+            RecordingError::Parse(e) => write!(f, "Parse error: {}", e),
+            RecordingError::Serialize(e) => write!(f, "Serialization error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RecordingError {}
+
+/// Captures raw input events frame by frame
+pub struct InputRecorder {
+    recording: InputRecording,
+    start: Instant,
+}
+
+impl Default for InputRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InputRecorder {
+    /// Start a new recording at frame 0
+    pub fn new() -> Self {
+        Self {
+            recording: InputRecording::default(),
+            start: Instant::now(),
+        }
+    }
+
+    /// Record an event in the current frame
+    pub fn record(&mut self, input: RecordedInput) {
+        self.recording.events.push(TimedInput {
+            frame: self.recording.frame_count,
+            time: self.start.elapsed().as_secs_f64(),
+            input,
+        });
+    }
+
+    /// Record a keyboard event
+    pub fn record_key(&mut self, key: KeyCode, state: ElementState) {
+        self.record(RecordedInput::Key { key, state });
+    }
+
+    /// Record a mouse button event
+    pub fn record_mouse_button(&mut self, button: MouseButton, state: ElementState) {
+        self.record(RecordedInput::MouseButton { button, state });
+    }
+
+    /// Record a raw mouse motion delta
+    pub fn record_mouse_motion(&mut self, dx: f64, dy: f64) {
+        self.record(RecordedInput::MouseMotion { dx, dy });
+    }
+
+    /// Mark the end of a frame (call after the frame's update)
+    pub fn end_frame(&mut self) {
+        self.recording.frame_count += 1;
+    }
+
+    /// Get the current frame index
+    pub fn frame(&self) -> u64 {
+        self.recording.frame_count
+    }
+
+    /// Get the recording so far
+    pub fn recording(&self) -> &InputRecording {
+        &self.recording
+    }
+
+    /// Stop recording and take the result
+    pub fn finish(self) -> InputRecording {
+        self.recording
+    }
+}
+
+/// Plays a recording back frame by frame
+pub struct InputReplayer {
+    recording: InputRecording,
+    /// Index of the next event to play
+    cursor: usize,
+    /// Frame about to be played
+    frame: u64,
+}
+
+impl InputReplayer {
+    /// Start replaying a recording from frame 0
+    pub fn new(recording: InputRecording) -> Self {
+        Self {
+            recording,
+            cursor: 0,
+            frame: 0,
+        }
+    }
+
+    /// Take the events for the current frame and advance to the next frame
+    pub fn next_frame(&mut self) -> &[TimedInput] {
+        let start = self.cursor;
+        let events = &self.recording.events;
+        while self.cursor < events.len() && events[self.cursor].frame <= self.frame {
+            self.cursor += 1;
+        }
+        self.frame += 1;
+        &self.recording.events[start..self.cursor]
+    }
+
+    /// Feed the current frame's events into a controller and advance
+    pub fn apply_frame(&mut self, controller: &mut CameraController) {
+        for event in self.next_frame() {
+            event.input.apply(controller);
+        }
+    }
+
+    /// Get the frame about to be played
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Check if every recorded frame has been played
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.recording.frame_count && self.cursor >= self.recording.events.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CameraControl;
+    use rust4d_math::{RotationPlane, Vec4};
+
+    /// Camera that accumulates everything the controller does to it
+    #[derive(Debug, Default, PartialEq)]
+    struct RecordingCamera {
+        position: Vec4,
+        yaw: f32,
+        pitch: f32,
+        w_angle: f32,
+        xw_angle: f32,
+        double_angle: f32,
+    }
+
+    impl CameraControl for RecordingCamera {
+        fn move_local_xz(&mut self, forward: f32, right: f32) {
+            self.position.z += forward;
+            self.position.x += right;
+        }
+        fn move_y(&mut self, delta: f32) {
+            self.position.y += delta;
+        }
+        fn move_w(&mut self, delta: f32) {
+            self.position.w += delta;
+        }
+        fn rotate_3d(&mut self, delta_yaw: f32, delta_pitch: f32) {
+            self.yaw += delta_yaw;
+            self.pitch += delta_pitch;
+        }
+        fn rotate_w(&mut self, delta: f32) {
+            self.w_angle += delta;
+        }
+        fn rotate_xw(&mut self, delta: f32) {
+            self.xw_angle += delta;
+        }
+        fn rotate_double(&mut self, _: RotationPlane, angle_a: f32, _: RotationPlane, angle_b: f32) {
+            self.double_angle += angle_a + angle_b;
+        }
+        fn position(&self) -> Vec4 {
+            self.position
+        }
+    }
+
+    /// Per-frame input script: events delivered before each update
+    fn script() -> Vec<Vec<RecordedInput>> {
+        use ElementState::{Pressed, Released};
+        vec![
+            vec![RecordedInput::Key { key: KeyCode::KeyW, state: Pressed }],
+            vec![RecordedInput::MouseMotion { dx: 12.0, dy: -3.0 }],
+            vec![
+                RecordedInput::MouseButton { button: MouseButton::Right, state: Pressed },
+                RecordedInput::MouseMotion { dx: 5.0, dy: 7.5 },
+            ],
+            vec![],
+            vec![
+                RecordedInput::MouseButton { button: MouseButton::Right, state: Released },
+                RecordedInput::Key { key: KeyCode::KeyW, state: Released },
+                RecordedInput::Key { key: KeyCode::KeyQ, state: Pressed },
+                RecordedInput::Key { key: KeyCode::Space, state: Pressed },
+            ],
+        ]
+    }
+
+    /// Snapshot of everything observable after a frame
+    type FrameState = (Vec4, f32, f32, f32, f32, bool, (f32, f32), f32);
+
+    fn snapshot(camera: &RecordingCamera, controller: &mut CameraController) -> FrameState {
+        (
+            camera.position,
+            camera.yaw,
+            camera.pitch,
+            camera.w_angle,
+            camera.xw_angle,
+            controller.consume_jump(),
+            controller.get_movement_input(),
+            controller.get_w_input(),
+        )
+    }
+
+    #[test]
+    fn test_replay_reproduces_controller_state() {
+        let dt = 1.0 / 60.0;
+
+        // Live run, recording as we go
+        let mut recorder = InputRecorder::new();
+        let mut controller = CameraController::new();
+        let mut camera = RecordingCamera::default();
+        let mut live = Vec::new();
+        for frame in script() {
+            for input in frame {
+                recorder.record(input);
+                input.apply(&mut controller);
+            }
+            controller.update(&mut camera, dt, true);
+            live.push(snapshot(&camera, &mut controller));
+            recorder.end_frame();
+        }
+        let recording = recorder.finish();
+        assert_eq!(recording.frame_count, 5);
+
+        // Replay through a serialized round trip
+        let text = recording.to_ron_string().unwrap();
+        let mut replayer = InputReplayer::new(InputRecording::from_ron_str(&text).unwrap());
+        let mut controller = CameraController::new();
+        let mut camera = RecordingCamera::default();
+        let mut replayed = Vec::new();
+        while !replayer.is_finished() {
+            replayer.apply_frame(&mut controller);
+            controller.update(&mut camera, dt, true);
+            replayed.push(snapshot(&camera, &mut controller));
+        }
+
+        assert_eq!(replayed, live);
+    }
+
+    #[test]
+    fn test_recorder_stamps_frames() {
+        let mut recorder = InputRecorder::new();
+        recorder.record_key(KeyCode::KeyA, ElementState::Pressed);
+        recorder.end_frame();
+        recorder.end_frame();
+        recorder.record_mouse_motion(1.0, 2.0);
+
+        let events = &recorder.recording().events;
+        assert_eq!(events[0].frame, 0);
+        assert_eq!(events[1].frame, 2);
+        assert!(events[1].time >= events[0].time);
+    }
+
+    #[test]
+    fn test_replayer_groups_events_by_frame() {
+        let mut recorder = InputRecorder::new();
+        recorder.record_mouse_motion(1.0, 0.0);
+        recorder.record_mouse_motion(2.0, 0.0);
+        recorder.end_frame();
+        recorder.end_frame();
+        recorder.record_mouse_motion(3.0, 0.0);
+        recorder.end_frame();
+
+        let mut replayer = InputReplayer::new(recorder.finish());
+        assert_eq!(replayer.next_frame().len(), 2);
+        assert!(replayer.next_frame().is_empty());
+        assert_eq!(replayer.next_frame().len(), 1);
+        assert!(replayer.is_finished());
+    }
+
+    #[test]
+    fn test_load_invalid_recording() {
+        assert!(InputRecording::from_ron_str("not a recording").is_err());
+    }
+}
@@ -0,0 +1,120 @@
+//! Canonical float formatting for scene serialization
+//!
+//! Floats are rounded to a fixed number of decimal places (and `-0.0` is
+//! folded into `0.0`) before they reach the serializer. The rounding is done
+//! in `f64`, which is exact IEEE arithmetic on every platform, and the
+//! serializer then writes the shortest round-trippable text for the rounded
+//! value. Saving the same scene anywhere yields byte-identical output.
+//!
+//! The submodules are `serialize_with` helpers for the float fields of
+//! serializable types.
+
+use crate::Vec4;
+use serde::{Serialize, Serializer};
+
+/// Number of decimal places kept when serializing floats
+pub const SCENE_FLOAT_DECIMALS: i32 = 6;
+
+/// Round a float to [`SCENE_FLOAT_DECIMALS`] places and normalize `-0.0`
+///
+/// Non-finite values are passed through unchanged.
+pub fn canonical_f32(value: f32) -> f32 {
+    if !value.is_finite() {
+        return value;
+    }
+    let scale = 10f64.powi(SCENE_FLOAT_DECIMALS);
+    let rounded = ((value as f64) * scale).round() / scale;
+    let rounded = rounded as f32;
+    if rounded == 0.0 { 0.0 } else { rounded }
+}
+
+/// Canonicalize every component of a Vec4
+pub(crate) fn canonical_vec4(v: Vec4) -> Vec4 {
+    Vec4::new(canonical_f32(v.x), canonical_f32(v.y), canonical_f32(v.z), canonical_f32(v.w))
+}
+
+/// `serialize_with` helper for `f32` fields
+pub mod scalar {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &f32, serializer: S) -> Result<S::Ok, S::Error> {
+        canonical_f32(*value).serialize(serializer)
+    }
+}
+
+/// `serialize_with` helper for `Option<f32>` fields
+pub mod option_scalar {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Option<f32>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.map(canonical_f32).serialize(serializer)
+    }
+}
+
+/// `serialize_with` helper for `[f32; 4]` fields
+pub mod array4 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &[f32; 4], serializer: S) -> Result<S::Ok, S::Error> {
+        value.map(canonical_f32).serialize(serializer)
+    }
+}
+
+/// `serialize_with` helper for `Option<[f32; 4]>` fields
+pub mod option_array4 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Option<[f32; 4]>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.map(|arr| arr.map(canonical_f32)).serialize(serializer)
+    }
+}
+
+/// `serialize_with` helper for `Vec4` fields
+pub mod vec4 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Vec4, serializer: S) -> Result<S::Ok, S::Error> {
+        canonical_vec4(*value).serialize(serializer)
+    }
+}
+
+/// `serialize_with` helper for `Option<Vec4>` fields
+pub mod option_vec4 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Option<Vec4>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.map(canonical_vec4).serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_rounds_to_fixed_places() {
+        assert_eq!(canonical_f32(0.123_456_78), 0.123_457);
+        assert_eq!(canonical_f32(1.0), 1.0);
+        assert_eq!(canonical_f32(-2.5), -2.5);
+    }
+
+    #[test]
+    fn test_canonical_normalizes_negative_zero() {
+        assert!(canonical_f32(-0.0).is_sign_positive());
+        assert!(canonical_f32(-0.000_000_1).is_sign_positive());
+    }
+
+    #[test]
+    fn test_canonical_passes_non_finite() {
+        assert!(canonical_f32(f32::NAN).is_nan());
+        assert_eq!(canonical_f32(f32::INFINITY), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_canonical_is_idempotent() {
+        for v in [0.1f32, 1.0 / 3.0, -7.654_321, 1_234.567_9, 1e-7] {
+            let once = canonical_f32(v);
+            assert_eq!(canonical_f32(once), once);
+        }
+    }
+}
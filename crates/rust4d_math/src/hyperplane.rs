@@ -197,6 +197,21 @@ impl ConvexShape4D for Hyperplane4D {
     fn tetrahedra(&self) -> &[Tetrahedron] {
         &self.tetrahedra
     }
+
+    // Each grid cell is an independent box pillar with tesseract topology.
+    // Note: the inherent `Hyperplane4D::cell_count` counts grid cells instead.
+
+    fn edge_count(&self) -> usize {
+        32 * self.grid_size * self.grid_size
+    }
+
+    fn face_count(&self) -> usize {
+        24 * self.grid_size * self.grid_size
+    }
+
+    fn cell_count(&self) -> usize {
+        8 * self.grid_size * self.grid_size
+    }
 }
 
 #[cfg(test)]
@@ -204,6 +219,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::len_zero)]
     fn test_hyperplane_creation() {
         let plane = Hyperplane4D::new(4.0, 4, 2.0, 0.01);
 
@@ -212,6 +228,17 @@ mod tests {
         assert!(plane.tetrahedra().len() > 0);
     }
 
+    #[test]
+    fn test_hyperplane_topology_counts() {
+        let plane = Hyperplane4D::new(4.0, 3, 2.0, 0.1);
+        // 3x3 grid = 9 pillars, each with tesseract topology
+        assert_eq!(plane.vertex_count(), 9 * 16);
+        assert_eq!(plane.edge_count(), 9 * 32);
+        assert_eq!(plane.face_count(), 9 * 24);
+        assert_eq!(ConvexShape4D::cell_count(&plane), 9 * 8);
+        assert_eq!(plane.euler_characteristic(), 0);
+    }
+
     #[test]
     fn test_hyperplane_vertex_positions_local_space() {
         let plane = Hyperplane4D::new(4.0, 2, 2.0, 0.1);
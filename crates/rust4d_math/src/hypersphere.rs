@@ -0,0 +1,208 @@
+//! Hypersphere (glome) geometry
+//!
+//! The 3-sphere is approximated by recursively subdividing the boundary of a
+//! 16-cell: each tetrahedron is split into 8 through its edge midpoints and
+//! the new vertices are pushed out onto the sphere. Like the 16-cell, the
+//! tetrahedra describe the boundary, so slicing at `|w| < radius` gives the
+//! surface of a 3D sphere of radius `sqrt(radius² - w²)`.
+
+use std::collections::HashMap;
+
+use crate::{Vec4, shape::{ConvexShape4D, Tetrahedron}};
+
+/// A 4D hypersphere (glome) centered at the origin - pure geometry without colors
+#[derive(Clone)]
+pub struct Hypersphere4D {
+    /// Distance from the center to every vertex
+    radius: f32,
+    /// Number of times the 16-cell boundary was subdivided
+    subdivisions: u32,
+    /// Vertices, all on the sphere
+    vertices: Vec<Vec4>,
+    /// Boundary tetrahedra
+    tetrahedra: Vec<Tetrahedron>,
+    /// Tetrahedra of the coarser subdivision levels, coarsest first
+    ///
+    /// Subdivision only appends vertices, so these index the same vertex list.
+    coarse_tetrahedra: Vec<Vec<Tetrahedron>>,
+}
+
+impl Hypersphere4D {
+    /// Subdivision level used by [`new`](Self::new)
+    pub const DEFAULT_SUBDIVISIONS: u32 = 2;
+
+    /// Create a hypersphere with the default subdivision level
+    pub fn new(radius: f32) -> Self {
+        Self::with_subdivisions(radius, Self::DEFAULT_SUBDIVISIONS)
+    }
+
+    /// Create a hypersphere with a given subdivision level
+    ///
+    /// Level 0 is the 16-cell itself (8 vertices, 16 tetrahedra); each level
+    /// multiplies the tetrahedron count by 8.
+    pub fn with_subdivisions(radius: f32, subdivisions: u32) -> Self {
+        let mut vertices: Vec<Vec4> = (0..8)
+            .map(|i| {
+                let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+                let mut axes = [0.0; 4];
+                axes[i / 2] = sign;
+                Vec4::new(axes[0], axes[1], axes[2], axes[3])
+            })
+            .collect();
+
+        // 16-cell boundary: one vertex from each axis pair, for every sign choice
+        let mut tetrahedra: Vec<Tetrahedron> = (0..16usize)
+            .map(|signs| Tetrahedron::new(std::array::from_fn(|axis| axis * 2 + ((signs >> axis) & 1))))
+            .collect();
+
+        let mut coarse_tetrahedra = Vec::with_capacity(subdivisions as usize);
+        for _ in 0..subdivisions {
+            let finer = subdivide(&mut vertices, &tetrahedra);
+            coarse_tetrahedra.push(std::mem::replace(&mut tetrahedra, finer));
+        }
+
+        for v in &mut vertices {
+            *v = v.normalized() * radius;
+        }
+
+        Self {
+            radius,
+            subdivisions,
+            vertices,
+            tetrahedra,
+            coarse_tetrahedra,
+        }
+    }
+
+    /// Get the radius
+    #[inline]
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Get the subdivision level
+    #[inline]
+    pub fn subdivisions(&self) -> u32 {
+        self.subdivisions
+    }
+}
+
+/// Split every tetrahedron into 8 through shared, sphere-projected edge midpoints
+fn subdivide(vertices: &mut Vec<Vec4>, tetrahedra: &[Tetrahedron]) -> Vec<Tetrahedron> {
+    let mut midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut midpoint = |a: usize, b: usize, vertices: &mut Vec<Vec4>| {
+        let key = (a.min(b), a.max(b));
+        *midpoints.entry(key).or_insert_with(|| {
+            vertices.push(((vertices[a] + vertices[b]) * 0.5).normalized());
+            vertices.len() - 1
+        })
+    };
+
+    let mut result = Vec::with_capacity(tetrahedra.len() * 8);
+    for tet in tetrahedra {
+        let [a, b, c, d] = tet.indices;
+        let ab = midpoint(a, b, vertices);
+        let ac = midpoint(a, c, vertices);
+        let ad = midpoint(a, d, vertices);
+        let bc = midpoint(b, c, vertices);
+        let bd = midpoint(b, d, vertices);
+        let cd = midpoint(c, d, vertices);
+
+        // Corner tetrahedra
+        result.push(Tetrahedron::new([a, ab, ac, ad]));
+        result.push(Tetrahedron::new([b, ab, bc, bd]));
+        result.push(Tetrahedron::new([c, ac, bc, cd]));
+        result.push(Tetrahedron::new([d, ad, bd, cd]));
+
+        // Inner octahedron, split around the ab-cd diagonal
+        result.push(Tetrahedron::new([ab, cd, ac, ad]));
+        result.push(Tetrahedron::new([ab, cd, ad, bd]));
+        result.push(Tetrahedron::new([ab, cd, bd, bc]));
+        result.push(Tetrahedron::new([ab, cd, bc, ac]));
+    }
+    result
+}
+
+impl ConvexShape4D for Hypersphere4D {
+    fn vertices(&self) -> &[Vec4] {
+        &self.vertices
+    }
+
+    fn tetrahedra(&self) -> &[Tetrahedron] {
+        &self.tetrahedra
+    }
+
+    /// Each level undoes one subdivision, down to the 16-cell
+    fn tetrahedra_at_lod(&self, level: u32) -> &[Tetrahedron] {
+        if level == 0 {
+            return &self.tetrahedra;
+        }
+        let target = self.subdivisions.saturating_sub(level) as usize;
+        self.coarse_tetrahedra.get(target).unwrap_or(&self.tetrahedra)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hypersphere_level_zero_is_sixteen_cell() {
+        let sphere = Hypersphere4D::with_subdivisions(1.0, 0);
+        assert_eq!(sphere.vertex_count(), 8);
+        assert_eq!(sphere.tetrahedron_count(), 16);
+    }
+
+    #[test]
+    fn test_hypersphere_counts_scale_with_subdivisions() {
+        // Each level adds one vertex per edge and splits every tetrahedron into 8
+        let mut previous = Hypersphere4D::with_subdivisions(1.0, 0);
+        for level in 1..=3 {
+            let sphere = Hypersphere4D::with_subdivisions(1.0, level);
+            assert_eq!(sphere.subdivisions(), level);
+            assert_eq!(sphere.vertex_count(), previous.vertex_count() + previous.edge_count());
+            assert_eq!(sphere.tetrahedron_count(), previous.tetrahedron_count() * 8);
+            previous = sphere;
+        }
+    }
+
+    #[test]
+    fn test_hypersphere_is_closed_three_sphere() {
+        let sphere = Hypersphere4D::with_subdivisions(1.0, 2);
+        assert_eq!(sphere.euler_characteristic(), 0);
+
+        // Every triangle is shared by exactly two tetrahedra
+        let mut faces: HashMap<[usize; 3], usize> = HashMap::new();
+        for tet in sphere.tetrahedra() {
+            let [a, b, c, d] = tet.canonical();
+            for face in [[a, b, c], [a, b, d], [a, c, d], [b, c, d]] {
+                *faces.entry(face).or_default() += 1;
+            }
+        }
+        assert!(faces.values().all(|&count| count == 2));
+    }
+
+    #[test]
+    fn test_hypersphere_lod_undoes_subdivisions() {
+        let sphere = Hypersphere4D::with_subdivisions(1.0, 2);
+        assert_eq!(sphere.tetrahedra_at_lod(0), sphere.tetrahedra());
+        assert_eq!(sphere.tetrahedra_at_lod(1).len(), 16 * 8);
+        assert_eq!(sphere.tetrahedra_at_lod(2).len(), 16);
+        assert_eq!(sphere.tetrahedra_at_lod(9).len(), 16);
+
+        // Coarse levels only use vertices from earlier subdivisions
+        let coarse = Hypersphere4D::with_subdivisions(1.0, 1);
+        assert_eq!(sphere.tetrahedra_at_lod(1), coarse.tetrahedra());
+        assert!(sphere.tetrahedra_at_lod(1).iter().flat_map(|t| t.indices).all(|i| i < coarse.vertex_count()));
+    }
+
+    #[test]
+    fn test_hypersphere_vertices_on_radius() {
+        let sphere = Hypersphere4D::new(2.5);
+        assert_eq!(sphere.radius(), 2.5);
+        for v in sphere.vertices() {
+            assert!(v.length() <= 2.5 + 1e-5);
+            assert!((v.length() - 2.5).abs() < 1e-5);
+        }
+    }
+}
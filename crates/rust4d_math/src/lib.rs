@@ -6,7 +6,9 @@
 //!
 //! - [`Vec4`] - 4D vector with x, y, z, w components
 //! - [`Rotor4`] - 4D rotation using geometric algebra
+//! - [`Bivector4`] - Oriented plane, used for angular velocity
 //! - [`Mat4`] - 4x4 matrix for transformations
+//! - [`Transform4D`] - Position, rotation, scale and mirroring of an object
 //!
 //! ## Shape Types
 //!
@@ -14,17 +16,34 @@
 //! - [`Tetrahedron`] - A 3-simplex defined by vertex indices
 //! - [`Tesseract4D`] - A 4D hypercube
 //! - [`Hyperplane4D`] - A floor/ground plane in 4D
+//! - [`SixteenCell4D`] - A 4D cross-polytope (16-cell)
+//! - [`Hypersphere4D`] - A tessellated 4D sphere (glome)
+//!
+//! ## Spatial Queries
+//!
+//! - [`SpatialGrid4D`] - Uniform 4D grid for radius, box and ray queries
 
 mod vec4;
 mod rotor4;
+mod bivector4;
 pub mod mat4;
 pub mod shape;
 pub mod tesseract;
 pub mod hyperplane;
+pub mod sixteen_cell;
+pub mod hypersphere;
+pub mod spatial_grid;
+mod transform;
+pub mod float_format;
 
 pub use vec4::Vec4;
 pub use rotor4::{Rotor4, RotationPlane};
+pub use bivector4::Bivector4;
 pub use mat4::Mat4;
 pub use shape::{ConvexShape4D, Tetrahedron};
 pub use tesseract::Tesseract4D;
 pub use hyperplane::Hyperplane4D;
+pub use sixteen_cell::SixteenCell4D;
+pub use hypersphere::Hypersphere4D;
+pub use spatial_grid::{GridCell, SpatialGrid4D, MAX_ITEM_CELLS};
+pub use transform::Transform4D;
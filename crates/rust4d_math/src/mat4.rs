@@ -150,6 +150,82 @@ pub fn transpose(m: Mat4) -> Mat4 {
     ]
 }
 
+/// Pivots smaller than this make a matrix count as singular
+const SINGULAR_EPSILON: f32 = 1e-6;
+
+/// Compute the determinant of a matrix
+///
+/// Uses Gaussian elimination with partial pivoting. The determinant of a
+/// matrix equals that of its transpose, so the storage order doesn't matter.
+#[allow(clippy::needless_range_loop)]
+pub fn determinant(m: Mat4) -> f32 {
+    let mut a = m;
+    let mut det = 1.0;
+
+    for col in 0..4 {
+        let pivot = (col..4)
+            .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+            .unwrap();
+        if a[pivot][col] == 0.0 {
+            return 0.0;
+        }
+        if pivot != col {
+            a.swap(pivot, col);
+            det = -det;
+        }
+        det *= a[col][col];
+
+        for row in col + 1..4 {
+            let factor = a[row][col] / a[col][col];
+            for k in col..4 {
+                a[row][k] -= factor * a[col][k];
+            }
+        }
+    }
+
+    det
+}
+
+/// Invert a matrix
+///
+/// Uses Gauss-Jordan elimination with partial pivoting. Returns `None` if
+/// the matrix is singular or too close to singular to invert reliably.
+#[allow(clippy::needless_range_loop)]
+pub fn inverse(m: Mat4) -> Option<Mat4> {
+    let mut a = m;
+    let mut inv = IDENTITY;
+
+    for col in 0..4 {
+        let pivot = (col..4)
+            .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+            .unwrap();
+        if a[pivot][col].abs() < SINGULAR_EPSILON {
+            return None;
+        }
+        a.swap(pivot, col);
+        inv.swap(pivot, col);
+
+        let scale = 1.0 / a[col][col];
+        for k in 0..4 {
+            a[col][k] *= scale;
+            inv[col][k] *= scale;
+        }
+
+        for row in 0..4 {
+            if row == col {
+                continue;
+            }
+            let factor = a[row][col];
+            for k in 0..4 {
+                a[row][k] -= factor * a[col][k];
+                inv[row][k] -= factor * inv[col][k];
+            }
+        }
+    }
+
+    Some(inv)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -317,4 +393,68 @@ mod tests {
         assert!(vec_approx_eq(col0, Vec4::new(1.0, 0.0, 0.0, 0.0)),
             "Column 0 should be X axis for YZ rotation");
     }
+
+    #[test]
+    fn test_determinant() {
+        assert!(approx_eq(determinant(IDENTITY), 1.0));
+        assert!(approx_eq(determinant(plane_rotation(0.7, 0, 3)), 1.0));
+
+        let scaled = [
+            [2.0, 0.0, 0.0, 0.0],
+            [0.0, 3.0, 0.0, 0.0],
+            [0.0, 0.0, 4.0, 0.0],
+            [0.0, 0.0, 0.0, 0.5],
+        ];
+        assert!(approx_eq(determinant(scaled), 12.0));
+
+        // Swapping two columns flips the sign
+        let mut swapped = IDENTITY;
+        swapped.swap(0, 1);
+        assert!(approx_eq(determinant(swapped), -1.0));
+    }
+
+    #[test]
+    fn test_inverse_round_trips_to_identity() {
+        let matrices = [
+            plane_rotation(0.3, 0, 2),
+            mul(plane_rotation(1.1, 1, 3), skip_y(plane_rotation(-0.4, 0, 1))),
+            [
+                [4.0, 7.0, 2.0, 3.0],
+                [0.0, 5.0, 0.0, 1.0],
+                [1.0, 0.0, 3.0, 0.0],
+                [2.0, 1.0, 0.0, 6.0],
+            ],
+            [
+                [0.0, 2.0, 1.0, -1.0],
+                [3.0, 0.0, 0.5, 2.0],
+                [1.0, 1.0, 0.0, 4.0],
+                [-2.0, 0.0, 3.0, 1.0],
+            ],
+        ];
+
+        for m in matrices {
+            let inv = inverse(m).expect("matrix should be invertible");
+            assert!(mat_approx_eq(mul(m, inv), IDENTITY), "m * inv = {:?}", mul(m, inv));
+            assert!(mat_approx_eq(mul(inv, m), IDENTITY), "inv * m = {:?}", mul(inv, m));
+        }
+    }
+
+    #[test]
+    fn test_inverse_of_rotation_is_transpose() {
+        let m = mul(plane_rotation(0.8, 2, 3), plane_rotation(-1.2, 0, 1));
+        assert!(mat_approx_eq(inverse(m).unwrap(), transpose(m)));
+    }
+
+    #[test]
+    fn test_inverse_singular_is_none() {
+        let singular = [
+            [1.0, 2.0, 3.0, 4.0],
+            [2.0, 4.0, 6.0, 8.0],
+            [0.0, 1.0, 0.0, 1.0],
+            [1.0, 0.0, 1.0, 0.0],
+        ];
+        assert!(approx_eq(determinant(singular), 0.0));
+        assert!(inverse(singular).is_none());
+        assert!(inverse([[0.0; 4]; 4]).is_none());
+    }
 }
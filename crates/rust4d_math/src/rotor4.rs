@@ -176,6 +176,27 @@ impl Rotor4 {
         }
     }
 
+    /// Create a double rotation: `plane_a` by `angle_a`, then `plane_b` by `angle_b`
+    ///
+    /// When the planes are orthogonal (e.g. XY and ZW) the two rotations
+    /// commute and happen simultaneously - a genuinely 4D rotation with no
+    /// fixed axis that single-plane rotors can't express.
+    pub fn from_double_rotation(
+        plane_a: RotationPlane,
+        angle_a: f32,
+        plane_b: RotationPlane,
+        angle_b: f32,
+    ) -> Self {
+        Self::from_plane_angle(plane_b, angle_b).compose(&Self::from_plane_angle(plane_a, angle_a))
+    }
+
+    /// Create an isoclinic rotation: XY and ZW both rotated by `angle`
+    ///
+    /// Every vector is rotated by the same angle, so no direction is left fixed.
+    pub fn isoclinic(angle: f32) -> Self {
+        Self::from_double_rotation(RotationPlane::XY, angle, RotationPlane::ZW, angle)
+    }
+
     /// Compute the squared magnitude of the rotor
     #[inline]
     pub fn magnitude_squared(&self) -> f32 {
@@ -275,25 +296,25 @@ impl Rotor4 {
         let new_x = rv_e1 * s
             + rv_e2 * b12 + rv_e3 * b13 + rv_e4 * b14  // from e_i * e_1i
             + rv_e123 * b23 + rv_e124 * b24 + rv_e134 * b34  // from e_1jk * e_jk
-            - rv_e234 * p;  // from e_234 * e_1234 = -e_1
+            + rv_e234 * p;  // from e_234 * e_1234 = e_1
 
         // e2 coefficient:
         let new_y = rv_e2 * s
             - rv_e1 * b12 + rv_e3 * b23 + rv_e4 * b24  // from e_i * e_2i
             - rv_e123 * b13 - rv_e124 * b14 + rv_e234 * b34  // from e_2jk * e_jk
-            + rv_e134 * p;  // from e_134 * e_1234 = e_2
+            - rv_e134 * p;  // from e_134 * e_1234 = -e_2
 
         // e3 coefficient:
         let new_z = rv_e3 * s
             - rv_e1 * b13 - rv_e2 * b23 + rv_e4 * b34  // from e_i * e_3i
             + rv_e123 * b12 - rv_e134 * b14 - rv_e234 * b24  // from e_3jk * e_jk
-            - rv_e124 * p;  // from e_124 * e_1234 = -e_3
+            + rv_e124 * p;  // from e_124 * e_1234 = e_3
 
         // e4 coefficient:
         let new_w = rv_e4 * s
             - rv_e1 * b14 - rv_e2 * b24 - rv_e3 * b34  // from e_i * e_4i
             + rv_e124 * b12 + rv_e134 * b13 + rv_e234 * b23  // from e_4jk * e_jk
-            + rv_e123 * p;  // from e_123 * e_1234 = e_4
+            - rv_e123 * p;  // from e_123 * e_1234 = -e_4
 
         Vec4::new(new_x, new_y, new_z, new_w)
     }
@@ -371,6 +392,65 @@ impl Rotor4 {
         }
     }
 
+    /// Dot product of the 8 rotor components
+    ///
+    /// For unit rotors this is the cosine of the angle between them on the
+    /// unit sphere (half the rotation angle between the orientations).
+    #[inline]
+    pub fn dot(&self, other: &Self) -> f32 {
+        self.s * other.s
+            + self.b_xy * other.b_xy
+            + self.b_xz * other.b_xz
+            + self.b_xw * other.b_xw
+            + self.b_yz * other.b_yz
+            + self.b_yw * other.b_yw
+            + self.b_zw * other.b_zw
+            + self.p * other.p
+    }
+
+    /// Spherical interpolation towards `other` along the shortest arc
+    ///
+    /// A 4D rotor splits into left- and right-isoclinic halves, each a unit
+    /// quaternion; each half is slerped separately so the result stays a valid
+    /// rotation even between double rotations. `R` and `-R` describe the same
+    /// rotation, so `other` is negated when that is closer, and nearly identical
+    /// halves fall back to a normalized lerp to avoid dividing by a vanishing
+    /// sine. The result is always unit length.
+    pub fn slerp(&self, other: &Self, t: f32) -> Self {
+        let sign = if self.dot(other) < 0.0 { -1.0 } else { 1.0 };
+        let (a_left, a_right) = self.isoclinic_halves();
+        let (b_left, b_right) = other.isoclinic_halves();
+
+        Self::from_isoclinic_halves(
+            slerp_unit4(a_left, b_left.map(|c| c * sign), t),
+            slerp_unit4(a_right, b_right.map(|c| c * sign), t),
+        )
+    }
+
+    /// Split into the two isoclinic halves (projections onto `(1 ± e1234) / 2`)
+    ///
+    /// Each half is a quaternion-like 4-vector; for a unit rotor both have unit length.
+    fn isoclinic_halves(&self) -> ([f32; 4], [f32; 4]) {
+        (
+            [self.s + self.p, self.b_xy - self.b_zw, self.b_xz + self.b_yw, self.b_xw - self.b_yz],
+            [self.s - self.p, self.b_xy + self.b_zw, self.b_xz - self.b_yw, self.b_xw + self.b_yz],
+        )
+    }
+
+    /// Inverse of [`isoclinic_halves`](Self::isoclinic_halves)
+    fn from_isoclinic_halves(left: [f32; 4], right: [f32; 4]) -> Self {
+        Self {
+            s: (left[0] + right[0]) * 0.5,
+            p: (left[0] - right[0]) * 0.5,
+            b_xy: (left[1] + right[1]) * 0.5,
+            b_zw: (right[1] - left[1]) * 0.5,
+            b_xz: (left[2] + right[2]) * 0.5,
+            b_yw: (left[2] - right[2]) * 0.5,
+            b_xw: (left[3] + right[3]) * 0.5,
+            b_yz: (right[3] - left[3]) * 0.5,
+        }
+    }
+
     /// Convert rotor to a 4x4 rotation matrix
     /// Useful for sending to GPU
     pub fn to_matrix(&self) -> [[f32; 4]; 4] {
@@ -388,6 +468,52 @@ impl Rotor4 {
             [w_col.x, w_col.y, w_col.z, w_col.w],
         ]
     }
+
+    /// Recover the Euler angles passed to [`from_euler_xyz`](Self::from_euler_xyz)
+    ///
+    /// Returns `(x, y, z)` in radians, assuming the rotor is a 3D rotation
+    /// (no planes involving W). The Y angle is in [-π/2, π/2]. Near gimbal
+    /// lock (Y at ±90°) X and Z rotate about the same axis, so Z is set to 0
+    /// and X carries the whole remaining rotation.
+    pub fn to_euler_xyz(&self) -> (f32, f32, f32) {
+        const GIMBAL_LOCK_THRESHOLD: f32 = 0.9999;
+
+        // Column-major: m[col][row]
+        let m = self.to_matrix();
+        let sin_y = (-m[0][2]).clamp(-1.0, 1.0);
+        let y = sin_y.asin();
+
+        if sin_y.abs() < GIMBAL_LOCK_THRESHOLD {
+            let x = m[1][2].atan2(m[2][2]);
+            let z = m[0][1].atan2(m[0][0]);
+            (x, y, z)
+        } else {
+            let x = (-m[2][1]).atan2(m[1][1]);
+            (x, y, 0.0)
+        }
+    }
+}
+
+/// Slerp between two 4-vectors on the unit 3-sphere (inputs are normalized)
+fn slerp_unit4(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+    const NLERP_THRESHOLD: f32 = 0.9995;
+
+    let normalize = |v: [f32; 4]| {
+        let len = v.iter().map(|c| c * c).sum::<f32>().sqrt();
+        if len > 0.0 { v.map(|c| c / len) } else { [1.0, 0.0, 0.0, 0.0] }
+    };
+    let a = normalize(a);
+    let b = normalize(b);
+    let cos_theta: f32 = (0..4).map(|i| a[i] * b[i]).sum::<f32>().clamp(-1.0, 1.0);
+
+    let (wa, wb) = if cos_theta > NLERP_THRESHOLD {
+        (1.0 - t, t)
+    } else {
+        let theta = cos_theta.acos();
+        let sin_theta = theta.sin();
+        (((1.0 - t) * theta).sin() / sin_theta, (t * theta).sin() / sin_theta)
+    };
+    normalize(std::array::from_fn(|i| a[i] * wa + b[i] * wb))
 }
 
 #[cfg(test)]
@@ -603,6 +729,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_range_loop)]
     fn test_rotation_matrix_is_orthogonal() {
         // An orthogonal matrix has M * M^T = I
         let r = Rotor4::from_plane_angle(RotationPlane::XZ, 0.7)
@@ -710,4 +837,178 @@ mod tests {
         // The first column of the matrix tells us where X goes
         println!("Matrix column 0: ({}, {}, {}, {})", m[0][0], m[1][0], m[2][0], m[3][0]);
     }
+
+    // ==================== Pseudoscalar Sign Tests ====================
+
+    #[test]
+    fn test_rotate_matches_sequential_orthogonal_rotations() {
+        // Each pair of orthogonal planes composes into a rotor with a
+        // pseudoscalar part, exercising every e_1234 term of the sandwich
+        let v = Vec4::new(1.0, -2.0, 0.5, 3.0);
+        for (a, b) in [
+            (RotationPlane::XY, RotationPlane::ZW),
+            (RotationPlane::XZ, RotationPlane::YW),
+            (RotationPlane::XW, RotationPlane::YZ),
+        ] {
+            let ra = Rotor4::from_plane_angle(a, 0.9);
+            let rb = Rotor4::from_plane_angle(b, -1.7);
+            let composed = rb.compose(&ra);
+            assert!(composed.p.abs() > 0.1);
+            assert!(vec_approx_eq(composed.rotate(v), rb.rotate(ra.rotate(v))));
+        }
+    }
+
+    #[test]
+    fn test_double_rotation_preserves_length() {
+        // Orthogonal planes compose into a rotor with a pseudoscalar part
+        let r = Rotor4::from_plane_angle(RotationPlane::XY, 0.7)
+            .compose(&Rotor4::from_plane_angle(RotationPlane::ZW, -2.1));
+        assert!(r.p.abs() > 0.1);
+
+        let v = Vec4::new(1.0, -2.0, 0.5, 3.0);
+        assert!(approx_eq(r.rotate(v).length(), v.length()));
+        assert!(vec_approx_eq(r.rotate(Vec4::X), Vec4::new(0.7f32.cos(), 0.7f32.sin(), 0.0, 0.0)));
+        assert!(vec_approx_eq(r.rotate(Vec4::Z), Vec4::new(0.0, 0.0, (-2.1f32).cos(), (-2.1f32).sin())));
+    }
+
+    // ==================== Double Rotation Tests ====================
+
+    #[test]
+    fn test_isoclinic_rotates_both_planes() {
+        let theta = 0.6f32;
+        let r = Rotor4::isoclinic(theta);
+
+        assert!(vec_approx_eq(r.rotate(Vec4::X), Vec4::X * theta.cos() + Vec4::Y * theta.sin()));
+        assert!(vec_approx_eq(r.rotate(Vec4::Z), Vec4::Z * theta.cos() + Vec4::W * theta.sin()));
+    }
+
+    #[test]
+    fn test_isoclinic_moves_every_vector_by_same_angle() {
+        let theta = 1.1f32;
+        let r = Rotor4::isoclinic(theta);
+
+        for v in [Vec4::new(1.0, 2.0, -3.0, 0.5), Vec4::new(0.0, 1.0, 1.0, 0.0), Vec4::W] {
+            let rotated = r.rotate(v);
+            assert!(approx_eq(rotated.length(), v.length()));
+            assert!(approx_eq(rotated.dot(v) / v.length_squared(), theta.cos()));
+        }
+        assert!(approx_eq(r.magnitude(), 1.0));
+    }
+
+    #[test]
+    fn test_double_rotation_orthogonal_planes_commute() {
+        let ab = Rotor4::from_double_rotation(RotationPlane::XZ, 0.4, RotationPlane::YW, -1.3);
+        let ba = Rotor4::from_double_rotation(RotationPlane::YW, -1.3, RotationPlane::XZ, 0.4);
+        let v = Vec4::new(1.0, -2.0, 0.5, 3.0);
+
+        assert!(vec_approx_eq(ab.rotate(v), ba.rotate(v)));
+        assert!(approx_eq(ab.rotate(v).length(), v.length()));
+    }
+
+    #[test]
+    fn test_double_rotation_applies_a_first() {
+        let r = Rotor4::from_double_rotation(RotationPlane::XY, PI / 2.0, RotationPlane::YZ, PI / 2.0);
+        // X -> Y (XY), then Y -> Z (YZ)
+        assert!(vec_approx_eq(r.rotate(Vec4::X), Vec4::Z));
+    }
+
+    // ==================== Slerp Tests ====================
+
+    fn rotor_approx_eq(a: &Rotor4, b: &Rotor4) -> bool {
+        [Vec4::X, Vec4::Y, Vec4::Z, Vec4::W]
+            .iter()
+            .all(|&v| vec_approx_eq(a.rotate(v), b.rotate(v)))
+    }
+
+    #[test]
+    fn test_slerp_endpoints() {
+        let a = Rotor4::from_plane_angle(RotationPlane::XY, 0.3);
+        let b = Rotor4::from_plane_angle(RotationPlane::ZW, 1.2);
+
+        assert!(rotor_approx_eq(&a.slerp(&b, 0.0), &a));
+        assert!(rotor_approx_eq(&a.slerp(&b, 1.0), &b));
+    }
+
+    #[test]
+    fn test_slerp_same_plane_halves_angle() {
+        let a = Rotor4::IDENTITY;
+        let b = Rotor4::from_plane_angle(RotationPlane::XY, PI / 2.0);
+        let expected = Rotor4::from_plane_angle(RotationPlane::XY, PI / 4.0);
+
+        assert!(rotor_approx_eq(&a.slerp(&b, 0.5), &expected));
+    }
+
+    #[test]
+    fn test_slerp_unit_and_length_preserving() {
+        let a = Rotor4::from_plane_angle(RotationPlane::XY, 0.7);
+        let b = Rotor4::from_plane_angle(RotationPlane::ZW, -2.1);
+        let v = Vec4::new(1.0, -2.0, 0.5, 3.0);
+
+        for i in 0..=10 {
+            let r = a.slerp(&b, i as f32 / 10.0);
+            assert!(approx_eq(r.magnitude(), 1.0));
+            assert!(approx_eq(r.rotate(v).length(), v.length()));
+        }
+    }
+
+    #[test]
+    fn test_slerp_takes_shortest_arc() {
+        let a = Rotor4::from_plane_angle(RotationPlane::XY, 0.2);
+        let b = Rotor4::from_plane_angle(RotationPlane::XY, 0.6);
+        // Same rotation as b, opposite sign in the double cover
+        let b_neg = Rotor4 { s: -b.s, b_xy: -b.b_xy, ..b };
+        let expected = Rotor4::from_plane_angle(RotationPlane::XY, 0.4);
+
+        assert!(rotor_approx_eq(&a.slerp(&b_neg, 0.5), &expected));
+    }
+
+    #[test]
+    fn test_slerp_nearly_identical() {
+        let a = Rotor4::from_plane_angle(RotationPlane::ZW, 1.0);
+        let b = Rotor4::from_plane_angle(RotationPlane::ZW, 1.0 + 1e-5);
+        let r = a.slerp(&b, 0.5);
+
+        assert!(r.s.is_finite() && approx_eq(r.magnitude(), 1.0));
+        assert!(rotor_approx_eq(&r, &a));
+    }
+
+    // ==================== Euler Extraction Tests ====================
+
+    fn matrices_approx_eq(a: &Rotor4, b: &Rotor4) -> bool {
+        let (ma, mb) = (a.to_matrix(), b.to_matrix());
+        (0..4).all(|i| (0..4).all(|j| (ma[i][j] - mb[i][j]).abs() < 1e-3))
+    }
+
+    #[test]
+    fn test_to_euler_xyz_recovers_angles() {
+        let (x, y, z) = Rotor4::from_euler_xyz(0.3, -0.5, 1.1).to_euler_xyz();
+        assert!(approx_eq(x, 0.3) && approx_eq(y, -0.5) && approx_eq(z, 1.1), "{:?}", (x, y, z));
+    }
+
+    #[test]
+    fn test_to_euler_xyz_round_trips_rotation() {
+        let angles = [
+            (0.0, 0.0, 0.0),
+            (1.2, 0.4, -2.5),
+            (-2.9, 1.3, 0.7),
+            (3.0, -1.0, 3.0),
+            (0.1, 2.0, -0.3), // Y beyond 90°: different angles, same rotation
+        ];
+        for (x, y, z) in angles {
+            let r = Rotor4::from_euler_xyz(x, y, z);
+            let (ex, ey, ez) = r.to_euler_xyz();
+            assert!(matrices_approx_eq(&r, &Rotor4::from_euler_xyz(ex, ey, ez)), "{:?}", (x, y, z));
+        }
+    }
+
+    #[test]
+    fn test_to_euler_xyz_gimbal_lock() {
+        for y in [PI / 2.0, -PI / 2.0] {
+            let r = Rotor4::from_euler_xyz(0.4, y, 0.3);
+            let (ex, ey, ez) = r.to_euler_xyz();
+            assert!(ex.is_finite() && ey.is_finite());
+            assert_eq!(ez, 0.0);
+            assert!(matrices_approx_eq(&r, &Rotor4::from_euler_xyz(ex, ey, ez)));
+        }
+    }
 }
@@ -3,6 +3,8 @@
 //! This module provides the core shape abstraction for 4D objects.
 //! Shapes are pure geometric data - no colors, materials, or rendering info.
 
+use std::collections::HashSet;
+
 use crate::Vec4;
 
 /// A tetrahedron (3-simplex) defined by vertex indices
@@ -54,6 +56,16 @@ pub trait ConvexShape4D: Send + Sync {
     /// Get the tetrahedra decomposition of this shape
     fn tetrahedra(&self) -> &[Tetrahedron];
 
+    /// Get a coarser tetrahedra decomposition for drawing the shape far away
+    ///
+    /// Level 0 is [`tetrahedra`](Self::tetrahedra); each higher level may
+    /// return fewer tetrahedra, indexing the same vertices. Shapes without a
+    /// coarser decomposition return the full one at every level.
+    fn tetrahedra_at_lod(&self, level: u32) -> &[Tetrahedron] {
+        let _ = level;
+        self.tetrahedra()
+    }
+
     /// Get the number of vertices
     #[inline]
     fn vertex_count(&self) -> usize {
@@ -65,6 +77,106 @@ pub trait ConvexShape4D: Send + Sync {
     fn tetrahedron_count(&self) -> usize {
         self.tetrahedra().len()
     }
+
+    /// Intersect a ray with the solid shape, in the shape's local space
+    ///
+    /// Returns the ray parameter `t` where `local_origin + local_dir * t`
+    /// enters the shape, or `None` if the ray misses. `local_dir` need not be
+    /// normalized; `t` is measured in multiples of it. A ray starting inside
+    /// the shape hits at 0.
+    ///
+    /// The shape is treated as the intersection of the half-spaces bounding
+    /// it: every tetrahedron with all vertices on one side of its hyperplane
+    /// contributes one, and the ray is clipped against each (slab method).
+    /// Tetrahedra cutting through the interior are skipped, so both boundary
+    /// and solid decompositions work.
+    fn ray_intersect(&self, local_origin: Vec4, local_dir: Vec4) -> Option<f32> {
+        let vertices = self.vertices();
+        let scale = vertices.iter().fold(0.0f32, |m, v| m.max(v.length()));
+        let epsilon = 1e-4 * scale.max(1e-6);
+
+        let mut t_enter = 0.0f32;
+        let mut t_exit = f32::INFINITY;
+        let mut has_face = false;
+        for tet in self.tetrahedra() {
+            let [a, b, c, d] = tet.indices.map(|i| vertices[i]);
+            let normal = Vec4::cross3(b - a, c - a, d - a).normalized();
+            if normal == Vec4::ZERO {
+                continue;
+            }
+
+            // Orient the normal outward, skipping hyperplanes through the interior
+            let offsets = vertices.iter().map(|v| normal.dot(*v - a));
+            let (below, above) = offsets.fold((false, false), |(lo, hi), o| (lo || o < -epsilon, hi || o > epsilon));
+            let normal = match (below, above) {
+                (true, false) => normal,
+                (false, true) => -normal,
+                _ => continue,
+            };
+            has_face = true;
+
+            // Positive outside the face, decreasing as the ray enters
+            let distance = normal.dot(local_origin - a);
+            let rate = normal.dot(local_dir);
+            if rate.abs() < 1e-12 {
+                if distance > 0.0 {
+                    return None;
+                }
+                continue;
+            }
+            let t = -distance / rate;
+            if rate < 0.0 {
+                t_enter = t_enter.max(t);
+            } else {
+                t_exit = t_exit.min(t);
+            }
+            if t_enter > t_exit {
+                return None;
+            }
+        }
+        has_face.then_some(t_enter)
+    }
+
+    // --- Topology ---
+    //
+    // The defaults treat the tetrahedra as the polytope's cells, which is
+    // correct for simplicial polytopes described by their boundary (e.g. the
+    // 16-cell). Shapes whose tetrahedra are a solid triangulation must override.
+
+    /// Get the number of edges (1D elements) of the polytope
+    fn edge_count(&self) -> usize {
+        let mut edges = HashSet::new();
+        for tet in self.tetrahedra() {
+            let [a, b, c, d] = tet.canonical();
+            for edge in [[a, b], [a, c], [a, d], [b, c], [b, d], [c, d]] {
+                edges.insert(edge);
+            }
+        }
+        edges.len()
+    }
+
+    /// Get the number of 2D faces of the polytope
+    fn face_count(&self) -> usize {
+        let mut faces = HashSet::new();
+        for tet in self.tetrahedra() {
+            let [a, b, c, d] = tet.canonical();
+            for face in [[a, b, c], [a, b, d], [a, c, d], [b, c, d]] {
+                faces.insert(face);
+            }
+        }
+        faces.len()
+    }
+
+    /// Get the number of 3D cells of the polytope
+    fn cell_count(&self) -> usize {
+        self.tetrahedron_count()
+    }
+
+    /// Euler characteristic `V - E + F - C` (0 for any convex 4-polytope)
+    fn euler_characteristic(&self) -> i64 {
+        self.vertex_count() as i64 - self.edge_count() as i64
+            + self.face_count() as i64 - self.cell_count() as i64
+    }
 }
 
 #[cfg(test)]
@@ -88,4 +200,42 @@ mod tests {
         let tet = Tetrahedron::new_canonical([3, 1, 0, 2]);
         assert_eq!(tet.indices, [0, 1, 2, 3]);
     }
+
+    /// The boundary of a 4-simplex: 5 vertices, every 4 of them a cell
+    struct FiveCell {
+        vertices: Vec<Vec4>,
+        tetrahedra: Vec<Tetrahedron>,
+    }
+
+    impl ConvexShape4D for FiveCell {
+        fn vertices(&self) -> &[Vec4] {
+            &self.vertices
+        }
+
+        fn tetrahedra(&self) -> &[Tetrahedron] {
+            &self.tetrahedra
+        }
+    }
+
+    #[test]
+    fn test_default_topology_counts_five_cell() {
+        let shape = FiveCell {
+            vertices: vec![Vec4::ZERO; 5],
+            tetrahedra: (0..5)
+                .map(|omit| {
+                    let mut indices = [0; 4];
+                    for (slot, v) in (0..5).filter(|&v| v != omit).enumerate() {
+                        indices[slot] = v;
+                    }
+                    Tetrahedron::new(indices)
+                })
+                .collect(),
+        };
+
+        assert_eq!(shape.vertex_count(), 5);
+        assert_eq!(shape.edge_count(), 10);
+        assert_eq!(shape.face_count(), 10);
+        assert_eq!(shape.cell_count(), 5);
+        assert_eq!(shape.euler_characteristic(), 0);
+    }
 }
@@ -0,0 +1,113 @@
+//! 16-cell (4D cross-polytope) geometry
+//!
+//! A 16-cell has 8 vertices (±r on each axis), 24 edges, 32 faces
+//! (triangles), and 16 cells (tetrahedra).
+//!
+//! The 16-cell is simplicial, so its boundary cells are already tetrahedra
+//! and are used directly for slicing.
+
+use crate::{Vec4, shape::{ConvexShape4D, Tetrahedron}};
+
+/// A 16-cell (4D cross-polytope / hyperoctahedron) - pure geometry without colors
+#[derive(Clone)]
+pub struct SixteenCell4D {
+    /// Distance from the center to each vertex
+    radius: f32,
+    /// The 8 vertices of the 16-cell
+    vertices: [Vec4; 8],
+    /// The 16 tetrahedral cells
+    tetrahedra: Vec<Tetrahedron>,
+}
+
+impl SixteenCell4D {
+    /// Create a new 16-cell centered at origin
+    ///
+    /// # Arguments
+    /// * `radius` - Distance from the center to each vertex
+    pub fn new(radius: f32) -> Self {
+        let r = radius;
+
+        // Vertex 2*axis is +r on that axis, 2*axis + 1 is -r
+        let vertices = [
+            Vec4::new( r, 0.0, 0.0, 0.0), // 0: +X
+            Vec4::new(-r, 0.0, 0.0, 0.0), // 1: -X
+            Vec4::new(0.0,  r, 0.0, 0.0), // 2: +Y
+            Vec4::new(0.0, -r, 0.0, 0.0), // 3: -Y
+            Vec4::new(0.0, 0.0,  r, 0.0), // 4: +Z
+            Vec4::new(0.0, 0.0, -r, 0.0), // 5: -Z
+            Vec4::new(0.0, 0.0, 0.0,  r), // 6: +W
+            Vec4::new(0.0, 0.0, 0.0, -r), // 7: -W
+        ];
+
+        Self {
+            radius,
+            vertices,
+            tetrahedra: Self::compute_tetrahedra(),
+        }
+    }
+
+    /// Get the center-to-vertex radius
+    #[inline]
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Compute the 16 cells: one vertex from each axis pair, for every sign choice
+    fn compute_tetrahedra() -> Vec<Tetrahedron> {
+        (0..16usize)
+            .map(|signs| {
+                let mut indices = [0usize; 4];
+                for (axis, index) in indices.iter_mut().enumerate() {
+                    *index = axis * 2 + ((signs >> axis) & 1);
+                }
+                Tetrahedron::new(indices)
+            })
+            .collect()
+    }
+}
+
+impl ConvexShape4D for SixteenCell4D {
+    fn vertices(&self) -> &[Vec4] {
+        &self.vertices
+    }
+
+    fn tetrahedra(&self) -> &[Tetrahedron] {
+        &self.tetrahedra
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sixteen_cell_topology_counts() {
+        let cell = SixteenCell4D::new(1.0);
+        assert_eq!(cell.vertex_count(), 8);
+        assert_eq!(cell.edge_count(), 24);
+        assert_eq!(cell.face_count(), 32);
+        assert_eq!(cell.cell_count(), 16);
+        assert_eq!(cell.euler_characteristic(), 0);
+    }
+
+    #[test]
+    fn test_sixteen_cell_vertices_on_radius() {
+        let cell = SixteenCell4D::new(2.5);
+        assert_eq!(cell.radius(), 2.5);
+        for v in cell.vertices() {
+            assert!((v.length() - 2.5).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_sixteen_cell_tetrahedra_skip_opposite_vertices() {
+        // A cell never contains both vertices of an axis pair
+        let cell = SixteenCell4D::new(1.0);
+        for tet in cell.tetrahedra() {
+            for axis in 0..4 {
+                let count = tet.indices.iter().filter(|&&i| i / 2 == axis).count();
+                assert_eq!(count, 1);
+            }
+        }
+    }
+}
@@ -0,0 +1,427 @@
+//! Uniform 4D spatial grid
+//!
+//! [`SpatialGrid4D`] buckets items (points or axis-aligned boxes) into cubic
+//! 4D cells so proximity queries only look at nearby cells. It knows nothing
+//! about bodies or entities; callers key items with any small `Copy` handle
+//! (an entity key, a body index) and build broadphase, radius and ray queries
+//! on top of it.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::Vec4;
+
+/// Integer coordinates of a grid cell
+pub type GridCell = [i32; 4];
+
+/// Items spanning more cells than this are kept in a list tested by every query
+pub const MAX_ITEM_CELLS: u64 = 4096;
+
+/// Bounds stored for an inserted item
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ItemBounds {
+    min: Vec4,
+    max: Vec4,
+}
+
+/// A uniform grid over 4D space
+///
+/// Items larger than a cell are registered in every cell their box overlaps,
+/// up to [`MAX_ITEM_CELLS`]; bigger ones skip the cells and every query tests
+/// them directly. Queries covering more than that many cells test every item
+/// instead of walking the cells. Queries return each matching item once, in
+/// no particular order.
+#[derive(Clone, Debug)]
+pub struct SpatialGrid4D<T> {
+    /// Edge length of a cell
+    cell_size: f32,
+    /// Items in each occupied cell
+    cells: HashMap<GridCell, Vec<T>>,
+    /// Bounds of every inserted item
+    items: HashMap<T, ItemBounds>,
+    /// Items spanning too many cells to register in each
+    oversized: Vec<T>,
+}
+
+impl<T: Copy + Eq + Hash> SpatialGrid4D<T> {
+    /// Create an empty grid with the given cell edge length
+    ///
+    /// # Panics
+    /// Panics if `cell_size` is not positive.
+    pub fn new(cell_size: f32) -> Self {
+        assert!(cell_size > 0.0, "cell_size must be positive");
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            items: HashMap::new(),
+            oversized: Vec::new(),
+        }
+    }
+
+    /// Get the cell edge length
+    #[inline]
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Get the cell containing a point
+    pub fn cell_of(&self, point: Vec4) -> GridCell {
+        [
+            (point.x / self.cell_size).floor() as i32,
+            (point.y / self.cell_size).floor() as i32,
+            (point.z / self.cell_size).floor() as i32,
+            (point.w / self.cell_size).floor() as i32,
+        ]
+    }
+
+    /// Insert an item at a point, replacing any previous entry for it
+    pub fn insert_point(&mut self, item: T, point: Vec4) {
+        self.insert_aabb(item, point, point);
+    }
+
+    /// Insert an item with an axis-aligned bounding box, replacing any previous entry
+    pub fn insert_aabb(&mut self, item: T, min: Vec4, max: Vec4) {
+        self.remove(item);
+        let (lo, hi) = (self.cell_of(min), self.cell_of(max));
+        if cells_spanned(lo, hi) > MAX_ITEM_CELLS {
+            self.oversized.push(item);
+        } else {
+            for_each_cell(lo, hi, |cell| self.cells.entry(cell).or_default().push(item));
+        }
+        self.items.insert(item, ItemBounds { min, max });
+    }
+
+    /// Remove an item, returning true if it was present
+    pub fn remove(&mut self, item: T) -> bool {
+        let Some(bounds) = self.items.remove(&item) else {
+            return false;
+        };
+        let (lo, hi) = (self.cell_of(bounds.min), self.cell_of(bounds.max));
+        if cells_spanned(lo, hi) > MAX_ITEM_CELLS {
+            self.oversized.retain(|&other| other != item);
+            return true;
+        }
+        for_each_cell(lo, hi, |cell| {
+            if let Some(bucket) = self.cells.get_mut(&cell) {
+                bucket.retain(|&other| other != item);
+                if bucket.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        });
+        true
+    }
+
+    /// Check if an item is in the grid
+    pub fn contains(&self, item: T) -> bool {
+        self.items.contains_key(&item)
+    }
+
+    /// Get the number of items in the grid
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check if the grid has no items
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Remove all items
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.items.clear();
+        self.oversized.clear();
+    }
+
+    /// Get the items registered in a single cell
+    ///
+    /// Items spanning more than [`MAX_ITEM_CELLS`] are not registered in any.
+    pub fn items_in_cell(&self, cell: GridCell) -> &[T] {
+        self.cells.get(&cell).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Get the items in a cell and its 80 surrounding cells
+    pub fn query_neighbors(&self, cell: GridCell) -> Vec<T> {
+        let lo = cell.map(|c| c - 1);
+        let hi = cell.map(|c| c + 1);
+        self.collect_cells(lo, hi, |_| true)
+    }
+
+    /// Get the items whose bounds overlap the box `[min, max]`
+    pub fn query_aabb(&self, min: Vec4, max: Vec4) -> Vec<T> {
+        let (lo, hi) = (self.cell_of(min), self.cell_of(max));
+        self.collect_cells(lo, hi, |b| {
+            b.min.x <= max.x && b.max.x >= min.x
+                && b.min.y <= max.y && b.max.y >= min.y
+                && b.min.z <= max.z && b.max.z >= min.z
+                && b.min.w <= max.w && b.max.w >= min.w
+        })
+    }
+
+    /// Get the items whose bounds are within `radius` of `center`
+    ///
+    /// For point items this is the exact 4D distance to the point.
+    pub fn query_radius(&self, center: Vec4, radius: f32) -> Vec<T> {
+        let r = Vec4::new(radius, radius, radius, radius);
+        let (lo, hi) = (self.cell_of(center - r), self.cell_of(center + r));
+        let radius_sq = radius * radius;
+        self.collect_cells(lo, hi, |b| {
+            let closest = Vec4::new(
+                center.x.clamp(b.min.x, b.max.x),
+                center.y.clamp(b.min.y, b.max.y),
+                center.z.clamp(b.min.z, b.max.z),
+                center.w.clamp(b.min.w, b.max.w),
+            );
+            (closest - center).length_squared() <= radius_sq
+        })
+    }
+
+    /// Walk the cells a ray passes through, in order along the ray
+    ///
+    /// Starts at the cell containing `origin` and stops once the ray has
+    /// travelled `max_distance` (in units of `direction`'s length).
+    pub fn ray_cells(&self, origin: Vec4, direction: Vec4, max_distance: f32) -> Vec<GridCell> {
+        let origin_c = [origin.x, origin.y, origin.z, origin.w];
+        let dir_c = [direction.x, direction.y, direction.z, direction.w];
+
+        let mut cell = self.cell_of(origin);
+        let mut step = [0i32; 4];
+        let mut t_max = [f32::INFINITY; 4];
+        let mut t_delta = [f32::INFINITY; 4];
+
+        for axis in 0..4 {
+            let d = dir_c[axis];
+            if d > 0.0 {
+                step[axis] = 1;
+                let boundary = (cell[axis] + 1) as f32 * self.cell_size;
+                t_max[axis] = (boundary - origin_c[axis]) / d;
+                t_delta[axis] = self.cell_size / d;
+            } else if d < 0.0 {
+                step[axis] = -1;
+                let boundary = cell[axis] as f32 * self.cell_size;
+                t_max[axis] = (boundary - origin_c[axis]) / d;
+                t_delta[axis] = -self.cell_size / d;
+            }
+        }
+
+        let mut visited = vec![cell];
+        loop {
+            // Advance along the axis whose next boundary is closest
+            let mut axis = 0;
+            for a in 1..4 {
+                if t_max[a] < t_max[axis] {
+                    axis = a;
+                }
+            }
+            if !t_max[axis].is_finite() || t_max[axis] > max_distance {
+                break;
+            }
+            cell[axis] += step[axis];
+            t_max[axis] += t_delta[axis];
+            visited.push(cell);
+        }
+        visited
+    }
+
+    /// Collect items in the cell range that pass the bounds filter, once each
+    ///
+    /// Oversized items, and every item when the range itself is too large to
+    /// walk, are checked against the range directly.
+    fn collect_cells(
+        &self,
+        lo: GridCell,
+        hi: GridCell,
+        filter: impl Fn(&ItemBounds) -> bool,
+    ) -> Vec<T> {
+        let in_range = |bounds: &ItemBounds| {
+            let (min, max) = (self.cell_of(bounds.min), self.cell_of(bounds.max));
+            (0..4).all(|i| min[i] <= hi[i] && max[i] >= lo[i]) && filter(bounds)
+        };
+        if cells_spanned(lo, hi) > MAX_ITEM_CELLS {
+            return self.items.iter().filter(|(_, b)| in_range(b)).map(|(&item, _)| item).collect();
+        }
+
+        let mut seen = HashSet::new();
+        let mut result = Vec::new();
+        for_each_cell(lo, hi, |cell| {
+            for &item in self.items_in_cell(cell) {
+                if seen.insert(item) && filter(&self.items[&item]) {
+                    result.push(item);
+                }
+            }
+        });
+        result.extend(self.oversized.iter().copied().filter(|item| in_range(&self.items[item])));
+        result
+    }
+}
+
+/// Number of cells in the inclusive range `lo..=hi`, saturating
+fn cells_spanned(lo: GridCell, hi: GridCell) -> u64 {
+    (0..4).fold(1u64, |cells, i| {
+        cells.saturating_mul((hi[i] as i64 - lo[i] as i64 + 1).max(0) as u64)
+    })
+}
+
+/// Call `f` for every cell in the inclusive range `lo..=hi`
+fn for_each_cell(lo: GridCell, hi: GridCell, mut f: impl FnMut(GridCell)) {
+    for x in lo[0]..=hi[0] {
+        for y in lo[1]..=hi[1] {
+            for z in lo[2]..=hi[2] {
+                for w in lo[3]..=hi[3] {
+                    f([x, y, z, w]);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut items: Vec<u32>) -> Vec<u32> {
+        items.sort();
+        items
+    }
+
+    #[test]
+    fn test_cell_of() {
+        let grid: SpatialGrid4D<u32> = SpatialGrid4D::new(2.0);
+        assert_eq!(grid.cell_of(Vec4::new(0.5, 1.9, 2.0, -0.1)), [0, 0, 1, -1]);
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut grid = SpatialGrid4D::new(1.0);
+        grid.insert_point(1u32, Vec4::new(0.5, 0.5, 0.5, 0.5));
+        assert!(grid.contains(1));
+        assert_eq!(grid.items_in_cell([0, 0, 0, 0]), &[1]);
+
+        assert!(grid.remove(1));
+        assert!(!grid.remove(1));
+        assert!(grid.is_empty());
+        assert!(grid.items_in_cell([0, 0, 0, 0]).is_empty());
+    }
+
+    #[test]
+    fn test_reinsert_moves_item() {
+        let mut grid = SpatialGrid4D::new(1.0);
+        grid.insert_point(1u32, Vec4::new(0.5, 0.5, 0.5, 0.5));
+        grid.insert_point(1u32, Vec4::new(5.5, 0.5, 0.5, 0.5));
+
+        assert_eq!(grid.len(), 1);
+        assert!(grid.items_in_cell([0, 0, 0, 0]).is_empty());
+        assert_eq!(grid.items_in_cell([5, 0, 0, 0]), &[1]);
+    }
+
+    #[test]
+    fn test_query_radius_exact() {
+        let mut grid = SpatialGrid4D::new(1.0);
+        let points = [
+            (0u32, Vec4::new(0.0, 0.0, 0.0, 0.0)),
+            (1, Vec4::new(1.5, 0.0, 0.0, 0.0)),
+            (2, Vec4::new(0.0, 0.0, 0.0, 1.9)),
+            (3, Vec4::new(1.2, 1.2, 0.0, 0.0)), // length ~1.70
+            (4, Vec4::new(0.0, -3.0, 0.0, 0.0)),
+            (5, Vec4::new(1.0, 1.0, 1.0, 1.0)), // length 2.0
+        ];
+        for (id, p) in points {
+            grid.insert_point(id, p);
+        }
+
+        let center = Vec4::ZERO;
+        let radius = 1.8;
+        let expected: Vec<u32> = points
+            .iter()
+            .filter(|(_, p)| (*p - center).length() <= radius)
+            .map(|(id, _)| *id)
+            .collect();
+
+        assert_eq!(sorted(grid.query_radius(center, radius)), expected);
+        assert_eq!(expected, vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn test_query_aabb() {
+        let mut grid = SpatialGrid4D::new(1.0);
+        grid.insert_aabb(1u32, Vec4::new(-0.5, -0.5, -0.5, -0.5), Vec4::new(0.5, 0.5, 0.5, 0.5));
+        grid.insert_point(2u32, Vec4::new(3.0, 0.0, 0.0, 0.0));
+
+        let hits = grid.query_aabb(Vec4::new(0.4, 0.4, 0.4, 0.4), Vec4::new(1.0, 1.0, 1.0, 1.0));
+        assert_eq!(hits, vec![1]);
+        assert!(grid.query_aabb(Vec4::new(1.0, 1.0, 1.0, 1.0), Vec4::new(2.0, 2.0, 2.0, 2.0)).is_empty());
+    }
+
+    #[test]
+    fn test_large_item_reported_once() {
+        let mut grid = SpatialGrid4D::new(1.0);
+        grid.insert_aabb(7u32, Vec4::new(-2.0, -2.0, -2.0, -2.0), Vec4::new(2.0, 2.0, 2.0, 2.0));
+        assert_eq!(grid.query_radius(Vec4::ZERO, 3.0), vec![7]);
+    }
+
+    #[test]
+    fn test_query_neighbors() {
+        let mut grid = SpatialGrid4D::new(1.0);
+        grid.insert_point(1u32, Vec4::new(0.5, 0.5, 0.5, 0.5));
+        grid.insert_point(2u32, Vec4::new(1.5, -0.5, 0.5, 1.5));
+        grid.insert_point(3u32, Vec4::new(2.5, 0.5, 0.5, 0.5));
+
+        assert_eq!(sorted(grid.query_neighbors([0, 0, 0, 0])), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_oversized_item_skips_cells_but_is_queried() {
+        let mut grid = SpatialGrid4D::new(1.0);
+        let huge = Vec4::new(1e6, 1e6, 1e6, 1e6);
+        grid.insert_aabb(1u32, -huge, huge);
+        grid.insert_point(2u32, Vec4::new(0.5, 0.5, 0.5, 0.5));
+
+        assert_eq!(grid.items_in_cell([0, 0, 0, 0]), &[2]);
+        assert_eq!(sorted(grid.query_radius(Vec4::ZERO, 1.0)), vec![1, 2]);
+        assert_eq!(sorted(grid.query_neighbors([0, 0, 0, 0])), vec![1, 2]);
+        assert_eq!(grid.query_aabb(Vec4::new(5.0, 5.0, 5.0, 5.0), Vec4::new(6.0, 6.0, 6.0, 6.0)), vec![1]);
+        // Entirely outside it, the big box is not reported
+        assert!(grid.query_radius(Vec4::new(3e6, 0.0, 0.0, 0.0), 1.0).is_empty());
+
+        assert!(grid.remove(1));
+        assert_eq!(grid.query_radius(Vec4::ZERO, 1.0), vec![2]);
+    }
+
+    #[test]
+    fn test_huge_query_tests_items_directly() {
+        let mut grid = SpatialGrid4D::new(1.0);
+        grid.insert_point(1u32, Vec4::new(0.5, 0.5, 0.5, 0.5));
+        grid.insert_point(2u32, Vec4::new(1e5, 0.0, 0.0, 0.0));
+
+        let huge = Vec4::new(1e7, 1e7, 1e7, 1e7);
+        assert_eq!(sorted(grid.query_aabb(-huge, huge)), vec![1, 2]);
+        assert_eq!(grid.query_radius(Vec4::ZERO, 1e4), vec![1]);
+    }
+
+    #[test]
+    fn test_ray_walk_visits_cells_in_order() {
+        let grid: SpatialGrid4D<u32> = SpatialGrid4D::new(1.0);
+        let cells = grid.ray_cells(Vec4::new(0.5, 0.5, 0.5, 0.5), Vec4::new(1.0, 0.0, 0.0, 0.0), 3.0);
+        assert_eq!(cells, vec![[0, 0, 0, 0], [1, 0, 0, 0], [2, 0, 0, 0], [3, 0, 0, 0]]);
+    }
+
+    #[test]
+    fn test_ray_walk_diagonal_is_connected() {
+        let grid: SpatialGrid4D<u32> = SpatialGrid4D::new(1.0);
+        let dir = Vec4::new(1.0, 0.0, 0.0, -0.5).normalized();
+        let cells = grid.ray_cells(Vec4::new(0.1, 0.5, 0.5, 0.9), dir, 4.0);
+
+        assert_eq!(cells[0], [0, 0, 0, 0]);
+        // Consecutive cells differ by one step along exactly one axis
+        for pair in cells.windows(2) {
+            let diff: i32 = (0..4).map(|a| (pair[1][a] - pair[0][a]).abs()).sum();
+            assert_eq!(diff, 1);
+        }
+        // Monotone along the ray direction
+        assert!(cells.windows(2).all(|p| p[1][0] >= p[0][0] && p[1][3] <= p[0][3]));
+        assert_eq!(*cells.last().unwrap(), [3, 0, 0, -1]);
+    }
+}
@@ -17,6 +17,8 @@ pub struct Tesseract4D {
     vertices: [Vec4; 16],
     /// Tetrahedra decomposition
     tetrahedra: Vec<Tetrahedron>,
+    /// The tetrahedra lying on the 8 cubic cells (LOD level 1 and up)
+    boundary_tetrahedra: Vec<Tetrahedron>,
 }
 
 impl Tesseract4D {
@@ -51,10 +53,23 @@ impl Tesseract4D {
         // Compute tetrahedra decomposition using Kuhn triangulation
         let tetrahedra = Self::compute_tetrahedra();
 
+        // A tetrahedron lies on a cubic cell when all its vertices agree on one bit
+        let boundary_tetrahedra = tetrahedra
+            .iter()
+            .filter(|tet| {
+                let [a, b, c, d] = tet.indices;
+                let all = a & b & c & d;
+                let any = a | b | c | d;
+                all != 0 || any != 0b1111
+            })
+            .copied()
+            .collect();
+
         Self {
             half_size: h,
             vertices,
             tetrahedra,
+            boundary_tetrahedra,
         }
     }
 
@@ -147,6 +162,29 @@ impl ConvexShape4D for Tesseract4D {
     fn tetrahedra(&self) -> &[Tetrahedron] {
         &self.tetrahedra
     }
+
+    /// Level 1 and up drop the interior tetrahedra of the Kuhn triangulation
+    fn tetrahedra_at_lod(&self, level: u32) -> &[Tetrahedron] {
+        if level == 0 {
+            &self.tetrahedra
+        } else {
+            &self.boundary_tetrahedra
+        }
+    }
+
+    // The tetrahedra are a solid Kuhn triangulation, so report the cube topology
+
+    fn edge_count(&self) -> usize {
+        32
+    }
+
+    fn face_count(&self) -> usize {
+        24
+    }
+
+    fn cell_count(&self) -> usize {
+        8
+    }
 }
 
 #[cfg(test)]
@@ -160,6 +198,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::len_zero)]
     fn test_tesseract_tetrahedron_count() {
         let t = Tesseract4D::new(2.0);
         // Should have some reasonable number of tetrahedra
@@ -167,6 +206,26 @@ mod tests {
         assert!(t.tetrahedra().len() <= 120); // Max: 24 * 5 before deduplication
     }
 
+    #[test]
+    fn test_tesseract_lod_keeps_only_cell_tetrahedra() {
+        let t = Tesseract4D::new(2.0);
+        assert_eq!(t.tetrahedra_at_lod(0), t.tetrahedra());
+
+        let coarse = t.tetrahedra_at_lod(1);
+        assert!(coarse.len() < t.tetrahedra().len());
+        assert_eq!(t.tetrahedra_at_lod(5), coarse);
+
+        // Every remaining tetrahedron lies on a face of the tesseract
+        for tet in coarse {
+            let v = tet.indices.map(|i| t.vertices[i]);
+            let on_cell = (0..4).any(|axis| {
+                let c = [v[0].x, v[0].y, v[0].z, v[0].w][axis];
+                v.iter().all(|p| [p.x, p.y, p.z, p.w][axis] == c)
+            });
+            assert!(on_cell, "interior tetrahedron {:?}", tet.indices);
+        }
+    }
+
     #[test]
     fn test_tesseract_vertices_positions() {
         let t = Tesseract4D::new(2.0);
@@ -184,6 +243,31 @@ mod tests {
         assert_eq!(t.vertices[15].w, h);
     }
 
+    #[test]
+    fn test_tesseract_topology_counts() {
+        let t = Tesseract4D::new(2.0);
+        assert_eq!(t.vertex_count(), 16);
+        assert_eq!(t.edge_count(), 32);
+        assert_eq!(t.face_count(), 24);
+        assert_eq!(t.cell_count(), 8);
+        assert_eq!(t.euler_characteristic(), 0);
+    }
+
+    #[test]
+    fn test_tesseract_edge_count_matches_geometry() {
+        // Edges join vertices differing in exactly one coordinate (one bit)
+        let t = Tesseract4D::new(2.0);
+        let mut edges = 0;
+        for a in 0..16usize {
+            for b in (a + 1)..16usize {
+                if (a ^ b).count_ones() == 1 {
+                    edges += 1;
+                }
+            }
+        }
+        assert_eq!(t.edge_count(), edges);
+    }
+
     #[test]
     fn test_tesseract_size() {
         let t = Tesseract4D::new(4.0);
@@ -251,4 +335,22 @@ mod tests {
         assert_eq!(t1.vertices().len(), t2.vertices().len());
         assert_eq!(t1.tetrahedra().len(), t2.tetrahedra().len());
     }
+
+    #[test]
+    fn test_tesseract_ray_intersect() {
+        let t = Tesseract4D::new(2.0);
+
+        // Through the center, entering the face at x = -1
+        let hit = t.ray_intersect(Vec4::new(-5.0, 0.0, 0.0, 0.0), Vec4::X).unwrap();
+        assert!((hit - 4.0).abs() < 1e-5);
+
+        // Along a diagonal, with an unnormalized direction
+        let hit = t.ray_intersect(Vec4::new(-3.0, -3.0, 0.5, 0.5), Vec4::new(2.0, 2.0, 0.0, 0.0)).unwrap();
+        assert!((hit - 1.0).abs() < 1e-5);
+
+        // Passing beside it in W, pointing away, and starting inside
+        assert!(t.ray_intersect(Vec4::new(-5.0, 0.0, 0.0, 1.5), Vec4::X).is_none());
+        assert!(t.ray_intersect(Vec4::new(-5.0, 0.0, 0.0, 0.0), -Vec4::X).is_none());
+        assert_eq!(t.ray_intersect(Vec4::ZERO, Vec4::Y), Some(0.0));
+    }
 }
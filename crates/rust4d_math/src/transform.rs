@@ -0,0 +1,505 @@
+//! 4D Transform (position, rotation, scale)
+//!
+//! A Transform4D represents the position, rotation, and scale of an entity in 4D space,
+//! optionally mirrored (see [`Transform4D::reflected`]).
+
+use crate::{Vec4, Rotor4, RotationPlane};
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+
+/// Custom serialization module for Rotor4
+///
+/// Since Rotor4 doesn't implement Serialize/Deserialize,
+/// we serialize it as an array of 8 floats: [s, b_xy, b_xz, b_xw, b_yz, b_yw, b_zw, p]
+mod rotor4_serde {
+    use super::*;
+    use crate::float_format::canonical_f32;
+
+    pub fn serialize<S: Serializer>(rotor: &Rotor4, serializer: S) -> Result<S::Ok, S::Error> {
+        let arr = [
+            rotor.s, rotor.b_xy, rotor.b_xz, rotor.b_xw,
+            rotor.b_yz, rotor.b_yw, rotor.b_zw, rotor.p
+        ];
+        arr.map(canonical_f32).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Rotor4, D::Error> {
+        let arr: [f32; 8] = <[f32; 8]>::deserialize(deserializer)?;
+        Ok(Rotor4 {
+            s: arr[0], b_xy: arr[1], b_xz: arr[2], b_xw: arr[3],
+            b_yz: arr[4], b_yw: arr[5], b_zw: arr[6], p: arr[7],
+        })
+    }
+}
+
+/// A 4D transform with position, rotation, and uniform scale
+///
+/// A transform can also be mirrored: points are then reflected across a
+/// local hyperplane before being scaled and rotated.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Transform4D {
+    /// Position in 4D space
+    #[serde(serialize_with = "crate::float_format::vec4::serialize")]
+    pub position: Vec4,
+    /// Rotation as a 4D rotor (serialized as 8-element array)
+    #[serde(with = "rotor4_serde")]
+    pub rotation: Rotor4,
+    /// Uniform scale factor
+    #[serde(serialize_with = "crate::float_format::scalar::serialize")]
+    pub scale: f32,
+    /// Unit normal of the local hyperplane (through the origin) points are
+    /// reflected across first, or `None` if the transform is not mirrored
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "crate::float_format::option_vec4::serialize"
+    )]
+    pub mirror: Option<Vec4>,
+}
+
+impl Default for Transform4D {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Transform4D {
+    /// Create an identity transform (no translation, rotation, or scale change)
+    pub fn identity() -> Self {
+        Self {
+            position: Vec4::ZERO,
+            rotation: Rotor4::IDENTITY,
+            scale: 1.0,
+            mirror: None,
+        }
+    }
+
+    /// Create a transform with just a position
+    pub fn from_position(position: Vec4) -> Self {
+        Self {
+            position,
+            rotation: Rotor4::IDENTITY,
+            scale: 1.0,
+            mirror: None,
+        }
+    }
+
+    /// Create a transform with position and rotation
+    pub fn from_position_rotation(position: Vec4, rotation: Rotor4) -> Self {
+        Self {
+            position,
+            rotation,
+            scale: 1.0,
+            mirror: None,
+        }
+    }
+
+    /// Get the rotation matrix as a 4x4 array
+    ///
+    /// This only includes rotation, not position or scale.
+    #[inline]
+    pub fn rotation_matrix(&self) -> [[f32; 4]; 4] {
+        self.rotation.to_matrix()
+    }
+
+    /// Check if this transform mirrors, reversing handedness
+    #[inline]
+    pub fn is_mirrored(&self) -> bool {
+        self.mirror.is_some()
+    }
+
+    /// Transform a point from local space to world space
+    ///
+    /// Applies the mirror (if any), then scale, then rotation, then translation.
+    pub fn transform_point(&self, p: Vec4) -> Vec4 {
+        // Scale
+        let scaled = self.apply_mirror(p) * self.scale;
+        // Rotate
+        let rotated = self.rotation.rotate(scaled);
+        // Translate
+        rotated + self.position
+    }
+
+    /// Transform a direction from local space to world space
+    ///
+    /// Applies the mirror, scale and rotation, but not translation.
+    pub fn transform_direction(&self, d: Vec4) -> Vec4 {
+        let scaled = self.apply_mirror(d) * self.scale;
+        self.rotation.rotate(scaled)
+    }
+
+    /// Reflect a local-space vector across the mirror hyperplane, if any
+    fn apply_mirror(&self, v: Vec4) -> Vec4 {
+        self.mirror.map_or(v, |normal| reflect(v, normal))
+    }
+
+    /// Compute the inverse transform
+    ///
+    /// The inverse transform undoes this transform:
+    /// `transform.inverse().transform_point(transform.transform_point(p)) == p`
+    pub fn inverse(&self) -> Self {
+        let inv_scale = if self.scale.abs() > 1e-10 {
+            1.0 / self.scale
+        } else {
+            1.0
+        };
+        let inv_rotation = self.rotation.reverse();
+        let inv_position = self.apply_mirror(inv_rotation.rotate(-self.position)) * inv_scale;
+
+        // Mirroring before the inverse rotation is the same as mirroring
+        // after it across the rotated plane
+        Self {
+            position: inv_position,
+            rotation: inv_rotation,
+            scale: inv_scale,
+            mirror: self.mirror.map(|normal| self.rotation.rotate(normal)),
+        }
+    }
+
+    /// Compose two transforms: result = self * other
+    ///
+    /// The composed transform applies `other` first, then `self`.
+    pub fn compose(&self, other: &Self) -> Self {
+        // Our mirror, moved before `other`'s rotation
+        let outer = self.mirror.map(|normal| other.rotation.reverse().rotate(normal));
+        let (mirror, rotation) = combine_mirrors(other.mirror, outer);
+        Self {
+            position: self.transform_point(other.position),
+            rotation: self.rotation.compose(&other.rotation).compose(&rotation),
+            scale: self.scale * other.scale,
+            mirror,
+        }
+    }
+
+    /// Copy of this transform mirrored across the world hyperplane through the origin with normal `plane_normal`
+    ///
+    /// Every transformed point is reflected across the plane. To mirror
+    /// across a plane through some other point `c`, reflect a transform
+    /// translated by `-c` and translate the result back.
+    ///
+    /// A reflection reverses handedness: a tetrahedron's vertices, transformed,
+    /// come out in the opposite orientation, so its winding is reversed.
+    /// Anything that derives facing from vertex order must swap it back for
+    /// mirrored transforms (see [`is_mirrored`](Self::is_mirrored)); the
+    /// renderer does this when building geometry. Mirroring twice gives a
+    /// rotation again.
+    pub fn reflected(&self, plane_normal: Vec4) -> Self {
+        let normal = plane_normal.normalized();
+        // Reflecting after the rotation is reflecting before it across the
+        // plane rotated back into local space
+        let local = self.rotation.reverse().rotate(normal);
+        let (mirror, rotation) = combine_mirrors(self.mirror, Some(local));
+        Self {
+            position: reflect(self.position, normal),
+            rotation: self.rotation.compose(&rotation).normalize(),
+            scale: self.scale,
+            mirror,
+        }
+    }
+
+    /// Translate the transform by an offset
+    pub fn translate(&mut self, offset: Vec4) {
+        self.position += offset;
+    }
+
+    /// Rotate the transform by a rotor
+    pub fn rotate(&mut self, rotor: Rotor4) {
+        self.rotation = rotor.compose(&self.rotation).normalize();
+    }
+
+    /// Rotate the transform by `angle` radians in a single plane
+    ///
+    /// Like [`rotate`](Self::rotate), the rotation is applied after the
+    /// current one and the result is normalized, so calling this every frame
+    /// does not drift.
+    pub fn rotate_plane(&mut self, plane: RotationPlane, angle: f32) {
+        self.rotate(Rotor4::from_plane_angle(plane, angle));
+    }
+
+    /// Copy of this transform rotated by `angle` radians in a single plane
+    pub fn rotated_plane(&self, plane: RotationPlane, angle: f32) -> Self {
+        let mut rotated = *self;
+        rotated.rotate_plane(plane, angle);
+        rotated
+    }
+
+    /// Set uniform scale
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+
+    /// Copy of this transform with the position rounded to a grid
+    ///
+    /// Each axis is rounded to the nearest multiple of the matching `cell`
+    /// component; a zero component leaves that axis as it is. Rotation and
+    /// scale are kept.
+    pub fn snap_to_grid(&self, cell: Vec4) -> Self {
+        let snap = |value: f32, cell: f32| {
+            if cell == 0.0 {
+                value
+            } else {
+                (value / cell).round() * cell
+            }
+        };
+        let p = self.position;
+        Self {
+            position: Vec4::new(snap(p.x, cell.x), snap(p.y, cell.y), snap(p.z, cell.z), snap(p.w, cell.w)),
+            ..*self
+        }
+    }
+}
+
+/// Reflect `v` across the hyperplane through the origin with unit normal `normal`
+fn reflect(v: Vec4, normal: Vec4) -> Vec4 {
+    v - normal * (2.0 * v.dot(normal))
+}
+
+/// Combine mirroring across `first`, then across `second`
+///
+/// Returns the remaining mirror and a rotation to apply after it. Two
+/// reflections make a rotation in the plane of their normals by twice the
+/// angle between them.
+fn combine_mirrors(first: Option<Vec4>, second: Option<Vec4>) -> (Option<Vec4>, Rotor4) {
+    match (first, second) {
+        (Some(a), Some(b)) => {
+            let angle = a.dot(b).clamp(-1.0, 1.0).acos();
+            (None, Rotor4::from_plane_vectors(a, b, 2.0 * angle))
+        }
+        (mirror, None) | (None, mirror) => (mirror, Rotor4::IDENTITY),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    const EPSILON: f32 = 0.0001;
+
+    fn approx_eq(a: f32, b: f32) -> bool {
+        (a - b).abs() < EPSILON
+    }
+
+    fn vec_approx_eq(a: Vec4, b: Vec4) -> bool {
+        approx_eq(a.x, b.x) && approx_eq(a.y, b.y) && approx_eq(a.z, b.z) && approx_eq(a.w, b.w)
+    }
+
+    #[test]
+    fn test_identity_transform() {
+        let t = Transform4D::identity();
+        let p = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        let transformed = t.transform_point(p);
+        assert!(vec_approx_eq(p, transformed));
+    }
+
+    #[test]
+    fn test_translation() {
+        let t = Transform4D::from_position(Vec4::new(1.0, 2.0, 3.0, 4.0));
+        let p = Vec4::ZERO;
+        let transformed = t.transform_point(p);
+        assert!(vec_approx_eq(transformed, Vec4::new(1.0, 2.0, 3.0, 4.0)));
+    }
+
+    #[test]
+    fn test_scale() {
+        let mut t = Transform4D::identity();
+        t.scale = 2.0;
+        let p = Vec4::new(1.0, 1.0, 1.0, 1.0);
+        let transformed = t.transform_point(p);
+        assert!(vec_approx_eq(transformed, Vec4::new(2.0, 2.0, 2.0, 2.0)));
+    }
+
+    #[test]
+    fn test_rotation() {
+        let rotor = Rotor4::from_plane_angle(RotationPlane::XY, PI / 2.0);
+        let t = Transform4D::from_position_rotation(Vec4::ZERO, rotor);
+        let p = Vec4::X;
+        let transformed = t.transform_point(p);
+        assert!(vec_approx_eq(transformed, Vec4::Y), "Expected Y, got {:?}", transformed);
+    }
+
+    #[test]
+    fn test_rotate_plane_matches_rotor() {
+        let rotor = Rotor4::from_plane_angle(RotationPlane::XY, PI / 2.0);
+        let mut t = Transform4D::from_position(Vec4::new(1.0, 2.0, 3.0, 4.0));
+        t.rotate_plane(RotationPlane::XY, PI / 2.0);
+
+        let p = Vec4::new(1.0, 0.5, -2.0, 3.0);
+        let expected = rotor.rotate(p) + t.position;
+        assert!(vec_approx_eq(t.transform_point(p), expected), "Expected {:?}, got {:?}", expected, t.transform_point(p));
+
+        // The copying variant leaves the original alone
+        let start = Transform4D::identity();
+        let rotated = start.rotated_plane(RotationPlane::XY, PI / 2.0);
+        assert!(vec_approx_eq(rotated.transform_point(Vec4::X), Vec4::Y));
+        assert!(vec_approx_eq(start.transform_point(Vec4::X), Vec4::X));
+    }
+
+    #[test]
+    fn test_repeated_rotate_plane_stays_normalized() {
+        let mut t = Transform4D::identity();
+        for i in 0..10_000 {
+            let plane = [RotationPlane::XY, RotationPlane::ZW, RotationPlane::XW][i % 3];
+            t.rotate_plane(plane, 0.0137);
+        }
+        assert!(approx_eq(t.rotation.magnitude(), 1.0), "magnitude {}", t.rotation.magnitude());
+
+        // Lengths are still preserved
+        let p = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        assert!(approx_eq(t.transform_direction(p).length(), p.length()));
+    }
+
+    #[test]
+    fn test_transform_order() {
+        // Transform applies: scale, then rotate, then translate
+        let rotor = Rotor4::from_plane_angle(RotationPlane::XY, PI / 2.0);
+        let mut t = Transform4D::identity();
+        t.scale = 2.0;
+        t.rotation = rotor;
+        t.position = Vec4::new(10.0, 0.0, 0.0, 0.0);
+
+        // X * 2 = (2, 0, 0, 0), rotated 90° in XY = (0, 2, 0, 0), + (10, 0, 0, 0) = (10, 2, 0, 0)
+        let p = Vec4::X;
+        let transformed = t.transform_point(p);
+        assert!(vec_approx_eq(transformed, Vec4::new(10.0, 2.0, 0.0, 0.0)),
+            "Expected (10, 2, 0, 0), got {:?}", transformed);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let rotor = Rotor4::from_plane_angle(RotationPlane::XZ, 0.5);
+        let mut t = Transform4D::from_position_rotation(Vec4::new(1.0, 2.0, 3.0, 4.0), rotor);
+        t.scale = 2.0;
+
+        let p = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        let transformed = t.transform_point(p);
+        let back = t.inverse().transform_point(transformed);
+
+        assert!(vec_approx_eq(p, back), "Expected {:?}, got {:?}", p, back);
+    }
+
+    #[test]
+    fn test_transform_direction() {
+        let t = Transform4D::from_position(Vec4::new(100.0, 100.0, 100.0, 100.0));
+        let d = Vec4::X;
+        let transformed = t.transform_direction(d);
+        // Direction should not be affected by position
+        assert!(vec_approx_eq(transformed, Vec4::X));
+    }
+
+    #[test]
+    fn test_compose() {
+        let t1 = Transform4D::from_position(Vec4::new(1.0, 0.0, 0.0, 0.0));
+        let t2 = Transform4D::from_position(Vec4::new(0.0, 2.0, 0.0, 0.0));
+
+        // t1.compose(t2) applies t2 first, then t1
+        let composed = t1.compose(&t2);
+
+        let p = Vec4::ZERO;
+        let result = composed.transform_point(p);
+        // Origin -> +y2 (t2) -> +x1 (t1) = (1, 2, 0, 0)
+        assert!(vec_approx_eq(result, Vec4::new(1.0, 2.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_snap_to_grid() {
+        let t = Transform4D::from_position(Vec4::new(1.3, 2.7, 0.0, 0.0));
+        let snapped = t.snap_to_grid(Vec4::new(1.0, 1.0, 1.0, 1.0));
+        assert!(vec_approx_eq(snapped.position, Vec4::new(1.0, 3.0, 0.0, 0.0)));
+
+        // Half-unit cells, and a zero cell leaves Y alone
+        let snapped = t.snap_to_grid(Vec4::new(0.5, 0.0, 1.0, 1.0));
+        assert!(vec_approx_eq(snapped.position, Vec4::new(1.5, 2.7, 0.0, 0.0)));
+        assert_eq!(snapped.scale, t.scale);
+    }
+
+    #[test]
+    fn test_reflected_tesseract_vertices_are_mirrored() {
+        use crate::{ConvexShape4D, Tesseract4D};
+
+        let rotor = Rotor4::from_plane_angle(RotationPlane::XW, 0.4);
+        let mut t = Transform4D::from_position_rotation(Vec4::new(3.0, 1.0, -2.0, 0.5), rotor);
+        t.scale = 1.5;
+        let normal = Vec4::new(1.0, 0.0, 0.0, 1.0).normalized();
+        let mirrored = t.reflected(normal);
+        assert!(mirrored.is_mirrored());
+
+        for v in Tesseract4D::new(2.0).vertices() {
+            let original = t.transform_point(*v);
+            let expected = original - normal * (2.0 * original.dot(normal));
+            let actual = mirrored.transform_point(*v);
+            assert!(vec_approx_eq(actual, expected), "Expected {:?}, got {:?}", expected, actual);
+        }
+
+        // The inverse undoes it
+        let p = Vec4::new(1.0, -2.0, 0.5, 3.0);
+        assert!(vec_approx_eq(mirrored.inverse().transform_point(mirrored.transform_point(p)), p));
+    }
+
+    #[test]
+    fn test_reflected_flips_winding() {
+        use crate::{ConvexShape4D, Tesseract4D};
+
+        // Signed 4D volume of a tetrahedron's vertices plus a point off its hyperplane
+        fn orientation(t: &Transform4D, p: [Vec4; 4], apex: Vec4) -> f32 {
+            let [a, b, c, d] = p.map(|v| t.transform_point(v));
+            Vec4::cross3(b - a, c - a, d - a).dot(t.transform_point(apex) - a)
+        }
+
+        let tesseract = Tesseract4D::new(2.0);
+        let vertices = tesseract.vertices();
+        let t = Transform4D::from_position_rotation(Vec4::new(1.0, 2.0, 0.0, -1.0), Rotor4::from_plane_angle(RotationPlane::YZ, 0.7));
+        let mirrored = t.reflected(Vec4::Y);
+        let mut checked = 0;
+        for tet in tesseract.tetrahedra() {
+            let p = tet.indices.map(|i| vertices[i]);
+            // The shape's center, unless it lies on the tetrahedron's hyperplane
+            let before = orientation(&t, p, Vec4::ZERO);
+            if before.abs() < EPSILON {
+                continue;
+            }
+            let after = orientation(&mirrored, p, Vec4::ZERO);
+            assert!(before * after < 0.0, "winding kept: {} then {}", before, after);
+            checked += 1;
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn test_reflecting_twice_is_a_rotation() {
+        let t = Transform4D::from_position(Vec4::new(1.0, 2.0, 3.0, 4.0));
+        let a = Vec4::X;
+        let b = Vec4::new(1.0, 1.0, 0.0, 0.0).normalized();
+        let twice = t.reflected(a).reflected(b);
+        assert!(!twice.is_mirrored());
+
+        let p = Vec4::new(0.5, -1.0, 2.0, 1.0);
+        let step = t.transform_point(p);
+        let step = step - a * (2.0 * step.dot(a));
+        let expected = step - b * (2.0 * step.dot(b));
+        assert!(vec_approx_eq(twice.transform_point(p), expected), "Expected {:?}, got {:?}", expected, twice.transform_point(p));
+
+        // The same plane twice is the original
+        let back = t.reflected(a).reflected(a);
+        assert!(vec_approx_eq(back.transform_point(p), t.transform_point(p)));
+    }
+
+    #[test]
+    fn test_compose_with_mirror() {
+        let mirrored = Transform4D::from_position_rotation(Vec4::new(1.0, 0.0, 0.0, 2.0), Rotor4::from_plane_angle(RotationPlane::XZ, 0.3))
+            .reflected(Vec4::Z);
+        let rotated = Transform4D::from_position_rotation(Vec4::new(0.0, 1.0, 0.0, 0.0), Rotor4::from_plane_angle(RotationPlane::YW, 1.1))
+            .reflected(Vec4::new(0.0, 1.0, 1.0, 0.0));
+        let p = Vec4::new(1.0, 2.0, -1.0, 0.5);
+        for (a, b) in [(&mirrored, &rotated), (&rotated, &mirrored), (&mirrored, &Transform4D::identity())] {
+            let expected = a.transform_point(b.transform_point(p));
+            assert!(vec_approx_eq(a.compose(b).transform_point(p), expected));
+        }
+    }
+
+    #[test]
+    fn test_default() {
+        let t = Transform4D::default();
+        assert!(vec_approx_eq(t.position, Vec4::ZERO));
+        assert_eq!(t.scale, 1.0);
+    }
+}
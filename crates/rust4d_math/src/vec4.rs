@@ -128,6 +128,26 @@ impl Vec4 {
             self.w * other.w,
         )
     }
+
+    /// Generalized cross product: a vector perpendicular to `a`, `b` and `c`
+    ///
+    /// Computed by cofactor expansion of the 4x4 determinant with the basis
+    /// vectors in the last row, so `cross3(X, Y, Z) == W`. The length equals
+    /// the 3-volume of the parallelepiped spanned by the inputs, and the
+    /// result is zero when they are linearly dependent.
+    pub fn cross3(a: Self, b: Self, c: Self) -> Self {
+        let det3 = |r0: [f32; 3], r1: [f32; 3], r2: [f32; 3]| {
+            r0[0] * (r1[1] * r2[2] - r1[2] * r2[1])
+                - r0[1] * (r1[0] * r2[2] - r1[2] * r2[0])
+                + r0[2] * (r1[0] * r2[1] - r1[1] * r2[0])
+        };
+        Self::new(
+            -det3([a.y, a.z, a.w], [b.y, b.z, b.w], [c.y, c.z, c.w]),
+            det3([a.x, a.z, a.w], [b.x, b.z, b.w], [c.x, c.z, c.w]),
+            -det3([a.x, a.y, a.w], [b.x, b.y, b.w], [c.x, c.y, c.w]),
+            det3([a.x, a.y, a.z], [b.x, b.y, b.z], [c.x, c.y, c.z]),
+        )
+    }
 }
 
 // Operator overloads
@@ -368,4 +388,44 @@ mod tests {
         let result = a.component_mul(b);
         assert_eq!(result, Vec4::new(2.0, 6.0, 12.0, 20.0));
     }
+
+    #[test]
+    fn test_cross3_basis() {
+        assert_eq!(Vec4::cross3(Vec4::X, Vec4::Y, Vec4::Z), Vec4::W);
+        // Cyclic order of the remaining axes follows the determinant sign
+        assert_eq!(Vec4::cross3(Vec4::Y, Vec4::Z, Vec4::W), -Vec4::X);
+        assert_eq!(Vec4::cross3(Vec4::Y, Vec4::X, Vec4::Z), -Vec4::W);
+    }
+
+    #[test]
+    fn test_cross3_orthogonal() {
+        let a = Vec4::new(1.0, 2.0, -1.0, 0.5);
+        let b = Vec4::new(0.0, 1.0, 3.0, -2.0);
+        let c = Vec4::new(2.0, -1.0, 1.0, 1.0);
+        let n = Vec4::cross3(a, b, c);
+
+        assert!(n.length() > 0.0);
+        assert!(n.dot(a).abs() < 1e-5);
+        assert!(n.dot(b).abs() < 1e-5);
+        assert!(n.dot(c).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_cross3_length_is_volume() {
+        // Box with sides 2, 3, 4 in the XYZ subspace
+        let n = Vec4::cross3(Vec4::X * 2.0, Vec4::Y * 3.0, Vec4::Z * 4.0);
+        assert_eq!(n.length(), 24.0);
+
+        // Shearing doesn't change the volume
+        let n = Vec4::cross3(Vec4::X * 2.0, Vec4::Y * 3.0 + Vec4::X, Vec4::Z * 4.0 + Vec4::Y);
+        assert!((n.length() - 24.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_cross3_dependent_is_zero() {
+        let a = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        let b = Vec4::new(0.0, 1.0, 0.0, 1.0);
+        assert_eq!(Vec4::cross3(a, b, a * 2.0 - b), Vec4::ZERO);
+        assert_eq!(Vec4::cross3(a, a, b), Vec4::ZERO);
+    }
 }
@@ -3,7 +3,7 @@
 use crate::collision::CollisionFilter;
 use crate::material::PhysicsMaterial;
 use crate::shapes::{Collider, Plane4D};
-use rust4d_math::Vec4;
+use rust4d_math::{Bivector4, Rotor4, Vec4};
 use slotmap::new_key_type;
 
 // Define generational key type for rigid bodies
@@ -35,6 +35,22 @@ pub struct RigidBody4D {
     pub position: Vec4,
     /// Velocity in 4D space (units per second)
     pub velocity: Vec4,
+    /// Orientation in world space
+    ///
+    /// Integrated from `angular_velocity` every step. The collider does not
+    /// rotate with it; it is carried along for rendering.
+    pub orientation: Rotor4,
+    /// Rotation rate in each plane (radians per second)
+    pub angular_velocity: Bivector4,
+    /// Fraction of velocity lost per second (0.0 = none)
+    pub linear_damping: f32,
+    /// Fraction of angular velocity lost per second (0.0 = none)
+    pub angular_damping: f32,
+    /// Force accumulated since the last step
+    ///
+    /// Added to by [`PhysicsWorld::apply_force`](crate::PhysicsWorld::apply_force),
+    /// applied as acceleration (`force / mass`) during the next step, then cleared.
+    pub force: Vec4,
     /// Mass of the body (used for push calculations)
     pub mass: f32,
     /// Physical material properties (friction and restitution)
@@ -47,8 +63,31 @@ pub struct RigidBody4D {
     pub grounded: bool,
     /// Collision filter (layer membership and collision mask)
     pub filter: CollisionFilter,
+    /// Push priority for body-body collisions
+    ///
+    /// When two movable bodies overlap, the one with the higher priority keeps
+    /// its position and velocity and the other takes the full correction,
+    /// regardless of mass. Equal priorities split the correction by mass.
+    pub push_priority: u8,
+    /// W coordinate this body is pinned to, if any
+    ///
+    /// A pinned body keeps moving in X, Y and Z, but every step its W
+    /// velocity is zeroed and it is put back at this W, whatever gravity,
+    /// forces or collisions did along W.
+    pub w_lock: Option<f32>,
+    /// Whether this body is held in place (see [`RigidBody4D::set_frozen`])
+    frozen: bool,
+    /// Whether this body is asleep (see [`RigidBody4D::is_sleeping`])
+    sleeping: bool,
+    /// Seconds the body has been moving slower than the sleep threshold
+    sleep_timer: f32,
 }
 
+/// Push priority given to the player body by [`PhysicsWorld::set_player_body`]
+///
+/// [`PhysicsWorld::set_player_body`]: crate::PhysicsWorld::set_player_body
+pub const PLAYER_PUSH_PRIORITY: u8 = 100;
+
 impl RigidBody4D {
     /// Check if this body is affected by gravity
     #[inline]
@@ -67,6 +106,58 @@ impl RigidBody4D {
     pub fn is_kinematic(&self) -> bool {
         self.body_type == BodyType::Kinematic
     }
+
+    /// Check if this body is frozen (held in place, see [`RigidBody4D::set_frozen`])
+    #[inline]
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    /// Freeze or unfreeze this body
+    ///
+    /// A frozen body is skipped during integration and is never moved by
+    /// collisions, but still collides and pushes other bodies. Unlike making
+    /// it static, its body type and velocity are kept, so motion resumes
+    /// when it is unfrozen.
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen;
+    }
+
+    /// Check if this body is asleep
+    ///
+    /// A dynamic body that has stayed nearly still long enough (see
+    /// [`PhysicsConfig::sleep_time`](crate::PhysicsConfig::sleep_time)) falls
+    /// asleep: it is skipped during integration and static collision
+    /// resolution until an impulse, force or collision with an awake body
+    /// wakes it.
+    #[inline]
+    pub fn is_sleeping(&self) -> bool {
+        self.sleeping
+    }
+
+    /// Wake this body up
+    ///
+    /// Needed after changing a sleeping body's velocity or position directly.
+    pub fn wake(&mut self) {
+        self.sleeping = false;
+        self.sleep_timer = 0.0;
+    }
+
+    /// Advance the sleep timer by `dt`, putting the body to sleep once it
+    /// has moved slower than `threshold` for `time` seconds
+    pub(crate) fn update_sleep(&mut self, dt: f32, threshold: f32, time: f32) {
+        let slow = self.velocity.length() < threshold && self.angular_velocity.magnitude() < threshold;
+        if !slow {
+            self.sleep_timer = 0.0;
+            return;
+        }
+        self.sleep_timer += dt;
+        if self.sleep_timer >= time {
+            self.sleeping = true;
+            self.velocity = Vec4::ZERO;
+            self.angular_velocity = Bivector4::ZERO;
+        }
+    }
 }
 
 // Additional RigidBody4D constructors and builder methods
@@ -77,12 +168,22 @@ impl RigidBody4D {
         Self {
             position,
             velocity: Vec4::ZERO,
+            orientation: Rotor4::IDENTITY,
+            angular_velocity: Bivector4::ZERO,
+            force: Vec4::ZERO,
+            linear_damping: 0.0,
+            angular_damping: 0.0,
             mass: 1.0,
             material: PhysicsMaterial::default(),
             collider: Collider::Sphere(Sphere4D::new(position, radius)),
             body_type: BodyType::Dynamic,
             grounded: false,
             filter: CollisionFilter::default(),
+            push_priority: 0,
+            w_lock: None,
+            frozen: false,
+            sleeping: false,
+            sleep_timer: 0.0,
         }
     }
 
@@ -92,12 +193,34 @@ impl RigidBody4D {
         Self {
             position,
             velocity: Vec4::ZERO,
+            orientation: Rotor4::IDENTITY,
+            angular_velocity: Bivector4::ZERO,
+            force: Vec4::ZERO,
+            linear_damping: 0.0,
+            angular_damping: 0.0,
             mass: 1.0,
             material: PhysicsMaterial::default(),
             collider: Collider::AABB(AABB4D::from_center_half_extents(position, half_extents)),
             body_type: BodyType::Dynamic,
             grounded: false,
             filter: CollisionFilter::default(),
+            push_priority: 0,
+            w_lock: None,
+            frozen: false,
+            sleeping: false,
+            sleep_timer: 0.0,
+        }
+    }
+
+    /// Create a new rigid body with an upright capsule collider
+    ///
+    /// The body extends `half_height + radius` above and below `position`.
+    /// Takes its sizes in the same order as [`Capsule4D::new`](crate::shapes::Capsule4D::new).
+    pub fn new_capsule(position: Vec4, half_height: f32, radius: f32) -> Self {
+        use crate::shapes::Capsule4D;
+        Self {
+            collider: Collider::Capsule(Capsule4D::new(position, half_height, radius)),
+            ..Self::new_sphere(position, radius)
         }
     }
 
@@ -112,6 +235,28 @@ impl RigidBody4D {
         self
     }
 
+    /// Set the orientation of this body
+    pub fn with_orientation(mut self, orientation: Rotor4) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Set the angular velocity of this body (radians per second in each plane)
+    pub fn with_angular_velocity(mut self, angular_velocity: Bivector4) -> Self {
+        self.angular_velocity = angular_velocity;
+        self
+    }
+
+    /// Set the linear and angular damping of this body
+    ///
+    /// Each step scales velocity by `1 - damping * dt` (never below zero),
+    /// so damping 0.5 bleeds off about half the speed per second.
+    pub fn with_damping(mut self, linear: f32, angular: f32) -> Self {
+        self.linear_damping = linear;
+        self.angular_damping = angular;
+        self
+    }
+
     /// Set the mass of this body
     pub fn with_mass(mut self, mass: f32) -> Self {
         self.mass = mass;
@@ -184,6 +329,27 @@ impl RigidBody4D {
         self
     }
 
+    /// Set the push priority used when resolving body-body collisions
+    pub fn with_push_priority(mut self, priority: u8) -> Self {
+        self.push_priority = priority;
+        self
+    }
+
+    /// Pin this body's W coordinate (see [`w_lock`](Self::w_lock))
+    pub fn with_w_lock(mut self, w: f32) -> Self {
+        self.w_lock = Some(w);
+        self
+    }
+
+    /// Put the body at `w` with no W velocity
+    pub(crate) fn hold_w(&mut self, w: f32) {
+        self.velocity.w = 0.0;
+        if self.position.w != w {
+            let position = self.position;
+            self.set_position(Vec4::new(position.x, position.y, position.z, w));
+        }
+    }
+
     /// Update the position and sync the collider
     pub fn set_position(&mut self, position: Vec4) {
         let delta = position - self.position;
@@ -210,6 +376,8 @@ pub struct StaticCollider {
     pub material: PhysicsMaterial,
     /// Collision filter (layer membership and collision mask)
     pub filter: CollisionFilter,
+    /// Whether this collider takes part in collision (see [`PhysicsWorld::set_static_enabled`](crate::PhysicsWorld::set_static_enabled))
+    pub enabled: bool,
 }
 
 impl StaticCollider {
@@ -219,6 +387,7 @@ impl StaticCollider {
             collider,
             material,
             filter: CollisionFilter::static_world(),
+            enabled: true,
         }
     }
 
@@ -228,6 +397,7 @@ impl StaticCollider {
             collider: Collider::Plane(Plane4D::new(normal, distance)),
             material,
             filter: CollisionFilter::static_world(),
+            enabled: true,
         }
     }
 
@@ -237,6 +407,7 @@ impl StaticCollider {
             collider: Collider::Plane(Plane4D::floor(y)),
             material,
             filter: CollisionFilter::static_world(),
+            enabled: true,
         }
     }
 
@@ -278,6 +449,7 @@ impl StaticCollider {
             collider: Collider::AABB(AABB4D::from_center_half_extents(center, half_extents)),
             material,
             filter: CollisionFilter::static_world(),
+            enabled: true,
         }
     }
 
@@ -288,6 +460,7 @@ impl StaticCollider {
             collider: Collider::AABB(AABB4D::from_center_half_extents(center, half_extents)),
             material,
             filter: CollisionFilter::static_world(),
+            enabled: true,
         }
     }
 
@@ -297,6 +470,22 @@ impl StaticCollider {
         self
     }
 
+    /// Set the collision layer (which layer this collider belongs to)
+    pub fn with_layer(mut self, layer: crate::collision::CollisionLayer) -> Self {
+        self.filter.layer = layer;
+        self
+    }
+
+    /// Set the collision mask (which layers this collider blocks or detects)
+    ///
+    /// Like body filters, a body is only blocked if each side's layer is in
+    /// the other's mask, so a wall masked to [`ENEMY`](crate::CollisionLayer::ENEMY)
+    /// lets the player through.
+    pub fn with_mask(mut self, mask: crate::collision::CollisionLayer) -> Self {
+        self.filter.mask = mask;
+        self
+    }
+
     /// Check if a position (ignoring Y) is within the XZW bounds of this collider
     ///
     /// This is used to detect when a player has walked off the edge of a bounded
@@ -316,7 +505,7 @@ impl StaticCollider {
                     && position.w <= aabb.max.w
             }
             Collider::Plane(_) => true, // Infinite planes extend forever
-            Collider::Sphere(_) => false, // Spheres aren't floor surfaces
+            Collider::Sphere(_) | Collider::Capsule(_) => false, // Not floor surfaces
         }
     }
 }
@@ -1,11 +1,13 @@
 //! Collision detection for 4D shapes
 //!
-//! Provides collision detection between spheres, AABBs, and planes.
-//! Also provides collision filtering via layer masks.
+//! Provides collision detection between spheres, AABBs, and planes,
+//! analytic ray tests against the same shapes, and collision filtering via
+//! layer masks.
 
 use bitflags::bitflags;
 
-use crate::shapes::{Plane4D, Sphere4D, AABB4D};
+use crate::body::BodyKey;
+use crate::shapes::{Capsule4D, Collider, Plane4D, Sphere4D, AABB4D};
 use rust4d_math::Vec4;
 
 bitflags! {
@@ -34,6 +36,20 @@ bitflags! {
     }
 }
 
+impl CollisionLayer {
+    /// Number of layer bits left for game-specific layers
+    pub const CUSTOM_COUNT: u32 = 25;
+
+    /// A game-specific layer, numbered from 0 after the built-in layers
+    ///
+    /// # Panics
+    /// If `index` is not below [`CUSTOM_COUNT`](Self::CUSTOM_COUNT).
+    pub const fn custom(index: u32) -> Self {
+        assert!(index < Self::CUSTOM_COUNT, "custom collision layer index out of range");
+        Self::from_bits_retain(Self::PICKUP.bits() << (index + 1))
+    }
+}
+
 /// Collision filter determining what an object collides with
 ///
 /// Uses a layer/mask system:
@@ -77,6 +93,14 @@ impl CollisionFilter {
         self.layer.intersects(other.mask) && other.layer.intersects(self.mask)
     }
 
+    /// Check if this filter is a trigger that detects another filter
+    ///
+    /// Trigger overlaps are reported as collision events without pushing
+    /// anything, even though [`collides_with`](Self::collides_with) is false.
+    pub fn triggers_on(&self, other: &Self) -> bool {
+        self.layer.contains(CollisionLayer::TRIGGER) && self.mask.intersects(other.layer)
+    }
+
     /// Create a filter for player objects
     ///
     /// Players collide with everything except other players, player projectiles, and triggers.
@@ -160,6 +184,24 @@ impl Contact {
     }
 }
 
+/// A contact found during a physics step
+///
+/// Recorded for every colliding pair, and for trigger overlaps that are not
+/// resolved (see [`CollisionFilter::triggers_on`]).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CollisionEvent {
+    /// The body that was hit
+    pub a: BodyKey,
+    /// The other body, or `None` for a static collider
+    pub b: Option<BodyKey>,
+    /// Point of contact
+    pub point: Vec4,
+    /// Contact normal, pointing from `b` toward `a`
+    pub normal: Vec4,
+    /// Penetration depth before resolution
+    pub penetration: f32,
+}
+
 /// Test sphere vs plane collision
 ///
 /// Returns a contact if the sphere is intersecting or touching the plane.
@@ -343,6 +385,208 @@ pub fn aabb_vs_aabb(a: &AABB4D, b: &AABB4D) -> Option<Contact> {
     Some(Contact::new(point, normal, min_overlap))
 }
 
+/// Contact between two spheres given by center and radius
+///
+/// The normal points from `b` toward `a`; coincident centers separate along +Y.
+fn spheres_contact(a: Vec4, a_radius: f32, b: Vec4, b_radius: f32) -> Option<Contact> {
+    let delta = a - b;
+    let dist_sq = delta.length_squared();
+    let min_dist = a_radius + b_radius;
+    if dist_sq >= min_dist * min_dist {
+        return None;
+    }
+
+    let dist = dist_sq.sqrt();
+    let normal = if dist > 0.0001 { delta / dist } else { Vec4::Y };
+    Some(Contact::new(b + normal * b_radius, normal, min_dist - dist))
+}
+
+/// Test capsule vs plane collision
+///
+/// Treats the capsule as a sphere at whichever end of its segment is deeper
+/// toward the plane. The normal points from the plane toward the capsule.
+pub fn capsule_vs_plane(capsule: &Capsule4D, plane: &Plane4D) -> Option<Contact> {
+    let (bottom, top) = (capsule.bottom(), capsule.top());
+    let end = if plane.signed_distance(bottom) <= plane.signed_distance(top) { bottom } else { top };
+    sphere_vs_plane(&Sphere4D::new(end, capsule.radius), plane)
+}
+
+/// Test capsule vs AABB collision
+///
+/// Tests the sphere at the point of the capsule's segment nearest the box:
+/// the middle of the height range they share, or the closer end when they
+/// don't overlap in Y. The normal points from the AABB toward the capsule.
+pub fn capsule_vs_aabb(capsule: &Capsule4D, aabb: &AABB4D) -> Option<Contact> {
+    let (bottom, top) = (capsule.bottom().y, capsule.top().y);
+    let (low, high) = (bottom.max(aabb.min.y), top.min(aabb.max.y));
+    let y = if low <= high {
+        (low + high) * 0.5
+    } else if top < aabb.min.y {
+        top
+    } else {
+        bottom
+    };
+    let center = Vec4::new(capsule.center.x, y, capsule.center.z, capsule.center.w);
+    sphere_vs_aabb(&Sphere4D::new(center, capsule.radius), aabb)
+}
+
+/// Test capsule vs sphere collision
+///
+/// The normal points from the sphere toward the capsule.
+pub fn capsule_vs_sphere(capsule: &Capsule4D, sphere: &Sphere4D) -> Option<Contact> {
+    let nearest = capsule.closest_segment_point(sphere.center);
+    spheres_contact(nearest, capsule.radius, sphere.center, sphere.radius)
+}
+
+/// Test capsule vs capsule collision
+///
+/// Both segments are vertical, so the nearest points share the middle of
+/// their common height range, or are the facing ends when they don't
+/// overlap in Y. The normal points from `b` toward `a`.
+pub fn capsule_vs_capsule(a: &Capsule4D, b: &Capsule4D) -> Option<Contact> {
+    let (low, high) = (a.bottom().y.max(b.bottom().y), a.top().y.min(b.top().y));
+    let (ya, yb) = if low <= high {
+        let y = (low + high) * 0.5;
+        (y, y)
+    } else if a.center.y > b.center.y {
+        (a.bottom().y, b.top().y)
+    } else {
+        (a.top().y, b.bottom().y)
+    };
+    let pa = Vec4::new(a.center.x, ya, a.center.z, a.center.w);
+    let pb = Vec4::new(b.center.x, yb, b.center.z, b.center.w);
+    spheres_contact(pa, a.radius, pb, b.radius)
+}
+
+/// Cast a ray against a sphere
+///
+/// `dir` must be normalized. Returns the distance along the ray and the
+/// surface normal at the hit. A ray starting inside the sphere hits at
+/// distance 0 with the normal facing back along the ray.
+pub fn ray_vs_sphere(origin: Vec4, dir: Vec4, sphere: &Sphere4D) -> Option<(f32, Vec4)> {
+    let offset = origin - sphere.center;
+    let c = offset.length_squared() - sphere.radius * sphere.radius;
+    if c <= 0.0 {
+        return Some((0.0, -dir));
+    }
+
+    // |offset + t * dir|^2 = r^2 with |dir| = 1
+    let b = offset.dot(dir);
+    let discriminant = b * b - c;
+    if b >= 0.0 || discriminant < 0.0 {
+        return None;
+    }
+    let t = -b - discriminant.sqrt();
+    Some((t, (offset + dir * t).normalized()))
+}
+
+/// Cast a ray against an AABB
+///
+/// Slab test on all four axes. `dir` must be normalized; the normal is the
+/// face the ray enters through. A ray starting inside hits at distance 0.
+pub fn ray_vs_aabb(origin: Vec4, dir: Vec4, aabb: &AABB4D) -> Option<(f32, Vec4)> {
+    if aabb.contains(origin) {
+        return Some((0.0, -dir));
+    }
+
+    let axes = [
+        (origin.x, dir.x, aabb.min.x, aabb.max.x, Vec4::X),
+        (origin.y, dir.y, aabb.min.y, aabb.max.y, Vec4::Y),
+        (origin.z, dir.z, aabb.min.z, aabb.max.z, Vec4::Z),
+        (origin.w, dir.w, aabb.min.w, aabb.max.w, Vec4::W),
+    ];
+
+    let mut t_enter = 0.0f32;
+    let mut t_exit = f32::INFINITY;
+    let mut normal = Vec4::ZERO;
+    for (o, d, min, max, axis) in axes {
+        if d.abs() < 1e-8 {
+            // Parallel to this slab: must already be between its faces
+            if o < min || o > max {
+                return None;
+            }
+            continue;
+        }
+        let (t0, t1) = ((min - o) / d, (max - o) / d);
+        let (near, far) = if t0 < t1 { (t0, t1) } else { (t1, t0) };
+        if near > t_enter {
+            t_enter = near;
+            normal = if d > 0.0 { -axis } else { axis };
+        }
+        t_exit = t_exit.min(far);
+        if t_enter > t_exit {
+            return None;
+        }
+    }
+    Some((t_enter, normal))
+}
+
+/// Cast a ray against a plane
+///
+/// The plane is treated as a solid half-space (everything behind it is
+/// inside), matching how planes collide. `dir` must be normalized.
+pub fn ray_vs_plane(origin: Vec4, dir: Vec4, plane: &Plane4D) -> Option<(f32, Vec4)> {
+    let dist = plane.signed_distance(origin);
+    if dist < 0.0 {
+        return Some((0.0, -dir));
+    }
+    let approach = plane.normal.dot(dir);
+    if approach >= 0.0 {
+        return None;
+    }
+    Some((-dist / approach, plane.normal))
+}
+
+/// Cast a ray against a capsule
+///
+/// `dir` must be normalized. Tests the side of the capsule (a cylinder around
+/// the Y axis in XZW) and both end caps, returning the nearest hit. A ray
+/// starting inside hits at distance 0.
+pub fn ray_vs_capsule(origin: Vec4, dir: Vec4, capsule: &Capsule4D) -> Option<(f32, Vec4)> {
+    if capsule.contains(origin) {
+        return Some((0.0, -dir));
+    }
+
+    let mut nearest: Option<(f32, Vec4)> = None;
+
+    // Side: |offset + t * dir|^2 = r^2, ignoring Y
+    let flat = |v: Vec4| Vec4::new(v.x, 0.0, v.z, v.w);
+    let offset = flat(origin - capsule.center);
+    let flat_dir = flat(dir);
+    let a = flat_dir.length_squared();
+    if a > 1e-12 {
+        let b = offset.dot(flat_dir);
+        let c = offset.length_squared() - capsule.radius * capsule.radius;
+        let discriminant = b * b - a * c;
+        if discriminant >= 0.0 {
+            let t = (-b - discriminant.sqrt()) / a;
+            let height = origin.y + dir.y * t - capsule.center.y;
+            if t >= 0.0 && height.abs() <= capsule.half_height {
+                nearest = Some((t, (offset + flat_dir * t).normalized()));
+            }
+        }
+    }
+
+    for end in [capsule.bottom(), capsule.top()] {
+        if let Some((t, normal)) = ray_vs_sphere(origin, dir, &Sphere4D::new(end, capsule.radius)) {
+            if nearest.is_none_or(|(best, _)| t < best) {
+                nearest = Some((t, normal));
+            }
+        }
+    }
+    nearest
+}
+
+/// Cast a ray against any collider
+pub fn ray_vs_collider(origin: Vec4, dir: Vec4, collider: &Collider) -> Option<(f32, Vec4)> {
+    match collider {
+        Collider::Sphere(sphere) => ray_vs_sphere(origin, dir, sphere),
+        Collider::AABB(aabb) => ray_vs_aabb(origin, dir, aabb),
+        Collider::Plane(plane) => ray_vs_plane(origin, dir, plane),
+        Collider::Capsule(capsule) => ray_vs_capsule(origin, dir, capsule),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -438,6 +682,104 @@ mod tests {
         assert!((contact.penetration - 0.5).abs() < 0.0001);
     }
 
+    #[test]
+    fn test_capsule_vs_plane_above() {
+        let capsule = Capsule4D::new(Vec4::new(0.0, 2.0, 0.0, 0.0), 0.5, 1.0);
+        let plane = Plane4D::floor(0.0);
+
+        // Lowest point at y=0.5
+        assert!(capsule_vs_plane(&capsule, &plane).is_none());
+    }
+
+    #[test]
+    fn test_capsule_vs_plane_colliding() {
+        let capsule = Capsule4D::new(Vec4::new(0.0, 1.0, 0.0, 0.0), 0.5, 1.0);
+        let plane = Plane4D::floor(0.0);
+
+        // Lower cap centered at y=0.5 dips 0.5 below the floor
+        let contact = capsule_vs_plane(&capsule, &plane).expect("Should collide");
+        assert!((contact.penetration - 0.5).abs() < 0.0001);
+        assert_eq!(contact.normal, Vec4::Y);
+        assert_eq!(contact.point, Vec4::new(0.0, -0.5, 0.0, 0.0));
+
+        // A ceiling is hit by the upper cap instead
+        let ceiling = Plane4D::new(-Vec4::Y, -2.0);
+        let contact = capsule_vs_plane(&capsule, &ceiling).expect("Should collide");
+        assert!((contact.penetration - 0.5).abs() < 0.0001);
+        assert_eq!(contact.normal, -Vec4::Y);
+    }
+
+    #[test]
+    fn test_capsule_vs_aabb_no_collision() {
+        let capsule = Capsule4D::new(Vec4::new(5.0, 0.0, 0.0, 0.0), 1.0, 1.0);
+        let aabb = AABB4D::unit();
+
+        assert!(capsule_vs_aabb(&capsule, &aabb).is_none());
+    }
+
+    #[test]
+    fn test_capsule_vs_aabb_colliding() {
+        let aabb = AABB4D::unit(); // -0.5 to 0.5 in all dimensions
+
+        // Beside the box: the side of the segment touches the face at x=0.5
+        let capsule = Capsule4D::new(Vec4::new(1.0, 0.0, 0.0, 0.0), 1.0, 1.0);
+        let contact = capsule_vs_aabb(&capsule, &aabb).expect("Should collide");
+        assert!((contact.penetration - 0.5).abs() < 0.0001);
+        assert_eq!(contact.normal, Vec4::X);
+
+        // Standing on top: the lower cap touches the top face at y=0.5
+        let capsule = Capsule4D::new(Vec4::new(0.0, 1.75, 0.0, 0.0), 0.5, 1.0);
+        let contact = capsule_vs_aabb(&capsule, &aabb).expect("Should collide");
+        assert!((contact.penetration - 0.25).abs() < 0.0001);
+        assert_eq!(contact.normal, Vec4::Y);
+    }
+
+    #[test]
+    fn test_capsule_vs_sphere() {
+        let capsule = Capsule4D::new(Vec4::ZERO, 1.0, 0.5);
+
+        // Level with the segment, off to the side in W
+        let sphere = Sphere4D::new(Vec4::new(0.0, 0.5, 0.0, 0.8), 0.5);
+        let contact = capsule_vs_sphere(&capsule, &sphere).expect("Should collide");
+        assert!((contact.penetration - 0.2).abs() < 0.0001);
+        assert!((contact.normal - -Vec4::W).length() < 0.0001);
+
+        // Above the top cap
+        let sphere = Sphere4D::new(Vec4::new(0.0, 2.5, 0.0, 0.0), 0.5);
+        assert!(capsule_vs_sphere(&capsule, &sphere).is_none());
+    }
+
+    #[test]
+    fn test_capsule_vs_capsule() {
+        let a = Capsule4D::new(Vec4::ZERO, 1.0, 0.5);
+        let side = Capsule4D::new(Vec4::new(0.8, 1.5, 0.0, 0.0), 1.0, 0.5);
+        let contact = capsule_vs_capsule(&a, &side).expect("Should collide");
+        assert!((contact.penetration - 0.2).abs() < 0.0001);
+        assert!((contact.normal - -Vec4::X).length() < 0.0001);
+
+        // Stacked end to end, caps just apart
+        let above = Capsule4D::new(Vec4::new(0.0, 3.1, 0.0, 0.0), 1.0, 0.5);
+        assert!(capsule_vs_capsule(&a, &above).is_none());
+    }
+
+    #[test]
+    fn test_ray_vs_capsule_side_and_cap() {
+        let capsule = Capsule4D::new(Vec4::ZERO, 1.0, 0.5);
+
+        // Into the side, level with the segment
+        let (t, normal) = ray_vs_capsule(Vec4::new(-5.0, 0.5, 0.0, 0.0), Vec4::X, &capsule).unwrap();
+        assert!((t - 4.5).abs() < 1e-5);
+        assert!((normal - -Vec4::X).length() < 1e-5);
+
+        // Down onto the top cap
+        let (t, normal) = ray_vs_capsule(Vec4::new(0.0, 5.0, 0.0, 0.0), -Vec4::Y, &capsule).unwrap();
+        assert!((t - 3.5).abs() < 1e-5);
+        assert!((normal - Vec4::Y).length() < 1e-5);
+
+        // Passing beside it
+        assert!(ray_vs_capsule(Vec4::new(-5.0, 0.0, 0.0, 0.6), Vec4::X, &capsule).is_none());
+    }
+
     // ===== Collision Filter Tests =====
 
     #[test]
@@ -541,6 +883,20 @@ mod tests {
         assert!(!combined.contains(CollisionLayer::STATIC));
     }
 
+    #[test]
+    fn test_custom_layers_are_distinct_from_builtin() {
+        let builtin = CollisionLayer::DEFAULT
+            | CollisionLayer::PLAYER
+            | CollisionLayer::ENEMY
+            | CollisionLayer::STATIC
+            | CollisionLayer::TRIGGER
+            | CollisionLayer::PROJECTILE
+            | CollisionLayer::PICKUP;
+        assert!(!builtin.intersects(CollisionLayer::custom(0)));
+        assert_ne!(CollisionLayer::custom(0), CollisionLayer::custom(1));
+        assert_eq!(CollisionLayer::custom(CollisionLayer::CUSTOM_COUNT - 1).bits(), 1 << 31);
+    }
+
     #[test]
     fn test_collision_filter_custom() {
         // Custom filter: belongs to PICKUP layer, only collides with PLAYER
@@ -613,4 +969,39 @@ mod tests {
         assert!(aabb_vs_aabb(&tesseract_slightly_in, &floor).is_some(),
             "Tesseract slightly below resting position should collide");
     }
+
+    #[test]
+    fn test_ray_vs_sphere_hit_and_miss() {
+        let sphere = Sphere4D::new(Vec4::new(5.0, 0.0, 0.0, 0.0), 1.0);
+
+        let (t, normal) = ray_vs_sphere(Vec4::ZERO, Vec4::X, &sphere).expect("Should hit");
+        assert!((t - 4.0).abs() < 1e-5);
+        assert!((normal - -Vec4::X).length() < 1e-5);
+
+        // Pointing away, and passing beside it
+        assert!(ray_vs_sphere(Vec4::ZERO, -Vec4::X, &sphere).is_none());
+        assert!(ray_vs_sphere(Vec4::new(0.0, 0.0, 0.0, 2.0), Vec4::X, &sphere).is_none());
+    }
+
+    #[test]
+    fn test_ray_vs_aabb_entry_face() {
+        let aabb = AABB4D::from_center_half_extents(Vec4::new(0.0, 0.0, 0.0, 10.0), Vec4::new(1.0, 1.0, 1.0, 1.0));
+
+        let (t, normal) = ray_vs_aabb(Vec4::ZERO, Vec4::W, &aabb).expect("Should hit");
+        assert!((t - 9.0).abs() < 1e-5);
+        assert_eq!(normal, -Vec4::W);
+
+        assert!(ray_vs_aabb(Vec4::new(2.0, 0.0, 0.0, 0.0), Vec4::W, &aabb).is_none());
+    }
+
+    #[test]
+    fn test_ray_vs_plane() {
+        let floor = Plane4D::floor(0.0);
+
+        let (t, normal) = ray_vs_plane(Vec4::new(0.0, 3.0, 0.0, 0.0), -Vec4::Y, &floor).expect("Should hit");
+        assert!((t - 3.0).abs() < 1e-5);
+        assert_eq!(normal, Vec4::Y);
+
+        assert!(ray_vs_plane(Vec4::new(0.0, 3.0, 0.0, 0.0), Vec4::X, &floor).is_none());
+    }
 }
@@ -2,19 +2,26 @@
 //!
 //! This crate provides physics simulation for 4D rigid bodies, including:
 //! - Collision shapes (spheres, AABBs, planes)
-//! - Collision detection
+//! - Collision detection and raycasts
 //! - Rigid body dynamics with gravity
 //! - Player physics for FPS-style movement
+//! - Teleporting portals
 
 pub mod body;
 pub mod collision;
 pub mod material;
+pub mod portal;
 pub mod shapes;
 pub mod world;
 
 // Re-export commonly used types
-pub use body::{BodyKey, BodyType, RigidBody4D, StaticCollider};
-pub use collision::{aabb_vs_aabb, aabb_vs_plane, sphere_vs_aabb, sphere_vs_plane, CollisionFilter, CollisionLayer, Contact};
+pub use body::{BodyKey, BodyType, RigidBody4D, StaticCollider, PLAYER_PUSH_PRIORITY};
+pub use collision::{
+    aabb_vs_aabb, aabb_vs_plane, capsule_vs_aabb, capsule_vs_capsule, capsule_vs_plane, capsule_vs_sphere,
+    ray_vs_aabb, ray_vs_capsule, ray_vs_collider, ray_vs_plane, ray_vs_sphere, sphere_vs_aabb, sphere_vs_plane,
+    CollisionEvent, CollisionFilter, CollisionLayer, Contact,
+};
 pub use material::PhysicsMaterial;
-pub use shapes::{Collider, Plane4D, Sphere4D, AABB4D};
-pub use world::{PhysicsConfig, PhysicsWorld};
+pub use portal::{Portal, TeleportEvent, PORTAL_COOLDOWN};
+pub use shapes::{Capsule4D, Collider, Plane4D, Sphere4D, AABB4D};
+pub use world::{ControllableHandle, PhysicsConfig, PhysicsWorld, StaticColliderId, DEFAULT_BODY_GRID_CELL_SIZE, DETERMINISM_QUANTUM};
@@ -1,10 +1,12 @@
 //! Physical material properties for collision response
 
+use serde::{Serialize, Deserialize};
+
 /// Physical material properties for collision response
 ///
 /// Materials define how objects interact during collisions, including
 /// friction (how much objects resist sliding) and restitution (bounciness).
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PhysicsMaterial {
     /// Friction coefficient (0.0 = ice, 1.0 = rubber)
     pub friction: f32,
@@ -97,6 +99,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_preset_constants() {
         // Test that presets have expected properties
         assert!(PhysicsMaterial::ICE.friction < 0.1);
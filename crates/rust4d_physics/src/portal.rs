@@ -0,0 +1,114 @@
+//! Teleporting portals
+//!
+//! A [`Portal`] is a one-way link: when a body's center enters the `entry`
+//! collider, [`PhysicsWorld::step`](crate::PhysicsWorld::step) moves it through
+//! the exit transform and turns its velocity with it, so a body walking into
+//! one portal walks out of the other facing the new direction. Two portals
+//! with mirrored transforms make a two-way pair.
+
+use crate::body::BodyKey;
+use crate::shapes::Collider;
+use rust4d_math::{Transform4D, Vec4};
+
+/// Seconds a body ignores portals after teleporting
+///
+/// Without this a body placed inside the partner portal's entry would bounce
+/// straight back on the next step.
+pub const PORTAL_COOLDOWN: f32 = 0.5;
+
+/// A one-way teleporter
+#[derive(Clone, Copy, Debug)]
+pub struct Portal {
+    /// Region a body's center must enter to teleport
+    ///
+    /// For planes, "inside" is the side the normal points away from.
+    pub entry: Collider,
+    /// Maps offsets from the entry's center to exit positions
+    ///
+    /// Its position is where the entry's center comes out; its rotation
+    /// (and any scale or mirror) also applies to velocity.
+    pub exit_transform: Transform4D,
+}
+
+impl Portal {
+    /// Create a portal from an entry region and the exit transform
+    pub fn new(entry: Collider, exit_transform: Transform4D) -> Self {
+        Self { entry, exit_transform }
+    }
+
+    /// Check if a point is inside the entry region
+    pub fn contains(&self, point: Vec4) -> bool {
+        match &self.entry {
+            Collider::Sphere(sphere) => sphere.contains(point),
+            Collider::AABB(aabb) => aabb.contains(point),
+            Collider::Plane(plane) => plane.signed_distance(point) < 0.0,
+            Collider::Capsule(capsule) => capsule.contains(point),
+        }
+    }
+
+    /// Map a position through the portal
+    ///
+    /// The offset from the entry center is transformed, so a body entering
+    /// off-center leaves off-center by the same (rotated) amount.
+    pub fn transform_position(&self, position: Vec4) -> Vec4 {
+        self.exit_transform.transform_point(position - self.entry.center())
+    }
+
+    /// Map a velocity through the portal
+    pub fn transform_velocity(&self, velocity: Vec4) -> Vec4 {
+        self.exit_transform.transform_direction(velocity)
+    }
+}
+
+/// A body passing through a portal during a step
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TeleportEvent {
+    /// The teleported body
+    pub body: BodyKey,
+    /// Index of the portal (as returned by [`PhysicsWorld::add_portal`](crate::PhysicsWorld::add_portal))
+    pub portal: usize,
+    /// Body position before teleporting
+    pub from: Vec4,
+    /// Body position after teleporting
+    pub to: Vec4,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::{Plane4D, Sphere4D, AABB4D};
+    use rust4d_math::{RotationPlane, Rotor4};
+    use std::f32::consts::FRAC_PI_2;
+
+    fn approx(a: Vec4, b: Vec4) -> bool {
+        (a - b).length() < 1e-5
+    }
+
+    #[test]
+    fn test_portal_contains() {
+        let sphere = Portal::new(Collider::Sphere(Sphere4D::new(Vec4::ZERO, 1.0)), Transform4D::identity());
+        assert!(sphere.contains(Vec4::new(0.5, 0.0, 0.0, 0.0)));
+        assert!(!sphere.contains(Vec4::new(2.0, 0.0, 0.0, 0.0)));
+
+        let aabb = Portal::new(Collider::AABB(AABB4D::unit()), Transform4D::identity());
+        assert!(aabb.contains(Vec4::ZERO));
+
+        // Behind a plane facing +X
+        let plane = Portal::new(Collider::Plane(Plane4D::new(Vec4::X, 3.0)), Transform4D::identity());
+        assert!(plane.contains(Vec4::new(2.0, 0.0, 0.0, 0.0)));
+        assert!(!plane.contains(Vec4::new(4.0, 0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_portal_transform_keeps_offset() {
+        let entry = Collider::Sphere(Sphere4D::new(Vec4::new(10.0, 0.0, 0.0, 0.0), 1.0));
+        let rotation = Rotor4::from_plane_angle(RotationPlane::XW, FRAC_PI_2);
+        let portal = Portal::new(entry, Transform4D::from_position_rotation(Vec4::new(0.0, 5.0, 0.0, 0.0), rotation));
+
+        // Center maps to the exit, offsets are rotated
+        assert!(approx(portal.transform_position(Vec4::new(10.0, 0.0, 0.0, 0.0)), Vec4::new(0.0, 5.0, 0.0, 0.0)));
+        let offset = portal.transform_position(Vec4::new(10.5, 0.0, 0.0, 0.0)) - Vec4::new(0.0, 5.0, 0.0, 0.0);
+        assert!(approx(offset, rotation.rotate(Vec4::new(0.5, 0.0, 0.0, 0.0))));
+        assert!(approx(portal.transform_velocity(Vec4::X), rotation.rotate(Vec4::X)));
+    }
+}
@@ -35,6 +35,53 @@ impl Sphere4D {
     }
 }
 
+/// A 4D capsule: a vertical segment along Y, swept by a sphere
+///
+/// The natural upright shape for a character: the rounded bottom slides over
+/// seams and small ledges where a box would catch, and it is taller than it
+/// is wide without needing a huge sphere.
+#[derive(Clone, Copy, Debug)]
+pub struct Capsule4D {
+    /// Midpoint of the segment
+    pub center: Vec4,
+    /// Half the length of the segment (0.0 makes it a sphere)
+    pub half_height: f32,
+    /// Distance from the segment to the surface
+    pub radius: f32,
+}
+
+impl Capsule4D {
+    /// Create a new capsule around a vertical segment
+    pub fn new(center: Vec4, half_height: f32, radius: f32) -> Self {
+        Self {
+            center,
+            half_height,
+            radius,
+        }
+    }
+
+    /// Bottom end of the segment (center of the lower cap)
+    pub fn bottom(&self) -> Vec4 {
+        self.center - Vec4::Y * self.half_height
+    }
+
+    /// Top end of the segment (center of the upper cap)
+    pub fn top(&self) -> Vec4 {
+        self.center + Vec4::Y * self.half_height
+    }
+
+    /// Get the closest point on the segment to a given point
+    pub fn closest_segment_point(&self, point: Vec4) -> Vec4 {
+        let y = point.y.clamp(self.center.y - self.half_height, self.center.y + self.half_height);
+        Vec4::new(self.center.x, y, self.center.z, self.center.w)
+    }
+
+    /// Check if a point is inside or on the capsule
+    pub fn contains(&self, point: Vec4) -> bool {
+        (point - self.closest_segment_point(point)).length_squared() <= self.radius * self.radius
+    }
+}
+
 /// A 4D axis-aligned bounding box
 #[derive(Clone, Copy, Debug)]
 pub struct AABB4D {
@@ -90,6 +137,18 @@ impl AABB4D {
             && point.w <= self.max.w
     }
 
+    /// Check if two AABBs overlap or touch
+    pub fn intersects(&self, other: &AABB4D) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+            && self.min.z <= other.max.z
+            && other.min.z <= self.max.z
+            && self.min.w <= other.max.w
+            && other.min.w <= self.max.w
+    }
+
     /// Get the closest point inside or on the AABB to a given point
     pub fn closest_point(&self, point: Vec4) -> Vec4 {
         point.clamp_components(self.min, self.max)
@@ -169,6 +228,7 @@ pub enum Collider {
     Sphere(Sphere4D),
     AABB(AABB4D),
     Plane(Plane4D),
+    Capsule(Capsule4D),
 }
 
 impl Collider {
@@ -180,6 +240,42 @@ impl Collider {
             Collider::Sphere(s) => s.center,
             Collider::AABB(b) => b.center(),
             Collider::Plane(p) => p.normal * p.distance,
+            Collider::Capsule(c) => c.center,
+        }
+    }
+
+    /// Get the collider's axis-aligned bounding box
+    ///
+    /// Returns `None` for planes, which are unbounded.
+    pub fn bounds(&self) -> Option<AABB4D> {
+        match self {
+            Collider::Sphere(s) => {
+                let r = Vec4::new(s.radius, s.radius, s.radius, s.radius);
+                Some(AABB4D::new(s.center - r, s.center + r))
+            }
+            Collider::AABB(b) => Some(*b),
+            Collider::Plane(_) => None,
+            Collider::Capsule(c) => {
+                let r = Vec4::new(c.radius, c.half_height + c.radius, c.radius, c.radius);
+                Some(AABB4D::new(c.center - r, c.center + r))
+            }
+        }
+    }
+
+    /// Grow the collider by a radius in every direction
+    ///
+    /// A sphere of that radius touches the collider exactly when its center
+    /// is inside the grown one, so sweeps can be done as ray casts. Boxes
+    /// keep square corners, which slightly overestimates them near edges.
+    pub fn inflated(&self, radius: f32) -> Self {
+        match self {
+            Collider::Sphere(s) => Collider::Sphere(Sphere4D::new(s.center, s.radius + radius)),
+            Collider::AABB(b) => {
+                let r = Vec4::new(radius, radius, radius, radius);
+                Collider::AABB(AABB4D::new(b.min - r, b.max + r))
+            }
+            Collider::Plane(p) => Collider::Plane(Plane4D::new(p.normal, p.distance + radius)),
+            Collider::Capsule(c) => Collider::Capsule(Capsule4D::new(c.center, c.half_height, c.radius + radius)),
         }
     }
 
@@ -195,6 +291,7 @@ impl Collider {
                 let new_distance = p.distance + p.normal.dot(delta);
                 Collider::Plane(Plane4D::new(p.normal, new_distance))
             }
+            Collider::Capsule(c) => Collider::Capsule(Capsule4D::new(c.center + delta, c.half_height, c.radius)),
         }
     }
 }
@@ -220,6 +317,37 @@ mod tests {
         assert_eq!(aabb.center(), Vec4::new(1.0, 2.0, 3.0, 4.0));
     }
 
+    #[test]
+    fn test_capsule_contains_and_bounds() {
+        let capsule = Capsule4D::new(Vec4::new(0.0, 1.0, 0.0, 0.0), 0.5, 0.5);
+        assert_eq!(capsule.bottom(), Vec4::new(0.0, 0.5, 0.0, 0.0));
+        assert_eq!(capsule.top(), Vec4::new(0.0, 1.5, 0.0, 0.0));
+
+        // Tip of the lower cap, the side of the segment, and just past the upper cap
+        assert!(capsule.contains(Vec4::ZERO));
+        assert!(capsule.contains(Vec4::new(0.0, 1.2, 0.0, 0.5)));
+        assert!(!capsule.contains(Vec4::new(0.0, 2.1, 0.0, 0.0)));
+
+        let bounds = Collider::Capsule(capsule).bounds().unwrap();
+        assert_eq!(bounds.min, Vec4::new(-0.5, 0.0, -0.5, -0.5));
+        assert_eq!(bounds.max, Vec4::new(0.5, 2.0, 0.5, 0.5));
+    }
+
+    #[test]
+    fn test_collider_inflated() {
+        let sphere = Collider::Sphere(Sphere4D::new(Vec4::ZERO, 1.0)).inflated(0.5);
+        assert!(matches!(sphere, Collider::Sphere(s) if s.radius == 1.5));
+
+        let aabb = Collider::AABB(AABB4D::unit()).inflated(0.5);
+        let bounds = aabb.bounds().unwrap();
+        assert_eq!(bounds.max - bounds.min, AABB4D::unit().max - AABB4D::unit().min + Vec4::new(1.0, 1.0, 1.0, 1.0));
+
+        let Collider::Plane(floor) = Collider::Plane(Plane4D::floor(2.0)).inflated(0.5) else {
+            panic!("plane stays a plane");
+        };
+        assert_eq!(floor.signed_distance(Vec4::new(0.0, 2.5, 0.0, 0.0)), 0.0);
+    }
+
     #[test]
     fn test_aabb_contains() {
         let aabb = AABB4D::new(Vec4::ZERO, Vec4::new(1.0, 1.0, 1.0, 1.0));
@@ -268,4 +396,17 @@ mod tests {
         assert!(floor.is_above(Vec4::new(0.0, 1.0, 0.0, 0.0)));
         assert!(!floor.is_above(Vec4::new(0.0, -1.0, 0.0, 0.0)));
     }
+
+    #[test]
+    fn test_collider_bounds() {
+        let sphere = Collider::Sphere(Sphere4D::new(Vec4::new(1.0, 2.0, 3.0, 4.0), 0.5));
+        let b = sphere.bounds().unwrap();
+        assert_eq!(b.min, Vec4::new(0.5, 1.5, 2.5, 3.5));
+        assert_eq!(b.max, Vec4::new(1.5, 2.5, 3.5, 4.5));
+
+        let aabb = AABB4D::from_center_half_extents(Vec4::ZERO, Vec4::new(1.0, 2.0, 3.0, 4.0));
+        assert_eq!(Collider::AABB(aabb).bounds().unwrap().max, Vec4::new(1.0, 2.0, 3.0, 4.0));
+
+        assert!(Collider::Plane(Plane4D::floor(0.0)).bounds().is_none());
+    }
 }
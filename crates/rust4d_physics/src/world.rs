@@ -1,45 +1,271 @@
 //! Physics world and simulation
 
-use crate::body::{BodyKey, RigidBody4D, StaticCollider};
-use crate::collision::{aabb_vs_aabb, aabb_vs_plane, sphere_vs_aabb, sphere_vs_plane, Contact};
-use crate::shapes::{Collider, Sphere4D};
-use rust4d_math::Vec4;
-use slotmap::SlotMap;
+use crate::body::{BodyKey, BodyType, RigidBody4D, StaticCollider, PLAYER_PUSH_PRIORITY};
+use crate::collision::{
+    aabb_vs_aabb, aabb_vs_plane, capsule_vs_aabb, capsule_vs_capsule, capsule_vs_plane, capsule_vs_sphere, ray_vs_collider,
+    sphere_vs_aabb, sphere_vs_plane, CollisionEvent, CollisionFilter, Contact,
+};
+use crate::portal::{Portal, TeleportEvent, PORTAL_COOLDOWN};
+use crate::shapes::{Collider, Sphere4D, AABB4D};
+use rust4d_math::{Rotor4, SpatialGrid4D, Vec4};
+use slotmap::{new_key_type, SecondaryMap, SlotMap};
 
 use serde::{Serialize, Deserialize};
 
 /// Configuration for the physics simulation
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PhysicsConfig {
-    /// Gravity acceleration (applied to Y-axis, negative = down)
-    pub gravity: f32,
+    /// Gravity acceleration as a 4D vector (`(0, -20, 0, 0)` by default)
+    ///
+    /// Also read from the old scalar `gravity` field, as an acceleration along Y.
+    #[serde(alias = "gravity", deserialize_with = "deserialize_gravity")]
+    pub gravity_vector: Vec4,
     /// Jump velocity for player
     pub jump_velocity: f32,
+    /// Run in determinism mode (see [`PhysicsWorld::step`])
+    #[serde(default)]
+    pub deterministic: bool,
+    /// Cell edge length of the body-body broadphase grid (must be positive)
+    #[serde(default = "default_body_grid_cell_size", deserialize_with = "deserialize_cell_size")]
+    pub body_grid_cell_size: f32,
+    /// Test every body pair instead of using the broadphase grid (for debugging)
+    #[serde(default)]
+    pub brute_force_broadphase: bool,
+    /// Substeps each [`PhysicsWorld::step`] is split into (0 is treated as 1)
+    #[serde(default = "default_substeps")]
+    pub substeps: u32,
+    /// Let resting dynamic bodies fall asleep (see [`RigidBody4D::is_sleeping`])
+    #[serde(default = "default_sleep_enabled")]
+    pub sleep_enabled: bool,
+    /// Speed below which a body counts as resting
+    #[serde(default = "default_sleep_threshold")]
+    pub sleep_threshold: f32,
+    /// Seconds a body has to rest before falling asleep
+    #[serde(default = "default_sleep_time")]
+    pub sleep_time: f32,
+    /// Steps over which gravity ramps up from zero after the world is created
+    ///
+    /// Above zero, the first step also skips collision velocity response, so
+    /// bodies placed slightly inside something are pushed out without bouncing.
+    #[serde(default)]
+    pub warmup_steps: u32,
+    /// Impulse per unit of approach speed the player gives dynamic bodies it walks into
+    ///
+    /// A body with this mass is shoved along at the player's speed; lighter
+    /// bodies fly off faster, heavier ones less. Zero leaves pushing to
+    /// position correction alone.
+    #[serde(default)]
+    pub player_push_strength: f32,
+}
+
+fn default_body_grid_cell_size() -> f32 {
+    DEFAULT_BODY_GRID_CELL_SIZE
+}
+
+/// Reject grid cell sizes that are not positive
+fn deserialize_cell_size<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+    let cell_size = f32::deserialize(deserializer)?;
+    if cell_size > 0.0 {
+        Ok(cell_size)
+    } else {
+        Err(serde::de::Error::custom(format!("body_grid_cell_size must be positive, got {cell_size}")))
+    }
+}
+
+/// Read gravity as a 4D vector, or as a scalar along Y as configs did before it was a vector
+fn deserialize_gravity<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Vec4, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Gravity {
+        Vector(Vec4),
+        Y(f32),
+    }
+    Ok(match Gravity::deserialize(deserializer)? {
+        Gravity::Vector(vector) => vector,
+        Gravity::Y(y) => Vec4::new(0.0, y, 0.0, 0.0),
+    })
+}
+
+fn default_substeps() -> u32 {
+    1
+}
+
+fn default_sleep_enabled() -> bool {
+    true
+}
+
+fn default_sleep_threshold() -> f32 {
+    0.1
+}
+
+fn default_sleep_time() -> f32 {
+    0.5
 }
 
 impl Default for PhysicsConfig {
     fn default() -> Self {
-        Self {
-            gravity: -20.0,
-            jump_velocity: 8.0,
-        }
+        Self::new(-20.0)
     }
 }
 
 impl PhysicsConfig {
-    /// Create a new physics config with the given gravity
+    /// Create a new physics config with the given gravity along Y (negative = down)
     pub fn new(gravity: f32) -> Self {
         Self {
-            gravity,
+            gravity_vector: Vec4::new(0.0, gravity, 0.0, 0.0),
             jump_velocity: 8.0,
+            deterministic: false,
+            body_grid_cell_size: DEFAULT_BODY_GRID_CELL_SIZE,
+            brute_force_broadphase: false,
+            substeps: 1,
+            sleep_enabled: true,
+            sleep_threshold: 0.1,
+            sleep_time: 0.5,
+            warmup_steps: 0,
+            player_push_strength: 0.0,
         }
     }
 
+    /// Set gravity to an arbitrary 4D acceleration
+    pub fn with_gravity_vector(mut self, gravity_vector: Vec4) -> Self {
+        self.gravity_vector = gravity_vector;
+        self
+    }
+
+    /// The "up" direction: opposite to gravity, or +Y when there is no gravity
+    pub fn up(&self) -> Vec4 {
+        let up = -self.gravity_vector.normalized();
+        if up == Vec4::ZERO { Vec4::Y } else { up }
+    }
+
     /// Create a physics config with both gravity and jump velocity
     pub fn with_jump_velocity(mut self, jump_velocity: f32) -> Self {
         self.jump_velocity = jump_velocity;
         self
     }
+
+    /// Enable or disable determinism mode
+    pub fn with_deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    /// Set the cell edge length of the body-body broadphase grid
+    ///
+    /// # Panics
+    /// Panics if `cell_size` is not positive.
+    pub fn with_body_grid_cell_size(mut self, cell_size: f32) -> Self {
+        assert!(cell_size > 0.0, "body_grid_cell_size must be positive");
+        self.body_grid_cell_size = cell_size;
+        self
+    }
+
+    /// Test every body pair instead of using the broadphase grid
+    pub fn with_brute_force_broadphase(mut self, brute_force: bool) -> Self {
+        self.brute_force_broadphase = brute_force;
+        self
+    }
+
+    /// Set how many substeps each step is split into
+    pub fn with_substeps(mut self, substeps: u32) -> Self {
+        self.substeps = substeps;
+        self
+    }
+
+    /// Enable or disable sleeping
+    pub fn with_sleeping(mut self, enabled: bool) -> Self {
+        self.sleep_enabled = enabled;
+        self
+    }
+
+    /// Set the resting speed and how long a body has to rest before sleeping
+    pub fn with_sleep_threshold(mut self, threshold: f32, time: f32) -> Self {
+        self.sleep_threshold = threshold;
+        self.sleep_time = time;
+        self
+    }
+
+    /// Ramp gravity up over the first `steps` steps (see [`warmup_steps`](Self::warmup_steps))
+    pub fn with_warmup_steps(mut self, steps: u32) -> Self {
+        self.warmup_steps = steps;
+        self
+    }
+
+    /// Set how hard the player shoves dynamic bodies (see [`player_push_strength`](Self::player_push_strength))
+    pub fn with_player_push_strength(mut self, strength: f32) -> Self {
+        self.player_push_strength = strength;
+        self
+    }
+}
+
+/// Cell edge length of the grid over static colliders
+const STATIC_GRID_CELL_SIZE: f32 = 8.0;
+
+/// Default cell edge length of the grid over bodies
+pub const DEFAULT_BODY_GRID_CELL_SIZE: f32 = 4.0;
+
+/// Colliders spanning more grid cells than this skip the grid and are tested against everything
+const MAX_GRID_CELLS: u64 = 4096;
+
+/// Threshold for considering a surface as "ground" (normal pointing mostly up)
+const GROUND_NORMAL_THRESHOLD: f32 = 0.7;
+
+/// Heights along `up` where the line through `position` enters and leaves `aabb`
+///
+/// Heights are measured like `position.dot(up)`; None if the line misses the box.
+fn heights_through_box(aabb: &AABB4D, position: Vec4, up: Vec4) -> Option<(f32, f32)> {
+    let height = position.dot(up);
+    let [p, u, min, max] = [position, up, aabb.min, aabb.max].map(|v| [v.x, v.y, v.z, v.w]);
+    let (mut enter, mut exit) = (f32::NEG_INFINITY, f32::INFINITY);
+    for axis in 0..4 {
+        if u[axis].abs() < 1e-6 {
+            if p[axis] < min[axis] || p[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+        let a = height + (min[axis] - p[axis]) / u[axis];
+        let b = height + (max[axis] - p[axis]) / u[axis];
+        enter = enter.max(a.min(b));
+        exit = exit.min(a.max(b));
+    }
+    (enter <= exit).then_some((enter, exit))
+}
+
+/// How far past a sweep hit the player is moved, so the surface registers as a contact
+const SWEEP_SKIN: f32 = 1e-3;
+
+/// Number of times the player can slide along a surface in one step
+const MAX_SWEEP_SLIDES: usize = 3;
+
+/// How far apart a sleeping body and a removed or toggled collider can be and still count as touching
+const WAKE_MARGIN: f32 = 0.01;
+
+/// Slack on overlap query radii so shapes exactly touching the query count
+const OVERLAP_TOLERANCE: f32 = 1e-5;
+
+/// Grid that determinism mode snaps positions and velocities to (16.16 fixed point)
+pub const DETERMINISM_QUANTUM: f32 = 1.0 / 65536.0;
+
+/// Handle to a static collider returned by [`PhysicsWorld::add_static_collider`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StaticColliderId(usize);
+
+new_key_type! {
+    /// Handle to a body registered with [`PhysicsWorld::register_controllable`]
+    ///
+    /// Generational, so a handle to a controllable whose body was removed
+    /// never refers to a later registration.
+    pub struct ControllableHandle;
+}
+
+/// A body moved by input (a player or player-like agent)
+#[derive(Clone, Copy, Debug)]
+struct Controllable {
+    /// The controlled body
+    body: BodyKey,
+    /// Vertical velocity a jump sets
+    jump_velocity: f32,
 }
 
 /// The physics world containing all rigid bodies
@@ -48,12 +274,38 @@ pub struct PhysicsWorld {
     bodies: SlotMap<BodyKey, RigidBody4D>,
     /// Static colliders (floors, walls, platforms)
     static_colliders: Vec<StaticCollider>,
+    /// Broadphase grid over bounded static colliders (by index)
+    static_grid: SpatialGrid4D<usize>,
+    /// Indices of static colliders every body is tested against (planes, huge boxes)
+    unbounded_statics: Vec<usize>,
+    /// Body-vs-static narrowphase tests run in the last step
+    static_tests: usize,
+    /// Broadphase grid over bodies (by index into the step order), rebuilt every step
+    body_grid: SpatialGrid4D<usize>,
+    /// Body-vs-body narrowphase tests run in the last step
+    body_tests: usize,
     /// Physics configuration
     pub config: PhysicsConfig,
-    /// The player body key (if a player has been registered)
-    player_body: Option<BodyKey>,
-    /// Jump velocity for the player
-    player_jump_velocity: f32,
+    /// Input-driven bodies
+    controllables: SlotMap<ControllableHandle, Controllable>,
+    /// The controllable that is the player, if any
+    player: Option<ControllableHandle>,
+    /// Jump velocity given to newly registered controllables
+    default_jump_velocity: f32,
+    /// Body keys in the order they were added
+    insertion_order: Vec<BodyKey>,
+    /// Teleporting portals
+    portals: Vec<Portal>,
+    /// Seconds left before a recently teleported body can use a portal again
+    portal_cooldowns: SecondaryMap<BodyKey, f32>,
+    /// Bodies teleported during the last step
+    teleport_events: Vec<TeleportEvent>,
+    /// Contacts found during the last step
+    collision_events: Vec<CollisionEvent>,
+    /// Steps taken so far (saturating), for the gravity warm-up
+    steps_taken: u32,
+    /// Whether the current step leaves velocities alone when resolving contacts
+    skip_velocity_response: bool,
 }
 
 impl PhysicsWorld {
@@ -68,15 +320,79 @@ impl PhysicsWorld {
         Self {
             bodies: SlotMap::with_key(),
             static_colliders: Vec::new(),
+            static_grid: SpatialGrid4D::new(STATIC_GRID_CELL_SIZE),
+            unbounded_statics: Vec::new(),
+            static_tests: 0,
+            body_grid: SpatialGrid4D::new(config.body_grid_cell_size),
+            body_tests: 0,
             config,
-            player_body: None,
-            player_jump_velocity: jump_velocity,
+            controllables: SlotMap::with_key(),
+            player: None,
+            default_jump_velocity: jump_velocity,
+            insertion_order: Vec::new(),
+            portals: Vec::new(),
+            portal_cooldowns: SecondaryMap::new(),
+            teleport_events: Vec::new(),
+            collision_events: Vec::new(),
+            steps_taken: 0,
+            skip_velocity_response: false,
         }
     }
 
     /// Add a static collider to the world
-    pub fn add_static_collider(&mut self, collider: StaticCollider) {
+    ///
+    /// Bounded colliders are registered in a spatial grid so each body only
+    /// narrowphase-tests the statics near it. The returned id stays valid for
+    /// the life of the world.
+    pub fn add_static_collider(&mut self, collider: StaticCollider) -> StaticColliderId {
+        let index = self.static_colliders.len();
+        match collider.collider.bounds() {
+            Some(bounds) if self.grid_cells_spanned(&bounds) <= MAX_GRID_CELLS => {
+                self.static_grid.insert_aabb(index, bounds.min, bounds.max);
+            }
+            _ => self.unbounded_statics.push(index),
+        }
         self.static_colliders.push(collider);
+        StaticColliderId(index)
+    }
+
+    /// Get a static collider by id
+    pub fn static_collider(&self, id: StaticColliderId) -> Option<&StaticCollider> {
+        self.static_colliders.get(id.0)
+    }
+
+    /// Turn a static collider on or off
+    ///
+    /// Disabled colliders are skipped by collision, sweeps and
+    /// [`lift_above_floor`](Self::lift_above_floor) until re-enabled, so a door
+    /// or breakable wall can be toggled without removing it. Toggling wakes
+    /// the sleeping bodies touching the collider, so a crate resting on a
+    /// trapdoor falls when it opens.
+    pub fn set_static_enabled(&mut self, id: StaticColliderId, enabled: bool) {
+        let Some(collider) = self.static_colliders.get_mut(id.0) else {
+            return;
+        };
+        if collider.enabled != enabled {
+            collider.enabled = enabled;
+            let shape = collider.collider;
+            self.wake_bodies_touching(&shape);
+        }
+    }
+
+    /// Wake the sleeping bodies whose bounds touch a collider's
+    ///
+    /// Unbounded colliders (planes) touch every body.
+    fn wake_bodies_touching(&mut self, collider: &Collider) {
+        let reach = collider.inflated(WAKE_MARGIN).bounds();
+        for body in self.bodies.values_mut().filter(|body| body.is_sleeping()) {
+            let touching = match (reach, body.collider.bounds()) {
+                (Some(reach), Some(bounds)) => reach.intersects(&bounds),
+                _ => true,
+            };
+            if touching {
+                body.wake();
+            }
+        }
     }
 
     /// Get immutable access to static colliders
@@ -84,14 +400,51 @@ impl PhysicsWorld {
         &self.static_colliders
     }
 
+    /// Add a portal and return its index
+    pub fn add_portal(&mut self, portal: Portal) -> usize {
+        self.portals.push(portal);
+        self.portals.len() - 1
+    }
+
+    /// Get the registered portals
+    pub fn portals(&self) -> &[Portal] {
+        &self.portals
+    }
+
+    /// Bodies that passed through a portal during the last step
+    pub fn teleport_events(&self) -> &[TeleportEvent] {
+        &self.teleport_events
+    }
+
+    /// Contacts found during the last step, including trigger overlaps
+    ///
+    /// Static collider contacts come first (in body order), then body pairs.
+    pub fn collision_events(&self) -> &[CollisionEvent] {
+        &self.collision_events
+    }
+
     /// Add a body to the world and return its key
     pub fn add_body(&mut self, body: RigidBody4D) -> BodyKey {
-        self.bodies.insert(body)
+        let key = self.bodies.insert(body);
+        self.insertion_order.push(key);
+        key
     }
 
     /// Remove a body from the world and return it
+    ///
+    /// A controllable registered for the body is unregistered with it, and
+    /// if it was the player the world no longer has one. Sleeping bodies
+    /// touching it are woken, so a crate stacked on a removed one falls.
     pub fn remove_body(&mut self, key: BodyKey) -> Option<RigidBody4D> {
-        self.bodies.remove(key)
+        let body = self.bodies.remove(key)?;
+        self.wake_bodies_touching(&body.collider);
+        self.controllables.retain(|_, c| c.body != key);
+        if self.player.is_some_and(|handle| !self.controllables.contains_key(handle)) {
+            self.player = None;
+        }
+        self.insertion_order.retain(|&k| k != key);
+        self.portal_cooldowns.remove(key);
+        Some(body)
     }
 
     /// Get an immutable reference to a body by key
@@ -104,6 +457,16 @@ impl PhysicsWorld {
         self.bodies.get_mut(key)
     }
 
+    /// Get the number of body-vs-static narrowphase tests run in the last step
+    pub fn static_collision_tests(&self) -> usize {
+        self.static_tests
+    }
+
+    /// Get the number of body-vs-body narrowphase tests run in the last step
+    pub fn body_collision_tests(&self) -> usize {
+        self.body_tests
+    }
+
     /// Get the number of bodies in the world
     pub fn body_count(&self) -> usize {
         self.bodies.len()
@@ -114,34 +477,375 @@ impl PhysicsWorld {
         self.bodies.keys()
     }
 
+    // ====== Queries ======
+
+    /// Find the nearest body hit by a ray
+    ///
+    /// Returns the body, the distance along the ray and the surface normal at
+    /// the hit. Static colliders are not tested. See
+    /// [`raycast_filtered`](Self::raycast_filtered) to skip bodies by layer or
+    /// to ignore the player.
+    pub fn raycast(&self, origin: Vec4, dir: Vec4, max_dist: f32) -> Option<(BodyKey, f32, Vec4)> {
+        self.raycast_filtered(origin, dir, max_dist, None, false)
+    }
+
+    /// Find the nearest body hit by a ray, skipping some bodies
+    ///
+    /// With a `filter`, only bodies whose filter collides with it are tested.
+    /// With `ignore_player`, the player body is skipped, so a ray fired from
+    /// the camera doesn't hit the player's own collider.
+    pub fn raycast_filtered(
+        &self,
+        origin: Vec4,
+        dir: Vec4,
+        max_dist: f32,
+        filter: Option<&CollisionFilter>,
+        ignore_player: bool,
+    ) -> Option<(BodyKey, f32, Vec4)> {
+        let dir = dir.normalized();
+        if dir == Vec4::ZERO {
+            return None;
+        }
+
+        let mut nearest: Option<(BodyKey, f32, Vec4)> = None;
+        for (key, body) in &self.bodies {
+            if ignore_player && self.player_key() == Some(key) {
+                continue;
+            }
+            if filter.is_some_and(|f| !f.collides_with(&body.filter)) {
+                continue;
+            }
+            if let Some((dist, normal)) = ray_vs_collider(origin, dir, &body.collider) {
+                let closest = nearest.map_or(max_dist, |(_, d, _)| d);
+                if dist <= closest {
+                    nearest = Some((key, dist, normal));
+                }
+            }
+        }
+        nearest
+    }
+
+    /// Sweep a sphere from `start` to `end` and find the earliest hit
+    ///
+    /// Tests static colliders and bodies whose filters collide with `filter`,
+    /// and returns the fraction of the way to `end` at which the sphere first
+    /// touches one, or `None` if the path is clear. Shapes the sphere already
+    /// overlaps at `start` are ignored, so a body resting on the floor can
+    /// still move along it. Boxes are swept as if their corners were square.
+    pub fn sweep_sphere(&self, start: Vec4, end: Vec4, radius: f32, filter: CollisionFilter) -> Option<f32> {
+        self.sweep(start, end, radius, &filter, None, true).map(|(fraction, _)| fraction)
+    }
+
+    /// Find every body whose collider intersects a sphere
+    ///
+    /// Only bodies whose filters collide with `filter` are returned, in
+    /// iteration order. Bodies just touching the sphere count as inside.
+    pub fn overlap_sphere(&self, center: Vec4, radius: f32, filter: CollisionFilter) -> Vec<BodyKey> {
+        let query = Sphere4D::new(center, radius + OVERLAP_TOLERANCE);
+        self.bodies
+            .iter()
+            .filter(|(_, body)| filter.collides_with(&body.filter))
+            .filter(|(_, body)| match &body.collider {
+                Collider::Sphere(sphere) => {
+                    // Unlike the contact helpers, coincident centers still overlap
+                    let reach = sphere.radius + query.radius;
+                    (sphere.center - query.center).length_squared() < reach * reach
+                }
+                Collider::AABB(aabb) => sphere_vs_aabb(&query, aabb).is_some(),
+                Collider::Plane(plane) => sphere_vs_plane(&query, plane).is_some(),
+                Collider::Capsule(capsule) => capsule_vs_sphere(capsule, &query).is_some(),
+            })
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    /// Sweep a sphere, returning the hit fraction and the surface normal
+    ///
+    /// `ignore` is skipped (the body being moved). Without `movable_bodies`,
+    /// only static colliders and static or frozen bodies are tested.
+    fn sweep(
+        &self,
+        start: Vec4,
+        end: Vec4,
+        radius: f32,
+        filter: &CollisionFilter,
+        ignore: Option<BodyKey>,
+        movable_bodies: bool,
+    ) -> Option<(f32, Vec4)> {
+        let delta = end - start;
+        let length = delta.length();
+        if length < 1e-8 {
+            return None;
+        }
+        let dir = delta * (1.0 / length);
+
+        let mut nearest: Option<(f32, Vec4)> = None;
+        let mut test = |collider: &Collider| {
+            if let Some((t, normal)) = ray_vs_collider(start, dir, &collider.inflated(radius)) {
+                // t == 0 means the sphere already overlaps this shape
+                if t > 0.0 && t <= length && nearest.is_none_or(|(best, _)| t / length < best) {
+                    nearest = Some((t / length, normal));
+                }
+            }
+        };
+
+        // Controllables skip bounded floors they aren't over, as in collision resolution
+        let is_player = ignore.is_some_and(|key| self.is_controllable(key));
+        let r = Vec4::new(radius, radius, radius, radius);
+        let swept = Collider::AABB(AABB4D::new(start.min_components(end) - r, start.max_components(end) + r));
+        for index in self.nearby_statics(&swept) {
+            let static_col = &self.static_colliders[index];
+            if !filter.collides_with(&static_col.filter) {
+                continue;
+            }
+            if is_player && matches!(static_col.collider, Collider::AABB(_)) && !static_col.is_position_over(start) {
+                continue;
+            }
+            test(&static_col.collider);
+        }
+
+        for (key, body) in &self.bodies {
+            if Some(key) == ignore || !filter.collides_with(&body.filter) {
+                continue;
+            }
+            if !movable_bodies && !body.is_static() && !body.is_frozen() {
+                continue;
+            }
+            test(&body.collider);
+        }
+        nearest
+    }
+
+    /// Lift a sphere that is sunk into a floor up to rest on top of it
+    ///
+    /// "Up" is against gravity (see [`PhysicsConfig::up`]). A floor is a box
+    /// (AABB) that the line through `position` along up passes through with
+    /// its bottom below `position`, or a plane facing up. If the sphere at
+    /// `position` dips below the top of any floor, it is raised along up to
+    /// sit `clearance` above the highest one; otherwise `position` is
+    /// returned unchanged. Used to fix up player spawns placed inside the
+    /// ground.
+    pub fn lift_above_floor(&self, position: Vec4, radius: f32, clearance: f32) -> Vec4 {
+        let up = self.config.up();
+        let mut position = position;
+        // Each lift clears one floor for good, so this ends within one pass per collider
+        for _ in 0..=self.static_colliders.len() {
+            let height = position.dot(up);
+            let mut top: Option<f32> = None;
+            for static_col in self.static_colliders.iter().filter(|c| c.enabled) {
+                let surface = match &static_col.collider {
+                    Collider::AABB(aabb) => match heights_through_box(aabb, position, up) {
+                        Some((bottom, top)) if bottom <= height => top,
+                        _ => continue,
+                    },
+                    Collider::Plane(plane) if plane.normal.dot(up) > GROUND_NORMAL_THRESHOLD => {
+                        height + (plane.distance - plane.normal.dot(position)) / plane.normal.dot(up)
+                    }
+                    _ => continue,
+                };
+                if height - radius < surface && top.is_none_or(|t| surface > t) {
+                    top = Some(surface);
+                }
+            }
+
+            match top {
+                Some(surface) => position = position - up * height + up * (surface + radius + clearance),
+                None => break,
+            }
+        }
+        position
+    }
+
+    // ====== Controllable Bodies ======
+
+    /// Register a body as controllable and return its handle
+    ///
+    /// Controllable bodies get player-like handling: gravity even when
+    /// kinematic, grounded detection, swept movement and edge falling, plus
+    /// their own jump velocity (initially [`PhysicsConfig::jump_velocity`]).
+    /// Their push priority is raised to at least [`PLAYER_PUSH_PRIORITY`],
+    /// and they never fall asleep; a sleeping body is woken. Registering a
+    /// body twice returns the existing handle. Removing the body unregisters it.
+    pub fn register_controllable(&mut self, key: BodyKey) -> ControllableHandle {
+        if let Some(handle) = self.controllable_handle(key) {
+            return handle;
+        }
+        if let Some(body) = self.bodies.get_mut(key) {
+            body.push_priority = body.push_priority.max(PLAYER_PUSH_PRIORITY);
+            body.wake();
+        }
+        self.controllables.insert(Controllable {
+            body: key,
+            jump_velocity: self.default_jump_velocity,
+        })
+    }
+
+    /// Get the body behind a controllable handle
+    pub fn controllable_key(&self, handle: ControllableHandle) -> Option<BodyKey> {
+        self.controllables.get(handle).map(|c| c.body)
+    }
+
+    /// Get the controllable handle registered for a body, if any
+    pub fn controllable_handle(&self, key: BodyKey) -> Option<ControllableHandle> {
+        self.controllables.iter().find(|(_, c)| c.body == key).map(|(handle, _)| handle)
+    }
+
+    /// Get the number of registered controllables
+    pub fn controllable_count(&self) -> usize {
+        self.controllables.len()
+    }
+
+    /// Set the jump velocity of a controllable
+    pub fn set_jump_velocity(&mut self, handle: ControllableHandle, velocity: f32) {
+        if let Some(c) = self.controllables.get_mut(handle) {
+            c.jump_velocity = velocity;
+        }
+    }
+
+    /// Check if a controllable is currently grounded
+    pub fn is_grounded(&self, handle: ControllableHandle) -> bool {
+        self.controllable_key(handle)
+            .and_then(|key| self.bodies.get(key))
+            .is_some_and(|body| body.grounded)
+    }
+
+    /// Apply horizontal movement to a controllable (XZ plane + W for 4D)
+    ///
+    /// This sets the body's velocity on the XZ and W axes.
+    /// The Y component is controlled by gravity and jumping.
+    pub fn apply_movement(&mut self, handle: ControllableHandle, movement: Vec4) {
+        let Some(key) = self.controllable_key(handle) else { return };
+        if let Some(body) = self.bodies.get_mut(key) {
+            // Only set horizontal velocity - preserve Y for gravity/jumping
+            body.velocity.x = movement.x;
+            body.velocity.z = movement.z;
+            body.velocity.w = movement.w;
+        }
+    }
+
+    /// Attempt to make a controllable jump
+    ///
+    /// Only succeeds if the body is grounded. Sets the velocity against
+    /// gravity (see [`PhysicsConfig::up`]) to the controllable's jump velocity.
+    pub fn jump(&mut self, handle: ControllableHandle) -> bool {
+        let Some(&Controllable { body: key, jump_velocity, .. }) = self.controllables.get(handle) else {
+            return false;
+        };
+        if let Some(body) = self.bodies.get_mut(key) {
+            if body.grounded {
+                // Replace the velocity along up, keeping the rest
+                let up = self.config.up();
+                body.velocity += up * (jump_velocity - body.velocity.dot(up));
+                body.grounded = false;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Lock or unlock a controllable's W coordinate
+    ///
+    /// While locked, the body is held at the W it had when the lock was set:
+    /// its W velocity is zeroed every step, so W movement, gravity and pushes
+    /// along W have no effect and motion is effectively 3D. Locking an
+    /// already locked body keeps the W it is held at. This sets the body's
+    /// [`w_lock`](RigidBody4D::w_lock).
+    pub fn set_w_locked(&mut self, handle: ControllableHandle, locked: bool) {
+        let Some(body) = self.controllable_key(handle).and_then(|key| self.bodies.get_mut(key)) else {
+            return;
+        };
+        if !locked {
+            body.w_lock = None;
+        } else if body.w_lock.is_none() {
+            body.w_lock = Some(body.position.w);
+        }
+    }
+
+    /// Check if a controllable's W coordinate is locked
+    pub fn is_w_locked(&self, handle: ControllableHandle) -> bool {
+        self.controllable_key(handle)
+            .and_then(|key| self.bodies.get(key))
+            .is_some_and(|body| body.w_lock.is_some())
+    }
+
+    /// Check if a body is registered as controllable
+    fn is_controllable(&self, key: BodyKey) -> bool {
+        self.controllables.values().any(|c| c.body == key)
+    }
+
+    /// Put W-locked bodies back at their held W, at rest along W
+    fn hold_locked_w(&mut self) {
+        for body in self.bodies.values_mut() {
+            if let Some(w) = body.w_lock {
+                body.hold_w(w);
+            }
+        }
+    }
+
     // ====== Player Body Management ======
 
     /// Register a body as the player body
     ///
-    /// This enables player-specific features like jump and grounded detection.
-    /// The body should typically be kinematic (no gravity, user-controlled velocity).
+    /// The player is a controllable (see
+    /// [`register_controllable`](Self::register_controllable)). A body that
+    /// is already controllable becomes the player under its existing handle;
+    /// the previous player, if any, stays an ordinary controllable. Otherwise
+    /// the player's registration moves to the new body, keeping its handle,
+    /// jump velocity and W lock (now holding the new body's W). The body
+    /// should typically be kinematic (no gravity, user-controlled velocity).
     pub fn set_player_body(&mut self, key: BodyKey) {
-        self.player_body = Some(key);
+        if let Some(body) = self.bodies.get_mut(key) {
+            body.push_priority = body.push_priority.max(PLAYER_PUSH_PRIORITY);
+        }
+        if let Some(handle) = self.controllable_handle(key) {
+            self.player = Some(handle);
+            return;
+        }
+        match self.player.and_then(|handle| self.controllables.get_mut(handle)) {
+            Some(player) => {
+                let previous = std::mem::replace(&mut player.body, key);
+                let locked = self.bodies.get_mut(previous).and_then(|body| body.w_lock.take()).is_some();
+                if let Some(body) = self.bodies.get_mut(key).filter(|_| locked) {
+                    body.w_lock.get_or_insert(body.position.w);
+                }
+            }
+            None => {
+                self.player = Some(self.controllables.insert(Controllable {
+                    body: key,
+                    jump_velocity: self.default_jump_velocity,
+                }));
+            }
+        }
+    }
+
+    /// Get the player's controllable handle
+    pub fn player_handle(&self) -> Option<ControllableHandle> {
+        self.player
     }
 
     /// Set the jump velocity for the player
+    ///
+    /// Also used for a player registered afterwards.
     pub fn set_player_jump_velocity(&mut self, velocity: f32) {
-        self.player_jump_velocity = velocity;
+        match self.player.and_then(|handle| self.controllables.get_mut(handle)) {
+            Some(player) => player.jump_velocity = velocity,
+            None => self.default_jump_velocity = velocity,
+        }
     }
 
     /// Get the player body key
     pub fn player_key(&self) -> Option<BodyKey> {
-        self.player_body
+        self.player.and_then(|handle| self.controllable_key(handle))
     }
 
     /// Get an immutable reference to the player body
     pub fn player(&self) -> Option<&RigidBody4D> {
-        self.player_body.and_then(|key| self.bodies.get(key))
+        self.player_key().and_then(|key| self.bodies.get(key))
     }
 
     /// Get a mutable reference to the player body
     pub fn player_mut(&mut self) -> Option<&mut RigidBody4D> {
-        self.player_body.and_then(|key| self.bodies.get_mut(key))
+        self.player_key().and_then(|key| self.bodies.get_mut(key))
     }
 
     /// Get the player's current position
@@ -151,78 +855,339 @@ impl PhysicsWorld {
 
     /// Check if the player is currently grounded
     pub fn player_is_grounded(&self) -> bool {
-        self.player().map(|body| body.grounded).unwrap_or(false)
+        self.player.is_some_and(|handle| self.is_grounded(handle))
     }
 
-    /// Apply horizontal movement to the player (XZ plane + W for 4D)
-    ///
-    /// This sets the player's velocity on the XZ and W axes.
-    /// The Y component is controlled by gravity and jumping.
+    /// Apply horizontal movement to the player (see [`apply_movement`](Self::apply_movement))
     pub fn apply_player_movement(&mut self, movement: Vec4) {
-        if let Some(body) = self.player_mut() {
-            // Only set horizontal velocity - preserve Y for gravity/jumping
-            body.velocity.x = movement.x;
-            body.velocity.z = movement.z;
-            body.velocity.w = movement.w;
+        if let Some(handle) = self.player {
+            self.apply_movement(handle, movement);
         }
     }
 
-    /// Attempt to make the player jump
-    ///
-    /// Only succeeds if the player is grounded. Sets vertical velocity
-    /// to the configured jump velocity.
+    /// Attempt to make the player jump (see [`jump`](Self::jump))
     pub fn player_jump(&mut self) -> bool {
-        let jump_vel = self.player_jump_velocity;
-        if let Some(body) = self.player_mut() {
-            if body.grounded {
-                body.velocity.y = jump_vel;
-                body.grounded = false;
-                return true;
+        self.player.is_some_and(|handle| self.jump(handle))
+    }
+
+    /// Lock or unlock the player's W coordinate (see [`set_w_locked`](Self::set_w_locked))
+    pub fn set_player_w_locked(&mut self, locked: bool) {
+        if let Some(handle) = self.player {
+            self.set_w_locked(handle, locked);
+        }
+    }
+
+    /// Check if the player's W coordinate is locked
+    pub fn player_w_locked(&self) -> bool {
+        self.player.is_some_and(|handle| self.is_w_locked(handle))
+    }
+
+    /// Apply an instantaneous impulse to a body
+    ///
+    /// Changes a dynamic body's velocity by `impulse / mass`, so heavier
+    /// bodies are knocked back less. Static and kinematic bodies ignore
+    /// impulses; kinematic velocity is left to whatever controls it. A
+    /// sleeping body is woken.
+    pub fn apply_impulse(&mut self, key: BodyKey, impulse: Vec4) {
+        if let Some(body) = self.bodies.get_mut(key) {
+            if body.body_type == BodyType::Dynamic {
+                body.wake();
+                body.velocity += impulse / body.mass;
+            }
+        }
+    }
+
+    /// Apply a force to a body for the next step
+    ///
+    /// Forces add up until the next [`step`](Self::step), which accelerates
+    /// the body by the total `force / mass` over `dt` and then clears it, so
+    /// a continuous force has to be applied every frame. Like impulses,
+    /// forces only affect dynamic bodies, and wake them if they are asleep.
+    pub fn apply_force(&mut self, key: BodyKey, force: Vec4) {
+        if let Some(body) = self.bodies.get_mut(key) {
+            if body.body_type == BodyType::Dynamic {
+                body.wake();
+                body.force += force;
             }
         }
-        false
     }
 
     /// Step the physics simulation forward by dt seconds
     ///
     /// This performs:
-    /// 1. Gravity application to non-static bodies with gravity enabled
-    /// 2. Velocity integration into position
-    /// 3. Static collider collision detection and resolution
-    /// 4. Body-body collision detection and resolution
+    /// 1. Gravity application to non-static bodies with gravity enabled,
+    ///    accumulated forces (see [`apply_force`](Self::apply_force)) and damping
+    /// 2. Velocity integration into position, and angular velocity into orientation
+    /// 3. Portal teleports (see [`teleport_events`](Self::teleport_events))
+    /// 4. Static collider collision detection and resolution
+    /// 5. Body-body collision detection and resolution, testing only pairs
+    ///    whose bounds share a cell of a grid rebuilt from the body AABBs
+    ///    (unless [`PhysicsConfig::brute_force_broadphase`] is set)
+    ///
+    /// With [`PhysicsConfig::substeps`] above 1, these phases run that many
+    /// times with `dt / substeps` each, so fast bodies sink less into what
+    /// they hit. Forces act over the whole step and events and test counts
+    /// cover all substeps.
+    ///
+    /// Afterwards, dynamic bodies other than controllables that have moved
+    /// slower than [`PhysicsConfig::sleep_threshold`] for
+    /// [`PhysicsConfig::sleep_time`] fall asleep and are left out of later steps until woken (see
+    /// [`RigidBody4D::is_sleeping`]).
+    ///
+    /// With [`PhysicsConfig::warmup_steps`] set, the first steps of a new
+    /// world apply only part of gravity (see [`gravity_scale`](Self::gravity_scale)),
+    /// and the very first one resolves overlaps without changing velocities.
+    ///
+    /// # Determinism mode
+    ///
+    /// With [`PhysicsConfig::deterministic`] set, bodies and body pairs are
+    /// processed in the order the bodies were added rather than storage slot
+    /// order, which changes as removed slots are reused. After each step,
+    /// positions and velocities are snapped to [`DETERMINISM_QUANTUM`], so the
+    /// state is exact 16.16 fixed point that peers can compare or serialize
+    /// losslessly.
+    ///
+    /// The step only uses IEEE-754 `+ - * /` and `sqrt`, which Rust never
+    /// reorders or fuses, so the same inputs give bit-identical results on
+    /// the same build and platform. Math is still f32: different compilers,
+    /// targets or libm versions are not guaranteed to match, and the snap
+    /// grid loses precision for coordinates beyond +-256. Orientations are
+    /// integrated with `sin`/`cos` and are not covered by these guarantees.
     pub fn step(&mut self, dt: f32) {
-        // Reset grounded state for player before collision detection
-        if let Some(key) = self.player_body {
-            if let Some(body) = self.bodies.get_mut(key) {
+        let order = self.step_order();
+        self.static_tests = 0;
+        self.body_tests = 0;
+        self.collision_events.clear();
+        self.teleport_events.clear();
+
+        let warming_up = self.steps_taken < self.config.warmup_steps;
+        self.skip_velocity_response = warming_up && self.steps_taken == 0;
+        let gravity = self.config.gravity_vector * self.gravity_scale();
+
+        let substeps = self.config.substeps.max(1);
+        let sub_dt = dt / substeps as f32;
+        for _ in 0..substeps {
+            self.substep(&order, gravity, sub_dt);
+        }
+        self.skip_velocity_response = false;
+        self.steps_taken = self.steps_taken.saturating_add(1);
+
+        // Forces only last one step
+        for body in self.bodies.values_mut() {
+            body.force = Vec4::ZERO;
+        }
+
+        // Bodies don't doze off while gravity is still ramping up, and
+        // controllables never do, so input and grounding keep working
+        for &key in &order {
+            let controllable = self.is_controllable(key);
+            let body = &mut self.bodies[key];
+            if !self.config.sleep_enabled {
+                if body.is_sleeping() {
+                    body.wake();
+                }
+            } else if !warming_up
+                && !controllable
+                && body.body_type == BodyType::Dynamic
+                && !body.is_frozen()
+                && !body.is_sleeping()
+            {
+                body.update_sleep(dt, self.config.sleep_threshold, self.config.sleep_time);
+            }
+        }
+
+        if self.config.deterministic {
+            for &key in &order {
+                let body = &mut self.bodies[key];
+                body.set_position(quantize(body.position));
+                body.velocity = quantize(body.velocity);
+            }
+        }
+    }
+
+    /// Fraction of gravity the next step applies
+    ///
+    /// Rises linearly from 0 on the first step to 1 once
+    /// [`PhysicsConfig::warmup_steps`] steps have been taken.
+    pub fn gravity_scale(&self) -> f32 {
+        let warmup = self.config.warmup_steps;
+        if self.steps_taken >= warmup {
+            1.0
+        } else {
+            self.steps_taken as f32 / warmup as f32
+        }
+    }
+
+    /// Run every phase of a step once over `dt`
+    fn substep(&mut self, order: &[BodyKey], gravity: Vec4, dt: f32) {
+        // Reset grounded state for controllables before collision detection
+        for c in self.controllables.values() {
+            if let Some(body) = self.bodies.get_mut(c.body) {
                 body.grounded = false;
             }
         }
 
-        // Phase 1: Apply gravity and integrate velocity
-        for (key, body) in &mut self.bodies {
-            if body.is_static() {
+        // Phase 1: Apply gravity and forces, and integrate velocity and angular velocity
+        for &key in order {
+            let is_player = self.is_controllable(key);
+            let body = &mut self.bodies[key];
+            let w_locked = body.w_lock.is_some();
+            let force = body.force;
+            if body.is_static() || body.is_frozen() || body.is_sleeping() {
                 continue;
             }
 
             // Apply gravity to:
             // - Dynamic bodies (normal physics objects)
-            // - The player body (kinematic but needs gravity for jumping/falling)
-            let is_player = self.player_body == Some(key);
+            // - Controllable bodies (kinematic but need gravity for jumping/falling)
             if body.affected_by_gravity() || is_player {
-                body.velocity.y += self.config.gravity * dt;
+                body.velocity += gravity * dt;
+            }
+            body.velocity += force * (dt / body.mass);
+            if body.linear_damping > 0.0 {
+                body.velocity *= (1.0 - body.linear_damping * dt).max(0.0);
+            }
+            if body.angular_damping > 0.0 {
+                body.angular_velocity = body.angular_velocity * (1.0 - body.angular_damping * dt).max(0.0);
+            }
+            if w_locked {
+                body.velocity.w = 0.0;
             }
 
-            // Integrate velocity into position
-            let displacement = body.velocity * dt;
+            // Integrate velocity into position; controllables stop at the
+            // first static surface in their way instead of tunneling through
+            let mut displacement = body.velocity * dt;
+            if is_player {
+                displacement = self.sweep_player_displacement(key, displacement);
+            }
+            let body = &mut self.bodies[key];
             body.position += displacement;
             body.collider = body.collider.translated(displacement);
+
+            // Integrate angular velocity into orientation (world-space rates)
+            if !body.angular_velocity.is_zero() {
+                let step = Rotor4::from_bivector(body.angular_velocity * dt);
+                body.orientation = step.compose(&body.orientation).normalize();
+            }
         }
 
-        // Phase 2: Resolve static collider collisions
-        self.resolve_static_collisions();
+        // Phase 2: Teleport bodies that entered a portal
+        self.apply_portals(order, dt);
+
+        // Phase 3: Resolve static collider collisions
+        self.resolve_static_collisions(order);
 
-        // Phase 3: Resolve body-body collisions
-        self.resolve_body_collisions();
+        // Phase 4: Resolve body-body collisions
+        self.resolve_body_collisions(order);
+
+        // Undo anything that moved a W-locked body along W
+        self.hold_locked_w();
+    }
+
+    /// Limit a controllable body's step to what it can travel before hitting static geometry
+    ///
+    /// The body moves to just inside the first surface in its way, so the
+    /// static collision pass still grounds it and stops its velocity, then
+    /// slides along that surface with the rest of the step (up to
+    /// [`MAX_SWEEP_SLIDES`] times). Other bodies don't stop it; it pushes
+    /// them as usual. A capsule is swept as spheres along its segment, from
+    /// one end to the other and no more than a radius apart, and stops at
+    /// whichever hits first.
+    fn sweep_player_displacement(&self, key: BodyKey, displacement: Vec4) -> Vec4 {
+        let body = &self.bodies[key];
+        let (start, radius, offsets) = match body.collider {
+            Collider::Sphere(sphere) => (sphere.center, sphere.radius, vec![Vec4::ZERO]),
+            Collider::Capsule(capsule) => {
+                let steps = (2.0 * capsule.half_height / capsule.radius).ceil().max(1.0) as usize;
+                let offsets = (0..=steps)
+                    .map(|i| Vec4::Y * (capsule.half_height * (2.0 * i as f32 / steps as f32 - 1.0)))
+                    .collect();
+                (capsule.center, capsule.radius, offsets)
+            }
+            _ => return displacement,
+        };
+
+        let mut position = start;
+        let mut remaining = displacement;
+        for _ in 0..MAX_SWEEP_SLIDES {
+            let end = position + remaining;
+            let hit = offsets
+                .iter()
+                .filter_map(|&offset| {
+                    self.sweep(position + offset, end + offset, radius, &body.filter, Some(key), false)
+                })
+                .min_by(|a, b| a.0.total_cmp(&b.0));
+            let Some((fraction, normal)) = hit else {
+                return end - start;
+            };
+            let travel = (fraction + SWEEP_SKIN / remaining.length()).min(1.0);
+            position += remaining * travel;
+            remaining *= 1.0 - travel;
+
+            // Keep only the part of the rest of the step along the surface
+            let into = remaining.dot(normal);
+            if into < 0.0 {
+                remaining -= normal * into;
+            }
+        }
+        position - start
+    }
+
+    /// Move bodies whose center entered a portal to its exit
+    ///
+    /// A teleported body ignores all portals for [`PORTAL_COOLDOWN`] seconds.
+    fn apply_portals(&mut self, order: &[BodyKey], dt: f32) {
+        self.portal_cooldowns.retain(|_, remaining| {
+            *remaining -= dt;
+            *remaining > 0.0
+        });
+        if self.portals.is_empty() {
+            return;
+        }
+
+        for &key in order {
+            let body = &mut self.bodies[key];
+            if body.is_static() || body.is_frozen() || self.portal_cooldowns.contains_key(key) {
+                continue;
+            }
+            let Some(index) = self.portals.iter().position(|portal| portal.contains(body.position)) else {
+                continue;
+            };
+
+            let portal = &self.portals[index];
+            let from = body.position;
+            body.set_position(portal.transform_position(from));
+            body.velocity = portal.transform_velocity(body.velocity);
+            self.portal_cooldowns.insert(key, PORTAL_COOLDOWN);
+            self.teleport_events.push(TeleportEvent { body: key, portal: index, from, to: body.position });
+        }
+    }
+
+    /// Count the static grid cells a box overlaps
+    fn grid_cells_spanned(&self, bounds: &AABB4D) -> u64 {
+        cells_spanned(&self.static_grid, bounds)
+    }
+
+    /// Indices of the enabled static colliders that may touch a collider, in insertion order
+    fn nearby_statics(&self, collider: &Collider) -> Vec<usize> {
+        let mut indices = self.unbounded_statics.clone();
+        match collider.bounds() {
+            Some(bounds) => indices.extend(self.static_grid.query_aabb(bounds.min, bounds.max)),
+            None => indices = (0..self.static_colliders.len()).collect(),
+        }
+        indices.retain(|&index| self.static_colliders[index].enabled);
+        // Resolve in the same order as testing every collider would
+        indices.sort_unstable();
+        indices
+    }
+
+    /// Body keys in the order a step processes them
+    fn step_order(&self) -> Vec<BodyKey> {
+        if self.config.deterministic {
+            self.insertion_order.clone()
+        } else {
+            self.bodies.keys().collect()
+        }
     }
 
     /// Check for collision between a body collider and a static collider
@@ -256,6 +1221,32 @@ impl PhysicsWorld {
                     c
                 })
             }
+            // Body capsule vs static shapes (normals already point toward the capsule)
+            (Collider::Capsule(capsule), Collider::Plane(plane)) => {
+                capsule_vs_plane(capsule, plane)
+            }
+            (Collider::Capsule(capsule), Collider::AABB(aabb)) => {
+                capsule_vs_aabb(capsule, aabb)
+            }
+            (Collider::Capsule(capsule), Collider::Sphere(sphere)) => {
+                capsule_vs_sphere(capsule, sphere)
+            }
+            (Collider::Capsule(body_capsule), Collider::Capsule(static_capsule)) => {
+                capsule_vs_capsule(body_capsule, static_capsule)
+            }
+            // Body sphere or AABB vs static capsule: flip to point toward the body
+            (Collider::Sphere(sphere), Collider::Capsule(capsule)) => {
+                capsule_vs_sphere(capsule, sphere).map(|mut c| {
+                    c.normal = -c.normal;
+                    c
+                })
+            }
+            (Collider::AABB(aabb), Collider::Capsule(capsule)) => {
+                capsule_vs_aabb(capsule, aabb).map(|mut c| {
+                    c.normal = -c.normal;
+                    c
+                })
+            }
             // Plane colliders don't move so body can't be a plane
             (Collider::Plane(_), _) => None,
         }
@@ -279,21 +1270,28 @@ impl PhysicsWorld {
     }
 
     /// Resolve collisions between bodies and static colliders
-    fn resolve_static_collisions(&mut self) {
-        // Threshold for considering a surface as "ground" (normal pointing mostly up)
-        const GROUND_NORMAL_THRESHOLD: f32 = 0.7;
+    fn resolve_static_collisions(&mut self, order: &[BodyKey]) {
+        let up = self.config.up();
 
-        for (key, body) in &mut self.bodies {
-            if body.is_static() {
+        for &key in order {
+            let body = &self.bodies[key];
+            if body.is_static() || body.is_frozen() || body.is_sleeping() {
                 continue;
             }
-
-            // Check if this is the player body - used for edge falling detection
-            let is_player = self.player_body == Some(key);
-
-            for static_col in &self.static_colliders {
-                // Check if collision layers allow this interaction
-                if !body.filter.collides_with(&static_col.filter) {
+            let candidates = self.nearby_statics(&self.bodies[key].collider);
+            // Controllables (the player) get edge falling detection
+            let is_player = self.is_controllable(key);
+            let body = &mut self.bodies[key];
+
+            for index in candidates {
+                let static_col = &self.static_colliders[index];
+                // Check if collision layers allow this interaction; trigger
+                // overlaps are only reported
+                let solid = body.filter.collides_with(&static_col.filter);
+                if !solid
+                    && !body.filter.triggers_on(&static_col.filter)
+                    && !static_col.filter.triggers_on(&body.filter)
+                {
                     continue;
                 }
 
@@ -308,17 +1306,29 @@ impl PhysicsWorld {
                     }
                 }
 
+                self.static_tests += 1;
                 let contact = Self::check_static_collision(&body.collider, &static_col.collider);
 
-                if let Some(contact) = contact {
-                    if contact.is_colliding() {
+                if let Some(contact) = contact {
+                    if contact.is_colliding() {
+                        self.collision_events.push(CollisionEvent {
+                            a: key,
+                            b: None,
+                            point: contact.point,
+                            normal: contact.normal,
+                            penetration: contact.penetration,
+                        });
+                        if !solid {
+                            continue;
+                        }
+
                         // Push the body out of the static collider
                         let correction = contact.normal * contact.penetration;
                         body.apply_correction(correction);
 
-                        // Check if this is a ground contact (normal pointing up)
+                        // Check if this is a ground contact (normal pointing against gravity)
                         // This is used for grounded state detection
-                        if contact.normal.y > GROUND_NORMAL_THRESHOLD {
+                        if contact.normal.dot(up) > GROUND_NORMAL_THRESHOLD {
                             body.grounded = true;
                         }
 
@@ -327,7 +1337,7 @@ impl PhysicsWorld {
 
                         // Handle velocity response
                         let velocity_along_normal = body.velocity.dot(contact.normal);
-                        if velocity_along_normal < 0.0 {
+                        if velocity_along_normal < 0.0 && !self.skip_velocity_response {
                             // Body is moving into the collider
                             // Remove the normal component of velocity and optionally bounce
                             let normal_velocity = contact.normal * velocity_along_normal;
@@ -351,71 +1361,206 @@ impl PhysicsWorld {
     }
 
     /// Resolve collisions between bodies
-    fn resolve_body_collisions(&mut self) {
-        // Collect all keys first (needed because we can't iterate and mutate)
-        let keys: Vec<BodyKey> = self.bodies.keys().collect();
-        let key_count = keys.len();
-
-        // Check all pairs of bodies
-        for i in 0..key_count {
-            for j in (i + 1)..key_count {
-                let key_a = keys[i];
-                let key_b = keys[j];
-
-                // Get colliders and filters for both bodies
-                let (collider_a, collider_b, is_static_a, is_static_b, filter_a, filter_b) = {
-                    let body_a = &self.bodies[key_a];
-                    let body_b = &self.bodies[key_b];
-                    (body_a.collider, body_b.collider, body_a.is_static(), body_b.is_static(), body_a.filter, body_b.filter)
-                };
+    ///
+    /// Pairs are visited in the same `(i, j)` order as testing every pair. With
+    /// the broadphase grid, pairs whose bounds don't overlap are skipped; the
+    /// grid entries of bodies pushed apart are refreshed, and the candidates of
+    /// the body being visited are re-queried, so the pairs resolved are exactly
+    /// those the brute-force pass would resolve.
+    fn resolve_body_collisions(&mut self, keys: &[BodyKey]) {
+        if self.config.brute_force_broadphase {
+            for i in 0..keys.len() {
+                for j in (i + 1)..keys.len() {
+                    self.collide_body_pair(keys[i], keys[j]);
+                }
+            }
+            return;
+        }
 
-                // Skip if both bodies are static
-                if is_static_a && is_static_b {
-                    continue;
+        // Bodies too large for the grid (and unbounded ones) are candidates for every pair
+        let oversized = self.rebuild_body_grid(keys);
+
+        for i in 0..keys.len() {
+            let mut candidates = self.body_candidates(keys, &oversized, i, i + 1);
+            let mut next = 0;
+            while let Some(&j) = candidates.get(next) {
+                next += 1;
+                if self.collide_body_pair(keys[i], keys[j]) {
+                    self.update_body_grid(keys, &oversized, i);
+                    self.update_body_grid(keys, &oversized, j);
+                    candidates = self.body_candidates(keys, &oversized, i, j + 1);
+                    next = 0;
                 }
+            }
+        }
+    }
 
-                // Check if collision layers allow this interaction
-                if !filter_a.collides_with(&filter_b) {
-                    continue;
+    /// Refill the body grid from the current body bounds
+    ///
+    /// Returns which bodies were kept out of the grid; those are tested
+    /// against every other body.
+    fn rebuild_body_grid(&mut self, keys: &[BodyKey]) -> Vec<bool> {
+        if self.body_grid.cell_size() != self.config.body_grid_cell_size {
+            self.body_grid = SpatialGrid4D::new(self.config.body_grid_cell_size);
+        } else {
+            self.body_grid.clear();
+        }
+
+        let mut oversized = vec![false; keys.len()];
+        for (i, &key) in keys.iter().enumerate() {
+            match self.bodies[key].collider.bounds() {
+                Some(bounds) if cells_spanned(&self.body_grid, &bounds) <= MAX_GRID_CELLS => {
+                    self.body_grid.insert_aabb(i, bounds.min, bounds.max);
                 }
+                _ => oversized[i] = true,
+            }
+        }
+        oversized
+    }
 
-                // Check for collision based on collider types
-                // The contact normal convention: points FROM body A TOWARD body B
-                let contact = match (&collider_a, &collider_b) {
-                    (Collider::Sphere(a), Collider::Sphere(b)) => {
-                        Self::sphere_vs_sphere(a, b)
-                    }
-                    (Collider::Sphere(sphere), Collider::AABB(aabb)) => {
-                        // sphere_vs_aabb returns normal pointing from AABB toward sphere
-                        // We want normal from A (sphere) toward B (AABB), so flip it
-                        sphere_vs_aabb(sphere, aabb).map(|mut c| {
-                            c.normal = -c.normal;
-                            c
-                        })
-                    }
-                    (Collider::AABB(aabb), Collider::Sphere(sphere)) => {
-                        // sphere_vs_aabb returns normal pointing from AABB toward sphere
-                        // We want normal from A (AABB) toward B (sphere), which is already correct
-                        sphere_vs_aabb(sphere, aabb)
-                    }
-                    (Collider::AABB(a), Collider::AABB(b)) => {
-                        // aabb_vs_aabb returns normal pointing from B toward A
-                        // We want normal from A toward B, so flip it
-                        aabb_vs_aabb(a, b).map(|mut c| {
-                            c.normal = -c.normal;
-                            c
-                        })
-                    }
-                    // Plane colliders are only used for static colliders
-                    (Collider::Plane(_), _) | (_, Collider::Plane(_)) => None,
-                };
+    /// Move a body's grid entry to its current bounds
+    fn update_body_grid(&mut self, keys: &[BodyKey], oversized: &[bool], index: usize) {
+        if oversized[index] {
+            return;
+        }
+        if let Some(bounds) = self.bodies[keys[index]].collider.bounds() {
+            self.body_grid.insert_aabb(index, bounds.min, bounds.max);
+        }
+    }
 
-                if let Some(contact) = contact {
-                    if contact.is_colliding() {
-                        self.resolve_body_pair_collision(key_a, key_b, &contact, is_static_a, is_static_b);
-                    }
+    /// Indices from `first` on of the bodies that may touch body `index`, ascending
+    fn body_candidates(&self, keys: &[BodyKey], oversized: &[bool], index: usize, first: usize) -> Vec<usize> {
+        let mut candidates: Vec<usize> = match self.bodies[keys[index]].collider.bounds() {
+            Some(bounds) if !oversized[index] => self
+                .body_grid
+                .query_aabb(bounds.min, bounds.max)
+                .into_iter()
+                .filter(|&j| j >= first)
+                .chain((first..keys.len()).filter(|&j| oversized[j]))
+                .collect(),
+            _ => (first..keys.len()).collect(),
+        };
+        candidates.sort_unstable();
+        candidates
+    }
+
+    /// Test a pair of bodies and push them apart if they overlap
+    ///
+    /// Returns true if the pair was pushed apart.
+    fn collide_body_pair(&mut self, key_a: BodyKey, key_b: BodyKey) -> bool {
+        self.body_tests += 1;
+
+        // Get colliders and filters for both bodies
+        // Frozen bodies are resolved as static: they push but are never pushed
+        let (collider_a, collider_b, is_static_a, is_static_b, filter_a, filter_b) = {
+            let body_a = &self.bodies[key_a];
+            let body_b = &self.bodies[key_b];
+            (
+                body_a.collider,
+                body_b.collider,
+                body_a.is_static() || body_a.is_frozen(),
+                body_b.is_static() || body_b.is_frozen(),
+                body_a.filter,
+                body_b.filter,
+            )
+        };
+        let sleeping_a = self.bodies[key_a].is_sleeping();
+        let sleeping_b = self.bodies[key_b].is_sleeping();
+
+        // Skip if neither body can move; a sleeping body only wakes when an
+        // awake one runs into it
+        if (is_static_a || sleeping_a) && (is_static_b || sleeping_b) {
+            return false;
+        }
+
+        // Check if collision layers allow this interaction; trigger overlaps
+        // are only reported
+        let solid = filter_a.collides_with(&filter_b);
+        if !solid && !filter_a.triggers_on(&filter_b) && !filter_b.triggers_on(&filter_a) {
+            return false;
+        }
+
+        // Check for collision based on collider types
+        // The contact normal convention: points FROM body A TOWARD body B
+        let contact = match (&collider_a, &collider_b) {
+            (Collider::Sphere(a), Collider::Sphere(b)) => {
+                Self::sphere_vs_sphere(a, b)
+            }
+            (Collider::Sphere(sphere), Collider::AABB(aabb)) => {
+                // sphere_vs_aabb returns normal pointing from AABB toward sphere
+                // We want normal from A (sphere) toward B (AABB), so flip it
+                sphere_vs_aabb(sphere, aabb).map(|mut c| {
+                    c.normal = -c.normal;
+                    c
+                })
+            }
+            (Collider::AABB(aabb), Collider::Sphere(sphere)) => {
+                // sphere_vs_aabb returns normal pointing from AABB toward sphere
+                // We want normal from A (AABB) toward B (sphere), which is already correct
+                sphere_vs_aabb(sphere, aabb)
+            }
+            (Collider::AABB(a), Collider::AABB(b)) => {
+                // aabb_vs_aabb returns normal pointing from B toward A
+                // We want normal from A toward B, so flip it
+                aabb_vs_aabb(a, b).map(|mut c| {
+                    c.normal = -c.normal;
+                    c
+                })
+            }
+            (Collider::Capsule(capsule), Collider::Sphere(sphere)) => {
+                // capsule_vs_sphere returns normal pointing from the sphere toward the capsule
+                // We want normal from A (capsule) toward B (sphere), so flip it
+                capsule_vs_sphere(capsule, sphere).map(|mut c| {
+                    c.normal = -c.normal;
+                    c
+                })
+            }
+            (Collider::Sphere(sphere), Collider::Capsule(capsule)) => {
+                // Normal from A (sphere) toward B (capsule), already correct
+                capsule_vs_sphere(capsule, sphere)
+            }
+            (Collider::Capsule(capsule), Collider::AABB(aabb)) => {
+                capsule_vs_aabb(capsule, aabb).map(|mut c| {
+                    c.normal = -c.normal;
+                    c
+                })
+            }
+            (Collider::AABB(aabb), Collider::Capsule(capsule)) => {
+                capsule_vs_aabb(capsule, aabb)
+            }
+            (Collider::Capsule(a), Collider::Capsule(b)) => {
+                // capsule_vs_capsule returns normal pointing from B toward A, so flip it
+                capsule_vs_capsule(a, b).map(|mut c| {
+                    c.normal = -c.normal;
+                    c
+                })
+            }
+            // Plane colliders are only used for static colliders
+            (Collider::Plane(_), _) | (_, Collider::Plane(_)) => None,
+        };
+
+        match contact {
+            Some(contact) if contact.is_colliding() => {
+                self.collision_events.push(CollisionEvent {
+                    a: key_a,
+                    b: Some(key_b),
+                    point: contact.point,
+                    normal: -contact.normal,
+                    penetration: contact.penetration,
+                });
+                if !solid {
+                    return false;
                 }
+                if sleeping_a {
+                    self.bodies[key_a].wake();
+                }
+                if sleeping_b {
+                    self.bodies[key_b].wake();
+                }
+                self.resolve_body_pair_collision(key_a, key_b, &contact, is_static_a, is_static_b);
+                true
             }
+            _ => false,
         }
     }
 
@@ -437,8 +1582,21 @@ impl PhysicsWorld {
         // - Dynamic bodies: always pushed
         //
         // can_correct = not static AND (not kinematic OR other is static)
-        let can_correct_a = !is_static_a && (!is_kinematic_a || is_static_b);
-        let can_correct_b = !is_static_b && (!is_kinematic_b || is_static_a);
+        let mut can_correct_a = !is_static_a && (!is_kinematic_a || is_static_b);
+        let mut can_correct_b = !is_static_b && (!is_kinematic_b || is_static_a);
+
+        // Push priority: between two movable bodies, the higher priority body
+        // holds its ground and the lower one takes the full correction
+        let priority_a = self.bodies[key_a].push_priority;
+        let priority_b = self.bodies[key_b].push_priority;
+        let a_wins = can_correct_a && can_correct_b && priority_a > priority_b;
+        let b_wins = can_correct_a && can_correct_b && priority_b > priority_a;
+        if a_wins {
+            can_correct_a = false;
+        }
+        if b_wins {
+            can_correct_b = false;
+        }
 
         // Determine how to split the correction
         let (correction_a, correction_b) = if !can_correct_a && can_correct_b {
@@ -480,8 +1638,11 @@ impl PhysicsWorld {
         // - Static bodies: no velocity (implicit)
         // - Kinematic bodies: velocity is user-controlled, never modified by collisions
         // - Dynamic bodies: velocity response applied
-        let can_modify_velocity_a = !is_static_a && !is_kinematic_a;
-        let can_modify_velocity_b = !is_static_b && !is_kinematic_b;
+        // - Higher push priority bodies: velocity kept, the other body yields
+        // - Nothing during the first warm-up step
+        let respond = !self.skip_velocity_response;
+        let can_modify_velocity_a = respond && !is_static_a && !is_kinematic_a && !a_wins;
+        let can_modify_velocity_b = respond && !is_static_b && !is_kinematic_b && !b_wins;
 
         // Handle velocity response with restitution
         if can_modify_velocity_a {
@@ -517,15 +1678,49 @@ impl PhysicsWorld {
                 }
             }
         }
+
+        // A controllable walking into a dynamic body shoves it along
+        if respond && self.config.player_push_strength > 0.0 {
+            if self.is_controllable(key_a) && !is_static_b {
+                self.apply_player_push(key_a, key_b, contact.normal);
+            } else if self.is_controllable(key_b) && !is_static_a {
+                self.apply_player_push(key_b, key_a, -contact.normal);
+            }
+        }
+    }
+
+    /// Give `object` an impulse from `player` moving into it along `normal`
+    ///
+    /// `normal` points from the player toward the object. The impulse is
+    /// [`player_push_strength`](PhysicsConfig::player_push_strength) times the
+    /// speed the player closes in at, so it stops once the object keeps pace.
+    fn apply_player_push(&mut self, player: BodyKey, object: BodyKey, normal: Vec4) {
+        let approach = (self.bodies[player].velocity - self.bodies[object].velocity).dot(normal);
+        if approach > 0.0 {
+            self.apply_impulse(object, normal * (approach * self.config.player_push_strength));
+        }
     }
 }
 
+/// Snap each component to the determinism grid
+fn quantize(v: Vec4) -> Vec4 {
+    let snap = |c: f32| (c / DETERMINISM_QUANTUM).round() * DETERMINISM_QUANTUM;
+    Vec4::new(snap(v.x), snap(v.y), snap(v.z), snap(v.w))
+}
+
 impl Default for PhysicsWorld {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Count the grid cells a box overlaps, saturating at `u64::MAX` for huge boxes
+fn cells_spanned(grid: &SpatialGrid4D<usize>, bounds: &AABB4D) -> u64 {
+    let lo = grid.cell_of(bounds.min);
+    let hi = grid.cell_of(bounds.max);
+    (0..4).fold(1u64, |cells, i| cells.saturating_mul((hi[i] as i64 - lo[i] as i64 + 1) as u64))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -534,13 +1729,24 @@ mod tests {
     #[test]
     fn test_physics_config_default() {
         let config = PhysicsConfig::default();
-        assert_eq!(config.gravity, -20.0);
+        assert_eq!(config.gravity_vector, Vec4::new(0.0, -20.0, 0.0, 0.0));
     }
 
     #[test]
     fn test_physics_config_custom() {
         let config = PhysicsConfig::new(-10.0);
-        assert_eq!(config.gravity, -10.0);
+        assert_eq!(config.gravity_vector, Vec4::new(0.0, -10.0, 0.0, 0.0));
+        assert_eq!(config.up(), Vec4::Y);
+    }
+
+    #[test]
+    fn test_physics_config_reads_scalar_gravity() {
+        let config: PhysicsConfig = ron::from_str("(gravity: -15.0, jump_velocity: 8.0)").unwrap();
+        assert_eq!(config.gravity_vector, Vec4::new(0.0, -15.0, 0.0, 0.0));
+
+        let config: PhysicsConfig =
+            ron::from_str("(gravity_vector: (x: 0.0, y: 0.0, z: 0.0, w: 5.0), jump_velocity: 8.0)").unwrap();
+        assert_eq!(config.gravity_vector, Vec4::new(0.0, 0.0, 0.0, 5.0));
     }
 
     /// Helper to create a world with a floor at the given Y position
@@ -628,6 +1834,56 @@ mod tests {
         assert!((body.velocity.y - (-2.0)).abs() < 0.0001);
     }
 
+    #[test]
+    fn test_gravity_along_w_accelerates_in_w() {
+        let config = PhysicsConfig::default().with_gravity_vector(Vec4::new(0.0, 0.0, 0.0, 10.0));
+        let mut world = PhysicsWorld::with_config(config);
+        let handle = world.add_body(RigidBody4D::new_sphere(Vec4::ZERO, 0.5));
+
+        world.step(0.1);
+        world.step(0.1);
+
+        let body = world.get_body(handle).unwrap();
+        assert!((body.velocity.w - 2.0).abs() < 0.0001);
+        assert!(body.position.w > 0.0);
+        assert_eq!(body.velocity.y, 0.0);
+    }
+
+    #[test]
+    fn test_gravity_along_w_lands_on_w_facing_plane() {
+        let config = PhysicsConfig::default().with_gravity_vector(Vec4::new(0.0, 0.0, 0.0, 20.0));
+        let mut world = PhysicsWorld::with_config(config);
+        assert_eq!(world.config.up(), -Vec4::W);
+
+        // Surface at w = 3 facing back towards -W
+        world.add_static_collider(StaticCollider::plane(-Vec4::W, -3.0, PhysicsMaterial::CONCRETE));
+        let handle = world.add_body(RigidBody4D::new_sphere(Vec4::ZERO, 0.5));
+
+        for _ in 0..120 {
+            world.step(1.0 / 60.0);
+        }
+
+        let body = world.get_body(handle).unwrap();
+        assert!(body.grounded, "Body should rest on the W-facing plane");
+        assert!((body.position.w - 2.5).abs() < 0.01, "w = {}", body.position.w);
+        assert!(body.velocity.w.abs() < 0.5);
+    }
+
+    #[test]
+    fn test_floor_is_not_ground_under_w_gravity() {
+        let config = PhysicsConfig::default().with_gravity_vector(Vec4::new(0.0, 0.0, 0.0, 20.0));
+        let mut world = PhysicsWorld::with_config(config);
+        world.add_static_collider(StaticCollider::floor(0.0, PhysicsMaterial::CONCRETE));
+
+        // Overlapping the Y floor: pushed out, but it is a wall rather than ground here
+        let handle = world.add_body(RigidBody4D::new_sphere(Vec4::new(0.0, 0.25, 0.0, 0.0), 0.5));
+        world.step(1.0 / 60.0);
+
+        let body = world.get_body(handle).unwrap();
+        assert!(body.position.y >= 0.5 - 0.001);
+        assert!(!body.grounded);
+    }
+
     #[test]
     fn test_velocity_integration() {
         let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0)); // No gravity
@@ -873,6 +2129,66 @@ mod tests {
             "Ball should be between floor and ceiling, got y={}", ball.position.y);
     }
 
+    #[test]
+    fn test_disabled_static_is_passed_through_until_reenabled() {
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(-20.0));
+        let floor = world.add_static_collider(StaticCollider::floor(0.0, PhysicsMaterial::CONCRETE));
+        let key = world.add_body(RigidBody4D::new_sphere(Vec4::new(0.0, 0.5, 0.0, 0.0), 0.5));
+        for _ in 0..60 {
+            world.step(1.0 / 60.0);
+        }
+        let resting_y = world.get_body(key).unwrap().position.y;
+        assert!((resting_y - 0.5).abs() < 0.05);
+        assert!(world.get_body(key).unwrap().is_sleeping());
+
+        // Open the "trapdoor": the body wakes and drops on the next step
+        world.set_static_enabled(floor, false);
+        assert!(!world.static_collider(floor).unwrap().enabled);
+        world.step(1.0 / 60.0);
+        assert!(world.get_body(key).unwrap().position.y < resting_y);
+        assert!(world.collision_events().is_empty());
+
+        // Close it again while the body is still overlapping: it is pushed back out
+        world.set_static_enabled(floor, true);
+        for _ in 0..5 {
+            world.step(1.0 / 60.0);
+        }
+        assert!((world.get_body(key).unwrap().position.y - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_toggling_a_distant_static_leaves_sleepers_alone() {
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(-20.0));
+        let pad = Vec4::new(2.0, 0.5, 2.0, 2.0);
+        world.add_static_collider(StaticCollider::aabb(
+            Vec4::new(0.0, -0.5, 0.0, 0.0), pad, PhysicsMaterial::CONCRETE,
+        ));
+        let far_pad = world.add_static_collider(StaticCollider::aabb(
+            Vec4::new(20.0, -0.5, 0.0, 0.0), pad, PhysicsMaterial::CONCRETE,
+        ));
+        let key = world.add_body(RigidBody4D::new_sphere(Vec4::new(0.0, 0.5, 0.0, 0.0), 0.5));
+        for _ in 0..60 {
+            world.step(1.0 / 60.0);
+        }
+        assert!(world.get_body(key).unwrap().is_sleeping());
+
+        world.set_static_enabled(far_pad, false);
+        assert!(world.get_body(key).unwrap().is_sleeping());
+    }
+
+    #[test]
+    fn test_static_collider_ids_are_stable() {
+        let mut world = PhysicsWorld::new();
+        let a = world.add_static_collider(StaticCollider::floor(0.0, PhysicsMaterial::CONCRETE));
+        let b = world.add_static_collider(StaticCollider::floor(5.0, PhysicsMaterial::ICE));
+        world.set_static_enabled(a, false);
+
+        assert_ne!(a, b);
+        assert!(!world.static_collider(a).unwrap().enabled);
+        assert!(world.static_collider(b).unwrap().enabled);
+        assert_eq!(world.static_collider(b).unwrap().material, PhysicsMaterial::ICE);
+    }
+
     // ====== Player Body Tests ======
 
     #[test]
@@ -996,23 +2312,332 @@ mod tests {
     }
 
     #[test]
-    fn test_player_jump_velocity_config() {
-        let mut world = PhysicsWorld::new();
-        world.set_player_jump_velocity(15.0);
+    fn test_player_jump_velocity_config() {
+        let mut world = PhysicsWorld::new();
+        world.set_player_jump_velocity(15.0);
+
+        // Player that's grounded
+        let mut player = RigidBody4D::new_sphere(Vec4::new(0.0, 0.5, 0.0, 0.0), 0.5)
+            .with_body_type(crate::body::BodyType::Kinematic);
+        player.grounded = true; // Manually set grounded for test
+        let player_key = world.add_body(player);
+        world.set_player_body(player_key);
+
+        // Jump
+        world.player_jump();
+
+        // Check custom velocity used
+        let vel = world.player().unwrap().velocity;
+        assert_eq!(vel.y, 15.0);
+    }
+
+    #[test]
+    fn test_player_jumps_against_w_gravity() {
+        let config = PhysicsConfig::default().with_gravity_vector(Vec4::new(0.0, 0.0, 0.0, 20.0));
+        let mut world = PhysicsWorld::with_config(config);
+        let mut player = RigidBody4D::new_sphere(Vec4::ZERO, 0.5)
+            .with_body_type(crate::body::BodyType::Kinematic)
+            .with_velocity(Vec4::new(1.0, 0.0, 0.0, 3.0));
+        player.grounded = true;
+        let player_key = world.add_body(player);
+        world.set_player_body(player_key);
+
+        // The jump replaces the velocity along -W and keeps the rest
+        assert!(world.player_jump());
+        assert_eq!(world.player().unwrap().velocity, Vec4::new(1.0, 0.0, 0.0, -8.0));
+    }
+
+    /// Kinematic player at the origin, with gravity pulling down and along +W
+    fn world_with_w_gravity_player() -> PhysicsWorld {
+        let config = PhysicsConfig::new(0.0).with_gravity_vector(Vec4::new(0.0, -10.0, 0.0, 10.0));
+        let mut world = PhysicsWorld::with_config(config);
+        let player = RigidBody4D::new_sphere(Vec4::new(0.0, 0.0, 0.0, 1.5), 0.5)
+            .with_body_type(crate::body::BodyType::Kinematic);
+        let player_key = world.add_body(player);
+        world.set_player_body(player_key);
+        world
+    }
+
+    #[test]
+    fn test_w_locked_player_keeps_w() {
+        let mut world = world_with_w_gravity_player();
+        world.set_player_w_locked(true);
+        assert!(world.player_w_locked());
+        assert_eq!(world.player().unwrap().w_lock, Some(1.5));
+
+        for _ in 0..10 {
+            world.apply_player_movement(Vec4::new(1.0, 0.0, 2.0, 5.0));
+            world.step(0.1);
+        }
+
+        // X, Y and Z still move; W stays put and at rest
+        let player = world.player().unwrap();
+        assert_eq!(player.position.w, 1.5);
+        assert_eq!(player.velocity.w, 0.0);
+        assert!((player.position.x - 1.0).abs() < 1e-4);
+        assert!((player.position.z - 2.0).abs() < 1e-4);
+        assert!(player.position.y < -1.0);
+    }
+
+    #[test]
+    fn test_unlocking_w_restores_w_motion() {
+        let mut world = world_with_w_gravity_player();
+        world.set_player_w_locked(true);
+        world.apply_player_movement(Vec4::new(0.0, 0.0, 0.0, 5.0));
+        world.step(0.1);
+        assert_eq!(world.player_position().unwrap().w, 1.5);
+
+        world.set_player_w_locked(false);
+        assert!(!world.player_w_locked());
+        world.apply_player_movement(Vec4::new(0.0, 0.0, 0.0, 5.0));
+        world.step(0.1);
+        // W movement plus one step of W gravity
+        assert!((world.player_position().unwrap().w - 2.1).abs() < 1e-4);
+
+        // Locking again holds the new W
+        world.set_player_w_locked(true);
+        world.step(0.1);
+        assert!((world.player_position().unwrap().w - 2.1).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_w_locked_body_keeps_w_under_4d_forces() {
+        let config = PhysicsConfig::new(0.0).with_gravity_vector(Vec4::new(0.0, -10.0, 0.0, 10.0));
+        let mut world = PhysicsWorld::with_config(config);
+        let key = world.add_body(
+            RigidBody4D::new_sphere(Vec4::new(0.0, 0.0, 0.0, 1.0), 0.5)
+                .with_velocity(Vec4::new(1.0, 0.0, 0.0, 3.0))
+                .with_w_lock(0.5),
+        );
+
+        for _ in 0..10 {
+            world.apply_force(key, Vec4::new(0.0, 0.0, 2.0, 4.0));
+            world.step(0.1);
+        }
+
+        // Pinned to the lock's W, not the W it started at
+        let body = world.get_body(key).unwrap();
+        assert_eq!(body.position.w, 0.5);
+        assert_eq!(body.velocity.w, 0.0);
+        assert!(body.position.x > 0.9);
+        assert!(body.position.y < -1.0);
+        assert!(body.position.z > 0.5);
+    }
+
+    // ====== Controllable Tests ======
+
+    /// Floor world with two kinematic controllables resting on it, 4 units apart in X
+    fn two_controllables() -> (PhysicsWorld, ControllableHandle, ControllableHandle) {
+        let mut world = world_with_floor(-20.0, 0.0, PhysicsMaterial::CONCRETE);
+        let mut add = |x: f32| {
+            let key = world.add_body(
+                RigidBody4D::new_sphere(Vec4::new(x, 0.5, 0.0, 0.0), 0.5).with_body_type(BodyType::Kinematic),
+            );
+            world.register_controllable(key)
+        };
+        let (a, b) = (add(0.0), add(4.0));
+        world.step(0.016);
+        (world, a, b)
+    }
+
+    #[test]
+    fn test_controllables_jump_independently() {
+        let (mut world, a, b) = two_controllables();
+        assert!(world.is_grounded(a) && world.is_grounded(b));
+        world.set_jump_velocity(b, 4.0);
+
+        assert!(world.jump(a));
+        world.step(0.016);
+        assert!(!world.is_grounded(a));
+        assert!(world.is_grounded(b), "b stays on the floor while a jumps");
+        assert!(!world.jump(a), "a cannot jump again in mid-air");
+
+        assert!(world.jump(b));
+        let velocity = |world: &PhysicsWorld, h| world.get_body(world.controllable_key(h).unwrap()).unwrap().velocity.y;
+        assert!(velocity(&world, a) > 7.0, "a jumps at the default velocity");
+        assert!((velocity(&world, b) - 4.0).abs() < 1e-5, "b uses its own jump velocity");
+    }
+
+    #[test]
+    fn test_controllables_move_independently() {
+        let (mut world, a, b) = two_controllables();
+        world.apply_movement(a, Vec4::new(0.0, 0.0, 10.0, 0.0));
+        world.apply_movement(b, Vec4::new(0.0, 0.0, 0.0, -5.0));
+        world.step(0.1);
+
+        let position = |h| world.get_body(world.controllable_key(h).unwrap()).unwrap().position;
+        assert!((position(a) - Vec4::new(0.0, 0.5, 1.0, 0.0)).length() < 0.01);
+        assert!((position(b) - Vec4::new(4.0, 0.5, 0.0, -0.5)).length() < 0.01);
+    }
+
+    #[test]
+    fn test_resting_dynamic_controllable_stays_awake() {
+        let mut world = world_with_floor(-20.0, 0.0, PhysicsMaterial::CONCRETE);
+        let key = world.add_body(RigidBody4D::new_sphere(Vec4::new(0.0, 0.5, 0.0, 0.0), 0.5));
+        let handle = world.register_controllable(key);
+        for _ in 0..120 {
+            world.step(1.0 / 60.0);
+        }
+        assert!(!world.get_body(key).unwrap().is_sleeping());
+
+        // Input still moves it, and it can still jump
+        world.apply_movement(handle, Vec4::new(5.0, 0.0, 0.0, 0.0));
+        world.step(1.0 / 60.0);
+        assert!(world.get_body(key).unwrap().position.x > 0.0);
+        assert!(world.jump(handle));
+    }
+
+    #[test]
+    fn test_registering_a_sleeping_body_wakes_it() {
+        let (mut world, key) = settled_ball(PhysicsConfig::new(-20.0), 3.0);
+        assert!(world.get_body(key).unwrap().is_sleeping());
+        world.register_controllable(key);
+        assert!(!world.get_body(key).unwrap().is_sleeping());
+    }
+
+    #[test]
+    fn test_grounded_tracked_per_controllable() {
+        let mut world = world_with_floor(-20.0, 0.0, PhysicsMaterial::CONCRETE);
+        let standing = world.add_body(RigidBody4D::new_sphere(Vec4::new(0.0, 0.5, 0.0, 0.0), 0.5).with_body_type(BodyType::Kinematic));
+        let falling = world.add_body(RigidBody4D::new_sphere(Vec4::new(4.0, 5.0, 0.0, 0.0), 0.5).with_body_type(BodyType::Kinematic));
+        let standing = world.register_controllable(standing);
+        let falling = world.register_controllable(falling);
+
+        world.step(0.016);
+        assert!(world.is_grounded(standing));
+        assert!(!world.is_grounded(falling));
+
+        // Gravity applies to kinematic controllables, so the second one lands
+        for _ in 0..120 {
+            world.step(0.016);
+        }
+        assert!(world.is_grounded(falling));
+    }
+
+    #[test]
+    fn test_set_player_body_moves_player_registration() {
+        let mut world = PhysicsWorld::new();
+        let a = world.add_body(RigidBody4D::new_sphere(Vec4::ZERO, 0.5));
+        let b = world.add_body(RigidBody4D::new_sphere(Vec4::X, 0.5));
+
+        world.set_player_body(a);
+        let handle = world.player_handle().unwrap();
+        assert_eq!(world.register_controllable(a), handle);
+        world.register_controllable(b);
+        assert_eq!(world.player_key(), Some(a));
+        assert_eq!(world.controllable_count(), 2);
+
+        // Replacing the player with an unregistered body keeps the handle
+        let c = world.add_body(RigidBody4D::new_sphere(Vec4::Y, 0.5));
+        world.set_player_body(c);
+        assert_eq!(world.player_key(), Some(c));
+        assert_eq!(world.controllable_key(handle), Some(c));
+        assert_eq!(world.controllable_count(), 2);
+        assert_eq!(world.get_body(c).unwrap().push_priority, PLAYER_PUSH_PRIORITY);
+    }
+
+    #[test]
+    fn test_npc_registered_first_is_not_the_player() {
+        let mut world = PhysicsWorld::new();
+        let npc = world.add_body(RigidBody4D::new_sphere(Vec4::ZERO, 0.5));
+        let npc_handle = world.register_controllable(npc);
+        assert_eq!(world.player_key(), None);
+        assert!(!world.player_jump());
+
+        let player = world.add_body(RigidBody4D::new_sphere(Vec4::X, 0.5));
+        world.set_player_body(player);
+        assert_eq!(world.player_key(), Some(player));
+        assert_eq!(world.controllable_key(npc_handle), Some(npc));
+        assert_eq!(world.controllable_count(), 2);
+    }
+
+    #[test]
+    fn test_set_player_body_reuses_existing_registration() {
+        let mut world = PhysicsWorld::new();
+        let npc = world.add_body(RigidBody4D::new_sphere(Vec4::ZERO, 0.5));
+        let body = world.add_body(RigidBody4D::new_sphere(Vec4::X, 0.5));
+        let npc_handle = world.register_controllable(npc);
+        let handle = world.register_controllable(body);
+
+        world.set_player_body(body);
+        assert_eq!(world.player_handle(), Some(handle));
+        assert_eq!(world.controllable_key(npc_handle), Some(npc));
+        assert_eq!(world.controllable_count(), 2);
+        assert_eq!(world.get_body(body).unwrap().push_priority, PLAYER_PUSH_PRIORITY);
+    }
+
+    #[test]
+    fn test_remove_body_unregisters_controllable() {
+        let mut world = PhysicsWorld::new();
+        let npc = world.add_body(RigidBody4D::new_sphere(Vec4::ZERO, 0.5));
+        let player = world.add_body(RigidBody4D::new_sphere(Vec4::X, 0.5));
+        let npc_handle = world.register_controllable(npc);
+        world.set_player_body(player);
+
+        world.remove_body(npc);
+        assert_eq!(world.controllable_key(npc_handle), None);
+        assert_eq!(world.controllable_count(), 1);
+
+        world.remove_body(player);
+        assert_eq!(world.player_key(), None);
+        assert_eq!(world.player_handle(), None);
+        assert_eq!(world.controllable_count(), 0);
+        assert!(!world.player_is_grounded());
+    }
+
+    // ====== Capsule Player Tests ======
+
+    fn capsule_player_world(floor: StaticCollider, spawn_y: f32) -> (PhysicsWorld, BodyKey) {
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(-20.0));
+        world.add_static_collider(floor);
+        let player = world.add_body(
+            RigidBody4D::new_capsule(Vec4::new(0.0, spawn_y, 0.0, 0.0), 0.5, 0.5)
+                .with_body_type(BodyType::Kinematic),
+        );
+        world.set_player_body(player);
+        (world, player)
+    }
+
+    #[test]
+    fn test_capsule_player_stands_on_plane_floor() {
+        // Bottom of the capsule is 1.0 below its center
+        let (mut world, _) = capsule_player_world(StaticCollider::floor(0.0, PhysicsMaterial::CONCRETE), 1.0);
+
+        for _ in 0..60 {
+            world.step(1.0 / 60.0);
+        }
+
+        assert!(world.player_is_grounded());
+        let y = world.player_position().unwrap().y;
+        assert!((y - 1.0).abs() < 0.01, "capsule sank or floated: y = {}", y);
+    }
+
+    #[test]
+    fn test_capsule_player_lands_on_bounded_floor() {
+        let floor = StaticCollider::floor_bounded(0.0, 10.0, 10.0, 5.0, PhysicsMaterial::CONCRETE);
+        let (mut world, _) = capsule_player_world(floor, 3.0);
 
-        // Player that's grounded
-        let mut player = RigidBody4D::new_sphere(Vec4::new(0.0, 0.5, 0.0, 0.0), 0.5)
-            .with_body_type(crate::body::BodyType::Kinematic);
-        player.grounded = true; // Manually set grounded for test
-        let player_key = world.add_body(player);
-        world.set_player_body(player_key);
+        for _ in 0..120 {
+            world.step(1.0 / 60.0);
+        }
 
-        // Jump
-        world.player_jump();
+        assert!(world.player_is_grounded());
+        let y = world.player_position().unwrap().y;
+        assert!((y - 1.0).abs() < 0.01, "capsule sank or floated: y = {}", y);
+    }
 
-        // Check custom velocity used
-        let vel = world.player().unwrap().velocity;
-        assert_eq!(vel.y, 15.0);
+    #[test]
+    fn test_capsule_player_pushes_sphere_body() {
+        let (mut world, _) = capsule_player_world(StaticCollider::floor(-10.0, PhysicsMaterial::CONCRETE), 0.0);
+        world.config.gravity_vector = Vec4::ZERO;
+        let ball = world.add_body(RigidBody4D::new_sphere(Vec4::new(0.8, 0.4, 0.0, 0.0), 0.5));
+
+        world.step(0.0);
+
+        // Pushed out along X to touch the side of the capsule
+        let ball = world.get_body(ball).unwrap();
+        assert!((ball.position.x - 1.0).abs() < 0.0001);
+        assert_eq!(world.player_position(), Some(Vec4::ZERO));
     }
 
     // ====== Collision Filtering Tests ======
@@ -1042,6 +2667,64 @@ mod tests {
         assert!(body.position.y < 0.5, "Body should fall through trigger zone");
     }
 
+    #[test]
+    fn test_custom_layer_static_blocks_only_bodies_masking_it() {
+        use crate::collision::{CollisionFilter, CollisionLayer};
+
+        // A ghost wall at x = 1 that only enemies run into
+        let ghost = CollisionLayer::custom(0);
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0));
+        world.add_static_collider(
+            StaticCollider::aabb(Vec4::new(1.5, 0.0, 0.0, 0.0), Vec4::new(0.5, 2.0, 2.0, 2.0), PhysicsMaterial::CONCRETE)
+                .with_layer(ghost)
+                .with_mask(CollisionLayer::ENEMY),
+        );
+
+        let moving = |filter: CollisionFilter, z: f32| {
+            RigidBody4D::new_sphere(Vec4::new(0.0, 0.0, z, 0.0), 0.25)
+                .with_filter(filter)
+                .with_velocity(Vec4::new(6.0, 0.0, 0.0, 0.0))
+        };
+        let enemy = world.add_body(moving(CollisionFilter::enemy(), -1.0));
+        let player = world.add_body(moving(CollisionFilter::player(), 1.0));
+        // An enemy whose own mask leaves the ghost layer out is not blocked either
+        let phasing = world.add_body(moving(
+            CollisionFilter::new(CollisionLayer::ENEMY, CollisionLayer::ALL & !ghost),
+            0.0,
+        ));
+
+        for _ in 0..30 {
+            world.step(1.0 / 60.0);
+        }
+
+        assert!(world.get_body(enemy).unwrap().position.x <= 0.75 + 1e-4);
+        assert!(world.get_body(player).unwrap().position.x > 2.0);
+        assert!(world.get_body(phasing).unwrap().position.x > 2.0);
+    }
+
+    #[test]
+    fn test_trigger_static_on_custom_layer_reports_without_blocking() {
+        use crate::collision::{CollisionFilter, CollisionLayer};
+
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0));
+        world.add_static_collider(
+            StaticCollider::aabb(Vec4::ZERO, Vec4::new(1.0, 1.0, 1.0, 1.0), PhysicsMaterial::CONCRETE)
+                .with_filter(CollisionFilter::trigger(CollisionLayer::custom(3))),
+        );
+        let key = world.add_body(
+            RigidBody4D::new_sphere(Vec4::new(0.5, 0.0, 0.0, 0.0), 0.25)
+                .with_layer(CollisionLayer::custom(3))
+                .with_mask(CollisionLayer::ALL & !CollisionLayer::TRIGGER)
+                .with_velocity(Vec4::new(1.0, 0.0, 0.0, 0.0)),
+        );
+
+        world.step(0.1);
+
+        assert_eq!(world.collision_events().len(), 1);
+        assert_eq!(world.collision_events()[0].a, key);
+        assert!((world.get_body(key).unwrap().position.x - 0.6).abs() < 1e-5);
+    }
+
     #[test]
     fn test_collision_filter_body_body_skip() {
         use crate::collision::CollisionFilter;
@@ -1193,47 +2876,539 @@ mod tests {
             "Kinematic body should not be pushed by dynamic body"
         );
 
-        // Dynamic should have bounced back or stopped (not passed through)
-        assert!(
-            dynamic_body.position.x >= kinematic_body.position.x + 0.9, // At least radius distance away
-            "Dynamic body should be separated from kinematic"
-        );
+        // Dynamic should have bounced back or stopped (not passed through)
+        assert!(
+            dynamic_body.position.x >= kinematic_body.position.x + 0.9, // At least radius distance away
+            "Dynamic body should be separated from kinematic"
+        );
+    }
+
+    #[test]
+    fn test_kinematic_velocity_not_modified() {
+        // Kinematic body velocity should be unchanged after collision with dynamic
+        use crate::body::BodyType;
+
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0)); // No gravity
+
+        let initial_velocity = Vec4::new(3.0, 0.0, 0.0, 0.0);
+
+        // Kinematic body moving right
+        let kinematic = RigidBody4D::new_sphere(Vec4::new(0.0, 0.0, 0.0, 0.0), 0.5)
+            .with_body_type(BodyType::Kinematic)
+            .with_velocity(initial_velocity);
+        let key_kinematic = world.add_body(kinematic);
+
+        // Dynamic body in the way
+        let dynamic = RigidBody4D::new_sphere(Vec4::new(0.8, 0.0, 0.0, 0.0), 0.5)
+            .with_body_type(BodyType::Dynamic);
+        world.add_body(dynamic);
+
+        // Step physics - collision should occur
+        for _ in 0..5 {
+            world.step(0.016);
+        }
+
+        let kinematic_body = world.get_body(key_kinematic).unwrap();
+
+        // Kinematic velocity should be unchanged (user-controlled)
+        assert!(
+            (kinematic_body.velocity - initial_velocity).length() < 0.001,
+            "Kinematic velocity should not be modified by collision. Expected {:?}, got {:?}",
+            initial_velocity,
+            kinematic_body.velocity
+        );
+    }
+
+    // ====== Push Priority Tests ======
+
+    /// Two overlapping dynamic spheres: pusher at the origin moving +X, object 0.8 to its right
+    fn make_push_pair(pusher_priority: u8, object_priority: u8) -> (PhysicsWorld, BodyKey, BodyKey) {
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0)); // No gravity
+
+        let pusher = RigidBody4D::new_sphere(Vec4::ZERO, 0.5)
+            .with_velocity(Vec4::new(3.0, 0.0, 0.0, 0.0))
+            .with_push_priority(pusher_priority);
+        let object = RigidBody4D::new_sphere(Vec4::new(0.8, 0.0, 0.0, 0.0), 0.5)
+            .with_push_priority(object_priority);
+
+        let key_pusher = world.add_body(pusher);
+        let key_object = world.add_body(object);
+        (world, key_pusher, key_object)
+    }
+
+    #[test]
+    fn test_higher_priority_takes_no_correction() {
+        let (mut world, key_pusher, key_object) = make_push_pair(10, 0);
+
+        // Zero dt: only the overlap resolution moves bodies
+        world.step(0.0);
+
+        let pusher = world.get_body(key_pusher).unwrap();
+        let object = world.get_body(key_object).unwrap();
+
+        // The object takes the whole 0.2 penetration, the pusher holds its ground
+        assert!(pusher.position.x.abs() < 0.0001);
+        assert!((object.position.x - 1.0).abs() < 0.0001);
+        // The pusher keeps its velocity
+        assert!((pusher.velocity.x - 3.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_equal_priority_splits_by_mass() {
+        let (mut world, key_pusher, key_object) = make_push_pair(0, 0);
+
+        world.step(0.0);
+
+        let pusher = world.get_body(key_pusher).unwrap();
+        let object = world.get_body(key_object).unwrap();
+
+        // Equal masses split the 0.2 penetration evenly
+        assert!((pusher.position.x + 0.1).abs() < 0.0001);
+        assert!((object.position.x - 0.9).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_priority_overrides_mass() {
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0));
+
+        // A light high-priority body against a very heavy low-priority one
+        let light = RigidBody4D::new_sphere(Vec4::ZERO, 0.5)
+            .with_mass(0.1)
+            .with_push_priority(1);
+        let heavy = RigidBody4D::new_sphere(Vec4::new(0.8, 0.0, 0.0, 0.0), 0.5)
+            .with_mass(1000.0);
+        let key_light = world.add_body(light);
+        let key_heavy = world.add_body(heavy);
+
+        world.step(0.0);
+
+        assert!(world.get_body(key_light).unwrap().position.x.abs() < 0.0001);
+        assert!((world.get_body(key_heavy).unwrap().position.x - 1.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_player_pushes_object_without_slowing() {
+        let (mut world, key_pusher, key_object) = make_push_pair(0, 0);
+        world.set_player_body(key_pusher);
+        assert_eq!(world.get_body(key_pusher).unwrap().push_priority, PLAYER_PUSH_PRIORITY);
+
+        for _ in 0..10 {
+            world.step(0.016);
+        }
+
+        let player = world.get_body(key_pusher).unwrap();
+        let object = world.get_body(key_object).unwrap();
+
+        // Player kept moving at full speed and the object stays ahead of it
+        assert!((player.velocity.x - 3.0).abs() < 0.0001);
+        assert!((player.position.x - 0.48).abs() < 0.001);
+        assert!(object.position.x >= player.position.x + 1.0 - 0.0001);
+    }
+
+    #[test]
+    fn test_player_push_strength_scales_object_velocity() {
+        let push = |strength: f32| {
+            let (mut world, key_pusher, key_object) = make_push_pair(0, 0);
+            world.config.player_push_strength = strength;
+            world.set_player_body(key_pusher);
+            world.step(0.0);
+            world.get_body(key_object).unwrap().velocity
+        };
+
+        // A strength equal to the object's mass matches the player's 3 units/s
+        let gentle = push(0.5);
+        let matched = push(1.0);
+        let hard = push(2.0);
+        assert!((gentle.x - 1.5).abs() < 0.0001);
+        assert!((matched.x - 3.0).abs() < 0.0001);
+        assert!((hard.x - 6.0).abs() < 0.0001);
+        assert!(gentle.y.abs() + gentle.z.abs() + gentle.w.abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_zero_push_strength_only_separates() {
+        let (mut world, key_pusher, key_object) = make_push_pair(0, 0);
+        world.set_player_body(key_pusher);
+        assert_eq!(world.config.player_push_strength, 0.0);
+
+        world.step(0.0);
+
+        // Moved out of the overlap, but given no velocity
+        let object = world.get_body(key_object).unwrap();
+        assert!((object.position.x - 1.0).abs() < 0.0001);
+        assert_eq!(object.velocity, Vec4::ZERO);
+    }
+
+    #[test]
+    fn test_player_push_ignores_receding_objects() {
+        let (mut world, key_pusher, key_object) = make_push_pair(0, 0);
+        world.config.player_push_strength = 1.0;
+        world.get_body_mut(key_object).unwrap().velocity = Vec4::new(5.0, 0.0, 0.0, 0.0);
+        world.set_player_body(key_pusher);
+
+        world.step(0.0);
+
+        // Already moving away faster than the player: no extra shove
+        assert!((world.get_body(key_object).unwrap().velocity.x - 5.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_set_player_body_keeps_higher_priority() {
+        let mut world = PhysicsWorld::new();
+        let key = world.add_body(
+            RigidBody4D::new_sphere(Vec4::ZERO, 0.5).with_push_priority(u8::MAX),
+        );
+        world.set_player_body(key);
+        assert_eq!(world.get_body(key).unwrap().push_priority, u8::MAX);
+    }
+
+    // ====== Angular Velocity Tests ======
+
+    #[test]
+    fn test_angular_velocity_accumulates_rotation() {
+        use rust4d_math::{Bivector4, RotationPlane};
+
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0));
+        let rate = Bivector4::from_plane(RotationPlane::ZW, 1.0);
+        let start = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        let key = world.add_body(RigidBody4D::new_sphere(start, 0.5).with_angular_velocity(rate));
+
+        for _ in 0..10 {
+            world.step(0.1);
+        }
+
+        // One radian in ZW after a second, without moving
+        let body = world.get_body(key).unwrap();
+        assert_eq!(body.position, start);
+        let expected = Rotor4::from_plane_angle(RotationPlane::ZW, 1.0);
+        let v = Vec4::new(0.0, 0.0, 1.0, 0.0);
+        assert!((body.orientation.rotate(v) - expected.rotate(v)).length() < 1e-4);
+        assert!((body.orientation.magnitude() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_frozen_body_does_not_rotate() {
+        use rust4d_math::{Bivector4, RotationPlane};
+
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0));
+        let mut body = RigidBody4D::new_sphere(Vec4::ZERO, 0.5)
+            .with_angular_velocity(Bivector4::from_plane(RotationPlane::XY, 2.0));
+        body.set_frozen(true);
+        let key = world.add_body(body);
+
+        world.step(0.5);
+
+        let v = Vec4::X;
+        assert_eq!(world.get_body(key).unwrap().orientation.rotate(v), v);
+    }
+
+    // ====== Collision Event Tests ======
+
+    #[test]
+    fn test_sphere_landing_reports_one_floor_event() {
+        let mut world = world_with_floor(-20.0, 0.0, PhysicsMaterial::CONCRETE);
+        let key = world.add_body(RigidBody4D::new_sphere(Vec4::new(0.0, 2.0, 0.0, 0.0), 0.5));
+
+        let mut steps = 0;
+        while world.collision_events().is_empty() {
+            world.step(1.0 / 60.0);
+            steps += 1;
+            assert!(steps < 120, "Sphere never reached the floor");
+        }
+
+        let events = world.collision_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].a, key);
+        assert_eq!(events[0].b, None);
+        assert!(events[0].normal.y > 0.99);
+        assert!(events[0].penetration > 0.0);
+    }
+
+    #[test]
+    fn test_trigger_reports_event_without_pushing() {
+        use crate::collision::CollisionLayer;
+
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0));
+        let trigger = StaticCollider::floor(0.0, PhysicsMaterial::CONCRETE)
+            .with_filter(CollisionFilter::trigger(CollisionLayer::PLAYER));
+        world.add_static_collider(trigger);
+        // Players don't collide with triggers, but the trigger detects them
+        let body = RigidBody4D::new_sphere(Vec4::new(0.0, 0.25, 0.0, 0.0), 0.5)
+            .with_filter(CollisionFilter::player());
+        let key = world.add_body(body);
+
+        world.step(1.0 / 60.0);
+
+        assert_eq!(world.collision_events().len(), 1);
+        assert_eq!(world.collision_events()[0].a, key);
+        assert_eq!(world.get_body(key).unwrap().position.y, 0.25);
+    }
+
+    #[test]
+    fn test_body_pair_event_and_clearing() {
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0));
+        let a = world.add_body(RigidBody4D::new_sphere(Vec4::ZERO, 1.0));
+        let b = world.add_body(RigidBody4D::new_sphere(Vec4::new(1.5, 0.0, 0.0, 0.0), 1.0));
+
+        world.step(1.0 / 60.0);
+
+        let events = world.collision_events().to_vec();
+        assert_eq!(events.len(), 1);
+        assert_eq!((events[0].a, events[0].b), (a, Some(b)));
+        // Points from b toward a
+        assert!(events[0].normal.x < -0.99);
+
+        // Pushed apart, so the next step reports nothing
+        world.step(1.0 / 60.0);
+        assert!(world.collision_events().is_empty());
+    }
+
+    // ====== Impulse and Force Tests ======
+
+    #[test]
+    fn test_impulse_changes_velocity_by_impulse_over_mass() {
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0));
+        let mass = 4.0;
+        let key = world.add_body(RigidBody4D::new_sphere(Vec4::ZERO, 0.5).with_mass(mass));
+
+        world.apply_impulse(key, Vec4::new(0.0, 0.0, 0.0, mass * 5.0));
+        assert_eq!(world.get_body(key).unwrap().velocity, Vec4::new(0.0, 0.0, 0.0, 5.0));
+    }
+
+    #[test]
+    fn test_impulse_ignored_by_static_and_kinematic_bodies() {
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0));
+        let wall = world.add_body(RigidBody4D::new_static_aabb(Vec4::ZERO, Vec4::new(1.0, 1.0, 1.0, 1.0)));
+        let mover = world.add_body(
+            RigidBody4D::new_sphere(Vec4::new(5.0, 0.0, 0.0, 0.0), 0.5).with_body_type(BodyType::Kinematic),
+        );
+
+        for key in [wall, mover] {
+            world.apply_impulse(key, Vec4::X * 10.0);
+            world.apply_force(key, Vec4::X * 10.0);
+        }
+        world.step(0.1);
+
+        assert_eq!(world.get_body(wall).unwrap().velocity, Vec4::ZERO);
+        assert_eq!(world.get_body(mover).unwrap().velocity, Vec4::ZERO);
+    }
+
+    #[test]
+    fn test_forces_accumulate_within_a_frame_and_clear_after_step() {
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0));
+        let key = world.add_body(RigidBody4D::new_sphere(Vec4::ZERO, 0.5).with_mass(2.0));
+
+        world.apply_force(key, Vec4::new(3.0, 0.0, 0.0, 0.0));
+        world.apply_force(key, Vec4::new(1.0, 0.0, 2.0, 0.0));
+        assert_eq!(world.get_body(key).unwrap().force, Vec4::new(4.0, 0.0, 2.0, 0.0));
+
+        // (4, 0, 2, 0) / 2 kg over 0.5 s
+        world.step(0.5);
+        let body = world.get_body(key).unwrap();
+        assert_eq!(body.velocity, Vec4::new(1.0, 0.0, 0.5, 0.0));
+        assert_eq!(body.force, Vec4::ZERO);
+
+        // Without reapplying, the velocity stays put
+        world.step(0.5);
+        assert_eq!(world.get_body(key).unwrap().velocity, Vec4::new(1.0, 0.0, 0.5, 0.0));
+    }
+
+    // ====== Damping Tests ======
+
+    #[test]
+    fn test_linear_damping_slows_body() {
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0));
+        let velocity = Vec4::new(10.0, 0.0, 0.0, 0.0);
+        let damped = world.add_body(RigidBody4D::new_sphere(Vec4::ZERO, 0.5).with_velocity(velocity).with_damping(0.5, 0.0));
+        let undamped = world.add_body(RigidBody4D::new_sphere(Vec4::new(0.0, 5.0, 0.0, 0.0), 0.5).with_velocity(velocity));
+
+        for _ in 0..100 {
+            world.step(0.01);
+        }
+
+        // (1 - 0.005)^100 ~= 0.606, close to e^-0.5
+        let speed = world.get_body(damped).unwrap().velocity.length();
+        assert!(speed < 6.1 && speed > 6.0, "speed = {}", speed);
+        assert_eq!(world.get_body(undamped).unwrap().velocity, velocity);
+
+        for _ in 0..1000 {
+            world.step(0.01);
+        }
+        assert!(world.get_body(damped).unwrap().velocity.length() < 0.1);
+    }
+
+    #[test]
+    fn test_damping_never_reverses_velocity() {
+        use rust4d_math::{Bivector4, RotationPlane};
+
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0));
+        let key = world.add_body(
+            RigidBody4D::new_sphere(Vec4::ZERO, 0.5)
+                .with_velocity(Vec4::X)
+                .with_angular_velocity(Bivector4::from_plane(RotationPlane::XY, 1.0))
+                .with_damping(50.0, 50.0),
+        );
+
+        world.step(0.1);
+        let body = world.get_body(key).unwrap();
+        assert_eq!(body.velocity, Vec4::ZERO);
+        assert!(body.angular_velocity.is_zero());
+    }
+
+    // ====== Substep Tests ======
+
+    /// Deepest contact while a fast ball bounces between a floor and a ceiling 2 units apart
+    fn max_bounce_penetration(substeps: u32) -> f32 {
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(-20.0).with_substeps(substeps));
+        world.add_static_collider(StaticCollider::floor(0.0, PhysicsMaterial::RUBBER));
+        world.add_static_collider(StaticCollider::plane(-Vec4::Y, -2.0, PhysicsMaterial::RUBBER));
+        let key = world.add_body(
+            RigidBody4D::new_sphere(Vec4::new(0.0, 1.0, 0.0, 0.0), 0.5).with_material(PhysicsMaterial::RUBBER),
+        );
+        world.get_body_mut(key).unwrap().velocity = Vec4::new(0.0, 30.0, 0.0, 0.0);
+
+        let mut deepest: f32 = 0.0;
+        for _ in 0..60 {
+            world.step(1.0 / 60.0);
+            for event in world.collision_events() {
+                deepest = deepest.max(event.penetration);
+            }
+        }
+        deepest
+    }
+
+    #[test]
+    fn test_substeps_reduce_penetration() {
+        let single = max_bounce_penetration(1);
+        let four = max_bounce_penetration(4);
+        assert!(single > 0.1, "one substep should sink noticeably, got {}", single);
+        assert!(four < single * 0.5, "4 substeps: {} vs 1 substep: {}", four, single);
+    }
+
+    #[test]
+    fn test_substeps_match_shorter_steps() {
+        let run = |substeps: u32, steps: usize| {
+            let mut world = world_with_floor(-20.0, 0.0, PhysicsMaterial::WOOD);
+            world.config.substeps = substeps;
+            // Lands on the floor during the step
+            let key = world.add_body(RigidBody4D::new_sphere(Vec4::new(0.0, 0.55, 0.0, 0.0), 0.5));
+            for _ in 0..steps {
+                world.step(0.1 / steps as f32);
+            }
+            world.get_body(key).unwrap().position
+        };
+        assert_eq!(run(2, 1), run(1, 2));
+        assert!(run(2, 1).y < 0.55);
+        assert_eq!(run(0, 1), run(1, 1));
+    }
+
+    // ====== Floor Lift Tests ======
+
+    #[test]
+    fn test_lift_above_floor_raises_sunken_position() {
+        let mut world = PhysicsWorld::new();
+        world.add_static_collider(StaticCollider::floor_bounded(0.0, 10.0, 10.0, 5.0, PhysicsMaterial::CONCRETE));
+
+        let lifted = world.lift_above_floor(Vec4::new(1.0, -2.0, 2.0, 3.0), 0.5, 0.1);
+        assert_eq!(lifted, Vec4::new(1.0, 0.6, 2.0, 3.0));
+
+        // Already clear of the floor, or off its edge
+        let above = Vec4::new(1.0, 3.0, 2.0, 3.0);
+        assert_eq!(world.lift_above_floor(above, 0.5, 0.1), above);
+        let beside = Vec4::new(20.0, -2.0, 0.0, 0.0);
+        assert_eq!(world.lift_above_floor(beside, 0.5, 0.1), beside);
+    }
+
+    #[test]
+    fn test_lift_above_floor_clears_stacked_floors() {
+        let mut world = PhysicsWorld::new();
+        world.add_static_collider(StaticCollider::floor(0.0, PhysicsMaterial::CONCRETE));
+        world.add_static_collider(StaticCollider::aabb(
+            Vec4::new(0.0, 0.5, 0.0, 0.0),
+            Vec4::new(1.0, 0.5, 1.0, 1.0),
+            PhysicsMaterial::WOOD,
+        ));
+
+        // Lifting off the plane lands inside the box, which lifts again
+        let lifted = world.lift_above_floor(Vec4::new(0.0, -1.0, 0.0, 0.0), 0.5, 0.0);
+        assert_eq!(lifted, Vec4::new(0.0, 1.5, 0.0, 0.0));
     }
 
     #[test]
-    fn test_kinematic_velocity_not_modified() {
-        // Kinematic body velocity should be unchanged after collision with dynamic
-        use crate::body::BodyType;
+    fn test_lift_above_floor_follows_gravity() {
+        let config = PhysicsConfig::default().with_gravity_vector(Vec4::new(0.0, 0.0, 0.0, 20.0));
+        let mut world = PhysicsWorld::with_config(config);
+        // A W-facing surface at w = 3, and a box whose -W face is at w = 1
+        world.add_static_collider(StaticCollider::plane(-Vec4::W, -3.0, PhysicsMaterial::CONCRETE));
+        world.add_static_collider(StaticCollider::aabb(
+            Vec4::new(10.0, 0.0, 0.0, 2.0),
+            Vec4::new(1.0, 1.0, 1.0, 1.0),
+            PhysicsMaterial::WOOD,
+        ));
 
-        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0)); // No gravity
+        // Sunk past the plane: lifted back along -W
+        let lifted = world.lift_above_floor(Vec4::new(0.0, 0.0, 0.0, 4.0), 0.5, 0.0);
+        assert_eq!(lifted, Vec4::new(0.0, 0.0, 0.0, 2.5));
 
-        let initial_velocity = Vec4::new(3.0, 0.0, 0.0, 0.0);
+        // Inside the box: lifted out of its -W face
+        let lifted = world.lift_above_floor(Vec4::new(10.0, 0.5, 0.0, 2.5), 0.5, 0.0);
+        assert_eq!(lifted, Vec4::new(10.0, 0.5, 0.0, 0.5));
 
-        // Kinematic body moving right
-        let kinematic = RigidBody4D::new_sphere(Vec4::new(0.0, 0.0, 0.0, 0.0), 0.5)
-            .with_body_type(BodyType::Kinematic)
-            .with_velocity(initial_velocity);
-        let key_kinematic = world.add_body(kinematic);
+        // The Y floor does not count as a floor under W gravity
+        world.add_static_collider(StaticCollider::floor(5.0, PhysicsMaterial::CONCRETE));
+        let beside = Vec4::new(-10.0, 0.0, 0.0, 0.0);
+        assert_eq!(world.lift_above_floor(beside, 0.5, 0.0), beside);
+    }
 
-        // Dynamic body in the way
-        let dynamic = RigidBody4D::new_sphere(Vec4::new(0.8, 0.0, 0.0, 0.0), 0.5)
-            .with_body_type(BodyType::Dynamic);
-        world.add_body(dynamic);
+    // ====== Frozen Body Tests ======
 
-        // Step physics - collision should occur
-        for _ in 0..5 {
+    #[test]
+    fn test_frozen_body_does_not_fall() {
+        let mut world = PhysicsWorld::new();
+        let start = Vec4::new(0.0, 10.0, 0.0, 0.0);
+        let key = world.add_body(RigidBody4D::new_sphere(start, 0.5));
+        world.get_body_mut(key).unwrap().set_frozen(true);
+
+        for _ in 0..10 {
             world.step(0.016);
         }
 
-        let kinematic_body = world.get_body(key_kinematic).unwrap();
+        let body = world.get_body(key).unwrap();
+        assert!(body.is_frozen());
+        assert_eq!(body.position, start);
+        assert_eq!(body.velocity, Vec4::ZERO);
+    }
 
-        // Kinematic velocity should be unchanged (user-controlled)
-        assert!(
-            (kinematic_body.velocity - initial_velocity).length() < 0.001,
-            "Kinematic velocity should not be modified by collision. Expected {:?}, got {:?}",
-            initial_velocity,
-            kinematic_body.velocity
+    #[test]
+    fn test_unfreezing_resumes_prior_velocity() {
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0)); // No gravity
+        let velocity = Vec4::new(2.0, 0.0, 0.0, 0.0);
+        let key = world.add_body(RigidBody4D::new_sphere(Vec4::ZERO, 0.5).with_velocity(velocity));
+
+        world.get_body_mut(key).unwrap().set_frozen(true);
+        world.step(0.1);
+        assert_eq!(world.get_body(key).unwrap().position, Vec4::ZERO);
+        assert_eq!(world.get_body(key).unwrap().velocity, velocity);
+
+        world.get_body_mut(key).unwrap().set_frozen(false);
+        world.step(0.1);
+        let body = world.get_body(key).unwrap();
+        assert!((body.position.x - 0.2).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_frozen_body_pushes_but_is_not_pushed() {
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0));
+        let frozen = world.add_body(RigidBody4D::new_sphere(Vec4::ZERO, 0.5).with_mass(0.1));
+        let other = world.add_body(
+            RigidBody4D::new_sphere(Vec4::new(0.8, 0.0, 0.0, 0.0), 0.5).with_mass(100.0),
         );
+        world.get_body_mut(frozen).unwrap().set_frozen(true);
+
+        world.step(0.0);
+
+        assert_eq!(world.get_body(frozen).unwrap().position, Vec4::ZERO);
+        assert!((world.get_body(other).unwrap().position.x - 1.0).abs() < 0.0001);
     }
 
     // ====== Edge Falling Tests ======
@@ -1472,4 +3647,653 @@ mod tests {
             final_y
         );
     }
+
+    // ====== Determinism Mode Tests ======
+
+    /// Add a pile of overlapping bodies above a floor
+    fn add_pile(world: &mut PhysicsWorld) -> Vec<BodyKey> {
+        let mut keys = Vec::new();
+        for i in 0..6 {
+            let offset = i as f32 * 0.37;
+            let body = if i % 2 == 0 {
+                RigidBody4D::new_sphere(Vec4::new(offset, 2.0 + offset, -offset, 0.1 * offset), 0.5)
+            } else {
+                RigidBody4D::new_aabb(Vec4::new(-offset, 1.5 + offset, offset, 0.0), Vec4::new(0.4, 0.4, 0.4, 0.4))
+            };
+            keys.push(world.add_body(body.with_velocity(Vec4::new(1.0 - offset, 0.0, offset, -0.5))));
+        }
+        keys
+    }
+
+    fn run_deterministic(steps: usize) -> Vec<[u32; 4]> {
+        let config = PhysicsConfig::default().with_deterministic(true);
+        let mut world = PhysicsWorld::with_config(config);
+        world.add_static_collider(StaticCollider::floor(0.0, PhysicsMaterial::RUBBER));
+        let keys = add_pile(&mut world);
+        world.set_player_body(keys[0]);
+
+        for step in 0..steps {
+            let angle = step as f32 * 0.1;
+            world.apply_player_movement(Vec4::new(angle.cos(), 0.0, angle.sin(), 0.0) * 3.0);
+            if step % 40 == 0 {
+                world.player_jump();
+            }
+            world.step(1.0 / 60.0);
+        }
+
+        keys.iter()
+            .map(|&key| {
+                let p = world.get_body(key).unwrap().position;
+                [p.x.to_bits(), p.y.to_bits(), p.z.to_bits(), p.w.to_bits()]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_deterministic_runs_are_bit_identical() {
+        assert_eq!(run_deterministic(300), run_deterministic(300));
+    }
+
+    #[test]
+    fn test_deterministic_state_is_on_grid() {
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::default().with_deterministic(true));
+        let keys = add_pile(&mut world);
+        world.step(0.0173);
+
+        for key in keys {
+            let body = world.get_body(key).unwrap();
+            for c in [body.position.x, body.position.y, body.velocity.x, body.velocity.y] {
+                let scaled = c / DETERMINISM_QUANTUM;
+                assert_eq!(scaled, scaled.round(), "{} is not on the grid", c);
+            }
+        }
+    }
+
+    #[test]
+    fn test_deterministic_order_ignores_slot_reuse() {
+        let config = PhysicsConfig::default().with_deterministic(true);
+        let bodies = || {
+            (0..3).map(|i| RigidBody4D::new_sphere(Vec4::new(i as f32 * 0.6, 1.0, 0.0, 0.0), 0.5))
+        };
+
+        // Fresh world: slots match insertion order
+        let mut fresh = PhysicsWorld::with_config(config.clone());
+        let fresh_keys: Vec<BodyKey> = bodies().map(|b| fresh.add_body(b)).collect();
+
+        // Reused world: the last body lands in a freed earlier slot
+        let mut reused = PhysicsWorld::with_config(config);
+        let mut bodies = bodies();
+        let first = reused.add_body(bodies.next().unwrap());
+        let temp = reused.add_body(RigidBody4D::new_sphere(Vec4::new(50.0, 0.0, 0.0, 0.0), 0.5));
+        let second = reused.add_body(bodies.next().unwrap());
+        reused.remove_body(temp);
+        let third = reused.add_body(bodies.next().unwrap());
+        let reused_keys = [first, second, third];
+
+        for _ in 0..30 {
+            fresh.step(1.0 / 60.0);
+            reused.step(1.0 / 60.0);
+        }
+        for (a, b) in fresh_keys.iter().zip(reused_keys) {
+            assert_eq!(fresh.get_body(*a).unwrap().position, reused.get_body(b).unwrap().position);
+        }
+    }
+
+    // ====== Static Broadphase Tests ======
+
+    /// A row of small floors 40 units apart along X, at varying heights
+    fn world_with_floor_row(count: usize) -> PhysicsWorld {
+        let mut world = PhysicsWorld::new();
+        for i in 0..count {
+            let top = (i % 3) as f32 * 0.5;
+            world.add_static_collider(StaticCollider::aabb(
+                Vec4::new(i as f32 * 40.0, top - 1.0, 0.0, 0.0),
+                Vec4::new(3.0, 1.0, 3.0, 3.0),
+                PhysicsMaterial::WOOD,
+            ));
+        }
+        world
+    }
+
+    #[test]
+    fn test_static_grid_limits_narrowphase_tests() {
+        let mut world = world_with_floor_row(50);
+        world.add_body(RigidBody4D::new_sphere(Vec4::new(80.0, 1.3, 0.0, 0.0), 0.5));
+
+        world.step(0.016);
+        assert_eq!(world.static_collision_tests(), 1);
+
+        // Infinite planes are always tested
+        world.add_static_collider(StaticCollider::floor(-10.0, PhysicsMaterial::CONCRETE));
+        world.step(0.016);
+        assert_eq!(world.static_collision_tests(), 2);
+    }
+
+    #[test]
+    fn test_huge_static_collider_tested_against_every_body() {
+        let mut world = PhysicsWorld::new();
+        world.add_static_collider(StaticCollider::floor_bounded(0.0, 1000.0, 1000.0, 5.0, PhysicsMaterial::CONCRETE));
+        assert_eq!(world.unbounded_statics, vec![0]);
+        assert!(world.static_grid.is_empty());
+    }
+
+    #[test]
+    fn test_static_grid_matches_brute_force() {
+        let build = || {
+            let mut world = world_with_floor_row(30);
+            world.add_static_collider(StaticCollider::floor(-20.0, PhysicsMaterial::CONCRETE));
+            let mut keys = Vec::new();
+            for i in 0..30 {
+                // Some bodies land on floors, some fall between them onto the plane
+                let x = i as f32 * 13.0;
+                let body = if i % 2 == 0 {
+                    RigidBody4D::new_sphere(Vec4::new(x, 3.0, 0.5, 0.0), 0.5)
+                } else {
+                    RigidBody4D::new_aabb(Vec4::new(x, 4.0, -0.5, 0.0), Vec4::new(0.5, 0.5, 0.5, 0.5))
+                };
+                keys.push(world.add_body(body.with_velocity(Vec4::new(2.0, 0.0, 0.0, 1.0))));
+            }
+            (world, keys)
+        };
+
+        let (mut grid, keys) = build();
+        let (mut brute, _) = build();
+        brute.static_grid.clear();
+        brute.unbounded_statics = (0..brute.static_colliders.len()).collect();
+
+        for _ in 0..120 {
+            grid.step(1.0 / 60.0);
+            brute.step(1.0 / 60.0);
+        }
+
+        assert!(grid.static_collision_tests() < brute.static_collision_tests());
+        for key in keys {
+            let (a, b) = (grid.get_body(key).unwrap(), brute.get_body(key).unwrap());
+            assert_eq!(a.position, b.position);
+            assert_eq!(a.velocity, b.velocity);
+            assert_eq!(a.grounded, b.grounded);
+        }
+    }
+
+    // ====== Body Broadphase Tests ======
+
+    /// 500 small spheres in isolated clusters, many overlapping their neighbours
+    fn sphere_swarm(config: PhysicsConfig) -> (PhysicsWorld, Vec<BodyKey>) {
+        let mut world = PhysicsWorld::with_config(config);
+        let mut keys = Vec::new();
+        for i in 0..500 {
+            let cluster = (i / 5) as f32;
+            let offset = (i % 5) as f32 * 0.7;
+            let position = Vec4::new(cluster * 6.0, 0.0, offset, (cluster * 0.37).sin() * 3.0);
+            let body = RigidBody4D::new_sphere(position, 0.5).with_velocity(Vec4::new(0.0, 0.0, (i % 3) as f32 - 1.0, 0.0));
+            keys.push(world.add_body(body));
+        }
+        (world, keys)
+    }
+
+    #[test]
+    fn test_body_grid_matches_brute_force() {
+        let (mut grid, keys) = sphere_swarm(PhysicsConfig::new(0.0));
+        let (mut brute, _) = sphere_swarm(PhysicsConfig::new(0.0).with_brute_force_broadphase(true));
+
+        for _ in 0..30 {
+            grid.step(1.0 / 60.0);
+            brute.step(1.0 / 60.0);
+            // 500 choose 2 pairs without the grid
+            assert_eq!(brute.body_collision_tests(), 124_750);
+            assert!(grid.body_collision_tests() * 50 < brute.body_collision_tests());
+        }
+
+        for key in keys {
+            let (a, b) = (grid.get_body(key).unwrap(), brute.get_body(key).unwrap());
+            assert_eq!(a.position, b.position);
+            assert_eq!(a.velocity, b.velocity);
+        }
+    }
+
+    #[test]
+    fn test_body_grid_cell_size_change_rebuilds_grid() {
+        let (mut world, keys) = sphere_swarm(PhysicsConfig::new(0.0));
+        world.config.body_grid_cell_size = 1.0;
+        world.step(1.0 / 60.0);
+        assert_eq!(world.body_grid.cell_size(), 1.0);
+        assert_eq!(world.body_grid.len(), keys.len());
+    }
+
+    #[test]
+    fn test_oversized_body_collides_through_grid() {
+        // The box spans 21^4 cells, too many for the grid
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(0.0).with_body_grid_cell_size(0.5));
+        let big = world.add_body(RigidBody4D::new_static_aabb(Vec4::ZERO, Vec4::new(5.0, 5.0, 5.0, 5.0)));
+        let small = world.add_body(RigidBody4D::new_sphere(Vec4::new(5.2, 0.0, 0.0, 0.0), 0.5));
+
+        world.step(1.0 / 60.0);
+        assert!(!world.body_grid.contains(0));
+        assert!(world.body_grid.contains(1));
+        assert_eq!(world.get_body(big).unwrap().position, Vec4::ZERO);
+        assert!(world.get_body(small).unwrap().position.x >= 5.5 - 1e-4);
+    }
+
+    #[test]
+    fn test_huge_aabb_does_not_overflow_grid() {
+        // Spans far more cells than fit in a u64; must be kept out of both grids
+        let mut world = PhysicsWorld::new();
+        let huge = Vec4::new(1e6, 1e6, 1e6, 1e6);
+        world.add_static_collider(StaticCollider::aabb(Vec4::ZERO, huge, PhysicsMaterial::CONCRETE));
+        world.add_body(RigidBody4D::new_static_aabb(Vec4::ZERO, huge));
+        world.step(1.0 / 60.0);
+        assert!(!world.body_grid.contains(0));
+        assert_eq!(world.unbounded_statics, vec![0]);
+    }
+
+    #[test]
+    fn test_non_positive_cell_size_rejected() {
+        use serde::de::value::{Error, F32Deserializer};
+        use serde::de::IntoDeserializer;
+        let cell_size = |v: f32| {
+            let deserializer: F32Deserializer<Error> = v.into_deserializer();
+            deserialize_cell_size(deserializer)
+        };
+        assert_eq!(cell_size(2.0).unwrap(), 2.0);
+        assert!(cell_size(0.0).is_err());
+        assert!(cell_size(-1.0).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "must be positive")]
+    fn test_with_body_grid_cell_size_panics_on_zero() {
+        let _ = PhysicsConfig::new(0.0).with_body_grid_cell_size(0.0);
+    }
+
+    // ====== Portal Tests ======
+
+    fn no_gravity_world() -> PhysicsWorld {
+        PhysicsWorld::with_config(PhysicsConfig::new(0.0))
+    }
+
+    #[test]
+    fn test_body_exits_portal_with_rotated_velocity() {
+        use rust4d_math::{RotationPlane, Rotor4, Transform4D};
+
+        let mut world = no_gravity_world();
+        let rotation = Rotor4::from_plane_angle(RotationPlane::XW, std::f32::consts::FRAC_PI_2);
+        let exit = Vec4::new(0.0, 0.0, 0.0, 20.0);
+        let portal = world.add_portal(Portal::new(
+            Collider::Sphere(Sphere4D::new(Vec4::new(2.0, 0.0, 0.0, 0.0), 0.5)),
+            Transform4D::from_position_rotation(exit, rotation),
+        ));
+        let mut body = RigidBody4D::new_sphere(Vec4::ZERO, 0.1).with_gravity(false);
+        body.velocity = Vec4::new(6.0, 0.0, 0.0, 0.0);
+        let key = world.add_body(body);
+
+        let mut event = None;
+        for _ in 0..30 {
+            world.step(1.0 / 60.0);
+            if let Some(e) = world.teleport_events().first() {
+                event = Some(*e);
+                break;
+            }
+        }
+
+        let event = event.expect("body never reached the portal");
+        assert_eq!(event.body, key);
+        assert_eq!(event.portal, portal);
+        assert!((event.from - Vec4::new(2.0, 0.0, 0.0, 0.0)).length() <= 0.5);
+
+        let body = world.get_body(key).unwrap();
+        assert_eq!(body.position, event.to);
+        assert!((body.position - exit).length() <= 0.5);
+        assert!((body.velocity - rotation.rotate(Vec4::new(6.0, 0.0, 0.0, 0.0))).length() < 1e-4);
+        assert!((body.collider.center() - body.position).length() < 1e-5);
+    }
+
+    #[test]
+    fn test_portal_cooldown_prevents_immediate_reentry() {
+        use rust4d_math::Transform4D;
+
+        // Two portals whose exits land in each other's entry
+        let mut world = no_gravity_world();
+        let a = Vec4::new(-5.0, 0.0, 0.0, 0.0);
+        let b = Vec4::new(5.0, 0.0, 0.0, 0.0);
+        world.add_portal(Portal::new(Collider::Sphere(Sphere4D::new(a, 1.0)), Transform4D::from_position(b)));
+        world.add_portal(Portal::new(Collider::Sphere(Sphere4D::new(b, 1.0)), Transform4D::from_position(a)));
+        let key = world.add_body(RigidBody4D::new_sphere(a, 0.1).with_gravity(false));
+
+        let dt = 1.0 / 60.0;
+        world.step(dt);
+        assert_eq!(world.teleport_events().len(), 1);
+        assert_eq!(world.get_body(key).unwrap().position, b);
+
+        // Resting inside the second entry, but still cooling down
+        let cooldown_steps = (PORTAL_COOLDOWN / dt) as usize - 1;
+        for _ in 0..cooldown_steps {
+            world.step(dt);
+            assert!(world.teleport_events().is_empty());
+        }
+        assert_eq!(world.get_body(key).unwrap().position, b);
+
+        // Once the cooldown ends the body can use the portal again
+        for _ in 0..2 {
+            world.step(dt);
+        }
+        assert_eq!(world.get_body(key).unwrap().position, a);
+    }
+
+    // ====== Raycast Tests ======
+
+    #[test]
+    fn test_raycast_hits_sphere_on_x_axis() {
+        let mut world = no_gravity_world();
+        let key = world.add_body(RigidBody4D::new_sphere(Vec4::new(5.0, 0.0, 0.0, 0.0), 1.0));
+
+        let (hit, dist, normal) = world.raycast(Vec4::ZERO, Vec4::X, 100.0).expect("ray should hit");
+        assert_eq!(hit, key);
+        assert!((dist - 4.0).abs() < 1e-5);
+        assert!((normal - -Vec4::X).length() < 1e-5);
+
+        // Missing it sideways, or stopping short
+        assert!(world.raycast(Vec4::new(0.0, 3.0, 0.0, 0.0), Vec4::X, 100.0).is_none());
+        assert!(world.raycast(Vec4::ZERO, Vec4::X, 3.0).is_none());
+    }
+
+    #[test]
+    fn test_raycast_returns_nearest_body() {
+        let mut world = no_gravity_world();
+        world.add_body(RigidBody4D::new_sphere(Vec4::new(10.0, 0.0, 0.0, 0.0), 1.0));
+        let near = world.add_body(RigidBody4D::new_aabb(Vec4::new(5.0, 0.0, 0.0, 0.0), Vec4::new(0.5, 0.5, 0.5, 0.5)));
+
+        let (hit, dist, normal) = world.raycast(Vec4::ZERO, Vec4::X, 100.0).unwrap();
+        assert_eq!(hit, near);
+        assert!((dist - 4.5).abs() < 1e-5);
+        assert_eq!(normal, -Vec4::X);
+    }
+
+    #[test]
+    fn test_raycast_filter_and_ignore_player() {
+        use crate::collision::CollisionLayer;
+
+        let mut world = no_gravity_world();
+        let player = world.add_body(RigidBody4D::new_sphere(Vec4::ZERO, 0.5).with_filter(CollisionFilter::player()));
+        world.set_player_body(player);
+        let enemy = world.add_body(
+            RigidBody4D::new_sphere(Vec4::new(5.0, 0.0, 0.0, 0.0), 1.0).with_filter(CollisionFilter::enemy()),
+        );
+
+        // From inside the player, the player is hit first unless ignored
+        assert_eq!(world.raycast(Vec4::ZERO, Vec4::X, 100.0).unwrap().0, player);
+        let (hit, _, _) = world.raycast_filtered(Vec4::ZERO, Vec4::X, 100.0, None, true).unwrap();
+        assert_eq!(hit, enemy);
+
+        // A ray that only looks for pickups passes through the enemy
+        let pickup_ray = CollisionFilter::new(CollisionLayer::DEFAULT, CollisionLayer::PICKUP);
+        assert!(world.raycast_filtered(Vec4::ZERO, Vec4::X, 100.0, Some(&pickup_ray), true).is_none());
+    }
+
+    // ====== Overlap Query Tests ======
+
+    #[test]
+    fn test_overlap_sphere_returns_bodies_within_radius() {
+        let mut world = no_gravity_world();
+        let inside = world.add_body(RigidBody4D::new_sphere(Vec4::new(2.0, 0.0, 0.0, 0.0), 1.0));
+        // Exactly touching the query sphere
+        let touching = world.add_body(RigidBody4D::new_sphere(Vec4::new(0.0, 0.0, 0.0, 4.0), 1.0));
+        world.add_body(RigidBody4D::new_sphere(Vec4::new(0.0, 6.0, 0.0, 0.0), 1.0));
+
+        let mut hits = world.overlap_sphere(Vec4::ZERO, 3.0, CollisionFilter::default());
+        hits.sort();
+        let mut expected = vec![inside, touching];
+        expected.sort();
+        assert_eq!(hits, expected);
+
+        // Shrinking the query drops the touching body
+        assert_eq!(world.overlap_sphere(Vec4::ZERO, 2.5, CollisionFilter::default()), vec![inside]);
+    }
+
+    #[test]
+    fn test_overlap_sphere_other_shapes_and_filter() {
+        use crate::collision::CollisionLayer;
+
+        let mut world = no_gravity_world();
+        let boxed = world.add_body(RigidBody4D::new_aabb(Vec4::new(3.0, 0.0, 0.0, 0.0), Vec4::new(1.0, 1.0, 1.0, 1.0)));
+        let enemy = world.add_body(
+            RigidBody4D::new_capsule(Vec4::new(0.0, 0.0, -2.5, 0.0), 1.0, 0.5).with_filter(CollisionFilter::enemy()),
+        );
+        // Centered on the query point
+        let centered = world.add_body(RigidBody4D::new_sphere(Vec4::ZERO, 0.5));
+
+        let hits = world.overlap_sphere(Vec4::ZERO, 2.0, CollisionFilter::default());
+        assert_eq!(hits.len(), 3);
+        assert!(hits.contains(&boxed) && hits.contains(&enemy) && hits.contains(&centered));
+
+        // A query that only looks for enemies
+        let enemy_query = CollisionFilter::new(CollisionLayer::DEFAULT, CollisionLayer::ENEMY);
+        assert_eq!(world.overlap_sphere(Vec4::ZERO, 2.0, enemy_query), vec![enemy]);
+    }
+
+    // ====== Sweep Tests ======
+
+    #[test]
+    fn test_sweep_sphere_hits_aabb() {
+        let mut world = no_gravity_world();
+        world.add_static_collider(StaticCollider::aabb(
+            Vec4::new(5.5, 0.0, 0.0, 0.0),
+            Vec4::new(0.5, 2.0, 2.0, 2.0),
+            PhysicsMaterial::CONCRETE,
+        ));
+
+        // Touches the face at x = 5 when the center reaches x = 4.5
+        let toi = world
+            .sweep_sphere(Vec4::ZERO, Vec4::new(10.0, 0.0, 0.0, 0.0), 0.5, CollisionFilter::default())
+            .expect("sweep should hit");
+        assert!(toi < 1.0);
+        assert!((toi - 0.45).abs() < 1e-5);
+
+        // Bodies are hit too
+        world.add_body(RigidBody4D::new_sphere(Vec4::new(2.0, 0.0, 0.0, 0.0), 0.5));
+        let toi = world
+            .sweep_sphere(Vec4::ZERO, Vec4::new(10.0, 0.0, 0.0, 0.0), 0.5, CollisionFilter::default())
+            .unwrap();
+        assert!((toi - 0.1).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_sweep_sphere_clear_path() {
+        let mut world = no_gravity_world();
+        world.add_static_collider(StaticCollider::aabb(
+            Vec4::new(5.5, 0.0, 0.0, 0.0),
+            Vec4::new(0.5, 2.0, 2.0, 2.0),
+            PhysicsMaterial::CONCRETE,
+        ));
+
+        // Passing above the box, or stopping short of it
+        let above = Vec4::new(0.0, 5.0, 0.0, 0.0);
+        let filter = CollisionFilter::default();
+        assert!(world.sweep_sphere(above, above + Vec4::new(10.0, 0.0, 0.0, 0.0), 0.5, filter).is_none());
+        assert!(world.sweep_sphere(Vec4::ZERO, Vec4::new(4.0, 0.0, 0.0, 0.0), 0.5, filter).is_none());
+    }
+
+    #[test]
+    fn test_fast_player_does_not_tunnel_through_thin_floor() {
+        let mut world = world_with_floor(-20.0, -100.0, PhysicsMaterial::CONCRETE);
+        world.add_static_collider(StaticCollider::aabb(
+            Vec4::new(0.0, -0.05, 0.0, 0.0),
+            Vec4::new(10.0, 0.05, 10.0, 10.0),
+            PhysicsMaterial::CONCRETE,
+        ));
+        let player = world.add_body(
+            RigidBody4D::new_sphere(Vec4::new(0.0, 2.0, 0.0, 0.0), 0.5)
+                .with_body_type(crate::body::BodyType::Kinematic)
+                .with_velocity(Vec4::new(0.0, -600.0, 0.0, 0.0)),
+        );
+        world.set_player_body(player);
+
+        // One step moves 10 units, far more than the slab is thick
+        world.step(1.0 / 60.0);
+
+        let pos = world.player_position().unwrap();
+        assert!(pos.y >= 0.45, "player passed through the floor: y = {}", pos.y);
+        assert!(world.player_is_grounded());
+    }
+
+    #[test]
+    fn test_fast_capsule_player_does_not_tunnel_through_thin_floor() {
+        let mut world = world_with_floor(-20.0, -100.0, PhysicsMaterial::CONCRETE);
+        world.add_static_collider(StaticCollider::aabb(
+            Vec4::new(0.0, -0.05, 0.0, 0.0),
+            Vec4::new(10.0, 0.05, 10.0, 10.0),
+            PhysicsMaterial::CONCRETE,
+        ));
+        let player = world.add_body(
+            RigidBody4D::new_capsule(Vec4::new(0.0, 3.0, 0.0, 0.0), 0.9, 0.3)
+                .with_body_type(crate::body::BodyType::Kinematic)
+                .with_velocity(Vec4::new(0.0, -600.0, 0.0, 0.0)),
+        );
+        world.set_player_body(player);
+
+        // One step moves 10 units, far more than the slab is thick
+        world.step(1.0 / 60.0);
+
+        let pos = world.player_position().unwrap();
+        assert!(pos.y >= 1.2, "capsule passed through the floor: y = {}", pos.y);
+        assert!(world.player_is_grounded());
+    }
+
+    // ====== Sleep Tests ======
+
+    /// A ball dropped onto a concrete floor, stepped for `seconds` at 60 Hz
+    fn settled_ball(config: PhysicsConfig, seconds: f32) -> (PhysicsWorld, BodyKey) {
+        let mut world = PhysicsWorld::with_config(config);
+        world.add_static_collider(StaticCollider::floor(0.0, PhysicsMaterial::CONCRETE));
+        let key = world.add_body(
+            RigidBody4D::new_sphere(Vec4::new(0.0, 1.0, 0.0, 0.0), 0.5).with_material(PhysicsMaterial::CONCRETE),
+        );
+        for _ in 0..(seconds * 60.0) as usize {
+            world.step(1.0 / 60.0);
+        }
+        (world, key)
+    }
+
+    #[test]
+    fn test_resting_ball_falls_asleep_and_stays_put() {
+        let (mut world, key) = settled_ball(PhysicsConfig::new(-20.0), 3.0);
+        let body = world.get_body(key).unwrap();
+        assert!(body.is_sleeping());
+        assert_eq!(body.velocity, Vec4::ZERO);
+
+        // Asleep: gravity and the floor no longer touch it
+        let position = body.position;
+        world.step(1.0 / 60.0);
+        assert_eq!(world.get_body(key).unwrap().position, position);
+        assert_eq!(world.static_collision_tests(), 0);
+    }
+
+    #[test]
+    fn test_impulse_wakes_sleeping_body() {
+        let (mut world, key) = settled_ball(PhysicsConfig::new(-20.0), 3.0);
+        let start = world.get_body(key).unwrap().position;
+
+        world.apply_impulse(key, Vec4::new(5.0, 0.0, 0.0, 0.0));
+        assert!(!world.get_body(key).unwrap().is_sleeping());
+        world.step(1.0 / 60.0);
+
+        let body = world.get_body(key).unwrap();
+        assert!(!body.is_sleeping());
+        assert!(body.position.x > start.x);
+    }
+
+    #[test]
+    fn test_collision_wakes_sleeping_body() {
+        let (mut world, sleeper) = settled_ball(PhysicsConfig::new(-20.0), 3.0);
+        let y = world.get_body(sleeper).unwrap().position.y;
+        let mover = world.add_body(
+            RigidBody4D::new_sphere(Vec4::new(-1.2, y, 0.0, 0.0), 0.5).with_velocity(Vec4::new(10.0, 0.0, 0.0, 0.0)),
+        );
+
+        for _ in 0..10 {
+            world.step(1.0 / 60.0);
+        }
+        assert!(!world.get_body(sleeper).unwrap().is_sleeping());
+        assert!(world.get_body(sleeper).unwrap().position.x > 0.0);
+        assert!(world.get_body(mover).unwrap().position.x < 0.0);
+    }
+
+    #[test]
+    fn test_removing_a_support_wakes_bodies_resting_on_it() {
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(-20.0));
+        world.add_static_collider(StaticCollider::floor(0.0, PhysicsMaterial::CONCRETE));
+        let support = world.add_body(RigidBody4D::new_static_aabb(Vec4::new(0.0, 2.0, 0.0, 0.0), Vec4::new(0.5, 0.5, 0.5, 0.5)));
+        let resting = world.add_body(
+            RigidBody4D::new_sphere(Vec4::new(0.0, 3.0, 0.0, 0.0), 0.5).with_material(PhysicsMaterial::CONCRETE),
+        );
+        let far = world.add_body(
+            RigidBody4D::new_sphere(Vec4::new(5.0, 1.0, 0.0, 0.0), 0.5).with_material(PhysicsMaterial::CONCRETE),
+        );
+        for _ in 0..180 {
+            world.step(1.0 / 60.0);
+        }
+        assert!(world.get_body(resting).unwrap().is_sleeping());
+        assert!(world.get_body(far).unwrap().is_sleeping());
+
+        world.remove_body(support);
+        assert!(!world.get_body(resting).unwrap().is_sleeping());
+        assert!(world.get_body(far).unwrap().is_sleeping());
+        for _ in 0..60 {
+            world.step(1.0 / 60.0);
+        }
+        assert!(world.get_body(resting).unwrap().position.y < 1.0);
+    }
+
+    #[test]
+    fn test_sleeping_disabled() {
+        let (world, key) = settled_ball(PhysicsConfig::new(-20.0).with_sleeping(false), 3.0);
+        assert!(!world.get_body(key).unwrap().is_sleeping());
+    }
+
+    // ====== Warm-up Tests ======
+
+    /// Highest point a rubber ball starting 0.2 into the floor reaches after a 0.5 s first frame
+    fn max_height_after_hitch(warmup_steps: u32) -> f32 {
+        let config = PhysicsConfig::new(-20.0).with_warmup_steps(warmup_steps);
+        let mut world = PhysicsWorld::with_config(config);
+        world.add_static_collider(StaticCollider::floor(0.0, PhysicsMaterial::RUBBER));
+        let key = world.add_body(
+            RigidBody4D::new_sphere(Vec4::new(0.0, 0.3, 0.0, 0.0), 0.5).with_material(PhysicsMaterial::RUBBER),
+        );
+
+        world.step(0.5);
+        let mut highest = world.get_body(key).unwrap().position.y;
+        for _ in 0..60 {
+            world.step(1.0 / 60.0);
+            highest = highest.max(world.get_body(key).unwrap().position.y);
+        }
+        highest
+    }
+
+    #[test]
+    fn test_warmup_settles_penetrating_body_without_popping() {
+        let popped = max_height_after_hitch(0);
+        let settled = max_height_after_hitch(10);
+        assert!(popped > 1.0, "without warm-up the ball should pop, got {}", popped);
+        assert!(settled < 0.55, "with warm-up the ball should stay put, got {}", settled);
+    }
+
+    #[test]
+    fn test_gravity_at_full_strength_after_warmup() {
+        let mut world = PhysicsWorld::with_config(PhysicsConfig::new(-20.0).with_warmup_steps(4));
+        let key = world.add_body(RigidBody4D::new_sphere(Vec4::new(0.0, 100.0, 0.0, 0.0), 0.5));
+        assert_eq!(world.gravity_scale(), 0.0);
+
+        let mut scales = Vec::new();
+        for _ in 0..4 {
+            scales.push(world.gravity_scale());
+            world.step(0.1);
+        }
+        assert_eq!(scales, vec![0.0, 0.25, 0.5, 0.75]);
+        assert_eq!(world.gravity_scale(), 1.0);
+
+        let before = world.get_body(key).unwrap().velocity.y;
+        world.step(0.1);
+        let after = world.get_body(key).unwrap().velocity.y;
+        assert!((after - before + 2.0).abs() < 1e-4);
+    }
 }
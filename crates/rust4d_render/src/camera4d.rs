@@ -7,10 +7,13 @@
 //! - **Y axis always remains aligned with gravity/world up**
 //!
 //! This design ensures intuitive movement behavior: walking forward stays
-//! horizontal regardless of 4D rotation state.
+//! horizontal regardless of 4D rotation state. The one exception is
+//! [`Camera4D::rotate_double`], whose genuinely 4D rotations can tilt the
+//! view off world Y until the camera is reset.
 
 use rust4d_math::{Vec4, Rotor4, RotationPlane, mat4};
 use rust4d_input::CameraControl;
+use serde::{Deserialize, Serialize};
 
 /// 4D Camera using Engine4D-style architecture
 ///
@@ -18,6 +21,9 @@ use rust4d_input::CameraControl;
 /// 1. `pitch` - Separate pitch angle (YZ plane rotation), clamped to ±89°
 /// 2. `rotation_4d` - 4D rotation in XZW hyperplane (via SkipY), preserving Y axis
 ///
+/// plus a camera-local `double_rotation`, identity unless
+/// [`rotate_double`](Self::rotate_double) is used.
+///
 /// This separation ensures that 4D rotations never affect the Y axis (gravity),
 /// making movement feel natural and predictable.
 pub struct Camera4D {
@@ -34,11 +40,226 @@ pub struct Camera4D {
     /// When converted to matrix and passed through SkipY, it only affects XZW axes.
     rotation_4d: Rotor4,
 
+    /// Camera-local rotation applied after pitch, built from double rotations
+    ///
+    /// A double rotation moves all four axes, which `rotation_4d` (a 3D
+    /// rotation remapped by SkipY) can't represent.
+    double_rotation: Rotor4,
+
     /// Cross-section offset from camera W position
     pub slice_offset: f32,
 
+    /// Camera-space normal of the slicing hyperplane (default: W, the ana axis)
+    ///
+    /// The slice keeps points at `slice_offset` along this direction. Tilting
+    /// it away from W cuts cross-sections that aren't perpendicular to ana.
+    pub slice_normal: Vec4,
+
+    /// Half-width of the rendered slab around the slice (0 = thin slice)
+    pub slab_thickness: f32,
+
     /// Maximum pitch angle in radians (default: ~89 degrees)
     pitch_limit: f32,
+
+    /// Slice offset animation in progress, if any
+    sweep: Option<SliceSweep>,
+
+    /// Impact shake applied to the rendered view
+    shake: CameraShake,
+}
+
+/// Easing curve for a slice sweep
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SweepEasing {
+    /// Constant speed
+    #[default]
+    Linear,
+    /// Slow start and end (smoothstep)
+    EaseInOut,
+}
+
+impl SweepEasing {
+    /// Map linear progress in [0, 1] to eased progress in [0, 1]
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            SweepEasing::Linear => t,
+            SweepEasing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// An animated sweep of the slice offset between two values
+///
+/// Started with [`Camera4D::sweep_slice`] and advanced by
+/// [`Camera4D::update_slice`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SliceSweep {
+    /// Slice offset at the start of the sweep
+    pub from: f32,
+    /// Slice offset at the end of the sweep
+    pub to: f32,
+    /// Length of the sweep in seconds
+    pub duration: f32,
+    /// Time elapsed so far in seconds
+    pub elapsed: f32,
+    /// Easing curve
+    pub easing: SweepEasing,
+}
+
+impl SliceSweep {
+    /// Slice offset at the current point of the sweep
+    pub fn value(&self) -> f32 {
+        let t = if self.duration > 0.0 { self.elapsed / self.duration } else { 1.0 };
+        self.from + (self.to - self.from) * self.easing.apply(t)
+    }
+
+    /// Check if the sweep has reached its end
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+/// Trauma-driven view shake
+///
+/// Trauma in [0, 1] is added by impacts and decays linearly. Each frame the
+/// view is rotated and offset by smooth noise scaled by trauma squared, so
+/// small knocks barely register while big ones shake hard. The shake only
+/// affects the rendered view, never the camera's position or aim.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraShake {
+    /// Whether [`Camera4D::add_shake`] has any effect (off by default)
+    pub enabled: bool,
+    /// Current trauma in [0, 1]
+    pub trauma: f32,
+    /// Trauma lost per second
+    pub decay: f32,
+    /// Largest rotation in radians (at full trauma)
+    pub max_angle: f32,
+    /// Largest XYZ offset in world units (at full trauma)
+    pub max_offset: f32,
+    /// Time the shake has been running, used to sample the noise
+    time: f32,
+    /// Current yaw, pitch and roll perturbation
+    angles: [f32; 3],
+    /// Current positional perturbation
+    offset: Vec4,
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            trauma: 0.0,
+            decay: 1.5,
+            max_angle: 0.05,
+            max_offset: 0.1,
+            time: 0.0,
+            angles: [0.0; 3],
+            offset: Vec4::ZERO,
+        }
+    }
+}
+
+impl CameraShake {
+    /// Advance the shake by `dt` seconds and resample the perturbation
+    pub fn update(&mut self, dt: f32) {
+        self.trauma = (self.trauma - self.decay * dt).max(0.0);
+        if self.trauma == 0.0 {
+            self.angles = [0.0; 3];
+            self.offset = Vec4::ZERO;
+            return;
+        }
+
+        self.time += dt;
+        let intensity = self.trauma * self.trauma;
+        let angle = self.max_angle * intensity;
+        let offset = self.max_offset * intensity;
+        self.angles = std::array::from_fn(|i| angle * shake_noise(self.time, i));
+        // No W offset: shaking along W would make the cross-section flicker
+        self.offset = Vec4::new(
+            offset * shake_noise(self.time, 3),
+            offset * shake_noise(self.time, 4),
+            offset * shake_noise(self.time, 5),
+            0.0,
+        );
+    }
+
+    /// Get the current yaw, pitch and roll perturbation in radians
+    pub fn angles(&self) -> [f32; 3] {
+        self.angles
+    }
+
+    /// Get the current positional perturbation
+    pub fn offset(&self) -> Vec4 {
+        self.offset
+    }
+}
+
+/// Smooth pseudo-random signal in [-1, 1], decorrelated per channel
+fn shake_noise(time: f32, channel: usize) -> f32 {
+    let phase = channel as f32 * 12.9898;
+    0.6 * (time * 23.0 + phase).sin() + 0.4 * (time * 41.0 + phase * 2.3).sin()
+}
+
+/// W-axis orientation data for a HUD "4D compass"
+///
+/// Built by [`Camera4D::w_compass`]; rendering it is up to the HUD.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WCompass {
+    /// The camera (player) W position
+    pub player_w: f32,
+    /// World W of the slice plane at the camera (player W + slice offset along ana)
+    pub slice_w: f32,
+    /// How much the camera forward points along W, in [-1, 1]
+    pub w_facing: f32,
+}
+
+/// Snapshot of a camera's placement, for bookmarks and cutscenes
+///
+/// Captured with [`Camera4D::capture_state`] and applied with
+/// [`Camera4D::restore_state`]. Rotors are stored as their 8 components
+/// `[s, b_xy, b_xz, b_xw, b_yz, b_yw, b_zw, p]`, as scenes store them, so the
+/// state serializes as plain numbers. The default is where
+/// [`Camera4D::reset`] puts the camera.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CameraState {
+    /// 4D position
+    pub position: Vec4,
+    /// Pitch angle in radians
+    pub pitch: f32,
+    /// 4D rotation in the XZW hyperplane
+    pub rotation_4d: [f32; 8],
+    /// Camera-local double rotation
+    pub double_rotation: [f32; 8],
+    /// Cross-section offset from the camera
+    pub slice_offset: f32,
+    /// Camera-space normal of the slicing hyperplane
+    pub slice_normal: Vec4,
+    /// Half-width of the rendered slab
+    pub slab_thickness: f32,
+}
+
+impl Default for CameraState {
+    fn default() -> Self {
+        Self {
+            position: Vec4::new(0.0, 0.0, 5.0, 0.0),
+            pitch: 0.0,
+            rotation_4d: rotor_components(Rotor4::IDENTITY),
+            double_rotation: rotor_components(Rotor4::IDENTITY),
+            slice_offset: 0.0,
+            slice_normal: Vec4::W,
+            slab_thickness: 0.0,
+        }
+    }
+}
+
+fn rotor_components(r: Rotor4) -> [f32; 8] {
+    [r.s, r.b_xy, r.b_xz, r.b_xw, r.b_yz, r.b_yw, r.b_zw, r.p]
+}
+
+fn rotor_from_components(c: [f32; 8]) -> Rotor4 {
+    Rotor4 { s: c[0], b_xy: c[1], b_xz: c[2], b_xw: c[3], b_yz: c[4], b_yw: c[5], b_zw: c[6], p: c[7] }
 }
 
 impl Default for Camera4D {
@@ -58,18 +279,25 @@ impl Camera4D {
 
     /// Create a new camera with a custom pitch limit (in radians)
     pub fn with_pitch_limit(pitch_limit: f32) -> Self {
-        Self {
-            position: Vec4::new(0.0, 0.0, 5.0, 0.0),
+        let mut camera = Self {
+            position: Vec4::ZERO,
             pitch: 0.0,
             rotation_4d: Rotor4::IDENTITY,
+            double_rotation: Rotor4::IDENTITY,
             slice_offset: 0.0,
+            slice_normal: Vec4::W,
+            slab_thickness: 0.0,
             pitch_limit,
-        }
+            sweep: None,
+            shake: CameraShake::default(),
+        };
+        camera.restore_state(&CameraState::default());
+        camera
     }
 
     /// Build the camera transformation matrix (Engine4D style)
     ///
-    /// Composition: `skip_y(rotation_4d) * pitch_rotation`
+    /// Composition: `skip_y(rotation_4d) * pitch_rotation * double_rotation`
     ///
     /// This ensures:
     /// 1. Pitch is applied first (local YZ plane rotation)
@@ -85,8 +313,9 @@ impl Camera4D {
         let rot_4d_raw = self.rotation_4d.to_matrix();
         let rot_4d_skip_y = mat4::skip_y(rot_4d_raw);
 
-        // 3. Combine: 4D rotation * pitch (right-to-left: pitch applied first)
-        mat4::mul(rot_4d_skip_y, pitch_mat)
+        // 3. Combine: 4D rotation * pitch * double rotation (right-to-left:
+        //    the camera-local double rotation first, then pitch)
+        mat4::mul(mat4::mul(rot_4d_skip_y, pitch_mat), self.double_rotation.to_matrix())
     }
 
     /// Standard 3D mouse look (yaw and pitch)
@@ -141,6 +370,63 @@ impl Camera4D {
         }
     }
 
+    /// Double rotation in camera-local space
+    ///
+    /// Rotates `plane_a` by `angle_a` and `plane_b` by `angle_b` (see
+    /// [`Rotor4::from_double_rotation`]). With orthogonal planes and equal
+    /// angles this is an isoclinic rotation, reaching orientations no
+    /// single-plane rotation can. Unlike the other rotations it can tilt the
+    /// camera's up axis away from world Y; [`reset`](Self::reset) undoes it.
+    pub fn rotate_double(&mut self, plane_a: RotationPlane, angle_a: f32, plane_b: RotationPlane, angle_b: f32) {
+        if angle_a.abs() > 0.0001 || angle_b.abs() > 0.0001 {
+            let r = Rotor4::from_double_rotation(plane_a, angle_a, plane_b, angle_b);
+            self.double_rotation = self.double_rotation.compose(&r).normalize();
+        }
+    }
+
+    /// Turn the camera to face `target`
+    ///
+    /// Builds an orthonormal frame by Gram-Schmidt: forward points from
+    /// `position` to `target`, up is `up` with its forward part removed, ana
+    /// is `over` with both removed, and right completes the frame. Pitch and
+    /// the XZW rotation take as much of the frame as they can, so mouse look
+    /// carries on naturally; what is left (an `up` tilted off world Y, or a
+    /// pitch past the limit) goes into the double rotation.
+    ///
+    /// Does nothing if `target` is at `position`. An `up` or `over` parallel
+    /// to the earlier axes is replaced by the nearest world axis.
+    pub fn look_at(&mut self, target: Vec4, up: Vec4, over: Vec4) {
+        let Some(forward) = orthonormalize(target - self.position, &[], &[]) else {
+            return;
+        };
+        let axes = [Vec4::Y, Vec4::W, Vec4::X, Vec4::Z];
+        let up = orthonormalize(up, &[forward], &axes).unwrap_or(Vec4::Y);
+        let ana = orthonormalize(over, &[forward, up], &axes).unwrap_or(Vec4::W);
+        let back = -forward;
+        let right = -Vec4::cross3(up, back, ana);
+
+        // Pitch lifts forward out of the horizontal hyperplane
+        self.pitch = forward.y.clamp(-1.0, 1.0).asin().clamp(-self.pitch_limit, self.pitch_limit);
+
+        // The XZW rotation turns the camera's horizontal axes into place.
+        // Looking straight up or down, forward's horizontal part is up's.
+        let horizontal = |v: Vec4| Vec4::new(v.x, 0.0, v.z, v.w);
+        let horizontal_axes = [-Vec4::Z, Vec4::W, Vec4::X, Vec4::Z];
+        let level_forward = orthonormalize(horizontal(forward), &[], &[])
+            .or_else(|| orthonormalize(horizontal(up) * -forward.y.signum(), &[], &horizontal_axes))
+            .unwrap_or(-Vec4::Z);
+        let level_ana = orthonormalize(horizontal(ana), &[level_forward], &horizontal_axes).unwrap_or(Vec4::W);
+        let level_right = -Vec4::cross3(Vec4::Y, -level_forward, level_ana);
+        // SkipY sends the rotor's X, Y, Z to world X, Z, W
+        let unskip = |v: Vec4| Vec4::new(v.x, v.z, v.w, 0.0);
+        self.rotation_4d = rotor_from_frame([unskip(level_right), unskip(-level_forward), unskip(level_ana), Vec4::W]);
+
+        // The double rotation takes the rest, in camera-local space
+        self.double_rotation = Rotor4::IDENTITY;
+        let to_local = mat4::transpose(self.camera_matrix());
+        self.double_rotation = rotor_from_frame([right, up, back, ana].map(|axis| mat4::transform(to_local, axis)));
+    }
+
     /// Move using camera matrix transformation (Engine4D style)
     ///
     /// Movement is transformed by the camera matrix, which ensures:
@@ -198,18 +484,123 @@ impl Camera4D {
         self.slice_offset
     }
 
+    /// Get the slice hyperplane normal in world space
+    ///
+    /// Uses the same orientation as the slice shader, including any view shake.
+    pub fn slice_normal_world(&self) -> Vec4 {
+        mat4::transform(self.view_rotation_matrix(), self.slice_normal.normalized())
+    }
+
     /// Adjust the slice offset
+    ///
+    /// Manual adjustment cancels any sweep in progress.
     pub fn adjust_slice_offset(&mut self, delta: f32) {
+        self.sweep = None;
         self.slice_offset += delta;
     }
 
+    /// Adjust the slab thickness, never going below a thin slice
+    pub fn adjust_slab_thickness(&mut self, delta: f32) {
+        self.slab_thickness = (self.slab_thickness + delta).max(0.0);
+    }
+
+    /// Animate the slice offset from `from` to `to` over `duration` seconds
+    ///
+    /// Useful for revealing a shape's full structure. Call
+    /// [`update_slice`](Self::update_slice) each frame to advance it.
+    pub fn sweep_slice(&mut self, from: f32, to: f32, duration: f32) {
+        self.sweep_slice_eased(from, to, duration, SweepEasing::Linear);
+    }
+
+    /// Animate the slice offset with an easing curve
+    pub fn sweep_slice_eased(&mut self, from: f32, to: f32, duration: f32, easing: SweepEasing) {
+        self.slice_offset = from;
+        self.sweep = Some(SliceSweep { from, to, duration, elapsed: 0.0, easing });
+    }
+
+    /// Advance the slice sweep (if any) by `dt` seconds
+    pub fn update_slice(&mut self, dt: f32) {
+        let Some(sweep) = &mut self.sweep else {
+            return;
+        };
+        sweep.elapsed = (sweep.elapsed + dt).min(sweep.duration);
+        self.slice_offset = sweep.value();
+        if sweep.is_finished() {
+            self.sweep = None;
+        }
+    }
+
+    /// Check if a slice sweep is in progress
+    pub fn is_sweeping(&self) -> bool {
+        self.sweep.is_some()
+    }
+
+    /// Get the slice sweep in progress, if any
+    pub fn slice_sweep(&self) -> Option<&SliceSweep> {
+        self.sweep.as_ref()
+    }
+
+    /// Enable or disable impact shake
+    pub fn set_shake_enabled(&mut self, enabled: bool) {
+        self.shake.enabled = enabled;
+    }
+
+    /// Add trauma to the view shake (ignored while shake is disabled)
+    ///
+    /// Trauma accumulates and is clamped to 1.0.
+    pub fn add_shake(&mut self, trauma: f32) {
+        if self.shake.enabled {
+            self.shake.trauma = (self.shake.trauma + trauma).clamp(0.0, 1.0);
+        }
+    }
+
+    /// Advance the view shake by `dt` seconds
+    pub fn update_shake(&mut self, dt: f32) {
+        self.shake.update(dt);
+    }
+
+    /// Get the view shake state
+    pub fn shake(&self) -> &CameraShake {
+        &self.shake
+    }
+
+    /// Snapshot the camera's position, orientation and slice
+    ///
+    /// The pitch limit, slice sweep and view shake are not part of the state.
+    pub fn capture_state(&self) -> CameraState {
+        CameraState {
+            position: self.position,
+            pitch: self.pitch,
+            rotation_4d: rotor_components(self.rotation_4d),
+            double_rotation: rotor_components(self.double_rotation),
+            slice_offset: self.slice_offset,
+            slice_normal: self.slice_normal,
+            slab_thickness: self.slab_thickness,
+        }
+    }
+
+    /// Put the camera back where a [`CameraState`] was captured
+    ///
+    /// Cancels any slice sweep in progress. The pitch is clamped to this
+    /// camera's limit and the slab thickness kept non-negative.
+    pub fn restore_state(&mut self, state: &CameraState) {
+        self.position = state.position;
+        self.pitch = state.pitch.clamp(-self.pitch_limit, self.pitch_limit);
+        self.rotation_4d = rotor_from_components(state.rotation_4d);
+        self.double_rotation = rotor_from_components(state.double_rotation);
+        self.slice_offset = state.slice_offset;
+        self.slice_normal = state.slice_normal;
+        self.slab_thickness = state.slab_thickness.max(0.0);
+        self.sweep = None;
+    }
+
     /// Reset camera to the default starting position and orientation
+    ///
+    /// Restores [`CameraState::default`] and clears the view shake.
     /// Note: pitch_limit is preserved
     pub fn reset(&mut self) {
-        self.position = Vec4::new(0.0, 0.0, 5.0, 0.0);
-        self.pitch = 0.0;
-        self.rotation_4d = Rotor4::IDENTITY;
-        self.slice_offset = 0.0;
+        self.restore_state(&CameraState::default());
+        self.shake = CameraShake { enabled: self.shake.enabled, ..CameraShake::default() };
         // pitch_limit is intentionally preserved
     }
 
@@ -239,6 +630,93 @@ impl Camera4D {
     pub fn rotation_matrix(&self) -> [[f32; 4]; 4] {
         self.camera_matrix()
     }
+
+    // --- Rendering transforms ---
+
+    /// Get the position the view is rendered from (position plus shake)
+    pub fn view_position(&self) -> Vec4 {
+        self.position + self.shake.offset
+    }
+
+    /// Get the orientation the view is rendered with (rotation plus shake)
+    ///
+    /// The shake is applied in camera-local space, after pitch.
+    pub fn view_rotation_matrix(&self) -> mat4::Mat4 {
+        let [yaw, pitch, roll] = self.shake.angles;
+        if yaw == 0.0 && pitch == 0.0 && roll == 0.0 {
+            return self.camera_matrix();
+        }
+        let shake = mat4::mul(
+            mat4::plane_rotation(roll, 0, 1),
+            mat4::mul(mat4::plane_rotation(pitch, 1, 2), mat4::plane_rotation(yaw, 0, 2)),
+        );
+        mat4::mul(self.camera_matrix(), shake)
+    }
+
+    /// Get the world→camera rotation used by the slice shader
+    ///
+    /// The slice compute shader maps a world point `p` to camera space as
+    /// `slice_matrix() * (p - position)`, then keeps points at camera-space
+    /// `w == slice_w`. This is the transpose of [`view_rotation_matrix`](Self::view_rotation_matrix),
+    /// which equals [`camera_matrix`](Self::camera_matrix) when the view isn't shaking.
+    pub fn slice_matrix(&self) -> mat4::Mat4 {
+        mat4::transpose(self.view_rotation_matrix())
+    }
+
+    /// Transform a world-space point into the camera space the slice shader outputs
+    ///
+    /// The xyz of the result is what the render pipeline receives as its
+    /// vertex position; w is compared against [`get_slice_w`](Self::get_slice_w).
+    pub fn world_to_camera(&self, point: Vec4) -> Vec4 {
+        mat4::transform(self.slice_matrix(), point - self.view_position())
+    }
+
+    /// Get the 3D view matrix passed to the render pipeline
+    ///
+    /// This is the identity: the slice shader already outputs camera-space
+    /// coordinates, so no further view transform is applied.
+    pub fn view_matrix(&self) -> [[f32; 4]; 4] {
+        mat4::IDENTITY
+    }
+
+    /// Project a world-space point to normalized device coordinates
+    ///
+    /// Reproduces the full renderer chain: slice transform, view matrix, then
+    /// `projection` (as built by [`perspective_matrix`](crate::pipeline::perspective_matrix)
+    /// or [`orthographic_matrix`](crate::pipeline::orthographic_matrix)).
+    /// Returns `[x, y, depth]` in NDC, or `None` if a perspective projection
+    /// puts the point behind the camera.
+    /// The point's camera-space W is ignored; use [`world_to_camera`](Self::world_to_camera)
+    /// to check whether it lies on the slice.
+    pub fn project_to_screen(&self, point: Vec4, projection: [[f32; 4]; 4]) -> Option<[f32; 3]> {
+        let camera_space = self.world_to_camera(point);
+        let position_3d = Vec4::new(camera_space.x, camera_space.y, camera_space.z, 1.0);
+        let view_pos = mat4::transform(self.view_matrix(), position_3d);
+        let clip = mat4::transform(projection, view_pos);
+        if clip.w <= 0.0 {
+            return None;
+        }
+        Some([clip.x / clip.w, clip.y / clip.w, clip.z / clip.w])
+    }
+
+    // --- HUD data ---
+
+    /// How much the camera forward direction points along world W
+    ///
+    /// 0.0 when looking within the XYZ hyperplane, ±1.0 when looking straight
+    /// toward ±W.
+    pub fn w_facing(&self) -> f32 {
+        self.forward().w
+    }
+
+    /// Snapshot of the camera's W-axis state for a HUD compass
+    pub fn w_compass(&self) -> WCompass {
+        WCompass {
+            player_w: self.position.w,
+            slice_w: self.position.w + self.ana().w * self.slice_offset,
+            w_facing: self.w_facing(),
+        }
+    }
 }
 
 impl CameraControl for Camera4D {
@@ -266,11 +744,50 @@ impl CameraControl for Camera4D {
         Camera4D::rotate_xw(self, delta);
     }
 
+    fn rotate_double(&mut self, plane_a: RotationPlane, angle_a: f32, plane_b: RotationPlane, angle_b: f32) {
+        Camera4D::rotate_double(self, plane_a, angle_a, plane_b, angle_b);
+    }
+
     fn position(&self) -> Vec4 {
         self.position
     }
 }
 
+/// `v` with its components along the unit vectors in `basis` removed, normalized
+///
+/// When that leaves (almost) nothing, the first of `fallbacks` that does not
+/// is used instead; `None` if none are left either.
+fn orthonormalize(v: Vec4, basis: &[Vec4], fallbacks: &[Vec4]) -> Option<Vec4> {
+    std::iter::once(v).chain(fallbacks.iter().copied()).find_map(|candidate| {
+        let rest = basis.iter().fold(candidate, |rest, axis| rest - *axis * rest.dot(*axis));
+        (rest.length_squared() > 1e-6).then(|| rest.normalized())
+    })
+}
+
+/// Rotor taking each world axis to the matching axis of an orthonormal, right-handed frame
+///
+/// Turns X, Y and Z onto their frame axes one plane at a time; each plane
+/// is orthogonal to the axes already placed, so they stay put, and W follows.
+fn rotor_from_frame(frame: [Vec4; 4]) -> Rotor4 {
+    let mut rotor = Rotor4::IDENTITY;
+    for (i, axis) in [Vec4::X, Vec4::Y, Vec4::Z].into_iter().enumerate() {
+        let current = rotor.rotate(axis);
+        let target = frame[i];
+        let cos = current.dot(target).clamp(-1.0, 1.0);
+        if cos > 1.0 - 1e-7 {
+            continue;
+        }
+        // Facing away, W's frame axis (off the placed axes) makes the half turn
+        let toward = orthonormalize(target, &[current], &[])
+            .filter(|_| cos > -1.0 + 1e-6)
+            .or_else(|| orthonormalize(frame[3], &[current], &[]))
+            .unwrap_or(target);
+        let step = Rotor4::from_plane_vectors(current, toward, cos.acos());
+        rotor = step.compose(&rotor).normalize();
+    }
+    rotor
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -392,6 +909,7 @@ mod tests {
         cam.rotate_3d(1.0, 0.5);
         cam.rotate_w(0.3);
         cam.rotate_xw(0.2);
+        cam.rotate_double(RotationPlane::XY, 0.4, RotationPlane::ZW, 0.4);
 
         cam.reset();
 
@@ -403,6 +921,71 @@ mod tests {
         assert!(approx_eq(up.y, 1.0), "Up should be +Y after reset, got {:?}", up);
     }
 
+    #[test]
+    fn test_restore_state_returns_to_capture() {
+        let mut cam = Camera4D::new();
+        cam.move_local_xz(1.5, -2.0);
+        cam.move_w(0.7);
+        cam.rotate_3d(0.4, 0.3);
+        cam.rotate_w(0.5);
+        cam.rotate_double(RotationPlane::XY, 0.2, RotationPlane::ZW, 0.3);
+        cam.adjust_slice_offset(0.25);
+
+        let state = cam.capture_state();
+        let matrix = cam.camera_matrix();
+        let slice_w = cam.get_slice_w();
+
+        cam.move_local_xz(-3.0, 1.0);
+        cam.rotate_3d(-1.0, 0.2);
+        cam.rotate_xw(0.8);
+        cam.adjust_slice_offset(-1.0);
+        assert!(!approx_eq(cam.get_slice_w(), slice_w));
+
+        cam.restore_state(&state);
+        assert_eq!(cam.capture_state(), state);
+        assert_eq!(cam.camera_matrix(), matrix);
+        assert_eq!(cam.get_slice_w(), slice_w);
+    }
+
+    #[test]
+    fn test_camera_state_ron_round_trip() {
+        let mut cam = Camera4D::new();
+        cam.rotate_w(0.6);
+        cam.move_w(1.25);
+        let state = cam.capture_state();
+
+        let text = ron::to_string(&state).unwrap();
+        let parsed: CameraState = ron::from_str(&text).unwrap();
+        assert_eq!(parsed, state);
+    }
+
+    #[test]
+    fn test_reset_restores_default_state() {
+        let mut cam = Camera4D::new();
+        cam.rotate_3d(0.5, 0.5);
+        cam.move_w(2.0);
+        cam.reset();
+        assert_eq!(cam.capture_state(), CameraState::default());
+        assert_eq!(Camera4D::new().capture_state(), CameraState::default());
+    }
+
+    #[test]
+    fn test_slab_thickness_stays_non_negative() {
+        let mut cam = Camera4D::new();
+        assert_eq!(cam.slab_thickness, 0.0);
+
+        cam.adjust_slab_thickness(0.3);
+        cam.adjust_slab_thickness(0.2);
+        assert!(approx_eq(cam.slab_thickness, 0.5));
+
+        cam.adjust_slab_thickness(-2.0);
+        assert_eq!(cam.slab_thickness, 0.0);
+
+        cam.adjust_slab_thickness(1.0);
+        cam.reset();
+        assert_eq!(cam.slab_thickness, 0.0);
+    }
+
     #[test]
     fn test_pitch_clamped() {
         let mut cam = Camera4D::new();
@@ -444,6 +1027,105 @@ mod tests {
         assert!(up.dot(ana).abs() < EPSILON, "Up not orthogonal to Ana");
     }
 
+    #[test]
+    fn test_isoclinic_double_rotation_turns_every_axis() {
+        let mut cam = Camera4D::new();
+        let angle = 0.3;
+        cam.rotate_double(RotationPlane::XY, angle, RotationPlane::ZW, angle);
+
+        // Every camera axis is turned by the same angle, including up (off world Y)
+        let axes = [
+            (cam.right(), Vec4::X),
+            (cam.up(), Vec4::Y),
+            (cam.forward(), -Vec4::Z),
+            (cam.ana(), Vec4::W),
+        ];
+        for (axis, original) in axes {
+            assert!(approx_eq(axis.length(), 1.0));
+            assert!(approx_eq(axis.dot(original), angle.cos()), "{:?} vs {:?}", axis, original);
+        }
+        assert!(cam.right().dot(cam.up()).abs() < EPSILON);
+        assert!(cam.forward().dot(cam.ana()).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_double_rotation_composes_with_fps_look() {
+        let mut cam = Camera4D::new();
+        cam.rotate_3d(0.5, 0.2);
+        let before = cam.camera_matrix();
+
+        // Rotating there and back leaves the FPS orientation as it was
+        cam.rotate_double(RotationPlane::XZ, 0.4, RotationPlane::YW, 0.4);
+        assert!(!approx_eq(cam.forward().dot(mat4::transform(before, -Vec4::Z)), 1.0));
+        cam.rotate_double(RotationPlane::XZ, -0.4, RotationPlane::YW, -0.4);
+        let after = cam.camera_matrix();
+        for (a, b) in before.iter().flatten().zip(after.iter().flatten()) {
+            assert!(approx_eq(*a, *b));
+        }
+    }
+
+    fn assert_orthonormal(cam: &Camera4D) {
+        let axes = [cam.right(), cam.up(), cam.forward(), cam.ana()];
+        for (i, a) in axes.iter().enumerate() {
+            assert!(approx_eq(a.length(), 1.0), "{:?} not unit", a);
+            for b in &axes[i + 1..] {
+                assert!(a.dot(*b).abs() < EPSILON, "{:?} not orthogonal to {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_look_at_target_on_x() {
+        let mut cam = Camera4D::new();
+        cam.position = Vec4::ZERO;
+        cam.look_at(Vec4::new(3.0, 0.0, 0.0, 0.0), Vec4::Y, Vec4::W);
+
+        assert!(approx_eq(cam.forward().dot(Vec4::X), 1.0), "forward {:?}", cam.forward());
+        assert!(approx_eq(cam.up().dot(Vec4::Y), 1.0), "up {:?}", cam.up());
+        assert!(approx_eq(cam.ana().dot(Vec4::W), 1.0), "ana {:?}", cam.ana());
+        assert_orthonormal(&cam);
+
+        // A world-up frame is plain FPS look: yaw only, nothing left over
+        assert!(approx_eq(cam.pitch, 0.0));
+        assert!(approx_eq(cam.double_rotation.s.abs(), 1.0));
+    }
+
+    #[test]
+    fn test_look_at_general_target() {
+        let mut cam = Camera4D::new();
+        cam.position = Vec4::new(1.0, 2.0, -1.0, 0.5);
+        let target = Vec4::new(-2.0, 3.5, 1.0, 3.0);
+        let up = Vec4::new(0.2, 1.0, 0.0, 0.3);
+        let over = Vec4::new(0.0, 0.0, 1.0, 1.0);
+        cam.look_at(target, up, over);
+
+        let forward = (target - cam.position).normalized();
+        assert!(approx_eq(cam.forward().dot(forward), 1.0), "forward {:?} vs {:?}", cam.forward(), forward);
+        assert_orthonormal(&cam);
+
+        // Up and ana are the references with the earlier axes projected out
+        let expected_up = (up - forward * up.dot(forward)).normalized();
+        assert!(approx_eq(cam.up().dot(expected_up), 1.0), "up {:?} vs {:?}", cam.up(), expected_up);
+        assert!(cam.ana().dot(over) > 0.0);
+        assert!(approx_eq(cam.ana().dot(cam.up()) + cam.ana().dot(cam.forward()), 0.0));
+    }
+
+    #[test]
+    fn test_look_at_straight_up_and_behind() {
+        for target in [Vec4::new(0.0, 10.0, 0.0, 0.0), Vec4::new(0.0, 0.0, 10.0, 0.0), Vec4::new(0.0, 0.0, 0.0, -4.0)] {
+            let mut cam = Camera4D::new();
+            cam.position = Vec4::ZERO;
+            cam.look_at(target, Vec4::Y, Vec4::W);
+            assert!(approx_eq(cam.forward().dot(target.normalized()), 1.0), "forward {:?} vs {:?}", cam.forward(), target);
+            assert_orthonormal(&cam);
+        }
+
+        // Looking at itself changes nothing
+        let mut cam = Camera4D::new();
+        cam.look_at(cam.position, Vec4::Y, Vec4::W);
+        assert!(approx_eq(cam.forward().dot(-Vec4::Z), 1.0));
+    }
+
     #[test]
     fn test_yaw_after_4d_rotation() {
         // Yaw should still work correctly after 4D rotation
@@ -606,4 +1288,290 @@ mod tests {
         );
     }
 
+
+    // ==================== Rendering Transform Tests ====================
+
+    #[test]
+    fn test_view_matrix_is_identity() {
+        let mut cam = Camera4D::new();
+        cam.rotate_3d(0.7, 0.3);
+        cam.rotate_w(0.4);
+        assert_eq!(cam.view_matrix(), mat4::IDENTITY);
+    }
+
+    #[test]
+    fn test_slice_matrix_inverts_camera_matrix() {
+        let mut cam = Camera4D::new();
+        cam.rotate_3d(0.5, 0.2);
+        cam.rotate_w(0.3);
+
+        let product = mat4::mul(cam.slice_matrix(), cam.camera_matrix());
+        for (i, col) in product.iter().enumerate() {
+            for (j, &v) in col.iter().enumerate() {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!(approx_eq(v, expected), "[{}][{}] = {}", i, j, v);
+            }
+        }
+    }
+
+    #[test]
+    fn test_world_to_camera_matches_shader() {
+        // Shader: transpose(camera_matrix) * (world_pos - camera_pos)
+        let mut cam = Camera4D::new();
+        cam.position = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        cam.rotate_3d(FRAC_PI_4, 0.1);
+        cam.rotate_w(0.6);
+
+        let world = Vec4::new(-2.0, 0.5, 1.0, 3.0);
+        let expected = mat4::transform(
+            mat4::transpose(cam.rotation_matrix()),
+            world - cam.position,
+        );
+        let actual = cam.world_to_camera(world);
+        assert!(approx_eq(actual.x, expected.x));
+        assert!(approx_eq(actual.y, expected.y));
+        assert!(approx_eq(actual.z, expected.z));
+        assert!(approx_eq(actual.w, expected.w));
+    }
+
+    #[test]
+    fn test_project_point_in_front_to_center() {
+        use crate::pipeline::perspective_matrix;
+
+        let cam = Camera4D::new();
+        let proj = perspective_matrix(FRAC_PI_2, 1.0, 0.1, 100.0);
+
+        // Camera at z=5 looking down -Z: the origin is straight ahead
+        let ndc = cam.project_to_screen(Vec4::ZERO, proj).unwrap();
+        assert!(approx_eq(ndc[0], 0.0));
+        assert!(approx_eq(ndc[1], 0.0));
+        assert!(ndc[2] > -1.0 && ndc[2] < 1.0);
+    }
+
+    #[test]
+    fn test_project_known_point() {
+        use crate::pipeline::perspective_matrix;
+
+        let cam = Camera4D::new();
+        // 90 degree FOV: a point at 45 degrees to the right maps to the screen edge
+        let proj = perspective_matrix(FRAC_PI_2, 1.0, 0.1, 100.0);
+        let ndc = cam.project_to_screen(Vec4::new(5.0, 0.0, 0.0, 0.0), proj).unwrap();
+        assert!(approx_eq(ndc[0], 1.0), "x = {}", ndc[0]);
+        assert!(approx_eq(ndc[1], 0.0));
+
+        // After turning, a point along the new forward direction is centered
+        let mut turned = Camera4D::new();
+        turned.rotate_3d(FRAC_PI_2, 0.0);
+        let ahead = turned.position + turned.forward() * 5.0;
+        let ndc = turned.project_to_screen(ahead, proj).unwrap();
+        assert!(approx_eq(ndc[0], 0.0));
+        assert!(approx_eq(ndc[1], 0.0));
+    }
+
+    #[test]
+    fn test_project_behind_camera_is_none() {
+        use crate::pipeline::perspective_matrix;
+
+        let cam = Camera4D::new();
+        let proj = perspective_matrix(FRAC_PI_2, 1.0, 0.1, 100.0);
+        assert!(cam.project_to_screen(Vec4::new(0.0, 0.0, 10.0, 0.0), proj).is_none());
+    }
+
+    // --- HUD data tests ---
+
+    #[test]
+    fn test_w_facing_zero_at_identity() {
+        let cam = Camera4D::new();
+        assert!(approx_eq(cam.w_facing(), 0.0));
+    }
+
+    #[test]
+    fn test_w_facing_increases_with_w_rotation() {
+        let mut cam = Camera4D::new();
+        cam.rotate_xw(-FRAC_PI_4);
+        let halfway = cam.w_facing();
+        cam.rotate_xw(-FRAC_PI_4);
+        let full = cam.w_facing();
+
+        // Forward tilts into +W and ends up pointing straight along it
+        assert!(halfway > 0.0 && halfway < full);
+        assert!(approx_eq(full, 1.0), "w_facing = {}", full);
+    }
+
+    #[test]
+    fn test_w_compass_reflects_camera_w() {
+        let mut cam = Camera4D::new();
+        cam.position.w = 3.0;
+        cam.slice_offset = 0.5;
+
+        let compass = cam.w_compass();
+        assert_eq!(compass.player_w, 3.0);
+        assert!(approx_eq(compass.slice_w, 3.5));
+        assert!(approx_eq(compass.w_facing, 0.0));
+    }
+
+    // --- Slice sweep tests ---
+
+    #[test]
+    fn test_sweep_reaches_target() {
+        let mut cam = Camera4D::new();
+        cam.sweep_slice(-1.0, 1.0, 2.0);
+        assert!(cam.is_sweeping());
+        assert_eq!(cam.get_slice_w(), -1.0);
+
+        for _ in 0..20 {
+            cam.update_slice(0.1);
+        }
+        assert!(approx_eq(cam.get_slice_w(), 1.0));
+        assert!(!cam.is_sweeping());
+
+        // Further updates leave the slice alone
+        cam.update_slice(0.1);
+        assert!(approx_eq(cam.get_slice_w(), 1.0));
+    }
+
+    #[test]
+    fn test_sweep_is_monotonic() {
+        for easing in [SweepEasing::Linear, SweepEasing::EaseInOut] {
+            let mut cam = Camera4D::new();
+            cam.sweep_slice_eased(2.0, -2.0, 1.0, easing);
+
+            let mut previous = cam.get_slice_w();
+            while cam.is_sweeping() {
+                cam.update_slice(1.0 / 30.0);
+                let current = cam.get_slice_w();
+                assert!(current <= previous, "{:?}: {} after {}", easing, current, previous);
+                assert!((-2.0..=2.0).contains(&current));
+                previous = current;
+            }
+            assert!(approx_eq(previous, -2.0));
+        }
+    }
+
+    #[test]
+    fn test_sweep_easing_midpoint() {
+        let mut cam = Camera4D::new();
+        cam.sweep_slice_eased(0.0, 1.0, 1.0, SweepEasing::EaseInOut);
+        cam.update_slice(0.25);
+        // Smoothstep starts slower than linear
+        assert!(cam.get_slice_w() < 0.25);
+        cam.update_slice(0.25);
+        assert!(approx_eq(cam.get_slice_w(), 0.5));
+    }
+
+    #[test]
+    fn test_manual_adjust_cancels_sweep() {
+        let mut cam = Camera4D::new();
+        cam.sweep_slice(0.0, 1.0, 1.0);
+        cam.update_slice(0.5);
+        cam.adjust_slice_offset(0.1);
+
+        assert!(!cam.is_sweeping());
+        assert!(approx_eq(cam.get_slice_w(), 0.6));
+    }
+
+    // --- Camera shake tests ---
+
+    fn shaking_camera(trauma: f32) -> Camera4D {
+        let mut cam = Camera4D::new();
+        cam.set_shake_enabled(true);
+        cam.add_shake(trauma);
+        cam
+    }
+
+    #[test]
+    fn test_zero_trauma_view_is_unperturbed() {
+        let mut cam = shaking_camera(0.0);
+        cam.rotate_3d(0.4, 0.2);
+        cam.update_shake(0.1);
+
+        assert_eq!(cam.view_position(), cam.position);
+        assert_eq!(cam.view_rotation_matrix(), cam.camera_matrix());
+    }
+
+    #[test]
+    fn test_trauma_perturbs_view_then_decays() {
+        let mut cam = shaking_camera(0.8);
+        cam.update_shake(1.0 / 60.0);
+
+        assert_ne!(cam.view_position(), cam.position);
+        assert_ne!(cam.view_rotation_matrix(), cam.camera_matrix());
+        // Only the rendered view moves
+        assert_eq!(cam.position, Vec4::new(0.0, 0.0, 5.0, 0.0));
+        assert_eq!(cam.view_position().w, cam.position.w);
+
+        for _ in 0..120 {
+            cam.update_shake(1.0 / 60.0);
+        }
+        assert_eq!(cam.shake().trauma, 0.0);
+        assert_eq!(cam.view_position(), cam.position);
+        assert_eq!(cam.view_rotation_matrix(), cam.camera_matrix());
+    }
+
+    #[test]
+    fn test_shake_scales_with_trauma_squared() {
+        let peak = |trauma: f32| {
+            let mut cam = shaking_camera(trauma);
+            cam.shake.decay = 0.0;
+            (0..60)
+                .map(|_| {
+                    cam.update_shake(1.0 / 60.0);
+                    cam.shake().offset().length()
+                })
+                .fold(0.0f32, f32::max)
+        };
+        let ratio = peak(1.0) / peak(0.5);
+        assert!((ratio - 4.0).abs() < 1e-3, "ratio {}", ratio);
+    }
+
+    #[test]
+    fn test_shake_disabled_by_default() {
+        let mut cam = Camera4D::new();
+        cam.add_shake(1.0);
+        cam.update_shake(1.0 / 60.0);
+
+        assert_eq!(cam.shake().trauma, 0.0);
+        assert_eq!(cam.view_position(), cam.position);
+    }
+
+    #[test]
+    fn test_trauma_clamped() {
+        let mut cam = shaking_camera(0.7);
+        cam.add_shake(0.7);
+        assert_eq!(cam.shake().trauma, 1.0);
+    }
+
+    // --- Slice plane tests ---
+
+    #[test]
+    fn test_default_slice_normal_is_ana() {
+        let mut cam = Camera4D::new();
+        cam.rotate_xw(0.7);
+        assert!((cam.slice_normal_world() - cam.ana()).length() < EPSILON);
+    }
+
+    #[test]
+    fn test_slice_normal_world_follows_rotation() {
+        let mut cam = Camera4D::new();
+        cam.slice_normal = Vec4::X;
+        let before = cam.slice_normal_world();
+        cam.rotate_3d(0.5, 0.0);
+        assert!((cam.slice_normal_world() - before).length() > 0.1);
+
+        cam.reset();
+        assert_eq!(cam.slice_normal, Vec4::W);
+    }
+
+    #[test]
+    fn test_slice_normal_world_matches_slice_orientation() {
+        let mut cam = Camera4D::new();
+        cam.slice_normal = Vec4::Z;
+        cam.rotate_3d(0.3, 0.4);
+        cam.shake.angles = [0.1, 0.2, 0.0];
+
+        // The slice shader rotates by the view rotation, shake included
+        let expected = mat4::transform(cam.view_rotation_matrix(), Vec4::Z);
+        assert!((cam.slice_normal_world() - expected).length() < EPSILON);
+        assert!((cam.slice_normal_world() - mat4::transform(cam.camera_matrix(), Vec4::Z)).length() > 0.05);
+    }
 }
@@ -5,6 +5,8 @@
 use std::sync::Arc;
 use winit::window::Window;
 
+use crate::pipeline::SliceMode;
+
 /// Rendering context holding wgpu state
 pub struct RenderContext {
     pub surface: wgpu::Surface<'static>,
@@ -12,6 +14,8 @@ pub struct RenderContext {
     pub queue: wgpu::Queue,
     pub config: wgpu::SurfaceConfiguration,
     pub size: winit::dpi::PhysicalSize<u32>,
+    /// Slicing path supported by the adapter (CPU fallback without compute shaders)
+    pub slice_mode: SliceMode,
     // Keep window alive
     _window: Arc<Window>,
 }
@@ -45,13 +49,39 @@ impl RenderContext {
             .await
             .expect("Failed to find an appropriate adapter");
 
-        // Request device
+        // Detect compute shader support; without it, request downlevel limits
+        // so device creation succeeds and slice on the CPU instead
+        let compute_shaders = adapter
+            .get_downlevel_capabilities()
+            .flags
+            .contains(wgpu::DownlevelFlags::COMPUTE_SHADERS);
+        let slice_mode = if compute_shaders {
+            SliceMode::for_limits(&adapter.limits())
+        } else {
+            SliceMode::Cpu
+        };
+        // Buffer sizes come from the adapter so slice buffers can be sized to what it really supports
+        let adapter_limits = adapter.limits();
+        let required_limits = match slice_mode {
+            SliceMode::Gpu => wgpu::Limits {
+                max_storage_buffer_binding_size: adapter_limits.max_storage_buffer_binding_size,
+                max_buffer_size: adapter_limits.max_buffer_size,
+                ..wgpu::Limits::default()
+            },
+            SliceMode::Cpu => wgpu::Limits {
+                max_buffer_size: adapter_limits.max_buffer_size,
+                ..wgpu::Limits::downlevel_webgl2_defaults().using_resolution(adapter_limits)
+            },
+        };
+        log::info!("Adapter '{}': slice mode {:?}", adapter.get_info().name, slice_mode);
+
+        // Request device, with timestamp queries for pass timings where supported
         let (device, queue) = adapter
             .request_device(
                 &wgpu::DeviceDescriptor {
                     label: Some("Rust4D Device"),
-                    required_features: wgpu::Features::empty(),
-                    required_limits: wgpu::Limits::default(),
+                    required_features: adapter.features() & wgpu::Features::TIMESTAMP_QUERY,
+                    required_limits,
                     memory_hints: wgpu::MemoryHints::default(),
                 },
                 None,
@@ -92,6 +122,7 @@ impl RenderContext {
             queue,
             config,
             size,
+            slice_mode,
             _window: window,
         }
     }
@@ -142,6 +173,13 @@ impl RenderContext {
         Ok(())
     }
 
+    /// Get the limits of the device, as granted by the adapter
+    ///
+    /// Use these to size GPU buffers (see [`SlicePipeline::output_capacity`](crate::pipeline::SlicePipeline::output_capacity)).
+    pub fn device_limits(&self) -> wgpu::Limits {
+        self.device.limits()
+    }
+
     /// Get the aspect ratio
     pub fn aspect_ratio(&self) -> f32 {
         self.size.width as f32 / self.size.height as f32
@@ -0,0 +1,222 @@
+//! Debug line drawing in the 3D cross-section
+//!
+//! Debug lines are built on the CPU in the camera space the slice shader
+//! outputs, and drawn unlit on top of the sliced geometry by
+//! [`RenderPipeline::render_lines`](crate::pipeline::RenderPipeline::render_lines).
+//!
+//! [`ProbeRay`] uses this to show where the crosshair ray goes: the ray lives
+//! in 4D, so it is drawn as its projection into the view, with markers where
+//! it crosses the slice hyperplane and where it hit something.
+
+use rust4d_core::Material;
+use rust4d_math::Vec4;
+
+use crate::camera4d::Camera4D;
+use crate::pipeline::Vertex3D;
+
+/// Color of the probe ray line
+pub const PROBE_RAY_COLOR: [f32; 4] = [1.0, 1.0, 0.2, 1.0];
+
+/// Color of the marker where the probe ray crosses the slice
+pub const PROBE_SLICE_COLOR: [f32; 4] = [0.2, 1.0, 1.0, 1.0];
+
+/// Color of the marker where the probe ray hit a body
+pub const PROBE_HIT_COLOR: [f32; 4] = [1.0, 0.2, 0.2, 1.0];
+
+/// Half-size of probe markers
+const PROBE_MARKER_SIZE: f32 = 0.15;
+
+/// A list of line segments, two vertices per segment
+#[derive(Clone, Debug, Default)]
+pub struct DebugLines {
+    vertices: Vec<Vertex3D>,
+}
+
+impl DebugLines {
+    /// Create an empty line list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a segment between two camera-space points
+    pub fn add_line(&mut self, a: [f32; 3], b: [f32; 3], color: [f32; 4]) {
+        for position in [a, b] {
+            self.vertices.push(Vertex3D {
+                position,
+                color,
+                material_id: Material::UNLIT_ID as u32,
+                ..Vertex3D::default()
+            });
+        }
+    }
+
+    /// Add a 3-axis cross centered on a camera-space point
+    pub fn add_marker(&mut self, center: [f32; 3], size: f32, color: [f32; 4]) {
+        for axis in 0..3 {
+            let (mut a, mut b) = (center, center);
+            a[axis] -= size;
+            b[axis] += size;
+            self.add_line(a, b, color);
+        }
+    }
+
+    /// Get the line vertices
+    pub fn vertices(&self) -> &[Vertex3D] {
+        &self.vertices
+    }
+
+    /// Get the number of segments
+    pub fn line_count(&self) -> usize {
+        self.vertices.len() / 2
+    }
+
+    /// Check if there are no lines
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// Remove all lines
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+    }
+}
+
+/// A 4D ray to visualize, usually the crosshair ray
+#[derive(Clone, Copy, Debug)]
+pub struct ProbeRay {
+    /// Ray origin in world space
+    pub origin: Vec4,
+    /// Ray direction in world space (normalized on use)
+    pub direction: Vec4,
+    /// Length of the drawn segment
+    pub length: f32,
+    /// Where the ray hit something, if anywhere
+    pub hit: Option<Vec4>,
+}
+
+impl ProbeRay {
+    /// Create a probe ray with no hit
+    pub fn new(origin: Vec4, direction: Vec4, length: f32) -> Self {
+        Self {
+            origin,
+            direction,
+            length,
+            hit: None,
+        }
+    }
+
+    /// Builder: set the hit position
+    pub fn with_hit(mut self, hit: Option<Vec4>) -> Self {
+        self.hit = hit;
+        self
+    }
+
+    /// Where the ray crosses the camera's slice hyperplane, in world space
+    ///
+    /// Returns `None` if the ray runs parallel to the slice or only crosses
+    /// it behind the origin or past `length`.
+    pub fn slice_crossing(&self, camera: &Camera4D) -> Option<Vec4> {
+        let direction = self.direction.normalized();
+        let normal = camera.slice_normal.normalized();
+        let slice_w = camera.get_slice_w();
+
+        // Camera-space distance from the slice is linear along the ray
+        let start = normal.dot(camera.world_to_camera(self.origin)) - slice_w;
+        let end = normal.dot(camera.world_to_camera(self.origin + direction)) - slice_w;
+        let rate = end - start;
+        if rate.abs() < 1e-6 {
+            return None;
+        }
+        let t = -start / rate;
+        (0.0..=self.length).contains(&t).then(|| self.origin + direction * t)
+    }
+
+    /// Build the lines showing this ray from `camera`'s point of view
+    ///
+    /// Produces the ray segment first, then a marker at the slice crossing
+    /// (if any), then a marker at the hit (if any).
+    pub fn lines(&self, camera: &Camera4D) -> DebugLines {
+        let to_view = |p: Vec4| {
+            let c = camera.world_to_camera(p);
+            [c.x, c.y, c.z]
+        };
+
+        let mut lines = DebugLines::new();
+        let end = self.origin + self.direction.normalized() * self.length;
+        lines.add_line(to_view(self.origin), to_view(end), PROBE_RAY_COLOR);
+        if let Some(crossing) = self.slice_crossing(camera) {
+            lines.add_marker(to_view(crossing), PROBE_MARKER_SIZE, PROBE_SLICE_COLOR);
+        }
+        if let Some(hit) = self.hit {
+            lines.add_marker(to_view(hit), PROBE_MARKER_SIZE, PROBE_HIT_COLOR);
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: [f32; 3], b: [f32; 3]) -> bool {
+        a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-5)
+    }
+
+    #[test]
+    fn test_marker_is_three_axis_lines() {
+        let mut lines = DebugLines::new();
+        lines.add_marker([1.0, 2.0, 3.0], 0.5, PROBE_HIT_COLOR);
+
+        assert_eq!(lines.line_count(), 3);
+        let v = lines.vertices();
+        assert!(approx(v[0].position, [0.5, 2.0, 3.0]));
+        assert!(approx(v[5].position, [1.0, 2.0, 3.5]));
+        assert!(v.iter().all(|v| v.material_id == Material::UNLIT_ID as u32));
+    }
+
+    #[test]
+    fn test_probe_ray_segment_and_hit_marker() {
+        // Default camera at the origin looking down -Z
+        let camera = Camera4D::new();
+        let hit = camera.position + camera.forward() * 5.0;
+        let probe = ProbeRay::new(camera.position, camera.forward(), 20.0).with_hit(Some(hit));
+        let lines = probe.lines(&camera);
+
+        // Segment from the camera toward the aim direction
+        let v = lines.vertices();
+        assert!(approx(v[0].position, [0.0, 0.0, 0.0]));
+        assert!(approx(v[1].position, [0.0, 0.0, -20.0]));
+        assert_eq!(v[0].color, PROBE_RAY_COLOR);
+
+        // The ray runs within the slice rather than crossing it: segment plus hit marker
+        assert_eq!(lines.line_count(), 1 + 3);
+        let hit_marker = &v[v.len() - 6..];
+        assert!(hit_marker.iter().all(|v| v.color == PROBE_HIT_COLOR));
+        let center = hit_marker
+            .iter()
+            .fold([0.0; 3], |acc, v| [acc[0] + v.position[0] / 6.0, acc[1] + v.position[1] / 6.0, acc[2] + v.position[2] / 6.0]);
+        assert!(approx(center, [0.0, 0.0, -5.0]));
+    }
+
+    #[test]
+    fn test_probe_ray_without_hit_has_no_hit_marker() {
+        let mut camera = Camera4D::new();
+        camera.adjust_slice_offset(1.0);
+        let lines = ProbeRay::new(camera.position, camera.forward(), 20.0).lines(&camera);
+
+        // Off-slice and parallel to it: just the segment
+        assert_eq!(lines.line_count(), 1);
+    }
+
+    #[test]
+    fn test_probe_slice_crossing_along_w() {
+        let camera = Camera4D::new();
+        let origin = Vec4::new(0.0, 0.0, 0.0, -2.0);
+        let direction = Vec4::new(0.0, 0.0, -1.0, 1.0);
+        let crossing = ProbeRay::new(origin, direction, 10.0).slice_crossing(&camera).unwrap();
+        assert!((crossing - Vec4::new(0.0, 0.0, -2.0, 0.0)).length() < 1e-5);
+
+        // Too short to reach the slice
+        assert!(ProbeRay::new(origin, direction, 1.0).slice_crossing(&camera).is_none());
+    }
+}
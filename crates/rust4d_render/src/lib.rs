@@ -10,6 +10,10 @@
 //! - [`pipeline::SlicePipeline`] - Compute shader for 4D->3D slicing
 //! - [`pipeline::RenderPipeline`] - 3D rendering with lighting
 //! - [`renderable::RenderableGeometry`] - Converts World/Entity to GPU buffers
+//! - [`visibility::VisibilityCache`] - Caches which entities intersect the slice
+//! - [`slice_cache::SliceCache`] - Cached CPU slicing for tools and headless rendering
+//! - [`screenshot::read_texture`] - GPU texture readback for screenshots
+//! - [`debug_lines::ProbeRay`] - Debug line overlay for visualizing 4D rays
 //!
 //! ## Shapes
 //!
@@ -20,6 +24,10 @@ pub mod context;
 pub mod camera4d;
 pub mod pipeline;
 pub mod renderable;
+pub mod visibility;
+pub mod slice_cache;
+pub mod screenshot;
+pub mod debug_lines;
 
 // Re-export core types for convenience
 pub use rust4d_core::{World, Entity, Transform4D, Material, ShapeRef, EntityKey};
@@ -27,4 +35,7 @@ pub use rust4d_core::{ConvexShape4D, Tetrahedron, Tesseract4D, Hyperplane4D};
 pub use rust4d_core::{Vec4, Rotor4, RotationPlane};
 
 // Re-export renderable for easy access
-pub use renderable::{RenderableGeometry, CheckerboardGeometry, position_gradient_color};
+pub use renderable::{RenderableGeometry, LodSettings, CheckerboardGeometry, ColorBlend, ColorFn, position_gradient_color, draw_order, sort_back_to_front};
+pub use visibility::VisibilityCache;
+pub use camera4d::{CameraShake, CameraState, WCompass, SliceSweep, SweepEasing};
+pub use slice_cache::{SliceCache, SliceTriangle, slice_shape, cancel_internal_faces};
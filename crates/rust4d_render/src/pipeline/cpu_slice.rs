@@ -0,0 +1,509 @@
+//! CPU implementation of the slice compute shader
+//!
+//! Used by [`SlicePipeline`](super::SlicePipeline) when the adapter cannot run
+//! compute shaders. This mirrors `slice_tetra.wgsl` step for step (camera
+//! transform, edge intersection, normals, camera-facing winding) so the CPU
+//! and GPU paths produce the same triangles.
+
+use rust4d_math::{mat4, Vec4};
+
+use super::lookup_tables::{TETRA_EDGES, TETRA_EDGE_TABLE, TETRA_FACES, TETRA_TRI_COUNT, TETRA_TRI_TABLE};
+use super::types::{GpuTetrahedron, SliceParams, Vertex3D, Vertex4D};
+
+/// Slice tetrahedra on the CPU, returning 3 output vertices per triangle
+///
+/// At most `params.tetrahedron_count` tetrahedra are processed, like the shader.
+/// With a positive [`slab_thickness`](SliceParams::slab_thickness) each
+/// tetrahedron is clipped to the slab instead of cut by the slice.
+pub fn slice_on_cpu(
+    vertices: &[Vertex4D],
+    tetrahedra: &[GpuTetrahedron],
+    params: &SliceParams,
+) -> Vec<Vertex3D> {
+    let slice_w = params.slice_w;
+    let world_to_slice = params.world_to_slice();
+    let camera_pos = to_vec4(params.camera_position);
+
+    let count = tetrahedra.len().min(params.tetrahedron_count as usize);
+    let mut output = Vec::new();
+
+    for tet in &tetrahedra[..count] {
+        let source = [tet.v0, tet.v1, tet.v2, tet.v3].map(|i| vertices[i as usize]);
+        let pos = source.map(|v| mat4::transform(world_to_slice, to_vec4(v.position) - camera_pos));
+        let color = source.map(|v| v.color);
+
+        if params.slab_thickness > 0.0 {
+            slab_tetrahedron(&pos, &color, &source[0], params, &mut output);
+        } else {
+            let dist = pos.map(|p| slice_distance(p.w, slice_w, params.slice_epsilon));
+            cut_tetrahedron(&pos, &color, dist, slice_w, &source[0], params, &mut output);
+        }
+    }
+
+    output
+}
+
+/// Emit the cross-section of one tetrahedron at the plane where `dist` is zero
+///
+/// `dist` must already be nudged away from zero by [`slice_distance`].
+fn cut_tetrahedron(
+    pos: &[Vec4; 4],
+    color: &[[f32; 4]; 4],
+    dist: [f32; 4],
+    w_depth: f32,
+    material: &Vertex4D,
+    params: &SliceParams,
+    output: &mut Vec<Vertex3D>,
+) {
+    let mut case_idx = 0usize;
+    for (i, d) in dist.iter().enumerate() {
+        if *d > 0.0 {
+            case_idx |= 1 << i;
+        }
+    }
+    if case_idx == 0 || case_idx == 15 {
+        return;
+    }
+
+    // Intersection points for crossed edges, in edge index order
+    let edge_mask = TETRA_EDGE_TABLE[case_idx];
+    let mut points = [Vertex3D::default(); 4];
+    let mut point_idx = 0;
+    for (edge, &[a, b]) in TETRA_EDGES.iter().enumerate() {
+        if (edge_mask >> edge) & 1 == 1 {
+            points[point_idx] = edge_intersection(pos[a], pos[b], color[a], color[b], dist[a], dist[b], w_depth);
+            point_idx += 1;
+        }
+    }
+
+    let tri_indices = TETRA_TRI_TABLE[case_idx];
+    for t in 0..TETRA_TRI_COUNT[case_idx] as usize {
+        let base = t * 3;
+        emit_triangle(
+            [
+                points[tri_indices[base] as usize],
+                points[tri_indices[base + 1] as usize],
+                points[tri_indices[base + 2] as usize],
+            ],
+            material,
+            params,
+            output,
+        );
+    }
+}
+
+/// A polygon vertex while clipping a face to the slab
+#[derive(Clone, Copy)]
+struct SlabPoint {
+    position: Vec4,
+    color: [f32; 4],
+    /// Signed distance from the center plane
+    dist: f32,
+}
+
+/// Emit the part of one tetrahedron that lies inside the slab
+///
+/// Each face is clipped to both bounding planes, and where a bounding plane
+/// cuts through the tetrahedron its cross-section closes the volume off.
+/// A tetrahedron entirely inside the slab just emits its four faces.
+fn slab_tetrahedron(
+    pos: &[Vec4; 4],
+    color: &[[f32; 4]; 4],
+    material: &Vertex4D,
+    params: &SliceParams,
+    output: &mut Vec<Vertex3D>,
+) {
+    let thickness = params.slab_thickness;
+    let dist = pos.map(|p| p.w - params.slice_w);
+    if dist.iter().all(|&d| d > thickness) || dist.iter().all(|&d| d < -thickness) {
+        return;
+    }
+
+    let faded = |c: [f32; 4], d: f32| [c[0], c[1], c[2], c[3] * slab_fade(d, thickness)];
+
+    for face in TETRA_FACES {
+        let polygon: Vec<SlabPoint> = face
+            .iter()
+            .map(|&i| SlabPoint { position: pos[i], color: color[i], dist: dist[i] })
+            .collect();
+        let polygon = clip_polygon(&polygon, -thickness, 1.0);
+        let polygon = clip_polygon(&polygon, thickness, -1.0);
+
+        let vertex = |p: &SlabPoint| Vertex3D {
+            position: [p.position.x, p.position.y, p.position.z],
+            normal: [0.0; 3],
+            color: faded(p.color, p.dist),
+            w_depth: params.slice_w + p.dist,
+            material_id: 0,
+            material_index: 0,
+        };
+        for i in 1..polygon.len().saturating_sub(1) {
+            emit_triangle([vertex(&polygon[0]), vertex(&polygon[i]), vertex(&polygon[i + 1])], material, params, output);
+        }
+    }
+
+    // Caps on the bounding planes
+    let edge_color = color.map(|c| faded(c, thickness));
+    for offset in [-thickness, thickness] {
+        let cap_dist = dist.map(|d| slice_distance(d, offset, params.slice_epsilon));
+        cut_tetrahedron(pos, &edge_color, cap_dist, params.slice_w + offset, material, params, output);
+    }
+}
+
+/// Clip a convex polygon to the side of a bounding plane it should keep
+///
+/// Keeps points with `side * (dist - offset) >= 0`, interpolating new
+/// points where edges cross the plane (Sutherland-Hodgman).
+fn clip_polygon(polygon: &[SlabPoint], offset: f32, side: f32) -> Vec<SlabPoint> {
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        let sa = side * (a.dist - offset);
+        let sb = side * (b.dist - offset);
+        if sa >= 0.0 {
+            clipped.push(a);
+        }
+        if (sa >= 0.0) != (sb >= 0.0) {
+            let t = sa / (sa - sb);
+            let mut color = [0.0; 4];
+            for (k, c) in color.iter_mut().enumerate() {
+                *c = a.color[k] + (b.color[k] - a.color[k]) * t;
+            }
+            clipped.push(SlabPoint {
+                position: a.position.lerp(b.position, t),
+                color,
+                dist: a.dist + (b.dist - a.dist) * t,
+            });
+        }
+    }
+    clipped
+}
+
+/// Alpha multiplier at signed distance `dist` from the center of the slab
+fn slab_fade(dist: f32, thickness: f32) -> f32 {
+    let t = (dist.abs() / thickness).min(1.0);
+    1.0 - (1.0 - SliceParams::SLAB_EDGE_ALPHA) * t
+}
+
+/// Orient a triangle towards the camera, set its normal and material, and emit it
+fn emit_triangle(tri: [Vertex3D; 3], material: &Vertex4D, params: &SliceParams, output: &mut Vec<Vertex3D>) {
+    let [mut tv0, mut tv1, mut tv2] = tri;
+    let mut normal = compute_normal(tv0.position, tv1.position, tv2.position);
+
+    // Ensure normals face toward the camera
+    let center = [
+        (tv0.position[0] + tv1.position[0] + tv2.position[0]) / 3.0,
+        (tv0.position[1] + tv1.position[1] + tv2.position[1]) / 3.0,
+        (tv0.position[2] + tv1.position[2] + tv2.position[2]) / 3.0,
+    ];
+    let to_camera = sub3(params.camera_eye, center);
+    if dot3(normal, to_camera) < 0.0 {
+        std::mem::swap(&mut tv1, &mut tv2);
+        normal = normal.map(|c| -c);
+    }
+
+    // A tetrahedron belongs to a single entity, so any vertex carries its material
+    for v in [&mut tv0, &mut tv1, &mut tv2] {
+        v.normal = normal;
+        v.material_id = material.material_id;
+        v.material_index = material.material_index;
+    }
+    output.extend([tv0, tv1, tv2]);
+}
+
+/// Signed distance from the slice, nudged to +epsilon for on-plane vertices
+///
+/// `w` is the vertex's coordinate along the slice normal.
+///
+/// Crossed edges then always have endpoint distances of opposite sign and at
+/// least epsilon in size, so interpolation never divides by ~0.
+pub(crate) fn slice_distance(w: f32, slice_w: f32, epsilon: f32) -> f32 {
+    let d = w - slice_w;
+    if d.abs() < epsilon { epsilon } else { d }
+}
+
+/// Interpolate position and color where the edge crosses the slice
+fn edge_intersection(p0: Vec4, p1: Vec4, c0: [f32; 4], c1: [f32; 4], d0: f32, d1: f32, w_depth: f32) -> Vertex3D {
+    let t = (d0 / (d0 - d1)).clamp(0.0, 1.0);
+    let pos = p0.lerp(p1, t);
+
+    let mut color = [0.0; 4];
+    for (i, c) in color.iter_mut().enumerate() {
+        *c = c0[i] + (c1[i] - c0[i]) * t;
+    }
+
+    Vertex3D {
+        position: [pos.x, pos.y, pos.z],
+        normal: [0.0; 3],
+        color,
+        w_depth,
+        material_id: 0,
+        material_index: 0,
+    }
+}
+
+fn compute_normal(p0: [f32; 3], p1: [f32; 3], p2: [f32; 3]) -> [f32; 3] {
+    let e1 = sub3(p1, p0);
+    let e2 = sub3(p2, p0);
+    let n = [
+        e1[1] * e2[2] - e1[2] * e2[1],
+        e1[2] * e2[0] - e1[0] * e2[2],
+        e1[0] * e2[1] - e1[1] * e2[0],
+    ];
+    let len = dot3(n, n).sqrt();
+    if len < 0.0001 {
+        return [0.0, 1.0, 0.0]; // Degenerate triangle fallback
+    }
+    n.map(|c| c / len)
+}
+
+fn to_vec4(a: [f32; 4]) -> Vec4 {
+    Vec4::new(a[0], a[1], a[2], a[3])
+}
+
+fn sub3(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot3(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::slice_cache::slice_shape;
+    use rust4d_math::{ConvexShape4D, Tesseract4D};
+
+    fn tesseract_buffers() -> (Tesseract4D, Vec<Vertex4D>, Vec<GpuTetrahedron>) {
+        let tesseract = Tesseract4D::new(2.0);
+        let vertices = tesseract
+            .vertices()
+            .iter()
+            .map(|v| Vertex4D::from_position([v.x, v.y, v.z, v.w]).with_material_id(3).with_material_index(7))
+            .collect();
+        let tetrahedra = tesseract
+            .tetrahedra()
+            .iter()
+            .map(|t| GpuTetrahedron::from_indices(t.indices.map(|i| i as u32)))
+            .collect();
+        (tesseract, vertices, tetrahedra)
+    }
+
+    /// Camera at the origin with identity rotation: camera space == world space
+    fn identity_params(slice_w: f32, tetrahedron_count: usize) -> SliceParams {
+        SliceParams {
+            slice_w,
+            tetrahedron_count: tetrahedron_count as u32,
+            camera_eye: [0.0, 0.0, 5.0],
+            camera_position: [0.0; 4],
+            ..SliceParams::default()
+        }
+    }
+
+    fn sorted_points(mut tri: [[f32; 3]; 3]) -> [[f32; 3]; 3] {
+        tri.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        tri
+    }
+
+    #[test]
+    fn test_cpu_slice_tesseract_non_empty() {
+        let (_, vertices, tetrahedra) = tesseract_buffers();
+        let output = slice_on_cpu(&vertices, &tetrahedra, &identity_params(0.0, tetrahedra.len()));
+
+        assert!(!output.is_empty());
+        assert_eq!(output.len() % 3, 0);
+        for v in &output {
+            assert_eq!(v.material_id, 3);
+            assert_eq!(v.material_index, 7);
+            assert_eq!(v.w_depth, 0.0);
+            let len = dot3(v.normal, v.normal).sqrt();
+            assert!((len - 1.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_cpu_slice_matches_reference_triangles() {
+        let (tesseract, vertices, tetrahedra) = tesseract_buffers();
+        let output = slice_on_cpu(&vertices, &tetrahedra, &identity_params(0.3, tetrahedra.len()));
+        let reference = slice_shape(&tesseract, 0.3);
+
+        assert_eq!(output.len() / 3, reference.len());
+        // Same triangles in the same order; only the winding may be flipped
+        for (tri, expected) in output.chunks(3).zip(reference) {
+            let got = sorted_points([tri[0].position, tri[1].position, tri[2].position]);
+            let want = sorted_points(expected);
+            for (a, b) in got.iter().flatten().zip(want.iter().flatten()) {
+                assert!((a - b).abs() < 1e-5, "{:?} vs {:?}", got, want);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cpu_slice_normals_face_camera() {
+        let (_, vertices, tetrahedra) = tesseract_buffers();
+        let params = identity_params(0.0, tetrahedra.len());
+        let output = slice_on_cpu(&vertices, &tetrahedra, &params);
+
+        for tri in output.chunks(3) {
+            let to_camera = sub3(params.camera_eye, tri[0].position);
+            assert!(dot3(tri[0].normal, to_camera) >= -1e-4);
+        }
+    }
+
+    #[test]
+    fn test_cpu_slice_through_vertices_is_finite() {
+        let (_, vertices, tetrahedra) = tesseract_buffers();
+
+        // Every vertex of the tesseract has w = +-1
+        for slice_w in [1.0, -1.0] {
+            let output = slice_on_cpu(&vertices, &tetrahedra, &identity_params(slice_w, tetrahedra.len()));
+            assert_eq!(output.len() % 3, 0);
+            for v in &output {
+                assert!(v.position.iter().all(|c| c.is_finite() && c.abs() <= 1.0 + 1e-5));
+                assert!(v.color.iter().all(|c| c.is_finite()));
+                assert!((dot3(v.normal, v.normal).sqrt() - 1.0).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cpu_slice_default_normal_is_w() {
+        let (_, vertices, tetrahedra) = tesseract_buffers();
+        let params = identity_params(0.3, tetrahedra.len());
+        let explicit = SliceParams { slice_normal: [0.0, 0.0, 0.0, 1.0], ..params };
+
+        let a = slice_on_cpu(&vertices, &tetrahedra, &params);
+        let b = slice_on_cpu(&vertices, &tetrahedra, &explicit);
+        assert_eq!(a.len(), b.len());
+        for (va, vb) in a.iter().zip(&b) {
+            assert_eq!(va.position, vb.position);
+        }
+    }
+
+    #[test]
+    fn test_cpu_slice_along_custom_normal() {
+        let (_, vertices, tetrahedra) = tesseract_buffers();
+
+        // Slicing across X gives the tesseract's YZW cube, seen face-on:
+        // X turns onto W and W onto -X, so the output fills the cube [-1, 1]^3
+        // instead of collapsing onto the x == 0.25 sheet
+        let params = SliceParams { slice_normal: [1.0, 0.0, 0.0, 0.0], ..identity_params(0.25, tetrahedra.len()) };
+        let output = slice_on_cpu(&vertices, &tetrahedra, &params);
+        assert!(!output.is_empty());
+        for axis in 0..3 {
+            let values = output.iter().map(|v| v.position[axis]);
+            assert!(values.clone().all(|c| c.abs() <= 1.0 + 1e-5));
+            assert!((values.clone().fold(f32::MAX, f32::min) + 1.0).abs() < 1e-5);
+            assert!((values.fold(f32::MIN, f32::max) - 1.0).abs() < 1e-5);
+        }
+        // The cut keeps the original x as depth along the normal
+        assert!(output.iter().all(|v| v.w_depth == 0.25));
+
+        // Tilting the plane towards X cuts the tesseract diagonally, which
+        // stretches the cross-section: 0.8x + 0.6w == 0 reaches x = 0.75,
+        // w = -1, 1.25 from the center along the plane
+        let tilted = SliceParams {
+            slice_normal: [0.8, 0.0, 0.0, 0.6],
+            ..identity_params(0.0, tetrahedra.len())
+        };
+        let straight = slice_on_cpu(&vertices, &tetrahedra, &identity_params(0.0, tetrahedra.len()));
+        let rotated = slice_on_cpu(&vertices, &tetrahedra, &tilted);
+        let max_x = |out: &[Vertex3D]| out.iter().map(|v| v.position[0]).fold(f32::MIN, f32::max);
+        assert!((max_x(&straight) - 1.0).abs() < 1e-5);
+        assert!((max_x(&rotated) - 1.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_slice_basis_turns_normal_onto_w() {
+        use crate::pipeline::types::slice_basis;
+
+        for normal in [Vec4::W, -Vec4::W, Vec4::X, Vec4::new(0.8, 0.0, 0.0, 0.6), Vec4::new(1.0, -2.0, 0.5, -1.0)] {
+            let basis = slice_basis(normal);
+            let n = normal.normalized();
+            assert!((mat4::transform(basis, n) - Vec4::W).length() < 1e-5, "{:?}", normal);
+            assert!((mat4::determinant(basis) - 1.0).abs() < 1e-5);
+        }
+        // Directions off the normal's plane are untouched
+        assert_eq!(mat4::transform(slice_basis(Vec4::X), Vec4::Y), Vec4::Y);
+    }
+
+    /// A single tetrahedron with the given vertex W coordinates
+    fn single_tetrahedron(w: [f32; 4]) -> (Vec<Vertex4D>, Vec<GpuTetrahedron>) {
+        let corners = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        let vertices = corners
+            .iter()
+            .zip(w)
+            .map(|(c, w)| Vertex4D::from_position([c[0], c[1], c[2], w]))
+            .collect();
+        (vertices, vec![GpuTetrahedron::from_indices([0, 1, 2, 3])])
+    }
+
+    #[test]
+    fn test_slab_keeps_tetrahedron_inside_whole() {
+        let (vertices, tetrahedra) = single_tetrahedron([0.0, 0.1, -0.1, 0.2]);
+        let params = SliceParams { slab_thickness: 0.5, ..identity_params(0.0, 1) };
+        let output = slice_on_cpu(&vertices, &tetrahedra, &params);
+
+        // The four faces, made of the projected corners
+        assert_eq!(output.len(), 4 * 3);
+        for v in &output {
+            assert!(vertices.iter().any(|c| c.position[..3] == v.position));
+        }
+
+        // Alpha fades with distance from the center plane
+        let alpha_at = |w: f32| output.iter().find(|v| (v.w_depth - w).abs() < 1e-6).unwrap().color[3];
+        assert_eq!(alpha_at(0.0), 1.0);
+        assert!(alpha_at(0.2) < alpha_at(0.1));
+        assert!(alpha_at(0.2) > SliceParams::SLAB_EDGE_ALPHA);
+
+        // Thin slicing the same tetrahedron only gives its cross-section
+        let thin = slice_on_cpu(&vertices, &tetrahedra, &identity_params(0.0, 1));
+        assert!(thin.len() < output.len());
+    }
+
+    #[test]
+    fn test_slab_clips_straddling_tetrahedron() {
+        let (vertices, tetrahedra) = single_tetrahedron([-1.0, 1.0, 1.0, 1.0]);
+        let params = SliceParams { slab_thickness: 0.5, ..identity_params(0.0, 1) };
+        let output = slice_on_cpu(&vertices, &tetrahedra, &params);
+
+        // Three faces clipped to quads, the fourth is outside, plus a cap on
+        // each bounding plane
+        assert_eq!(output.len() / 3, 3 * 2 + 2);
+        for v in &output {
+            assert!((-0.5 - 1e-5..=0.5 + 1e-5).contains(&v.w_depth), "{}", v.w_depth);
+            // w rises linearly from -1 at the origin to 1 on the far face, so
+            // every point of the tetrahedron has x + y + z == (w + 1) / 2
+            let sum: f32 = v.position.iter().sum();
+            assert!((sum - (v.w_depth + 1.0) / 2.0).abs() < 1e-5);
+        }
+
+        // The caps are the thin slices at the bounding planes, at the edge alpha
+        for w in [-0.5, 0.5] {
+            let cap: Vec<_> = output.chunks(3).filter(|tri| tri.iter().all(|v| (v.w_depth - w).abs() < 1e-6)).collect();
+            assert_eq!(cap.len(), 1);
+            let thin = slice_on_cpu(&vertices, &tetrahedra, &identity_params(w, 1));
+            let got = sorted_points([cap[0][0].position, cap[0][1].position, cap[0][2].position]);
+            let want = sorted_points([thin[0].position, thin[1].position, thin[2].position]);
+            for (a, b) in got.iter().flatten().zip(want.iter().flatten()) {
+                assert!((a - b).abs() < 1e-5);
+            }
+            assert!(cap[0].iter().all(|v| (v.color[3] - SliceParams::SLAB_EDGE_ALPHA).abs() < 1e-6));
+        }
+    }
+
+    #[test]
+    fn test_slab_skips_tetrahedron_outside() {
+        let (vertices, tetrahedra) = single_tetrahedron([2.0, 3.0, 2.5, 2.0]);
+        let params = SliceParams { slab_thickness: 0.5, ..identity_params(0.0, 1) };
+        assert!(slice_on_cpu(&vertices, &tetrahedra, &params).is_empty());
+    }
+
+    #[test]
+    fn test_cpu_slice_respects_tetrahedron_count() {
+        let (_, vertices, tetrahedra) = tesseract_buffers();
+        let output = slice_on_cpu(&vertices, &tetrahedra, &identity_params(0.0, 0));
+        assert!(output.is_empty());
+    }
+}
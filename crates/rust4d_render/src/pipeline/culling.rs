@@ -0,0 +1,247 @@
+//! Culling tetrahedra before slicing
+//!
+//! A tetrahedron can only add visible triangles if it reaches the slice
+//! hyperplane (or the slab around it) and its cross-section lands inside the
+//! view frustum. Both are tested conservatively against the tetrahedron's 4D
+//! bounding box, carried into camera space, so culling never removes anything
+//! that slicing would have drawn.
+//!
+//! [`SlicePipeline`](super::SlicePipeline) runs this on the CPU each frame
+//! when culling is enabled and slices only the surviving tetrahedra.
+
+use rust4d_math::{mat4, Vec4};
+
+use super::types::{GpuTetrahedron, SliceParams, Vertex4D};
+
+/// World-space axis-aligned bounds of one tetrahedron
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TetraBounds {
+    pub min: Vec4,
+    pub max: Vec4,
+}
+
+impl TetraBounds {
+    /// Bounds of the tetrahedron's four vertices
+    pub fn new(vertices: &[Vertex4D], tet: &GpuTetrahedron) -> Self {
+        let corners = [tet.v0, tet.v1, tet.v2, tet.v3].map(|i| to_vec4(vertices[i as usize].position));
+        let mut bounds = Self { min: corners[0], max: corners[0] };
+        for c in &corners[1..] {
+            bounds.min = bounds.min.min_components(*c);
+            bounds.max = bounds.max.max_components(*c);
+        }
+        bounds
+    }
+
+    /// Slice-space center and half-extents of a box containing these bounds
+    ///
+    /// See [`SliceParams::world_to_slice`].
+    fn to_slice_space(self, params: &SliceParams) -> (Vec4, Vec4) {
+        let world_to_slice = params.world_to_slice();
+        let center = (self.min + self.max) * 0.5;
+        let half = (self.max - self.min) * 0.5;
+        let center = mat4::transform(world_to_slice, center - to_vec4(params.camera_position));
+
+        // Each slice axis spans the rotated extents in absolute value
+        let m = world_to_slice;
+        let extent = |row: usize| {
+            m[0][row].abs() * half.x + m[1][row].abs() * half.y + m[2][row].abs() * half.z + m[3][row].abs() * half.w
+        };
+        (center, Vec4::new(extent(0), extent(1), extent(2), extent(3)))
+    }
+}
+
+/// The 3D view volume of the render pass, as six inward-facing planes
+///
+/// Planes are in the space the slice shader outputs (camera space for the
+/// default slice normal).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViewFrustum {
+    /// `(a, b, c, d)` with `a*x + b*y + c*z + d >= 0` inside
+    planes: [[f32; 4]; 6],
+}
+
+impl ViewFrustum {
+    /// Extract the frustum from a view-projection matrix (column-major, depth 0..1)
+    pub fn from_matrix(view_projection: [[f32; 4]; 4]) -> Self {
+        let m = view_projection;
+        let row = |i: usize| [m[0][i], m[1][i], m[2][i], m[3][i]];
+        let (x, y, z, w) = (row(0), row(1), row(2), row(3));
+        let add = |a: [f32; 4], b: [f32; 4]| [a[0] + b[0], a[1] + b[1], a[2] + b[2], a[3] + b[3]];
+        let sub = |a: [f32; 4], b: [f32; 4]| [a[0] - b[0], a[1] - b[1], a[2] - b[2], a[3] - b[3]];
+        Self {
+            planes: [add(w, x), sub(w, x), add(w, y), sub(w, y), z, sub(w, z)],
+        }
+    }
+
+    /// Check if a box (center and half-extents) may be at least partly inside
+    pub fn intersects_box(&self, center: [f32; 3], extents: [f32; 3]) -> bool {
+        self.planes.iter().all(|p| {
+            let distance = p[0] * center[0] + p[1] * center[1] + p[2] * center[2] + p[3];
+            let radius = p[0].abs() * extents[0] + p[1].abs() * extents[1] + p[2].abs() * extents[2];
+            distance + radius >= 0.0
+        })
+    }
+}
+
+/// Check if a tetrahedron with these bounds may produce visible triangles
+///
+/// It must reach within `slab_thickness` (plus the on-plane epsilon) of the
+/// slice, and, given a `frustum`, overlap it.
+pub fn may_be_visible(bounds: TetraBounds, params: &SliceParams, frustum: Option<&ViewFrustum>) -> bool {
+    let (center, half) = bounds.to_slice_space(params);
+
+    let distance = center.w - params.slice_w;
+    if distance.abs() > half.w + params.slab_thickness + params.slice_epsilon {
+        return false;
+    }
+
+    frustum.is_none_or(|f| f.intersects_box([center.x, center.y, center.z], [half.x, half.y, half.z]))
+}
+
+/// The tetrahedra among the first `params.tetrahedron_count` that may be visible, in order
+///
+/// `bounds` holds one entry per tetrahedron (see [`TetraBounds::new`]).
+pub fn cull_tetrahedra(
+    tetrahedra: &[GpuTetrahedron],
+    bounds: &[TetraBounds],
+    params: &SliceParams,
+    frustum: Option<&ViewFrustum>,
+) -> Vec<GpuTetrahedron> {
+    let count = tetrahedra.len().min(params.tetrahedron_count as usize);
+    tetrahedra[..count]
+        .iter()
+        .zip(bounds)
+        .filter(|(_, b)| may_be_visible(**b, params, frustum))
+        .map(|(t, _)| *t)
+        .collect()
+}
+
+fn to_vec4(v: [f32; 4]) -> Vec4 {
+    Vec4::new(v[0], v[1], v[2], v[3])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::{perspective_matrix, slice_on_cpu};
+    use rust4d_math::{ConvexShape4D, Tesseract4D};
+
+    /// Tesseracts of size 2 centered at each offset
+    fn tesseracts(offsets: &[Vec4]) -> (Vec<Vertex4D>, Vec<GpuTetrahedron>) {
+        let tesseract = Tesseract4D::new(2.0);
+        let mut vertices = Vec::new();
+        let mut tetrahedra = Vec::new();
+        for offset in offsets {
+            let base = vertices.len() as u32;
+            vertices.extend(tesseract.vertices().iter().map(|v| {
+                let p = *v + *offset;
+                Vertex4D::from_position([p.x, p.y, p.z, p.w])
+            }));
+            tetrahedra.extend(
+                tesseract
+                    .tetrahedra()
+                    .iter()
+                    .map(|t| GpuTetrahedron::from_indices(t.indices.map(|i| base + i as u32))),
+            );
+        }
+        (vertices, tetrahedra)
+    }
+
+    fn bounds(vertices: &[Vertex4D], tetrahedra: &[GpuTetrahedron]) -> Vec<TetraBounds> {
+        tetrahedra.iter().map(|t| TetraBounds::new(vertices, t)).collect()
+    }
+
+    /// Camera at the origin looking down -Z: camera space == world space
+    fn params(tetrahedron_count: usize) -> SliceParams {
+        SliceParams {
+            tetrahedron_count: tetrahedron_count as u32,
+            camera_eye: [0.0; 3],
+            camera_position: [0.0; 4],
+            ..SliceParams::default()
+        }
+    }
+
+    fn frustum() -> ViewFrustum {
+        ViewFrustum::from_matrix(perspective_matrix(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0))
+    }
+
+    #[test]
+    fn test_geometry_far_from_slice_is_culled() {
+        let (vertices, tetrahedra) = tesseracts(&[Vec4::new(0.0, 0.0, -5.0, 10.0)]);
+        let params = params(tetrahedra.len());
+
+        assert!(slice_on_cpu(&vertices, &tetrahedra, &params).is_empty());
+        assert!(cull_tetrahedra(&tetrahedra, &bounds(&vertices, &tetrahedra), &params, None).is_empty());
+
+        // A slab wide enough to reach it keeps all of it
+        let slab = SliceParams { slab_thickness: 11.5, ..params };
+        assert_eq!(cull_tetrahedra(&tetrahedra, &bounds(&vertices, &tetrahedra), &slab, None).len(), tetrahedra.len());
+    }
+
+    #[test]
+    fn test_geometry_behind_camera_is_culled() {
+        let (vertices, tetrahedra) = tesseracts(&[Vec4::new(0.0, 0.0, 10.0, 0.0)]);
+        let bounds = bounds(&vertices, &tetrahedra);
+        let params = params(tetrahedra.len());
+
+        // It is on the slice, but outside the view
+        assert!(!cull_tetrahedra(&tetrahedra, &bounds, &params, None).is_empty());
+        assert!(cull_tetrahedra(&tetrahedra, &bounds, &params, Some(&frustum())).is_empty());
+    }
+
+    #[test]
+    fn test_culling_keeps_visible_output_identical() {
+        // In view, far along W, behind the camera, and straddling the frustum edge
+        let (vertices, tetrahedra) = tesseracts(&[
+            Vec4::new(0.0, 0.0, -5.0, 0.5),
+            Vec4::new(0.0, 0.0, -5.0, 10.0),
+            Vec4::new(0.0, 0.0, 10.0, 0.0),
+            Vec4::new(5.0, 0.0, -5.0, -0.5),
+        ]);
+        let params = params(tetrahedra.len());
+        let culled = cull_tetrahedra(&tetrahedra, &bounds(&vertices, &tetrahedra), &params, Some(&frustum()));
+        assert!(culled.len() < tetrahedra.len() / 2);
+
+        // Everything the full slice draws inside the view is still there, in order
+        let frustum = frustum();
+        let in_view = |v: &[f32; 3]| frustum.intersects_box(*v, [0.0; 3]);
+        let visible: Vec<[f32; 3]> = slice_on_cpu(&vertices, &tetrahedra, &params)
+            .chunks(3)
+            .filter(|tri| tri.iter().any(|v| in_view(&v.position)))
+            .flatten()
+            .map(|v| v.position)
+            .collect();
+        let kept_params = SliceParams { tetrahedron_count: culled.len() as u32, ..params };
+        let kept: Vec<[f32; 3]> = slice_on_cpu(&vertices, &culled, &kept_params)
+            .chunks(3)
+            .filter(|tri| tri.iter().any(|v| in_view(&v.position)))
+            .flatten()
+            .map(|v| v.position)
+            .collect();
+        assert!(!visible.is_empty());
+        assert_eq!(kept, visible);
+    }
+
+    #[test]
+    fn test_bounds_follow_camera_rotation() {
+        // On the unrotated slice, and on either side of it along W
+        let (vertices, tetrahedra) = tesseracts(&[
+            Vec4::new(0.0, 0.0, -5.0, 0.0),
+            Vec4::new(0.0, 0.0, 0.0, 5.0),
+            Vec4::new(0.0, 0.0, 0.0, -5.0),
+        ]);
+        let bounds = bounds(&vertices, &tetrahedra);
+        let per_tesseract = tetrahedra.len() / 3;
+
+        // Turned 90 degrees in ZW, world W is camera-space Z, so the first is
+        // now far from the slice and the others cross it
+        let quarter = rust4d_math::Rotor4::from_plane_angle(rust4d_math::RotationPlane::ZW, std::f32::consts::FRAC_PI_2);
+        let params = SliceParams { camera_matrix: quarter.to_matrix(), ..params(tetrahedra.len()) };
+        let full = slice_on_cpu(&vertices, &tetrahedra, &params);
+        let culled = cull_tetrahedra(&tetrahedra, &bounds, &params, None);
+        let kept = slice_on_cpu(&vertices, &culled, &SliceParams { tetrahedron_count: culled.len() as u32, ..params });
+        assert!(!full.is_empty());
+        assert!(culled.len() <= 2 * per_tesseract);
+        assert_eq!(kept.len(), full.len());
+    }
+}
@@ -17,6 +17,14 @@ pub const TETRA_EDGES: [[usize; 2]; 6] = [
     [2, 3], // Edge 5
 ];
 
+/// Face definitions for a tetrahedron: face i is the one opposite vertex i
+pub const TETRA_FACES: [[usize; 3]; 4] = [
+    [1, 2, 3], // Face 0
+    [0, 2, 3], // Face 1
+    [0, 1, 3], // Face 2
+    [0, 1, 2], // Face 3
+];
+
 /// For each case (0-15), which edges are crossed by the slice plane.
 /// Bit i is set if edge i is crossed.
 pub const TETRA_EDGE_TABLE: [u8; 16] = compute_tetra_edge_table();
@@ -263,6 +271,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_range_loop)]
     fn test_tetra_tri_indices_valid() {
         for case_idx in 0..16 {
             let edge_mask = TETRA_EDGE_TABLE[case_idx];
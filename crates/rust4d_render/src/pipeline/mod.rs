@@ -7,19 +7,56 @@ pub mod lookup_tables;
 pub mod types;
 pub mod slice_pipeline;
 pub mod render_pipeline;
+pub mod upscale_pipeline;
+pub mod cpu_slice;
+pub mod smooth_normals;
+pub mod slice_cap;
+pub mod culling;
+pub mod timestamps;
 
 // Re-export lookup tables (tetrahedra tables only)
 pub use lookup_tables::{
-    TETRA_EDGES, TETRA_EDGE_TABLE, TETRA_TRI_TABLE, TETRA_TRI_COUNT,
+    TETRA_EDGES, TETRA_FACES, TETRA_EDGE_TABLE, TETRA_TRI_TABLE, TETRA_TRI_COUNT,
     tetra_edge_count, tetra_crossed_edges,
 };
 
 // Re-export types
 pub use types::{
-    Vertex4D, Vertex3D, SliceParams, RenderUniforms,
-    AtomicCounter, GpuTetrahedron, MAX_OUTPUT_TRIANGLES, TRIANGLE_VERTEX_COUNT,
+    Vertex4D, Vertex3D, SliceParams, RenderUniforms, GpuMaterial, Light, MAX_LIGHTS,
+    AtomicCounter, GpuTetrahedron, MAX_OUTPUT_TRIANGLES, TRIANGLE_VERTEX_COUNT, TRANSPARENT_MATERIAL_BIT,
 };
 
 // Re-export pipelines
-pub use slice_pipeline::SlicePipeline;
-pub use render_pipeline::{RenderPipeline, DrawIndirectArgs, perspective_matrix, look_at_matrix, mat4_mul};
+pub use slice_pipeline::{SlicePipeline, SliceMode};
+pub use cpu_slice::slice_on_cpu;
+pub use culling::{cull_tetrahedra, may_be_visible, TetraBounds, ViewFrustum};
+pub use timestamps::{GpuTimer, PassTimings};
+pub use smooth_normals::{smooth_normals, NORMAL_POSITION_QUANTUM, SMOOTH_NORMAL_MIN_DOT};
+pub use slice_cap::cap_slice;
+pub use render_pipeline::{RenderPipeline, DrawIndirectArgs, perspective_matrix, orthographic_matrix, look_at_matrix, mat4_mul};
+pub use upscale_pipeline::{UpscalePipeline, scaled_size, MIN_RENDER_SCALE};
+
+/// A windowless device for GPU tests, or `None` (with a note) without an adapter
+///
+/// The device gets the adapter's own limits and whichever of `features` the
+/// adapter has. GPU tests return early on `None`, so they pass on machines
+/// without a GPU.
+#[cfg(test)]
+pub(crate) fn headless_device(features: wgpu::Features) -> Option<(wgpu::Device, wgpu::Queue)> {
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+    let device = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default())).and_then(|adapter| {
+        pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                required_features: adapter.features() & features,
+                required_limits: adapter.limits(),
+                ..Default::default()
+            },
+            None,
+        ))
+        .ok()
+    });
+    if device.is_none() {
+        eprintln!("skipping: no GPU adapter available");
+    }
+    device
+}
@@ -2,10 +2,15 @@
 //!
 //! This pipeline renders the triangles produced by the slice compute shader.
 //! It uses indirect drawing to handle variable triangle counts efficiently.
+//! Transparent entities are drawn after the opaque ones, alpha blended.
+//! Debug lines (see [`crate::debug_lines`]) are drawn over the result.
 
+use std::collections::HashMap;
+
+use rust4d_core::Material;
 use wgpu::util::DeviceExt;
 
-use super::types::{RenderUniforms, Vertex3D};
+use super::types::{GpuMaterial, Light, RenderUniforms, Vertex3D};
 
 /// Indirect draw arguments structure (matches wgpu's DrawIndirect)
 #[repr(C)]
@@ -17,22 +22,67 @@ pub struct DrawIndirectArgs {
     pub first_instance: u32,
 }
 
+/// The wgpu pipelines registered for one material id
+struct MaterialPipeline {
+    material_id: u16,
+    /// Draws opaque triangles, writing depth
+    pipeline: wgpu::RenderPipeline,
+    /// Draws transparent triangles, blended and without depth writes
+    transparent_pipeline: wgpu::RenderPipeline,
+}
+
 /// Render pipeline for 3D cross-section display
-#[allow(dead_code)] // bind_group_layout needed for potential future bind group recreation
+///
+/// Holds one wgpu pipeline per registered material id. The built-in
+/// [`Material::LIT_ID`] and [`Material::UNLIT_ID`] pipelines are created
+/// on construction; more can be added with [`register_material`](Self::register_material).
+///
+/// Per-entity lighting multipliers live in a storage buffer filled by
+/// [`upload_materials`](Self::upload_materials) and indexed by each vertex's
+/// `material_index`.
+///
+/// Triangles flagged with [`TRANSPARENT_MATERIAL_BIT`](super::TRANSPARENT_MATERIAL_BIT)
+/// are drawn by a second pipeline per material id, after all opaque ones,
+/// with alpha blending, depth testing and no depth writes. They blend in the
+/// order the slice pass emits them, which keeps the uploaded order of
+/// transparent entities, so the geometry should list them back to front (see
+/// [`sort_back_to_front`](crate::sort_back_to_front)). Each material id's
+/// transparent triangles are drawn together, so the order only holds among
+/// entities sharing a material id.
 pub struct RenderPipeline {
-    /// The render pipeline
-    pipeline: wgpu::RenderPipeline,
-    /// Bind group layout for uniforms
+    /// Registered pipelines, sorted by material id
+    pipelines: Vec<MaterialPipeline>,
+    /// Shader module shared by all material pipelines
+    shader: wgpu::ShaderModule,
+    /// Pipeline layout shared by all material pipelines
+    pipeline_layout: wgpu::PipelineLayout,
+    /// Output surface format
+    surface_format: wgpu::TextureFormat,
+    /// Bind group layout for uniforms and materials
     bind_group_layout: wgpu::BindGroupLayout,
     /// Uniform buffer
     uniform_buffer: wgpu::Buffer,
-    /// Bind group for uniforms
+    /// Lights from [`set_lights`](Self::set_lights), if any
+    lights: Option<Vec<Light>>,
+    /// Per-entity material buffer
+    material_buffer: wgpu::Buffer,
+    /// Number of materials the material buffer can hold
+    material_capacity: usize,
+    /// Bind group for uniforms and materials
     bind_group: wgpu::BindGroup,
     /// Indirect draw buffer
     indirect_buffer: wgpu::Buffer,
     /// Depth texture
     depth_texture: Option<wgpu::TextureView>,
     depth_size: (u32, u32),
+    /// Unlit line-list pipeline for debug lines
+    line_pipeline: wgpu::RenderPipeline,
+    /// Debug line vertices
+    line_buffer: wgpu::Buffer,
+    /// Number of vertices the line buffer can hold
+    line_capacity: usize,
+    /// Number of line vertices uploaded for the next draw
+    line_vertex_count: u32,
 }
 
 impl RenderPipeline {
@@ -53,6 +103,17 @@ impl RenderPipeline {
                     },
                     count: None,
                 },
+                // Per-entity materials
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
             ],
         });
 
@@ -70,21 +131,106 @@ impl RenderPipeline {
             source: wgpu::ShaderSource::Wgsl(shader_source.into()),
         });
 
-        // Create render pipeline
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Render Pipeline"),
-            layout: Some(&pipeline_layout),
+        // Create uniform buffer
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Render Uniform Buffer"),
+            contents: bytemuck::bytes_of(&RenderUniforms::default()),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        // Storage buffers cannot be empty, so start with one default material
+        let material_buffer = Self::create_material_buffer(device, &[GpuMaterial::default()]);
+        let bind_group = Self::create_bind_group(device, &bind_group_layout, &uniform_buffer, &material_buffer);
+
+        // Create indirect draw buffer
+        let indirect_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Indirect Draw Buffer"),
+            contents: bytemuck::bytes_of(&DrawIndirectArgs {
+                vertex_count: 0,
+                instance_count: 1,
+                first_vertex: 0,
+                first_instance: 0,
+            }),
+            usage: wgpu::BufferUsages::INDIRECT | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let line_pipeline = Self::create_line_pipeline(device, &shader, &pipeline_layout, surface_format);
+        let line_buffer = Self::create_line_buffer(device, 1);
+
+        let mut render_pipeline = Self {
+            pipelines: Vec::new(),
+            shader,
+            pipeline_layout,
+            surface_format,
+            bind_group_layout,
+            uniform_buffer,
+            lights: None,
+            material_buffer,
+            material_capacity: 1,
+            bind_group,
+            indirect_buffer,
+            depth_texture: None,
+            depth_size: (0, 0),
+            line_pipeline,
+            line_buffer,
+            line_capacity: 1,
+            line_vertex_count: 0,
+        };
+
+        render_pipeline.register_material(device, Material::LIT_ID, "fs_main");
+        render_pipeline.register_material(device, Material::UNLIT_ID, "fs_unlit");
+        render_pipeline
+    }
+
+    /// Register (or replace) the pipelines used for a material id
+    ///
+    /// `fragment_entry` names a fragment entry point in `render.wgsl`. It
+    /// serves both the opaque and the transparent pass.
+    pub fn register_material(&mut self, device: &wgpu::Device, material_id: u16, fragment_entry: &str) {
+        let pipeline = self.create_material_pipeline(device, material_id, fragment_entry, false);
+        let transparent_pipeline = self.create_material_pipeline(device, material_id, fragment_entry, true);
+
+        match self.pipelines.binary_search_by_key(&material_id, |p| p.material_id) {
+            Ok(idx) => {
+                self.pipelines[idx].pipeline = pipeline;
+                self.pipelines[idx].transparent_pipeline = transparent_pipeline;
+            }
+            Err(idx) => self.pipelines.insert(idx, MaterialPipeline { material_id, pipeline, transparent_pipeline }),
+        }
+    }
+
+    /// Build the opaque or transparent pipeline for a material id
+    fn create_material_pipeline(
+        &self,
+        device: &wgpu::Device,
+        material_id: u16,
+        fragment_entry: &str,
+        transparent: bool,
+    ) -> wgpu::RenderPipeline {
+        let constants = HashMap::from([("MATERIAL_ID".to_string(), material_id as f64)]);
+        let (label, vertex_entry) = if transparent {
+            (format!("Transparent Render Pipeline (material {})", material_id), "vs_transparent")
+        } else {
+            (format!("Render Pipeline (material {})", material_id), "vs_main")
+        };
+
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(&label),
+            layout: Some(&self.pipeline_layout),
             vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
+                module: &self.shader,
+                entry_point: Some(vertex_entry),
                 buffers: &[Self::vertex_buffer_layout()],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                compilation_options: wgpu::PipelineCompilationOptions {
+                    constants: &constants,
+                    ..Default::default()
+                },
             },
             fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
+                module: &self.shader,
+                entry_point: Some(fragment_entry),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_format,
+                    format: self.surface_format,
                     blend: Some(wgpu::BlendState::ALPHA_BLENDING),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
@@ -99,9 +245,10 @@ impl RenderPipeline {
                 polygon_mode: wgpu::PolygonMode::Fill,
                 conservative: false,
             },
+            // Transparent surfaces are hidden by opaque ones but not by each other
             depth_stencil: Some(wgpu::DepthStencilState {
                 format: wgpu::TextureFormat::Depth32Float,
-                depth_write_enabled: true,
+                depth_write_enabled: !transparent,
                 depth_compare: wgpu::CompareFunction::Less,
                 stencil: wgpu::StencilState::default(),
                 bias: wgpu::DepthBiasState::default(),
@@ -113,48 +260,122 @@ impl RenderPipeline {
             },
             multiview: None,
             cache: None,
-        });
-
-        // Create uniform buffer
-        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Render Uniform Buffer"),
-            contents: bytemuck::bytes_of(&RenderUniforms::default()),
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-        });
+        })
+    }
 
-        // Create bind group
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Render Bind Group"),
-            layout: &bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: uniform_buffer.as_entire_binding(),
+    /// Build the debug line pipeline
+    ///
+    /// Lines use the unlit fragment shader and ignore depth, so they stay
+    /// visible through the geometry they describe.
+    fn create_line_pipeline(
+        device: &wgpu::Device,
+        shader: &wgpu::ShaderModule,
+        layout: &wgpu::PipelineLayout,
+        surface_format: wgpu::TextureFormat,
+    ) -> wgpu::RenderPipeline {
+        let constants = HashMap::from([("MATERIAL_ID".to_string(), Material::UNLIT_ID as f64)]);
+
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Debug Line Pipeline"),
+            layout: Some(layout),
+            vertex: wgpu::VertexState {
+                module: shader,
+                entry_point: Some("vs_main"),
+                buffers: &[Self::vertex_buffer_layout()],
+                compilation_options: wgpu::PipelineCompilationOptions {
+                    constants: &constants,
+                    ..Default::default()
                 },
-            ],
-        });
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: shader,
+                entry_point: Some("fs_unlit"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::LineList,
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::Always,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        })
+    }
 
-        // Create indirect draw buffer
-        let indirect_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Indirect Draw Buffer"),
-            contents: bytemuck::bytes_of(&DrawIndirectArgs {
-                vertex_count: 0,
-                instance_count: 1,
-                first_vertex: 0,
-                first_instance: 0,
+    fn create_line_buffer(device: &wgpu::Device, capacity: usize) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Debug Line Buffer"),
+            size: (capacity * std::mem::size_of::<Vertex3D>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Upload the debug lines drawn by [`render_lines`](Self::render_lines)
+    ///
+    /// `vertices` holds two vertices per segment, in camera space. Passing an
+    /// empty slice clears the lines. The buffer grows when needed.
+    pub fn upload_lines(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, vertices: &[Vertex3D]) {
+        if vertices.len() > self.line_capacity {
+            self.line_capacity = vertices.len().next_power_of_two();
+            self.line_buffer = Self::create_line_buffer(device, self.line_capacity);
+        }
+        if !vertices.is_empty() {
+            queue.write_buffer(&self.line_buffer, 0, bytemuck::cast_slice(vertices));
+        }
+        self.line_vertex_count = vertices.len() as u32;
+    }
+
+    /// Draw the uploaded debug lines over what [`render`](Self::render) drew into `view`
+    pub fn render_lines(&self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView) {
+        if self.line_vertex_count == 0 {
+            return;
+        }
+        let depth_view = self.depth_texture.as_ref().expect("Depth texture not created. Call ensure_depth_texture first.");
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Debug Line Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
             }),
-            usage: wgpu::BufferUsages::INDIRECT | wgpu::BufferUsages::COPY_DST,
+            timestamp_writes: None,
+            occlusion_query_set: None,
         });
 
-        Self {
-            pipeline,
-            bind_group_layout,
-            uniform_buffer,
-            bind_group,
-            indirect_buffer,
-            depth_texture: None,
-            depth_size: (0, 0),
-        }
+        render_pass.set_pipeline(&self.line_pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.line_buffer.slice(..));
+        render_pass.draw(0..self.line_vertex_count, 0..1);
+    }
+
+    /// Get the registered material ids, in draw order
+    pub fn material_ids(&self) -> impl Iterator<Item = u16> + '_ {
+        self.pipelines.iter().map(|p| p.material_id)
     }
 
     /// Get the vertex buffer layout for Vertex3D
@@ -187,31 +408,104 @@ impl RenderPipeline {
                     offset: 40,
                     shader_location: 3,
                 },
+                // material_id: u32
+                wgpu::VertexAttribute {
+                    format: wgpu::VertexFormat::Uint32,
+                    offset: 44,
+                    shader_location: 4,
+                },
+                // material_index: u32
+                wgpu::VertexAttribute {
+                    format: wgpu::VertexFormat::Uint32,
+                    offset: 48,
+                    shader_location: 5,
+                },
+            ],
+        }
+    }
+
+    fn create_material_buffer(device: &wgpu::Device, materials: &[GpuMaterial]) -> wgpu::Buffer {
+        device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Render Material Buffer"),
+            contents: bytemuck::cast_slice(materials),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        })
+    }
+
+    fn create_bind_group(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        uniform_buffer: &wgpu::Buffer,
+        material_buffer: &wgpu::Buffer,
+    ) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Render Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: material_buffer.as_entire_binding(),
+                },
             ],
+        })
+    }
+
+    /// Upload the per-entity materials referenced by vertex material indices
+    ///
+    /// The buffer (and bind group) is recreated when it needs to grow.
+    pub fn upload_materials(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, materials: &[GpuMaterial]) {
+        if materials.is_empty() {
+            return;
+        }
+        if materials.len() > self.material_capacity {
+            self.material_buffer = Self::create_material_buffer(device, materials);
+            self.material_capacity = materials.len();
+            self.bind_group = Self::create_bind_group(device, &self.bind_group_layout, &self.uniform_buffer, &self.material_buffer);
+        } else {
+            queue.write_buffer(&self.material_buffer, 0, bytemuck::cast_slice(materials));
         }
     }
 
     /// Update uniforms
+    ///
+    /// Lights given to [`set_lights`](Self::set_lights) replace the ones in `uniforms`.
     pub fn update_uniforms(&self, queue: &wgpu::Queue, uniforms: &RenderUniforms) {
-        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(uniforms));
+        match &self.lights {
+            Some(lights) => {
+                let uniforms = uniforms.with_lights(lights);
+                queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
+            }
+            None => queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(uniforms)),
+        }
+    }
+
+    /// Set the directional lights (at most [`MAX_LIGHTS`](super::MAX_LIGHTS) are used)
+    ///
+    /// The lights are written straight away and kept for later
+    /// [`update_uniforms`](Self::update_uniforms) calls.
+    pub fn set_lights(&mut self, queue: &wgpu::Queue, lights: &[Light]) {
+        let start = std::mem::offset_of!(RenderUniforms, light_directions);
+        let end = std::mem::offset_of!(RenderUniforms, ambient_strength);
+        let packed = RenderUniforms::default().with_lights(lights);
+        queue.write_buffer(&self.uniform_buffer, start as u64, &bytemuck::bytes_of(&packed)[start..end]);
+        self.lights = Some(lights.to_vec());
     }
 
     /// Prepare indirect draw from counter
     ///
-    /// This copies the triangle count from the compute shader's counter buffer
-    /// to the indirect draw buffer, multiplying by 3 for vertex count.
+    /// This copies the vertex count from the compute shader's counter buffer
+    /// to the indirect draw buffer. The count is already clamped to the output
+    /// buffer: the slice shader drops triangles that don't fit and takes them
+    /// back off the count, and the CPU path truncates its output the same way.
     pub fn prepare_indirect_draw(
         &self,
         encoder: &mut wgpu::CommandEncoder,
         counter_buffer: &wgpu::Buffer,
     ) {
-        // We need to convert counter (triangle count) to vertex count (*3)
-        // For now, we'll use a simple copy and handle the *3 in a compute shader or CPU
-        // TODO: Add a small compute shader to multiply by 3, or do it on CPU
-
-        // For simplicity, we copy the counter directly and the draw will use it as vertex count
-        // This requires the counter to already be vertex_count (triangles * 3)
-        // We'll update the slice shader to output vertex count instead of triangle count
         encoder.copy_buffer_to_buffer(
             counter_buffer,
             0,
@@ -246,13 +540,27 @@ impl RenderPipeline {
 
     /// Render the cross-section
     ///
-    /// Uses indirect drawing with the vertex count from the compute shader.
+    /// Uses indirect drawing with the vertex count from the compute shader,
+    /// issuing one draw per registered material pipeline: first every opaque
+    /// one, then every transparent one.
     pub fn render(
         &self,
         encoder: &mut wgpu::CommandEncoder,
         view: &wgpu::TextureView,
         vertex_buffer: &wgpu::Buffer,
         clear_color: wgpu::Color,
+    ) {
+        self.render_timed(encoder, view, vertex_buffer, clear_color, None);
+    }
+
+    /// Render the sliced geometry, writing timestamps around the render pass
+    pub fn render_timed(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        vertex_buffer: &wgpu::Buffer,
+        clear_color: wgpu::Color,
+        timestamp_writes: Option<wgpu::RenderPassTimestampWrites>,
     ) {
         let depth_view = self.depth_texture.as_ref().expect("Depth texture not created. Call ensure_depth_texture first.");
 
@@ -274,16 +582,24 @@ impl RenderPipeline {
                 }),
                 stencil_ops: None,
             }),
-            timestamp_writes: None,
+            timestamp_writes,
             occlusion_query_set: None,
         });
 
-        render_pass.set_pipeline(&self.pipeline);
         render_pass.set_bind_group(0, &self.bind_group, &[]);
         render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
 
-        // Use indirect drawing with the counter from compute shader
-        render_pass.draw_indirect(&self.indirect_buffer, 0);
+        // Use indirect drawing with the counter from compute shader.
+        // Each material pipeline only keeps triangles with its own id, and
+        // only the opaque or the transparent ones.
+        for material in &self.pipelines {
+            render_pass.set_pipeline(&material.pipeline);
+            render_pass.draw_indirect(&self.indirect_buffer, 0);
+        }
+        for material in &self.pipelines {
+            render_pass.set_pipeline(&material.transparent_pipeline);
+            render_pass.draw_indirect(&self.indirect_buffer, 0);
+        }
     }
 }
 
@@ -300,6 +616,24 @@ pub fn perspective_matrix(fov_y: f32, aspect: f32, near: f32, far: f32) -> [[f32
     ]
 }
 
+/// Helper to create an orthographic projection matrix
+///
+/// Maps the box `[left, right] x [bottom, top]` between the `near` and `far`
+/// planes (distances along -Z) to NDC with wgpu's depth range: the near plane
+/// lands at z = 0, the far plane at 1.
+pub fn orthographic_matrix(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> [[f32; 4]; 4] {
+    let rl = 1.0 / (right - left);
+    let tb = 1.0 / (top - bottom);
+    let fn_ = 1.0 / (far - near);
+
+    [
+        [2.0 * rl, 0.0, 0.0, 0.0],
+        [0.0, 2.0 * tb, 0.0, 0.0],
+        [0.0, 0.0, -fn_, 0.0],
+        [-(right + left) * rl, -(top + bottom) * tb, -near * fn_, 1.0],
+    ]
+}
+
 /// Helper to create a look-at view matrix
 pub fn look_at_matrix(eye: [f32; 3], target: [f32; 3], up: [f32; 3]) -> [[f32; 4]; 4] {
     let f = normalize([
@@ -353,6 +687,10 @@ fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::pipeline::{headless_device, SliceMode, SliceParams, SlicePipeline};
+    use crate::renderable::RenderableGeometry;
+    use crate::screenshot::read_texture;
+    use rust4d_core::{Entity, ShapeRef, Tesseract4D, Transform4D, Vec4};
 
     #[test]
     fn test_vertex_buffer_layout_stride() {
@@ -368,8 +706,170 @@ mod tests {
         assert!(proj[1][1] != 0.0);
     }
 
+    fn project(m: [[f32; 4]; 4], p: [f32; 3]) -> [f32; 3] {
+        let clip: [f32; 4] = std::array::from_fn(|row| m[0][row] * p[0] + m[1][row] * p[1] + m[2][row] * p[2] + m[3][row]);
+        [clip[0] / clip[3], clip[1] / clip[3], clip[2] / clip[3]]
+    }
+
+    #[test]
+    fn test_orthographic_depth_range() {
+        // wgpu clips depth to [0, 1]
+        let proj = orthographic_matrix(-4.0, 4.0, -3.0, 3.0, 0.5, 50.0);
+        assert!(project(proj, [0.0, 0.0, -0.5])[2].abs() < 1e-5);
+        assert!((project(proj, [0.0, 0.0, -50.0])[2] - 1.0).abs() < 1e-5);
+        assert!((project(proj, [0.0, 0.0, -25.25])[2] - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_orthographic_xy_independent_of_depth() {
+        let proj = orthographic_matrix(-4.0, 4.0, -3.0, 3.0, 0.1, 100.0);
+        let near = project(proj, [2.0, -1.5, -1.0]);
+        let far = project(proj, [2.0, -1.5, -80.0]);
+        assert!((near[0] - 0.5).abs() < 1e-5 && (near[1] + 0.5).abs() < 1e-5);
+        assert!((far[0] - near[0]).abs() < 1e-5 && (far[1] - near[1]).abs() < 1e-5);
+
+        // Off-center boxes shift the view rather than scaling it
+        let shifted = orthographic_matrix(0.0, 8.0, -3.0, 3.0, 0.1, 100.0);
+        assert!((project(shifted, [4.0, 0.0, -10.0])[0]).abs() < 1e-5);
+    }
+
     #[test]
     fn test_draw_indirect_args_size() {
         assert_eq!(std::mem::size_of::<DrawIndirectArgs>(), 16);
     }
+
+    const SIZE: u32 = 64;
+
+    /// A device that can run the GPU slice pass, or `None` (with a note) if there is none
+    fn slicing_device() -> Option<(wgpu::Device, wgpu::Queue)> {
+        let (device, queue) = headless_device(wgpu::Features::empty())?;
+        if SliceMode::for_limits(&device.limits()) != SliceMode::Gpu {
+            eprintln!("skipping: adapter cannot run the slice compute shader");
+            return None;
+        }
+        Some((device, queue))
+    }
+
+    /// Slice geometry at W = 0 from the origin and render it with an identity view
+    fn render_geometry(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        geometry: &RenderableGeometry,
+        projection_matrix: [[f32; 4]; 4],
+    ) -> image::RgbaImage {
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let mut slicer = SlicePipeline::with_mode(device, 10_000, SliceMode::Gpu);
+        slicer.upload_tetrahedra(device, &geometry.vertices, &geometry.tetrahedra);
+        slicer.update_params(
+            queue,
+            &SliceParams {
+                tetrahedron_count: geometry.tetrahedron_count() as u32,
+                camera_eye: [0.0; 3],
+                camera_position: [0.0; 4],
+                ..SliceParams::default()
+            },
+        );
+
+        let mut renderer = RenderPipeline::new(device, format);
+        renderer.ensure_depth_texture(device, SIZE, SIZE);
+        renderer.upload_materials(device, queue, geometry.materials());
+        renderer.update_uniforms(
+            queue,
+            &RenderUniforms {
+                view_matrix: rust4d_math::mat4::IDENTITY,
+                projection_matrix,
+                ..RenderUniforms::default()
+            },
+        );
+
+        let output = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Test Output"),
+            size: wgpu::Extent3d { width: SIZE, height: SIZE, depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let output_view = output.create_view(&wgpu::TextureViewDescriptor::default());
+
+        slicer.reset_counter(queue);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        slicer.run_slice_pass(&mut encoder);
+        renderer.prepare_indirect_draw(&mut encoder, slicer.counter_buffer());
+        renderer.render(&mut encoder, &output_view, slicer.output_buffer(), wgpu::Color::BLACK);
+        queue.submit(std::iter::once(encoder.finish()));
+        read_texture(device, queue, &output).unwrap()
+    }
+
+    #[test]
+    fn test_overlapping_translucent_tesseracts_blend() {
+        use crate::renderable::sort_back_to_front;
+        use rust4d_core::World;
+
+        let Some((device, queue)) = slicing_device() else { return };
+        // Half-transparent red in front of half-transparent blue, overlapping
+        // in the middle of the view; red is added first so only sorting puts
+        // it last
+        let mut world = World::new();
+        for (position, color) in [
+            (Vec4::new(-0.6, 0.0, -5.0, 0.0), Material::new(1.0, 0.0, 0.0, 0.5)),
+            (Vec4::new(0.6, 0.0, -7.0, 0.0), Material::new(0.0, 0.0, 1.0, 0.5)),
+        ] {
+            world.add_entity(Entity::with_transform(
+                ShapeRef::shared(Tesseract4D::new(2.0)),
+                Transform4D::from_position(position),
+                color.with_material_id(Material::UNLIT_ID),
+            ));
+        }
+        let mut entities: Vec<_> = world.iter_with_keys().collect();
+        sort_back_to_front(&world, &mut entities, Vec4::ZERO);
+        let mut geometry = RenderableGeometry::new();
+        geometry.cancel_internal_faces(true);
+        for (_, entity) in entities {
+            geometry.add_entity(entity);
+        }
+
+        let frame = render_geometry(
+            &device,
+            &queue,
+            &geometry,
+            perspective_matrix(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0),
+        );
+
+        // Where they overlap both show through, the nearer red on top
+        let [r, g, b, _] = frame.get_pixel(SIZE / 2, SIZE / 2).0;
+        assert!(b > 0 && r > b && g == 0, "overlap pixel {:?}", [r, g, b]);
+        assert!(r < 255, "overlap pixel {:?} is not blended", [r, g, b]);
+
+        // Off to the left only red is drawn, and to the right only blue
+        let [r, _, b, _] = frame.get_pixel(SIZE / 2 - 6, SIZE / 2).0;
+        assert!(r > 0 && b == 0);
+        let [r, _, b, _] = frame.get_pixel(SIZE / 2 + 6, SIZE / 2).0;
+        assert!(r == 0 && b > 0);
+    }
+
+    #[test]
+    fn test_orthographic_draws_objects_just_past_near() {
+        let Some((device, queue)) = slicing_device() else { return };
+
+        // The tesseract's front face is 0.4 past the near plane, far nearer
+        // than halfway to the far plane
+        let mut geometry = RenderableGeometry::new();
+        geometry.add_entity(&Entity::with_transform(
+            ShapeRef::shared(Tesseract4D::new(2.0)),
+            Transform4D::from_position(Vec4::new(0.0, 0.0, -1.5, 0.0)),
+            Material::new(0.0, 1.0, 0.0, 1.0).with_material_id(Material::UNLIT_ID),
+        ));
+        let frame = render_geometry(
+            &device,
+            &queue,
+            &geometry,
+            orthographic_matrix(-4.0, 4.0, -4.0, 4.0, 0.1, 100.0),
+        );
+
+        let [r, g, b, _] = frame.get_pixel(SIZE / 2, SIZE / 2).0;
+        assert!(g > 200 && r == 0 && b == 0, "center pixel {:?}", [r, g, b]);
+    }
 }
@@ -0,0 +1,279 @@
+//! Capping open cross-sections
+//!
+//! Slicing a closed [`ConvexShape4D`](rust4d_math::ConvexShape4D) gives a
+//! closed surface, but geometry that is missing boundary tetrahedra (or whose
+//! slice lost triangles to degenerate cuts) leaves holes you can see into.
+//! Capping finds the boundary edges of the slice, the edges only one triangle
+//! of an entity uses, chains them into loops, and fans each loop from its
+//! centroid. Convex shapes have convex cross-sections, so their loops are
+//! closed exactly; a slice without boundary edges is left untouched.
+//!
+//! [`cap_slice`] runs on the CPU slice output. Positions are matched on the
+//! same grid as [`smooth_normals`](super::smooth_normals), and triangles of
+//! different entities are never joined.
+
+use std::collections::HashMap;
+
+use super::smooth_normals::NORMAL_POSITION_QUANTUM;
+use super::types::{Vertex3D, TRIANGLE_VERTEX_COUNT};
+
+/// Vertices sharing a key are the same point of the same entity
+type CapKey = ([i32; 3], u32);
+
+/// An edge between two keys, in key order, with one vertex at each end
+struct Edge {
+    ends: [usize; 2],
+    uses: u32,
+}
+
+/// Close the holes in a slice by triangulating its boundary loops
+///
+/// `vertices` holds 3 vertices per triangle, as produced by slicing. Cap
+/// triangles are appended with flat normals facing `camera_eye`, like the
+/// slice's own triangles. Boundary chains that do not close into a loop are
+/// left open.
+pub fn cap_slice(vertices: &mut Vec<Vertex3D>, camera_eye: [f32; 3]) {
+    let keys: Vec<CapKey> = vertices.iter().map(cap_key).collect();
+
+    // Count the triangles on each edge, keeping edges in first-seen order
+    let mut edge_index: HashMap<(CapKey, CapKey), usize> = HashMap::new();
+    let mut edges: Vec<Edge> = Vec::new();
+    let triangle_vertices = vertices.len() - vertices.len() % TRIANGLE_VERTEX_COUNT;
+    for base in (0..triangle_vertices).step_by(TRIANGLE_VERTEX_COUNT) {
+        for corner in 0..TRIANGLE_VERTEX_COUNT {
+            let mut ends = [base + corner, base + (corner + 1) % TRIANGLE_VERTEX_COUNT];
+            if keys[ends[0]] == keys[ends[1]] {
+                continue;
+            }
+            if keys[ends[0]] > keys[ends[1]] {
+                ends.swap(0, 1);
+            }
+            let index = *edge_index.entry((keys[ends[0]], keys[ends[1]])).or_insert_with(|| {
+                edges.push(Edge { ends, uses: 0 });
+                edges.len() - 1
+            });
+            edges[index].uses += 1;
+        }
+    }
+
+    // Boundary edges at each point
+    let boundary: Vec<&Edge> = edges.iter().filter(|e| e.uses == 1).collect();
+    let mut at_point: HashMap<CapKey, Vec<usize>> = HashMap::new();
+    for (i, edge) in boundary.iter().enumerate() {
+        for end in edge.ends {
+            at_point.entry(keys[end]).or_default().push(i);
+        }
+    }
+
+    let mut used = vec![false; boundary.len()];
+    let mut caps = Vec::new();
+    for start in 0..boundary.len() {
+        if used[start] {
+            continue;
+        }
+        used[start] = true;
+        let [first, mut current] = boundary[start].ends;
+        let mut boundary_loop = vec![vertices[first]];
+        let closed = loop {
+            if keys[current] == keys[first] {
+                break true;
+            }
+            boundary_loop.push(vertices[current]);
+            let next = at_point[&keys[current]].iter().copied().find(|&i| !used[i]);
+            let Some(next) = next else {
+                break false;
+            };
+            used[next] = true;
+            let [a, b] = boundary[next].ends;
+            current = if keys[a] == keys[current] { b } else { a };
+        };
+        if closed {
+            fan_loop(&boundary_loop, camera_eye, &mut caps);
+        }
+    }
+    vertices.extend(caps);
+}
+
+/// Triangulate a loop of boundary vertices
+///
+/// Triangles are fanned from a vertex at the loop's centroid that averages
+/// the loop's color and W depth.
+fn fan_loop(boundary_loop: &[Vertex3D], camera_eye: [f32; 3], output: &mut Vec<Vertex3D>) {
+    if boundary_loop.len() < TRIANGLE_VERTEX_COUNT {
+        return;
+    }
+    if boundary_loop.len() == TRIANGLE_VERTEX_COUNT {
+        emit_cap_triangle([boundary_loop[0], boundary_loop[1], boundary_loop[2]], camera_eye, output);
+        return;
+    }
+
+    let scale = 1.0 / boundary_loop.len() as f32;
+    let mut center = Vertex3D { position: [0.0; 3], color: [0.0; 4], w_depth: 0.0, ..boundary_loop[0] };
+    for v in boundary_loop {
+        for (c, p) in center.position.iter_mut().zip(v.position) {
+            *c += p * scale;
+        }
+        for (c, p) in center.color.iter_mut().zip(v.color) {
+            *c += p * scale;
+        }
+        center.w_depth += v.w_depth * scale;
+    }
+
+    for (i, &v) in boundary_loop.iter().enumerate() {
+        let next = boundary_loop[(i + 1) % boundary_loop.len()];
+        emit_cap_triangle([center, v, next], camera_eye, output);
+    }
+}
+
+/// Orient a cap triangle towards the camera, set its flat normal, and emit it
+fn emit_cap_triangle(tri: [Vertex3D; 3], camera_eye: [f32; 3], output: &mut Vec<Vertex3D>) {
+    let [v0, mut v1, mut v2] = tri;
+    let mut normal = face_normal(v0.position, v1.position, v2.position);
+    let center = [0, 1, 2].map(|i| (v0.position[i] + v1.position[i] + v2.position[i]) / 3.0);
+    if dot(normal, sub(camera_eye, center)) < 0.0 {
+        std::mem::swap(&mut v1, &mut v2);
+        normal = normal.map(|c| -c);
+    }
+    output.extend([v0, v1, v2].map(|v| Vertex3D { normal, ..v }));
+}
+
+fn cap_key(vertex: &Vertex3D) -> CapKey {
+    (
+        vertex.position.map(|p| (p / NORMAL_POSITION_QUANTUM).round() as i32),
+        vertex.material_index,
+    )
+}
+
+fn face_normal(p0: [f32; 3], p1: [f32; 3], p2: [f32; 3]) -> [f32; 3] {
+    let e1 = sub(p1, p0);
+    let e2 = sub(p2, p0);
+    let n = [
+        e1[1] * e2[2] - e1[2] * e2[1],
+        e1[2] * e2[0] - e1[0] * e2[2],
+        e1[0] * e2[1] - e1[1] * e2[0],
+    ];
+    let len = dot(n, n).sqrt();
+    if len < 1e-12 {
+        return [0.0, 1.0, 0.0]; // Degenerate triangle fallback
+    }
+    n.map(|c| c / len)
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::{slice_on_cpu, GpuTetrahedron, SliceParams, Vertex4D};
+    use rust4d_math::{ConvexShape4D, SixteenCell4D};
+
+    const EYE: [f32; 3] = [0.3, 4.0, 5.0];
+
+    /// Number of edges used by exactly one triangle of the same entity
+    fn boundary_edge_count(vertices: &[Vertex3D]) -> usize {
+        let mut uses: HashMap<(CapKey, CapKey), u32> = HashMap::new();
+        for tri in vertices.chunks_exact(TRIANGLE_VERTEX_COUNT) {
+            for corner in 0..TRIANGLE_VERTEX_COUNT {
+                let a = cap_key(&tri[corner]);
+                let b = cap_key(&tri[(corner + 1) % TRIANGLE_VERTEX_COUNT]);
+                if a != b {
+                    *uses.entry((a.min(b), a.max(b))).or_default() += 1;
+                }
+            }
+        }
+        uses.values().filter(|&&n| n == 1).count()
+    }
+
+    /// A 16-cell sliced between its center and +W vertex: a closed octahedron
+    fn sixteen_cell_slice(material_index: u32) -> Vec<Vertex3D> {
+        let shape = SixteenCell4D::new(1.0);
+        let vertices: Vec<Vertex4D> = shape
+            .vertices()
+            .iter()
+            .map(|v| Vertex4D::from_position([v.x, v.y, v.z, v.w]).with_material_index(material_index))
+            .collect();
+        let tetrahedra: Vec<GpuTetrahedron> = shape
+            .tetrahedra()
+            .iter()
+            .map(|t| GpuTetrahedron::from_indices(t.indices.map(|i| i as u32)))
+            .collect();
+        let params = SliceParams {
+            slice_w: 0.5,
+            tetrahedron_count: tetrahedra.len() as u32,
+            camera_eye: EYE,
+            camera_position: [0.0; 4],
+            ..SliceParams::default()
+        };
+        slice_on_cpu(&vertices, &tetrahedra, &params)
+    }
+
+    /// The six faces of a unit cube as triangle pairs, outward-wound
+    fn cube(material_index: u32) -> Vec<Vertex3D> {
+        let corner = |i: usize| [(i & 1) as f32, ((i >> 1) & 1) as f32, ((i >> 2) & 1) as f32];
+        let faces = [[0, 2, 3, 1], [4, 5, 7, 6], [0, 1, 5, 4], [2, 6, 7, 3], [0, 4, 6, 2], [1, 3, 7, 5]];
+        faces
+            .iter()
+            .flat_map(|f| [f[0], f[1], f[2], f[0], f[2], f[3]])
+            .map(|i| Vertex3D { position: corner(i), material_index, ..Vertex3D::default() })
+            .collect()
+    }
+
+    #[test]
+    fn test_capping_closes_open_slice() {
+        // Drop the cube's top face, leaving one square hole
+        let mut open: Vec<Vertex3D> = cube(0)
+            .chunks_exact(3)
+            .filter(|tri| !tri.iter().all(|v| v.position[1] == 1.0))
+            .flatten()
+            .copied()
+            .collect();
+        assert_eq!(boundary_edge_count(&open), 4);
+
+        cap_slice(&mut open, EYE);
+        assert_eq!(open.len() % TRIANGLE_VERTEX_COUNT, 0);
+        assert_eq!(boundary_edge_count(&open), 0);
+
+        // The cap lies in the hole and faces the camera
+        let caps = &open[10 * TRIANGLE_VERTEX_COUNT..];
+        assert_eq!(caps.len(), 4 * TRIANGLE_VERTEX_COUNT);
+        for v in caps {
+            assert_eq!(v.position[1], 1.0);
+            assert_eq!(v.normal, [0.0, 1.0, 0.0]);
+        }
+    }
+
+    #[test]
+    fn test_capping_closes_each_entity_separately() {
+        // Two open slices at the same place, from different entities
+        let mut open = Vec::new();
+        for material_index in [1, 2] {
+            let slice = sixteen_cell_slice(material_index);
+            open.extend(slice[..slice.len() - 2 * TRIANGLE_VERTEX_COUNT].iter().copied());
+        }
+        assert!(boundary_edge_count(&open) > 0);
+
+        cap_slice(&mut open, EYE);
+        assert_eq!(boundary_edge_count(&open), 0);
+    }
+
+    #[test]
+    fn test_capping_leaves_closed_slice_unchanged() {
+        let closed = sixteen_cell_slice(0);
+        assert_eq!(closed.len(), 8 * TRIANGLE_VERTEX_COUNT);
+        assert_eq!(boundary_edge_count(&closed), 0);
+
+        let mut capped = closed.clone();
+        cap_slice(&mut capped, EYE);
+        assert_eq!(capped.len(), closed.len());
+
+        let mut cube = cube(0);
+        cap_slice(&mut cube, EYE);
+        assert_eq!(cube.len(), 12 * TRIANGLE_VERTEX_COUNT);
+    }
+}
@@ -2,27 +2,213 @@
 //!
 //! This pipeline takes 4D geometry (tetrahedra) and produces 3D triangles
 //! by intersecting with a hyperplane at a given W coordinate.
+//!
+//! When the device cannot run the compute shader, the pipeline falls back to
+//! slicing on the CPU ([`SliceMode::Cpu`]) and uploads the triangles each frame.
+//! Both paths fill the same output and counter buffers, so rendering is unchanged.
+//!
+//! With [`set_smooth_normals`](SlicePipeline::set_smooth_normals) the flat
+//! triangle normals are replaced by smoothed per-vertex normals afterwards
+//! (see [`smooth_normals`](super::smooth_normals)).
+//!
+//! With [`set_slice_capping`](SlicePipeline::set_slice_capping) holes in the
+//! cross-section are closed (see [`slice_cap`](super::slice_cap)). Capping
+//! needs the whole slice at once, so while it is enabled the pipeline slices
+//! on the CPU even when the compute shader is available.
+//!
+//! With [`set_culling_enabled`](SlicePipeline::set_culling_enabled) only the
+//! tetrahedra that can reach the slice and the view frustum are sliced (see
+//! [`culling`](super::culling)); the output is the same either way.
+//!
+//! Triangles from one compute dispatch land in the output in no particular
+//! order, so each transparent entity is sliced by a dispatch of its own (see
+//! [`dispatch_ranges`]). Transparent entities then come out in the order they
+//! were uploaded, as the CPU path keeps them, and blend back to front when
+//! uploaded that way.
+//!
+//! The GPU path never waits for the counter: [`SlicePipeline::triangle_count`]
+//! reports the last count that finished reading back, usually a frame or two old.
 
+use std::num::NonZeroU64;
+use std::sync::{Arc, Mutex};
+
+use bytemuck::{Pod, Zeroable};
 use wgpu::util::DeviceExt;
 
+use super::cpu_slice::slice_on_cpu;
+use super::culling::{cull_tetrahedra, TetraBounds, ViewFrustum};
+use super::slice_cap::cap_slice;
+use super::smooth_normals::smooth_normals;
 use super::types::{
     SliceParams, Vertex3D, Vertex4D, GpuTetrahedron, AtomicCounter,
-    TRIANGLE_VERTEX_COUNT,
+    TRIANGLE_VERTEX_COUNT, TRANSPARENT_MATERIAL_BIT,
 };
 
-/// Compute pipeline for slicing 4D geometry
-pub struct SlicePipeline {
+/// Minimum storage buffers per compute stage needed by the slice shader
+const REQUIRED_STORAGE_BUFFERS: u32 = 4;
+/// Workgroup size used by the slice shader
+const WORKGROUP_SIZE: u32 = 64;
+/// Workgroups dispatched by each normal smoothing pass (the shader strides over the output)
+const SMOOTH_WORKGROUPS: u32 = 256;
+/// Size of one normal smoothing hash table slot (key + 3 fixed-point sums)
+const NORMAL_SLOT_SIZE: u64 = 16;
+/// Size of one entity's center sum for normal smoothing (3 fixed-point sums + count)
+const ENTITY_CENTER_SIZE: u64 = 16;
+
+/// A run of tetrahedra sliced by one dispatch (matches `DispatchRange` in the shader)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+struct DispatchRange {
+    /// Index of the first tetrahedron in the run
+    first: u32,
+    /// Number of tetrahedra in the run
+    count: u32,
+    /// Padding to the 16-byte uniform struct size
+    _padding: [u32; 2],
+}
+
+/// Split tetrahedra into the runs the slice pass dispatches one after another
+///
+/// A dispatch finishes before the next one starts, so runs come out in order.
+/// Consecutive opaque tetrahedra share a run, while each transparent entity
+/// (one material buffer entry) gets its own.
+fn dispatch_ranges(vertices: &[Vertex4D], tetrahedra: &[GpuTetrahedron]) -> Vec<DispatchRange> {
+    let run_key = |tet: &GpuTetrahedron| {
+        let v = &vertices[tet.v0 as usize];
+        (v.material_id & TRANSPARENT_MATERIAL_BIT != 0).then_some(v.material_index)
+    };
+    let mut ranges: Vec<DispatchRange> = Vec::new();
+    let mut previous = None;
+    for (i, tet) in tetrahedra.iter().enumerate() {
+        let key = run_key(tet);
+        match ranges.last_mut() {
+            Some(range) if key == previous => range.count += 1,
+            _ => ranges.push(DispatchRange { first: i as u32, count: 1, _padding: [0; 2] }),
+        }
+        previous = key;
+    }
+    ranges
+}
+
+/// Lay ranges out one per `stride` bytes, for dynamic offsets (at least one slot)
+fn strided_ranges(ranges: &[DispatchRange], stride: u64) -> Vec<u8> {
+    let stride = stride as usize;
+    let mut bytes = vec![0; ranges.len().max(1) * stride];
+    for (slot, range) in bytes.chunks_exact_mut(stride).zip(ranges) {
+        slot[..std::mem::size_of::<DispatchRange>()].copy_from_slice(bytemuck::bytes_of(range));
+    }
+    bytes
+}
+
+/// How cross-sections are computed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SliceMode {
+    /// Slice in the compute shader
+    Gpu,
+    /// Slice on the CPU and upload the triangles (fallback)
+    Cpu,
+}
+
+impl SliceMode {
+    /// Pick the best mode the device limits allow
+    ///
+    /// Downlevel devices (e.g. WebGL2) report zero compute/storage limits,
+    /// which selects [`SliceMode::Cpu`].
+    pub fn for_limits(limits: &wgpu::Limits) -> Self {
+        let gpu_supported = limits.max_storage_buffers_per_shader_stage >= REQUIRED_STORAGE_BUFFERS
+            && limits.max_compute_workgroup_size_x >= WORKGROUP_SIZE
+            && limits.max_compute_invocations_per_workgroup >= WORKGROUP_SIZE
+            && limits.max_compute_workgroups_per_dimension > 0;
+        if gpu_supported {
+            SliceMode::Gpu
+        } else {
+            SliceMode::Cpu
+        }
+    }
+}
+
+/// Compute shader state (only created in [`SliceMode::Gpu`])
+struct GpuSlicer {
     /// The compute pipeline for tetrahedra slicing
     pipeline: wgpu::ComputePipeline,
     /// Bind group layout for tetrahedra pipeline
     bind_group_layout: wgpu::BindGroupLayout,
+    /// Mappable copy of the counter, for reading the output size back
+    counter_readback: wgpu::Buffer,
+    /// Distance between dispatch ranges in the range buffer (the uniform offset alignment)
+    range_stride: u64,
+}
+
+/// Normal smoothing compute state (only created in [`SliceMode::Gpu`] when enabled)
+struct NormalSmoother {
+    /// Pass claiming a hash table slot per distinct vertex
+    insert_keys: wgpu::ComputePipeline,
+    /// Pass adding weighted face normals to the hash table
+    accumulate: wgpu::ComputePipeline,
+    /// Pass writing the averaged normals back to the output
+    resolve: wgpu::ComputePipeline,
+    /// Hash table of normal sums, cleared every frame
+    table: wgpu::Buffer,
+    /// Per-entity sums of output positions, indexed by material index and cleared every frame
+    centers: wgpu::Buffer,
+    /// Bind group over the output, counter, table and centers buffers
+    bind_group: wgpu::BindGroup,
+}
+
+/// Where the counter readback is in its copy/map cycle
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum ReadbackState {
+    /// Nothing pending; the next frame may copy the counter
+    Idle,
+    /// A copy was encoded but mapping has not been requested yet
+    Copied,
+    /// Waiting for the map callback
+    Mapping,
+}
+
+/// Compute pipeline for slicing 4D geometry
+pub struct SlicePipeline {
+    /// Active slicing path
+    mode: SliceMode,
+    /// Compute shader state (None in CPU mode)
+    gpu: Option<GpuSlicer>,
+    /// Normal smoothing passes (None in CPU mode or when disabled)
+    smoother: Option<NormalSmoother>,
+    /// Whether output normals are smoothed
+    smooth_normals: bool,
+    /// Whether tetrahedra are culled before slicing
+    culling_enabled: bool,
+    /// Whether open cross-sections are capped (forces CPU slicing)
+    slice_capping: bool,
+    /// View volume tested when culling (None = only test against the slice)
+    view_frustum: Option<ViewFrustum>,
+    /// CPU copy of the uploaded vertices, for CPU slicing
+    cpu_vertices: Vec<Vertex4D>,
+    /// CPU copy of the uploaded tetrahedra, for slicing or culling
+    cpu_tetrahedra: Vec<GpuTetrahedron>,
+    /// World-space bounds of each uploaded tetrahedron
+    tetra_bounds: Vec<TetraBounds>,
+    /// Output capacity in triangles
+    max_triangles: usize,
     /// Vertex buffer (4D vertices)
     vertex_buffer: Option<wgpu::Buffer>,
     /// Tetrahedra buffer (indices into vertex buffer)
     tetra_buffer: Option<wgpu::Buffer>,
     tetra_count: u32,
+    /// Tetrahedra the next slice pass processes (fewer than `tetra_count` when culled)
+    sliced_tetra_count: u32,
+    /// Whether the tetrahedra buffer holds a culled subset rather than the upload
+    tetra_buffer_culled: bool,
+    /// Parameters the buffers were last updated for (None once anything else changes)
+    uploaded_params: Option<SliceParams>,
     /// Bind group for pipeline
     bind_group: Option<wgpu::BindGroup>,
+    /// Runs of the uploaded tetrahedra, in dispatch order
+    upload_ranges: Vec<DispatchRange>,
+    /// Runs the next slice pass dispatches (of the culled subset when culling)
+    dispatch_ranges: Vec<DispatchRange>,
+    /// One [`DispatchRange`] per `range_stride`, selected by dynamic offset
+    range_buffer: Option<wgpu::Buffer>,
 
     /// Output buffer for triangles
     output_buffer: wgpu::Buffer,
@@ -30,32 +216,132 @@ pub struct SlicePipeline {
     counter_buffer: wgpu::Buffer,
     /// Slice parameters uniform buffer
     params_buffer: wgpu::Buffer,
+
+    /// Progress of the GPU counter readback
+    readback_state: ReadbackState,
+    /// Map result set by the callback (None while pending)
+    readback_result: Arc<Mutex<Option<bool>>>,
+    /// Output vertex count of the last slice that was read back
+    output_vertices: u32,
 }
 
 impl SlicePipeline {
     /// Create a new slice pipeline with the specified maximum triangle capacity
     ///
+    /// The slicing path is chosen from the device limits (see [`SliceMode::for_limits`]).
+    ///
     /// # Arguments
     /// * `device` - The wgpu device
     /// * `max_triangles` - Maximum number of triangles to allocate buffer space for.
-    ///   Each triangle requires 3 vertices x 48 bytes = 144 bytes.
+    ///   Each triangle requires 3 vertices x 52 bytes = 156 bytes.
     ///   Will be clamped to the GPU's max_storage_buffer_binding_size limit.
     pub fn new(device: &wgpu::Device, max_triangles: usize) -> Self {
-        // Calculate bytes per triangle and clamp to GPU limits
+        Self::with_mode(device, max_triangles, SliceMode::for_limits(&device.limits()))
+    }
+
+    /// Create a new slice pipeline using a specific slicing path
+    ///
+    /// Passing [`SliceMode::Cpu`] forces the fallback even on capable devices.
+    pub fn with_mode(device: &wgpu::Device, max_triangles: usize, mode: SliceMode) -> Self {
+        let max_triangles = Self::output_capacity(max_triangles, &device.limits(), mode);
+
+        match mode {
+            SliceMode::Gpu => log::info!("Slicing 4D geometry with the compute shader"),
+            SliceMode::Cpu => log::warn!("Compute slicing unavailable; slicing 4D geometry on the CPU"),
+        }
+
+        let gpu = (mode == SliceMode::Gpu).then(|| Self::create_gpu_slicer(device));
+
+        // Output and counter buffers are only bound as storage by the compute shader.
+        // Both take CPU writes, since capping slices on the CPU in either mode.
+        let storage = match mode {
+            SliceMode::Gpu => wgpu::BufferUsages::STORAGE,
+            SliceMode::Cpu => wgpu::BufferUsages::empty(),
+        };
+
+        // Create output buffer sized by max_triangles parameter
+        let output_size = (max_triangles * TRIANGLE_VERTEX_COUNT * std::mem::size_of::<Vertex3D>()) as u64;
+        let output_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Slice Output Buffer"),
+            size: output_size,
+            usage: storage | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        // Create counter buffer
+        let counter_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Slice Counter Buffer"),
+            size: std::mem::size_of::<AtomicCounter>() as u64,
+            usage: storage | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::INDIRECT,
+            mapped_at_creation: false,
+        });
+
+        // Create params buffer
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Slice Params Buffer"),
+            size: std::mem::size_of::<SliceParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            mode,
+            gpu,
+            smoother: None,
+            smooth_normals: false,
+            culling_enabled: false,
+            slice_capping: false,
+            view_frustum: None,
+            cpu_vertices: Vec::new(),
+            cpu_tetrahedra: Vec::new(),
+            tetra_bounds: Vec::new(),
+            max_triangles,
+            vertex_buffer: None,
+            tetra_buffer: None,
+            tetra_count: 0,
+            sliced_tetra_count: 0,
+            tetra_buffer_culled: false,
+            uploaded_params: None,
+            bind_group: None,
+            upload_ranges: Vec::new(),
+            dispatch_ranges: Vec::new(),
+            range_buffer: None,
+            output_buffer,
+            counter_buffer,
+            params_buffer,
+            readback_state: ReadbackState::Idle,
+            readback_result: Arc::new(Mutex::new(None)),
+            output_vertices: 0,
+        }
+    }
+
+    /// Number of output triangles that fit in a buffer the device allows
+    ///
+    /// The compute path binds the output as a storage buffer, so it is bound by
+    /// `max_storage_buffer_binding_size` as well as `max_buffer_size`; the CPU
+    /// path only by `max_buffer_size`. Logs a warning when `requested` is clamped.
+    pub fn output_capacity(requested: usize, limits: &wgpu::Limits, mode: SliceMode) -> usize {
         let bytes_per_triangle = TRIANGLE_VERTEX_COUNT * std::mem::size_of::<Vertex3D>();
-        let max_buffer_size = device.limits().max_storage_buffer_binding_size as usize;
-        let max_triangles_for_gpu = max_buffer_size / bytes_per_triangle;
+        let max_buffer_size = match mode {
+            SliceMode::Gpu => limits.max_storage_buffer_binding_size as u64,
+            SliceMode::Cpu => u64::MAX,
+        }
+        .min(limits.max_buffer_size);
+        let max_triangles_for_gpu = (max_buffer_size / bytes_per_triangle as u64).min(usize::MAX as u64) as usize;
 
-        let max_triangles = if max_triangles > max_triangles_for_gpu {
+        if requested > max_triangles_for_gpu {
             log::warn!(
-                "Requested {} triangles exceeds GPU limit of {} (max_storage_buffer_binding_size={}). Clamping.",
-                max_triangles, max_triangles_for_gpu, max_buffer_size
+                "Requested {} triangles exceeds GPU limit of {} (max buffer size={}). Clamping.",
+                requested, max_triangles_for_gpu, max_buffer_size
             );
             max_triangles_for_gpu
         } else {
-            max_triangles
-        };
+            requested
+        }
+    }
 
+    /// Build the compute pipeline and its bind group layout
+    fn create_gpu_slicer(device: &wgpu::Device) -> GpuSlicer {
         // Bind group layout for tetrahedra slicing
         let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("Slice Bind Group Layout"),
@@ -115,6 +401,17 @@ impl SlicePipeline {
                     },
                     count: None,
                 },
+                // Tetrahedra run of the current dispatch
+                wgpu::BindGroupLayoutEntry {
+                    binding: 5,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: NonZeroU64::new(std::mem::size_of::<DispatchRange>() as u64),
+                    },
+                    count: None,
+                },
             ],
         });
 
@@ -142,47 +439,229 @@ impl SlicePipeline {
             cache: None,
         });
 
-        // Create output buffer sized by max_triangles parameter
-        let output_size = (max_triangles * TRIANGLE_VERTEX_COUNT * std::mem::size_of::<Vertex3D>()) as u64;
-        let output_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Slice Output Buffer"),
-            size: output_size,
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_SRC,
+        let counter_readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Slice Counter Readback Buffer"),
+            size: std::mem::size_of::<AtomicCounter>() as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
             mapped_at_creation: false,
         });
 
-        // Create counter buffer
-        let counter_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Slice Counter Buffer"),
-            size: std::mem::size_of::<AtomicCounter>() as u64,
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::INDIRECT,
-            mapped_at_creation: false,
+        let range_stride = (device.limits().min_uniform_buffer_offset_alignment as u64)
+            .max(std::mem::size_of::<DispatchRange>() as u64);
+
+        GpuSlicer {
+            pipeline,
+            bind_group_layout,
+            counter_readback,
+            range_stride,
+        }
+    }
+
+    /// Build the normal smoothing passes over the output and counter buffers
+    fn create_normal_smoother(&self, device: &wgpu::Device) -> NormalSmoother {
+        let storage = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Smooth Normals Bind Group Layout"),
+            entries: &[
+                // Output vertices (normals rewritten in place)
+                storage(0, false),
+                // Output vertex count
+                storage(1, true),
+                // Hash table of normal sums
+                storage(2, false),
+                // Entity center sums
+                storage(3, false),
+            ],
         });
 
-        // Create params buffer
-        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Slice Params Buffer"),
-            size: std::mem::size_of::<SliceParams>() as u64,
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Smooth Normals Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let shader_source = include_str!("../shaders/smooth_normals.wgsl");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Smooth Normals Compute Shader"),
+            source: wgpu::ShaderSource::Wgsl(shader_source.into()),
+        });
+
+        let create_pipeline = |label, entry_point| {
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                module: &shader,
+                entry_point: Some(entry_point),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                cache: None,
+            })
+        };
+        let insert_keys = create_pipeline("Smooth Normals Insert Keys Pipeline", "insert_keys");
+        let accumulate = create_pipeline("Smooth Normals Accumulate Pipeline", "accumulate");
+        let resolve = create_pipeline("Smooth Normals Resolve Pipeline", "resolve");
+
+        // One slot per output triangle leaves room for every distinct vertex position
+        let limits = device.limits();
+        let max_slots = (limits.max_storage_buffer_binding_size as u64).min(limits.max_buffer_size) / NORMAL_SLOT_SIZE;
+        let slots = (self.max_triangles.max(1).next_power_of_two() as u64).min(max_slots);
+        let table = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Smooth Normals Table Buffer"),
+            size: slots * NORMAL_SLOT_SIZE,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let centers = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Smooth Normals Entity Centers Buffer"),
+            size: self.entity_capacity() * ENTITY_CENTER_SIZE,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
             mapped_at_creation: false,
         });
 
-        Self {
-            pipeline,
-            bind_group_layout,
-            vertex_buffer: None,
-            tetra_buffer: None,
-            tetra_count: 0,
-            bind_group: None,
-            output_buffer,
-            counter_buffer,
-            params_buffer,
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Smooth Normals Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: self.output_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: self.counter_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: table.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: centers.as_entire_binding(),
+                },
+            ],
+        });
+
+        NormalSmoother {
+            insert_keys,
+            accumulate,
+            resolve,
+            table,
+            centers,
+            bind_group,
+        }
+    }
+
+    /// Entities the uploaded geometry can produce output for (at least one)
+    fn entity_capacity(&self) -> u64 {
+        self.cpu_vertices.iter().map(|v| v.material_index as u64 + 1).max().unwrap_or(1)
+    }
+
+    /// Enable or disable smoothed output normals
+    ///
+    /// Disabled, every triangle keeps the flat normal of its face. The GPU
+    /// smoothing passes are created the first time this is enabled.
+    pub fn set_smooth_normals(&mut self, device: &wgpu::Device, enabled: bool) {
+        self.smooth_normals = enabled;
+        self.uploaded_params = None;
+        if enabled && self.mode == SliceMode::Gpu && self.smoother.is_none() {
+            self.smoother = Some(self.create_normal_smoother(device));
+        }
+    }
+
+    /// Check if output normals are smoothed
+    pub fn smooth_normals(&self) -> bool {
+        self.smooth_normals
+    }
+
+    /// Enable or disable culling tetrahedra before slicing
+    ///
+    /// Enabled, [`update_params`](Self::update_params) drops the tetrahedra
+    /// whose bounds miss the slice (or slab) and, once
+    /// [`set_view_frustum`](Self::set_view_frustum) is called, the view
+    /// frustum. The visible output is unchanged; only the work shrinks.
+    /// While capping, the frustum is not tested, since a partly culled
+    /// cross-section would have its cut edges capped.
+    pub fn set_culling_enabled(&mut self, enabled: bool) {
+        self.culling_enabled = enabled;
+        self.uploaded_params = None;
+    }
+
+    /// Check if tetrahedra are culled before slicing
+    pub fn culling_enabled(&self) -> bool {
+        self.culling_enabled
+    }
+
+    /// Enable or disable capping open cross-sections
+    ///
+    /// Enabled, the boundary loops of each entity's slice are triangulated so
+    /// the cross-section is closed (see [`cap_slice`]). Slices that are
+    /// already closed are unchanged. Capping runs on the CPU, so the compute
+    /// shader sits idle while it is enabled.
+    pub fn set_slice_capping(&mut self, enabled: bool) {
+        self.slice_capping = enabled;
+        self.uploaded_params = None;
+    }
+
+    /// Check if open cross-sections are capped
+    pub fn slice_capping(&self) -> bool {
+        self.slice_capping
+    }
+
+    /// Check if the next slice runs on the CPU
+    fn slices_on_cpu(&self) -> bool {
+        self.mode == SliceMode::Cpu || self.slice_capping
+    }
+
+    /// Set the view volume tested when culling
+    ///
+    /// The frustum is in the camera space the slice outputs, so it comes from
+    /// the render pass's view-projection matrix (see [`ViewFrustum::from_matrix`]).
+    pub fn set_view_frustum(&mut self, frustum: Option<ViewFrustum>) {
+        if frustum != self.view_frustum {
+            self.view_frustum = frustum;
+            self.uploaded_params = None;
         }
     }
 
     /// Upload tetrahedra and vertices to the GPU
+    ///
+    /// In CPU mode the geometry is kept on the CPU instead. Everything is
+    /// also kept on the CPU for culling and capping.
     pub fn upload_tetrahedra(&mut self, device: &wgpu::Device, vertices: &[Vertex4D], tetrahedra: &[GpuTetrahedron]) {
         self.tetra_count = tetrahedra.len() as u32;
+        self.sliced_tetra_count = self.tetra_count;
+        self.tetra_buffer_culled = false;
+        self.uploaded_params = None;
+        self.cpu_tetrahedra = tetrahedra.to_vec();
+        self.tetra_bounds = tetrahedra.iter().map(|t| TetraBounds::new(vertices, t)).collect();
+        self.cpu_vertices = vertices.to_vec();
+        self.upload_ranges = dispatch_ranges(vertices, tetrahedra);
+        self.dispatch_ranges = self.upload_ranges.clone();
+
+        // Make room for the new geometry's entity centers
+        let centers_size = self.entity_capacity() * ENTITY_CENTER_SIZE;
+        if self.smoother.as_ref().is_some_and(|smoother| smoother.centers.size() < centers_size) {
+            self.smoother = Some(self.create_normal_smoother(device));
+        }
+
+        let Some(gpu) = &self.gpu else {
+            return;
+        };
+
+        // Culling only ever merges or drops runs, so this fits every culled subset too
+        self.range_buffer = Some(device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Slice Dispatch Range Buffer"),
+            contents: &strided_ranges(&self.upload_ranges, gpu.range_stride),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        }));
 
         // Create vertex buffer
         self.vertex_buffer = Some(device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -195,13 +674,13 @@ impl SlicePipeline {
         self.tetra_buffer = Some(device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Tetrahedra Buffer"),
             contents: bytemuck::cast_slice(tetrahedra),
-            usage: wgpu::BufferUsages::STORAGE,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
         }));
 
         // Recreate bind group
         self.bind_group = Some(device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Slice Bind Group"),
-            layout: &self.bind_group_layout,
+            layout: &gpu.bind_group_layout,
             entries: &[
                 wgpu::BindGroupEntry {
                     binding: 0,
@@ -223,40 +702,237 @@ impl SlicePipeline {
                     binding: 4,
                     resource: self.params_buffer.as_entire_binding(),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                        buffer: self.range_buffer.as_ref().unwrap(),
+                        offset: 0,
+                        size: NonZeroU64::new(std::mem::size_of::<DispatchRange>() as u64),
+                    }),
+                },
             ],
         }));
     }
 
     /// Update slice parameters
-    pub fn update_params(&self, queue: &wgpu::Queue, params: &SliceParams) {
-        queue.write_buffer(&self.params_buffer, 0, bytemuck::bytes_of(params));
+    ///
+    /// In CPU mode this also slices the geometry and uploads the resulting
+    /// triangles and vertex count. With culling enabled, the surviving
+    /// tetrahedra are gathered first and `tetrahedron_count` lowered to match.
+    /// Nothing is redone when the parameters, geometry and settings are the
+    /// same as last time, as while the camera stands still.
+    pub fn update_params(&mut self, queue: &wgpu::Queue, params: &SliceParams) {
+        if self.uploaded_params.is_some_and(|last| bytemuck::bytes_of(&last) == bytemuck::bytes_of(params)) {
+            return;
+        }
+        self.uploaded_params = Some(*params);
+
+        let frustum = self.view_frustum.as_ref().filter(|_| !self.slice_capping);
+        let culled = self
+            .culling_enabled
+            .then(|| cull_tetrahedra(&self.cpu_tetrahedra, &self.tetra_bounds, params, frustum));
+        let (params, tetrahedra) = match &culled {
+            Some(visible) => (SliceParams { tetrahedron_count: visible.len() as u32, ..*params }, visible.as_slice()),
+            None => (*params, self.cpu_tetrahedra.as_slice()),
+        };
+        let sliced = if culled.is_some() { params.tetrahedron_count } else { self.tetra_count };
+        self.sliced_tetra_count = sliced;
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::bytes_of(&params));
+
+        let ranges = match &culled {
+            Some(_) => dispatch_ranges(&self.cpu_vertices, tetrahedra),
+            None => self.upload_ranges.clone(),
+        };
+        if let (Some(gpu), Some(range_buffer)) = (&self.gpu, &self.range_buffer) {
+            if !ranges.is_empty() {
+                queue.write_buffer(range_buffer, 0, &strided_ranges(&ranges, gpu.range_stride));
+            }
+        }
+        self.dispatch_ranges = ranges;
+
+        if let Some(tetra_buffer) = &self.tetra_buffer {
+            // Pack the survivors at the front; restore the upload once culling stops
+            if culled.is_some() {
+                if !tetrahedra.is_empty() {
+                    queue.write_buffer(tetra_buffer, 0, bytemuck::cast_slice(tetrahedra));
+                }
+                self.tetra_buffer_culled = true;
+            } else if self.tetra_buffer_culled {
+                if !tetrahedra.is_empty() {
+                    queue.write_buffer(tetra_buffer, 0, bytemuck::cast_slice(tetrahedra));
+                }
+                self.tetra_buffer_culled = false;
+            }
+        }
+
+        if self.slices_on_cpu() {
+            let mut output = slice_on_cpu(&self.cpu_vertices, tetrahedra, &params);
+            if self.slice_capping {
+                cap_slice(&mut output, params.camera_eye);
+            }
+            output.truncate(self.max_triangles * TRIANGLE_VERTEX_COUNT);
+            if self.smooth_normals {
+                smooth_normals(&mut output);
+            }
+            if !output.is_empty() {
+                queue.write_buffer(&self.output_buffer, 0, bytemuck::cast_slice(&output));
+            }
+            let count = AtomicCounter { count: output.len() as u32 };
+            queue.write_buffer(&self.counter_buffer, 0, bytemuck::bytes_of(&count));
+            self.output_vertices = count.count;
+        }
     }
 
     /// Reset the triangle counter to zero
+    ///
+    /// No-op when slicing on the CPU, where the counter holds the count written by `update_params()`.
     pub fn reset_counter(&self, queue: &wgpu::Queue) {
+        if self.slices_on_cpu() {
+            return;
+        }
         let zero = AtomicCounter { count: 0 };
         queue.write_buffer(&self.counter_buffer, 0, bytemuck::bytes_of(&zero));
     }
 
     /// Run the slice compute pass
     ///
-    /// This dispatches the compute shader to process all geometry.
+    /// This dispatches the compute shader to process all geometry, once per
+    /// run from [`dispatch_ranges`], followed by the normal smoothing passes
+    /// when enabled.
     /// Call reset_counter() before this and update_params() with current parameters.
     pub fn run_slice_pass(&self, encoder: &mut wgpu::CommandEncoder) {
-        if self.bind_group.is_none() || self.tetra_count == 0 {
-            return;
+        self.run_slice_pass_timed(encoder, None);
+    }
+
+    /// Run the slice compute pass, writing timestamps around it
+    ///
+    /// The beginning timestamp is written by the slice pass and the end one
+    /// by the last pass run, so normal smoothing is included. Returns whether
+    /// any pass was recorded; when slicing on the CPU or with nothing to
+    /// slice, the timestamps are not written.
+    pub fn run_slice_pass_timed(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        timestamp_writes: Option<wgpu::ComputePassTimestampWrites>,
+    ) -> bool {
+        let Some(gpu) = self.gpu.as_ref().filter(|_| !self.slice_capping) else {
+            return false;
+        };
+        let tetra_count = self.sliced_tetra_count;
+        if self.bind_group.is_none() || tetra_count == 0 {
+            return false;
         }
 
+        let smoother = self.smoother.as_ref().filter(|_| self.smooth_normals);
         let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
             label: Some("Slice Compute Pass"),
-            timestamp_writes: None,
+            timestamp_writes: timestamp_writes.as_ref().map(|t| wgpu::ComputePassTimestampWrites {
+                query_set: t.query_set,
+                beginning_of_pass_write_index: t.beginning_of_pass_write_index,
+                end_of_pass_write_index: t.end_of_pass_write_index.filter(|_| smoother.is_none()),
+            }),
         });
 
-        compute_pass.set_pipeline(&self.pipeline);
-        compute_pass.set_bind_group(0, self.bind_group.as_ref().unwrap(), &[]);
+        compute_pass.set_pipeline(&gpu.pipeline);
+        for (i, range) in self.dispatch_ranges.iter().enumerate() {
+            let offset = (i as u64 * gpu.range_stride) as u32;
+            compute_pass.set_bind_group(0, self.bind_group.as_ref().unwrap(), &[offset]);
+            compute_pass.dispatch_workgroups(range.count.div_ceil(WORKGROUP_SIZE), 1, 1);
+        }
+        drop(compute_pass);
+
+        if let Some(smoother) = smoother {
+            encoder.clear_buffer(&smoother.table, 0, None);
+            encoder.clear_buffer(&smoother.centers, 0, None);
+            let mut smooth_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Smooth Normals Pass"),
+                timestamp_writes: timestamp_writes.map(|t| wgpu::ComputePassTimestampWrites {
+                    beginning_of_pass_write_index: None,
+                    ..t
+                }),
+            });
+            smooth_pass.set_bind_group(0, &smoother.bind_group, &[]);
+            smooth_pass.set_pipeline(&smoother.insert_keys);
+            smooth_pass.dispatch_workgroups(SMOOTH_WORKGROUPS, 1, 1);
+            smooth_pass.set_pipeline(&smoother.accumulate);
+            smooth_pass.dispatch_workgroups(SMOOTH_WORKGROUPS, 1, 1);
+            smooth_pass.set_pipeline(&smoother.resolve);
+            smooth_pass.dispatch_workgroups(SMOOTH_WORKGROUPS, 1, 1);
+        }
+        true
+    }
+
+    /// Copy the counter so its value can be read back after submission
+    ///
+    /// Call after [`run_slice_pass`](Self::run_slice_pass), then
+    /// [`request_counter_readback`](Self::request_counter_readback) once the
+    /// encoder is submitted. Skipped when slicing on the CPU, where the count
+    /// is already known, and while an earlier readback is still pending.
+    pub fn copy_counter_for_readback(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        let Some(gpu) = self.gpu.as_ref().filter(|_| !self.slice_capping) else {
+            return;
+        };
+        if self.readback_state != ReadbackState::Idle {
+            return;
+        }
+        encoder.copy_buffer_to_buffer(
+            &self.counter_buffer,
+            0,
+            &gpu.counter_readback,
+            0,
+            std::mem::size_of::<AtomicCounter>() as u64,
+        );
+        self.readback_state = ReadbackState::Copied;
+    }
+
+    /// Start mapping the counter copied by [`copy_counter_for_readback`](Self::copy_counter_for_readback)
+    ///
+    /// Does not block; [`triangle_count`](Self::triangle_count) picks up the
+    /// value once the map has finished.
+    pub fn request_counter_readback(&mut self) {
+        let Some(gpu) = &self.gpu else {
+            return;
+        };
+        if self.readback_state != ReadbackState::Copied {
+            return;
+        }
+        *self.readback_result.lock().unwrap() = None;
+        let result = Arc::clone(&self.readback_result);
+        gpu.counter_readback.slice(..).map_async(wgpu::MapMode::Read, move |r| {
+            *result.lock().unwrap() = Some(r.is_ok());
+        });
+        self.readback_state = ReadbackState::Mapping;
+    }
 
-        let workgroup_count = self.tetra_count.div_ceil(64);
-        compute_pass.dispatch_workgroups(workgroup_count, 1, 1);
+    /// Number of triangles the last slice produced
+    ///
+    /// Exact when slicing on the CPU. Otherwise this polls the device without waiting
+    /// and returns the most recent count that has been read back.
+    pub fn triangle_count(&mut self, device: &wgpu::Device) -> u32 {
+        if let (Some(gpu), ReadbackState::Mapping) = (&self.gpu, self.readback_state) {
+            device.poll(wgpu::Maintain::Poll);
+            let result = self.readback_result.lock().unwrap().take();
+            if let Some(ok) = result {
+                if ok {
+                    let data = gpu.counter_readback.slice(..).get_mapped_range();
+                    let count: AtomicCounter = *bytemuck::from_bytes(&data);
+                    drop(data);
+                    gpu.counter_readback.unmap();
+                    // A count from before capping was enabled is stale
+                    if !self.slice_capping {
+                        self.output_vertices = count.count;
+                    }
+                }
+                self.readback_state = ReadbackState::Idle;
+            }
+        }
+        let vertices = self.output_vertices as usize;
+        (vertices.min(self.max_triangles * TRIANGLE_VERTEX_COUNT) / TRIANGLE_VERTEX_COUNT) as u32
+    }
+
+    /// Get the active slicing path
+    pub fn mode(&self) -> SliceMode {
+        self.mode
     }
 
     /// Get the output buffer for use as vertex buffer in rendering
@@ -274,6 +950,13 @@ impl SlicePipeline {
         self.tetra_count
     }
 
+    /// Get the number of tetrahedra the last [`update_params`](Self::update_params) left to slice
+    ///
+    /// Equal to [`tetrahedron_count`](Self::tetrahedron_count) unless culling is enabled.
+    pub fn sliced_tetrahedron_count(&self) -> u32 {
+        self.sliced_tetra_count
+    }
+
     /// Get the primitive count (tetrahedra count)
     pub fn primitive_count(&self) -> u32 {
         self.tetra_count
@@ -283,22 +966,526 @@ impl SlicePipeline {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::pipeline::{headless_device, MAX_OUTPUT_TRIANGLES};
 
-    // Note: GPU tests require a wgpu device which isn't available in unit tests
-    // Integration tests should be used for full pipeline testing
+    // GPU tests skip themselves when no adapter (or no compute support) is available
 
     #[test]
     fn test_output_buffer_size_calculation() {
         // Test the buffer size calculation for various triangle counts
         let vertex_size = std::mem::size_of::<Vertex3D>();
-        assert_eq!(vertex_size, 48); // 48 bytes per vertex
+        assert_eq!(vertex_size, 52); // 52 bytes per vertex
 
-        // 100,000 triangles * 3 vertices * 48 bytes = 14,400,000 bytes
+        // 100,000 triangles * 3 vertices * 52 bytes = 15,600,000 bytes
         let size_100k = 100_000 * TRIANGLE_VERTEX_COUNT * vertex_size;
-        assert_eq!(size_100k, 14_400_000);
+        assert_eq!(size_100k, 15_600_000);
 
-        // 1,000,000 triangles (config default) * 3 vertices * 48 bytes = 144,000,000 bytes
+        // 1,000,000 triangles (config default) * 3 vertices * 52 bytes = 156,000,000 bytes
         let size_1m = 1_000_000 * TRIANGLE_VERTEX_COUNT * vertex_size;
-        assert_eq!(size_1m, 144_000_000);
+        assert_eq!(size_1m, 156_000_000);
+    }
+
+    /// Copy the sliced vertices back from the output buffer
+    fn read_output(device: &wgpu::Device, queue: &wgpu::Queue, slicer: &SlicePipeline) -> Vec<Vertex3D> {
+        let size = std::mem::size_of::<AtomicCounter>() as u64;
+        let count_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_buffer_to_buffer(slicer.counter_buffer(), 0, &count_buffer, 0, size);
+        queue.submit(std::iter::once(encoder.finish()));
+        count_buffer.slice(..).map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::Maintain::Wait);
+        let count = bytemuck::from_bytes::<AtomicCounter>(&count_buffer.slice(..).get_mapped_range()).count;
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let size = (count as usize * std::mem::size_of::<Vertex3D>()) as u64;
+        let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_buffer_to_buffer(slicer.output_buffer(), 0, &vertex_buffer, 0, size);
+        queue.submit(std::iter::once(encoder.finish()));
+        vertex_buffer.slice(..).map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::Maintain::Wait);
+        let vertices = bytemuck::cast_slice(&vertex_buffer.slice(..).get_mapped_range()).to_vec();
+        vertices
+    }
+
+    #[test]
+    fn test_gpu_smooth_normals_match_cpu() {
+        use crate::pipeline::smooth_normals;
+        use rust4d_math::{ConvexShape4D, Hypersphere4D};
+
+        let Some((device, queue)) = headless_device(wgpu::Features::empty()) else {
+            return;
+        };
+        if SliceMode::for_limits(&device.limits()) != SliceMode::Gpu {
+            eprintln!("skipping: adapter cannot run the slice compute shader");
+            return;
+        }
+
+        let sphere = Hypersphere4D::new(1.0);
+        let vertices: Vec<Vertex4D> = sphere.vertices().iter().map(|v| Vertex4D::from_position([v.x, v.y, v.z, v.w])).collect();
+        let tetrahedra: Vec<GpuTetrahedron> = sphere
+            .tetrahedra()
+            .iter()
+            .map(|t| GpuTetrahedron::from_indices(t.indices.map(|i| i as u32)))
+            .collect();
+        let params = SliceParams {
+            slice_w: 0.3,
+            tetrahedron_count: tetrahedra.len() as u32,
+            camera_eye: [0.0, 0.0, 5.0],
+            camera_position: [0.0; 4],
+            ..SliceParams::default()
+        };
+
+        let mut slicer = SlicePipeline::with_mode(&device, 10_000, SliceMode::Gpu);
+        slicer.upload_tetrahedra(&device, &vertices, &tetrahedra);
+        slicer.update_params(&queue, &params);
+        let slice = |slicer: &SlicePipeline| {
+            slicer.reset_counter(&queue);
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            slicer.run_slice_pass(&mut encoder);
+            queue.submit(std::iter::once(encoder.finish()));
+            read_output(&device, &queue, slicer)
+        };
+
+        // Smooth the flat GPU output on the CPU, then let the GPU do it
+        let mut expected = slice(&slicer);
+        smooth_normals(&mut expected);
+        slicer.set_smooth_normals(&device, true);
+        let smoothed = slice(&slicer);
+
+        // Same vertices, in any order, with the same normals
+        assert_eq!(smoothed.len(), expected.len());
+        let close = |a: [f32; 3], b: [f32; 3]| a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-3);
+        for v in &smoothed {
+            assert!(
+                expected.iter().any(|e| close(e.position, v.position) && close(e.normal, v.normal)),
+                "no CPU-smoothed vertex matches {:?}",
+                v
+            );
+        }
+    }
+
+    #[test]
+    fn test_gpu_output_stops_at_capacity() {
+        use rust4d_math::{ConvexShape4D, Hypersphere4D};
+
+        let Some((device, queue)) = headless_device(wgpu::Features::empty()) else {
+            return;
+        };
+        if SliceMode::for_limits(&device.limits()) != SliceMode::Gpu {
+            eprintln!("skipping: adapter cannot run the slice compute shader");
+            return;
+        }
+
+        let sphere = Hypersphere4D::new(1.0);
+        let vertices: Vec<Vertex4D> = sphere.vertices().iter().map(|v| Vertex4D::from_position([v.x, v.y, v.z, v.w])).collect();
+        let tetrahedra: Vec<GpuTetrahedron> = sphere
+            .tetrahedra()
+            .iter()
+            .map(|t| GpuTetrahedron::from_indices(t.indices.map(|i| i as u32)))
+            .collect();
+        let params = SliceParams {
+            tetrahedron_count: tetrahedra.len() as u32,
+            camera_eye: [0.0, 0.0, 5.0],
+            camera_position: [0.0; 4],
+            ..SliceParams::default()
+        };
+
+        // Far more triangles than the output holds
+        let mut slicer = SlicePipeline::with_mode(&device, 8, SliceMode::Gpu);
+        slicer.upload_tetrahedra(&device, &vertices, &tetrahedra);
+        slicer.update_params(&queue, &params);
+        slicer.reset_counter(&queue);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        slicer.run_slice_pass(&mut encoder);
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let output = read_output(&device, &queue, &slicer);
+        assert_eq!(output.len(), 8 * TRIANGLE_VERTEX_COUNT);
+        assert!(output.iter().all(|v| v.normal != [0.0; 3]));
+    }
+
+    #[test]
+    fn test_gpu_custom_slice_normal_matches_cpu() {
+        use rust4d_math::{ConvexShape4D, Tesseract4D};
+
+        let Some((device, queue)) = headless_device(wgpu::Features::empty()) else {
+            return;
+        };
+        if SliceMode::for_limits(&device.limits()) != SliceMode::Gpu {
+            eprintln!("skipping: adapter cannot run the slice compute shader");
+            return;
+        }
+
+        let shape = Tesseract4D::new(2.0);
+        let vertices: Vec<Vertex4D> = shape.vertices().iter().map(|v| Vertex4D::from_position([v.x, v.y, v.z, v.w])).collect();
+        let tetrahedra: Vec<GpuTetrahedron> = shape
+            .tetrahedra()
+            .iter()
+            .map(|t| GpuTetrahedron::from_indices(t.indices.map(|i| i as u32)))
+            .collect();
+        let params = SliceParams {
+            slice_w: 0.2,
+            tetrahedron_count: tetrahedra.len() as u32,
+            camera_matrix: rust4d_math::mat4::plane_rotation(0.4, 0, 2),
+            camera_position: [0.0; 4],
+            slice_normal: [0.48, 0.0, 0.64, 0.6],
+            ..SliceParams::default()
+        };
+
+        let mut slicer = SlicePipeline::with_mode(&device, 10_000, SliceMode::Gpu);
+        slicer.upload_tetrahedra(&device, &vertices, &tetrahedra);
+        slicer.update_params(&queue, &params);
+        slicer.reset_counter(&queue);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        slicer.run_slice_pass(&mut encoder);
+        queue.submit(std::iter::once(encoder.finish()));
+        let gpu = read_output(&device, &queue, &slicer);
+
+        let cpu = slice_on_cpu(&vertices, &tetrahedra, &params);
+        assert!(!cpu.is_empty());
+        assert_eq!(gpu.len(), cpu.len());
+        let close = |a: [f32; 3], b: [f32; 3]| a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-4);
+        for v in &gpu {
+            assert!(cpu.iter().any(|c| close(c.position, v.position)), "no CPU vertex matches {:?}", v);
+        }
+    }
+
+    #[test]
+    fn test_gpu_culling_skips_geometry_far_from_slice() {
+        use crate::pipeline::{perspective_matrix, ViewFrustum};
+        use rust4d_math::{ConvexShape4D, Tesseract4D, Vec4};
+
+        let Some((device, queue)) = headless_device(wgpu::Features::empty()) else {
+            return;
+        };
+        if SliceMode::for_limits(&device.limits()) != SliceMode::Gpu {
+            eprintln!("skipping: adapter cannot run the slice compute shader");
+            return;
+        }
+
+        // One tesseract on the slice in front of the camera, one far along W
+        let tesseract = Tesseract4D::new(2.0);
+        let mut vertices = Vec::new();
+        let mut tetrahedra = Vec::new();
+        for offset in [Vec4::new(0.0, 0.0, -5.0, 0.5), Vec4::new(0.0, 0.0, -5.0, 10.0)] {
+            let base = vertices.len() as u32;
+            vertices.extend(tesseract.vertices().iter().map(|v| {
+                let p = *v + offset;
+                Vertex4D::from_position([p.x, p.y, p.z, p.w])
+            }));
+            tetrahedra.extend(
+                tesseract
+                    .tetrahedra()
+                    .iter()
+                    .map(|t| GpuTetrahedron::from_indices(t.indices.map(|i| base + i as u32))),
+            );
+        }
+        let per_tesseract = tesseract.tetrahedra().len() as u32;
+        let params = SliceParams {
+            tetrahedron_count: tetrahedra.len() as u32,
+            camera_eye: [0.0; 3],
+            camera_position: [0.0; 4],
+            ..SliceParams::default()
+        };
+
+        let mut slicer = SlicePipeline::with_mode(&device, 10_000, SliceMode::Gpu);
+        slicer.set_view_frustum(Some(ViewFrustum::from_matrix(perspective_matrix(1.2, 1.0, 0.1, 100.0))));
+        let slice = |slicer: &mut SlicePipeline| {
+            slicer.update_params(&queue, &params);
+            slicer.reset_counter(&queue);
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            slicer.run_slice_pass(&mut encoder);
+            queue.submit(std::iter::once(encoder.finish()));
+            let mut positions: Vec<[f32; 3]> = read_output(&device, &queue, slicer).iter().map(|v| v.position).collect();
+            positions.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            positions
+        };
+
+        // Only the far tesseract: nothing to slice once culled
+        slicer.upload_tetrahedra(&device, &vertices, &tetrahedra[per_tesseract as usize..]);
+        assert!(slice(&mut slicer).is_empty());
+        slicer.set_culling_enabled(true);
+        assert!(slice(&mut slicer).is_empty());
+        assert_eq!(slicer.sliced_tetrahedron_count(), 0);
+
+        // Both: culling drops the far one and draws the same triangles
+        slicer.set_culling_enabled(false);
+        slicer.upload_tetrahedra(&device, &vertices, &tetrahedra);
+        let full = slice(&mut slicer);
+        assert!(!full.is_empty());
+        assert_eq!(slicer.sliced_tetrahedron_count(), 2 * per_tesseract);
+        slicer.set_culling_enabled(true);
+        assert_eq!(slice(&mut slicer), full);
+        assert!(slicer.sliced_tetrahedron_count() <= per_tesseract);
+
+        // Turning culling off again slices the whole upload
+        slicer.set_culling_enabled(false);
+        assert_eq!(slice(&mut slicer), full);
+        assert_eq!(slicer.sliced_tetrahedron_count(), 2 * per_tesseract);
+    }
+
+    #[test]
+    fn test_dispatch_ranges_split_transparent_entities() {
+        let vertices: Vec<Vertex4D> = [(false, 0), (false, 1), (true, 2), (true, 3)]
+            .into_iter()
+            .map(|(transparent, index)| {
+                Vertex4D::from_position([0.0; 4]).with_transparent(transparent).with_material_index(index)
+            })
+            .collect();
+        let tetrahedra: Vec<GpuTetrahedron> =
+            [0, 1, 2, 2, 3].into_iter().map(|v| GpuTetrahedron::from_indices([v; 4])).collect();
+
+        let runs: Vec<(u32, u32)> =
+            dispatch_ranges(&vertices, &tetrahedra).iter().map(|r| (r.first, r.count)).collect();
+        // Both opaque entities share a run; each transparent entity has its own
+        assert_eq!(runs, vec![(0, 2), (2, 2), (4, 1)]);
+        assert!(dispatch_ranges(&vertices, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_gpu_keeps_transparent_entities_in_order() {
+        use rust4d_math::{ConvexShape4D, Tesseract4D};
+
+        let Some((device, queue)) = headless_device(wgpu::Features::empty()) else {
+            return;
+        };
+        if SliceMode::for_limits(&device.limits()) != SliceMode::Gpu {
+            eprintln!("skipping: adapter cannot run the slice compute shader");
+            return;
+        }
+
+        // Transparent tesseracts, each spanning several workgroups
+        let shape = Tesseract4D::new(2.0);
+        let mut vertices = Vec::new();
+        let mut tetrahedra = Vec::new();
+        for entity in 0..8u32 {
+            let offset = vertices.len() as u32;
+            vertices.extend(shape.vertices().iter().map(|v| {
+                Vertex4D::from_position([v.x + entity as f32, v.y, v.z, v.w])
+                    .with_transparent(true)
+                    .with_material_index(entity)
+            }));
+            tetrahedra.extend(
+                shape.tetrahedra().iter().map(|t| GpuTetrahedron::from_indices(t.indices.map(|i| i as u32 + offset))),
+            );
+        }
+        let params = SliceParams {
+            tetrahedron_count: tetrahedra.len() as u32,
+            camera_position: [0.0; 4],
+            ..SliceParams::default()
+        };
+
+        let mut slicer = SlicePipeline::with_mode(&device, 100_000, SliceMode::Gpu);
+        slicer.upload_tetrahedra(&device, &vertices, &tetrahedra);
+        slicer.update_params(&queue, &params);
+        slicer.reset_counter(&queue);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        slicer.run_slice_pass(&mut encoder);
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let order: Vec<u32> = read_output(&device, &queue, &slicer).iter().map(|v| v.material_index).collect();
+        assert!(!order.is_empty());
+        assert!(order.windows(2).all(|pair| pair[0] <= pair[1]), "entities interleaved: {:?}", order);
+        assert_eq!(order.last(), Some(&7));
+    }
+
+    #[test]
+    fn test_capping_closes_slice_in_gpu_mode() {
+        use rust4d_math::{ConvexShape4D, SixteenCell4D};
+
+        let Some((device, queue)) = headless_device(wgpu::Features::empty()) else {
+            return;
+        };
+        if SliceMode::for_limits(&device.limits()) != SliceMode::Gpu {
+            eprintln!("skipping: adapter cannot run the slice compute shader");
+            return;
+        }
+
+        // A 16-cell missing one cell: its slice near +W is an octahedron with a face missing
+        let shape = SixteenCell4D::new(1.0);
+        let vertices: Vec<Vertex4D> = shape.vertices().iter().map(|v| Vertex4D::from_position([v.x, v.y, v.z, v.w])).collect();
+        let tetrahedra: Vec<GpuTetrahedron> = shape
+            .tetrahedra()
+            .iter()
+            .filter(|t| t.indices.contains(&6))
+            .skip(1)
+            .map(|t| GpuTetrahedron::from_indices(t.indices.map(|i| i as u32)))
+            .collect();
+        let params = SliceParams {
+            slice_w: 0.5,
+            tetrahedron_count: tetrahedra.len() as u32,
+            camera_eye: [0.0, 0.0, 5.0],
+            camera_position: [0.0; 4],
+            ..SliceParams::default()
+        };
+
+        let mut slicer = SlicePipeline::with_mode(&device, 10_000, SliceMode::Gpu);
+        slicer.upload_tetrahedra(&device, &vertices, &tetrahedra);
+        let slice = |slicer: &mut SlicePipeline| {
+            slicer.update_params(&queue, &params);
+            slicer.reset_counter(&queue);
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            slicer.run_slice_pass(&mut encoder);
+            slicer.copy_counter_for_readback(&mut encoder);
+            queue.submit(std::iter::once(encoder.finish()));
+            slicer.request_counter_readback();
+            read_output(&device, &queue, slicer).len() / TRIANGLE_VERTEX_COUNT
+        };
+
+        assert_eq!(slice(&mut slicer), 7);
+        slicer.set_slice_capping(true);
+        assert_eq!(slice(&mut slicer), 8);
+        assert_eq!(slicer.triangle_count(&device), 8);
+    }
+
+    #[test]
+    fn test_capping_ignores_frustum_when_culling() {
+        use crate::pipeline::perspective_matrix;
+        use rust4d_math::{ConvexShape4D, Hypersphere4D};
+
+        let Some((device, queue)) = headless_device(wgpu::Features::empty()) else {
+            return;
+        };
+
+        // A hypersphere in front of the camera, wider than the view
+        let sphere = Hypersphere4D::new(1.0);
+        let vertices: Vec<Vertex4D> = sphere
+            .vertices()
+            .iter()
+            .map(|v| Vertex4D::from_position([v.x, v.y, v.z - 1.6, v.w]))
+            .collect();
+        let tetrahedra: Vec<GpuTetrahedron> = sphere
+            .tetrahedra()
+            .iter()
+            .map(|t| GpuTetrahedron::from_indices(t.indices.map(|i| i as u32)))
+            .collect();
+        let params = SliceParams {
+            tetrahedron_count: tetrahedra.len() as u32,
+            camera_eye: [0.0; 3],
+            camera_position: [0.0; 4],
+            ..SliceParams::default()
+        };
+
+        let mut slicer = SlicePipeline::with_mode(&device, 100_000, SliceMode::Cpu);
+        slicer.upload_tetrahedra(&device, &vertices, &tetrahedra);
+        slicer.set_slice_capping(true);
+        slicer.update_params(&queue, &params);
+        let capped = slicer.triangle_count(&device);
+        assert!(capped > 0);
+
+        // The frustum alone culls the sides of the sphere away
+        slicer.set_view_frustum(Some(ViewFrustum::from_matrix(perspective_matrix(0.6, 1.0, 0.1, 100.0))));
+        slicer.set_culling_enabled(true);
+        slicer.set_slice_capping(false);
+        slicer.update_params(&queue, &params);
+        assert!(slicer.sliced_tetrahedron_count() < slicer.tetrahedron_count());
+        let frustum_culled = slicer.sliced_tetrahedron_count();
+
+        // With capping too, only the slice culls, so no cut edges get capped
+        slicer.set_slice_capping(true);
+        slicer.update_params(&queue, &params);
+        assert!(slicer.sliced_tetrahedron_count() > frustum_culled);
+        assert_eq!(slicer.triangle_count(&device), capped);
+    }
+
+    #[test]
+    fn test_unchanged_params_skip_the_upload() {
+        use crate::pipeline::perspective_matrix;
+        use rust4d_math::{ConvexShape4D, Hypersphere4D};
+
+        let Some((device, queue)) = headless_device(wgpu::Features::empty()) else {
+            return;
+        };
+
+        let sphere = Hypersphere4D::new(1.0);
+        let vertices: Vec<Vertex4D> = sphere
+            .vertices()
+            .iter()
+            .map(|v| Vertex4D::from_position([v.x, v.y, v.z - 3.0, v.w]))
+            .collect();
+        let tetrahedra: Vec<GpuTetrahedron> = sphere
+            .tetrahedra()
+            .iter()
+            .map(|t| GpuTetrahedron::from_indices(t.indices.map(|i| i as u32)))
+            .collect();
+        let params = SliceParams {
+            tetrahedron_count: tetrahedra.len() as u32,
+            camera_eye: [0.0; 3],
+            camera_position: [0.0; 4],
+            ..SliceParams::default()
+        };
+        let frustum = || Some(ViewFrustum::from_matrix(perspective_matrix(1.2, 1.0, 0.1, 100.0)));
+
+        let mut slicer = SlicePipeline::with_mode(&device, 100_000, SliceMode::Cpu);
+        slicer.upload_tetrahedra(&device, &vertices, &tetrahedra);
+        slicer.set_view_frustum(frustum());
+        slicer.update_params(&queue, &params);
+        assert!(!read_output(&device, &queue, &slicer).is_empty());
+
+        // The same camera and frustum again: the (cleared) output is left alone
+        queue.write_buffer(slicer.counter_buffer(), 0, bytemuck::bytes_of(&AtomicCounter { count: 0 }));
+        slicer.set_view_frustum(frustum());
+        slicer.update_params(&queue, &params);
+        assert!(read_output(&device, &queue, &slicer).is_empty());
+
+        // Moving the camera slices again
+        let moved = SliceParams { camera_position: [0.0, 0.0, 0.1, 0.0], ..params };
+        slicer.update_params(&queue, &moved);
+        assert!(!read_output(&device, &queue, &slicer).is_empty());
+    }
+
+    #[test]
+    fn test_slice_mode_for_default_limits_is_gpu() {
+        assert_eq!(SliceMode::for_limits(&wgpu::Limits::default()), SliceMode::Gpu);
+    }
+
+    #[test]
+    fn test_slice_mode_for_downlevel_limits_is_cpu() {
+        // WebGL2-class devices have no compute or storage buffer support
+        let limits = wgpu::Limits::downlevel_webgl2_defaults();
+        assert_eq!(SliceMode::for_limits(&limits), SliceMode::Cpu);
+    }
+
+    #[test]
+    fn test_output_capacity_within_limits_is_unchanged() {
+        let limits = wgpu::Limits::default();
+        assert_eq!(SlicePipeline::output_capacity(1000, &limits, SliceMode::Gpu), 1000);
+    }
+
+    #[test]
+    fn test_output_capacity_clamped_to_small_storage_limit() {
+        // A weak adapter: 1 MiB storage bindings
+        let limits = wgpu::Limits {
+            max_storage_buffer_binding_size: 1 << 20,
+            ..wgpu::Limits::default()
+        };
+        let capacity = SlicePipeline::output_capacity(MAX_OUTPUT_TRIANGLES, &limits, SliceMode::Gpu);
+        let bytes = capacity * TRIANGLE_VERTEX_COUNT * std::mem::size_of::<Vertex3D>();
+
+        assert!(capacity > 0 && capacity < MAX_OUTPUT_TRIANGLES);
+        assert!(bytes as u64 <= limits.max_storage_buffer_binding_size as u64);
+    }
+
+    #[test]
+    fn test_output_capacity_respects_max_buffer_size() {
+        let limits = wgpu::Limits {
+            max_buffer_size: 156 * 10,
+            ..wgpu::Limits::default()
+        };
+        assert_eq!(SlicePipeline::output_capacity(1000, &limits, SliceMode::Gpu), 10);
+        assert_eq!(SlicePipeline::output_capacity(1000, &limits, SliceMode::Cpu), 10);
     }
 }
@@ -0,0 +1,306 @@
+//! Smooth vertex normals for sliced geometry
+//!
+//! Slicing emits unconnected triangles with flat, camera-facing normals, so
+//! curved cross-sections such as a hypersphere's look faceted. Smoothing gives
+//! each vertex the average of the normals of every triangle of the same entity
+//! that touches its position, weighted by the triangle's corner angle there.
+//! Vertices on a sharp crease (a cube edge, say) keep their flat normal; see
+//! [`SMOOTH_NORMAL_MIN_DOT`].
+//!
+//! Camera-facing normals disagree where a surface turns away from the camera,
+//! so before averaging each face normal is turned to point away from the
+//! center of its entity's cross-section, and the result is turned back to face
+//! the camera the way the triangle did.
+//!
+//! [`smooth_normals`] runs on the CPU slice output. `smooth_normals.wgsl` does
+//! the same on the GPU, accumulating fixed-point sums in a hash table keyed by
+//! the quantized position.
+
+use std::collections::HashMap;
+
+use super::types::{Vertex3D, TRIANGLE_VERTEX_COUNT};
+
+/// Grid size positions are snapped to when matching shared vertices
+///
+/// Adjacent tetrahedra compute the same edge intersection from opposite ends,
+/// so shared vertices agree only up to rounding.
+pub const NORMAL_POSITION_QUANTUM: f32 = 1e-3;
+
+/// Smallest dot product between the smoothed and flat normal that is kept
+///
+/// Below this (about 37 degrees) the vertex sits on a crease and keeps the
+/// flat normal of its triangle.
+pub const SMOOTH_NORMAL_MIN_DOT: f32 = 0.8;
+
+/// Vertices sharing a key get the same smoothed normal
+type NormalKey = ([i32; 3], u32);
+
+/// Replace flat triangle normals with smoothed per-vertex normals
+///
+/// `vertices` holds 3 vertices per triangle, each carrying its triangle's
+/// unit face normal, as produced by slicing.
+pub fn smooth_normals(vertices: &mut [Vertex3D]) {
+    let centers = entity_centers(vertices);
+    let outward_sign = |tri: &[Vertex3D]| outward_sign(tri, centers[&tri[0].material_index]);
+
+    let mut sums: HashMap<NormalKey, [f32; 3]> = HashMap::new();
+    for tri in vertices.chunks_exact(TRIANGLE_VERTEX_COUNT) {
+        let sign = outward_sign(tri);
+        for corner in 0..TRIANGLE_VERTEX_COUNT {
+            let vertex = &tri[corner];
+            let weight = corner_angle(vertex.position, tri[(corner + 1) % 3].position, tri[(corner + 2) % 3].position);
+            let sum = sums.entry(normal_key(vertex)).or_insert([0.0; 3]);
+            for (s, n) in sum.iter_mut().zip(vertex.normal) {
+                *s += n * sign * weight;
+            }
+        }
+    }
+
+    for tri in vertices.chunks_exact_mut(TRIANGLE_VERTEX_COUNT) {
+        let sign = outward_sign(tri);
+        for vertex in tri {
+            let sum = sums[&normal_key(vertex)];
+            vertex.normal = resolve_normal(sum, vertex.normal, sign);
+        }
+    }
+}
+
+/// Average vertex position of each entity's triangles, keyed by material index
+fn entity_centers(vertices: &[Vertex3D]) -> HashMap<u32, [f32; 3]> {
+    let mut sums: HashMap<u32, ([f32; 3], f32)> = HashMap::new();
+    for vertex in vertices {
+        let (sum, count) = sums.entry(vertex.material_index).or_insert(([0.0; 3], 0.0));
+        for (s, p) in sum.iter_mut().zip(vertex.position) {
+            *s += p;
+        }
+        *count += 1.0;
+    }
+    sums.into_iter().map(|(index, (sum, count))| (index, sum.map(|s| s / count))).collect()
+}
+
+/// 1 if a triangle's normal points away from its entity's center, else -1
+fn outward_sign(tri: &[Vertex3D], center: [f32; 3]) -> f32 {
+    let tri_center = [0, 1, 2].map(|axis| (tri[0].position[axis] + tri[1].position[axis] + tri[2].position[axis]) / 3.0);
+    if dot(tri[0].normal, sub(tri_center, center)) < 0.0 { -1.0 } else { 1.0 }
+}
+
+/// Pick the smoothed normal for a vertex, or keep `flat` on a crease
+///
+/// `sum` is the outward-facing sum and `sign` turns it back to the side
+/// `flat` faces.
+fn resolve_normal(sum: [f32; 3], flat: [f32; 3], sign: f32) -> [f32; 3] {
+    let length = dot(sum, sum).sqrt();
+    if length < 1e-6 {
+        return flat;
+    }
+    let smooth = sum.map(|s| s * sign / length);
+    if dot(smooth, flat) >= SMOOTH_NORMAL_MIN_DOT {
+        smooth
+    } else {
+        flat
+    }
+}
+
+/// Angle of the triangle corner at `p` between the edges to `a` and `b`
+///
+/// Zero for degenerate corners, so they add nothing to the average.
+fn corner_angle(p: [f32; 3], a: [f32; 3], b: [f32; 3]) -> f32 {
+    let e1 = sub(a, p);
+    let e2 = sub(b, p);
+    let lengths = (dot(e1, e1) * dot(e2, e2)).sqrt();
+    if lengths < 1e-12 {
+        return 0.0;
+    }
+    (dot(e1, e2) / lengths).clamp(-1.0, 1.0).acos()
+}
+
+fn normal_key(vertex: &Vertex3D) -> NormalKey {
+    (
+        vertex.position.map(|p| (p / NORMAL_POSITION_QUANTUM).round() as i32),
+        vertex.material_index,
+    )
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn length(v: [f32; 3]) -> f32 {
+        dot(v, v).sqrt()
+    }
+
+    fn normalized(v: [f32; 3]) -> [f32; 3] {
+        v.map(|c| c / length(v))
+    }
+
+    fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+        [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+    }
+
+    /// A triangle with its flat normal, as slicing outputs it
+    fn triangle(p: [[f32; 3]; 3], material_index: u32) -> [Vertex3D; 3] {
+        let normal = normalized(cross(sub(p[1], p[0]), sub(p[2], p[0])));
+        p.map(|position| Vertex3D { position, normal, material_index, ..Vertex3D::default() })
+    }
+
+    /// A cone cap: triangles fanned around an apex above a hexagon
+    fn cone_fan() -> Vec<Vertex3D> {
+        let apex = [0.0, 0.5, 0.0];
+        let ring: Vec<[f32; 3]> = (0..6)
+            .map(|i| {
+                let a = i as f32 * std::f32::consts::TAU / 6.0;
+                [a.cos(), 0.0, -a.sin()]
+            })
+            .collect();
+        (0..6).flat_map(|i| triangle([apex, ring[i], ring[(i + 1) % 6]], 0)).collect()
+    }
+
+    #[test]
+    fn test_fan_normals_are_unit_and_outward() {
+        let mut vertices = cone_fan();
+        // Flat normals point up and out of the cone
+        assert!(vertices.iter().all(|v| v.normal[1] > 0.0));
+        smooth_normals(&mut vertices);
+
+        for v in &vertices {
+            assert!((length(v.normal) - 1.0).abs() < 1e-5);
+            if v.position == [0.0, 0.5, 0.0] {
+                // The apex averages to straight up
+                assert!(length(sub(v.normal, [0.0, 1.0, 0.0])) < 1e-5);
+            } else {
+                // Rim vertices tilt up and away from the axis
+                let radial = [v.position[0], 0.0, v.position[2]];
+                assert!(v.normal[1] > 0.0);
+                assert!(dot(v.normal, radial) > 0.0);
+            }
+        }
+
+        // Both triangles meeting at a rim vertex now agree there
+        let rim = vertices.iter().filter(|v| v.position == vertices[1].position);
+        assert!(rim.map(|v| v.normal).all(|n| length(sub(n, vertices[1].normal)) < 1e-6));
+    }
+
+    /// A UV sphere sliced as seen from +Z: each normal faces the camera
+    fn camera_facing_sphere() -> Vec<Vertex3D> {
+        let (rings, segments) = (8, 16);
+        let point = |i: usize, j: usize| {
+            let theta = std::f32::consts::PI * i as f32 / rings as f32;
+            let phi = std::f32::consts::TAU * j as f32 / segments as f32;
+            [theta.sin() * phi.cos(), theta.cos(), theta.sin() * phi.sin()]
+        };
+        let mut vertices = Vec::new();
+        for i in 0..rings {
+            for j in 0..segments {
+                let (a, b, c, d) = (point(i, j), point(i + 1, j), point(i + 1, j + 1), point(i, j + 1));
+                // The poles only get the triangle that isn't degenerate there
+                if i != rings - 1 {
+                    vertices.extend(triangle([a, b, c], 0));
+                }
+                if i != 0 {
+                    vertices.extend(triangle([a, c, d], 0));
+                }
+            }
+        }
+        for tri in vertices.chunks_exact_mut(3) {
+            let center = [0, 1, 2].map(|axis| (tri[0].position[axis] + tri[1].position[axis] + tri[2].position[axis]) / 3.0);
+            if dot(tri[0].normal, sub([0.0, 0.0, 5.0], center)) < 0.0 {
+                tri.iter_mut().for_each(|v| v.normal = v.normal.map(|c| -c));
+            }
+        }
+        vertices
+    }
+
+    #[test]
+    fn test_normals_are_averaged_outward_across_the_silhouette() {
+        let mut vertices = camera_facing_sphere();
+        // Triangles facing away from the camera carry inward normals
+        let outward: Vec<bool> = vertices.chunks_exact(3).map(|tri| dot(tri[0].normal, tri[0].position) > 0.0).collect();
+        assert!(outward.contains(&false));
+        smooth_normals(&mut vertices);
+
+        for (tri, outward) in vertices.chunks_exact(3).zip(outward) {
+            for v in tri {
+                assert!((length(v.normal) - 1.0).abs() < 1e-5);
+                // Follows the sphere, still on the side the triangle faced
+                let radial = dot(v.normal, normalized(v.position));
+                let facing = if outward { radial } else { -radial };
+                assert!(facing > 0.995, "normal {:?} at {:?}", v.normal, v.position);
+            }
+        }
+    }
+
+    #[test]
+    fn test_crease_keeps_flat_normals() {
+        // Two faces of a cube meeting at a right angle along the Z axis
+        let mut vertices: Vec<Vertex3D> = triangle([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]], 0)
+            .into_iter()
+            .chain(triangle([[0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]], 0))
+            .collect();
+        let flat: Vec<[f32; 3]> = vertices.iter().map(|v| v.normal).collect();
+        smooth_normals(&mut vertices);
+        assert_eq!(vertices.iter().map(|v| v.normal).collect::<Vec<_>>(), flat);
+    }
+
+    #[test]
+    fn test_different_entities_are_not_smoothed_together() {
+        // Same shallow fan, but every triangle from a different entity
+        let mut vertices = cone_fan();
+        for (i, v) in vertices.iter_mut().enumerate() {
+            v.material_index = (i / 3) as u32;
+        }
+        let flat: Vec<[f32; 3]> = vertices.iter().map(|v| v.normal).collect();
+        smooth_normals(&mut vertices);
+        for (v, n) in vertices.iter().zip(flat) {
+            assert!(length(sub(v.normal, n)) < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_hypersphere_slice_normals_follow_the_sphere() {
+        use crate::pipeline::{slice_on_cpu, GpuTetrahedron, SliceParams, Vertex4D};
+        use rust4d_math::{ConvexShape4D, Hypersphere4D};
+
+        let sphere = Hypersphere4D::new(1.0);
+        let vertices: Vec<Vertex4D> = sphere.vertices().iter().map(|v| Vertex4D::from_position([v.x, v.y, v.z, v.w])).collect();
+        let tetrahedra: Vec<GpuTetrahedron> = sphere
+            .tetrahedra()
+            .iter()
+            .map(|t| GpuTetrahedron::from_indices(t.indices.map(|i| i as u32)))
+            .collect();
+        let params = SliceParams {
+            slice_w: 0.3,
+            tetrahedron_count: tetrahedra.len() as u32,
+            camera_eye: [0.0, 0.0, 5.0],
+            camera_position: [0.0; 4],
+            ..SliceParams::default()
+        };
+        let flat = slice_on_cpu(&vertices, &tetrahedra, &params);
+        let mut smooth = flat.clone();
+        smooth_normals(&mut smooth);
+
+        // Alignment with the sphere's radius (normals may face in or out)
+        let radial_error = |vertices: &[Vertex3D]| {
+            vertices
+                .iter()
+                .map(|v| 1.0 - dot(v.normal, normalized(v.position)).abs())
+                .sum::<f32>()
+                / vertices.len() as f32
+        };
+        assert!(radial_error(&smooth) < radial_error(&flat) * 0.75);
+        assert!(smooth.iter().all(|v| (length(v.normal) - 1.0).abs() < 1e-5));
+    }
+
+    #[test]
+    fn test_corner_angle() {
+        assert!((corner_angle([0.0; 3], [1.0, 0.0, 0.0], [0.0, 2.0, 0.0]) - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+        assert_eq!(corner_angle([0.0; 3], [0.0; 3], [1.0, 0.0, 0.0]), 0.0);
+    }
+}
@@ -0,0 +1,311 @@
+//! GPU timestamp queries for the slice and render passes
+//!
+//! [`GpuTimer`] writes a timestamp at the start and end of the slice compute
+//! passes and of the render pass, resolves them into a buffer and reads that
+//! back without blocking, the same way [`SlicePipeline`](super::SlicePipeline)
+//! reads its triangle counter. Timings therefore lag a frame or two.
+//!
+//! Timestamps need [`wgpu::Features::TIMESTAMP_QUERY`]. On devices without it
+//! the timer is disabled and every timing reads as zero.
+
+use std::sync::{Arc, Mutex};
+
+use super::slice_pipeline::ReadbackState;
+
+/// Query indices: begin and end of the slice passes, then of the render pass
+const SLICE_BEGIN: u32 = 0;
+const SLICE_END: u32 = 1;
+const RENDER_BEGIN: u32 = 2;
+const RENDER_END: u32 = 3;
+const QUERY_COUNT: u32 = 4;
+
+/// Bytes of one pass's begin and end timestamps
+const PASS_SIZE: u64 = 2 * std::mem::size_of::<u64>() as u64;
+
+/// Offset of the render pass timestamps in the resolve buffer, which must be aligned
+const RENDER_RESOLVE_OFFSET: u64 = wgpu::QUERY_RESOLVE_BUFFER_ALIGNMENT;
+
+/// GPU time spent in each pass of a frame, in microseconds
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PassTimings {
+    /// Slice compute pass, plus normal smoothing when enabled (0 when slicing on the CPU)
+    pub slice_us: f32,
+    /// Render pass drawing the cross-section
+    pub render_us: f32,
+}
+
+/// Query set and buffers (None when timestamps are unsupported)
+struct TimestampQueries {
+    query_set: wgpu::QuerySet,
+    /// Resolved timestamps, one aligned slot per pass
+    resolve_buffer: wgpu::Buffer,
+    /// Mappable copy of both passes' timestamps
+    readback_buffer: wgpu::Buffer,
+    /// Nanoseconds per timestamp tick
+    period: f32,
+}
+
+/// Times the slice and render passes with timestamp queries
+pub struct GpuTimer {
+    queries: Option<TimestampQueries>,
+    /// Progress of the timestamp readback
+    readback_state: ReadbackState,
+    /// Map result set by the callback (None while pending)
+    readback_result: Arc<Mutex<Option<bool>>>,
+    /// Whether the slice pass wrote the timestamps being read back
+    slice_timed: bool,
+    /// Timings of the last frame that was read back
+    timings: PassTimings,
+}
+
+impl GpuTimer {
+    /// Create a timer, enabled if the device has [`wgpu::Features::TIMESTAMP_QUERY`]
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        let queries = device.features().contains(wgpu::Features::TIMESTAMP_QUERY).then(|| TimestampQueries {
+            query_set: device.create_query_set(&wgpu::QuerySetDescriptor {
+                label: Some("Pass Timestamps"),
+                ty: wgpu::QueryType::Timestamp,
+                count: QUERY_COUNT,
+            }),
+            resolve_buffer: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Timestamp Resolve Buffer"),
+                size: RENDER_RESOLVE_OFFSET + PASS_SIZE,
+                usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            }),
+            readback_buffer: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Timestamp Readback Buffer"),
+                size: 2 * PASS_SIZE,
+                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            }),
+            period: queue.get_timestamp_period(),
+        });
+        if queries.is_none() {
+            log::info!("Timestamp queries unavailable; GPU pass timings will read as zero");
+        }
+
+        Self {
+            queries,
+            readback_state: ReadbackState::Idle,
+            readback_result: Arc::new(Mutex::new(None)),
+            slice_timed: false,
+            timings: PassTimings::default(),
+        }
+    }
+
+    /// Check if the device supports timestamps, so timings are measured
+    pub fn is_enabled(&self) -> bool {
+        self.queries.is_some()
+    }
+
+    /// Timestamp writes for the slice compute passes
+    pub fn slice_writes(&self) -> Option<wgpu::ComputePassTimestampWrites<'_>> {
+        self.queries.as_ref().map(|q| wgpu::ComputePassTimestampWrites {
+            query_set: &q.query_set,
+            beginning_of_pass_write_index: Some(SLICE_BEGIN),
+            end_of_pass_write_index: Some(SLICE_END),
+        })
+    }
+
+    /// Timestamp writes for the render pass
+    pub fn render_writes(&self) -> Option<wgpu::RenderPassTimestampWrites<'_>> {
+        self.queries.as_ref().map(|q| wgpu::RenderPassTimestampWrites {
+            query_set: &q.query_set,
+            beginning_of_pass_write_index: Some(RENDER_BEGIN),
+            end_of_pass_write_index: Some(RENDER_END),
+        })
+    }
+
+    /// Resolve this frame's timestamps so they can be read back after submission
+    ///
+    /// Call after both passes, with whether the slice pass was recorded (it is
+    /// skipped when slicing on the CPU), then
+    /// [`request_readback`](Self::request_readback) once the encoder is
+    /// submitted. Skipped while an earlier readback is still pending.
+    pub fn resolve(&mut self, encoder: &mut wgpu::CommandEncoder, slice_timed: bool) {
+        let Some(q) = &self.queries else {
+            return;
+        };
+        if self.readback_state != ReadbackState::Idle {
+            return;
+        }
+        // Only queries written this frame may be resolved
+        if slice_timed {
+            encoder.resolve_query_set(&q.query_set, SLICE_BEGIN..SLICE_END + 1, &q.resolve_buffer, 0);
+        }
+        encoder.resolve_query_set(&q.query_set, RENDER_BEGIN..RENDER_END + 1, &q.resolve_buffer, RENDER_RESOLVE_OFFSET);
+        encoder.copy_buffer_to_buffer(&q.resolve_buffer, 0, &q.readback_buffer, 0, PASS_SIZE);
+        encoder.copy_buffer_to_buffer(&q.resolve_buffer, RENDER_RESOLVE_OFFSET, &q.readback_buffer, PASS_SIZE, PASS_SIZE);
+        self.slice_timed = slice_timed;
+        self.readback_state = ReadbackState::Copied;
+    }
+
+    /// Start mapping the timestamps resolved by [`resolve`](Self::resolve)
+    ///
+    /// Does not block; [`timings`](Self::timings) picks them up once the map
+    /// has finished.
+    pub fn request_readback(&mut self) {
+        let Some(q) = &self.queries else {
+            return;
+        };
+        if self.readback_state != ReadbackState::Copied {
+            return;
+        }
+        *self.readback_result.lock().unwrap() = None;
+        let result = Arc::clone(&self.readback_result);
+        q.readback_buffer.slice(..).map_async(wgpu::MapMode::Read, move |r| {
+            *result.lock().unwrap() = Some(r.is_ok());
+        });
+        self.readback_state = ReadbackState::Mapping;
+    }
+
+    /// GPU time of the passes in the last frame that was read back
+    ///
+    /// Polls the device without waiting. All zero when timestamps are unsupported.
+    pub fn timings(&mut self, device: &wgpu::Device) -> PassTimings {
+        if let (Some(q), ReadbackState::Mapping) = (&self.queries, self.readback_state) {
+            device.poll(wgpu::Maintain::Poll);
+            let result = self.readback_result.lock().unwrap().take();
+            if let Some(ok) = result {
+                if ok {
+                    let data = q.readback_buffer.slice(..).get_mapped_range();
+                    let ticks: [u64; QUERY_COUNT as usize] = bytemuck::pod_read_unaligned(&data);
+                    drop(data);
+                    q.readback_buffer.unmap();
+                    let micros = |begin: u64, end: u64| end.saturating_sub(begin) as f32 * q.period / 1000.0;
+                    self.timings = PassTimings {
+                        slice_us: if self.slice_timed { micros(ticks[0], ticks[1]) } else { 0.0 },
+                        render_us: micros(ticks[2], ticks[3]),
+                    };
+                }
+                self.readback_state = ReadbackState::Idle;
+            }
+        }
+        self.timings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::headless_device;
+
+    // GPU tests skip themselves when no adapter is available
+
+    /// An empty compute pass, standing in for the slice pass
+    fn empty_pass(encoder: &mut wgpu::CommandEncoder, writes: Option<wgpu::ComputePassTimestampWrites>) -> bool {
+        drop(encoder.begin_compute_pass(&wgpu::ComputePassDescriptor { label: None, timestamp_writes: writes }));
+        true
+    }
+
+    /// Record `slice` and a cleared render pass, timed, and read the timings back
+    fn time_frame(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        timer: &mut GpuTimer,
+        slice: impl FnOnce(&mut wgpu::CommandEncoder, Option<wgpu::ComputePassTimestampWrites>) -> bool,
+    ) -> PassTimings {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d { width: 4, height: 4, depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        let slice_timed = slice(&mut encoder, timer.slice_writes());
+        drop(encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations { load: wgpu::LoadOp::Clear(wgpu::Color::BLACK), store: wgpu::StoreOp::Store },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: timer.render_writes(),
+            occlusion_query_set: None,
+        }));
+        timer.resolve(&mut encoder, slice_timed);
+        queue.submit(std::iter::once(encoder.finish()));
+        timer.request_readback();
+        device.poll(wgpu::Maintain::Wait);
+        timer.timings(device)
+    }
+
+    #[test]
+    fn test_timings_are_non_negative() {
+        let Some((device, queue)) = headless_device(wgpu::Features::TIMESTAMP_QUERY) else {
+            return;
+        };
+        let mut timer = GpuTimer::new(&device, &queue);
+        if !timer.is_enabled() {
+            eprintln!("skipping: adapter has no timestamp queries");
+            return;
+        }
+
+        for _ in 0..3 {
+            let timings = time_frame(&device, &queue, &mut timer, empty_pass);
+            assert!(timings.slice_us >= 0.0 && timings.slice_us.is_finite(), "{:?}", timings);
+            assert!(timings.render_us >= 0.0 && timings.render_us.is_finite(), "{:?}", timings);
+        }
+    }
+
+    #[test]
+    fn test_slice_pass_timed_with_smoothing() {
+        use crate::pipeline::{GpuTetrahedron, SliceMode, SliceParams, SlicePipeline, Vertex4D};
+        use rust4d_math::{ConvexShape4D, Tesseract4D};
+
+        let Some((device, queue)) = headless_device(wgpu::Features::TIMESTAMP_QUERY) else {
+            return;
+        };
+        let mut timer = GpuTimer::new(&device, &queue);
+        if !timer.is_enabled() || SliceMode::for_limits(&device.limits()) != SliceMode::Gpu {
+            eprintln!("skipping: adapter has no timestamp queries or compute slicing");
+            return;
+        }
+
+        let tesseract = Tesseract4D::new(2.0);
+        let vertices: Vec<Vertex4D> =
+            tesseract.vertices().iter().map(|v| Vertex4D::from_position([v.x, v.y, v.z, v.w])).collect();
+        let tetrahedra: Vec<GpuTetrahedron> = tesseract
+            .tetrahedra()
+            .iter()
+            .map(|t| GpuTetrahedron::from_indices(t.indices.map(|i| i as u32)))
+            .collect();
+        let mut slicer = SlicePipeline::with_mode(&device, 10_000, SliceMode::Gpu);
+        slicer.set_smooth_normals(&device, true);
+
+        // Nothing uploaded: no pass, so the slice time is zero
+        let timings = time_frame(&device, &queue, &mut timer, |encoder, writes| slicer.run_slice_pass_timed(encoder, writes));
+        assert_eq!(timings.slice_us, 0.0);
+
+        // The begin timestamp goes on the slice pass and the end one on the smoothing pass
+        slicer.upload_tetrahedra(&device, &vertices, &tetrahedra);
+        slicer.update_params(&queue, &SliceParams { tetrahedron_count: tetrahedra.len() as u32, ..SliceParams::default() });
+        slicer.reset_counter(&queue);
+        let timings = time_frame(&device, &queue, &mut timer, |encoder, writes| {
+            let timed = slicer.run_slice_pass_timed(encoder, writes);
+            assert!(timed);
+            timed
+        });
+        assert!(timings.slice_us >= 0.0 && timings.slice_us.is_finite(), "{:?}", timings);
+    }
+
+    #[test]
+    fn test_without_timestamp_feature_timings_are_zero() {
+        let Some((device, queue)) = headless_device(wgpu::Features::empty()) else {
+            return;
+        };
+        let mut timer = GpuTimer::new(&device, &queue);
+        assert!(!timer.is_enabled());
+        assert!(timer.slice_writes().is_none());
+        assert!(timer.render_writes().is_none());
+        assert_eq!(time_frame(&device, &queue, &mut timer, empty_pass), PassTimings::default());
+    }
+}
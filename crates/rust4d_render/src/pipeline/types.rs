@@ -4,6 +4,14 @@
 //! All types derive Pod and Zeroable for safe GPU buffer operations.
 
 use bytemuck::{Pod, Zeroable};
+use rust4d_math::{mat4, Vec4};
+
+/// Bit set in a vertex's `material_id` when its entity is transparent
+///
+/// Material ids are 16-bit, so the flag sits above them. The render shader
+/// draws flagged triangles in the blended transparent pass instead of the
+/// opaque one.
+pub const TRANSPARENT_MATERIAL_BIT: u32 = 1 << 16;
 
 /// A vertex in 4D space with color
 #[repr(C)]
@@ -13,20 +21,53 @@ pub struct Vertex4D {
     pub position: [f32; 4],
     /// RGBA color
     pub color: [f32; 4],
+    /// Shading pipeline id, plus [`TRANSPARENT_MATERIAL_BIT`] (copied to the sliced output vertices)
+    pub material_id: u32,
+    /// Index of the owning entity's entry in the material buffer
+    pub material_index: u32,
+    /// Padding to match the 16-byte aligned WGSL struct
+    pub _padding: [u32; 2],
 }
 
 impl Vertex4D {
     /// Create a new 4D vertex
     pub fn new(position: [f32; 4], color: [f32; 4]) -> Self {
-        Self { position, color }
+        Self {
+            position,
+            color,
+            material_id: 0,
+            material_index: 0,
+            _padding: [0; 2],
+        }
     }
 
     /// Create a vertex from position with default white color
     pub fn from_position(position: [f32; 4]) -> Self {
-        Self {
-            position,
-            color: [1.0, 1.0, 1.0, 1.0],
+        Self::new(position, [1.0, 1.0, 1.0, 1.0])
+    }
+
+    /// Set the shading pipeline id for this vertex
+    ///
+    /// Keeps the [`TRANSPARENT_MATERIAL_BIT`].
+    pub fn with_material_id(mut self, material_id: u16) -> Self {
+        self.material_id = (self.material_id & TRANSPARENT_MATERIAL_BIT) | material_id as u32;
+        self
+    }
+
+    /// Mark this vertex as part of a transparent entity
+    pub fn with_transparent(mut self, transparent: bool) -> Self {
+        if transparent {
+            self.material_id |= TRANSPARENT_MATERIAL_BIT;
+        } else {
+            self.material_id &= !TRANSPARENT_MATERIAL_BIT;
         }
+        self
+    }
+
+    /// Set the material buffer index for this vertex
+    pub fn with_material_index(mut self, material_index: u32) -> Self {
+        self.material_index = material_index;
+        self
     }
 }
 
@@ -78,8 +119,10 @@ pub struct Vertex3D {
     pub color: [f32; 4],
     /// Original W depth (for depth-based effects)
     pub w_depth: f32,
-    /// Padding to align to 16 bytes
-    pub _padding: f32,
+    /// Shading pipeline id of the source geometry, plus [`TRANSPARENT_MATERIAL_BIT`]
+    pub material_id: u32,
+    /// Material buffer index of the source entity
+    pub material_index: u32,
 }
 
 impl Default for Vertex3D {
@@ -89,21 +132,60 @@ impl Default for Vertex3D {
             normal: [0.0, 0.0, 1.0],
             color: [1.0; 4],
             w_depth: 0.0,
-            _padding: 0.0,
+            material_id: 0,
+            material_index: 0,
         }
     }
 }
 
+/// Per-entity lighting response, looked up by the render shader
+/// Layout: 16 bytes (must match render.wgsl Material)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct GpuMaterial {
+    /// Multiplier on the ambient light strength
+    pub ambient: f32,
+    /// Multiplier on the diffuse light strength
+    pub diffuse: f32,
+    /// Padding for 16-byte alignment
+    pub _padding: [f32; 2],
+}
+
+impl GpuMaterial {
+    /// Create a material entry with the given light multipliers
+    pub fn new(ambient: f32, diffuse: f32) -> Self {
+        Self {
+            ambient,
+            diffuse,
+            _padding: [0.0; 2],
+        }
+    }
+}
+
+impl Default for GpuMaterial {
+    fn default() -> Self {
+        Self::new(1.0, 1.0)
+    }
+}
+
 /// Parameters for the slice compute shader
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct SliceParams {
-    /// W-coordinate of the slicing hyperplane
+    /// Offset of the slicing hyperplane along `slice_normal`
+    ///
+    /// With the default normal this is the camera-space W of the slice.
     pub slice_w: f32,
     /// Number of tetrahedra to process
     pub tetrahedron_count: u32,
-    /// Padding for 16-byte alignment
-    pub _padding: [f32; 2],
+    /// Vertices within this W distance of the slice are treated as just above it
+    pub slice_epsilon: f32,
+    /// Half-width of the slab around the slice (0 = thin slice)
+    ///
+    /// When positive, geometry with `slice_w - slab_thickness <= w <=
+    /// slice_w + slab_thickness` is drawn as a projected volume instead of a
+    /// cross-section, fading towards the slab's bounding planes.
+    pub slab_thickness: f32,
     /// 4D camera rotation matrix (camera-local to world, needs transpose for view)
     pub camera_matrix: [[f32; 4]; 4],
     /// 3D camera eye position (for normal orientation in render pass)
@@ -112,6 +194,61 @@ pub struct SliceParams {
     pub _padding2: f32,
     /// 4D camera position (for translating geometry to camera space)
     pub camera_position: [f32; 4],
+    /// Unit normal of the slicing hyperplane in camera space
+    ///
+    /// Points with `dot(slice_normal, p) == slice_w` are on the slice. The
+    /// default (0, 0, 0, 1) gives the usual `w == slice_w` cross-section.
+    /// Other normals are rotated onto W before slicing (see
+    /// [`world_to_slice`](Self::world_to_slice)), so the output is the
+    /// cross-section seen face-on rather than squashed onto camera x/y/z.
+    pub slice_normal: [f32; 4],
+}
+
+impl SliceParams {
+    /// Default on-plane tolerance for [`slice_epsilon`](Self::slice_epsilon)
+    pub const DEFAULT_EPSILON: f32 = 1e-4;
+
+    /// Default [`slice_normal`](Self::slice_normal): slice along camera-space W
+    pub const DEFAULT_SLICE_NORMAL: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+
+    /// Alpha multiplier for slab geometry on the slab's bounding planes
+    ///
+    /// Fades linearly from 1 on the center plane. Must match
+    /// `SLAB_EDGE_ALPHA` in `slice_tetra.wgsl`.
+    pub const SLAB_EDGE_ALPHA: f32 = 0.25;
+
+    /// World→slice rotation: camera space turned so the slice normal is +W
+    ///
+    /// Slicing maps `p - camera_position` through this matrix, keeps points
+    /// at `w == slice_w` and outputs their x, y and z. With the default
+    /// normal it is the plain world→camera rotation.
+    pub fn world_to_slice(&self) -> mat4::Mat4 {
+        let normal = Vec4::new(self.slice_normal[0], self.slice_normal[1], self.slice_normal[2], self.slice_normal[3]);
+        mat4::mul(slice_basis(normal), mat4::transpose(self.camera_matrix))
+    }
+}
+
+/// Rotation taking `normal` to +W, turning only the plane of `normal` and W
+///
+/// Directions perpendicular to both are left alone, so the default W normal
+/// gives the identity. A normal of -W turns through the XW plane. Must match
+/// `slice_basis` in `slice_tetra.wgsl`.
+pub(crate) fn slice_basis(normal: Vec4) -> mat4::Mat4 {
+    let n = normal.normalized();
+    let (c, s) = (n.w, (n.x * n.x + n.y * n.y + n.z * n.z).sqrt());
+    let u = if s > 1e-6 { [n.x / s, n.y / s, n.z / s] } else { [1.0, 0.0, 0.0] };
+
+    // Column j is the image of axis j
+    let mut m = mat4::IDENTITY;
+    for (j, &u_j) in u.iter().enumerate() {
+        for (i, &u_i) in u.iter().enumerate() {
+            m[j][i] += (c - 1.0) * u_j * u_i;
+        }
+        m[j][3] = s * u_j;
+        m[3][j] = -s * u_j;
+    }
+    m[3][3] = c;
+    m
 }
 
 impl Default for SliceParams {
@@ -119,7 +256,8 @@ impl Default for SliceParams {
         Self {
             slice_w: 0.0,
             tetrahedron_count: 0,
-            _padding: [0.0; 2],
+            slice_epsilon: Self::DEFAULT_EPSILON,
+            slab_thickness: 0.0,
             camera_matrix: [
                 [1.0, 0.0, 0.0, 0.0],
                 [0.0, 1.0, 0.0, 0.0],
@@ -129,12 +267,52 @@ impl Default for SliceParams {
             camera_eye: [0.0, 0.0, 5.0],
             _padding2: 0.0,
             camera_position: [0.0, 0.0, 5.0, 0.0],
+            slice_normal: Self::DEFAULT_SLICE_NORMAL,
+        }
+    }
+}
+
+/// Maximum number of directional lights in [`RenderUniforms`]
+///
+/// Must match `MAX_LIGHTS` in `render.wgsl`.
+pub const MAX_LIGHTS: usize = 4;
+
+/// A directional light for the 3D rendering pass
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Light {
+    /// Direction towards the light (normalized in the shader)
+    pub direction: [f32; 3],
+    /// Light color
+    pub color: [f32; 3],
+    /// Brightness multiplier on the diffuse term
+    pub intensity: f32,
+}
+
+impl Light {
+    /// Create a white light of full intensity
+    pub fn new(direction: [f32; 3]) -> Self {
+        Self {
+            direction,
+            color: [1.0; 3],
+            intensity: 1.0,
         }
     }
+
+    /// Builder: set the color
+    pub fn with_color(mut self, color: [f32; 3]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Builder: set the intensity
+    pub fn with_intensity(mut self, intensity: f32) -> Self {
+        self.intensity = intensity;
+        self
+    }
 }
 
 /// Render uniforms for the 3D rendering pass
-/// Layout: 160 bytes total (must match render.wgsl RenderUniforms)
+/// Layout: 288 bytes total (must match render.wgsl RenderUniforms)
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct RenderUniforms {
@@ -142,9 +320,13 @@ pub struct RenderUniforms {
     pub view_matrix: [[f32; 4]; 4],
     /// Projection matrix (64 bytes)
     pub projection_matrix: [[f32; 4]; 4],
-    /// Light direction (normalized) + padding (16 bytes)
-    pub light_dir: [f32; 3],
-    pub _padding: f32,
+    /// Light directions, xyz with w unused (64 bytes)
+    pub light_directions: [[f32; 4]; MAX_LIGHTS],
+    /// Light colors, rgb with intensity in w (64 bytes)
+    pub light_colors: [[f32; 4]; MAX_LIGHTS],
+    /// Number of lights in use + padding (16 bytes)
+    pub light_count: u32,
+    pub _padding: [u32; 3],
     /// Lighting parameters (16 bytes)
     pub ambient_strength: f32,
     pub diffuse_strength: f32,
@@ -152,6 +334,28 @@ pub struct RenderUniforms {
     pub w_range: f32,
 }
 
+impl RenderUniforms {
+    /// Replace the lights, keeping the first [`MAX_LIGHTS`]
+    pub fn set_lights(&mut self, lights: &[Light]) {
+        let count = lights.len().min(MAX_LIGHTS);
+        self.light_directions = [[0.0; 4]; MAX_LIGHTS];
+        self.light_colors = [[0.0; 4]; MAX_LIGHTS];
+        for (i, light) in lights[..count].iter().enumerate() {
+            let [x, y, z] = light.direction;
+            let [r, g, b] = light.color;
+            self.light_directions[i] = [x, y, z, 0.0];
+            self.light_colors[i] = [r, g, b, light.intensity];
+        }
+        self.light_count = count as u32;
+    }
+
+    /// Builder: replace the lights
+    pub fn with_lights(mut self, lights: &[Light]) -> Self {
+        self.set_lights(lights);
+        self
+    }
+}
+
 impl Default for RenderUniforms {
     fn default() -> Self {
         Self {
@@ -167,13 +371,16 @@ impl Default for RenderUniforms {
                 [0.0, 0.0, 1.0, 0.0],
                 [0.0, 0.0, 0.0, 1.0],
             ],
-            light_dir: [0.5, 1.0, 0.3],
-            _padding: 0.0,
+            light_directions: [[0.0; 4]; MAX_LIGHTS],
+            light_colors: [[0.0; 4]; MAX_LIGHTS],
+            light_count: 0,
+            _padding: [0; 3],
             ambient_strength: 0.3,
             diffuse_strength: 0.7,
             w_color_strength: 0.5,
             w_range: 2.0,
         }
+        .with_lights(&[Light::new([0.5, 1.0, 0.3])])
     }
 }
 
@@ -198,8 +405,8 @@ mod tests {
 
     #[test]
     fn test_vertex4d_size() {
-        // 4 floats position + 4 floats color = 32 bytes
-        assert_eq!(size_of::<Vertex4D>(), 32);
+        // 4 floats position + 4 floats color + material id + material index + 2 u32 padding = 48 bytes
+        assert_eq!(size_of::<Vertex4D>(), 48);
     }
 
     #[test]
@@ -210,22 +417,72 @@ mod tests {
 
     #[test]
     fn test_vertex3d_size() {
-        // 3 floats position + 3 floats normal + 4 floats color + 1 float w_depth + 1 float padding
-        // = 12 floats = 48 bytes
-        assert_eq!(size_of::<Vertex3D>(), 48);
+        // 3 floats position + 3 floats normal + 4 floats color + 1 float w_depth + 1 u32 material id
+        // + 1 u32 material index = 13 words = 52 bytes
+        assert_eq!(size_of::<Vertex3D>(), 52);
+    }
+
+    #[test]
+    fn test_gpu_material_size() {
+        // 2 floats (ambient, diffuse) + 2 floats padding = 16 bytes
+        assert_eq!(size_of::<GpuMaterial>(), 16);
     }
 
     #[test]
     fn test_slice_params_size() {
-        // 1 float + 1 u32 + 2 floats padding + 16 floats matrix + 3 floats eye + 1 float padding + 4 floats position = 112 bytes
-        assert_eq!(size_of::<SliceParams>(), 112);
+        // 1 float + 1 u32 + 2 floats padding + 16 floats matrix + 3 floats eye + 1 float padding
+        // + 4 floats position + 4 floats slice normal = 128 bytes
+        assert_eq!(size_of::<SliceParams>(), 128);
     }
 
     #[test]
     fn test_render_uniforms_size() {
-        // 16 floats view_matrix + 16 floats projection_matrix + 3 floats light_dir + 1 padding
-        // + 4 floats (ambient, diffuse, w_color, w_range) = 40 floats = 160 bytes
-        assert_eq!(size_of::<RenderUniforms>(), 160);
+        // 16 floats view_matrix + 16 floats projection_matrix + 16 floats light directions
+        // + 16 floats light colors + 1 u32 light count + 3 padding
+        // + 4 floats (ambient, diffuse, w_color, w_range) = 72 words = 288 bytes
+        assert_eq!(size_of::<RenderUniforms>(), 288);
+    }
+
+    #[test]
+    fn test_render_uniforms_std140_offsets() {
+        use std::mem::offset_of;
+
+        // vec4 arrays and the scalar block after them must start on 16-byte boundaries
+        assert_eq!(offset_of!(RenderUniforms, light_directions), 128);
+        assert_eq!(offset_of!(RenderUniforms, light_colors), 192);
+        assert_eq!(offset_of!(RenderUniforms, light_count), 256);
+        assert_eq!(offset_of!(RenderUniforms, ambient_strength), 272);
+        assert_eq!(offset_of!(RenderUniforms, w_range), 284);
+    }
+
+    #[test]
+    fn test_render_uniforms_light_packing() {
+        let key = Light::new([0.5, 1.0, 0.3]);
+        let fill = Light::new([-0.6, -0.2, 0.4]).with_color([0.8, 0.9, 1.0]).with_intensity(0.35);
+        let uniforms = RenderUniforms::default().with_lights(&[key, fill]);
+
+        assert_eq!(uniforms.light_count, 2);
+        assert_eq!(uniforms.light_directions[1], [-0.6, -0.2, 0.4, 0.0]);
+        assert_eq!(uniforms.light_colors[1], [0.8, 0.9, 1.0, 0.35]);
+        assert_eq!(uniforms.light_colors[2], [0.0; 4]);
+
+        // Same words in the raw buffer the shader reads
+        let words: &[f32] = bytemuck::cast_slice(bytemuck::bytes_of(&uniforms));
+        assert_eq!(&words[36..40], &[-0.6, -0.2, 0.4, 0.0]);
+        assert_eq!(&words[52..56], &[0.8, 0.9, 1.0, 0.35]);
+        assert_eq!(bytemuck::cast_slice::<f32, u32>(&words[64..65]), &[2]);
+    }
+
+    #[test]
+    fn test_render_uniforms_default_is_single_light() {
+        let uniforms = RenderUniforms::default();
+        assert_eq!(uniforms.light_count, 1);
+        assert_eq!(uniforms.light_directions[0], [0.5, 1.0, 0.3, 0.0]);
+        assert_eq!(uniforms.light_colors[0], [1.0, 1.0, 1.0, 1.0]);
+
+        // Extra lights past MAX_LIGHTS are dropped
+        let many = RenderUniforms::default().with_lights(&[Light::new([0.0, 1.0, 0.0]); MAX_LIGHTS + 2]);
+        assert_eq!(many.light_count as usize, MAX_LIGHTS);
     }
 
     #[test]
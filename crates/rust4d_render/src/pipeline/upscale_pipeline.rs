@@ -0,0 +1,273 @@
+//! Dynamic resolution: render below the surface size, then upscale
+//!
+//! With a render scale below 1, the scene is drawn into a smaller offscreen
+//! target owned by [`UpscalePipeline`], which [`blit`](UpscalePipeline::blit)
+//! then stretches onto the surface with linear filtering. Slicing cost is
+//! unchanged, but fill rate drops with the square of the scale.
+
+/// Smallest supported render scale
+pub const MIN_RENDER_SCALE: f32 = 0.25;
+
+/// Size of the scene target for a `width` x `height` surface at `scale`
+///
+/// `scale` is clamped to [`MIN_RENDER_SCALE`]..=1, and each side to at least
+/// one pixel.
+pub fn scaled_size(width: u32, height: u32, scale: f32) -> (u32, u32) {
+    let scale = scale.clamp(MIN_RENDER_SCALE, 1.0);
+    let side = |n: u32| ((n as f32 * scale).round() as u32).max(1);
+    (side(width), side(height))
+}
+
+/// The offscreen scene target and the bind group sampling it
+struct ScaledTarget {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    bind_group: wgpu::BindGroup,
+}
+
+/// Upscales a reduced-resolution scene target onto the output
+pub struct UpscalePipeline {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    /// Format of both the scene target and the output
+    format: wgpu::TextureFormat,
+    target: Option<ScaledTarget>,
+}
+
+impl UpscalePipeline {
+    /// Create an upscale pipeline writing `format` outputs
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Upscale Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Upscale Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let shader_source = include_str!("../shaders/upscale.wgsl");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Upscale Shader"),
+            source: wgpu::ShaderSource::Wgsl(shader_source.into()),
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Upscale Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Upscale Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            sampler,
+            format,
+            target: None,
+        }
+    }
+
+    /// Ensure the scene target fits a `width` x `height` output at `scale`
+    ///
+    /// The target is only recreated when its size changes. Returns the
+    /// target size (see [`scaled_size`]).
+    pub fn ensure_target(&mut self, device: &wgpu::Device, width: u32, height: u32, scale: f32) -> (u32, u32) {
+        let (width, height) = scaled_size(width, height, scale);
+        if self.target_size() == Some((width, height)) {
+            return (width, height);
+        }
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Scaled Scene Texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Upscale Bind Group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+
+        self.target = Some(ScaledTarget { texture, view, bind_group });
+        (width, height)
+    }
+
+    /// Get the scene target to render into, once created
+    pub fn target_view(&self) -> Option<&wgpu::TextureView> {
+        self.target.as_ref().map(|t| &t.view)
+    }
+
+    /// Get the scene target texture, once created
+    pub fn target_texture(&self) -> Option<&wgpu::Texture> {
+        self.target.as_ref().map(|t| &t.texture)
+    }
+
+    /// Get the scene target size, once created
+    pub fn target_size(&self) -> Option<(u32, u32)> {
+        self.target.as_ref().map(|t| (t.texture.width(), t.texture.height()))
+    }
+
+    /// Stretch the scene target over all of `output`
+    ///
+    /// Does nothing before [`ensure_target`](Self::ensure_target) is called.
+    pub fn blit(&self, encoder: &mut wgpu::CommandEncoder, output: &wgpu::TextureView) {
+        let Some(target) = &self.target else { return };
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Upscale Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: output,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &target.bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::headless_device;
+    use crate::screenshot::read_texture;
+
+    #[test]
+    fn test_scaled_size() {
+        assert_eq!(scaled_size(1280, 720, 1.0), (1280, 720));
+        assert_eq!(scaled_size(1280, 720, 0.5), (640, 360));
+        assert_eq!(scaled_size(1281, 721, 0.5), (641, 361));
+
+        // Clamped to the supported range and at least one pixel
+        assert_eq!(scaled_size(1280, 720, 2.0), (1280, 720));
+        assert_eq!(scaled_size(1280, 720, 0.0), (320, 180));
+        assert_eq!(scaled_size(1, 1, 0.25), (1, 1));
+    }
+
+    #[test]
+    fn test_half_scale_target_upscales_to_full_frame() {
+        let Some((device, queue)) = headless_device(wgpu::Features::empty()) else {
+            return;
+        };
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let mut upscale = UpscalePipeline::new(&device, format);
+
+        // Half-size scene target for a 64x48 frame
+        assert_eq!(upscale.ensure_target(&device, 64, 48, 0.5), (32, 24));
+        assert_eq!(upscale.target_size(), Some((32, 24)));
+
+        let output = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Test Output"),
+            size: wgpu::Extent3d { width: 64, height: 48, depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let output_view = output.create_view(&wgpu::TextureViewDescriptor::default());
+
+        // Fill the scene target with one color, then upscale it
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: upscale.target_view().unwrap(),
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color { r: 1.0, g: 0.0, b: 0.0, a: 1.0 }),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        upscale.blit(&mut encoder, &output_view);
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let scene = read_texture(&device, &queue, upscale.target_texture().unwrap()).unwrap();
+        assert_eq!(scene.dimensions(), (32, 24));
+        let frame = read_texture(&device, &queue, &output).unwrap();
+        assert_eq!(frame.dimensions(), (64, 48));
+        assert!(frame.pixels().all(|p| p.0 == [255, 0, 0, 255]));
+
+        // Same size again keeps the existing target
+        assert_eq!(upscale.ensure_target(&device, 64, 48, 0.5), (32, 24));
+    }
+}
@@ -3,19 +3,100 @@
 //! This module converts the abstract shape data from rust4d_core into
 //! GPU-compatible vertex and tetrahedra buffers.
 
-use rust4d_core::{Entity, World, Material};
-use rust4d_math::Vec4;
-use crate::pipeline::{Vertex4D, GpuTetrahedron};
+use std::cmp::Ordering;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+
+use rust4d_core::{ConvexShape4D, DirtyFlags, Entity, EntityKey, World, Material};
+use rust4d_math::{Tetrahedron, Vec4};
+use crate::camera4d::Camera4D;
+use crate::pipeline::{slice_on_cpu, Vertex4D, GpuTetrahedron, GpuMaterial, SliceParams};
+
+/// Distance thresholds for picking each entity's level of detail
+///
+/// An entity's level is the number of thresholds its distance from the
+/// camera has reached, so `[20.0, 50.0]` draws everything within 20 units at
+/// full detail, up to 50 at level 1 and beyond that at level 2 (see
+/// [`ConvexShape4D::tetrahedra_at_lod`]).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LodSettings {
+    /// Where distances are measured from
+    pub camera_position: Vec4,
+    /// Ascending distances at which each coarser level starts
+    pub distances: Vec<f32>,
+}
+
+impl LodSettings {
+    /// Create LOD settings for a camera position
+    pub fn new(camera_position: Vec4, distances: Vec<f32>) -> Self {
+        Self {
+            camera_position,
+            distances,
+        }
+    }
+
+    /// Get the level of detail for a point
+    pub fn level_at(&self, position: Vec4) -> u32 {
+        let distance = (position - self.camera_position).length();
+        self.distances.iter().filter(|&&d| distance >= d).count() as u32
+    }
+}
 
 /// GPU-ready geometry collected from entities
 ///
 /// This struct holds the vertices and tetrahedra in a format ready for
-/// upload to GPU buffers.
+/// upload to GPU buffers. Vertices carry their entity's material id, which
+/// picks the pipeline that draws their cross-section. Every added
+/// entity also gets one [`GpuMaterial`] entry, and its vertices carry that
+/// entry's index so the render shader can light each entity differently.
 pub struct RenderableGeometry {
     /// Vertices with 4D positions and colors
     pub vertices: Vec<Vertex4D>,
     /// Tetrahedra as indices into the vertex buffer
     pub tetrahedra: Vec<GpuTetrahedron>,
+    /// Per-entity materials, indexed by each vertex's `material_index`
+    materials: Vec<GpuMaterial>,
+    /// Skip tetrahedra inside their shape so slices only show the surface
+    cancel_internal_faces: bool,
+    /// Close holes in the slice by capping its boundary loops
+    slice_capping: bool,
+    /// Vertex ranges of entities added with their world key
+    spans: Vec<EntitySpan>,
+    /// Level of detail selection for entities added afterwards (None = full detail)
+    lod: Option<LodSettings>,
+    /// Changes whenever the keyed entities or their bounds do
+    revision: u64,
+}
+
+/// Source of geometry revisions, shared so a rebuilt geometry never reuses one
+static NEXT_REVISION: AtomicU64 = AtomicU64::new(0);
+
+/// A revision no geometry has had yet
+fn next_revision() -> u64 {
+    NEXT_REVISION.fetch_add(1, AtomicOrdering::Relaxed)
+}
+
+/// The vertices one world entity contributed
+struct EntitySpan {
+    key: EntityKey,
+    first_vertex: usize,
+    vertex_count: usize,
+    lod_level: u32,
+    /// Whether the tetrahedra were added with flipped winding
+    mirrored: bool,
+    /// World-space bounds of the vertices, kept current as they move
+    min: Vec4,
+    max: Vec4,
+}
+
+impl EntitySpan {
+    /// Recompute the bounds from the span's vertices
+    fn refresh_bounds(&mut self, vertices: &[Vertex4D]) {
+        let mut positions = vertices[self.first_vertex..self.first_vertex + self.vertex_count]
+            .iter()
+            .map(|v| Vec4::new(v.position[0], v.position[1], v.position[2], v.position[3]));
+        let first = positions.next().unwrap_or(Vec4::ZERO);
+        (self.min, self.max) = positions.fold((first, first), |(min, max), p| (min.min_components(p), max.max_components(p)));
+    }
 }
 
 impl RenderableGeometry {
@@ -24,6 +105,12 @@ impl RenderableGeometry {
         Self {
             vertices: Vec::new(),
             tetrahedra: Vec::new(),
+            materials: Vec::new(),
+            cancel_internal_faces: false,
+            slice_capping: false,
+            spans: Vec::new(),
+            lod: None,
+            revision: next_revision(),
         }
     }
 
@@ -32,6 +119,12 @@ impl RenderableGeometry {
         Self {
             vertices: Vec::with_capacity(vertex_capacity),
             tetrahedra: Vec::with_capacity(tetrahedron_capacity),
+            materials: Vec::new(),
+            cancel_internal_faces: false,
+            slice_capping: false,
+            spans: Vec::new(),
+            lod: None,
+            revision: next_revision(),
         }
     }
 
@@ -67,12 +160,92 @@ impl RenderableGeometry {
         }
 
         let mut result = Self::with_capacity(total_vertices, total_tetrahedra);
-        for entity in world.iter() {
-            result.add_entity_with_color(entity, color_fn);
+        for (key, _) in world.iter_with_keys() {
+            result.add_keyed_entity_with_color(world, key, color_fn);
         }
         result
     }
 
+    /// Enable or disable internal face cancellation for entities added afterwards
+    ///
+    /// Solid triangulations (like the tesseract's Kuhn decomposition) contain
+    /// tetrahedra inside the shape, and their cross-sections show up as
+    /// diagonal faces inside the slice. When enabled, only tetrahedra on the
+    /// shape's boundary are kept, so the slice is just the cross-section's
+    /// surface. Shapes are assumed convex, as [`ConvexShape4D`] requires.
+    pub fn cancel_internal_faces(&mut self, enabled: bool) {
+        self.cancel_internal_faces = enabled;
+    }
+
+    /// Check if internal face cancellation is enabled
+    pub fn cancels_internal_faces(&self) -> bool {
+        self.cancel_internal_faces
+    }
+
+    /// Builder: cap open cross-sections of this geometry
+    ///
+    /// Geometry that does not enclose a volume, or whose slice loses
+    /// triangles at degenerate cuts, slices to a surface with holes. With
+    /// capping, the slice pipeline triangulates each hole's boundary loop (see
+    /// [`cap_slice`](crate::pipeline::cap_slice)); closed slices are unchanged.
+    pub fn with_slice_capping(mut self, enabled: bool) -> Self {
+        self.slice_capping = enabled;
+        self
+    }
+
+    /// Check if open cross-sections are capped
+    pub fn caps_slices(&self) -> bool {
+        self.slice_capping
+    }
+
+    /// Set the level of detail selection for entities added afterwards
+    ///
+    /// Levels are picked from each entity's position when it is added; use
+    /// [`lod_outdated`](Self::lod_outdated) to tell when the camera has moved
+    /// far enough that the geometry should be rebuilt.
+    pub fn set_lod(&mut self, lod: Option<LodSettings>) {
+        self.lod = lod;
+    }
+
+    /// Get the level of detail selection, if enabled
+    pub fn lod(&self) -> Option<&LodSettings> {
+        self.lod.as_ref()
+    }
+
+    /// Check if the keyed transparent entities are no longer back to front from `camera_eye`
+    ///
+    /// Transparent entities blend in the order they were added (see
+    /// [`sort_back_to_front`]), so the geometry should be rebuilt once the
+    /// camera has moved past one of them.
+    pub fn transparency_order_outdated(&self, world: &World, camera_eye: Vec4) -> bool {
+        let transparent: Vec<EntityKey> = self
+            .spans
+            .iter()
+            .map(|span| span.key)
+            .filter(|&key| world.get_entity(key).is_some_and(|entity| entity.material.is_transparent()))
+            .collect();
+        transparent
+            .windows(2)
+            .any(|pair| draw_order(world, pair[0], pair[1], camera_eye) == Ordering::Greater)
+    }
+
+    /// Check if any keyed entity would now get a different level of detail
+    ///
+    /// Keyed entities are measured at their world positions, so a child
+    /// counts as moved when its parent moves.
+    pub fn lod_outdated(&self, world: &World, lod: &LodSettings) -> bool {
+        self.spans.iter().any(|span| {
+            world
+                .world_transform(span.key)
+                .is_some_and(|transform| lod.level_at(transform.position) != span.lod_level)
+        })
+    }
+
+    /// Level of detail to add an entity at `position` with
+    fn lod_level(&self, position: Vec4) -> u32 {
+        self.lod.as_ref().map_or(0, |lod| lod.level_at(position))
+    }
+
     /// Add an entity's geometry to this collection
     ///
     /// Uses the entity's material base_color for all vertices.
@@ -80,10 +253,50 @@ impl RenderableGeometry {
         self.add_entity_with_color(entity, &default_color_fn);
     }
 
+    /// Add a world entity's geometry, remembering its key
+    ///
+    /// Entities added this way can later be moved in place by
+    /// [`update_transforms`](Self::update_transforms). The level of detail
+    /// is picked from the entity's world position. Does nothing if the key
+    /// is stale.
+    pub fn add_keyed_entity_with_color(&mut self, world: &World, key: EntityKey, color_fn: &dyn Fn(&Vec4, &Material) -> [f32; 4]) {
+        let (Some(entity), Some(transform)) = (world.get_entity(key), world.world_transform(key)) else {
+            return;
+        };
+        let lod_level = self.lod_level(transform.position);
+        let mut span = EntitySpan {
+            key,
+            first_vertex: self.vertices.len(),
+            vertex_count: entity.shape().vertex_count(),
+            lod_level,
+            mirrored: entity.transform.is_mirrored(),
+            min: Vec4::ZERO,
+            max: Vec4::ZERO,
+        };
+        self.add_entity_at_lod(entity, color_fn, lod_level);
+        span.refresh_bounds(&self.vertices);
+        self.spans.push(span);
+        self.revision = next_revision();
+    }
+
     /// Add an entity's geometry with a custom color function
+    ///
+    /// Vertices of an entity whose material is transparent are flagged for
+    /// the blended pass (see [`Vertex4D::with_transparent`]). A mirrored
+    /// transform reverses the winding of the entity's tetrahedra, so they are
+    /// added with two vertices swapped to keep their world-space orientation.
     pub fn add_entity_with_color(&mut self, entity: &Entity, color_fn: &dyn Fn(&Vec4, &Material) -> [f32; 4]) {
+        self.add_entity_at_lod(entity, color_fn, self.lod_level(entity.transform.position));
+    }
+
+    /// Add an entity's geometry using the tetrahedra of one level of detail
+    fn add_entity_at_lod(&mut self, entity: &Entity, color_fn: &dyn Fn(&Vec4, &Material) -> [f32; 4], lod_level: u32) {
         let shape = entity.shape();
         let vertex_offset = self.vertices.len();
+        let material_id = entity.material.material_id;
+        let material_index = self.materials.len() as u32;
+        let transparent = entity.material.is_transparent();
+        self.materials.push(GpuMaterial::new(entity.material.ambient, entity.material.diffuse));
 
         // Transform and add vertices
         for v in shape.vertices() {
@@ -92,24 +305,88 @@ impl RenderableGeometry {
             self.vertices.push(Vertex4D::new(
                 [world_pos.x, world_pos.y, world_pos.z, world_pos.w],
                 color,
-            ));
+            ).with_material_id(material_id).with_material_index(material_index).with_transparent(transparent));
         }
 
-        // Add tetrahedra with offset indices
-        for tet in shape.tetrahedra() {
+        let source = shape.tetrahedra_at_lod(lod_level);
+        let tetrahedra: Vec<&Tetrahedron> = if self.cancel_internal_faces {
+            source.iter().filter(|tet| is_boundary_tetrahedron(shape, tet)).collect()
+        } else {
+            source.iter().collect()
+        };
+
+        // Add tetrahedra with offset indices, flipping mirrored ones back
+        let (second, third) = if entity.transform.is_mirrored() { (2, 1) } else { (1, 2) };
+        for tet in tetrahedra {
             self.tetrahedra.push(GpuTetrahedron::from_indices([
                 (tet.indices[0] + vertex_offset) as u32,
-                (tet.indices[1] + vertex_offset) as u32,
-                (tet.indices[2] + vertex_offset) as u32,
+                (tet.indices[second] + vertex_offset) as u32,
+                (tet.indices[third] + vertex_offset) as u32,
                 (tet.indices[3] + vertex_offset) as u32,
             ]));
         }
     }
 
+    /// Re-transform the vertices of entities whose transform changed
+    ///
+    /// Tetrahedra and colors are kept, so this is much cheaper than a rebuild.
+    /// Returns false, leaving the geometry untouched, if any dirty entity
+    /// needs more than a transform update: its [`DirtyFlags::SHAPE`] or
+    /// [`DirtyFlags::MATERIAL`] is set, it was removed from the world, it
+    /// was mirrored or unmirrored (flipping the winding), or it was not
+    /// added with its key. The caller must then rebuild the geometry.
+    pub fn update_transforms(&mut self, world: &World) -> bool {
+        let rebuild = DirtyFlags::SHAPE | DirtyFlags::MATERIAL;
+        if world.entity_count() != self.spans.len() {
+            return false;
+        }
+        for span in &self.spans {
+            match world.get_entity(span.key) {
+                Some(entity)
+                    if !entity.dirty_flags().intersects(rebuild)
+                        && entity.transform.is_mirrored() == span.mirrored => {}
+                _ => return false,
+            }
+        }
+
+        for span in &mut self.spans {
+            let Some(entity) = world.get_entity(span.key) else { continue };
+            if !entity.dirty_flags().contains(DirtyFlags::TRANSFORM) {
+                continue;
+            }
+            let targets = &mut self.vertices[span.first_vertex..span.first_vertex + span.vertex_count];
+            for (vertex, local) in targets.iter_mut().zip(entity.shape().vertices()) {
+                let p = entity.transform.transform_point(*local);
+                vertex.position = [p.x, p.y, p.z, p.w];
+            }
+            span.refresh_bounds(&self.vertices);
+            self.revision = next_revision();
+        }
+        true
+    }
+
     /// Clear all geometry
     pub fn clear(&mut self) {
         self.vertices.clear();
         self.tetrahedra.clear();
+        self.materials.clear();
+        self.spans.clear();
+        self.revision = next_revision();
+    }
+
+    /// Get the revision of the keyed entities and their bounds
+    ///
+    /// Adding a keyed entity, moving one in place, or clearing the geometry
+    /// gives a new revision, unique across all geometries, so caches such as
+    /// [`VisibilityCache`](crate::visibility::VisibilityCache) can tell when
+    /// their result is stale, even after a rebuild.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Get the per-entity materials, in the order entities were added
+    pub fn materials(&self) -> &[GpuMaterial] {
+        &self.materials
     }
 
     /// Get the number of vertices
@@ -123,6 +400,63 @@ impl RenderableGeometry {
     pub fn tetrahedron_count(&self) -> usize {
         self.tetrahedra.len()
     }
+
+    /// Count the keyed entities whose bounds reach both sides of the camera's slice
+    ///
+    /// These are the entities that show up in the cross-section. The test
+    /// uses each entity's cached world-space bounds rather than its
+    /// vertices, so it is exact when the slice runs along world W and
+    /// conservative when the camera is rotated out of it. Entities added
+    /// without their key are not tracked and never counted.
+    pub fn entities_in_slice(&self, camera: &Camera4D) -> usize {
+        self.entity_keys_in_slice(camera).count()
+    }
+
+    /// Keys of the keyed entities counted by [`entities_in_slice`](Self::entities_in_slice)
+    pub fn entity_keys_in_slice(&self, camera: &Camera4D) -> impl Iterator<Item = EntityKey> + '_ {
+        let normal = camera.slice_normal.normalized();
+        let slice_w = camera.get_slice_w();
+        // The signed distance is linear in the world position, along this direction
+        let m = camera.slice_matrix();
+        let column = |c: usize| Vec4::new(m[c][0], m[c][1], m[c][2], m[c][3]).dot(normal);
+        let direction = Vec4::new(column(0), column(1), column(2), column(3));
+        let eye = camera.view_position();
+        self.spans
+            .iter()
+            .filter(move |span| {
+                let center = (span.min + span.max) * 0.5;
+                let half = (span.max - span.min) * 0.5;
+                let d = direction.dot(center - eye) - slice_w;
+                let reach = direction.x.abs() * half.x
+                    + direction.y.abs() * half.y
+                    + direction.z.abs() * half.z
+                    + direction.w.abs() * half.w;
+                d - reach < 0.0 && d + reach > 0.0
+            })
+            .map(|span| span.key)
+    }
+
+    /// Camera-space bounding box (min, max) of the cross-section `params` produces
+    ///
+    /// Slices the geometry on the CPU, so this is not free; call it when
+    /// framing the view rather than every frame. The tetrahedron count in
+    /// `params` is ignored and the whole geometry is sliced. Returns `None`
+    /// if nothing crosses the slice.
+    pub fn slice_bounds(&self, params: &SliceParams) -> Option<([f32; 3], [f32; 3])> {
+        let params = SliceParams {
+            tetrahedron_count: self.tetrahedra.len() as u32,
+            ..*params
+        };
+        let triangles = slice_on_cpu(&self.vertices, &self.tetrahedra, &params);
+        let (first, rest) = triangles.split_first()?;
+        Some(rest.iter().fold((first.position, first.position), |(mut min, mut max), v| {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(v.position[axis]);
+                max[axis] = max[axis].max(v.position[axis]);
+            }
+            (min, max)
+        }))
+    }
 }
 
 impl Default for RenderableGeometry {
@@ -131,6 +465,62 @@ impl Default for RenderableGeometry {
     }
 }
 
+/// Check if a tetrahedron lies on the boundary of a convex shape
+///
+/// A boundary tetrahedron spans a supporting hyperplane: every vertex of the
+/// shape is on one side of it. Interior (and degenerate) tetrahedra have
+/// vertices on both sides.
+fn is_boundary_tetrahedron(shape: &dyn ConvexShape4D, tet: &Tetrahedron) -> bool {
+    const EPSILON: f32 = 1e-4;
+
+    let vertices = shape.vertices();
+    let [p0, p1, p2, p3] = tet.indices.map(|i| vertices[i]);
+    let normal = Vec4::cross3(p1 - p0, p2 - p0, p3 - p0);
+    let len = normal.length();
+    if len < 1e-8 {
+        return false;
+    }
+    let normal = normal * (1.0 / len);
+
+    let (mut above, mut below) = (false, false);
+    for v in vertices {
+        let d = normal.dot(*v - p0);
+        above |= d > EPSILON;
+        below |= d < -EPSILON;
+    }
+    !(above && below)
+}
+
+/// Order two world entities for drawing as seen from `camera_eye`
+///
+/// Opaque entities come first, in no particular order. Transparent ones
+/// follow from farthest to nearest, by the distance of their world positions
+/// (so children are placed by their parents), and each blends over
+/// everything behind it. Entities missing from the world count as opaque.
+pub fn draw_order(world: &World, a: EntityKey, b: EntityKey, camera_eye: Vec4) -> Ordering {
+    let transparent = |key| world.get_entity(key).is_some_and(|entity| entity.material.is_transparent());
+    match (transparent(a), transparent(b)) {
+        (false, false) => Ordering::Equal,
+        (false, true) => Ordering::Less,
+        (true, false) => Ordering::Greater,
+        (true, true) => {
+            let distance = |key| {
+                world
+                    .world_transform(key)
+                    .map_or(0.0, |transform| (transform.position - camera_eye).length_squared())
+            };
+            distance(b).total_cmp(&distance(a))
+        }
+    }
+}
+
+/// Sort entities of `world` into [`draw_order`] from `camera_eye`
+///
+/// The sort is stable, so opaque entities keep their relative order.
+pub fn sort_back_to_front(world: &World, entities: &mut [(EntityKey, &Entity)], camera_eye: Vec4) {
+    entities.sort_by(|(a, _), (b, _)| draw_order(world, *a, *b, camera_eye));
+}
+
 /// Default color function - uses material's base_color for all vertices
 fn default_color_fn(_vertex: &Vec4, material: &Material) -> [f32; 4] {
     material.base_color
@@ -138,13 +528,14 @@ fn default_color_fn(_vertex: &Vec4, material: &Material) -> [f32; 4] {
 
 /// Color function that creates a gradient based on vertex position
 ///
-/// Maps each coordinate component to RGB channels.
-pub fn position_gradient_color(vertex: &Vec4, _material: &Material) -> [f32; 4] {
+/// Maps each coordinate component to RGB channels. Alpha comes from the
+/// material, so translucent materials stay translucent.
+pub fn position_gradient_color(vertex: &Vec4, material: &Material) -> [f32; 4] {
     [
         (vertex.x + 1.0) / 2.0, // Red from x
         (vertex.y + 1.0) / 2.0, // Green from y
         (vertex.z + 1.0) / 2.0, // Blue from z
-        1.0,
+        material.base_color[3],
     ]
 }
 
@@ -183,6 +574,64 @@ impl CheckerboardGeometry {
     }
 }
 
+/// A per-vertex color function, as taken by [`RenderableGeometry::add_entity_with_color`]
+pub type ColorFn<'a> = Box<dyn Fn(&Vec4, &Material) -> [f32; 4] + 'a>;
+
+/// Weighted blend of several color functions
+///
+/// Each layer is evaluated per vertex and the results are mixed by weight.
+/// Weights are normalized, so `0.7`/`0.3` and `7`/`3` blend the same way.
+///
+/// # Example
+/// ```ignore
+/// let blend = ColorBlend::new()
+///     .with_layer(0.7, position_gradient_color)
+///     .with_layer(0.3, checkerboard.color_fn());
+/// geometry.add_entity_with_color(entity, &blend.color_fn());
+/// ```
+#[derive(Default)]
+pub struct ColorBlend<'a> {
+    layers: Vec<(f32, ColorFn<'a>)>,
+}
+
+impl<'a> ColorBlend<'a> {
+    /// Create an empty blend (colors with the material's base color)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a color function with a blending weight
+    pub fn with_layer(mut self, weight: f32, color_fn: impl Fn(&Vec4, &Material) -> [f32; 4] + 'a) -> Self {
+        self.layers.push((weight, Box::new(color_fn)));
+        self
+    }
+
+    /// Evaluate the blended color for a vertex
+    ///
+    /// Falls back to the material's base color if there are no layers or the
+    /// weights sum to zero.
+    pub fn color(&self, vertex: &Vec4, material: &Material) -> [f32; 4] {
+        let total: f32 = self.layers.iter().map(|(weight, _)| weight).sum();
+        if total <= 0.0 {
+            return material.base_color;
+        }
+
+        let mut color = [0.0; 4];
+        for (weight, color_fn) in &self.layers {
+            let layer = color_fn(vertex, material);
+            for (c, l) in color.iter_mut().zip(layer) {
+                *c += l * weight / total;
+            }
+        }
+        color
+    }
+
+    /// Create a color function that evaluates this blend
+    pub fn color_fn(&self) -> impl Fn(&Vec4, &Material) -> [f32; 4] + '_ {
+        move |vertex, material| self.color(vertex, material)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -240,6 +689,48 @@ mod tests {
         assert_eq!(geom.vertex_count(), 32);
     }
 
+    #[test]
+    fn test_mirrored_entity_keeps_tetrahedron_orientation() {
+        // Signed volume of each tetrahedron against the entity's center
+        fn orientations(geom: &RenderableGeometry, center: Vec4) -> Vec<f32> {
+            let p = |i: u32| {
+                let [x, y, z, w] = geom.vertices[i as usize].position;
+                Vec4::new(x, y, z, w)
+            };
+            geom.tetrahedra
+                .iter()
+                .map(|t| {
+                    let a = p(t.v0);
+                    Vec4::cross3(p(t.v1) - a, p(t.v2) - a, p(t.v3) - a).dot(center - a)
+                })
+                .collect()
+        }
+
+        let original = entity_at(Vec4::new(-3.0, 0.0, 0.0, 1.0), 1.0);
+        let mut mirrored = entity_at(Vec4::ZERO, 1.0);
+        mirrored.transform = original.transform.reflected(Vec4::X);
+
+        let before = orientations(&RenderableGeometry::from_entity(&original), original.transform.position);
+        let after = orientations(&RenderableGeometry::from_entity(&mirrored), mirrored.transform.position);
+        assert_eq!(before.len(), after.len());
+        assert!(before.iter().zip(&after).all(|(b, a)| b * a >= 0.0));
+        assert!(before.iter().any(|b| b.abs() > 1e-4));
+    }
+
+    #[test]
+    fn test_mirroring_needs_rebuild() {
+        let mut world = World::new();
+        let key = world.add_entity(make_test_entity());
+        let mut geom = RenderableGeometry::new();
+        geom.add_keyed_entity_with_color(&world, key, &default_color_fn);
+        world.clear_all_dirty();
+
+        let entity = world.get_entity_mut(key).unwrap();
+        entity.transform = entity.transform.reflected(Vec4::Z);
+        entity.mark_dirty(DirtyFlags::TRANSFORM);
+        assert!(!geom.update_transforms(&world));
+    }
+
     #[test]
     fn test_renderable_geometry_clear() {
         let entity = make_test_entity();
@@ -251,6 +742,141 @@ mod tests {
         assert_eq!(geom.tetrahedron_count(), 0);
     }
 
+    #[test]
+    fn test_default_material_id_is_lit() {
+        let mut world = World::new();
+        world.add_entity(make_test_entity());
+        world.add_entity(make_test_entity());
+
+        let geom = RenderableGeometry::from_world(&world);
+        assert!(geom.vertices.iter().all(|v| v.material_id == Material::LIT_ID as u32));
+    }
+
+    #[test]
+    fn test_unlit_entities_keep_unlit_id() {
+        let unlit = Material::GREEN.with_material_id(Material::UNLIT_ID);
+        let mut world = World::new();
+        world.add_entity(make_test_entity());
+        world.add_entity(Entity::with_material(ShapeRef::shared(Tesseract4D::new(1.0)), unlit));
+        world.add_entity(make_test_entity());
+
+        let geom = RenderableGeometry::from_world(&world);
+        let ids: Vec<u32> = geom.vertices.iter().map(|v| v.material_id).collect();
+        let per_entity = ids.len() / 3;
+        assert!(ids[..per_entity].iter().all(|&id| id == Material::LIT_ID as u32));
+        assert!(ids[per_entity..2 * per_entity].iter().all(|&id| id == Material::UNLIT_ID as u32));
+        assert!(ids[2 * per_entity..].iter().all(|&id| id == Material::LIT_ID as u32));
+    }
+
+    #[test]
+    fn test_entities_get_distinct_material_indices() {
+        let floor = Material::GRAY.with_lighting(1.5, 0.2);
+        let cube = Material::RED.with_lighting(0.5, 1.0);
+        let mut geom = RenderableGeometry::new();
+        geom.add_entity(&Entity::with_material(ShapeRef::shared(Tesseract4D::new(4.0)), floor));
+        geom.add_entity(&Entity::with_material(ShapeRef::shared(Tesseract4D::new(1.0)), cube));
+
+        assert_eq!(geom.materials(), &[GpuMaterial::new(1.5, 0.2), GpuMaterial::new(0.5, 1.0)]);
+
+        // Each entity's tetrahedra only reference vertices with its own index
+        let half = geom.tetrahedron_count() / 2;
+        for (i, tet) in geom.tetrahedra.iter().enumerate() {
+            let expected = if i < half { 0 } else { 1 };
+            for v in [tet.v0, tet.v1, tet.v2, tet.v3] {
+                assert_eq!(geom.vertices[v as usize].material_index, expected);
+            }
+        }
+
+        geom.clear();
+        assert!(geom.materials().is_empty());
+    }
+
+    /// A tesseract at `position` with the given alpha
+    fn entity_at(position: Vec4, alpha: f32) -> Entity {
+        Entity::with_transform(
+            ShapeRef::shared(Tesseract4D::new(2.0)),
+            Transform4D::from_position(position),
+            Material::new(1.0, 0.5, 0.25, alpha),
+        )
+    }
+
+    #[test]
+    fn test_draw_order_puts_far_transparent_entities_first() {
+        let camera_eye = Vec4::new(0.0, 0.0, 5.0, 0.0);
+        let mut world = World::new();
+        let near = world.add_entity(entity_at(Vec4::new(0.0, 0.0, 2.0, 0.0), 0.5));
+        let far = world.add_entity(entity_at(Vec4::new(0.0, 0.0, -3.0, 0.0), 0.5));
+        let opaque = world.add_entity(entity_at(Vec4::new(0.0, 0.0, 4.0, 0.0), 1.0));
+
+        assert_eq!(draw_order(&world, far, near, camera_eye), Ordering::Less);
+        assert_eq!(draw_order(&world, near, far, camera_eye), Ordering::Greater);
+        // Opaque entities come first however close they are
+        assert_eq!(draw_order(&world, opaque, far, camera_eye), Ordering::Less);
+        assert_eq!(draw_order(&world, opaque, opaque, camera_eye), Ordering::Equal);
+
+        // From the other side the transparent order flips
+        let behind = Vec4::new(0.0, 0.0, -10.0, 0.0);
+        assert_eq!(draw_order(&world, far, near, behind), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_draw_order_uses_world_positions() {
+        let camera_eye = Vec4::new(0.0, 0.0, 5.0, 0.0);
+        let mut world = World::new();
+        let parent = world.add_entity(entity_at(Vec4::new(0.0, 0.0, -10.0, 0.0), 1.0));
+        // Locally the child is nearest the camera, but its parent puts it farthest away
+        let child = world.add_entity(entity_at(Vec4::new(0.0, 0.0, 4.0, 0.0), 0.5));
+        let other = world.add_entity(entity_at(Vec4::new(0.0, 0.0, 0.0, 0.0), 0.5));
+        world.add_child(parent, child).unwrap();
+
+        assert_eq!(draw_order(&world, child, other, camera_eye), Ordering::Less);
+    }
+
+    #[test]
+    fn test_sort_back_to_front() {
+        let mut world = World::new();
+        let near = world.add_entity(entity_at(Vec4::new(0.0, 0.0, 2.0, 0.0), 0.5));
+        let opaque = world.add_entity(entity_at(Vec4::ZERO, 1.0));
+        let far = world.add_entity(entity_at(Vec4::new(0.0, 0.0, -3.0, 0.0), 0.5));
+
+        let mut entities: Vec<_> = world.iter_with_keys().collect();
+        sort_back_to_front(&world, &mut entities, Vec4::new(0.0, 0.0, 5.0, 0.0));
+        let keys: Vec<EntityKey> = entities.iter().map(|(key, _)| *key).collect();
+        assert_eq!(keys, vec![opaque, far, near]);
+    }
+
+    #[test]
+    fn test_transparent_entities_flag_their_vertices() {
+        use crate::pipeline::TRANSPARENT_MATERIAL_BIT;
+
+        let mut geom = RenderableGeometry::new();
+        geom.add_entity(&entity_at(Vec4::ZERO, 1.0));
+        geom.add_entity(&entity_at(Vec4::ZERO, 0.5));
+        let (opaque, transparent) = geom.vertices.split_at(16);
+        assert!(opaque.iter().all(|v| v.material_id == Material::LIT_ID as u32));
+        assert!(transparent.iter().all(|v| v.material_id == Material::LIT_ID as u32 | TRANSPARENT_MATERIAL_BIT));
+        assert_eq!(transparent[0].color[3], 0.5);
+    }
+
+    #[test]
+    fn test_transparency_order_outdated() {
+        let mut world = World::new();
+        world.add_entity(entity_at(Vec4::new(0.0, 0.0, 2.0, 0.0), 0.5));
+        world.add_entity(entity_at(Vec4::new(0.0, 0.0, -3.0, 0.0), 0.5));
+        let front = Vec4::new(0.0, 0.0, 5.0, 0.0);
+
+        let mut entities: Vec<_> = world.iter_with_keys().collect();
+        sort_back_to_front(&world, &mut entities, front);
+        let mut geom = RenderableGeometry::new();
+        for (key, _) in entities {
+            geom.add_keyed_entity_with_color(&world, key, &default_color_fn);
+        }
+
+        assert!(!geom.transparency_order_outdated(&world, front));
+        // Walking around to the other side reverses which one is farther
+        assert!(geom.transparency_order_outdated(&world, Vec4::new(0.0, 0.0, -10.0, 0.0)));
+    }
+
     #[test]
     fn test_position_gradient_color() {
         let v = Vec4::new(1.0, 1.0, 1.0, 0.0);
@@ -262,6 +888,10 @@ mod tests {
         let v2 = Vec4::new(-1.0, -1.0, -1.0, 0.0);
         let color2 = position_gradient_color(&v2, &m);
         assert_eq!(color2, [0.0, 0.0, 0.0, 1.0]);
+
+        // Alpha follows the material
+        let glass = Material::new(1.0, 1.0, 1.0, 0.25);
+        assert_eq!(position_gradient_color(&v, &glass)[3], 0.25);
     }
 
     #[test]
@@ -281,6 +911,58 @@ mod tests {
         assert_eq!(c2, [0.0, 0.0, 0.0, 1.0]);
     }
 
+    fn solid(color: [f32; 4]) -> impl Fn(&Vec4, &Material) -> [f32; 4] {
+        move |_, _| color
+    }
+
+    #[test]
+    fn test_color_blend_zero_weight_layer_ignored() {
+        let blend = ColorBlend::new()
+            .with_layer(1.0, solid([1.0, 0.0, 0.0, 1.0]))
+            .with_layer(0.0, solid([0.0, 0.0, 1.0, 1.0]));
+        assert_eq!(blend.color(&Vec4::ZERO, &Material::WHITE), [1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_color_blend_even_mix() {
+        let blend = ColorBlend::new()
+            .with_layer(0.5, solid([1.0, 0.0, 0.0, 1.0]))
+            .with_layer(0.5, solid([0.0, 0.0, 1.0, 1.0]));
+        assert_eq!(blend.color(&Vec4::ZERO, &Material::WHITE), [0.5, 0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn test_color_blend_normalizes_weights() {
+        let blend = ColorBlend::new()
+            .with_layer(3.0, solid([1.0, 0.0, 0.0, 1.0]))
+            .with_layer(1.0, solid([0.0, 0.0, 1.0, 1.0]));
+        assert_eq!(blend.color(&Vec4::ZERO, &Material::WHITE), [0.75, 0.0, 0.25, 1.0]);
+    }
+
+    #[test]
+    fn test_color_blend_empty_uses_material() {
+        let blend = ColorBlend::new();
+        let material = Material::from_rgb(0.2, 0.4, 0.6);
+        assert_eq!(blend.color(&Vec4::ZERO, &material), material.base_color);
+    }
+
+    #[test]
+    fn test_color_blend_as_entity_color_fn() {
+        let entity = make_test_entity();
+        let blend = ColorBlend::new()
+            .with_layer(1.0, position_gradient_color)
+            .with_layer(1.0, solid([0.0, 0.0, 0.0, 1.0]));
+
+        let mut geometry = RenderableGeometry::new();
+        geometry.add_entity_with_color(&entity, &blend.color_fn());
+
+        // Half of the gradient, blended with black
+        let v = entity.shape().vertices()[0];
+        let gradient = position_gradient_color(&v, &entity.material);
+        let expected = [gradient[0] * 0.5, gradient[1] * 0.5, gradient[2] * 0.5, 1.0];
+        assert_eq!(geometry.vertices[0].color, expected);
+    }
+
     #[test]
     fn test_transform_applied() {
         let tesseract = Tesseract4D::new(2.0);
@@ -298,6 +980,216 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cancel_internal_faces_keeps_surface_only() {
+        use crate::pipeline::{slice_on_cpu, SliceParams};
+
+        let mut geom = RenderableGeometry::new();
+        assert!(!geom.cancels_internal_faces());
+        geom.cancel_internal_faces(true);
+        geom.add_entity(&make_test_entity());
+
+        let full = RenderableGeometry::from_entity(&make_test_entity());
+        assert!(geom.tetrahedron_count() < full.tetrahedron_count());
+
+        let params = SliceParams {
+            tetrahedron_count: geom.tetrahedron_count() as u32,
+            camera_eye: [0.0, 0.0, 5.0],
+            camera_position: [0.0; 4],
+            ..SliceParams::default()
+        };
+        let output = slice_on_cpu(&geom.vertices, &geom.tetrahedra, &params);
+        assert!(!output.is_empty());
+
+        // Every remaining triangle lies on a face of the [-1, 1]^3 cube
+        for tri in output.chunks(3) {
+            let on_face = (0..3).any(|axis| {
+                let c = tri[0].position[axis];
+                (c.abs() - 1.0).abs() < 1e-5
+                    && tri.iter().all(|v| (v.position[axis] - c).abs() < 1e-5)
+            });
+            assert!(on_face, "interior triangle {:?}", tri.iter().map(|v| v.position).collect::<Vec<_>>());
+        }
+    }
+
+    // --- Level of detail tests ---
+
+    #[test]
+    fn test_lod_level_at_distance() {
+        let lod = LodSettings::new(Vec4::ZERO, vec![10.0, 30.0]);
+        assert_eq!(lod.level_at(Vec4::new(5.0, 0.0, 0.0, 0.0)), 0);
+        assert_eq!(lod.level_at(Vec4::new(0.0, 0.0, 0.0, 10.0)), 1);
+        assert_eq!(lod.level_at(Vec4::new(0.0, 40.0, 0.0, 0.0)), 2);
+    }
+
+    #[test]
+    fn test_lod_nearest_entity_uses_full_detail() {
+        let mut world = World::new();
+        world.add_entity(make_test_entity());
+        let mut far = make_test_entity();
+        far.set_position(Vec4::new(50.0, 0.0, 0.0, 0.0));
+        world.add_entity(far);
+
+        let mut geom = RenderableGeometry::new();
+        geom.set_lod(Some(LodSettings::new(Vec4::ZERO, vec![20.0])));
+        for (key, _) in world.iter_with_keys() {
+            geom.add_keyed_entity_with_color(&world, key, &default_color_fn);
+        }
+
+        let shape = world.iter().next().unwrap().shape();
+        let full = shape.tetrahedron_count();
+        let coarse = shape.tetrahedra_at_lod(1).len();
+        assert!(coarse < full);
+        assert_eq!(geom.tetrahedron_count(), full + coarse);
+
+        // The near entity's tetrahedra come first and use all of them
+        let near = RenderableGeometry::from_entity(world.iter().next().unwrap());
+        let first: Vec<[u32; 4]> = geom.tetrahedra[..full].iter().map(|t| [t.v0, t.v1, t.v2, t.v3]).collect();
+        let expected: Vec<[u32; 4]> = near.tetrahedra.iter().map(|t| [t.v0, t.v1, t.v2, t.v3]).collect();
+        assert_eq!(first, expected);
+    }
+
+    #[test]
+    fn test_lod_outdated_when_camera_moves() {
+        let mut world = World::new();
+        world.add_entity(make_test_entity());
+
+        let mut geom = RenderableGeometry::new();
+        let near = LodSettings::new(Vec4::ZERO, vec![20.0]);
+        geom.set_lod(Some(near.clone()));
+        for (key, _) in world.iter_with_keys() {
+            geom.add_keyed_entity_with_color(&world, key, &default_color_fn);
+        }
+
+        assert!(!geom.lod_outdated(&world, &near));
+        let moved = LodSettings::new(Vec4::new(0.0, 0.0, 25.0, 0.0), vec![20.0]);
+        assert!(geom.lod_outdated(&world, &moved));
+    }
+
+    #[test]
+    fn test_lod_uses_world_positions() {
+        let mut world = World::new();
+        let mut parent = make_test_entity();
+        parent.set_position(Vec4::new(50.0, 0.0, 0.0, 0.0));
+        let parent = world.add_entity(parent);
+        let child = world.add_entity(make_test_entity());
+        world.add_child(parent, child).unwrap();
+
+        let lod = LodSettings::new(Vec4::ZERO, vec![20.0]);
+        let mut geom = RenderableGeometry::new();
+        geom.set_lod(Some(lod.clone()));
+        geom.add_keyed_entity_with_color(&world, child, &default_color_fn);
+
+        // The child sits at the origin locally but 50 units away in the world
+        let shape = world.get_entity(child).unwrap().shape();
+        assert_eq!(geom.tetrahedron_count(), shape.tetrahedra_at_lod(1).len());
+        assert!(!geom.lod_outdated(&world, &lod));
+
+        world.get_entity_mut(parent).unwrap().set_position(Vec4::ZERO);
+        assert!(geom.lod_outdated(&world, &lod));
+    }
+
+    // --- Incremental update tests ---
+
+    #[test]
+    fn test_update_transforms_moves_vertices_without_retetrahedralizing() {
+        let mut world = World::new();
+        let key = world.add_entity(make_test_entity());
+        let mut geom = RenderableGeometry::from_world(&world);
+        world.clear_all_dirty();
+        let tetrahedra: Vec<[u32; 4]> = geom.tetrahedra.iter().map(|t| [t.v0, t.v1, t.v2, t.v3]).collect();
+
+        world.get_entity_mut(key).unwrap().set_position(Vec4::new(10.0, 0.0, 0.0, 0.0));
+        assert!(geom.update_transforms(&world));
+
+        let rebuilt = RenderableGeometry::from_world(&world);
+        for (a, b) in geom.vertices.iter().zip(&rebuilt.vertices) {
+            assert_eq!(a.position, b.position);
+        }
+        let after: Vec<[u32; 4]> = geom.tetrahedra.iter().map(|t| [t.v0, t.v1, t.v2, t.v3]).collect();
+        assert_eq!(after, tetrahedra);
+    }
+
+    #[test]
+    fn test_update_transforms_refuses_shape_changes() {
+        let mut world = World::new();
+        let key = world.add_entity(make_test_entity());
+        let mut geom = RenderableGeometry::from_world(&world);
+        world.clear_all_dirty();
+
+        world.get_entity_mut(key).unwrap().set_shape(ShapeRef::owned(Tesseract4D::new(4.0)));
+        assert!(!geom.update_transforms(&world));
+
+        // A rebuild picks up the new shape
+        let rebuilt = RenderableGeometry::from_world(&world);
+        assert!(rebuilt.vertices.iter().any(|v| v.position[0] == 2.0));
+    }
+
+    #[test]
+    fn test_update_transforms_requires_keyed_entities() {
+        let mut world = World::new();
+        world.add_entity(make_test_entity());
+        let mut geom = RenderableGeometry::new();
+        geom.add_entity(world.iter().next().unwrap());
+
+        assert!(!geom.update_transforms(&world));
+    }
+
+    #[test]
+    fn test_entities_in_slice_skips_entities_off_the_slice() {
+        let mut world = World::new();
+        world.add_entity(make_test_entity());
+        let mut far = make_test_entity();
+        far.transform = Transform4D::from_position(Vec4::new(0.0, 0.0, 0.0, 5.0));
+        world.add_entity(far);
+        let geom = RenderableGeometry::from_world(&world);
+
+        let mut camera = Camera4D::new();
+        assert_eq!(geom.entities_in_slice(&camera), 1);
+
+        // Moving the slice to the far one's W swaps which one shows
+        camera.adjust_slice_offset(4.5);
+        assert_eq!(geom.entities_in_slice(&camera), 1);
+
+        // Unkeyed geometry is not tracked
+        assert_eq!(RenderableGeometry::from_entity(&make_test_entity()).entities_in_slice(&camera), 0);
+    }
+
+    #[test]
+    fn test_entities_in_slice_follows_moved_entities() {
+        let mut world = World::new();
+        let key = world.add_entity(make_test_entity());
+        let mut geom = RenderableGeometry::from_world(&world);
+        world.clear_all_dirty();
+        let camera = Camera4D::new();
+        assert_eq!(geom.entities_in_slice(&camera), 1);
+
+        // Moving it off the slice in place refreshes its cached bounds
+        world.get_entity_mut(key).unwrap().set_position(Vec4::new(0.0, 0.0, 0.0, 5.0));
+        assert!(geom.update_transforms(&world));
+        assert_eq!(geom.entities_in_slice(&camera), 0);
+    }
+
+    #[test]
+    fn test_slice_bounds_of_centered_tesseract_is_a_cube() {
+        let geom = RenderableGeometry::from_entity(&make_test_entity());
+        let params = SliceParams { camera_position: [0.0; 4], ..SliceParams::default() };
+
+        let (min, max) = geom.slice_bounds(&params).unwrap();
+        for axis in 0..3 {
+            assert!((min[axis] + 1.0).abs() < 1e-5, "min {:?}", min);
+            assert!((max[axis] - 1.0).abs() < 1e-5, "max {:?}", max);
+        }
+    }
+
+    #[test]
+    fn test_slice_bounds_empty_slice_is_none() {
+        let geom = RenderableGeometry::from_entity(&make_test_entity());
+        let params = SliceParams { camera_position: [0.0; 4], slice_w: 5.0, ..SliceParams::default() };
+        assert_eq!(geom.slice_bounds(&params), None);
+        assert_eq!(RenderableGeometry::new().slice_bounds(&SliceParams::default()), None);
+    }
+
     #[test]
     fn test_tetrahedra_indices_offset() {
         let mut geom = RenderableGeometry::new();
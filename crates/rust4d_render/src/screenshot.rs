@@ -0,0 +1,178 @@
+//! Reading rendered frames back from the GPU
+//!
+//! Texture-to-buffer copies must use rows padded to
+//! [`wgpu::COPY_BYTES_PER_ROW_ALIGNMENT`] (256 bytes), so [`read_texture`]
+//! copies into a padded buffer, waits for it to map, then strips the padding
+//! and converts BGRA surfaces to RGBA.
+
+pub use image::RgbaImage;
+
+/// Bytes per pixel of the 8-bit RGBA/BGRA formats screenshots support
+const BYTES_PER_PIXEL: u32 = 4;
+
+/// Error reading a texture back or saving it
+#[derive(Debug)]
+pub enum ScreenshotError {
+    /// The requested image has zero width or height
+    EmptySize,
+    /// The texture format is not 8-bit RGBA or BGRA
+    UnsupportedFormat(wgpu::TextureFormat),
+    /// The readback buffer could not be mapped
+    Map(wgpu::BufferAsyncError),
+    /// Encoding or writing the image failed
+    Image(image::ImageError),
+}
+
+impl From<wgpu::BufferAsyncError> for ScreenshotError {
+    fn from(e: wgpu::BufferAsyncError) -> Self {
+        ScreenshotError::Map(e)
+    }
+}
+
+impl From<image::ImageError> for ScreenshotError {
+    fn from(e: image::ImageError) -> Self {
+        ScreenshotError::Image(e)
+    }
+}
+
+impl std::fmt::Display for ScreenshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScreenshotError::EmptySize => write!(f, "Image has zero width or height"),
+            ScreenshotError::UnsupportedFormat(format) => write!(f, "Unsupported texture format: {:?}", format),
+            ScreenshotError::Map(e) => write!(f, "Buffer map error: {}", e),
+            ScreenshotError::Image(e) => write!(f, "Image error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ScreenshotError {}
+
+/// Row pitch of a texture-to-buffer copy, rounded up to the copy alignment
+pub fn padded_bytes_per_row(width: u32) -> u32 {
+    let unpadded = width * BYTES_PER_PIXEL;
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    unpadded.div_ceil(align) * align
+}
+
+/// Strip the per-row padding from a texture copy
+pub fn unpad_rows(data: &[u8], width: u32, height: u32, padded_bytes_per_row: u32) -> Vec<u8> {
+    let row = (width * BYTES_PER_PIXEL) as usize;
+    let mut pixels = Vec::with_capacity(row * height as usize);
+    for chunk in data.chunks(padded_bytes_per_row as usize).take(height as usize) {
+        pixels.extend_from_slice(&chunk[..row]);
+    }
+    pixels
+}
+
+/// Check if a format can be read back, and whether its channels are BGRA
+fn channel_order(format: wgpu::TextureFormat) -> Result<bool, ScreenshotError> {
+    use wgpu::TextureFormat::*;
+    match format {
+        Rgba8Unorm | Rgba8UnormSrgb => Ok(false),
+        Bgra8Unorm | Bgra8UnormSrgb => Ok(true),
+        other => Err(ScreenshotError::UnsupportedFormat(other)),
+    }
+}
+
+/// Copy a texture into an RGBA image
+///
+/// The texture needs [`wgpu::TextureUsages::COPY_SRC`]. This submits the copy
+/// and blocks on the device until the pixels are readable.
+pub fn read_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+) -> Result<RgbaImage, ScreenshotError> {
+    let bgra = channel_order(texture.format())?;
+    let (width, height) = (texture.width(), texture.height());
+    let padded_row = padded_bytes_per_row(width);
+
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Screenshot Readback Buffer"),
+        size: (padded_row * height) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Screenshot Encoder"),
+    });
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row),
+                rows_per_image: Some(height),
+            },
+        },
+        texture.size(),
+    );
+    queue.submit(std::iter::once(encoder.finish()));
+
+    let slice = buffer.slice(..);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    device.poll(wgpu::Maintain::Wait);
+    receiver
+        .recv()
+        .expect("map_async callback dropped without running")?;
+
+    let mut pixels = unpad_rows(&slice.get_mapped_range(), width, height, padded_row);
+    buffer.unmap();
+
+    if bgra {
+        for pixel in pixels.chunks_exact_mut(BYTES_PER_PIXEL as usize) {
+            pixel.swap(0, 2);
+        }
+    }
+    Ok(RgbaImage::from_raw(width, height, pixels).expect("pixel buffer matches image size"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_padded_bytes_per_row() {
+        assert_eq!(padded_bytes_per_row(64), 256);
+        assert_eq!(padded_bytes_per_row(65), 512);
+        assert_eq!(padded_bytes_per_row(1), 256);
+        assert_eq!(padded_bytes_per_row(1920), 7680);
+    }
+
+    #[test]
+    fn test_unpad_rows_drops_padding() {
+        // 3 pixels wide: 12 bytes of pixels per 256-byte row
+        let (width, height) = (3, 2);
+        let padded = padded_bytes_per_row(width);
+        let mut data = vec![0xAA; (padded * height) as usize];
+        for y in 0..height as usize {
+            for x in 0..12 {
+                data[y * padded as usize + x] = (y * 12 + x) as u8;
+            }
+        }
+
+        let pixels = unpad_rows(&data, width, height, padded);
+        assert_eq!(pixels, (0..24).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn test_channel_order() {
+        assert!(!channel_order(wgpu::TextureFormat::Rgba8UnormSrgb).unwrap());
+        assert!(channel_order(wgpu::TextureFormat::Bgra8Unorm).unwrap());
+        assert!(matches!(
+            channel_order(wgpu::TextureFormat::Rgba16Float),
+            Err(ScreenshotError::UnsupportedFormat(_))
+        ));
+    }
+}
@@ -5,8 +5,15 @@
 //
 // Features:
 // - W-depth visualization: red (+W) to blue (-W) gradient
-// - Basic diffuse lighting
+// - Diffuse lighting from up to MAX_LIGHTS directional lights (fs_main)
+//   or flat color (fs_unlit)
 // - Vertex color blending
+//
+// One render pipeline is built per material id. Each pipeline sets the
+// MATERIAL_ID override and drops triangles belonging to other materials.
+// Transparent entities carry TRANSPARENT_BIT in their material id and are
+// only kept by the transparent pass's vertex entry point (vs_transparent).
+// Lighting strengths are scaled per entity through the material buffer.
 
 // ============================================================================
 // Data Structures
@@ -18,6 +25,8 @@ struct VertexInput {
     @location(1) normal: vec3<f32>,
     @location(2) color: vec4<f32>,
     @location(3) w_depth: f32,
+    @location(4) material_id: u32,
+    @location(5) material_index: u32,
 }
 
 /// Vertex output to fragment shader
@@ -27,14 +36,30 @@ struct VertexOutput {
     @location(1) world_normal: vec3<f32>,
     @location(2) vertex_color: vec4<f32>,
     @location(3) w_depth: f32,
+    @location(4) @interpolate(flat) material_index: u32,
 }
 
+/// Per-entity lighting response
+struct Material {
+    ambient: f32,            // Multiplier on ambient_strength
+    diffuse: f32,            // Multiplier on diffuse_strength
+    _pad0: f32,
+    _pad1: f32,
+}
+
+/// Maximum number of directional lights (must match Rust MAX_LIGHTS)
+const MAX_LIGHTS: u32 = 4u;
+
 /// Render uniforms
 struct RenderUniforms {
     view_matrix: mat4x4<f32>,
     projection_matrix: mat4x4<f32>,
-    light_direction: vec3<f32>,
-    _pad0: f32,
+    light_directions: array<vec4<f32>, 4>,  // xyz direction towards the light
+    light_colors: array<vec4<f32>, 4>,      // rgb color, a intensity
+    light_count: u32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
     ambient_strength: f32,
     diffuse_strength: f32,
     w_color_strength: f32,   // How much W-depth affects color (0-1)
@@ -46,6 +71,13 @@ struct RenderUniforms {
 // ============================================================================
 
 @group(0) @binding(0) var<uniform> uniforms: RenderUniforms;
+@group(0) @binding(1) var<storage, read> materials: array<Material>;
+
+/// Material id handled by this pipeline
+override MATERIAL_ID: u32 = 0u;
+
+/// Flag in material_id marking transparent entities (TRANSPARENT_MATERIAL_BIT)
+const TRANSPARENT_BIT: u32 = 0x10000u;
 
 // ============================================================================
 // Vertex Shader
@@ -53,6 +85,20 @@ struct RenderUniforms {
 
 @vertex
 fn vs_main(input: VertexInput) -> VertexOutput {
+    return transform_vertex(input, MATERIAL_ID);
+}
+
+/// Vertex entry point of the transparent pass
+///
+/// A separate entry point rather than another override, since the GL backend
+/// caches programs without regard to override values.
+@vertex
+fn vs_transparent(input: VertexInput) -> VertexOutput {
+    return transform_vertex(input, MATERIAL_ID | TRANSPARENT_BIT);
+}
+
+/// Project a vertex, dropping it unless its material id is `material_id`
+fn transform_vertex(input: VertexInput, material_id: u32) -> VertexOutput {
     var output: VertexOutput;
 
     // Transform to clip space
@@ -60,6 +106,12 @@ fn vs_main(input: VertexInput) -> VertexOutput {
     let view_pos = uniforms.view_matrix * world_pos;
     output.clip_position = uniforms.projection_matrix * view_pos;
 
+    // Triangles for other materials or the other pass are drawn by their own
+    // pipeline; move them outside the clip volume so they are discarded here
+    if (input.material_id != material_id) {
+        output.clip_position = vec4<f32>(2.0, 2.0, 2.0, 1.0);
+    }
+
     // Pass through world position and normal for lighting
     output.world_position = input.position;
     output.world_normal = input.normal;
@@ -67,6 +119,7 @@ fn vs_main(input: VertexInput) -> VertexOutput {
     // Pass through color and W-depth
     output.vertex_color = input.color;
     output.w_depth = input.w_depth;
+    output.material_index = input.material_index;
 
     return output;
 }
@@ -105,15 +158,21 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
     // Normalize the interpolated normal
     let normal = normalize(input.world_normal);
 
-    // Normalize light direction
-    let light_dir = normalize(uniforms.light_direction);
+    // Sum diffuse lighting (Lambert) over the active lights
+    var lit = vec3<f32>(0.0);
+    let light_count = min(uniforms.light_count, MAX_LIGHTS);
+    for (var i: u32 = 0u; i < light_count; i++) {
+        let light_dir = normalize(uniforms.light_directions[i].xyz);
+        let n_dot_l = max(dot(normal, light_dir), 0.0);
+        let color = uniforms.light_colors[i];
+        lit += color.rgb * color.a * n_dot_l;
+    }
 
-    // Calculate diffuse lighting (Lambert)
-    let n_dot_l = max(dot(normal, light_dir), 0.0);
-    let diffuse = n_dot_l * uniforms.diffuse_strength;
+    let material = materials[input.material_index];
+    let diffuse = lit * uniforms.diffuse_strength * material.diffuse;
 
     // Total light contribution
-    let light = uniforms.ambient_strength + diffuse;
+    let light = vec3<f32>(uniforms.ambient_strength * material.ambient) + diffuse;
 
     // Get W-depth based color
     let w_color = w_depth_to_color(input.w_depth, uniforms.w_range);
@@ -128,3 +187,9 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
     // Output with original alpha
     return vec4<f32>(final_color, input.vertex_color.a);
 }
+
+@fragment
+fn fs_unlit(input: VertexOutput) -> @location(0) vec4<f32> {
+    // Flat vertex color, ignoring lighting and W-depth tinting
+    return input.vertex_color;
+}
@@ -5,11 +5,16 @@
 //
 // Algorithm:
 // 1. For each tetrahedron, transform vertices by camera matrix
-// 2. Compute which vertices are above the slice plane (case index 0-15)
+// 2. Compute which vertices are above the slice plane (case index 0-15);
+//    vertices within slice_epsilon of the plane count as just above it
 // 3. Use lookup tables to determine which edges are crossed
 // 4. Interpolate intersection points along crossed edges
 // 5. Generate 0-2 triangles from intersection points
 //
+// With a positive slab_thickness, step 2 onwards is replaced by clipping each
+// face to the slab around the slice and capping the volume with the
+// cross-sections at the slab's bounding planes (up to 16 triangles).
+//
 // Tetrahedra are simpler than 5-cells:
 // - Only 16 cases (4 vertices) instead of 32 (5 vertices)
 // - Maximum 4 intersection points (quad) instead of 6 (prism)
@@ -23,6 +28,8 @@
 struct Vertex4D {
     position: vec4<f32>,  // x, y, z, w
     color: vec4<f32>,     // r, g, b, a
+    material_id: u32,     // Shading pipeline id
+    material_index: u32,  // Entry in the render material buffer
 }
 
 /// A tetrahedron specified by 4 vertex indices
@@ -34,7 +41,7 @@ struct Tetrahedron {
 }
 
 /// A 3D triangle vertex for output
-/// Layout must match Rust Vertex3D: 48 bytes total (12 floats)
+/// Layout must match Rust Vertex3D: 52 bytes total (13 words)
 struct Vertex3D {
     pos_x: f32,
     pos_y: f32,
@@ -47,7 +54,8 @@ struct Vertex3D {
     color_b: f32,
     color_a: f32,
     w_depth: f32,
-    _padding: f32,
+    material_id: u32,
+    material_index: u32,
 }
 
 /// A 3D triangle (3 vertices)
@@ -57,16 +65,26 @@ struct Triangle3D {
     v2: Vertex3D,
 }
 
+/// The run of tetrahedra handled by one dispatch
+/// Layout must match Rust DispatchRange
+struct DispatchRange {
+    first: u32,
+    count: u32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
 /// Parameters for the slice operation
 struct SliceParams {
     slice_w: f32,
     tetrahedron_count: u32,
-    _pad0: f32,
-    _pad1: f32,
+    slice_epsilon: f32,          // On-plane tolerance (vertices this close count as above)
+    slab_thickness: f32,         // Half-width of the slab around the slice (0 = thin slice)
     camera_matrix: mat4x4<f32>,  // Camera-local to world (needs transpose for view)
     camera_eye: vec3<f32>,
     _pad2: f32,
     camera_position: vec4<f32>,  // 4D camera position
+    slice_normal: vec4<f32>,     // Camera-space normal of the slice hyperplane
 }
 
 // ============================================================================
@@ -79,6 +97,7 @@ struct SliceParams {
 @group(0) @binding(2) var<storage, read_write> triangles: array<Triangle3D>;
 @group(0) @binding(3) var<storage, read_write> triangle_count: atomic<u32>;
 @group(0) @binding(4) var<uniform> params: SliceParams;
+@group(0) @binding(5) var<uniform> range: DispatchRange;
 
 // ============================================================================
 // Constants - Tetrahedron Lookup Tables
@@ -138,35 +157,93 @@ const TETRA_TRI_TABLE: array<array<i32, 6>, 16> = array<array<i32, 6>, 16>(
     array<i32, 6>(-1, -1, -1, -1, -1, -1)  // Case 15
 );
 
+// Face definitions for a tetrahedron: face i is the one opposite vertex i
+const TETRA_FACE_V0: array<u32, 4> = array<u32, 4>(1u, 0u, 0u, 0u);
+const TETRA_FACE_V1: array<u32, 4> = array<u32, 4>(2u, 2u, 1u, 1u);
+const TETRA_FACE_V2: array<u32, 4> = array<u32, 4>(3u, 3u, 3u, 2u);
+
+// Alpha multiplier for slab geometry on the bounding planes (SliceParams::SLAB_EDGE_ALPHA)
+const SLAB_EDGE_ALPHA: f32 = 0.25;
+
+// A face clipped by two planes has at most 5 vertices
+const MAX_SLAB_POLYGON: u32 = 5u;
+
+// ============================================================================
+// Slab Data Structures
+// ============================================================================
+
+/// A polygon vertex while clipping a face to the slab
+struct SlabPoint {
+    position: vec4<f32>,
+    color: vec4<f32>,
+    dist: f32,            // Signed distance from the center plane
+}
+
+/// A convex polygon of up to MAX_SLAB_POLYGON points
+struct SlabPolygon {
+    points: array<SlabPoint, 5>,
+    count: u32,
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
-/// Transform a 4D world position to camera space
+/// Rotation taking `normal` to +W, turning only the plane of `normal` and W
+///
+/// Directions perpendicular to both are left alone, so the default W normal
+/// gives the identity. A normal of -W turns through the XW plane. Must match
+/// `slice_basis` in `types.rs`.
+fn slice_basis(normal: vec4<f32>) -> mat4x4<f32> {
+    let n = normalize(normal);
+    let c = n.w;
+    let s = length(n.xyz);
+    let u = select(vec3<f32>(1.0, 0.0, 0.0), n.xyz / s, s > 1e-6);
+
+    // Column j is the image of axis j
+    return mat4x4<f32>(
+        vec4<f32>(vec3<f32>(1.0, 0.0, 0.0) + (c - 1.0) * u.x * u, s * u.x),
+        vec4<f32>(vec3<f32>(0.0, 1.0, 0.0) + (c - 1.0) * u.y * u, s * u.y),
+        vec4<f32>(vec3<f32>(0.0, 0.0, 1.0) + (c - 1.0) * u.z * u, s * u.z),
+        vec4<f32>(-s * u, c),
+    );
+}
+
+/// Transform a 4D world position to slice space
 /// 1. Translate by -camera_position (move camera to origin)
-/// 2. Rotate by transpose(camera_matrix) (camera_matrix is camera→world, transpose gives world→camera)
-fn transform_to_camera_space(world_pos: vec4<f32>, camera_pos: vec4<f32>, camera_mat: mat4x4<f32>) -> vec4<f32> {
-    let relative_pos = world_pos - camera_pos;
-    // camera_mat transforms camera-local to world, so transpose transforms world to camera-local
-    return transpose(camera_mat) * relative_pos;
+/// 2. Rotate by world_to_slice: transpose(camera_matrix) (camera_matrix is
+///    camera→world, transpose gives world→camera), then slice_basis, so the
+///    slice normal lies along W
+fn transform_to_slice_space(world_pos: vec4<f32>, camera_pos: vec4<f32>, world_to_slice: mat4x4<f32>) -> vec4<f32> {
+    return world_to_slice * (world_pos - camera_pos);
+}
+
+/// Signed distance from the slice plane, nudged to +epsilon for on-plane vertices
+///
+/// `w` is the vertex's coordinate along the slice normal.
+///
+/// Crossed edges then always have endpoint distances of opposite sign and at
+/// least epsilon in size, so the interpolation below never divides by ~0.
+fn slice_distance(w: f32, slice_w: f32, epsilon: f32) -> f32 {
+    let d = w - slice_w;
+    return select(d, epsilon, abs(d) < epsilon);
 }
 
-/// Compute the intersection point on an edge
+/// Compute the intersection point on an edge from the endpoints' slice distances
 fn edge_intersection(
     p0: vec4<f32>,
     p1: vec4<f32>,
     c0: vec4<f32>,
     c1: vec4<f32>,
-    slice_w: f32
+    d0: f32,
+    d1: f32,
+    w_depth: f32
 ) -> Vertex3D {
-    let w0 = p0.w;
-    let w1 = p1.w;
-    let dw = w1 - w0;
-    let t = select((slice_w - w0) / dw, 0.5, abs(dw) < 0.0001);
-
-    let pos = mix(p0, p1, t);
-    let color = mix(c0, c1, t);
+    let t = clamp(d0 / (d0 - d1), 0.0, 1.0);
+    return make_vertex(mix(p0, p1, t), mix(c0, c1, t), w_depth);
+}
 
+fn make_vertex(pos: vec4<f32>, color: vec4<f32>, w_depth: f32) -> Vertex3D {
     var vertex: Vertex3D;
     vertex.pos_x = pos.x;
     vertex.pos_y = pos.y;
@@ -178,8 +255,9 @@ fn edge_intersection(
     vertex.color_g = color.g;
     vertex.color_b = color.b;
     vertex.color_a = color.a;
-    vertex.w_depth = slice_w;
-    vertex._padding = 0.0;
+    vertex.w_depth = w_depth;
+    vertex.material_id = 0u;
+    vertex.material_index = 0u;
     return vertex;
 }
 
@@ -206,47 +284,82 @@ fn compute_normal(p0: vec3<f32>, p1: vec3<f32>, p2: vec3<f32>) -> vec3<f32> {
     return n / len;
 }
 
-// ============================================================================
-// Main Compute Shader
-// ============================================================================
-
-@compute @workgroup_size(64)
-fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
-    let tet_idx = global_id.x;
+/// Orient a triangle towards the camera, set its normal and material, and output it
+fn emit_triangle(v0: Vertex3D, v1: Vertex3D, v2: Vertex3D, material_id: u32, material_index: u32) {
+    var tv0 = v0;
+    var tv1 = v1;
+    var tv2 = v2;
+
+    // Compute normal
+    let p0 = vertex_position(tv0);
+    let p1 = vertex_position(tv1);
+    let p2 = vertex_position(tv2);
+    var normal = compute_normal(p0, p1, p2);
+
+    // Ensure normals face toward the camera
+    // The normal should point in the same direction as the vector from triangle to camera
+    let tri_center = (p0 + p1 + p2) / 3.0;
+    let to_camera = params.camera_eye - tri_center;
+    if (dot(normal, to_camera) < 0.0) {
+        // Normal points away from camera, flip to face camera
+        let tmp = tv1;
+        tv1 = tv2;
+        tv2 = tmp;
+        normal = -normal;
+    }
 
-    if (tet_idx >= params.tetrahedron_count) {
+    tv0 = vertex_with_normal(tv0, normal);
+    tv1 = vertex_with_normal(tv1, normal);
+    tv2 = vertex_with_normal(tv2, normal);
+
+    // A tetrahedron belongs to a single entity, so any vertex carries its material
+    tv0.material_id = material_id;
+    tv1.material_id = material_id;
+    tv2.material_id = material_id;
+    tv0.material_index = material_index;
+    tv1.material_index = material_index;
+    tv2.material_index = material_index;
+
+    // Allocate output slot atomically
+    // Increment by 3 because DrawIndirect needs vertex count, not triangle count
+    let vertex_idx = atomicAdd(&triangle_count, 3u);
+    let output_idx = vertex_idx / 3u;
+
+    // Drop triangles past the end of the output, handing the slot back so the
+    // count never exceeds the capacity (the first slots all succeed, so the
+    // count only drops back to a value at or past the end)
+    if (output_idx >= arrayLength(&triangles)) {
+        atomicSub(&triangle_count, 3u);
         return;
     }
 
-    let tet = tetrahedra[tet_idx];
-    let slice_w = params.slice_w;
-    let camera_mat = params.camera_matrix;
-    let camera_pos = params.camera_position;
-
-    // Load and transform vertices to camera space
-    let v0 = vertices[tet.v0];
-    let v1 = vertices[tet.v1];
-    let v2 = vertices[tet.v2];
-    let v3 = vertices[tet.v3];
-
-    var pos: array<vec4<f32>, 4>;
-    pos[0] = transform_to_camera_space(v0.position, camera_pos, camera_mat);
-    pos[1] = transform_to_camera_space(v1.position, camera_pos, camera_mat);
-    pos[2] = transform_to_camera_space(v2.position, camera_pos, camera_mat);
-    pos[3] = transform_to_camera_space(v3.position, camera_pos, camera_mat);
+    triangles[output_idx].v0 = tv0;
+    triangles[output_idx].v1 = tv1;
+    triangles[output_idx].v2 = tv2;
+}
 
-    var col: array<vec4<f32>, 4>;
-    col[0] = v0.color;
-    col[1] = v1.color;
-    col[2] = v2.color;
-    col[3] = v3.color;
+/// Output the cross-section of one tetrahedron at the plane where `dist` is zero
+///
+/// `dist` must already be nudged away from zero by slice_distance.
+fn cut_tetrahedron(
+    pos_in: array<vec4<f32>, 4>,
+    col_in: array<vec4<f32>, 4>,
+    dist_in: array<f32, 4>,
+    w_depth: f32,
+    material_id: u32,
+    material_index: u32
+) {
+    // Local copies so they can be indexed dynamically
+    var pos = pos_in;
+    var col = col_in;
+    var dist = dist_in;
 
     // Compute case index (4 bits)
     var case_idx: u32 = 0u;
-    if (pos[0].w > slice_w) { case_idx |= 1u; }
-    if (pos[1].w > slice_w) { case_idx |= 2u; }
-    if (pos[2].w > slice_w) { case_idx |= 4u; }
-    if (pos[3].w > slice_w) { case_idx |= 8u; }
+    if (dist[0] > 0.0) { case_idx |= 1u; }
+    if (dist[1] > 0.0) { case_idx |= 2u; }
+    if (dist[2] > 0.0) { case_idx |= 4u; }
+    if (dist[3] > 0.0) { case_idx |= 8u; }
 
     // Skip if no intersection
     if (case_idx == 0u || case_idx == 15u) {
@@ -268,55 +381,182 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
             points[point_idx] = edge_intersection(
                 pos[ev0], pos[ev1],
                 col[ev0], col[ev1],
-                slice_w
+                dist[ev0], dist[ev1],
+                w_depth
             );
             point_idx++;
         }
     }
 
     // Output triangles
-    let tri_indices = TETRA_TRI_TABLE[case_idx];
-    let camera_eye = params.camera_eye;
-
+    var tri_indices = TETRA_TRI_TABLE[case_idx];
     for (var t: u32 = 0u; t < tri_count; t++) {
         let base = t * 3u;
-        let i0 = u32(tri_indices[base]);
-        let i1 = u32(tri_indices[base + 1u]);
-        let i2 = u32(tri_indices[base + 2u]);
-
-        var tv0 = points[i0];
-        var tv1 = points[i1];
-        var tv2 = points[i2];
-
-        // Compute normal
-        let p0 = vertex_position(tv0);
-        let p1 = vertex_position(tv1);
-        let p2 = vertex_position(tv2);
-        var normal = compute_normal(p0, p1, p2);
-
-        // Ensure normals face toward the camera
-        // The normal should point in the same direction as the vector from triangle to camera
-        let tri_center = (p0 + p1 + p2) / 3.0;
-        let to_camera = camera_eye - tri_center;
-        if (dot(normal, to_camera) < 0.0) {
-            // Normal points away from camera, flip to face camera
-            let tmp = tv1;
-            tv1 = tv2;
-            tv2 = tmp;
-            normal = -normal;
+        emit_triangle(
+            points[u32(tri_indices[base])],
+            points[u32(tri_indices[base + 1u])],
+            points[u32(tri_indices[base + 2u])],
+            material_id,
+            material_index
+        );
+    }
+}
+
+/// Alpha multiplier at signed distance `dist` from the center of the slab
+fn slab_fade(dist: f32, thickness: f32) -> f32 {
+    let t = min(abs(dist) / thickness, 1.0);
+    return 1.0 - (1.0 - SLAB_EDGE_ALPHA) * t;
+}
+
+/// Clip a convex polygon to the side of a bounding plane it should keep
+///
+/// Keeps points with `side * (dist - offset) >= 0`, interpolating new
+/// points where edges cross the plane (Sutherland-Hodgman).
+fn clip_polygon(polygon_in: SlabPolygon, offset: f32, side: f32) -> SlabPolygon {
+    var polygon = polygon_in;
+    var clipped: SlabPolygon;
+    clipped.count = 0u;
+
+    for (var i: u32 = 0u; i < polygon.count; i++) {
+        let a = polygon.points[i];
+        let b = polygon.points[(i + 1u) % polygon.count];
+        let sa = side * (a.dist - offset);
+        let sb = side * (b.dist - offset);
+        if (sa >= 0.0 && clipped.count < MAX_SLAB_POLYGON) {
+            clipped.points[clipped.count] = a;
+            clipped.count++;
         }
+        if ((sa >= 0.0) != (sb >= 0.0) && clipped.count < MAX_SLAB_POLYGON) {
+            let t = sa / (sa - sb);
+            var p: SlabPoint;
+            p.position = mix(a.position, b.position, t);
+            p.color = mix(a.color, b.color, t);
+            p.dist = mix(a.dist, b.dist, t);
+            clipped.points[clipped.count] = p;
+            clipped.count++;
+        }
+    }
+    return clipped;
+}
+
+/// Output the part of one tetrahedron that lies inside the slab
+///
+/// Each face is clipped to both bounding planes, and where a bounding plane
+/// cuts through the tetrahedron its cross-section closes the volume off.
+fn slab_tetrahedron(
+    pos_in: array<vec4<f32>, 4>,
+    col_in: array<vec4<f32>, 4>,
+    material_id: u32,
+    material_index: u32
+) {
+    var pos = pos_in;
+    var col = col_in;
+    let thickness = params.slab_thickness;
+    let slice_w = params.slice_w;
+
+    var dist: array<f32, 4>;
+    var all_above = true;
+    var all_below = true;
+    for (var i: u32 = 0u; i < 4u; i++) {
+        dist[i] = pos[i].w - slice_w;
+        all_above = all_above && dist[i] > thickness;
+        all_below = all_below && dist[i] < -thickness;
+    }
+    if (all_above || all_below) {
+        return;
+    }
+
+    for (var face: u32 = 0u; face < 4u; face++) {
+        var polygon: SlabPolygon;
+        polygon.count = 3u;
+        let corners = array<u32, 3>(TETRA_FACE_V0[face], TETRA_FACE_V1[face], TETRA_FACE_V2[face]);
+        for (var k: u32 = 0u; k < 3u; k++) {
+            let v = corners[k];
+            polygon.points[k].position = pos[v];
+            polygon.points[k].color = col[v];
+            polygon.points[k].dist = dist[v];
+        }
+        polygon = clip_polygon(polygon, -thickness, 1.0);
+        polygon = clip_polygon(polygon, thickness, -1.0);
+
+        // Fan-triangulate the clipped face
+        var fan: array<Vertex3D, 5>;
+        for (var k: u32 = 0u; k < polygon.count; k++) {
+            let p = polygon.points[k];
+            let color = vec4<f32>(p.color.rgb, p.color.a * slab_fade(p.dist, thickness));
+            fan[k] = make_vertex(p.position, color, slice_w + p.dist);
+        }
+        for (var k: u32 = 1u; k + 1u < polygon.count; k++) {
+            emit_triangle(fan[0], fan[k], fan[k + 1u], material_id, material_index);
+        }
+    }
+
+    // Caps on the bounding planes
+    var edge_col: array<vec4<f32>, 4>;
+    for (var i: u32 = 0u; i < 4u; i++) {
+        edge_col[i] = vec4<f32>(col[i].rgb, col[i].a * SLAB_EDGE_ALPHA);
+    }
+    let epsilon = params.slice_epsilon;
+    for (var c: u32 = 0u; c < 2u; c++) {
+        let offset = select(-thickness, thickness, c == 1u);
+        var cap_dist: array<f32, 4>;
+        for (var i: u32 = 0u; i < 4u; i++) {
+            cap_dist[i] = slice_distance(dist[i], offset, epsilon);
+        }
+        cut_tetrahedron(pos, edge_col, cap_dist, slice_w + offset, material_id, material_index);
+    }
+}
+
+// ============================================================================
+// Main Compute Shader
+// ============================================================================
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    if (global_id.x >= range.count) {
+        return;
+    }
+    let tet_idx = range.first + global_id.x;
 
-        tv0 = vertex_with_normal(tv0, normal);
-        tv1 = vertex_with_normal(tv1, normal);
-        tv2 = vertex_with_normal(tv2, normal);
+    if (tet_idx >= params.tetrahedron_count) {
+        return;
+    }
 
-        // Allocate output slot atomically
-        // Increment by 3 because DrawIndirect needs vertex count, not triangle count
-        let vertex_idx = atomicAdd(&triangle_count, 3u);
-        let output_idx = vertex_idx / 3u;
+    let tet = tetrahedra[tet_idx];
+    let slice_w = params.slice_w;
+    let world_to_slice = slice_basis(params.slice_normal) * transpose(params.camera_matrix);
+    let camera_pos = params.camera_position;
 
-        triangles[output_idx].v0 = tv0;
-        triangles[output_idx].v1 = tv1;
-        triangles[output_idx].v2 = tv2;
+    // Load and transform vertices to slice space
+    let v0 = vertices[tet.v0];
+    let v1 = vertices[tet.v1];
+    let v2 = vertices[tet.v2];
+    let v3 = vertices[tet.v3];
+
+    var pos: array<vec4<f32>, 4>;
+    pos[0] = transform_to_slice_space(v0.position, camera_pos, world_to_slice);
+    pos[1] = transform_to_slice_space(v1.position, camera_pos, world_to_slice);
+    pos[2] = transform_to_slice_space(v2.position, camera_pos, world_to_slice);
+    pos[3] = transform_to_slice_space(v3.position, camera_pos, world_to_slice);
+
+    var col: array<vec4<f32>, 4>;
+    col[0] = v0.color;
+    col[1] = v1.color;
+    col[2] = v2.color;
+    col[3] = v3.color;
+
+    if (params.slab_thickness > 0.0) {
+        slab_tetrahedron(pos, col, v0.material_id, v0.material_index);
+        return;
     }
+
+    // Signed distances from the slice (never within epsilon of zero)
+    let epsilon = params.slice_epsilon;
+    var dist: array<f32, 4>;
+    dist[0] = slice_distance(pos[0].w, slice_w, epsilon);
+    dist[1] = slice_distance(pos[1].w, slice_w, epsilon);
+    dist[2] = slice_distance(pos[2].w, slice_w, epsilon);
+    dist[3] = slice_distance(pos[3].w, slice_w, epsilon);
+
+    cut_tetrahedron(pos, col, dist, slice_w, v0.material_id, v0.material_index);
 }
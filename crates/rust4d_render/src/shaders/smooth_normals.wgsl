@@ -0,0 +1,288 @@
+// Smooth Normals Compute Shader
+//
+// Replaces the flat normals written by the slice shader with per-vertex
+// normals averaged over every triangle sharing the vertex's position.
+// Mirrors pipeline/smooth_normals.rs.
+//
+// Algorithm:
+// 1. insert_keys: each output vertex claims a hash table slot keyed by its
+//    quantized position and material index, and adds its position to its
+//    entity's center sum
+// 2. accumulate: each output vertex adds its flat normal, turned away from
+//    its entity's center and weighted by its triangle's corner angle, to its
+//    slot (fixed-point, since WGSL only has integer atomics)
+// 3. resolve: each vertex looks its slot up again and takes the normalized
+//    sum, turned back to the side its triangle faced, unless that differs
+//    too much from the flat normal (a crease)
+//
+// The table and centers are cleared before each frame. Every entry point strides over the
+// output, so a fixed dispatch covers any vertex count.
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+/// Layout must match Rust Vertex3D: 52 bytes total (13 words)
+struct Vertex3D {
+    pos_x: f32,
+    pos_y: f32,
+    pos_z: f32,
+    norm_x: f32,
+    norm_y: f32,
+    norm_z: f32,
+    color_r: f32,
+    color_g: f32,
+    color_b: f32,
+    color_a: f32,
+    w_depth: f32,
+    material_id: u32,
+    material_index: u32,
+}
+
+/// One hash table entry: a key fingerprint and a fixed-point normal sum
+struct NormalSlot {
+    key: atomic<u32>,     // 0 = empty
+    sum_x: atomic<i32>,
+    sum_y: atomic<i32>,
+    sum_z: atomic<i32>,
+}
+
+/// Fixed-point sum of one entity's output vertex positions
+struct EntityCenter {
+    sum_x: atomic<i32>,
+    sum_y: atomic<i32>,
+    sum_z: atomic<i32>,
+    count: atomic<u32>,
+}
+
+// ============================================================================
+// Buffers
+// ============================================================================
+
+@group(0) @binding(0) var<storage, read_write> vertices: array<Vertex3D>;
+@group(0) @binding(1) var<storage, read> vertex_count: u32;
+@group(0) @binding(2) var<storage, read_write> slots: array<NormalSlot>;
+@group(0) @binding(3) var<storage, read_write> centers: array<EntityCenter>;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+// Grid size positions are snapped to (NORMAL_POSITION_QUANTUM)
+const POSITION_QUANTUM: f32 = 1e-3;
+
+// Smallest dot product between smoothed and flat normal kept (SMOOTH_NORMAL_MIN_DOT)
+const MIN_DOT: f32 = 0.8;
+
+// Fixed-point scale of the normal sums
+const FIXED_SCALE: f32 = 65536.0;
+
+// Fixed-point scale of the entity center sums (coarse, so large scenes don't overflow)
+const CENTER_SCALE: f32 = 256.0;
+
+// Slots probed from the hashed one before giving up (the vertex stays flat)
+const MAX_PROBES: u32 = 32u;
+
+const WORKGROUP_SIZE: u32 = 64u;
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn vertex_position(v: Vertex3D) -> vec3<f32> {
+    return vec3<f32>(v.pos_x, v.pos_y, v.pos_z);
+}
+
+fn vertex_normal(v: Vertex3D) -> vec3<f32> {
+    return vec3<f32>(v.norm_x, v.norm_y, v.norm_z);
+}
+
+/// Number of vertices written by the slice pass that fit in the buffer
+fn output_count() -> u32 {
+    return min(vertex_count, arrayLength(&vertices));
+}
+
+/// Integer hash (lowbias32)
+fn hash(x: u32) -> u32 {
+    var h = x;
+    h ^= h >> 16u;
+    h *= 0x7feb352du;
+    h ^= h >> 15u;
+    h *= 0x846ca68bu;
+    h ^= h >> 16u;
+    return h;
+}
+
+/// Hash of a vertex's quantized position and material, mixed with `seed`
+fn vertex_hash(v: Vertex3D, seed: u32) -> u32 {
+    let q = vec3<i32>(round(vertex_position(v) / POSITION_QUANTUM));
+    var h = hash(seed ^ bitcast<u32>(q.x));
+    h = hash(h ^ bitcast<u32>(q.y));
+    h = hash(h ^ bitcast<u32>(q.z));
+    return hash(h ^ v.material_index);
+}
+
+/// Fingerprint telling apart vertices that hash to the same slot (never 0)
+fn vertex_key(v: Vertex3D) -> u32 {
+    return vertex_hash(v, 0x9e3779b9u) | 1u;
+}
+
+/// Add a vertex's key to the table, if it is not there yet
+///
+/// Linear probing with atomicMax instead of compare-exchange (which the GL
+/// backend cannot express): a larger key takes a slot and the key it
+/// displaced moves on along the probe sequence. Slots only ever grow, so a
+/// key is never stored twice. Keys pushed past MAX_PROBES are dropped.
+fn insert_key(v: Vertex3D) {
+    let capacity = arrayLength(&slots);
+    var key = vertex_key(v);
+    var index = vertex_hash(v, 0u) % capacity;
+    for (var probe: u32 = 0u; probe < MAX_PROBES; probe++) {
+        let old = atomicMax(&slots[index].key, key);
+        if (old == 0u || old == key) {
+            return;
+        }
+        key = min(key, old);
+        index = (index + 1u) % capacity;
+    }
+}
+
+/// Find the slot holding a vertex's key, or -1 if it was dropped
+fn find_slot(v: Vertex3D) -> i32 {
+    let capacity = arrayLength(&slots);
+    let key = vertex_key(v);
+    var index = vertex_hash(v, 0u) % capacity;
+    for (var probe: u32 = 0u; probe < MAX_PROBES; probe++) {
+        let current = atomicLoad(&slots[index].key);
+        if (current == key) {
+            return i32(index);
+        }
+        if (current == 0u) {
+            return -1;
+        }
+        index = (index + 1u) % capacity;
+    }
+    return -1;
+}
+
+/// Add a vertex's position to its entity's center sum
+fn add_to_center(v: Vertex3D) {
+    let entity = v.material_index;
+    if (entity >= arrayLength(&centers)) {
+        return;
+    }
+    let sum = vec3<i32>(round(vertex_position(v) * CENTER_SCALE));
+    atomicAdd(&centers[entity].sum_x, sum.x);
+    atomicAdd(&centers[entity].sum_y, sum.y);
+    atomicAdd(&centers[entity].sum_z, sum.z);
+    atomicAdd(&centers[entity].count, 1u);
+}
+
+/// 1 if the triangle starting at `base` faces away from its entity's center, else -1
+fn outward_sign(base: u32) -> f32 {
+    let v = vertices[base];
+    let entity = v.material_index;
+    if (entity >= arrayLength(&centers)) {
+        return 1.0;
+    }
+    let count = atomicLoad(&centers[entity].count);
+    if (count == 0u) {
+        return 1.0;
+    }
+    let sum = vec3<f32>(vec3<i32>(
+        atomicLoad(&centers[entity].sum_x),
+        atomicLoad(&centers[entity].sum_y),
+        atomicLoad(&centers[entity].sum_z)
+    ));
+    let center = sum / (f32(count) * CENTER_SCALE);
+    let tri_center = (vertex_position(v) + vertex_position(vertices[base + 1u]) + vertex_position(vertices[base + 2u])) / 3.0;
+    if (dot(vertex_normal(v), tri_center - center) < 0.0) {
+        return -1.0;
+    }
+    return 1.0;
+}
+
+/// Angle of the triangle corner at `p` between the edges to `a` and `b`
+fn corner_angle(p: vec3<f32>, a: vec3<f32>, b: vec3<f32>) -> f32 {
+    let e1 = a - p;
+    let e2 = b - p;
+    let lengths = sqrt(dot(e1, e1) * dot(e2, e2));
+    if (lengths < 1e-12) {
+        return 0.0;
+    }
+    return acos(clamp(dot(e1, e2) / lengths, -1.0, 1.0));
+}
+
+// ============================================================================
+// Entry Points
+// ============================================================================
+
+@compute @workgroup_size(64)
+fn insert_keys(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(num_workgroups) workgroups: vec3<u32>
+) {
+    let count = output_count();
+    let stride = workgroups.x * WORKGROUP_SIZE;
+    for (var i = global_id.x; i < count; i += stride) {
+        insert_key(vertices[i]);
+        add_to_center(vertices[i]);
+    }
+}
+
+@compute @workgroup_size(64)
+fn accumulate(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(num_workgroups) workgroups: vec3<u32>
+) {
+    let count = output_count();
+    let stride = workgroups.x * WORKGROUP_SIZE;
+    for (var i = global_id.x; i < count; i += stride) {
+        let v = vertices[i];
+        let base = i - i % 3u;
+        let corner = i - base;
+        let a = vertex_position(vertices[base + (corner + 1u) % 3u]);
+        let b = vertex_position(vertices[base + (corner + 2u) % 3u]);
+        let weight = corner_angle(vertex_position(v), a, b);
+
+        let slot = find_slot(v);
+        if (slot < 0) {
+            continue;
+        }
+        let sum = vec3<i32>(round(vertex_normal(v) * outward_sign(base) * weight * FIXED_SCALE));
+        atomicAdd(&slots[slot].sum_x, sum.x);
+        atomicAdd(&slots[slot].sum_y, sum.y);
+        atomicAdd(&slots[slot].sum_z, sum.z);
+    }
+}
+
+@compute @workgroup_size(64)
+fn resolve(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(num_workgroups) workgroups: vec3<u32>
+) {
+    let count = output_count();
+    let stride = workgroups.x * WORKGROUP_SIZE;
+    for (var i = global_id.x; i < count; i += stride) {
+        let v = vertices[i];
+        let slot = find_slot(v);
+        if (slot < 0) {
+            continue;
+        }
+        let sum = vec3<f32>(vec3<i32>(
+            atomicLoad(&slots[slot].sum_x),
+            atomicLoad(&slots[slot].sum_y),
+            atomicLoad(&slots[slot].sum_z)
+        ));
+        let len = length(sum);
+        if (len < 1e-6 * FIXED_SCALE) {
+            continue;
+        }
+        let averaged = sum * outward_sign(i - i % 3u) / len;
+        if (dot(averaged, vertex_normal(v)) >= MIN_DOT) {
+            vertices[i].norm_x = averaged.x;
+            vertices[i].norm_y = averaged.y;
+            vertices[i].norm_z = averaged.z;
+        }
+    }
+}
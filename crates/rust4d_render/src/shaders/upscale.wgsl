@@ -0,0 +1,42 @@
+// Upscale Blit Shader
+//
+// Stretches the scene, rendered below the surface resolution, onto the
+// surface. A single triangle covers the whole screen and samples the scene
+// texture with linear filtering.
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+// ============================================================================
+// Bindings
+// ============================================================================
+
+@group(0) @binding(0) var scene_texture: texture_2d<f32>;
+@group(0) @binding(1) var scene_sampler: sampler;
+
+// ============================================================================
+// Shaders
+// ============================================================================
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    // Corners (0, 0), (2, 0), (0, 2): a triangle covering the [0, 1] square
+    let corner = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+
+    var output: VertexOutput;
+    output.clip_position = vec4<f32>(corner * 2.0 - 1.0, 0.0, 1.0);
+    // Texture rows run top to bottom, clip space Y runs bottom to top
+    output.uv = vec2<f32>(corner.x, 1.0 - corner.y);
+    return output;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(scene_texture, scene_sampler, input.uv);
+}
@@ -0,0 +1,455 @@
+//! CPU cross-section slicing with caching
+//!
+//! [`slice_shape`] is a CPU port of the `slice_tetra.wgsl` compute pass for
+//! tools and headless rendering: it cuts every tetrahedron of a shape with
+//! the hyperplane `w = slice_w` using the same lookup tables as the GPU.
+//!
+//! [`cancel_internal_faces`] reduces a convex cross-section to its boundary
+//! surface, dropping the interior diagonal triangles of solid triangulations.
+//!
+//! [`SliceCache`] keeps the last result and returns it while the shape and
+//! slice W are unchanged, so static scenes are only sliced once.
+
+use std::sync::Arc;
+
+use rust4d_core::{DirtyFlags, Entity, ShapeRef};
+use rust4d_math::{ConvexShape4D, Vec4};
+use crate::pipeline::cpu_slice::slice_distance;
+use crate::pipeline::lookup_tables::{TETRA_EDGES, TETRA_EDGE_TABLE, TETRA_TRI_COUNT, TETRA_TRI_TABLE};
+use crate::pipeline::SliceParams;
+
+/// A cross-section triangle as three 3D (x, y, z) points
+pub type SliceTriangle = [[f32; 3]; 3];
+
+/// Slice a shape with the hyperplane `w = slice_w`
+///
+/// Vertices are used as-is (shape local space). Triangles are produced in the
+/// same order and winding as the GPU lookup tables, without camera-facing flips.
+/// Vertices on the slice are handled like the GPU with the default
+/// [`SliceParams::DEFAULT_EPSILON`].
+pub fn slice_shape(shape: &dyn ConvexShape4D, slice_w: f32) -> Vec<SliceTriangle> {
+    let vertices = shape.vertices();
+    let mut triangles = Vec::new();
+
+    for tet in shape.tetrahedra() {
+        let pos = tet.indices.map(|i| vertices[i]);
+        let dist = pos.map(|p| slice_distance(p.w, slice_w, SliceParams::DEFAULT_EPSILON));
+
+        let mut case_idx = 0usize;
+        for (i, d) in dist.iter().enumerate() {
+            if *d > 0.0 {
+                case_idx |= 1 << i;
+            }
+        }
+        if case_idx == 0 || case_idx == 15 {
+            continue;
+        }
+
+        // Intersection points for crossed edges, in edge index order
+        let edge_mask = TETRA_EDGE_TABLE[case_idx];
+        let mut points = [[0.0f32; 3]; 4];
+        let mut point_count = 0;
+        for (edge, &[a, b]) in TETRA_EDGES.iter().enumerate() {
+            if (edge_mask >> edge) & 1 == 1 {
+                points[point_count] = edge_intersection(pos[a], pos[b], dist[a], dist[b]);
+                point_count += 1;
+            }
+        }
+
+        let tri_indices = TETRA_TRI_TABLE[case_idx];
+        for t in 0..TETRA_TRI_COUNT[case_idx] as usize {
+            let base = t * 3;
+            triangles.push([
+                points[tri_indices[base] as usize],
+                points[tri_indices[base + 1] as usize],
+                points[tri_indices[base + 2] as usize],
+            ]);
+        }
+    }
+
+    triangles
+}
+
+/// Point where the edge p0-p1 crosses the slice, from the endpoints' slice
+/// distances (matches the shader)
+fn edge_intersection(p0: Vec4, p1: Vec4, d0: f32, d1: f32) -> [f32; 3] {
+    let t = (d0 / (d0 - d1)).clamp(0.0, 1.0);
+    let p = p0.lerp(p1, t);
+    [p.x, p.y, p.z]
+}
+
+/// Reduce the cross-section of a convex shape to its boundary surface
+///
+/// Triangles with cross-section points on both sides of their plane are
+/// interior and are dropped. The remaining triangles are matched by plane,
+/// and each face is re-triangulated from the convex hull of its points, so a
+/// face tiled by many small triangles becomes a minimal fan. Output triangles
+/// are wound counter-clockwise when seen from outside.
+pub fn cancel_internal_faces(triangles: &[SliceTriangle]) -> Vec<SliceTriangle> {
+    const EPSILON: f32 = 1e-4;
+
+    let points: Vec<[f32; 3]> = triangles.iter().flatten().copied().collect();
+
+    // Boundary planes (outward normal, offset) with the points on each
+    let mut faces: Vec<([f32; 3], f32, Vec<[f32; 3]>)> = Vec::new();
+    for tri in triangles {
+        let n = cross3(sub3(tri[1], tri[0]), sub3(tri[2], tri[0]));
+        let len = dot3(n, n).sqrt();
+        if len < 1e-8 {
+            continue; // Degenerate
+        }
+        let mut normal = n.map(|c| c / len);
+        let mut offset = dot3(normal, tri[0]);
+
+        let (mut above, mut below) = (false, false);
+        for p in &points {
+            let d = dot3(normal, *p) - offset;
+            above |= d > EPSILON;
+            below |= d < -EPSILON;
+        }
+        if above && below {
+            continue; // Interior
+        }
+        if above {
+            normal = normal.map(|c| -c);
+            offset = -offset;
+        }
+
+        let face = faces.iter_mut().find(|(n, d, _)| {
+            dot3(*n, normal) > 1.0 - EPSILON && (d - offset).abs() < EPSILON
+        });
+        match face {
+            Some((_, _, face_points)) => face_points.extend_from_slice(tri),
+            None => faces.push((normal, offset, tri.to_vec())),
+        }
+    }
+
+    let mut result = Vec::new();
+    for (normal, _, face_points) in faces {
+        let hull = face_hull(normal, &face_points);
+        for i in 1..hull.len().saturating_sub(1) {
+            result.push([hull[0], hull[i], hull[i + 1]]);
+        }
+    }
+    result
+}
+
+/// Convex hull of coplanar points, counter-clockwise around `normal`
+fn face_hull(normal: [f32; 3], points: &[[f32; 3]]) -> Vec<[f32; 3]> {
+    // In-plane basis with u x v = normal
+    let helper = if normal[0].abs() < 0.9 { [1.0, 0.0, 0.0] } else { [0.0, 1.0, 0.0] };
+    let u = cross3(helper, normal);
+    let u = u.map(|c| c / dot3(u, u).sqrt());
+    let v = cross3(normal, u);
+
+    let mut projected: Vec<(f32, f32, [f32; 3])> =
+        points.iter().map(|p| (dot3(*p, u), dot3(*p, v), *p)).collect();
+    projected.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+
+    // Andrew's monotone chain: lower hull, then upper hull over the reversed
+    // points. Collinear and duplicate points are dropped.
+    let mut hull: Vec<(f32, f32, [f32; 3])> = Vec::new();
+    for _ in 0..2 {
+        let start = hull.len();
+        for p in &projected {
+            while hull.len() >= start + 2 && turn(hull[hull.len() - 2], hull[hull.len() - 1], *p) <= 1e-8 {
+                hull.pop();
+            }
+            hull.push(*p);
+        }
+        hull.pop();
+        projected.reverse();
+    }
+    hull.into_iter().map(|(_, _, p)| p).collect()
+}
+
+/// Cross product z of (a - o) x (b - o) for projected points
+fn turn(o: (f32, f32, [f32; 3]), a: (f32, f32, [f32; 3]), b: (f32, f32, [f32; 3])) -> f32 {
+    (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+}
+
+fn sub3(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot3(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross3(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Shape and slice W the cached triangles were computed for
+///
+/// Holding the `Arc` keeps the shape alive, so its address can't be reused
+/// by another shape, and keeps it shared, so it can't be mutated in place.
+struct SliceKey {
+    shape: Arc<dyn ConvexShape4D>,
+    slice_w_bits: u32,
+}
+
+impl SliceKey {
+    fn matches(&self, shape: &Arc<dyn ConvexShape4D>, slice_w: f32) -> bool {
+        Arc::ptr_eq(&self.shape, shape) && self.slice_w_bits == slice_w.to_bits()
+    }
+}
+
+/// Cache of the last CPU slice result
+///
+/// Keyed on the shape's `Arc` and the exact slice W.
+#[derive(Default)]
+pub struct SliceCache {
+    /// Key for the cached triangles (None = invalid)
+    key: Option<SliceKey>,
+    /// Cached cross-section triangles
+    triangles: Vec<SliceTriangle>,
+    /// Number of times the slice has been recomputed
+    recompute_count: u64,
+}
+
+impl SliceCache {
+    /// Create an empty (invalid) cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the cross-section of a shape at `slice_w`, slicing only if needed
+    pub fn slice(&mut self, shape: &Arc<dyn ConvexShape4D>, slice_w: f32) -> &[SliceTriangle] {
+        if !self.key.as_ref().is_some_and(|key| key.matches(shape, slice_w)) {
+            self.triangles = slice_shape(shape.as_ref(), slice_w);
+            self.key = Some(SliceKey { shape: Arc::clone(shape), slice_w_bits: slice_w.to_bits() });
+            self.recompute_count += 1;
+        }
+        &self.triangles
+    }
+
+    /// Get the cross-section of an entity's shape (in shape local space) at `slice_w`
+    ///
+    /// An entity with [`DirtyFlags::SHAPE`] set is always re-sliced. Only
+    /// shared shapes are cached; an owned shape has no identity that outlives
+    /// it, so it is sliced on every call.
+    pub fn slice_entity(&mut self, entity: &Entity, slice_w: f32) -> &[SliceTriangle] {
+        if entity.dirty_flags().contains(DirtyFlags::SHAPE) {
+            self.invalidate();
+        }
+        match &entity.shape {
+            ShapeRef::Shared(shape) => self.slice(shape, slice_w),
+            ShapeRef::Owned(shape) => {
+                self.triangles = slice_shape(shape.as_ref(), slice_w);
+                self.key = None;
+                self.recompute_count += 1;
+                &self.triangles
+            }
+        }
+    }
+
+    /// Force the next query to re-slice
+    pub fn invalidate(&mut self) {
+        self.key = None;
+    }
+
+    /// Check if the cache currently holds a result
+    pub fn is_valid(&self) -> bool {
+        self.key.is_some()
+    }
+
+    /// Number of times the slice has been recomputed
+    pub fn recompute_count(&self) -> u64 {
+        self.recompute_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust4d_math::Tesseract4D;
+
+    #[test]
+    fn test_slice_tesseract_through_center() {
+        let tesseract = Tesseract4D::new(2.0);
+        let triangles = slice_shape(&tesseract, 0.0);
+        assert!(!triangles.is_empty());
+
+        // Every point of the cross-section lies on the 3D cube [-1, 1]^3
+        for point in triangles.iter().flatten() {
+            for c in point {
+                assert!(c.abs() <= 1.0 + 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_slice_exactly_through_vertices() {
+        use rust4d_math::SixteenCell4D;
+
+        let tesseract = Tesseract4D::new(2.0);
+        let sixteen_cell = SixteenCell4D::new(1.0);
+        let cases: [(&dyn ConvexShape4D, f32); 3] =
+            [(&tesseract, 1.0), (&tesseract, -1.0), (&sixteen_cell, 0.0)];
+
+        for (shape, slice_w) in cases {
+            for tri in slice_shape(shape, slice_w) {
+                assert!(tri.iter().flatten().all(|c| c.is_finite() && c.abs() <= 1.0 + 1e-5));
+            }
+        }
+
+        // The 16-cell has six vertices on w = 0: the slice is its octahedron surface
+        let octahedron = slice_shape(&sixteen_cell, 0.0);
+        assert!(!octahedron.is_empty());
+        for tri in &octahedron {
+            // Every point lies on the |x| + |y| + |z| = 1 surface
+            for p in tri {
+                assert!((p[0].abs() + p[1].abs() + p[2].abs() - 1.0).abs() < 1e-3, "{:?}", p);
+            }
+            let n = cross3(sub3(tri[1], tri[0]), sub3(tri[2], tri[0]));
+            assert!(dot3(n, n) > 0.0, "degenerate triangle {:?}", tri);
+        }
+    }
+
+    #[test]
+    fn test_slice_hypersphere() {
+        use rust4d_math::Hypersphere4D;
+
+        let sphere = Hypersphere4D::with_subdivisions(2.0, 3);
+
+        // Inside: a closed surface close to the sphere of radius sqrt(r² - w²)
+        let expected = (4.0f32 - 1.0).sqrt();
+        let triangles = slice_shape(&sphere, 1.0);
+        assert!(triangles.len() > 100);
+        for p in triangles.iter().flatten() {
+            let r = dot3(*p, *p).sqrt();
+            assert!(r <= expected + 1e-4 && r > expected * 0.9, "radius {} vs {}", r, expected);
+        }
+
+        // At the pole: collapses to a point
+        for p in slice_shape(&sphere, 2.0).iter().flatten() {
+            assert!(dot3(*p, *p).sqrt() < 1e-2, "{:?}", p);
+        }
+
+        // Outside: nothing
+        assert!(slice_shape(&sphere, 2.5).is_empty());
+    }
+
+    #[test]
+    fn test_slice_outside_shape_is_empty() {
+        let tesseract = Tesseract4D::new(2.0);
+        assert!(slice_shape(&tesseract, 5.0).is_empty());
+        assert!(slice_shape(&tesseract, -5.0).is_empty());
+    }
+
+    #[test]
+    fn test_cancel_internal_faces_leaves_cube_surface() {
+        let tesseract = Tesseract4D::new(2.0);
+        let triangles = slice_shape(&tesseract, 0.0);
+        let surface = cancel_internal_faces(&triangles);
+
+        assert!(triangles.len() > 12);
+        assert_eq!(surface.len(), 12);
+
+        let mut area = 0.0;
+        for tri in &surface {
+            // Each triangle lies on one face of the [-1, 1]^3 cube...
+            let axis = (0..3)
+                .find(|&a| tri.iter().all(|p| (p[a] - tri[0][a]).abs() < 1e-5 && (p[a].abs() - 1.0).abs() < 1e-5))
+                .expect("triangle not on a cube face");
+            // ...and is wound to face outward
+            let n = cross3(sub3(tri[1], tri[0]), sub3(tri[2], tri[0]));
+            assert!(n[axis] * tri[0][axis] > 0.0);
+            area += dot3(n, n).sqrt() / 2.0;
+        }
+        assert!((area - 24.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_cancel_internal_faces_empty() {
+        assert!(cancel_internal_faces(&[]).is_empty());
+    }
+
+    fn shared(size: f32) -> Arc<dyn ConvexShape4D> {
+        Arc::new(Tesseract4D::new(size))
+    }
+
+    #[test]
+    fn test_same_w_returns_cached_result() {
+        let tesseract = shared(2.0);
+        let mut cache = SliceCache::new();
+
+        let first = cache.slice(&tesseract, 0.25).to_vec();
+        let second = cache.slice(&tesseract, 0.25).to_vec();
+
+        assert_eq!(first, second);
+        assert_eq!(cache.recompute_count(), 1);
+    }
+
+    #[test]
+    fn test_changing_w_invalidates_cache() {
+        let tesseract = shared(2.0);
+        let mut cache = SliceCache::new();
+
+        cache.slice(&tesseract, 0.0);
+        let moved = cache.slice(&tesseract, 0.5).to_vec();
+
+        assert_eq!(cache.recompute_count(), 2);
+        assert_eq!(moved, slice_shape(tesseract.as_ref(), 0.5));
+    }
+
+    #[test]
+    fn test_different_shape_invalidates_cache() {
+        let small = shared(1.0);
+        let large = shared(4.0);
+        let mut cache = SliceCache::new();
+
+        cache.slice(&small, 0.0);
+        let result = cache.slice(&large, 0.0).to_vec();
+
+        assert_eq!(cache.recompute_count(), 2);
+        assert_eq!(result, slice_shape(large.as_ref(), 0.0));
+    }
+
+    #[test]
+    fn test_invalidate() {
+        let tesseract = shared(2.0);
+        let mut cache = SliceCache::new();
+
+        assert!(!cache.is_valid());
+        cache.slice(&tesseract, 0.0);
+        assert!(cache.is_valid());
+
+        cache.invalidate();
+        assert!(!cache.is_valid());
+        cache.slice(&tesseract, 0.0);
+        assert_eq!(cache.recompute_count(), 2);
+    }
+
+    #[test]
+    fn test_freed_shape_address_is_not_reused() {
+        let mut cache = SliceCache::new();
+        cache.slice(&shared(1.0), 0.0);
+
+        // The first shape is still held by the cache, so this one can't take its address
+        let large = shared(4.0);
+        let result = cache.slice(&large, 0.0).to_vec();
+        assert_eq!(cache.recompute_count(), 2);
+        assert_eq!(result, slice_shape(large.as_ref(), 0.0));
+    }
+
+    #[test]
+    fn test_dirty_entity_shape_is_resliced() {
+        let mut entity = Entity::new(ShapeRef::Shared(shared(2.0)));
+        let mut cache = SliceCache::new();
+
+        cache.slice_entity(&entity, 0.0);
+        entity.clear_dirty();
+        cache.slice_entity(&entity, 0.0);
+        assert_eq!(cache.recompute_count(), 1);
+
+        entity.mark_dirty(DirtyFlags::SHAPE);
+        cache.slice_entity(&entity, 0.0);
+        assert_eq!(cache.recompute_count(), 2);
+    }
+}
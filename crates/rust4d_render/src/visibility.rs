@@ -0,0 +1,261 @@
+//! Visibility caching keyed on camera and slice state
+//!
+//! An entity is visible when its cached bounds reach both sides of the slice
+//! hyperplane (see [`RenderableGeometry::entities_in_slice`]). The result only
+//! changes when the camera moves or rotates, the slice offset changes, or the
+//! geometry changes, so [`VisibilityCache`] keeps the last visible set and
+//! reuses it otherwise.
+
+use rust4d_core::EntityKey;
+use rust4d_math::{mat4, Vec4};
+use crate::camera4d::Camera4D;
+use crate::renderable::RenderableGeometry;
+
+/// Default tolerance for camera/slice comparisons
+const DEFAULT_EPSILON: f32 = 1e-5;
+
+/// Camera, slice and geometry state the cached visible set was computed for
+#[derive(Clone, Copy, Debug)]
+struct CameraKey {
+    position: Vec4,
+    rotation: mat4::Mat4,
+    slice_w: f32,
+    slice_normal: Vec4,
+    /// [`RenderableGeometry::revision`] of the geometry
+    revision: u64,
+}
+
+impl CameraKey {
+    fn new(camera: &Camera4D, geometry: &RenderableGeometry) -> Self {
+        Self {
+            position: camera.view_position(),
+            rotation: camera.view_rotation_matrix(),
+            slice_w: camera.get_slice_w(),
+            slice_normal: camera.slice_normal,
+            revision: geometry.revision(),
+        }
+    }
+
+    fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        let close = |a: f32, b: f32| (a - b).abs() <= epsilon;
+        self.revision == other.revision
+            && (self.position - other.position).length() <= epsilon
+            && close(self.slice_w, other.slice_w)
+            && (self.slice_normal - other.slice_normal).length() <= epsilon
+            && self.rotation.iter().flatten()
+                .zip(other.rotation.iter().flatten())
+                .all(|(&a, &b)| close(a, b))
+    }
+}
+
+/// Cache of which entities intersect the current slice
+///
+/// Call [`visible_entities`](VisibilityCache::visible_entities) each frame;
+/// the set is only recomputed when the camera or slice moved by more than
+/// `epsilon`, or the geometry changed since the last call (an entity was
+/// moved in place, added, or the geometry was rebuilt).
+pub struct VisibilityCache {
+    /// State for the cached set (None = invalid)
+    key: Option<CameraKey>,
+    /// Cached visible entity keys
+    visible: Vec<EntityKey>,
+    /// Tolerance for camera/slice comparisons
+    pub epsilon: f32,
+    /// Number of times the visible set has been recomputed
+    recompute_count: u64,
+}
+
+impl Default for VisibilityCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VisibilityCache {
+    /// Create an empty (invalid) cache
+    pub fn new() -> Self {
+        Self {
+            key: None,
+            visible: Vec::new(),
+            epsilon: DEFAULT_EPSILON,
+            recompute_count: 0,
+        }
+    }
+
+    /// Builder: set the camera/slice comparison tolerance
+    pub fn with_epsilon(mut self, epsilon: f32) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Get the keyed entities visible from the camera, recomputing only if needed
+    pub fn visible_entities(&mut self, geometry: &RenderableGeometry, camera: &Camera4D) -> &[EntityKey] {
+        let key = CameraKey::new(camera, geometry);
+        if !self.key.as_ref().is_some_and(|cached| cached.approx_eq(&key, self.epsilon)) {
+            self.visible.clear();
+            self.visible.extend(geometry.entity_keys_in_slice(camera));
+            self.key = Some(key);
+            self.recompute_count += 1;
+        }
+        &self.visible
+    }
+
+    /// Force the next query to recompute the visible set
+    pub fn invalidate(&mut self) {
+        self.key = None;
+    }
+
+    /// Check if the cache currently holds a computed set
+    pub fn is_valid(&self) -> bool {
+        self.key.is_some()
+    }
+
+    /// Number of times the visible set has been recomputed
+    pub fn recompute_count(&self) -> u64 {
+        self.recompute_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust4d_core::{Entity, Material, ShapeRef, Tesseract4D, Transform4D, World};
+
+    fn make_entity_at(position: Vec4) -> Entity {
+        Entity::with_transform(
+            ShapeRef::shared(Tesseract4D::new(2.0)),
+            Transform4D::from_position(position),
+            Material::WHITE,
+        )
+    }
+
+    fn make_world() -> (World, EntityKey, EntityKey) {
+        let mut world = World::new();
+        let near = world.add_entity(make_entity_at(Vec4::new(0.0, 0.0, 0.0, 0.0)));
+        let far = world.add_entity(make_entity_at(Vec4::new(0.0, 0.0, 0.0, 10.0)));
+        world.clear_all_dirty();
+        (world, near, far)
+    }
+
+    #[test]
+    fn test_visible_set_filters_by_slice() {
+        let (world, near, far) = make_world();
+        let geometry = RenderableGeometry::from_world(&world);
+        let camera = Camera4D::new();
+        let mut cache = VisibilityCache::new();
+
+        let visible = cache.visible_entities(&geometry, &camera);
+        assert!(visible.contains(&near));
+        assert!(!visible.contains(&far));
+    }
+
+    #[test]
+    fn test_static_frame_reuses_cache() {
+        let (world, _, _) = make_world();
+        let geometry = RenderableGeometry::from_world(&world);
+        let camera = Camera4D::new();
+        let mut cache = VisibilityCache::new();
+
+        cache.visible_entities(&geometry, &camera);
+        cache.visible_entities(&geometry, &camera);
+        cache.visible_entities(&geometry, &camera);
+        assert_eq!(cache.recompute_count(), 1);
+    }
+
+    #[test]
+    fn test_camera_move_past_epsilon_invalidates_cache() {
+        let (world, near, far) = make_world();
+        let geometry = RenderableGeometry::from_world(&world);
+        let mut camera = Camera4D::new();
+        let mut cache = VisibilityCache::new().with_epsilon(0.01);
+
+        cache.visible_entities(&geometry, &camera);
+        camera.move_w(0.001);
+        cache.visible_entities(&geometry, &camera);
+        assert_eq!(cache.recompute_count(), 1);
+
+        camera.move_w(10.0);
+        let visible = cache.visible_entities(&geometry, &camera).to_vec();
+        assert_eq!(cache.recompute_count(), 2);
+        assert!(visible.contains(&far));
+        assert!(!visible.contains(&near));
+    }
+
+    #[test]
+    fn test_camera_rotation_invalidates_cache() {
+        let (world, _, _) = make_world();
+        let geometry = RenderableGeometry::from_world(&world);
+        let mut camera = Camera4D::new();
+        let mut cache = VisibilityCache::new();
+
+        cache.visible_entities(&geometry, &camera);
+        camera.rotate_w(0.5);
+        cache.visible_entities(&geometry, &camera);
+        assert_eq!(cache.recompute_count(), 2);
+    }
+
+    #[test]
+    fn test_slice_change_invalidates_cache() {
+        let (world, _, _) = make_world();
+        let geometry = RenderableGeometry::from_world(&world);
+        let mut camera = Camera4D::new();
+        let mut cache = VisibilityCache::new();
+
+        cache.visible_entities(&geometry, &camera);
+        camera.adjust_slice_offset(1.0);
+        cache.visible_entities(&geometry, &camera);
+        assert_eq!(cache.recompute_count(), 2);
+    }
+
+    #[test]
+    fn test_dirty_entity_invalidates_cache() {
+        let (mut world, near, _) = make_world();
+        let mut geometry = RenderableGeometry::from_world(&world);
+        let camera = Camera4D::new();
+        let mut cache = VisibilityCache::new();
+
+        cache.visible_entities(&geometry, &camera);
+        world.get_entity_mut(near).unwrap().set_position(Vec4::new(0.0, 0.0, 0.0, 20.0));
+        assert!(geometry.update_transforms(&world));
+        world.clear_all_dirty();
+        let visible = cache.visible_entities(&geometry, &camera).to_vec();
+        assert_eq!(cache.recompute_count(), 2);
+        assert!(!visible.contains(&near));
+
+        // An update with nothing dirty leaves the geometry, and the cache, alone
+        assert!(geometry.update_transforms(&world));
+        cache.visible_entities(&geometry, &camera);
+        assert_eq!(cache.recompute_count(), 2);
+    }
+
+    #[test]
+    fn test_rebuilt_geometry_invalidates_cache() {
+        let (mut world, _, _) = make_world();
+        let camera = Camera4D::new();
+        let mut cache = VisibilityCache::new();
+
+        cache.visible_entities(&RenderableGeometry::from_world(&world), &camera);
+        let key = world.add_entity(make_entity_at(Vec4::ZERO));
+        let geometry = RenderableGeometry::from_world(&world);
+
+        assert!(cache.visible_entities(&geometry, &camera).contains(&key));
+        assert_eq!(cache.recompute_count(), 2);
+    }
+
+    #[test]
+    fn test_invalidate() {
+        let (world, _, _) = make_world();
+        let geometry = RenderableGeometry::from_world(&world);
+        let camera = Camera4D::new();
+        let mut cache = VisibilityCache::new();
+
+        assert!(!cache.is_valid());
+        cache.visible_entities(&geometry, &camera);
+        assert!(cache.is_valid());
+
+        cache.invalidate();
+        assert!(!cache.is_valid());
+        cache.visible_entities(&geometry, &camera);
+        assert_eq!(cache.recompute_count(), 2);
+    }
+}
@@ -52,7 +52,6 @@ struct App {
     render_context: Option<RenderContext>,
     slice_pipeline: Option<SlicePipeline>,
     render_pipeline: Option<RenderPipeline>,
-    world: World,
     geometry: RenderableGeometry,
     camera: Camera4D,
     controller: CameraController,
@@ -116,7 +115,6 @@ impl App {
             render_context: None,
             slice_pipeline: None,
             render_pipeline: None,
-            world,
             geometry,
             camera,
             controller,
@@ -200,6 +198,11 @@ impl ApplicationHandler for App {
                 &self.geometry.vertices,
                 &self.geometry.tetrahedra,
             );
+            render_pipeline.upload_materials(
+                &render_context.device,
+                &render_context.queue,
+                self.geometry.materials(),
+            );
 
             self.window = Some(window);
             self.render_context = Some(render_context);
@@ -310,18 +313,20 @@ impl ApplicationHandler for App {
                 // Render
                 if let (Some(ctx), Some(sp), Some(rp)) = (
                     &self.render_context,
-                    &self.slice_pipeline,
+                    &mut self.slice_pipeline,
                     &self.render_pipeline,
                 ) {
                     let pos = self.camera.position;
                     let slice_params = SliceParams {
                         slice_w: self.camera.get_slice_w(),
                         tetrahedron_count: self.geometry.tetrahedron_count() as u32,
-                        _padding: [0.0; 2],
+                        slice_epsilon: SliceParams::DEFAULT_EPSILON,
+                        slab_thickness: 0.0,
                         camera_matrix: self.camera.rotation_matrix(),
                         camera_eye: [pos.x, pos.y, pos.z],
                         _padding2: 0.0,
                         camera_position: [pos.x, pos.y, pos.z, pos.w],
+                        slice_normal: SliceParams::DEFAULT_SLICE_NORMAL,
                     };
                     sp.update_params(&ctx.queue, &slice_params);
 
@@ -338,12 +343,11 @@ impl ApplicationHandler for App {
                             0.1,
                             100.0,
                         ),
-                        light_dir: [0.5, 1.0, 0.3],
-                        _padding: 0.0,
                         ambient_strength: 0.3,
                         diffuse_strength: 0.7,
                         w_color_strength: 0.5,
                         w_range: 2.0,
+                        ..RenderUniforms::default()
                     };
                     rp.update_uniforms(&ctx.queue, &render_uniforms);
 
@@ -9,6 +9,7 @@ use figment::{Figment, providers::{Format, Toml, Env}};
 use serde::{Serialize, Deserialize};
 use std::path::Path;
 
+use rust4d_input::KeyBindings;
 // Re-export PhysicsConfig from the physics crate for convenience
 pub use rust4d_physics::PhysicsConfig;
 
@@ -121,6 +122,30 @@ pub struct CameraConfig {
     pub far: f32,
     /// Maximum pitch angle in degrees
     pub pitch_limit: f32,
+    /// Shake the view on hard landings
+    #[serde(default)]
+    pub shake_enabled: bool,
+    /// How the 3D cross-section is projected to the screen
+    #[serde(default)]
+    pub projection: Projection,
+    /// Half the view height in world units for the orthographic projection
+    #[serde(default = "default_ortho_size")]
+    pub ortho_size: f32,
+}
+
+fn default_ortho_size() -> f32 {
+    5.0
+}
+
+/// Projection used by the 3D render pass
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Projection {
+    /// Perspective with the configured field of view
+    #[default]
+    Perspective,
+    /// Parallel projection showing `ortho_size` units above and below the center
+    Orthographic,
 }
 
 impl Default for CameraConfig {
@@ -131,6 +156,9 @@ impl Default for CameraConfig {
             near: 0.1,
             far: 100.0,
             pitch_limit: 89.0,
+            shake_enabled: false,
+            projection: Projection::Perspective,
+            ortho_size: 5.0,
         }
     }
 }
@@ -150,6 +178,36 @@ pub struct InputConfig {
     pub smoothing_half_life: f32,
     /// Enable input smoothing by default
     pub smoothing_enabled: bool,
+    /// Mouse deltas shorter than this (in pixels) are ignored
+    #[serde(default)]
+    pub mouse_dead_zone: f32,
+    /// Holding jump re-jumps the moment the player lands
+    #[serde(default)]
+    pub auto_bhop: bool,
+    /// Discard the first mouse delta after capturing the cursor (avoids a view snap)
+    #[serde(default = "default_skip_first_capture_motion")]
+    pub skip_first_capture_motion: bool,
+    /// Gamepad stick and trigger values with a smaller magnitude read as zero
+    #[serde(default = "default_gamepad_dead_zone")]
+    pub gamepad_dead_zone: f32,
+    /// Right stick turn rate at full deflection (radians per second)
+    #[serde(default = "default_gamepad_look_speed")]
+    pub gamepad_look_speed: f32,
+    /// Keys driving each movement action (unlisted actions keep their defaults)
+    #[serde(default)]
+    pub bindings: KeyBindings,
+}
+
+fn default_skip_first_capture_motion() -> bool {
+    true
+}
+
+fn default_gamepad_dead_zone() -> f32 {
+    0.15
+}
+
+fn default_gamepad_look_speed() -> f32 {
+    2.5
 }
 
 impl Default for InputConfig {
@@ -161,6 +219,12 @@ impl Default for InputConfig {
             w_rotation_sensitivity: 0.005,
             smoothing_half_life: 0.05,
             smoothing_enabled: false,
+            mouse_dead_zone: 0.0,
+            auto_bhop: false,
+            skip_first_capture_motion: true,
+            gamepad_dead_zone: 0.15,
+            gamepad_look_speed: 2.5,
+            bindings: KeyBindings::default(),
         }
     }
 }
@@ -178,6 +242,33 @@ pub struct PhysicsConfigToml {
     pub gravity: f32,
     /// Jump velocity
     pub jump_velocity: f32,
+    /// Bit-reproducible stepping for lockstep experiments
+    #[serde(default)]
+    pub deterministic: bool,
+    /// Substeps per physics step (more is steadier for fast bodies)
+    #[serde(default = "default_substeps")]
+    pub substeps: u32,
+    /// Let resting bodies fall asleep until something disturbs them
+    #[serde(default = "default_sleep_enabled")]
+    pub sleep_enabled: bool,
+    /// Steps over which gravity ramps up after a scene loads (0 = off)
+    #[serde(default)]
+    pub warmup_steps: u32,
+    /// How hard the player shoves dynamic objects it walks into (0 = only separate them)
+    #[serde(default = "default_player_push_strength")]
+    pub player_push_strength: f32,
+}
+
+fn default_substeps() -> u32 {
+    1
+}
+
+fn default_sleep_enabled() -> bool {
+    true
+}
+
+fn default_player_push_strength() -> f32 {
+    1.0
 }
 
 impl Default for PhysicsConfigToml {
@@ -185,6 +276,11 @@ impl Default for PhysicsConfigToml {
         Self {
             gravity: -20.0,
             jump_velocity: 8.0,
+            deterministic: false,
+            substeps: 1,
+            sleep_enabled: true,
+            warmup_steps: 0,
+            player_push_strength: 1.0,
         }
     }
 }
@@ -193,8 +289,13 @@ impl PhysicsConfigToml {
     /// Convert to the physics engine's PhysicsConfig
     pub fn to_physics_config(&self) -> PhysicsConfig {
         PhysicsConfig {
-            gravity: self.gravity,
             jump_velocity: self.jump_velocity,
+            deterministic: self.deterministic,
+            substeps: self.substeps,
+            sleep_enabled: self.sleep_enabled,
+            warmup_steps: self.warmup_steps,
+            player_push_strength: self.player_push_strength,
+            ..PhysicsConfig::new(self.gravity)
         }
     }
 }
@@ -208,6 +309,12 @@ pub struct RenderingConfig {
     pub background_color: [f32; 4],
     /// Light direction [x, y, z]
     pub light_dir: [f32; 3],
+    /// Fill light direction [x, y, z]
+    #[serde(default = "default_fill_light_dir")]
+    pub fill_light_dir: [f32; 3],
+    /// Fill light intensity (0.0 = key light only)
+    #[serde(default)]
+    pub fill_light_intensity: f32,
     /// Ambient light strength
     pub ambient_strength: f32,
     /// Diffuse light strength
@@ -216,6 +323,47 @@ pub struct RenderingConfig {
     pub w_color_strength: f32,
     /// W-axis distance for full color effect
     pub w_range: f32,
+    /// Drop interior diagonal faces so slices only show shape surfaces
+    #[serde(default)]
+    pub cancel_internal_faces: bool,
+    /// Vertices within this W distance of the slice count as on it
+    #[serde(default = "default_slice_epsilon")]
+    pub slice_epsilon: f32,
+    /// Camera distances at which shapes switch to coarser levels of detail (empty = off)
+    #[serde(default)]
+    pub lod_distances: Vec<f32>,
+    /// Scene resolution relative to the window (0.25-1.0); lower is faster but blurrier
+    #[serde(default = "default_render_scale")]
+    pub render_scale: f32,
+    /// Average normals across neighboring triangles (false = flat-shaded facets)
+    #[serde(default = "default_smooth_normals")]
+    pub smooth_normals: bool,
+    /// Skip tetrahedra that cannot reach the slice or the view before slicing
+    #[serde(default = "default_frustum_culling")]
+    pub frustum_culling: bool,
+    /// Close holes in cross-sections by triangulating their boundary loops (slices on the CPU)
+    #[serde(default)]
+    pub slice_capping: bool,
+}
+
+fn default_slice_epsilon() -> f32 {
+    1e-4
+}
+
+fn default_render_scale() -> f32 {
+    1.0
+}
+
+fn default_smooth_normals() -> bool {
+    true
+}
+
+fn default_frustum_culling() -> bool {
+    true
+}
+
+fn default_fill_light_dir() -> [f32; 3] {
+    [-0.6, -0.2, 0.4]
 }
 
 impl Default for RenderingConfig {
@@ -224,10 +372,19 @@ impl Default for RenderingConfig {
             max_triangles: 900_000,
             background_color: [0.02, 0.02, 0.08, 1.0],
             light_dir: [0.5, 1.0, 0.3],
+            fill_light_dir: default_fill_light_dir(),
+            fill_light_intensity: 0.35,
             ambient_strength: 0.3,
             diffuse_strength: 0.7,
             w_color_strength: 0.5,
             w_range: 2.0,
+            cancel_internal_faces: false,
+            slice_epsilon: default_slice_epsilon(),
+            lod_distances: Vec::new(),
+            render_scale: default_render_scale(),
+            smooth_normals: default_smooth_normals(),
+            frustum_culling: default_frustum_culling(),
+            slice_capping: false,
         }
     }
 }
@@ -241,6 +398,15 @@ pub struct DebugConfig {
     pub log_level: String,
     /// Show physics colliders
     pub show_colliders: bool,
+    /// Draw the crosshair ray, where it crosses the slice and what it hits
+    #[serde(default)]
+    pub show_probe_ray: bool,
+    /// Record raw input to this RON file (saved on exit)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub record_input: Option<String>,
+    /// Replay raw input from this RON file instead of live input
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replay_input: Option<String>,
 }
 
 impl Default for DebugConfig {
@@ -249,6 +415,9 @@ impl Default for DebugConfig {
             show_overlay: false,
             log_level: "info".to_string(),
             show_colliders: false,
+            show_probe_ray: false,
+            record_input: None,
+            replay_input: None,
         }
     }
 }
@@ -260,6 +429,12 @@ pub struct SceneConfig {
     pub path: String,
     /// Player collision radius
     pub player_radius: f32,
+    /// Half the length of the player capsule's segment (0.0 = a sphere)
+    #[serde(default)]
+    pub player_half_height: f32,
+    /// Lift a player spawned inside the floor to rest this far above it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spawn_floor_clearance: Option<f32>,
 }
 
 impl Default for SceneConfig {
@@ -267,6 +442,8 @@ impl Default for SceneConfig {
         Self {
             path: "scenes/default.ron".to_string(),
             player_radius: 0.5,
+            player_half_height: 0.0,
+            spawn_floor_clearance: None,
         }
     }
 }
@@ -311,4 +488,29 @@ mod tests {
         assert!(toml.contains("title"));
         assert!(toml.contains("gravity"));
     }
+
+    #[test]
+    fn test_projection_parses_lowercase() {
+        let camera: CameraConfig = toml::from_str(
+            "start_position = [0.0, 0.0, 5.0, 0.0]\nfov = 45.0\nnear = 0.1\nfar = 100.0\npitch_limit = 89.0\nprojection = \"orthographic\"",
+        )
+        .unwrap();
+        assert_eq!(camera.projection, Projection::Orthographic);
+        assert_eq!(camera.ortho_size, 5.0);
+        assert_eq!(CameraConfig::default().projection, Projection::Perspective);
+    }
+
+    #[test]
+    fn test_key_bindings_parse() {
+        use rust4d_input::InputAction;
+        use winit::keyboard::KeyCode;
+
+        let input: InputConfig = toml::from_str(
+            "move_speed = 3.0\nw_move_speed = 2.0\nmouse_sensitivity = 0.002\nw_rotation_sensitivity = 0.005\n\
+             smoothing_half_life = 0.05\nsmoothing_enabled = false\n[bindings]\nforward = [\"ArrowUp\"]",
+        )
+        .unwrap();
+        assert_eq!(input.bindings.keys(InputAction::Forward), &[KeyCode::ArrowUp]);
+        assert_eq!(input.bindings.keys(InputAction::Back), &[KeyCode::KeyS]);
+    }
 }
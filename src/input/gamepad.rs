@@ -0,0 +1,156 @@
+//! Gamepad input through gilrs
+//!
+//! Translates gilrs events into the camera controller's gamepad calls:
+//! sticks and triggers become [`GamepadAxis`] values, face buttons become
+//! [`GamepadButton`] presses. Pads that report their triggers as buttons
+//! with an analog value are handled the same as trigger axes.
+
+use gilrs::{Axis, Button, Event, EventType, Gilrs};
+use rust4d_input::{CameraController, GamepadAxis, GamepadButton};
+
+/// Connected gamepads, pumped once per frame
+pub struct GamepadInput {
+    gilrs: Gilrs,
+}
+
+impl GamepadInput {
+    /// Start listening for gamepads
+    ///
+    /// Returns None (logging why) when the platform has no gamepad support,
+    /// in which case the game runs on keyboard and mouse alone.
+    pub fn new() -> Option<Self> {
+        match Gilrs::new() {
+            Ok(gilrs) => Some(Self { gilrs }),
+            Err(e) => {
+                log::warn!("Gamepad support unavailable: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Feed every pending gamepad event into the controller
+    pub fn pump(&mut self, controller: &mut CameraController) {
+        while let Some(Event { event, .. }) = self.gilrs.next_event() {
+            match event {
+                EventType::AxisChanged(axis, value, _) => {
+                    if let Some(axis) = map_axis(axis) {
+                        controller.process_gamepad_axis(axis, value);
+                    }
+                }
+                EventType::ButtonChanged(button, value, _) => {
+                    if let Some(axis) = map_trigger(button) {
+                        controller.process_gamepad_axis(axis, value);
+                    }
+                }
+                EventType::ButtonPressed(button, _) => {
+                    if let Some(button) = map_button(button) {
+                        controller.process_gamepad_button(button, true);
+                    }
+                }
+                EventType::ButtonReleased(button, _) => {
+                    if let Some(button) = map_button(button) {
+                        controller.process_gamepad_button(button, false);
+                    }
+                }
+                EventType::Disconnected => {
+                    log::info!("Gamepad disconnected");
+                    release_all(controller);
+                }
+                EventType::Connected => log::info!("Gamepad connected"),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// The controller axis for a gilrs axis, if it is one the controller uses
+///
+/// Some pads report their triggers as the Z axes rather than as buttons.
+pub fn map_axis(axis: Axis) -> Option<GamepadAxis> {
+    match axis {
+        Axis::LeftStickX => Some(GamepadAxis::LeftStickX),
+        Axis::LeftStickY => Some(GamepadAxis::LeftStickY),
+        Axis::RightStickX => Some(GamepadAxis::RightStickX),
+        Axis::RightStickY => Some(GamepadAxis::RightStickY),
+        Axis::LeftZ => Some(GamepadAxis::LeftTrigger),
+        Axis::RightZ => Some(GamepadAxis::RightTrigger),
+        _ => None,
+    }
+}
+
+/// The controller trigger axis for an analog trigger button
+pub fn map_trigger(button: Button) -> Option<GamepadAxis> {
+    match button {
+        Button::LeftTrigger2 => Some(GamepadAxis::LeftTrigger),
+        Button::RightTrigger2 => Some(GamepadAxis::RightTrigger),
+        _ => None,
+    }
+}
+
+/// The controller button for a gilrs face button
+pub fn map_button(button: Button) -> Option<GamepadButton> {
+    match button {
+        Button::South => Some(GamepadButton::South),
+        Button::East => Some(GamepadButton::East),
+        Button::North => Some(GamepadButton::North),
+        Button::West => Some(GamepadButton::West),
+        _ => None,
+    }
+}
+
+/// Center every axis and release every button, so a pad unplugged mid-move doesn't keep moving
+fn release_all(controller: &mut CameraController) {
+    for axis in [
+        GamepadAxis::LeftStickX,
+        GamepadAxis::LeftStickY,
+        GamepadAxis::RightStickX,
+        GamepadAxis::RightStickY,
+        GamepadAxis::LeftTrigger,
+        GamepadAxis::RightTrigger,
+    ] {
+        controller.process_gamepad_axis(axis, 0.0);
+    }
+    for button in [GamepadButton::South, GamepadButton::East, GamepadButton::North, GamepadButton::West] {
+        controller.process_gamepad_button(button, false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sticks_map_to_controller_axes() {
+        assert_eq!(map_axis(Axis::LeftStickX), Some(GamepadAxis::LeftStickX));
+        assert_eq!(map_axis(Axis::LeftStickY), Some(GamepadAxis::LeftStickY));
+        assert_eq!(map_axis(Axis::RightStickX), Some(GamepadAxis::RightStickX));
+        assert_eq!(map_axis(Axis::RightStickY), Some(GamepadAxis::RightStickY));
+        assert_eq!(map_axis(Axis::DPadX), None);
+    }
+
+    #[test]
+    fn test_triggers_map_from_axes_and_buttons() {
+        assert_eq!(map_axis(Axis::LeftZ), Some(GamepadAxis::LeftTrigger));
+        assert_eq!(map_axis(Axis::RightZ), Some(GamepadAxis::RightTrigger));
+        assert_eq!(map_trigger(Button::LeftTrigger2), Some(GamepadAxis::LeftTrigger));
+        assert_eq!(map_trigger(Button::RightTrigger2), Some(GamepadAxis::RightTrigger));
+        assert_eq!(map_trigger(Button::LeftTrigger), None);
+    }
+
+    #[test]
+    fn test_face_buttons_map_to_controller_buttons() {
+        assert_eq!(map_button(Button::South), Some(GamepadButton::South));
+        assert_eq!(map_button(Button::East), Some(GamepadButton::East));
+        assert_eq!(map_button(Button::Start), None);
+    }
+
+    #[test]
+    fn test_release_all_stops_movement() {
+        let mut controller = CameraController::new();
+        controller.process_gamepad_axis(GamepadAxis::LeftStickY, 1.0);
+        controller.process_gamepad_axis(GamepadAxis::RightTrigger, 1.0);
+        release_all(&mut controller);
+        assert_eq!(controller.get_movement_input(), (0.0, 0.0));
+        assert_eq!(controller.get_w_input(), 0.0);
+    }
+}
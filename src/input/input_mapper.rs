@@ -19,6 +19,18 @@ pub enum InputAction {
     ToggleFullscreen,
     /// Toggle input smoothing (G key)
     ToggleSmoothing,
+    /// Reset scene entities to their initial state (T key)
+    ResetPhysics,
+    /// Switch between walking and free-fly (noclip) camera (V key)
+    ToggleCameraMode,
+    /// Save the current view as a PNG (F12 key)
+    Screenshot,
+    /// Step down through render scale presets, wrapping back to full (F9 key)
+    CycleRenderScale,
+    /// Lock or unlock the player's W coordinate (L key)
+    ToggleWLock,
+    /// Select the entity under the crosshair (left click when captured)
+    Select,
 }
 
 /// Maps raw input events to semantic actions
@@ -52,22 +64,30 @@ impl InputMapper {
             KeyCode::KeyR => Some(InputAction::ResetCamera),
             KeyCode::KeyF => Some(InputAction::ToggleFullscreen),
             KeyCode::KeyG => Some(InputAction::ToggleSmoothing),
+            KeyCode::KeyT => Some(InputAction::ResetPhysics),
+            KeyCode::KeyV => Some(InputAction::ToggleCameraMode),
+            KeyCode::F12 => Some(InputAction::Screenshot),
+            KeyCode::F9 => Some(InputAction::CycleRenderScale),
+            KeyCode::KeyL => Some(InputAction::ToggleWLock),
             _ => None, // Movement keys handled by controller
         }
     }
 
     /// Map mouse button to an action
     ///
-    /// Returns `Some(ToggleCursor)` for left click when cursor not captured
+    /// Returns `Some(ToggleCursor)` for left click when cursor not captured,
+    /// and `Some(Select)` for left click when it is
     pub fn map_mouse_button(
         button: MouseButton,
         state: ElementState,
         cursor_captured: bool,
     ) -> Option<InputAction> {
-        if button == MouseButton::Left && state == ElementState::Pressed && !cursor_captured {
-            Some(InputAction::ToggleCursor)
-        } else {
+        if button != MouseButton::Left || state != ElementState::Pressed {
             None
+        } else if cursor_captured {
+            Some(InputAction::Select)
+        } else {
+            Some(InputAction::ToggleCursor)
         }
     }
 }
@@ -123,13 +143,13 @@ mod tests {
     }
 
     #[test]
-    fn test_click_when_captured_no_action() {
+    fn test_click_when_captured_selects() {
         let action = InputMapper::map_mouse_button(
             MouseButton::Left,
             ElementState::Pressed,
             true, // cursor already captured
         );
-        assert_eq!(action, None);
+        assert_eq!(action, Some(InputAction::Select));
     }
 
     #[test]
@@ -146,5 +166,25 @@ mod tests {
             InputMapper::map_keyboard(KeyCode::KeyG, ElementState::Pressed, true),
             Some(InputAction::ToggleSmoothing)
         );
+        assert_eq!(
+            InputMapper::map_keyboard(KeyCode::KeyT, ElementState::Pressed, true),
+            Some(InputAction::ResetPhysics)
+        );
+        assert_eq!(
+            InputMapper::map_keyboard(KeyCode::KeyV, ElementState::Pressed, true),
+            Some(InputAction::ToggleCameraMode)
+        );
+        assert_eq!(
+            InputMapper::map_keyboard(KeyCode::F12, ElementState::Pressed, true),
+            Some(InputAction::Screenshot)
+        );
+        assert_eq!(
+            InputMapper::map_keyboard(KeyCode::F9, ElementState::Pressed, true),
+            Some(InputAction::CycleRenderScale)
+        );
+        assert_eq!(
+            InputMapper::map_keyboard(KeyCode::KeyL, ElementState::Pressed, true),
+            Some(InputAction::ToggleWLock)
+        );
     }
 }
@@ -1,7 +1,12 @@
 //! Input handling module
 //!
-//! Provides input mapping from raw events to semantic actions.
+//! Provides input mapping from raw events to semantic actions, and (with the
+//! `gamepad` feature) gamepad events to the camera controller.
 
+#[cfg(feature = "gamepad")]
+mod gamepad;
 mod input_mapper;
 
+#[cfg(feature = "gamepad")]
+pub use gamepad::GamepadInput;
 pub use input_mapper::{InputMapper, InputAction};
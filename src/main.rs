@@ -6,24 +6,31 @@ mod config;
 mod input;
 mod systems;
 
+use std::time::Duration;
+
 use winit::{
     application::ApplicationHandler,
     event::{DeviceEvent, DeviceId, WindowEvent},
     event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
-    keyboard::PhysicalKey,
+    keyboard::{ModifiersState, PhysicalKey},
     window::WindowId,
 };
 
+#[cfg(feature = "gamepad")]
+use input::GamepadInput;
 use input::{InputMapper, InputAction};
-use systems::{RenderError, RenderSystem, SimulationSystem, WindowSystem};
+use systems::{
+    next_render_scale, CameraMode, ManualClock, RenderError, RenderSystem, SimulationSystem, SystemClock, WindowSystem,
+};
 
 use rust4d_core::{World, SceneManager};
 use rust4d_render::{
     camera4d::Camera4D,
-    RenderableGeometry, CheckerboardGeometry, position_gradient_color,
+    RenderableGeometry, LodSettings, CheckerboardGeometry, position_gradient_color, sort_back_to_front,
 };
-use rust4d_input::CameraController;
+use rust4d_input::{CameraController, InputRecorder, InputRecording, InputReplayer, RecordedInput};
 use rust4d_math::Vec4;
+use rust4d_physics::BodyKey;
 
 use config::AppConfig;
 
@@ -41,10 +48,27 @@ struct App {
     geometry: RenderableGeometry,
     camera: Camera4D,
     controller: CameraController,
+    /// Gamepad events (None without gamepad support)
+    #[cfg(feature = "gamepad")]
+    gamepad: Option<GamepadInput>,
     /// Simulation system for game loop
     simulation: SimulationSystem,
+    /// Raw input recorder (when `debug.record_input` is set)
+    recorder: Option<InputRecorder>,
+    /// Raw input replayer (when `debug.replay_input` is set); replaces live input
+    replayer: Option<InputReplayer>,
+    /// Simulation clock during a replay, advanced a fixed step per replayed frame
+    replay_clock: Option<ManualClock>,
+    /// Keyboard modifiers currently held
+    modifiers: ModifiersState,
 }
 
+/// How far the crosshair ray reaches when selecting or probing
+const CROSSHAIR_RANGE: f32 = 100.0;
+
+/// Simulated time per frame while replaying input
+const REPLAY_FRAME_TIME: Duration = Duration::from_micros(16_667);
+
 impl App {
     fn new() -> Self {
         // Load configuration
@@ -57,7 +81,11 @@ impl App {
         // Pass physics config from TOML to the physics engine
         let mut scene_manager = SceneManager::new()
             .with_player_radius(config.scene.player_radius)
+            .with_player_half_height(config.scene.player_half_height)
             .with_physics(config.physics.to_physics_config());
+        if let Some(clearance) = config.scene.spawn_floor_clearance {
+            scene_manager = scene_manager.with_spawn_snap(clearance);
+        }
 
         // Load scene from configured path
         let scene_name = scene_manager.load_scene(&config.scene.path)
@@ -83,7 +111,7 @@ impl App {
             ));
 
         // Build GPU geometry from the world
-        let geometry = Self::build_geometry(scene_manager.active_world().unwrap());
+        let geometry = Self::build_geometry(scene_manager.active_world().unwrap(), &config, player_start);
 
         log::info!("Loaded scene '{}' with {} entities",
             scene_name,
@@ -94,6 +122,7 @@ impl App {
         // Set camera with configured pitch limit and player start position
         let mut camera = Camera4D::with_pitch_limit(config.camera.pitch_limit.to_radians());
         camera.position = player_start;
+        camera.set_shake_enabled(config.camera.shake_enabled);
 
         // Configure controller from config
         let controller = CameraController::new()
@@ -102,7 +131,37 @@ impl App {
             .with_mouse_sensitivity(config.input.mouse_sensitivity)
             .with_w_rotation_sensitivity(config.input.w_rotation_sensitivity)
             .with_smoothing_half_life(config.input.smoothing_half_life)
-            .with_smoothing(config.input.smoothing_enabled);
+            .with_smoothing(config.input.smoothing_enabled)
+            .with_mouse_dead_zone(config.input.mouse_dead_zone)
+            .with_auto_bhop(config.input.auto_bhop)
+            .with_skip_first_capture_motion(config.input.skip_first_capture_motion)
+            .with_gamepad_dead_zone(config.input.gamepad_dead_zone)
+            .with_gamepad_look_speed(config.input.gamepad_look_speed)
+            .with_bindings(config.input.bindings.clone());
+
+        let recorder = config.debug.record_input.as_ref().map(|path| {
+            log::info!("Recording input to {}", path);
+            InputRecorder::new()
+        });
+        let replayer = config.debug.replay_input.as_ref().and_then(|path| {
+            match InputRecording::load(path) {
+                Ok(recording) => {
+                    log::info!("Replaying {} input frames from {}", recording.frame_count, path);
+                    Some(InputReplayer::new(recording))
+                }
+                Err(e) => {
+                    log::warn!("Failed to load input recording {}: {}", path, e);
+                    None
+                }
+            }
+        });
+
+        // Replays run on a fixed frame time so they don't depend on the frame rate
+        let replay_clock = replayer.as_ref().map(|_| ManualClock::new());
+        let simulation = match &replay_clock {
+            Some(clock) => SimulationSystem::with_clock(Box::new(clock.clone())),
+            None => SimulationSystem::new(),
+        };
 
         Self {
             config,
@@ -112,13 +171,191 @@ impl App {
             geometry,
             camera,
             controller,
-            simulation: SimulationSystem::new(),
+            #[cfg(feature = "gamepad")]
+            gamepad: GamepadInput::new(),
+            simulation,
+            recorder,
+            replayer,
+            replay_clock,
+            modifiers: ModifiersState::empty(),
         }
     }
 
+    /// Save the current view to a timestamped PNG in the working directory
+    fn save_screenshot(&mut self) {
+        let Some(rs) = &mut self.render_system else { return };
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let path = format!("screenshot-{}.png", timestamp);
+        match rs.save_screenshot(&self.camera, &self.geometry, &path) {
+            Ok(()) => log::info!("Saved screenshot to {}", path),
+            Err(e) => log::warn!("Failed to save screenshot: {}", e),
+        }
+    }
+
+    /// Find the body under the crosshair and its distance, ignoring the player
+    fn crosshair_hit(&self) -> Option<(BodyKey, f32)> {
+        let physics = self.scene_manager.active_world()?.physics()?;
+        physics
+            .raycast_filtered(self.camera.position, self.camera.forward(), CROSSHAIR_RANGE, None, true)
+            .map(|(body, distance, _)| (body, distance))
+    }
+
+    /// Log the entity under the crosshair
+    fn select_under_crosshair(&self) {
+        let Some(world) = self.scene_manager.active_world() else { return };
+        let Some((body, distance)) = self.crosshair_hit() else {
+            log::info!("Nothing under the crosshair");
+            return;
+        };
+
+        match world.iter().find(|e| e.physics_body == Some(body)) {
+            Some(entity) => log::info!(
+                "Selected {} at {:.2} units",
+                entity.name.as_deref().unwrap_or("<unnamed entity>"),
+                distance
+            ),
+            None => log::info!("Selected a body with no entity at {:.2} units", distance),
+        }
+    }
+
+    /// Route a raw input event to the input mapper and controller
+    ///
+    /// Live events are recorded here when recording is enabled; replayed
+    /// events come in through the same path so they behave identically.
+    fn handle_input(&mut self, event_loop: &ActiveEventLoop, input: RecordedInput) {
+        let cursor_captured = self.window_system.as_ref()
+            .map(|ws| ws.is_cursor_captured())
+            .unwrap_or(false);
+
+        match input {
+            RecordedInput::Key { key, state } => {
+                // Map to action via InputMapper
+                if let Some(action) = InputMapper::map_keyboard(key, state, cursor_captured) {
+                    match action {
+                        InputAction::ToggleCursor => {
+                            if let Some(ws) = &mut self.window_system {
+                                ws.release_cursor();
+                            }
+                        }
+                        InputAction::Exit => {
+                            event_loop.exit();
+                        }
+                        InputAction::ResetCamera => {
+                            self.camera.reset();
+                            log::info!("Camera reset to starting position");
+                        }
+                        InputAction::ToggleFullscreen => {
+                            if let Some(ws) = &self.window_system {
+                                ws.toggle_fullscreen();
+                            }
+                        }
+                        InputAction::ToggleSmoothing => {
+                            let enabled = self.controller.toggle_smoothing();
+                            log::info!("Input smoothing: {}", if enabled { "ON" } else { "OFF" });
+                        }
+                        InputAction::ResetPhysics => {
+                            match self.scene_manager.reset_physics() {
+                                Ok(()) => log::info!("Scene physics reset to template"),
+                                Err(e) => log::warn!("Failed to reset physics: {}", e),
+                            }
+                        }
+                        InputAction::ToggleCameraMode => {
+                            self.simulation.toggle_mode(&mut self.scene_manager);
+                            let mode = match self.simulation.mode() {
+                                CameraMode::Walk => "walk",
+                                CameraMode::FreeFly => "free-fly (noclip)",
+                            };
+                            log::info!("Camera mode: {}", mode);
+                        }
+                        InputAction::Screenshot => {
+                            self.save_screenshot();
+                        }
+                        InputAction::CycleRenderScale => {
+                            if let Some(rs) = &mut self.render_system {
+                                rs.set_render_scale(next_render_scale(rs.render_scale()));
+                                let (width, height) = rs.scene_size();
+                                log::info!("Render scale: {:.2} ({}x{})", rs.render_scale(), width, height);
+                            }
+                        }
+                        InputAction::ToggleWLock => {
+                            let locked = self.simulation.toggle_w_lock();
+                            log::info!("W lock: {}", if locked { "ON" } else { "OFF" });
+                        }
+                        InputAction::Select => {
+                            self.select_under_crosshair();
+                        }
+                    }
+                    return;
+                }
+
+                // Pass to controller for movement keys
+                self.controller.process_keyboard(key, state);
+            }
+            RecordedInput::MouseButton { button, state } => {
+                // Map to action via InputMapper
+                match InputMapper::map_mouse_button(button, state, cursor_captured) {
+                    Some(InputAction::ToggleCursor) => {
+                        if let Some(ws) = &mut self.window_system {
+                            if ws.capture_cursor() {
+                                self.controller.notify_cursor_captured();
+                            }
+                        }
+                    }
+                    Some(InputAction::Select) => self.select_under_crosshair(),
+                    _ => {}
+                }
+                self.controller.process_mouse_button(button, state);
+            }
+            RecordedInput::MouseMotion { dx, dy } => {
+                self.controller.process_mouse_motion(dx, dy);
+            }
+        }
+    }
+
+    /// Handle a live input event (ignored while a replay is running)
+    fn handle_live_input(&mut self, event_loop: &ActiveEventLoop, input: RecordedInput) {
+        if self.replayer.is_some() {
+            return;
+        }
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(input);
+        }
+        self.handle_input(event_loop, input);
+    }
+
+    /// Feed the next frame of a running replay through the input path
+    fn replay_frame(&mut self, event_loop: &ActiveEventLoop) {
+        let Some(replayer) = &mut self.replayer else {
+            return;
+        };
+        let events: Vec<RecordedInput> = replayer.next_frame().iter().map(|e| e.input).collect();
+        if let Some(clock) = &self.replay_clock {
+            clock.advance(REPLAY_FRAME_TIME);
+        }
+        if replayer.is_finished() {
+            log::info!("Input replay finished");
+            self.replayer = None;
+            self.replay_clock = None;
+            self.simulation.set_clock(Box::new(SystemClock::new()));
+        }
+        for input in events {
+            self.handle_input(event_loop, input);
+        }
+    }
+
+    /// Level-of-detail selection around the camera (None if disabled)
+    fn lod_settings(config: &AppConfig, camera_position: Vec4) -> Option<LodSettings> {
+        let distances = &config.rendering.lod_distances;
+        (!distances.is_empty()).then(|| LodSettings::new(camera_position, distances.clone()))
+    }
+
     /// Build GPU geometry from the world using custom coloring
-    fn build_geometry(world: &World) -> RenderableGeometry {
-        let mut geometry = RenderableGeometry::new();
+    fn build_geometry(world: &World, config: &AppConfig, camera_position: Vec4) -> RenderableGeometry {
+        let mut geometry = RenderableGeometry::new().with_slice_capping(config.rendering.slice_capping);
+        geometry.cancel_internal_faces(config.rendering.cancel_internal_faces);
+        geometry.set_lod(Self::lod_settings(config, camera_position));
 
         // Checkerboard pattern for the floor
         let checkerboard = CheckerboardGeometry::new(
@@ -127,13 +364,17 @@ impl App {
             2.0, // Cell size
         );
 
-        for (_key, entity) in world.iter_with_keys() {
+        // Transparent entities go last, farthest first, so they blend correctly
+        let mut entities: Vec<_> = world.iter_with_keys().collect();
+        sort_back_to_front(world, &mut entities, camera_position);
+
+        for (key, entity) in entities {
             if entity.has_tag("dynamic") {
                 // Dynamic entities (tesseract): use position gradient
-                geometry.add_entity_with_color(entity, &position_gradient_color);
+                geometry.add_keyed_entity_with_color(world, key, &position_gradient_color);
             } else {
                 // Static entities (floor): use checkerboard pattern
-                geometry.add_entity_with_color(entity, &|v, _m| {
+                geometry.add_keyed_entity_with_color(world, key, &|v, _m| {
                     checkerboard.color_for_position(v.x, v.z)
                 });
             }
@@ -161,6 +402,7 @@ impl ApplicationHandler for App {
 
             // Upload initial geometry
             render_system.upload_geometry(&self.geometry);
+            render_system.set_show_probe_ray(self.config.debug.show_probe_ray);
 
             self.window_system = Some(window_system);
             self.render_system = Some(render_system);
@@ -173,6 +415,23 @@ impl ApplicationHandler for App {
                 event_loop.exit();
             }
 
+            WindowEvent::Focused(focused) => {
+                let changed = self.window_system.as_mut()
+                    .map(|ws| ws.set_focused(event_loop, focused))
+                    .unwrap_or(false);
+                if changed {
+                    if focused {
+                        // Fresh frame timer so the time spent idle isn't one huge step
+                        self.simulation.resume();
+                        if let Some(ws) = &self.window_system {
+                            ws.request_redraw();
+                        }
+                    } else {
+                        self.simulation.pause();
+                    }
+                }
+            }
+
             WindowEvent::Resized(physical_size) => {
                 if let Some(rs) = &mut self.render_system {
                     rs.resize(physical_size.width, physical_size.height);
@@ -181,69 +440,39 @@ impl ApplicationHandler for App {
 
             WindowEvent::KeyboardInput { event, .. } => {
                 if let PhysicalKey::Code(key) = event.physical_key {
-                    // Map to action via InputMapper
-                    let cursor_captured = self.window_system.as_ref()
-                        .map(|ws| ws.is_cursor_captured())
-                        .unwrap_or(false);
-
-                    if let Some(action) = InputMapper::map_keyboard(key, event.state, cursor_captured) {
-                        match action {
-                            InputAction::ToggleCursor => {
-                                if let Some(ws) = &mut self.window_system {
-                                    ws.release_cursor();
-                                }
-                            }
-                            InputAction::Exit => {
-                                event_loop.exit();
-                            }
-                            InputAction::ResetCamera => {
-                                self.camera.reset();
-                                log::info!("Camera reset to starting position");
-                            }
-                            InputAction::ToggleFullscreen => {
-                                if let Some(ws) = &self.window_system {
-                                    ws.toggle_fullscreen();
-                                }
-                            }
-                            InputAction::ToggleSmoothing => {
-                                let enabled = self.controller.toggle_smoothing();
-                                log::info!("Input smoothing: {}", if enabled { "ON" } else { "OFF" });
-                            }
-                        }
-                        return;
-                    }
-
-                    // Pass to controller for movement keys
-                    self.controller.process_keyboard(key, event.state);
+                    self.handle_live_input(event_loop, RecordedInput::Key { key, state: event.state });
                 }
             }
 
             WindowEvent::MouseInput { state, button, .. } => {
-                // Map to action via InputMapper
-                let cursor_captured = self.window_system.as_ref()
-                    .map(|ws| ws.is_cursor_captured())
-                    .unwrap_or(false);
+                self.handle_live_input(event_loop, RecordedInput::MouseButton { button, state });
+            }
 
-                if let Some(action) = InputMapper::map_mouse_button(button, state, cursor_captured) {
-                    if action == InputAction::ToggleCursor {
-                        if let Some(ws) = &mut self.window_system {
-                            ws.capture_cursor();
-                        }
-                    }
-                }
-                self.controller.process_mouse_button(button, state);
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers = modifiers.state();
             }
 
             WindowEvent::MouseWheel { delta, .. } => {
-                // Scroll wheel adjusts slice offset
+                // Scroll wheel adjusts slice offset, or slab thickness with Ctrl held
                 let scroll = match delta {
                     winit::event::MouseScrollDelta::LineDelta(_, y) => y,
                     winit::event::MouseScrollDelta::PixelDelta(pos) => pos.y as f32 / 100.0,
                 };
-                self.camera.adjust_slice_offset(scroll * 0.1);
+                if self.modifiers.control_key() {
+                    self.camera.adjust_slab_thickness(scroll * 0.1);
+                } else {
+                    self.camera.adjust_slice_offset(scroll * 0.1);
+                }
             }
 
             WindowEvent::RedrawRequested => {
+                // Apply this frame's replayed input (if replaying), or live gamepad input
+                self.replay_frame(event_loop);
+                #[cfg(feature = "gamepad")]
+                if let (Some(gamepad), None) = (&mut self.gamepad, &self.replayer) {
+                    gamepad.pump(&mut self.controller);
+                }
+
                 // Run simulation
                 let cursor_captured = self.window_system.as_ref()
                     .map(|ws| ws.is_cursor_captured())
@@ -255,10 +484,22 @@ impl ApplicationHandler for App {
                     &mut self.controller,
                     cursor_captured,
                 );
+                if let Some(recorder) = &mut self.recorder {
+                    recorder.end_frame();
+                }
 
-                // Rebuild geometry if entities changed
-                if result.geometry_dirty {
-                    self.geometry = Self::build_geometry(self.scene_manager.active_world().unwrap());
+                // Update geometry if entities changed; only shape and material
+                // changes need a full rebuild, as does anything crossing a
+                // level-of-detail distance or reordering transparent entities
+                let world = self.scene_manager.active_world().unwrap();
+                let lod_outdated = Self::lod_settings(&self.config, self.camera.position)
+                    .is_some_and(|lod| self.geometry.lod_outdated(world, &lod));
+                let order_outdated = self.geometry.transparency_order_outdated(world, self.camera.position);
+                let rebuild = lod_outdated || order_outdated;
+                if result.geometry_dirty || rebuild {
+                    if rebuild || !self.geometry.update_transforms(world) {
+                        self.geometry = Self::build_geometry(world, &self.config, self.camera.position);
+                    }
                     if let Some(rs) = &mut self.render_system {
                         rs.upload_geometry(&self.geometry);
                     }
@@ -266,6 +507,10 @@ impl ApplicationHandler for App {
                         w.clear_all_dirty();
                     }
                 }
+                // The geometry reads dirty flags instead, so just keep batches from piling up
+                if let Some(w) = self.scene_manager.active_world_mut() {
+                    w.drain_dirty_batches();
+                }
 
                 // Update window title with debug info
                 if let Some(ws) = &self.window_system {
@@ -274,9 +519,15 @@ impl ApplicationHandler for App {
                 }
 
                 // Render frame
+                let show_probe = self.render_system.as_ref().is_some_and(|rs| rs.show_probe_ray());
+                let probe_hit = show_probe
+                    .then(|| self.crosshair_hit())
+                    .flatten()
+                    .map(|(_, distance)| self.camera.position + self.camera.forward() * distance);
                 if let Some(rs) = &mut self.render_system {
+                    rs.set_probe_hit(probe_hit);
                     match rs.render_frame(&self.camera, &self.geometry) {
-                        Ok(()) => {}
+                        Ok(()) => log::trace!("Frame stats: {:?}", rs.stats()),
                         Err(RenderError::SurfaceLost) => {
                             let (w, h) = rs.size();
                             rs.resize(w, h);
@@ -291,9 +542,11 @@ impl ApplicationHandler for App {
                     }
                 }
 
-                // Request next frame
+                // Request next frame (unfocused windows only redraw on events)
                 if let Some(ws) = &self.window_system {
-                    ws.request_redraw();
+                    if ws.is_focused() {
+                        ws.request_redraw();
+                    }
                 }
             }
 
@@ -303,12 +556,22 @@ impl ApplicationHandler for App {
 
     fn device_event(
         &mut self,
-        _event_loop: &ActiveEventLoop,
+        event_loop: &ActiveEventLoop,
         _device_id: DeviceId,
         event: DeviceEvent,
     ) {
         if let DeviceEvent::MouseMotion { delta } = event {
-            self.controller.process_mouse_motion(delta.0, delta.1);
+            self.handle_live_input(event_loop, RecordedInput::MouseMotion { dx: delta.0, dy: delta.1 });
+        }
+    }
+
+    fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
+        if let (Some(recorder), Some(path)) = (self.recorder.take(), &self.config.debug.record_input) {
+            let recording = recorder.finish();
+            match recording.save(path) {
+                Ok(()) => log::info!("Saved {} input frames to {}", recording.frame_count, path),
+                Err(e) => log::warn!("Failed to save input recording {}: {}", path, e),
+            }
         }
     }
 }
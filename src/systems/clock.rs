@@ -0,0 +1,91 @@
+//! Frame clocks
+//!
+//! [`SimulationSystem`](super::SimulationSystem) reads time through the
+//! [`Clock`] trait instead of calling `Instant::now()` directly, so frame
+//! timing can be driven by hand: [`SystemClock`] follows the wall clock,
+//! [`ManualClock`] only moves when told to (tests and input replays).
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// Source of the current time
+pub trait Clock {
+    /// Time elapsed since the clock's own epoch
+    fn now(&self) -> Duration;
+}
+
+/// Wall-clock time, measured from when the clock was created
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    /// Create a clock starting at zero now
+    pub fn new() -> Self {
+        Self { start: Instant::now() }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// A clock that only advances when told to
+///
+/// Clones share the same time, so one handle can be given to a system while
+/// another is kept to drive it.
+#[derive(Clone, Default)]
+pub struct ManualClock {
+    now: Rc<Cell<Duration>>,
+}
+
+impl ManualClock {
+    /// Create a clock stopped at zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move the clock forward
+    pub fn advance(&self, delta: Duration) {
+        self.now.set(self.now.get() + delta);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock_clones_share_time() {
+        let clock = ManualClock::new();
+        let handle = clock.clone();
+        assert_eq!(clock.now(), Duration::ZERO);
+
+        handle.advance(Duration::from_millis(250));
+        handle.advance(Duration::from_millis(250));
+        assert_eq!(clock.now(), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_system_clock_is_monotonic() {
+        let clock = SystemClock::new();
+        let a = clock.now();
+        let b = clock.now();
+        assert!(b >= a);
+    }
+}
@@ -2,10 +2,12 @@
 //!
 //! Modular systems extracted from main.rs for better organization and testability.
 
+mod clock;
 mod render;
 mod simulation;
 mod window;
 
-pub use render::{RenderError, RenderSystem};
-pub use simulation::SimulationSystem;
+pub use clock::{ManualClock, SystemClock};
+pub use render::{next_render_scale, RenderError, RenderSystem};
+pub use simulation::{CameraMode, SimulationSystem};
 pub use window::WindowSystem;
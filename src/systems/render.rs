@@ -4,16 +4,31 @@
 //! - Render context and surface
 //! - Slice and render pipelines
 //! - Frame rendering
+//! - Offscreen screenshots
+//! - The probe ray debug overlay
+//! - Per-frame render statistics
 
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 use winit::window::Window;
+use super::clock::{Clock, SystemClock};
 use rust4d_render::{
     context::RenderContext,
     camera4d::Camera4D,
-    pipeline::{perspective_matrix, RenderPipeline, RenderUniforms, SliceParams, SlicePipeline},
-    RenderableGeometry,
+    debug_lines::{DebugLines, ProbeRay},
+    pipeline::{
+        orthographic_matrix, perspective_matrix, scaled_size, GpuTimer, MIN_RENDER_SCALE, Light, RenderPipeline, RenderUniforms,
+        SliceParams, SlicePipeline, UpscalePipeline, ViewFrustum,
+    },
+    screenshot::{read_texture, RgbaImage, ScreenshotError},
+    RenderableGeometry, Vec4, VisibilityCache,
 };
-use crate::config::{CameraConfig, RenderingConfig};
+use rust4d_math::mat4;
+use crate::config::{CameraConfig, Projection, RenderingConfig};
+
+/// Length of the drawn probe ray
+const PROBE_RAY_LENGTH: f32 = 100.0;
 
 /// Render error types
 #[derive(Debug)]
@@ -38,13 +53,76 @@ impl std::fmt::Display for RenderError {
 
 impl std::error::Error for RenderError {}
 
+/// Counts describing the last rendered frame
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RenderStats {
+    /// Triangles in the cross-section (GPU slicing reports this a frame or two late)
+    pub triangles: u32,
+    /// Draw calls issued: the cross-section, any debug lines, and the upscale blit
+    pub draw_calls: u32,
+    /// Entities that cross the slice hyperplane
+    pub visible_entities: u32,
+    /// Tetrahedra sent to the slice pass, after culling
+    pub sliced_tetrahedra: u32,
+    /// 4D vertices in the uploaded geometry
+    pub uploaded_vertices: u32,
+    /// Time since the previous frame, in milliseconds (0 for the first frame)
+    pub frame_time_ms: f32,
+    /// GPU time of the slice compute passes, in microseconds (0 when slicing on the CPU)
+    pub slice_pass_us: f32,
+    /// GPU time of the render pass, in microseconds
+    ///
+    /// Both pass times need timestamp queries and are 0 without them. Like
+    /// `triangles`, they arrive a frame or two late.
+    pub render_pass_us: f32,
+}
+
+/// Stats of the last frame rendered to the window, timed by a [`Clock`]
+struct FrameStats {
+    clock: Box<dyn Clock>,
+    /// Clock time of the last recorded frame
+    last_frame: Option<Duration>,
+    /// Entities on the slice, only recounted when the camera or geometry changed
+    visibility: VisibilityCache,
+    stats: RenderStats,
+}
+
+impl FrameStats {
+    fn new(clock: Box<dyn Clock>) -> Self {
+        Self { clock, last_frame: None, visibility: VisibilityCache::new(), stats: RenderStats::default() }
+    }
+
+    /// Record a finished frame, filling in its frame time and visible entities
+    fn record(&mut self, camera: &Camera4D, geometry: &RenderableGeometry, stats: RenderStats) {
+        let now = self.clock.now();
+        self.stats = RenderStats {
+            visible_entities: self.visibility.visible_entities(geometry, camera).len() as u32,
+            frame_time_ms: self.last_frame.map_or(0.0, |last| (now - last).as_secs_f32() * 1000.0),
+            ..stats
+        };
+        self.last_frame = Some(now);
+    }
+}
+
 /// Manages GPU rendering
 pub struct RenderSystem {
     context: RenderContext,
     slice_pipeline: SlicePipeline,
     render_pipeline: RenderPipeline,
+    /// Stretches the scene onto the surface when rendering below full resolution
+    upscale_pipeline: UpscalePipeline,
+    /// Timestamp queries around the slice and render passes
+    timer: GpuTimer,
+    /// Scene resolution relative to the surface (1.0 = native)
+    render_scale: f32,
     render_config: RenderingConfig,
     camera_config: CameraConfig,
+    /// Draw the crosshair ray as debug lines
+    show_probe_ray: bool,
+    /// Where the crosshair ray hit something, for the probe overlay
+    probe_hit: Option<Vec4>,
+    /// Counts from the last frame rendered to the window
+    frame_stats: FrameStats,
 }
 
 impl RenderSystem {
@@ -56,35 +134,110 @@ impl RenderSystem {
         vsync: bool,
     ) -> Self {
         let context = pollster::block_on(RenderContext::with_vsync(window, vsync));
+        let limits = context.device_limits();
+        log::info!(
+            "Device limits: storage binding {} bytes, buffer {} bytes",
+            limits.max_storage_buffer_binding_size, limits.max_buffer_size
+        );
 
-        let slice_pipeline = SlicePipeline::new(
+        let mut slice_pipeline = SlicePipeline::with_mode(
             &context.device,
             render_config.max_triangles as usize,
+            context.slice_mode,
         );
+        slice_pipeline.set_smooth_normals(&context.device, render_config.smooth_normals);
+        slice_pipeline.set_culling_enabled(render_config.frustum_culling);
 
         let mut render_pipeline = RenderPipeline::new(&context.device, context.config.format);
+        render_pipeline.set_lights(&context.queue, &scene_lights(&render_config));
+        let upscale_pipeline = UpscalePipeline::new(&context.device, context.config.format);
+        let timer = GpuTimer::new(&context.device, &context.queue);
 
-        // Ensure depth texture exists
-        render_pipeline.ensure_depth_texture(
-            &context.device,
-            context.size.width,
-            context.size.height,
-        );
-
-        Self {
+        let mut render_system = Self {
             context,
             slice_pipeline,
             render_pipeline,
+            upscale_pipeline,
+            timer,
+            render_scale: clamp_render_scale(render_config.render_scale),
             render_config,
             camera_config,
-        }
+            show_probe_ray: false,
+            probe_hit: None,
+            frame_stats: FrameStats::new(Box::new(SystemClock::new())),
+        };
+        render_system.resize_scene_targets();
+        render_system
+    }
+
+    /// Get the counts from the last frame rendered to the window
+    ///
+    /// Screenshots rendered with [`render_to_image`](Self::render_to_image)
+    /// do not change these.
+    pub fn stats(&self) -> RenderStats {
+        self.frame_stats.stats
+    }
+
+    /// Show or hide the crosshair probe ray overlay
+    ///
+    /// The ray is drawn from the camera along its forward direction, with
+    /// markers where it crosses the slice and where it hit (see
+    /// [`set_probe_hit`](Self::set_probe_hit)).
+    pub fn set_show_probe_ray(&mut self, show: bool) {
+        self.show_probe_ray = show;
+    }
+
+    /// Check if the probe ray overlay is shown
+    pub fn show_probe_ray(&self) -> bool {
+        self.show_probe_ray
+    }
+
+    /// Set where the crosshair ray hit something, drawn by the probe overlay
+    pub fn set_probe_hit(&mut self, hit: Option<Vec4>) {
+        self.probe_hit = hit;
     }
 
     /// Handle window resize
     pub fn resize(&mut self, width: u32, height: u32) {
         self.context
             .resize(winit::dpi::PhysicalSize::new(width, height));
+        self.resize_scene_targets();
+    }
+
+    /// Set the scene resolution relative to the window
+    ///
+    /// Below 1.0 the scene renders into a smaller offscreen target that is
+    /// upscaled to the window. Clamped to `MIN_RENDER_SCALE..=1.0`.
+    pub fn set_render_scale(&mut self, scale: f32) {
+        self.render_scale = clamp_render_scale(scale);
+        self.resize_scene_targets();
+    }
+
+    /// Get the scene resolution relative to the window
+    pub fn render_scale(&self) -> f32 {
+        self.render_scale
+    }
+
+    /// Size the scene is rendered at, before any upscaling
+    pub fn scene_size(&self) -> (u32, u32) {
+        let (width, height) = self.size();
+        scaled_size(width, height, self.render_scale)
+    }
+
+    /// Check if the scene renders below the window resolution
+    fn is_scaled(&self) -> bool {
+        self.scene_size() != self.size()
+    }
+
+    /// Size the depth buffer (and the upscale target, if used) to the scene
+    fn resize_scene_targets(&mut self) {
+        let (width, height) = self.scene_size();
         self.render_pipeline.ensure_depth_texture(&self.context.device, width, height);
+        if self.is_scaled() {
+            let (window_width, window_height) = self.size();
+            self.upscale_pipeline
+                .ensure_target(&self.context.device, window_width, window_height, self.render_scale);
+        }
     }
 
     /// Upload geometry to GPU
@@ -94,6 +247,13 @@ impl RenderSystem {
             &geometry.vertices,
             &geometry.tetrahedra,
         );
+        self.slice_pipeline.set_slice_capping(geometry.caps_slices());
+        self.render_pipeline.upload_materials(
+            &self.context.device,
+            &self.context.queue,
+            geometry.materials(),
+        );
+        self.frame_stats.stats.uploaded_vertices = geometry.vertex_count() as u32;
         log::info!(
             "Uploaded {} vertices and {} tetrahedra",
             geometry.vertex_count(),
@@ -107,66 +267,125 @@ impl RenderSystem {
         camera: &Camera4D,
         geometry: &RenderableGeometry,
     ) -> Result<(), RenderError> {
-        let pos = camera.position;
-        let eye_3d = [pos.x, pos.y, pos.z];
-        let camera_pos_4d = [pos.x, pos.y, pos.z, pos.w];
-
-        // Update slice parameters
-        let camera_matrix = camera.rotation_matrix();
-        let slice_params = SliceParams {
-            slice_w: camera.get_slice_w(),
-            tetrahedron_count: geometry.tetrahedron_count() as u32,
-            _padding: [0.0; 2],
-            camera_matrix,
-            camera_eye: eye_3d,
-            _padding2: 0.0,
-            camera_position: camera_pos_4d,
+        // Get surface texture
+        let output = match self.context.surface.get_current_texture() {
+            Ok(output) => output,
+            Err(wgpu::SurfaceError::Lost) => return Err(RenderError::SurfaceLost),
+            Err(wgpu::SurfaceError::OutOfMemory) => return Err(RenderError::OutOfMemory),
+            Err(e) => return Err(RenderError::Other(format!("{:?}", e))),
         };
-        self.slice_pipeline
-            .update_params(&self.context.queue, &slice_params);
 
-        // Create view and projection matrices
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
         let aspect = self.context.aspect_ratio();
-        let proj_matrix = perspective_matrix(
-            self.camera_config.fov.to_radians(),
-            aspect,
-            self.camera_config.near,
-            self.camera_config.far,
-        );
+        let draw_calls = self.encode_and_submit(camera, geometry, aspect, &view, self.is_scaled());
+        output.present();
+
+        let timings = self.timer.timings(&self.context.device);
+        let stats = RenderStats {
+            triangles: self.slice_pipeline.triangle_count(&self.context.device),
+            draw_calls,
+            sliced_tetrahedra: self.slice_pipeline.sliced_tetrahedron_count(),
+            slice_pass_us: timings.slice_us,
+            render_pass_us: timings.render_us,
+            ..self.frame_stats.stats
+        };
+        self.frame_stats.record(camera, geometry, stats);
+
+        Ok(())
+    }
+
+    /// Render the current cross-section into an offscreen image
+    ///
+    /// Uses the surface format, so the image matches what the window shows.
+    /// Blocks until the GPU has finished and the pixels are read back.
+    pub fn render_to_image(
+        &mut self,
+        camera: &Camera4D,
+        geometry: &RenderableGeometry,
+        width: u32,
+        height: u32,
+    ) -> Result<RgbaImage, ScreenshotError> {
+        if width == 0 || height == 0 {
+            return Err(ScreenshotError::EmptySize);
+        }
+        let texture = self.context.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Screenshot Texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.context.config.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        // The depth buffer must match the target, then go back to the scene size
+        self.render_pipeline.ensure_depth_texture(&self.context.device, width, height);
+        self.encode_and_submit(camera, geometry, width as f32 / height as f32, &view, false);
+        self.resize_scene_targets();
+
+        read_texture(&self.context.device, &self.context.queue, &texture)
+    }
+
+    /// Render the current cross-section at window size and save it as a PNG
+    pub fn save_screenshot(
+        &mut self,
+        camera: &Camera4D,
+        geometry: &RenderableGeometry,
+        path: impl AsRef<Path>,
+    ) -> Result<(), ScreenshotError> {
+        let (width, height) = self.size();
+        let image = self.render_to_image(camera, geometry, width, height)?;
+        image.save(path)?;
+        Ok(())
+    }
+
+    /// Update uniforms, then slice and render the geometry into `view`
+    ///
+    /// With `scaled`, the scene goes into the upscale target first and is
+    /// stretched onto `view`. Returns the number of draw calls issued.
+    fn encode_and_submit(
+        &mut self,
+        camera: &Camera4D,
+        geometry: &RenderableGeometry,
+        aspect: f32,
+        view: &wgpu::TextureView,
+        scaled: bool,
+    ) -> u32 {
+        // Create view and projection matrices
+        let proj_matrix = projection_matrix(&self.camera_config, aspect);
 
         // View matrix is identity (slice shader outputs camera-space coordinates)
-        let view_matrix = [
-            [1.0, 0.0, 0.0, 0.0],
-            [0.0, 1.0, 0.0, 0.0],
-            [0.0, 0.0, 1.0, 0.0],
-            [0.0, 0.0, 0.0, 1.0],
-        ];
+        let view_matrix = camera.view_matrix();
+
+        // Update slice parameters, culling against this view
+        let slice_params = slice_params(camera, geometry, self.render_config.slice_epsilon);
+        self.slice_pipeline
+            .set_view_frustum(Some(ViewFrustum::from_matrix(mat4::mul(proj_matrix, view_matrix))));
+        self.slice_pipeline
+            .update_params(&self.context.queue, &slice_params);
 
         let render_uniforms = RenderUniforms {
             view_matrix,
             projection_matrix: proj_matrix,
-            light_dir: self.render_config.light_dir,
-            _padding: 0.0,
             ambient_strength: self.render_config.ambient_strength,
             diffuse_strength: self.render_config.diffuse_strength,
             w_color_strength: self.render_config.w_color_strength,
             w_range: self.render_config.w_range,
+            // The configured lights were given to the pipeline on creation
+            ..RenderUniforms::default()
         };
         self.render_pipeline
             .update_uniforms(&self.context.queue, &render_uniforms);
 
-        // Get surface texture
-        let output = match self.context.surface.get_current_texture() {
-            Ok(output) => output,
-            Err(wgpu::SurfaceError::Lost) => return Err(RenderError::SurfaceLost),
-            Err(wgpu::SurfaceError::OutOfMemory) => return Err(RenderError::OutOfMemory),
-            Err(e) => return Err(RenderError::Other(format!("{:?}", e))),
-        };
-
-        let view = output
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
-
         // Create command encoder
         let mut encoder = self
             .context
@@ -177,17 +396,31 @@ impl RenderSystem {
 
         // Reset counter and run compute pass
         self.slice_pipeline.reset_counter(&self.context.queue);
-        self.slice_pipeline.run_slice_pass(&mut encoder);
+        let slice_timed = self.slice_pipeline.run_slice_pass_timed(&mut encoder, self.timer.slice_writes());
+        self.slice_pipeline.copy_counter_for_readback(&mut encoder);
 
         // Copy triangle count to indirect buffer
         self.render_pipeline
             .prepare_indirect_draw(&mut encoder, self.slice_pipeline.counter_buffer());
 
-        // Render pass
+        // Debug lines drawn over the cross-section
+        let lines = if self.show_probe_ray {
+            ProbeRay::new(camera.position, camera.forward(), PROBE_RAY_LENGTH)
+                .with_hit(self.probe_hit)
+                .lines(camera)
+        } else {
+            DebugLines::new()
+        };
+        self.render_pipeline
+            .upload_lines(&self.context.device, &self.context.queue, lines.vertices());
+
+        // Render pass, into the reduced-resolution target when scaling
+        let target = if scaled { self.upscale_pipeline.target_view().cloned() } else { None };
+        let scene_view = target.as_ref().unwrap_or(view);
         let bg = &self.render_config.background_color;
-        self.render_pipeline.render(
+        self.render_pipeline.render_timed(
             &mut encoder,
-            &view,
+            scene_view,
             self.slice_pipeline.output_buffer(),
             wgpu::Color {
                 r: bg[0] as f64,
@@ -195,13 +428,20 @@ impl RenderSystem {
                 b: bg[2] as f64,
                 a: bg[3] as f64,
             },
+            self.timer.render_writes(),
         );
+        self.render_pipeline.render_lines(&mut encoder, scene_view);
+        if target.is_some() {
+            self.upscale_pipeline.blit(&mut encoder, view);
+        }
+        self.timer.resolve(&mut encoder, slice_timed);
 
         // Submit
         self.context.queue.submit(std::iter::once(encoder.finish()));
-        output.present();
+        self.slice_pipeline.request_counter_readback();
+        self.timer.request_readback();
 
-        Ok(())
+        1 + u32::from(!lines.is_empty()) + u32::from(target.is_some())
     }
 
     /// Get current surface size
@@ -210,6 +450,60 @@ impl RenderSystem {
     }
 }
 
+/// Render scales stepped through at runtime, from full resolution down
+const RENDER_SCALE_PRESETS: [f32; 3] = [1.0, 0.75, 0.5];
+
+/// A render scale limited to what the scene target supports, `MIN_RENDER_SCALE..=1.0`
+fn clamp_render_scale(scale: f32) -> f32 {
+    scale.clamp(MIN_RENDER_SCALE, 1.0)
+}
+
+/// The next preset below `current`, wrapping back to full resolution
+pub fn next_render_scale(current: f32) -> f32 {
+    RENDER_SCALE_PRESETS
+        .into_iter()
+        .find(|&scale| scale < current - 1e-3)
+        .unwrap_or(RENDER_SCALE_PRESETS[0])
+}
+
+/// Projection matrix for the configured projection mode
+fn projection_matrix(config: &CameraConfig, aspect: f32) -> [[f32; 4]; 4] {
+    match config.projection {
+        Projection::Perspective => perspective_matrix(config.fov.to_radians(), aspect, config.near, config.far),
+        Projection::Orthographic => {
+            let half_height = config.ortho_size;
+            let half_width = half_height * aspect;
+            orthographic_matrix(-half_width, half_width, -half_height, half_height, config.near, config.far)
+        }
+    }
+}
+
+/// Key light from `light_dir`, plus the fill light if it has any intensity
+fn scene_lights(config: &RenderingConfig) -> Vec<Light> {
+    let mut lights = vec![Light::new(config.light_dir)];
+    if config.fill_light_intensity > 0.0 {
+        lights.push(Light::new(config.fill_light_dir).with_intensity(config.fill_light_intensity));
+    }
+    lights
+}
+
+/// Slice parameters for viewing `geometry` from `camera`
+fn slice_params(camera: &Camera4D, geometry: &RenderableGeometry, slice_epsilon: f32) -> SliceParams {
+    let pos = camera.view_position();
+    let normal = camera.slice_normal.normalized();
+    SliceParams {
+        slice_w: camera.get_slice_w(),
+        tetrahedron_count: geometry.tetrahedron_count() as u32,
+        slice_epsilon,
+        slab_thickness: camera.slab_thickness,
+        camera_matrix: camera.view_rotation_matrix(),
+        camera_eye: [pos.x, pos.y, pos.z],
+        _padding2: 0.0,
+        camera_position: [pos.x, pos.y, pos.z, pos.w],
+        slice_normal: [normal.x, normal.y, normal.z, normal.w],
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,4 +517,133 @@ mod tests {
             "Render error: test"
         );
     }
+
+    #[test]
+    fn test_cpu_slice_counts_for_known_scene() {
+        use rust4d_render::{pipeline::slice_on_cpu, Entity, Material, ShapeRef, Tesseract4D, Transform4D, World};
+
+        let tesseract_at = |position| {
+            let mut entity = Entity::with_material(ShapeRef::shared(Tesseract4D::new(2.0)), Material::default());
+            entity.transform = Transform4D::from_position(position);
+            entity
+        };
+        let triangles = |geometry: &RenderableGeometry, camera: &Camera4D| {
+            slice_on_cpu(&geometry.vertices, &geometry.tetrahedra, &slice_params(camera, geometry, 1e-4)).len() / 3
+        };
+        let camera = Camera4D::new();
+
+        let single = RenderableGeometry::from_entity(&tesseract_at(Vec4::ZERO));
+        let per_tesseract = triangles(&single, &camera);
+        assert!(per_tesseract > 0);
+
+        // Two tesseracts on the slice, one far off it in W
+        let mut world = World::new();
+        for position in [Vec4::ZERO, Vec4::new(4.0, 0.0, 0.0, 0.0), Vec4::new(0.0, 0.0, 0.0, 5.0)] {
+            world.add_entity(tesseract_at(position));
+        }
+        let geometry = RenderableGeometry::from_world(&world);
+
+        // What the CPU slicing path uploads and RenderSystem::stats reports
+        assert_eq!(triangles(&geometry, &camera), 2 * per_tesseract);
+        assert_eq!(geometry.entities_in_slice(&camera), 2);
+        assert_eq!(geometry.vertex_count(), 3 * 16);
+    }
+
+    #[test]
+    fn test_frame_stats_are_timed_by_the_clock() {
+        use crate::systems::ManualClock;
+        use rust4d_render::{Entity, Material, ShapeRef, Tesseract4D, World};
+
+        let mut world = World::new();
+        world.add_entity(Entity::with_material(ShapeRef::shared(Tesseract4D::new(2.0)), Material::default()));
+        let geometry = RenderableGeometry::from_world(&world);
+        let camera = Camera4D::new();
+        let clock = ManualClock::new();
+        let mut frame_stats = FrameStats::new(Box::new(clock.clone()));
+        let counts = RenderStats { triangles: 12, draw_calls: 2, uploaded_vertices: 16, ..RenderStats::default() };
+
+        // The first frame has nothing to time against
+        frame_stats.record(&camera, &geometry, counts);
+        assert_eq!(frame_stats.stats, RenderStats { visible_entities: 1, ..counts });
+
+        clock.advance(Duration::from_millis(20));
+        frame_stats.record(&camera, &geometry, counts);
+        assert_eq!(frame_stats.stats.frame_time_ms, 20.0);
+        assert_eq!(frame_stats.stats.visible_entities, 1);
+        assert_eq!(frame_stats.stats.triangles, 12);
+    }
+
+    #[test]
+    fn test_frame_stats_reuse_visibility_while_nothing_moves() {
+        use rust4d_render::{Entity, Material, ShapeRef, Tesseract4D, World};
+
+        let mut world = World::new();
+        let key = world.add_entity(Entity::with_material(ShapeRef::shared(Tesseract4D::new(2.0)), Material::default()));
+        world.clear_all_dirty();
+        let mut geometry = RenderableGeometry::from_world(&world);
+        let mut camera = Camera4D::new();
+        let mut frame_stats = FrameStats::new(Box::new(SystemClock::new()));
+
+        for _ in 0..3 {
+            frame_stats.record(&camera, &geometry, RenderStats::default());
+        }
+        assert_eq!(frame_stats.visibility.recompute_count(), 1);
+
+        // Moving the camera off the entity's W recounts it
+        camera.adjust_slice_offset(5.0);
+        frame_stats.record(&camera, &geometry, RenderStats::default());
+        assert_eq!(frame_stats.visibility.recompute_count(), 2);
+        assert_eq!(frame_stats.stats.visible_entities, 0);
+
+        // So does moving the entity onto the new slice
+        world.get_entity_mut(key).unwrap().set_position(Vec4::new(0.0, 0.0, 0.0, 5.0));
+        assert!(geometry.update_transforms(&world));
+        frame_stats.record(&camera, &geometry, RenderStats::default());
+        assert_eq!(frame_stats.visibility.recompute_count(), 3);
+        assert_eq!(frame_stats.stats.visible_entities, 1);
+    }
+
+    #[test]
+    fn test_render_scale_is_clamped() {
+        assert_eq!(clamp_render_scale(0.1), MIN_RENDER_SCALE);
+        assert_eq!(clamp_render_scale(3.0), 1.0);
+        assert_eq!(clamp_render_scale(0.5), 0.5);
+    }
+
+    #[test]
+    fn test_next_render_scale_cycles_presets() {
+        assert_eq!(next_render_scale(1.0), 0.75);
+        assert_eq!(next_render_scale(0.75), 0.5);
+        assert_eq!(next_render_scale(0.5), 1.0);
+        // A configured scale between presets steps to the next one down
+        assert_eq!(next_render_scale(0.9), 0.75);
+        assert_eq!(next_render_scale(0.3), 1.0);
+    }
+
+    #[test]
+    fn test_scene_lights_add_fill_when_enabled() {
+        let single = RenderingConfig { fill_light_intensity: 0.0, ..RenderingConfig::default() };
+        let lights = scene_lights(&single);
+        assert_eq!(lights, vec![Light::new(single.light_dir)]);
+
+        let config = RenderingConfig { fill_light_intensity: 0.35, ..RenderingConfig::default() };
+        let lights = scene_lights(&config);
+        assert_eq!(lights.len(), 2);
+        assert_eq!(lights[1].direction, config.fill_light_dir);
+        assert_eq!(lights[1].intensity, 0.35);
+    }
+
+    #[test]
+    fn test_projection_matrix_follows_config() {
+        let mut config = CameraConfig::default();
+        assert_eq!(projection_matrix(&config, 2.0), perspective_matrix(config.fov.to_radians(), 2.0, config.near, config.far));
+
+        // Orthographic: ortho_size units above center, widened by the aspect ratio
+        config.projection = Projection::Orthographic;
+        config.ortho_size = 4.0;
+        let proj = projection_matrix(&config, 2.0);
+        assert_eq!(proj[0][0], 1.0 / 8.0);
+        assert_eq!(proj[1][1], 1.0 / 4.0);
+        assert_eq!(proj[2][3], 0.0);
+    }
 }
@@ -5,13 +5,45 @@
 //! - Input → physics movement
 //! - Physics stepping
 //! - Camera synchronization
+//! - Walk vs free-fly (noclip) camera modes
+//! - Locking the player's W coordinate (freeze W)
+//! - Pausing the clock while the window is in the background
+//! - Reading frame times from an injectable [`Clock`]
 
-use std::time::Instant;
+use std::time::Duration;
 use rust4d_core::SceneManager;
 use rust4d_input::CameraController;
 use rust4d_math::Vec4;
 use rust4d_render::camera4d::Camera4D;
 
+use super::clock::{Clock, SystemClock};
+
+/// Fall speed below which landings don't shake the view
+const LANDING_SHAKE_MIN_SPEED: f32 = 10.0;
+
+/// Trauma added per unit of fall speed above the minimum
+const LANDING_SHAKE_PER_SPEED: f32 = 0.04;
+
+/// How player input moves the camera
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CameraMode {
+    /// Movement drives the physics player; the camera follows the player body
+    #[default]
+    Walk,
+    /// Noclip: the camera moves directly, ignoring gravity and collisions
+    FreeFly,
+}
+
+impl CameraMode {
+    /// Get the other mode
+    pub fn toggled(self) -> Self {
+        match self {
+            CameraMode::Walk => CameraMode::FreeFly,
+            CameraMode::FreeFly => CameraMode::Walk,
+        }
+    }
+}
+
 /// Result of a simulation update
 pub struct SimulationResult {
     /// Whether geometry needs to be rebuilt and re-uploaded
@@ -26,17 +58,85 @@ pub struct SimulationResult {
 /// - Physics stepping
 /// - Camera synchronization
 pub struct SimulationSystem {
-    last_frame: Instant,
+    clock: Box<dyn Clock>,
+    last_frame: Duration,
+    mode: CameraMode,
+    paused: bool,
+    /// Hold the player at its current W (walk mode only)
+    w_locked: bool,
 }
 
 impl SimulationSystem {
-    /// Create a new simulation system
+    /// Create a new simulation system driven by the wall clock
     pub fn new() -> Self {
+        Self::with_clock(Box::new(SystemClock::new()))
+    }
+
+    /// Create a simulation system that reads frame times from `clock`
+    pub fn with_clock(clock: Box<dyn Clock>) -> Self {
+        let last_frame = clock.now();
         Self {
-            last_frame: Instant::now(),
+            clock,
+            last_frame,
+            mode: CameraMode::Walk,
+            paused: false,
+            w_locked: false,
         }
     }
 
+    /// Swap the frame clock, restarting the frame timer from the new clock
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.last_frame = clock.now();
+        self.clock = clock;
+    }
+
+    /// Stop the simulation clock (e.g. when the window loses focus)
+    ///
+    /// While paused, [`update`](Self::update) does not step the world.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Restart the simulation clock with a fresh frame timer
+    ///
+    /// The time spent paused is discarded rather than fed into the next step.
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.last_frame = self.clock.now();
+    }
+
+    /// Get the current camera mode
+    pub fn mode(&self) -> CameraMode {
+        self.mode
+    }
+
+    /// Switch between walk and free-fly, returning the new mode
+    ///
+    /// The player body is stopped either way, so it doesn't keep walking
+    /// off while the camera flies or resume a fall when walking again.
+    /// Returning to walk mode snaps the camera back to the player body.
+    pub fn toggle_mode(&mut self, scene_manager: &mut SceneManager) -> CameraMode {
+        self.mode = self.mode.toggled();
+        let player = scene_manager
+            .active_world_mut()
+            .and_then(|w| w.physics_mut())
+            .and_then(|p| p.player_mut());
+        if let Some(player) = player {
+            player.velocity = Vec4::ZERO;
+        }
+        self.mode
+    }
+
+    /// Lock or unlock the player's W coordinate, returning the new state
+    ///
+    /// While locked, walking moves the player in 3D only, which keeps new
+    /// players from drifting through W by accident. The lock is reapplied
+    /// every walk frame, so it survives physics resets and scene changes.
+    pub fn toggle_w_lock(&mut self) -> bool {
+        self.w_locked = !self.w_locked;
+        self.w_locked
+    }
+
     /// Run one simulation frame
     ///
     /// # Arguments
@@ -55,12 +155,36 @@ impl SimulationSystem {
         cursor_captured: bool,
     ) -> SimulationResult {
         // 1. Calculate delta time
-        let now = Instant::now();
-        let raw_dt = (now - self.last_frame).as_secs_f32();
+        let now = self.clock.now();
+        if self.paused {
+            self.last_frame = now;
+            return SimulationResult { geometry_dirty: false };
+        }
+        let raw_dt = now.saturating_sub(self.last_frame).as_secs_f32();
         // Cap dt to prevent huge physics steps on first frame or after window focus
         let dt = raw_dt.min(1.0 / 30.0); // Max 33ms per frame
         self.last_frame = now;
 
+        self.step(scene_manager, camera, controller, dt, cursor_captured)
+    }
+
+    /// Run one simulation frame with an explicit time step
+    pub fn step(
+        &mut self,
+        scene_manager: &mut SceneManager,
+        camera: &mut Camera4D,
+        controller: &mut CameraController,
+        dt: f32,
+        cursor_captured: bool,
+    ) -> SimulationResult {
+        // Advance any slice sweep animation and view shake
+        camera.update_slice(dt);
+        camera.update_shake(dt);
+
+        if self.mode == CameraMode::FreeFly {
+            return Self::step_free_fly(scene_manager, camera, controller, dt, cursor_captured);
+        }
+
         // 2. Get movement input from controller
         let (forward_input, right_input) = controller.get_movement_input();
         let w_input = controller.get_w_input();
@@ -86,11 +210,12 @@ impl SimulationSystem {
             .active_world_mut()
             .and_then(|w| w.physics_mut())
         {
+            physics.set_player_w_locked(self.w_locked);
             physics.apply_player_movement(move_dir * move_speed);
         }
 
-        // 5. Handle jump
-        if controller.consume_jump() {
+        // 5. Handle jump (a held key keeps trying when auto-bhop is on)
+        if controller.wants_jump() {
             if let Some(physics) = scene_manager
                 .active_world_mut()
                 .and_then(|w| w.physics_mut())
@@ -99,8 +224,23 @@ impl SimulationSystem {
             }
         }
 
-        // 6. Step world physics
+        // 6. Step world physics, shaking the view on hard landings
+        let fall_speed = scene_manager
+            .active_world()
+            .and_then(|w| w.physics())
+            .and_then(|p| p.player().map(|player| (player, p.config.up())))
+            .filter(|(player, _)| !player.grounded)
+            .map(|(player, up)| -player.velocity.dot(up));
         scene_manager.update(dt);
+        let landed = scene_manager
+            .active_world()
+            .and_then(|w| w.physics())
+            .is_some_and(|p| p.player_is_grounded());
+        if let (Some(speed), true) = (fall_speed, landed) {
+            if speed > LANDING_SHAKE_MIN_SPEED {
+                camera.add_shake((speed - LANDING_SHAKE_MIN_SPEED) * LANDING_SHAKE_PER_SPEED);
+            }
+        }
 
         // 7. Check for dirty entities
         let geometry_dirty = scene_manager
@@ -117,7 +257,8 @@ impl SimulationSystem {
             camera.position = pos;
         }
 
-        // 9. Apply mouse look for camera rotation
+        // 9. Apply mouse look for camera rotation (gated off while a modal overlay is up)
+        controller.set_look_enabled(!scene_manager.has_modal_overlay());
         controller.update(camera, dt, cursor_captured);
 
         // 10. Re-sync position after controller (discard its movement, keep rotation)
@@ -131,6 +272,31 @@ impl SimulationSystem {
 
         SimulationResult { geometry_dirty }
     }
+
+    /// Free-fly frame: the controller moves the camera directly (including
+    /// along W) and the player body is neither driven nor followed
+    fn step_free_fly(
+        scene_manager: &mut SceneManager,
+        camera: &mut Camera4D,
+        controller: &mut CameraController,
+        dt: f32,
+        cursor_captured: bool,
+    ) -> SimulationResult {
+        // Jumps pressed while flying shouldn't fire on landing
+        controller.consume_jump();
+
+        // The rest of the world keeps simulating
+        scene_manager.update(dt);
+        let geometry_dirty = scene_manager
+            .active_world()
+            .map(|w| w.has_dirty_entities())
+            .unwrap_or(false);
+
+        controller.set_look_enabled(!scene_manager.has_modal_overlay());
+        controller.update(camera, dt, cursor_captured);
+
+        SimulationResult { geometry_dirty }
+    }
 }
 
 impl Default for SimulationSystem {
@@ -142,21 +308,343 @@ impl Default for SimulationSystem {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::systems::clock::ManualClock;
+
+    use rust4d_core::{ActiveScene, EntityTemplate, Material, Scene, ShapeTemplate, Transform4D};
+    use rust4d_physics::{PhysicsConfig, PhysicsMaterial, StaticCollider};
+    use winit::event::ElementState;
+    use winit::keyboard::KeyCode;
+
+    /// Scene with a player at the origin and no gravity, camera on the player
+    fn player_scene() -> (SceneManager, Camera4D) {
+        let template = Scene::new("Flat").with_player_spawn(0.0, 0.0, 0.0, 0.0);
+        let scene = ActiveScene::from_template(&template, Some(PhysicsConfig::new(0.0)), 0.5);
+        let mut manager = SceneManager::new();
+        manager.register_active_scene("flat", scene);
+        manager.push_scene("flat").unwrap();
+        (manager, Camera4D::new())
+    }
+
+    /// Scene with gravity and a floor at y=0, player standing on it
+    fn floor_scene() -> (SceneManager, Camera4D) {
+        let mut template = Scene::new("Floor").with_player_spawn(0.0, 0.5, 0.0, 0.0);
+        template.add_entity(
+            EntityTemplate::new(
+                ShapeTemplate::hyperplane(0.0, 10.0, 1, 5.0, 0.001),
+                Transform4D::identity(),
+                Material::GRAY,
+            )
+            .with_tag("static"),
+        );
+        let scene = ActiveScene::from_template(&template, Some(PhysicsConfig::new(-20.0)), 0.5);
+        let mut manager = SceneManager::new();
+        manager.register_active_scene("floor", scene);
+        manager.push_scene("floor").unwrap();
+        (manager, Camera4D::new())
+    }
+
+    fn player_body(manager: &SceneManager) -> &rust4d_physics::RigidBody4D {
+        manager.active_world().and_then(|w| w.physics()).and_then(|p| p.player()).unwrap()
+    }
+
+    /// Hold jump and step until the player has jumped and landed again
+    fn jump_and_land(manager: &mut SceneManager, camera: &mut Camera4D, controller: &mut CameraController) {
+        let mut sim = SimulationSystem::new();
+        let dt = 1.0 / 60.0;
+        // Settle onto the floor first
+        sim.step(manager, camera, controller, dt, false);
+        assert!(player_body(manager).grounded);
+
+        controller.process_keyboard(KeyCode::Space, ElementState::Pressed);
+        sim.step(manager, camera, controller, dt, false);
+        assert!(!player_body(manager).grounded, "first press should jump");
+        for _ in 0..600 {
+            sim.step(manager, camera, controller, dt, false);
+            if player_body(manager).grounded {
+                return;
+            }
+        }
+        panic!("player never landed");
+    }
+
+    fn player_position(manager: &SceneManager) -> Vec4 {
+        manager.active_world().and_then(|w| w.physics()).and_then(|p| p.player_position()).unwrap()
+    }
 
     #[test]
-    fn test_delta_time_capped() {
-        let sim = SimulationSystem::new();
-        // Simulate a 100ms pause (first frame or window focus)
-        std::thread::sleep(std::time::Duration::from_millis(100));
+    fn test_camera_mode_toggle() {
+        let (mut manager, _) = player_scene();
+        let mut sim = SimulationSystem::new();
+        assert_eq!(sim.mode(), CameraMode::Walk);
+        assert_eq!(sim.toggle_mode(&mut manager), CameraMode::FreeFly);
+        assert_eq!(sim.toggle_mode(&mut manager), CameraMode::Walk);
+    }
 
-        // Can't fully test without scene manager, but we can verify construction
-        assert!(sim.last_frame.elapsed().as_millis() >= 100);
+    #[test]
+    fn test_walk_mode_moves_player_body() {
+        let (mut manager, mut camera) = player_scene();
+        let mut controller = CameraController::new().with_move_speed(2.0);
+        let mut sim = SimulationSystem::new();
+
+        controller.process_keyboard(KeyCode::KeyW, ElementState::Pressed);
+        sim.step(&mut manager, &mut camera, &mut controller, 0.1, false);
+
+        let physics = manager.active_world().unwrap().physics().unwrap();
+        let velocity = physics.player().unwrap().velocity;
+        assert!((Vec4::new(velocity.x, 0.0, velocity.z, velocity.w).length() - 2.0).abs() < 1e-4);
+
+        // The camera follows the physics player
+        assert_eq!(camera.position, player_position(&manager));
+        assert!(player_position(&manager).length() > 0.0);
+    }
+
+    #[test]
+    fn test_w_lock_keeps_player_w() {
+        let (mut manager, mut camera) = player_scene();
+        let mut controller = CameraController::new().with_move_speed(2.0).with_w_move_speed(1.0);
+        let mut sim = SimulationSystem::new();
+        assert!(sim.toggle_w_lock());
+
+        controller.process_keyboard(KeyCode::KeyW, ElementState::Pressed);
+        controller.process_keyboard(KeyCode::KeyQ, ElementState::Pressed);
+        sim.step(&mut manager, &mut camera, &mut controller, 0.1, false);
+        assert_eq!(player_position(&manager).w, 0.0);
+        assert!(player_position(&manager).length() > 0.0);
+
+        // Unlocked, the same input moves through W again
+        assert!(!sim.toggle_w_lock());
+        sim.step(&mut manager, &mut camera, &mut controller, 0.1, false);
+        assert_ne!(player_position(&manager).w, 0.0);
+    }
+
+    #[test]
+    fn test_free_fly_moves_camera_directly() {
+        let (mut manager, mut camera) = player_scene();
+        let mut controller = CameraController::new().with_move_speed(2.0).with_w_move_speed(1.0);
+        let mut sim = SimulationSystem::new();
+        sim.toggle_mode(&mut manager);
+
+        let start = camera.position;
+        controller.process_keyboard(KeyCode::KeyW, ElementState::Pressed);
+        controller.process_keyboard(KeyCode::KeyQ, ElementState::Pressed);
+        sim.step(&mut manager, &mut camera, &mut controller, 0.1, false);
+
+        // Forward and W movement applied straight to the camera
+        assert!(((camera.position - start).length() - (0.2f32.powi(2) + 0.1f32.powi(2)).sqrt()).abs() < 1e-4);
+
+        // The player body was not driven or followed
+        let physics = manager.active_world().unwrap().physics().unwrap();
+        assert_eq!(physics.player().unwrap().velocity, Vec4::ZERO);
+        assert_eq!(player_position(&manager), Vec4::ZERO);
+    }
+
+    #[test]
+    fn test_leaving_free_fly_snaps_back_to_player() {
+        let (mut manager, mut camera) = player_scene();
+        let mut controller = CameraController::new();
+        let mut sim = SimulationSystem::new();
+
+        sim.toggle_mode(&mut manager);
+        controller.process_keyboard(KeyCode::KeyW, ElementState::Pressed);
+        sim.step(&mut manager, &mut camera, &mut controller, 0.1, false);
+        assert_ne!(camera.position, player_position(&manager));
+
+        controller.process_keyboard(KeyCode::KeyW, ElementState::Released);
+        sim.toggle_mode(&mut manager);
+        sim.step(&mut manager, &mut camera, &mut controller, 0.1, false);
+        assert_eq!(camera.position, player_position(&manager));
+    }
+
+    #[test]
+    fn test_switching_modes_stops_the_player() {
+        let (mut manager, mut camera) = floor_scene();
+        let mut controller = CameraController::new().with_move_speed(2.0);
+        let mut sim = SimulationSystem::new();
+
+        // Walking when switching to free-fly
+        controller.process_keyboard(KeyCode::KeyW, ElementState::Pressed);
+        sim.step(&mut manager, &mut camera, &mut controller, 0.1, false);
+        assert_ne!(player_body(&manager).velocity, Vec4::ZERO);
+        sim.toggle_mode(&mut manager);
+        assert_eq!(player_body(&manager).velocity, Vec4::ZERO);
+
+        // Falling when switching back to walk
+        manager.active_world_mut().unwrap().physics_mut().unwrap().player_mut().unwrap().velocity =
+            Vec4::new(0.0, -30.0, 0.0, 0.0);
+        sim.toggle_mode(&mut manager);
+        assert_eq!(player_body(&manager).velocity, Vec4::ZERO);
+    }
+
+    /// Simulation on a manual clock, plus a handle to drive it
+    fn manual_sim() -> (SimulationSystem, ManualClock) {
+        let clock = ManualClock::new();
+        (SimulationSystem::with_clock(Box::new(clock.clone())), clock)
+    }
+
+    /// Distance the player walks forward over a series of clock advances
+    fn walk_distance(frame_times_ms: &[u64]) -> f32 {
+        let (mut manager, mut camera) = player_scene();
+        let mut controller = CameraController::new().with_move_speed(1.0);
+        let (mut sim, clock) = manual_sim();
+
+        controller.process_keyboard(KeyCode::KeyW, ElementState::Pressed);
+        for &ms in frame_times_ms {
+            clock.advance(Duration::from_millis(ms));
+            sim.update(&mut manager, &mut camera, &mut controller, false);
+        }
+        player_position(&manager).length()
+    }
+
+    #[test]
+    fn test_update_steps_by_clock_time() {
+        // Ten 10ms frames at 1 unit/s walk exactly 0.1 units
+        assert!((walk_distance(&[10; 10]) - 0.1).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_update_without_clock_advance_does_not_move() {
+        assert_eq!(walk_distance(&[0; 5]), 0.0);
+    }
+
+    #[test]
+    fn test_delta_time_capped() {
+        // A 100ms hitch only counts as one 33ms frame
+        assert!((walk_distance(&[100]) - 1.0 / 30.0).abs() < 1e-5);
+        assert!((walk_distance(&[100, 100, 100]) - 0.1).abs() < 1e-5);
     }
 
     #[test]
     fn test_default_construction() {
         let sim = SimulationSystem::default();
         // Just verify it constructs without panic
-        assert!(sim.last_frame.elapsed().as_millis() < 100);
+        assert_eq!(sim.mode(), CameraMode::Walk);
+        assert!(!sim.paused);
+    }
+
+    #[test]
+    fn test_paused_update_does_not_step() {
+        let (mut manager, mut camera) = player_scene();
+        let mut controller = CameraController::new();
+        let (mut sim, clock) = manual_sim();
+        let start = player_position(&manager);
+
+        sim.pause();
+        controller.process_keyboard(KeyCode::KeyW, ElementState::Pressed);
+        clock.advance(Duration::from_millis(20));
+        sim.update(&mut manager, &mut camera, &mut controller, false);
+
+        assert_eq!(player_position(&manager), start);
+        assert_eq!(sim.last_frame, clock.now());
+    }
+
+    #[test]
+    fn test_resume_resets_frame_timer() {
+        let (mut sim, clock) = manual_sim();
+        sim.pause();
+        clock.advance(Duration::from_millis(100));
+
+        sim.resume();
+        assert!(!sim.paused);
+        assert_eq!(sim.last_frame, clock.now());
+    }
+
+    #[test]
+    fn test_set_clock_restarts_frame_timer() {
+        let (mut sim, _) = manual_sim();
+        let replacement = ManualClock::new();
+        replacement.advance(Duration::from_secs(60));
+
+        sim.set_clock(Box::new(replacement.clone()));
+        assert_eq!(sim.last_frame, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_auto_bhop_rejumps_on_landing() {
+        let (mut manager, mut camera) = floor_scene();
+        let mut controller = CameraController::new().with_auto_bhop(true);
+        jump_and_land(&mut manager, &mut camera, &mut controller);
+
+        // Still holding jump: the next step launches again
+        let mut sim = SimulationSystem::new();
+        sim.step(&mut manager, &mut camera, &mut controller, 1.0 / 60.0, false);
+        assert!(!player_body(&manager).grounded);
+        assert!(player_body(&manager).velocity.y > 7.0);
+    }
+
+    #[test]
+    fn test_held_jump_without_auto_bhop_stays_grounded() {
+        let (mut manager, mut camera) = floor_scene();
+        let mut controller = CameraController::new();
+        jump_and_land(&mut manager, &mut camera, &mut controller);
+
+        // Only the small landing bounce, never a full jump
+        let mut sim = SimulationSystem::new();
+        for _ in 0..60 {
+            sim.step(&mut manager, &mut camera, &mut controller, 1.0 / 60.0, false);
+            assert!(player_body(&manager).velocity.y < 4.0);
+        }
+        assert!(player_body(&manager).grounded);
+
+        // A fresh press jumps again
+        controller.process_keyboard(KeyCode::Space, ElementState::Released);
+        controller.process_keyboard(KeyCode::Space, ElementState::Pressed);
+        sim.step(&mut manager, &mut camera, &mut controller, 1.0 / 60.0, false);
+        assert!(player_body(&manager).velocity.y > 7.0);
+    }
+
+    #[test]
+    fn test_hard_landing_shakes_camera() {
+        let (mut manager, mut camera) = floor_scene();
+        camera.set_shake_enabled(true);
+        let mut controller = CameraController::new();
+        let mut sim = SimulationSystem::new();
+        manager
+            .active_world_mut()
+            .and_then(|w| w.physics_mut())
+            .and_then(|p| p.player_mut())
+            .unwrap()
+            .set_position(Vec4::new(0.0, 8.0, 0.0, 0.0));
+
+        for _ in 0..120 {
+            sim.step(&mut manager, &mut camera, &mut controller, 1.0 / 60.0, false);
+            if player_body(&manager).grounded {
+                break;
+            }
+        }
+        assert!(player_body(&manager).grounded);
+        assert!(camera.shake().trauma > 0.0);
+    }
+
+    #[test]
+    fn test_hard_landing_on_a_ceiling_shakes_camera() {
+        let (mut manager, mut camera) = player_scene();
+        camera.set_shake_enabled(true);
+        let mut controller = CameraController::new();
+        let mut sim = SimulationSystem::new();
+        let physics = manager.active_world_mut().and_then(|w| w.physics_mut()).unwrap();
+        physics.config = PhysicsConfig::new(0.0).with_gravity_vector(Vec4::new(0.0, 20.0, 0.0, 0.0));
+        physics.add_static_collider(StaticCollider::aabb(
+            Vec4::new(0.0, 8.5, 0.0, 0.0),
+            Vec4::new(10.0, 0.5, 10.0, 10.0),
+            PhysicsMaterial::CONCRETE,
+        ));
+
+        for _ in 0..120 {
+            sim.step(&mut manager, &mut camera, &mut controller, 1.0 / 60.0, false);
+            if player_body(&manager).grounded {
+                break;
+            }
+        }
+        assert!(player_body(&manager).grounded);
+        assert!(camera.shake().trauma > 0.0);
+    }
+
+    #[test]
+    fn test_soft_landing_does_not_shake_camera() {
+        let (mut manager, mut camera) = floor_scene();
+        camera.set_shake_enabled(true);
+        let mut controller = CameraController::new();
+        jump_and_land(&mut manager, &mut camera, &mut controller);
+        assert_eq!(camera.shake().trauma, 0.0);
     }
 }
@@ -1,19 +1,64 @@
 //! Window management system
 //!
-//! Handles window creation, cursor capture/release, fullscreen toggle, and title updates.
+//! Handles window creation, cursor capture/release, fullscreen toggle, title updates,
+//! and idling the event loop while the window is unfocused.
 
 use std::sync::Arc;
 use winit::{
-    event_loop::ActiveEventLoop,
+    event_loop::{ActiveEventLoop, ControlFlow},
     window::{CursorGrabMode, Fullscreen, Window},
 };
 use crate::config::WindowConfig;
 
+/// Tracks window focus and the event loop mode that goes with it
+///
+/// Focused windows poll so frames run back to back; unfocused windows wait
+/// and only wake for events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FocusState {
+    focused: bool,
+}
+
+impl Default for FocusState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FocusState {
+    /// Start focused
+    pub fn new() -> Self {
+        Self { focused: true }
+    }
+
+    /// Check if the window has focus
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Record a focus event, returning true if the focus actually changed
+    pub fn set_focused(&mut self, focused: bool) -> bool {
+        let changed = self.focused != focused;
+        self.focused = focused;
+        changed
+    }
+
+    /// Event loop control flow for the current focus
+    pub fn control_flow(&self) -> ControlFlow {
+        if self.focused {
+            ControlFlow::Poll
+        } else {
+            ControlFlow::Wait
+        }
+    }
+}
+
 /// Manages the application window and cursor state
 pub struct WindowSystem {
     window: Arc<Window>,
     cursor_captured: bool,
     base_title: String,
+    focus: FocusState,
 }
 
 impl WindowSystem {
@@ -43,6 +88,7 @@ impl WindowSystem {
             window,
             cursor_captured: false,
             base_title: config.title.clone(),
+            focus: FocusState::new(),
         })
     }
 
@@ -56,6 +102,27 @@ impl WindowSystem {
         self.cursor_captured
     }
 
+    /// Check if the window has focus
+    pub fn is_focused(&self) -> bool {
+        self.focus.is_focused()
+    }
+
+    /// Handle a focus change, switching the event loop between poll and wait
+    ///
+    /// Returns true if the focus actually changed.
+    pub fn set_focused(&mut self, event_loop: &ActiveEventLoop, focused: bool) -> bool {
+        if !self.focus.set_focused(focused) {
+            return false;
+        }
+        event_loop.set_control_flow(self.focus.control_flow());
+        if focused {
+            log::info!("Window focused - resuming");
+        } else {
+            log::info!("Window unfocused - idling until focus returns");
+        }
+        true
+    }
+
     /// Capture cursor for FPS-style controls
     pub fn capture_cursor(&mut self) -> bool {
         let grab_result = self.window
@@ -130,6 +197,33 @@ impl std::error::Error for WindowError {}
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focus_starts_polling() {
+        let focus = FocusState::new();
+        assert!(focus.is_focused());
+        assert_eq!(focus.control_flow(), ControlFlow::Poll);
+    }
+
+    #[test]
+    fn test_losing_focus_waits() {
+        let mut focus = FocusState::new();
+        assert!(focus.set_focused(false));
+        assert_eq!(focus.control_flow(), ControlFlow::Wait);
+
+        // Repeated events are not changes
+        assert!(!focus.set_focused(false));
+    }
+
+    #[test]
+    fn test_regaining_focus_polls() {
+        let mut focus = FocusState::new();
+        focus.set_focused(false);
+        assert!(focus.set_focused(true));
+        assert_eq!(focus.control_flow(), ControlFlow::Poll);
+    }
+
     #[test]
     fn test_title_formatting_captured() {
         // Test title format when cursor is captured